@@ -68,12 +68,33 @@ export function initializeCorePorts(): void {
     getImagePath: (hash: string) => invoke("get_image_path", { hash }),
     createChat: (title: string, imageHash: string, ocrLang?: string | null) =>
       invoke("create_chat", { title, imageHash, ocrLang }),
+    forkChat: (chatId: string, fromMessageIndex: number) =>
+      invoke("fork_chat", { chatId, fromMessageIndex }),
+    mergeChats: (targetId: string, sourceIds: string[]) =>
+      invoke("merge_chats", { targetId, sourceIds }),
     loadChat: (chatId: string) => invoke("load_chat", { chatId }),
-    listChats: () => invoke("list_chats"),
+    listChats: (filter) => invoke("list_chats", { filter }),
+    setChatTags: (chatId: string, tags: string[]) =>
+      invoke("set_chat_tags", { chatId, tags }),
+    listTags: () => invoke("list_tags"),
+    moveChatToFolder: (chatId: string, folder: string | null) =>
+      invoke("move_chat_to_folder", { chatId, folder }),
     searchChats: (query: string, limit: number) =>
       invoke("search_chats", { query, limit }),
+    findSimilarCaptures: (hash: string, threshold?: number) =>
+      invoke("find_similar_captures", { hash, threshold: threshold ?? null }),
     deleteChat: (chatId: string) => invoke("delete_chat", { chatId }),
     updateChatMetadata: (metadata) => invoke("update_chat_metadata", { metadata }),
+    setChatOcrLanguage: (chatId: string, lang: string) =>
+      invoke("set_chat_ocr_language", { chatId, lang }),
+    addChatAttachment: (
+      chatId: string,
+      hash: string,
+      mime?: string | null,
+      label?: string | null,
+    ) => invoke("add_chat_attachment", { chatId, hash, mime, label }),
+    removeChatAttachment: (chatId: string, hash: string) =>
+      invoke("remove_chat_attachment", { chatId, hash }),
     appendChatMessage: (
       chatId: string,
       role: "user" | "assistant",
@@ -81,19 +102,28 @@ export function initializeCorePorts(): void {
     ) => invoke("append_chat_message", { chatId, role, content }),
     overwriteChatMessages: (chatId, messages) =>
       invoke("overwrite_chat_messages", { chatId, messages }),
+    editMessage: (chatId, messageId, content, truncate, model) =>
+      invoke("edit_message", { chatId, messageId, content, truncate, model }),
+    deleteMessage: (chatId, messageId) =>
+      invoke("delete_message", { chatId, messageId }),
+    getMessageHistory: (chatId, messageId) =>
+      invoke("get_message_history", { chatId, messageId }),
     saveOcrData: (chatId, modelId, ocrData) =>
       invoke("save_ocr_data", { chatId, modelId, ocrData }),
     getOcrData: (chatId, modelId) => invoke("get_ocr_data", { chatId, modelId }),
     getOcrFrame: (chatId) => invoke("get_ocr_frame", { chatId }),
     initOcrFrame: (chatId, modelIds) =>
       invoke("init_ocr_frame", { chatId, modelIds }),
-    cancelOcrJob: () => invoke("cancel_ocr_job"),
+    cancelOcrJob: (chatId) => invoke("cancel_ocr_job", { chatId: chatId ?? null }),
+    isChatOcrPending: (chatId) => invoke("is_chat_ocr_pending", { chatId }),
     saveImgbbUrl: (chatId, url) => invoke("save_imgbb_url", { chatId, url }),
     getImgbbUrl: (chatId) => invoke("get_imgbb_url", { chatId }),
     saveRollingSummary: (chatId, summary) =>
       invoke("save_rolling_summary", { chatId, summary }),
     saveImageTone: (chatId, tone) => invoke("save_image_tone", { chatId, tone }),
     saveImageBrief: (chatId, brief) => invoke("save_image_brief", { chatId, brief }),
+    getChatEncryptionEnabled: () => invoke("get_chat_encryption_enabled"),
+    encryptChatStorage: () => invoke("encrypt_chat_storage"),
   });
 
   setPreferencesPort({
@@ -237,9 +237,6 @@ export const useAppCapture = ({
           await new Promise((resolve) => setTimeout(resolve, 10));
 
           chatHistoryRef.current.setActiveSessionId(chatId);
-          if (!systemRef.current.ocrEnabled) {
-            await saveOcrData(chatId, AUTO_OCR_DISABLED_MODEL_ID, []);
-          }
           chatHistoryRef.current.refreshChats();
         } catch (error) {
           console.error("[capture-complete] Failed:", error);
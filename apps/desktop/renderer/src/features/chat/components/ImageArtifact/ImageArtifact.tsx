@@ -12,6 +12,7 @@ import React, {
   RefObject,
 } from "react";
 import { invoke, convertFileSrc } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { save } from "@tauri-apps/plugin-dialog";
 import { CloseCrossIcon } from "@/components/icons";
 import { ImageToolbar } from "./ImageToolbar";
@@ -37,7 +38,9 @@ import {
 import {
   AUTO_OCR_DISABLED_MODEL_ID,
   cancelOcrJob,
+  isChatOcrPending,
   type OcrFrame,
+  type OcrRegion,
   saveImageTone,
   saveOcrData,
 } from "@squigit/core/config";
@@ -257,10 +260,19 @@ export const ImageArtifact: React.FC<ImageArtifactProps> = ({
 
   const currentModelData = ocrData[currentOcrModel] || [];
 
-  const displayData = currentModelData.map((d) => ({
-    text: d.text,
-    box: d.bbox,
-  }));
+  // Regions streamed in while a scan runs, shown until the full result lands.
+  const [streamedRegions, setStreamedRegions] = useState<
+    { index: number; text: string; box: number[][] }[]
+  >([]);
+  const streamPathRef = useRef<string | null>(null);
+
+  const displayData =
+    currentModelData.length === 0 && loading && streamedRegions.length > 0
+      ? streamedRegions.map(({ text, box }) => ({ text, box }))
+      : currentModelData.map((d) => ({
+          text: d.text,
+          box: d.bbox,
+        }));
   const lastTranslateDisabledRef = useRef(displayData.length === 0);
   useEffect(() => {
     if (!isNavigating) {
@@ -334,6 +346,8 @@ export const ImageArtifact: React.FC<ImageArtifactProps> = ({
       cancelOcrJob();
       hasScannedRef.current = false;
       prevScanContextRef.current = scanContextKey;
+      streamPathRef.current = null;
+      setStreamedRegions([]);
       setLoading(false);
       cancelledRef.current = false;
     }
@@ -365,6 +379,22 @@ export const ImageArtifact: React.FC<ImageArtifactProps> = ({
         return;
       }
 
+      if (!options?.manual && scanContext.chatId) {
+        const pending = await isChatOcrPending(scanContext.chatId).catch(
+          () => false,
+        );
+        if (!isScanContextCurrent(scanContext, requestId)) {
+          return;
+        }
+        if (pending) {
+          console.log(
+            `[ImageArtifact] Waiting for background OCR of chat: ${scanContext.chatId}`,
+          );
+          setLoading(true);
+          return;
+        }
+      }
+
       const lockKey = `${scanContext.chatId ?? "__no_chat__"}-${scanContext.imageId}-${modelToUse}`;
       if (globalScanLock.has(lockKey)) {
         console.log(`[ImageArtifact] Scan already in progress for: ${lockKey}`);
@@ -387,10 +417,14 @@ export const ImageArtifact: React.FC<ImageArtifactProps> = ({
             ? `objects/${startupImage.imageId.slice(0, 2)}/${startupImage.imageId}.png`
             : startupImage.path;
 
+        streamPathRef.current = casRelativePath;
+        setStreamedRegions([]);
         const results = await invoke<OCRBox[]>("ocr_image", {
           imageData: casRelativePath,
           isBase64: false,
           modelName: modelToUse,
+          stream: true,
+          chatId: scanContext.chatId,
         });
 
         if (!isScanContextCurrent(scanContext, requestId)) {
@@ -451,6 +485,8 @@ export const ImageArtifact: React.FC<ImageArtifactProps> = ({
         globalScanLock.delete(lockKey);
 
         if (isScanContextCurrent(scanContext, requestId)) {
+          streamPathRef.current = null;
+          setStreamedRegions([]);
           setLoading(false);
         }
       }
@@ -519,9 +555,12 @@ export const ImageArtifact: React.FC<ImageArtifactProps> = ({
 
     scanRequestRef.current += 1;
     hasScannedRef.current = false;
+    streamPathRef.current = null;
+    setStreamedRegions([]);
     setLoading(false);
     onOcrModelChange("");
-    await cancelOcrJob();
+    // Scans of a chat, this view's or the one after capture, run as its job.
+    await cancelOcrJob(chatId);
     if (chatId) {
       saveOcrData(chatId, AUTO_OCR_DISABLED_MODEL_ID, []).catch((err) =>
         console.error("Failed to persist OCR auto-run opt-out:", err),
@@ -529,6 +568,65 @@ export const ImageArtifact: React.FC<ImageArtifactProps> = ({
     }
   };
 
+  // Background OCR started by the backend after a capture.
+  useEffect(() => {
+    const unlisten = listen<{
+      chatId: string;
+      modelId: string;
+      regions: OcrRegion[] | null;
+      error?: string | null;
+    }>("ocr-ready", (event) => {
+      const { chatId: readyChatId, modelId, regions } = event.payload;
+      if (readyChatId !== latestContextRef.current.chatId) return;
+
+      if (regions) {
+        onUpdateOCRData(
+          readyChatId,
+          modelId,
+          regions.map((region) => ({ text: region.text, box: region.bbox })),
+        );
+        hasScannedRef.current = true;
+      }
+      setStreamedRegions([]);
+      setLoading(false);
+    });
+
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, [onUpdateOCRData, setLoading]);
+
+  // Regions as the sidecar recognizes them, from this view's own scan or
+  // from the background OCR of its chat.
+  useEffect(() => {
+    const unlisten = listen<{
+      imagePath: string;
+      chatId: string | null;
+      index: number;
+      detected: number | null;
+      region: OCRBox;
+    }>("ocr-progress", (event) => {
+      const { imagePath, chatId: regionChatId, index, region } = event.payload;
+      const ownScan =
+        streamPathRef.current !== null && imagePath === streamPathRef.current;
+      const ownChat =
+        regionChatId !== null &&
+        regionChatId === latestContextRef.current.chatId;
+      if (!ownScan && !ownChat) return;
+
+      setStreamedRegions((prev) =>
+        [
+          ...prev.filter((item) => item.index !== index),
+          { index, text: region.text, box: region.box_coords },
+        ].sort((a, b) => a.index - b.index),
+      );
+    });
+
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
   useEffect(() => {
     if (
       !isNavigating &&
@@ -0,0 +1,65 @@
+/**
+ * @license
+ * Copyright 2026 a7mddra
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+import React, { useEffect, useState } from "react";
+import { X } from "lucide-react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import styles from "./HudOverlay.module.css";
+
+type HudStreamEvent =
+  | { type: "token"; token: string }
+  | { type: "reset" }
+  | { type: string };
+
+export const HudOverlay: React.FC = () => {
+  const [summary, setSummary] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlisteners = [
+      listen("hud-frame", () => {
+        setSummary("");
+        setError(null);
+      }),
+      listen<HudStreamEvent>("hud-stream", (event) => {
+        const payload = event.payload;
+        if (payload.type === "reset") {
+          setSummary("");
+        } else if (payload.type === "token" && "token" in payload) {
+          setSummary((current) => current + payload.token);
+        }
+      }),
+      listen<{ reason: string }>("hud-error", (event) => {
+        setError(event.payload.reason);
+      }),
+    ];
+
+    return () => {
+      unlisteners.forEach((pending) => pending.then((unlisten) => unlisten()));
+    };
+  }, []);
+
+  return (
+    <div className={styles.hud} data-tauri-drag-region>
+      <div className={styles.header} data-tauri-drag-region>
+        <span>Explain my screen</span>
+        <button
+          type="button"
+          className={styles.close}
+          onClick={() => invoke("stop_hud")}
+          aria-label="Stop HUD"
+        >
+          <X size={14} />
+        </button>
+      </div>
+      <div className={styles.summary}>
+        {summary || (error ? "" : "Watching your screen…")}
+        {error && <span className={styles.error}>{error}</span>}
+      </div>
+    </div>
+  );
+};
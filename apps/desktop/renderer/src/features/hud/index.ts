@@ -0,0 +1,7 @@
+/**
+ * @license
+ * Copyright 2026 a7mddra
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+export * from "./components/HudOverlay";
@@ -7,7 +7,8 @@ import { invoke } from "@tauri-apps/api/core";
 import { OcrModel } from "../ocr-models.types";
 
 /**
- * Triggers the backend to download and extract an OCR model.
+ * Triggers the backend to download and extract an OCR model, then starts
+ * scanning existing chats that have no text for it yet.
  * @param model - The model metadata containing the download URL and ID.
  * @returns A promise that resolves to the path of the downloaded model.
  * @throws If the download or extraction fails.
@@ -18,6 +19,9 @@ export const downloadModel = async (model: OcrModel): Promise<string> => {
       url: model.downloadUrl,
       modelId: model.id,
     });
+    invoke("backfill_ocr", { modelId: model.id }).catch((error) => {
+      console.warn(`OCR backfill for ${model.id} did not start:`, error);
+    });
     return downloadedPath;
   } catch (error) {
     console.error(`Failed to download model ${model.id}:`, error);
@@ -7,6 +7,7 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "@/app/App";
+import { HudOverlay } from "@/features/hud";
 import { initializeCorePorts } from "@/app/bootstrap/initCorePorts";
 
 import "@/styles/fonts.css";
@@ -14,10 +15,23 @@ import "@/styles/variables.css";
 import "@/styles/animations.css";
 import "@/styles/globals.css";
 
-initializeCorePorts();
+const windowKind = new URLSearchParams(window.location.search).get("window");
+const isHudWindow = windowKind === "hud";
+// The capture flash is a plain white window shown for a moment.
+const isFlashWindow = windowKind === "flash";
+
+if (!isHudWindow && !isFlashWindow) {
+  initializeCorePorts();
+}
+
+const root = isFlashWindow ? (
+  <div style={{ position: "fixed", inset: 0, background: "#ffffff" }} />
+) : isHudWindow ? (
+  <HudOverlay />
+) : (
+  <App />
+);
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
-  <React.StrictMode>
-    <App />
-  </React.StrictMode>,
+  <React.StrictMode>{root}</React.StrictMode>,
 );
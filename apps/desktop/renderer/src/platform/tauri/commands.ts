@@ -51,6 +51,8 @@ export const commands = {
   // OCR Model Management
   cancelDownloadOcrModel: (modelId: string) =>
     invoke("cancel_download_ocr_model", { modelId }),
+  backfillOcr: (modelId: string) => invoke("backfill_ocr", { modelId }),
+  stopOcrBackfill: () => invoke("stop_ocr_backfill"),
 
   // AI Runtime Control
   quickAnswerProviderRequest: (channelId: string) =>
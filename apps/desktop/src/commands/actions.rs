@@ -0,0 +1,25 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command palette actions shared with the tray and external integrations.
+
+use crate::services::actions::{self, ActionInfo};
+use tauri::AppHandle;
+
+/// Every invokable action with its ID, display name and shortcut binding.
+#[tauri::command]
+pub fn list_actions(app: AppHandle) -> Vec<ActionInfo> {
+    actions::list(&app)
+}
+
+/// Run an action by ID. Renderer-side actions come back as `action-invoked`.
+#[tauri::command]
+pub async fn invoke_action(
+    app: AppHandle,
+    id: String,
+    argument: Option<String>,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || actions::invoke(&app, &id, argument.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+}
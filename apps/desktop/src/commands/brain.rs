@@ -2,12 +2,30 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::services::brain::DesktopBrainService;
+use crate::services::llm::LlmProvider;
+use crate::services::recovery::RecoveryState;
+use crate::services::session::SessionState;
+use crate::services::{a11y, metrics};
+use ops_chat_storage::{DanglingUserTurn, Extraction};
+use ops_profile_store::security::ApiKeyProvider;
+use ops_squigit_brain::context::builder::RESPONSE_LANGUAGES;
+use ops_squigit_brain::context::calendar::{events_to_ics, CalendarEvent};
+use ops_squigit_brain::context::code::CodeSnippet;
+use ops_squigit_brain::context::extraction::{ExtractionProfile, ExtractionProfileInfo};
+use ops_squigit_brain::provider::gemini::commands::models::ModelInfo;
+use ops_squigit_brain::provider::gemini::transport::types::GeminiPromptPreview;
 use ops_squigit_brain::service::{
-    CompressConversationRequest, GenerateChatTitleRequest, GenerateImageBriefRequest,
-    StreamChatRequest,
+    BackfillChatTitlesRequest, CodeFromScreenshotRequest, CompressConversationRequest,
+    ExtractEventsRequest, ExtractStructuredRequest, GenerateChatTitleRequest,
+    GenerateImageBriefRequest, ListModelsRequest, OcrTranslation, StreamChatRequest,
+    TranslateOcrRegionsRequest,
 };
-use tauri::{AppHandle, State};
+use std::str::FromStr;
+use tauri::{AppHandle, Manager, State};
 
+/// Returns the model that answered, which differs from `model` after a
+/// fallback. `provider` picks the backend (`gemini` when unset,
+/// `openai-compatible` or `anthropic`); `api_key` is only used by Gemini.
 #[tauri::command]
 #[allow(clippy::too_many_arguments)]
 pub async fn stream_chat(
@@ -17,6 +35,7 @@ pub async fn stream_chat(
     model: String,
     is_initial_turn: bool,
     image_path: Option<String>,
+    image_paths: Option<Vec<String>>,
     image_description: Option<String>,
     user_first_msg: Option<String>,
     history_log: Option<String>,
@@ -28,31 +47,132 @@ pub async fn stream_chat(
     user_email: Option<String>,
     user_instruction: Option<String>,
     image_brief: Option<String>,
-) -> Result<(), String> {
+    animation_frames: Option<usize>,
+    provider: Option<String>,
+) -> Result<String, String> {
+    let provider = provider
+        .as_deref()
+        .map(LlmProvider::from_str)
+        .transpose()?
+        .unwrap_or_default();
+    let session = app.state::<SessionState>();
+    session.stream_started(chat_id.clone(), &channel_id);
+    a11y::polite(&app, "chat", "Reply started");
+    let finished_channel = channel_id.clone();
+    let analyzed_chat = chat_id.clone().filter(|_| is_initial_turn);
+
+    let result = crate::services::llm::stream_chat(
+        &app,
+        &brain,
+        provider,
+        StreamChatRequest {
+            api_key,
+            model,
+            is_initial_turn,
+            image_path,
+            image_paths: image_paths.unwrap_or_default(),
+            image_description,
+            user_first_msg,
+            history_log,
+            rolling_summary,
+            user_message,
+            channel_id,
+            chat_id,
+            user_name,
+            user_email,
+            user_instruction,
+            image_brief,
+            response_language: crate::services::brain::response_language(&app),
+            glossary: crate::services::brain::active_glossary(),
+            include_ocr_in_prompt: crate::services::brain::include_ocr_in_prompt(&app),
+            redact_pii: crate::services::brain::redact_pii(&app),
+            animation_frames: crate::services::brain::animation_frames(&app, animation_frames),
+            fallback_models: crate::services::brain::active_model_fallbacks(),
+        },
+    )
+    .await;
+
+    session.stream_finished(&finished_channel);
+    metrics::record(&app, "chat", &result);
+    if let (Ok(_), Some(chat_id)) = (&result, analyzed_chat) {
+        crate::services::ephemeral::first_reply_finished(&app, chat_id);
+    }
+    match &result {
+        Ok(_) => a11y::polite(&app, "chat", "Reply finished"),
+        Err(e) if e == "CANCELLED" => a11y::polite(&app, "chat", "Reply stopped"),
+        Err(_) => a11y::assertive(&app, "chat", "Reply failed"),
+    }
+    result
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn preview_chat(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    api_key: String,
+    model: String,
+    is_initial_turn: bool,
+    image_path: Option<String>,
+    image_paths: Option<Vec<String>>,
+    image_description: Option<String>,
+    user_first_msg: Option<String>,
+    history_log: Option<String>,
+    rolling_summary: Option<String>,
+    user_message: String,
+    channel_id: String,
+    chat_id: Option<String>,
+    user_name: Option<String>,
+    user_email: Option<String>,
+    user_instruction: Option<String>,
+    image_brief: Option<String>,
+    animation_frames: Option<usize>,
+) -> Result<GeminiPromptPreview, String> {
     brain
-        .stream_chat(
-            app,
-            StreamChatRequest {
-                api_key,
-                model,
-                is_initial_turn,
-                image_path,
-                image_description,
-                user_first_msg,
-                history_log,
-                rolling_summary,
-                user_message,
-                channel_id,
-                chat_id,
-                user_name,
-                user_email,
-                user_instruction,
-                image_brief,
-            },
-        )
+        .preview_chat(StreamChatRequest {
+            api_key,
+            model,
+            is_initial_turn,
+            image_path,
+            image_paths: image_paths.unwrap_or_default(),
+            image_description,
+            user_first_msg,
+            history_log,
+            rolling_summary,
+            user_message,
+            channel_id,
+            chat_id,
+            user_name,
+            user_email,
+            user_instruction,
+            image_brief,
+            response_language: crate::services::brain::response_language(&app),
+            glossary: crate::services::brain::active_glossary(),
+            include_ocr_in_prompt: crate::services::brain::include_ocr_in_prompt(&app),
+            redact_pii: crate::services::brain::redact_pii(&app),
+            animation_frames: crate::services::brain::animation_frames(&app, animation_frames),
+            fallback_models: crate::services::brain::active_model_fallbacks(),
+        })
         .await
 }
 
+/// Sends a request from `preview_chat`, as shown or edited, streaming the
+/// reply on `channel_id`. Returns the model.
+#[tauri::command]
+pub async fn send_chat_preview(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    api_key: String,
+    channel_id: String,
+    preview: GeminiPromptPreview,
+) -> Result<String, String> {
+    let result = brain
+        .send_prompt_preview(app.clone(), api_key, channel_id, preview)
+        .await;
+    metrics::record(&app, "chat", &result);
+    result
+}
+
 #[tauri::command]
 pub async fn generate_chat_title(
     brain: State<'_, DesktopBrainService>,
@@ -85,6 +205,152 @@ pub async fn generate_image_brief(
         .await
 }
 
+/// Translates the selected OCR regions of a chat into `target_lang` with the
+/// preferred model. Region ids are indexes into the chat's OCR data; the chat
+/// itself is left untouched.
+#[tauri::command]
+pub async fn translate_ocr_region(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    chat_id: String,
+    region_ids: Vec<usize>,
+    target_lang: String,
+) -> Result<OcrTranslation, String> {
+    let credentials =
+        tauri::async_runtime::spawn_blocking(crate::services::brain::resolve_credentials)
+            .await
+            .map_err(|e| e.to_string())??;
+    brain
+        .translate_ocr_regions(TranslateOcrRegionsRequest {
+            api_key: credentials.api_key,
+            model: crate::services::brain::preferred_model(&app),
+            chat_id,
+            region_ids,
+            target_lang,
+        })
+        .await
+}
+
+/// Finds calendar events in a chat's screenshot with the preferred model.
+/// Relative dates are resolved against today's local date.
+#[tauri::command]
+pub async fn extract_events(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    chat_id: String,
+) -> Result<Vec<CalendarEvent>, String> {
+    let credentials =
+        tauri::async_runtime::spawn_blocking(crate::services::brain::resolve_credentials)
+            .await
+            .map_err(|e| e.to_string())??;
+    brain
+        .extract_events(ExtractEventsRequest {
+            api_key: credentials.api_key,
+            model: crate::services::brain::preferred_model(&app),
+            chat_id,
+            today: chrono::Local::now().date_naive(),
+            redact_pii: crate::services::brain::redact_pii(&app),
+        })
+        .await
+}
+
+/// Reconstructs the code in a chat's screenshot with the preferred model and
+/// adds it to the chat as a code block. Unless `format` is `false`, the code
+/// is run through a locally installed formatter for its language.
+#[tauri::command]
+pub async fn code_from_screenshot(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    chat_id: String,
+    format: Option<bool>,
+) -> Result<CodeSnippet, String> {
+    let credentials =
+        tauri::async_runtime::spawn_blocking(crate::services::brain::resolve_credentials)
+            .await
+            .map_err(|e| e.to_string())??;
+    brain
+        .code_from_screenshot(CodeFromScreenshotRequest {
+            api_key: credentials.api_key,
+            model: crate::services::brain::preferred_model(&app),
+            chat_id,
+            format: format.unwrap_or(true),
+            redact_pii: crate::services::brain::redact_pii(&app),
+        })
+        .await
+}
+
+/// Built-in extraction profiles and their fields.
+#[tauri::command]
+pub fn list_extraction_profiles() -> Vec<ExtractionProfileInfo> {
+    ExtractionProfile::ALL
+        .into_iter()
+        .map(ExtractionProfile::info)
+        .collect()
+}
+
+/// Fills the `profile` schema from a chat's screenshot with the preferred
+/// model and stores the result on the chat.
+#[tauri::command]
+pub async fn extract_structured(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    chat_id: String,
+    profile: String,
+) -> Result<Extraction, String> {
+    let profile = ExtractionProfile::from_id(&profile)
+        .ok_or_else(|| format!("ERR_UNKNOWN_EXTRACTION_PROFILE: {}", profile))?;
+    let credentials =
+        tauri::async_runtime::spawn_blocking(crate::services::brain::resolve_credentials)
+            .await
+            .map_err(|e| e.to_string())??;
+    brain
+        .extract_structured(ExtractStructuredRequest {
+            api_key: credentials.api_key,
+            model: crate::services::brain::preferred_model(&app),
+            chat_id,
+            profile,
+            redact_pii: crate::services::brain::redact_pii(&app),
+        })
+        .await
+}
+
+/// Writes `events` as an `.ics` file and returns its path. Without `path`
+/// the file goes to the app cache. The file is also saved as the chat's
+/// `events.ics` artifact. With `open`, it is handed to the system calendar.
+#[tauri::command]
+pub fn save_events_ics(
+    app: AppHandle,
+    chat_id: String,
+    events: Vec<CalendarEvent>,
+    path: Option<String>,
+    open: bool,
+) -> Result<String, String> {
+    if events.is_empty() {
+        return Err("ERR_NO_EVENTS".to_string());
+    }
+    let path = match path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let dir = app
+                .path()
+                .app_cache_dir()
+                .map_err(|e| e.to_string())?
+                .join("events");
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            dir.join(format!("{}.ics", chat_id))
+        }
+    };
+    let ics = events_to_ics(&events, &chat_id, chrono::Utc::now());
+    ops_squigit_brain::context::media::get_active_storage()?
+        .save_artifact(&chat_id, "events.ics", ics.as_bytes(), None)
+        .map_err(|e| e.to_string())?;
+    std::fs::write(&path, ics).map_err(|e| e.to_string())?;
+    if open {
+        opener::open(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(path.to_string_lossy().into_owned())
+}
+
 #[tauri::command]
 pub async fn compress_conversation(
     brain: State<'_, DesktopBrainService>,
@@ -101,6 +367,42 @@ pub async fn compress_conversation(
         .await
 }
 
+/// Chat models the active profile's key can use with `provider`, for the
+/// model picker. Served from an hourly cache unless `refresh` is set.
+#[tauri::command]
+pub async fn list_available_models(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    provider: String,
+    refresh: Option<bool>,
+) -> Result<Vec<ModelInfo>, String> {
+    let provider = ApiKeyProvider::from_str(&provider).map_err(|e| e.to_string())?;
+    if provider == ApiKeyProvider::OpenAiCompatible {
+        let config = crate::services::llm::openai_compatible_config(&app).await?;
+        return brain.list_openai_models(&config).await;
+    }
+    if provider == ApiKeyProvider::Anthropic {
+        let config = crate::services::llm::claude_config().await?;
+        return brain.list_claude_models(&config).await;
+    }
+    if provider != ApiKeyProvider::GoogleAiStudio {
+        return Err(format!(
+            "ERR_UNSUPPORTED_PROVIDER: {}",
+            provider.display_name()
+        ));
+    }
+    let credentials =
+        tauri::async_runtime::spawn_blocking(crate::services::brain::resolve_credentials)
+            .await
+            .map_err(|e| e.to_string())??;
+    brain
+        .list_available_models(ListModelsRequest {
+            api_key: credentials.api_key,
+            refresh: refresh.unwrap_or(false),
+        })
+        .await
+}
+
 #[tauri::command]
 pub async fn cancel_request(
     brain: State<'_, DesktopBrainService>,
@@ -116,3 +418,67 @@ pub async fn quick_answer_request(
 ) -> Result<(), String> {
     brain.quick_answer_request(channel_id).await
 }
+
+/// Drop cached provider file handles and model lists. Returns how many
+/// entries were removed.
+#[tauri::command]
+pub async fn clear_caches(app: AppHandle) -> Result<usize, String> {
+    Ok(crate::services::memory::clear_caches(&app).await)
+}
+
+/// Queue title + summary generation for chats still named with a placeholder.
+/// Runs in the background; progress arrives as `title-backfill-progress` events.
+#[tauri::command]
+pub fn backfill_chat_titles(
+    app: AppHandle,
+    brain: State<'_, DesktopBrainService>,
+    api_key: String,
+    model: String,
+) -> Result<(), String> {
+    if brain.is_title_backfill_running() {
+        return Err("Title backfill is already running".to_string());
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let brain = app.state::<DesktopBrainService>();
+        if let Err(e) = brain
+            .backfill_chat_titles(app.clone(), BackfillChatTitlesRequest { api_key, model })
+            .await
+        {
+            log::warn!("Title backfill failed: {}", e);
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_title_backfill(brain: State<'_, DesktopBrainService>) {
+    brain.stop_title_backfill();
+}
+
+/// Languages accepted by the `responseLanguage` preference.
+#[tauri::command]
+pub fn get_response_languages() -> Vec<serde_json::Value> {
+    RESPONSE_LANGUAGES
+        .iter()
+        .map(|(tag, name)| serde_json::json!({ "tag": tag, "name": name }))
+        .collect()
+}
+
+/// Chats whose reply was lost when the app quit mid-generation.
+#[tauri::command]
+pub fn get_resumable_chats(recovery: State<'_, RecoveryState>) -> Vec<DanglingUserTurn> {
+    recovery.pending()
+}
+
+/// Generate the missing reply for `chat_id`'s last user turn, streaming on
+/// `channel_id` like `stream_chat`. Returns the reply text.
+#[tauri::command]
+pub async fn resume_generation(
+    app: AppHandle,
+    chat_id: String,
+    channel_id: Option<String>,
+) -> Result<String, String> {
+    let channel_id = channel_id.unwrap_or_else(|| format!("resume-{}", chat_id));
+    crate::services::recovery::resume(&app, chat_id, channel_id).await
+}
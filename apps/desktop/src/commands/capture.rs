@@ -1,3 +1,4 @@
+use crate::services::capture::{DisplayInfo, MonitorInfo};
 use crate::state::AppState;
 use tauri::{AppHandle, State};
 
@@ -12,3 +13,46 @@ pub fn spawn_capture_to_input(app: AppHandle, _state: State<'_, AppState>) -> Re
     crate::services::capture::spawn_capture_to_input(&app);
     Ok(())
 }
+
+/// Connected displays the capture overlay can be limited to.
+#[tauri::command]
+pub async fn list_displays() -> Result<Vec<DisplayInfo>, String> {
+    // Enumerating can shell out (system_profiler on macOS).
+    tauri::async_runtime::spawn_blocking(crate::services::capture::list_displays)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Open the selection overlay after a countdown of `seconds`, ticked with
+/// `capture-countdown` events.
+#[tauri::command]
+pub fn capture_with_delay(app: AppHandle, seconds: u64) -> Result<(), String> {
+    crate::services::capture::capture_with_delay(&app, seconds)
+}
+
+/// Re-capture the region of the last capture without the selection UI.
+#[tauri::command]
+pub fn recapture_last_region(app: AppHandle) -> Result<(), String> {
+    crate::services::capture::recapture_last_region(&app)
+}
+
+/// Grab a whole display, by its index in `list_monitors`, into a new chat
+/// without the selection UI.
+#[tauri::command]
+pub async fn capture_monitor(app: AppHandle, monitor_index: usize) -> Result<(), String> {
+    crate::services::capture::capture_monitor(&app, monitor_index).await
+}
+
+/// Grab the focused window into a new chat without the selection UI.
+#[tauri::command]
+pub async fn capture_active_window(app: AppHandle) -> Result<(), String> {
+    crate::services::capture::capture_active_window(&app).await
+}
+
+/// Connected displays `capture_monitor` can grab, in index order.
+#[tauri::command]
+pub async fn list_monitors() -> Result<Vec<MonitorInfo>, String> {
+    tauri::async_runtime::spawn_blocking(crate::services::capture::list_monitors)
+        .await
+        .map_err(|e| e.to_string())
+}
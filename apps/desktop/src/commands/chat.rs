@@ -4,11 +4,27 @@
 //! Chat storage Tauri commands.
 
 use crate::services::tone::detect_image_tone_from_bytes;
+use ops_chat_export::{
+    suggested_file_name, ChatExportFormat, ExportConnector, ExportSource, GalleryReport,
+    ObsidianVault,
+};
 use ops_chat_storage::{
-    ChatData, ChatMessage, ChatMetadata, ChatStorage, OcrFrame, OcrRegion, StoredImage,
+    Artifact, AttachmentFilter, AttachmentInfo, AttachmentKind, ChatAnalytics, ChatData,
+    ChatFilter, ChatMessage, ChatMetadata, ChatStorage, DateRange, GcReport, MessageRevision,
+    OcrFrame, OcrRegion, OcrTextLayout, RetentionPolicy, RetentionReport, SimilarCapture,
+    StorageStats, StoredImage, TagCount, DEFAULT_SIMILARITY_THRESHOLD,
+};
+use ops_profile_store::ProfileStore;
+use ops_squigit_brain::context::export::{
+    export_chat_as_llm_json as export_chat_as_llm_json_internal, LlmExportSchema,
 };
+use ops_squigit_brain::context::extraction::{extractions_to_csv, ExtractionProfile};
 use ops_squigit_brain::provider::attachments::resolve_attachment_path_buf;
 use ops_squigit_brain::tools::chat_search::{search_local_chats, ChatSearchResult};
+use tauri_plugin_dialog::DialogExt;
+
+/// Default size of the composer's recent attachments list.
+const RECENT_ATTACHMENTS_LIMIT: usize = 20;
 
 /// Helper to get storage for the active profile.
 fn get_active_storage() -> Result<ChatStorage, String> {
@@ -22,6 +38,7 @@ fn get_active_storage() -> Result<ChatStorage, String> {
 /// Store image bytes and return hash + path.
 #[tauri::command]
 pub fn store_image_bytes(bytes: Vec<u8>) -> Result<StoredImage, String> {
+    let bytes = crate::services::image::normalize_image_bytes(bytes)?;
     let explicit_tone = detect_image_tone_from_bytes(&bytes);
     ops_squigit_brain::context::media::process_bytes_internal(bytes, explicit_tone)
 }
@@ -30,6 +47,7 @@ pub fn store_image_bytes(bytes: Vec<u8>) -> Result<StoredImage, String> {
 #[tauri::command]
 pub fn store_image_from_path(path: String) -> Result<StoredImage, String> {
     let bytes = std::fs::read(&path).map_err(|e| e.to_string())?;
+    let bytes = crate::services::image::normalize_image_bytes(bytes)?;
     let explicit_tone = detect_image_tone_from_bytes(&bytes);
     ops_squigit_brain::context::media::process_bytes_internal(bytes, explicit_tone)
 }
@@ -43,6 +61,37 @@ pub fn store_file_from_path(path: String) -> Result<StoredImage, String> {
         .map_err(|e| e.to_string())
 }
 
+/// List stored attachments, newest first, for the "all files" view.
+#[tauri::command]
+pub fn list_attachments(filter: Option<AttachmentFilter>) -> Result<Vec<AttachmentInfo>, String> {
+    let storage = get_active_storage()?;
+    storage
+        .list_attachments(&filter.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+/// Recently stored attachments across chats, for quick re-attachment in
+/// the composer.
+#[tauri::command]
+pub fn list_recent_attachments(
+    kind: Option<AttachmentKind>,
+    limit: Option<usize>,
+) -> Result<Vec<AttachmentInfo>, String> {
+    let storage = get_active_storage()?;
+    storage
+        .list_recent_attachments(kind, limit.unwrap_or(RECENT_ATTACHMENTS_LIMIT))
+        .map_err(|e| e.to_string())
+}
+
+/// Get the index entry of a stored attachment by its content hash.
+#[tauri::command]
+pub fn get_attachment_info(hash: String) -> Result<AttachmentInfo, String> {
+    let storage = get_active_storage()?;
+    storage
+        .get_attachment_info(&hash)
+        .map_err(|e| e.to_string())
+}
+
 /// Validate if a file is safe text (valid UTF-8 and no null bytes).
 #[tauri::command]
 pub fn validate_text_file(path: String) -> Result<bool, String> {
@@ -176,15 +225,54 @@ pub fn reveal_in_file_manager(path: String) -> Result<(), String> {
 /// Create a new thread with the given image hash.
 #[tauri::command]
 pub fn create_chat(
+    app: tauri::AppHandle,
     title: String,
     image_hash: String,
     ocr_lang: Option<String>,
+    capture_type: Option<String>,
 ) -> Result<ChatMetadata, String> {
     let storage = get_active_storage()?;
     let mut metadata = ChatMetadata::new(title, image_hash.clone(), ocr_lang);
     metadata.image_tone = storage.get_image_tone(&image_hash);
+    metadata.capture_type = capture_type;
     let chat = ChatData::new(metadata.clone());
     storage.save_chat(&chat).map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &metadata.id);
+    Ok(metadata)
+}
+
+/// Start a new chat about the same image from the messages up to and
+/// including `from_message_index`.
+#[tauri::command]
+pub fn fork_chat(
+    app: tauri::AppHandle,
+    chat_id: String,
+    from_message_index: usize,
+) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    let metadata = storage
+        .fork_chat(&chat_id, from_message_index)
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &metadata.id);
+    Ok(metadata)
+}
+
+/// Merge `source_ids` into `target_id` and move the sources to the trash.
+/// Returns the target's updated metadata.
+#[tauri::command]
+pub fn merge_chats(
+    app: tauri::AppHandle,
+    target_id: String,
+    source_ids: Vec<String>,
+) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    let metadata = storage
+        .merge_chats(&target_id, &source_ids)
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &metadata.id);
+    for source_id in &source_ids {
+        crate::services::search_index::chat_deleted(&app, source_id);
+    }
     Ok(metadata)
 }
 
@@ -195,11 +283,160 @@ pub fn load_chat(chat_id: String) -> Result<ChatData, String> {
     storage.load_chat(&chat_id).map_err(|e| e.to_string())
 }
 
-/// List all chats (metadata only).
+/// List chats (metadata only), optionally narrowed by tag, folder, pin,
+/// star or creation date.
+#[tauri::command]
+pub fn list_chats(filter: Option<ChatFilter>) -> Result<Vec<ChatMetadata>, String> {
+    let storage = get_active_storage()?;
+    match filter {
+        Some(filter) => storage.list_chats_filtered(&filter),
+        None => storage.list_chats(),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Replace a chat's tags.
+#[tauri::command]
+pub fn set_chat_tags(
+    app: tauri::AppHandle,
+    chat_id: String,
+    tags: Vec<String>,
+) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    let metadata = storage
+        .set_chat_tags(&chat_id, &tags)
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &chat_id);
+    Ok(metadata)
+}
+
+/// Every tag in use with its chat count, alphabetically.
 #[tauri::command]
-pub fn list_chats() -> Result<Vec<ChatMetadata>, String> {
+pub fn list_tags() -> Result<Vec<TagCount>, String> {
     let storage = get_active_storage()?;
-    storage.list_chats().map_err(|e| e.to_string())
+    storage.list_tags().map_err(|e| e.to_string())
+}
+
+/// File a chat under a `/`-separated folder path; `None` takes it out of
+/// any folder.
+#[tauri::command]
+pub fn move_chat_to_folder(
+    app: tauri::AppHandle,
+    chat_id: String,
+    folder: Option<String>,
+) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    let metadata = storage
+        .move_chat_to_folder(&chat_id, folder.as_deref())
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &chat_id);
+    Ok(metadata)
+}
+
+/// Usage statistics over `range` (all time when omitted) for the stats
+/// dashboard.
+#[tauri::command]
+pub fn get_chat_analytics(range: Option<DateRange>) -> Result<ChatAnalytics, String> {
+    let storage = get_active_storage()?;
+    storage
+        .chat_analytics(range.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+/// What a retention pass would do right now, without doing it. Uses the
+/// saved policy unless `policy` is given, so settings can preview edits.
+#[tauri::command]
+pub fn preview_retention(
+    app: tauri::AppHandle,
+    policy: Option<RetentionPolicy>,
+) -> Result<RetentionReport, String> {
+    let storage = get_active_storage()?;
+    let policy = policy.unwrap_or_else(|| crate::services::retention::policy(&app));
+    storage
+        .apply_retention(&policy, true)
+        .map_err(|e| e.to_string())
+}
+
+/// Run a retention pass with the saved policy now instead of waiting for
+/// the scheduled one. Emits the same events as a scheduled pass.
+#[tauri::command]
+pub async fn run_retention(app: tauri::AppHandle) -> Result<RetentionReport, String> {
+    let policy = crate::services::retention::policy(&app);
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let storage = get_active_storage()?;
+        storage
+            .apply_retention(&policy, false)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    crate::services::retention::emit_report(&app, report.clone());
+    report
+}
+
+/// Remove stored images and files no chat references any more, or with
+/// `dry_run` only report them and the bytes they take up.
+#[tauri::command]
+pub async fn run_storage_gc(dry_run: Option<bool>) -> Result<GcReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let storage = get_active_storage()?;
+        storage
+            .gc(dry_run.unwrap_or(false))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// How much a profile's chats take up, the active profile's unless
+/// `profile_id` is given.
+#[tauri::command]
+pub async fn get_storage_stats(profile_id: Option<String>) -> Result<StorageStats, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let storage = match profile_id {
+            Some(profile_id) => {
+                let store = ProfileStore::new().map_err(|e| e.to_string())?;
+                store
+                    .get_profile(&profile_id)
+                    .map_err(|e| e.to_string())?
+                    .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+                store
+                    .open_chat_storage(&profile_id)
+                    .map_err(|e| e.to_string())?
+            }
+            None => get_active_storage()?,
+        };
+        storage.stats().map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Move a chat the retention policy trashed back into the chat list.
+#[tauri::command]
+pub fn restore_trashed_chat(chat_id: String) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    storage
+        .restore_trashed_chat(&chat_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Chats whose image looks like the image with BLAKE3 `hash`, closest
+/// first. `threshold` is the number of differing perceptual-hash bits, out
+/// of 64, still counted as similar.
+#[tauri::command]
+pub async fn find_similar_captures(
+    hash: String,
+    threshold: Option<u32>,
+) -> Result<Vec<SimilarCapture>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let storage = get_active_storage()?;
+        storage
+            .find_similar_captures(&hash, threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 /// Search chats and return ranked message hits.
@@ -210,19 +447,316 @@ pub fn search_chats(query: String, limit: Option<usize>) -> Result<Vec<ChatSearc
     search_local_chats(&storage, &query, max_results)
 }
 
+/// Rewrite or remove the system search stubs after `systemSearchIndex`
+/// changed. Returns the number of chats with a stub.
+#[tauri::command]
+pub async fn sync_system_search_index(app: tauri::AppHandle) -> Result<usize, String> {
+    crate::services::search_index::sync(&app).await
+}
+
 /// Delete a chat by ID.
 #[tauri::command]
-pub fn delete_chat(chat_id: String) -> Result<(), String> {
+pub fn delete_chat(app: tauri::AppHandle, chat_id: String) -> Result<(), String> {
     let storage = get_active_storage()?;
-    storage.delete_chat(&chat_id).map_err(|e| e.to_string())
+    storage.delete_chat(&chat_id).map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_deleted(&app, &chat_id);
+    Ok(())
+}
+
+/// Export a chat with its OCR text and images as one Markdown, JSON, HTML
+/// or ZIP file, at a path the user picks in a save dialog. Returns the
+/// written path, or `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn export_chat(
+    app: tauri::AppHandle,
+    chat_id: String,
+    format: ChatExportFormat,
+) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let storage = get_active_storage()?;
+        let chat = storage.load_chat(&chat_id).map_err(|e| e.to_string())?;
+        let filter_name = match format {
+            ChatExportFormat::Markdown => "Markdown",
+            ChatExportFormat::Json => "JSON",
+            ChatExportFormat::Html => "HTML",
+            ChatExportFormat::Zip => "ZIP archive",
+        };
+        let Some(path) = app
+            .dialog()
+            .file()
+            .set_file_name(suggested_file_name(&chat, format))
+            .add_filter(filter_name, &[format.extension()])
+            .blocking_save_file()
+        else {
+            return Ok(None);
+        };
+        let path = path.into_path().map_err(|e| e.to_string())?;
+        ops_chat_export::export_chat(&storage, &chat_id, format, &path)
+            .map_err(|e| e.to_string())?;
+        Ok(Some(path.to_string_lossy().into_owned()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Write the chats matching `filter`, starred chats by default, into
+/// `dest_dir` as a static HTML site that can be published or archived.
+#[tauri::command]
+pub async fn export_gallery(
+    dest_dir: String,
+    filter: Option<ChatFilter>,
+) -> Result<GalleryReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let storage = get_active_storage()?;
+        let filter = filter.unwrap_or(ChatFilter {
+            starred: Some(true),
+            ..ChatFilter::default()
+        });
+        ops_chat_export::export_gallery(&storage, std::path::Path::new(&dest_dir), &filter)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Import a chat from a JSON or ZIP export the user picks in an open
+/// dialog into the active profile. Returns the new chat's metadata, or
+/// `None` if the dialog was cancelled.
+#[tauri::command]
+pub async fn import_chat(app: tauri::AppHandle) -> Result<Option<ChatMetadata>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(path) = app
+            .dialog()
+            .file()
+            .add_filter("Chat export", &["json", "zip"])
+            .blocking_pick_file()
+        else {
+            return Ok(None);
+        };
+        let path = path.into_path().map_err(|e| e.to_string())?;
+        let storage = get_active_storage()?;
+        let metadata = storage.import_chat(&path).map_err(|e| e.to_string())?;
+        crate::services::search_index::chat_saved(&app, &metadata.id);
+        Ok(Some(metadata))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Export a chat as a raw LLM conversation (`schema`: "gemini" or "openai").
+#[tauri::command]
+pub fn export_chat_as_llm_json(
+    chat_id: String,
+    schema: String,
+) -> Result<serde_json::Value, String> {
+    let schema = LlmExportSchema::parse(&schema)?;
+    export_chat_as_llm_json_internal(&chat_id, schema)
+}
+
+/// Export a chat as a note in an Obsidian vault and return the note's path.
+/// `vault_path` and `template` default to the active profile's connector
+/// settings, which also provide the folder and tags.
+#[tauri::command]
+pub async fn export_chat_to_vault(
+    chat_id: String,
+    vault_path: Option<String>,
+    template: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = store
+            .get_active_profile_id()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "No active profile".to_string())?;
+        let settings = store
+            .get_export_connectors(&profile_id)
+            .map_err(|e| e.to_string())?
+            .obsidian;
+
+        let vault_path = vault_path
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .unwrap_or(settings.vault_path);
+        if vault_path.is_empty() {
+            return Err("No Obsidian vault configured".to_string());
+        }
+        let mut vault = ObsidianVault::new(vault_path);
+        if !settings.folder.is_empty() {
+            vault.folder = settings.folder;
+        }
+        if let Some(template) = template
+            .filter(|template| !template.trim().is_empty())
+            .or(settings.template)
+        {
+            vault.template = template;
+        }
+        vault.tags = settings.tags;
+
+        let storage = get_active_storage()?;
+        let chat = storage.load_chat(&chat_id).map_err(|e| e.to_string())?;
+        let image_path = storage
+            .get_image_path(&chat.metadata.image_hash)
+            .ok()
+            .map(std::path::PathBuf::from);
+        vault
+            .export(&ExportSource {
+                chat: &chat,
+                image_path: image_path.as_deref(),
+            })
+            .map(|path| path.to_string_lossy().into_owned())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Write every stored extraction of kind `profile` as CSV to `path`, one
+/// row per chat, oldest chat first. Returns the number of rows written.
+#[tauri::command]
+pub async fn export_extractions_csv(profile: String, path: String) -> Result<usize, String> {
+    let profile = ExtractionProfile::from_id(&profile)
+        .ok_or_else(|| format!("ERR_UNKNOWN_EXTRACTION_PROFILE: {}", profile))?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let storage = get_active_storage()?;
+        let mut chats = storage.list_chats().map_err(|e| e.to_string())?;
+        chats.sort_by_key(|metadata| metadata.created_at);
+
+        let mut rows = Vec::new();
+        for metadata in chats {
+            let extraction = storage
+                .get_extractions(&metadata.id)
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .find(|extraction| extraction.kind == profile.id());
+            if let Some(extraction) = extraction {
+                rows.push((metadata, extraction));
+            }
+        }
+        std::fs::write(&path, extractions_to_csv(profile, &rows)).map_err(|e| e.to_string())?;
+        Ok(rows.len())
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 /// Update chat metadata (rename, pin, star, etc.).
 #[tauri::command]
-pub fn update_chat_metadata(metadata: ChatMetadata) -> Result<(), String> {
+pub fn update_chat_metadata(app: tauri::AppHandle, metadata: ChatMetadata) -> Result<(), String> {
     let storage = get_active_storage()?;
     storage
         .update_chat_metadata(&metadata)
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &metadata.id);
+    Ok(())
+}
+
+/// Use another OCR language for one chat, without changing the global
+/// `ocrLanguage`. The chat is OCR'd again in the background, after the
+/// model is downloaded if needed; `chat-ocr-updated` reports the outcome.
+#[tauri::command]
+pub fn set_chat_ocr_language(
+    app: tauri::AppHandle,
+    chat_id: String,
+    lang: String,
+) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    let metadata = storage
+        .set_chat_ocr_lang(&chat_id, &lang)
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &chat_id);
+    if let Some(model_id) = metadata.ocr_lang.clone() {
+        crate::services::ocr::rerun_chat_ocr(&app, chat_id, model_id);
+    }
+    Ok(metadata)
+}
+
+/// Attach a stored object to a chat, after the ones it already has.
+/// `mime` is inferred from the stored file when not given.
+#[tauri::command]
+pub fn add_chat_attachment(
+    app: tauri::AppHandle,
+    chat_id: String,
+    hash: String,
+    mime: Option<String>,
+    label: Option<String>,
+) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    let metadata = storage
+        .add_chat_attachment(&chat_id, &hash, mime.as_deref(), label.as_deref())
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &chat_id);
+    Ok(metadata)
+}
+
+/// Detach a stored object from a chat.
+#[tauri::command]
+pub fn remove_chat_attachment(
+    app: tauri::AppHandle,
+    chat_id: String,
+    hash: String,
+) -> Result<ChatMetadata, String> {
+    let storage = get_active_storage()?;
+    let metadata = storage
+        .remove_chat_attachment(&chat_id, &hash)
+        .map_err(|e| e.to_string())?;
+    crate::services::search_index::chat_saved(&app, &chat_id);
+    Ok(metadata)
+}
+
+// =============================================================================
+// Artifact Commands
+// =============================================================================
+
+/// Save generated text as artifact `name` of a chat. Saving under an
+/// existing name adds a version.
+#[tauri::command]
+pub fn save_artifact(
+    chat_id: String,
+    name: String,
+    content: String,
+    model: Option<String>,
+) -> Result<Artifact, String> {
+    let storage = get_active_storage()?;
+    storage
+        .save_artifact(&chat_id, &name, content.as_bytes(), model.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// A chat's artifacts with their versions, oldest first.
+#[tauri::command]
+pub fn list_artifacts(chat_id: String) -> Result<Vec<Artifact>, String> {
+    let storage = get_active_storage()?;
+    storage.list_artifacts(&chat_id).map_err(|e| e.to_string())
+}
+
+/// Text of an artifact version, the latest when `version` is omitted.
+#[tauri::command]
+pub fn read_artifact_text(
+    chat_id: String,
+    artifact_id: String,
+    version: Option<u32>,
+) -> Result<String, String> {
+    let storage = get_active_storage()?;
+    let bytes = storage
+        .read_artifact(&chat_id, &artifact_id, version)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|_| "ERR_ARTIFACT_NOT_TEXT".to_string())
+}
+
+/// Copy an artifact version, the latest when `version` is omitted, to
+/// `path`. A directory gets a file named after the artifact. Returns the
+/// written path.
+#[tauri::command]
+pub fn export_artifact(
+    chat_id: String,
+    artifact_id: String,
+    version: Option<u32>,
+    path: String,
+) -> Result<String, String> {
+    let storage = get_active_storage()?;
+    storage
+        .export_artifact(&chat_id, &artifact_id, version, std::path::Path::new(&path))
+        .map(|path| path.to_string_lossy().into_owned())
         .map_err(|e| e.to_string())
 }
 
@@ -230,28 +764,89 @@ pub fn update_chat_metadata(metadata: ChatMetadata) -> Result<(), String> {
 // Message Commands
 // =============================================================================
 
+/// Append a message. `model` records which model wrote an assistant reply.
 #[tauri::command]
-pub fn append_chat_message(chat_id: String, role: String, content: String) -> Result<(), String> {
+pub fn append_chat_message(
+    chat_id: String,
+    role: String,
+    content: String,
+    model: Option<String>,
+) -> Result<(), String> {
     let storage = get_active_storage()?;
-    let message = if role == "user" {
+    let mut message = if role == "user" {
         ChatMessage::user(content)
     } else {
         ChatMessage::assistant(content)
     };
+    message.model = model.filter(|model| !model.trim().is_empty());
     storage
         .append_message(&chat_id, &message)
         .map_err(|e| e.to_string())
 }
 
-/// Overwrite all messages in a chat.
+/// Overwrite all messages in a chat. A message sent without an ID keeps the
+/// ID and edit history of the stored message at its position, if the roles
+/// match.
 #[tauri::command]
 pub fn overwrite_chat_messages(chat_id: String, messages: Vec<ChatMessage>) -> Result<(), String> {
     let storage = get_active_storage()?;
     let mut chat = storage.load_chat(&chat_id).map_err(|e| e.to_string())?;
-    chat.messages = messages;
+    let previous = std::mem::take(&mut chat.messages);
+    chat.messages = messages
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut message)| {
+            let stored = previous
+                .get(index)
+                .filter(|stored| stored.role == message.role);
+            if let Some(stored) = stored.filter(|_| message.id.is_empty()) {
+                message.id = stored.id.clone();
+                message.revisions = stored.revisions.clone();
+            }
+            message
+        })
+        .collect();
     storage.save_chat(&chat).map_err(|e| e.to_string())
 }
 
+/// Replace a message's content, keeping the old version in its history.
+/// `truncate` drops the messages after it, for regenerating from there.
+#[tauri::command]
+pub fn edit_message(
+    chat_id: String,
+    message_id: String,
+    content: String,
+    model: Option<String>,
+    truncate: bool,
+) -> Result<ChatMessage, String> {
+    let storage = get_active_storage()?;
+    let model = model.filter(|model| !model.trim().is_empty());
+    storage
+        .edit_message(&chat_id, &message_id, content, model, truncate)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a single message.
+#[tauri::command]
+pub fn delete_message(chat_id: String, message_id: String) -> Result<(), String> {
+    let storage = get_active_storage()?;
+    storage
+        .delete_message(&chat_id, &message_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Earlier versions of a message, oldest first.
+#[tauri::command]
+pub fn get_message_history(
+    chat_id: String,
+    message_id: String,
+) -> Result<Vec<MessageRevision>, String> {
+    let storage = get_active_storage()?;
+    storage
+        .get_message_history(&chat_id, &message_id)
+        .map_err(|e| e.to_string())
+}
+
 // =============================================================================
 // OCR Commands
 // =============================================================================
@@ -259,6 +854,7 @@ pub fn overwrite_chat_messages(chat_id: String, messages: Vec<ChatMessage>) -> R
 /// Save OCR data for a specific model.
 #[tauri::command]
 pub fn save_ocr_data(
+    app: tauri::AppHandle,
     chat_id: String,
     model_id: String,
     ocr_data: Vec<OcrRegion>,
@@ -266,7 +862,9 @@ pub fn save_ocr_data(
     let storage = get_active_storage()?;
     storage
         .save_ocr_data(&chat_id, &model_id, &ocr_data)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    crate::services::plugins::ocr_saved(&app, &chat_id, &model_id, &ocr_data);
+    Ok(())
 }
 
 /// Get OCR data for a specific model.
@@ -278,6 +876,19 @@ pub fn get_ocr_data(chat_id: String, model_id: String) -> Result<Option<Vec<OcrR
         .map_err(|e| e.to_string())
 }
 
+/// OCR text for a specific model in reading order, for copying.
+#[tauri::command]
+pub fn get_ocr_text(
+    chat_id: String,
+    model_id: String,
+    layout: OcrTextLayout,
+) -> Result<Option<String>, String> {
+    let storage = get_active_storage()?;
+    storage
+        .get_ocr_text(&chat_id, &model_id, layout)
+        .map_err(|e| e.to_string())
+}
+
 /// Get the entire OCR frame for a chat.
 #[tauri::command]
 pub fn get_ocr_frame(chat_id: String) -> Result<OcrFrame, String> {
@@ -4,8 +4,7 @@
 use tauri::State;
 
 use crate::state::AppState;
-use ops_chat_storage::{ChatStorage, StoredImage};
-use ops_profile_store::ProfileStore;
+use ops_chat_storage::StoredImage;
 
 /// Read image from clipboard and store in CAS.
 /// Returns StoredImage { hash, path }.
@@ -41,19 +40,7 @@ pub async fn read_clipboard_image(_state: State<'_, AppState>) -> Result<StoredI
         .map_err(|e| format!("Failed to encode image: {}", e))?;
 
     // Store in CAS using active profile's storage
-    let profile_store = ProfileStore::new().map_err(|e| e.to_string())?;
-    let active_id = profile_store
-        .get_active_profile_id()
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "No active profile. Please log in first.".to_string())?;
-
-    let chats_dir = profile_store.get_chats_dir(&active_id);
-    let storage = ChatStorage::with_base_dir(chats_dir).map_err(|e| e.to_string())?;
-    let stored = storage
-        .store_image(&buffer, None)
-        .map_err(|e| e.to_string())?;
-
-    Ok(stored)
+    ops_squigit_brain::context::media::process_bytes_internal(buffer, None)
 }
 
 /// Read text from clipboard.
@@ -69,6 +56,13 @@ pub async fn read_clipboard_text() -> Result<String, String> {
         .map_err(|e| format!("Failed to get text from clipboard: {}", e))
 }
 
+/// Copy the latest assistant reply of the active chat to the clipboard.
+/// Returns the number of characters copied.
+#[tauri::command]
+pub async fn copy_last_answer(app: tauri::AppHandle) -> Result<usize, String> {
+    crate::services::clipboard::copy_last_answer(&app)
+}
+
 #[tauri::command]
 pub async fn copy_image_to_clipboard(image_base64: String) -> Result<(), String> {
     use arboard::{Clipboard, ImageData};
@@ -12,6 +12,7 @@ pub struct AppConstants {
     pub default_prompt: &'static str,
     pub preferences_file_name: &'static str,
     pub default_capture_type: &'static str,
+    pub default_capture_display: &'static str,
     pub default_ocr_language: &'static str,
     pub default_active_account: &'static str,
 }
@@ -25,6 +26,7 @@ pub fn get_app_constants() -> AppConstants {
         default_prompt: DEFAULT_PROMPT,
         preferences_file_name: PREFERENCES_FILE_NAME,
         default_capture_type: DEFAULT_CAPTURE_TYPE,
+        default_capture_display: DEFAULT_CAPTURE_DISPLAY,
         default_ocr_language: DEFAULT_OCR_LANGUAGE,
         default_active_account: DEFAULT_ACTIVE_ACCOUNT,
     }
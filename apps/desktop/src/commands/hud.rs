@@ -0,0 +1,35 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::services::hud::{HudConfig, HudState};
+use tauri::{AppHandle, State};
+
+/// Start the "explain my screen" HUD overlay.
+#[tauri::command]
+pub fn start_hud(
+    app: AppHandle,
+    api_key: String,
+    model: String,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    crate::services::hud::start_hud(
+        &app,
+        HudConfig {
+            api_key,
+            model,
+            interval_secs,
+        },
+    )
+}
+
+/// Stop the HUD loop, cancel any in-flight summary and close the overlay.
+#[tauri::command]
+pub async fn stop_hud(app: AppHandle) -> Result<(), String> {
+    crate::services::hud::stop_hud(&app).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn is_hud_running(hud: State<'_, HudState>) -> bool {
+    hud.is_running()
+}
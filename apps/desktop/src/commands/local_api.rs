@@ -0,0 +1,24 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local API Tauri commands, for pairing companion apps.
+
+use crate::services::local_api::{self, LocalApiStatus};
+use tauri::AppHandle;
+
+/// Whether the local API is on, its port and the pairing token.
+#[tauri::command]
+pub fn get_local_api_status(app: AppHandle) -> Result<LocalApiStatus, String> {
+    local_api::status(&app)
+}
+
+#[tauri::command]
+pub fn set_local_api_enabled(app: AppHandle, enabled: bool) -> Result<LocalApiStatus, String> {
+    local_api::set_enabled(&app, enabled)
+}
+
+/// Issue a new pairing token. Paired companions must be set up again.
+#[tauri::command]
+pub fn regenerate_local_api_token(app: AppHandle) -> Result<LocalApiStatus, String> {
+    local_api::regenerate_token(&app)
+}
@@ -1,16 +1,22 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod actions;
 pub mod audio;
 pub mod auth;
 pub mod brain;
 pub mod chat;
 pub mod clipboard;
+pub mod hud;
 pub mod image;
+pub mod local_api;
 pub mod models;
 pub mod ocr;
+pub mod plugins;
 pub mod profile;
+pub mod realtime;
 pub mod security;
+pub mod session;
 pub mod system;
 
 pub mod capture;
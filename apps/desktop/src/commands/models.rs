@@ -1,9 +1,10 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::services::a11y::DownloadAnnouncer;
 use crate::services::ocr::DesktopOcrService;
 use serde::{Deserialize, Serialize};
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModelStatus {
@@ -19,23 +20,29 @@ pub async fn download_ocr_model(
     url: String,
     model_id: String,
 ) -> Result<String, String> {
+    crate::services::battery::wait_for_download(window.app_handle(), &model_id).await?;
     println!("Downloading OCR model: {} -> {}", url, model_id);
 
-    let path = state
+    let mut announcer = DownloadAnnouncer::new(window.app_handle());
+    let result = state
         .download_model(&url, &model_id, |payload| {
+            announcer.progress(&payload.status, payload.progress);
             let _ = window.emit("download-progress", payload);
         })
-        .await?;
+        .await;
+    announcer.finished(&result);
 
-    Ok(path.to_string_lossy().to_string())
+    Ok(result?.to_string_lossy().to_string())
 }
 
 #[tauri::command]
 pub async fn cancel_download_ocr_model(
+    app: tauri::AppHandle,
     state: tauri::State<'_, DesktopOcrService>,
     model_id: String,
 ) -> Result<(), String> {
     println!("Cancelling download for model: {}", model_id);
+    crate::services::battery::cancel_deferred_download(&app, &model_id);
     state.cancel_model_download(&model_id);
     Ok(())
 }
@@ -1,59 +1,207 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::services::ocr::DesktopOcrService;
+use crate::services::ocr::{
+    emit_ocr_progress, DesktopOcrService, IMAGE_DISCARDED_ERROR, OCR_CANCELLED_ERROR,
+};
+use crate::services::{a11y, metrics};
+use ops_chat_storage::OcrRegion;
 use ops_squigit_brain::provider::attachments::resolve_attachment_path_buf;
-use ops_squigit_ocr::ocr::{OcrBox, OcrRequest};
-use tauri::Manager;
+use ops_squigit_ocr::formula::{
+    apply_formula_results, resolve_formula_sidecar_path, select_formula_candidates, FormulaRequest,
+};
+use ops_squigit_ocr::ocr::{apply_min_confidence, OcrBox, OcrLimits, OcrRegionCallback};
+use sys_process_priority::SidecarRole;
 
+/// OCR a stored image. With `stream`, each region is also emitted as
+/// `ocr-progress` as soon as the sidecar recognizes it; the returned list
+/// is the final, glossary-corrected one.
+/// Jobs for different chats run side by side up to the configured limit;
+/// a new job for the same `chat_id` replaces the running one.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn ocr_image(
     app: tauri::AppHandle,
     ocr: tauri::State<'_, DesktopOcrService>,
     image_data: String,
     is_base64: bool,
     model_name: Option<String>,
+    min_confidence: Option<f64>,
+    drop_low_confidence: Option<bool>,
+    stream: Option<bool>,
+    chat_id: Option<String>,
 ) -> Result<Vec<OcrBox>, String> {
-    let resource_dir = app
-        .path()
-        .resource_dir()
-        .map_err(|e| format!("Failed to get resource dir: {}", e))?;
-
-    let (sidecar_path, runtime_dir) = ocr.resolve_sidecar_path(&resource_dir);
-
-    if sidecar_path.is_absolute() && !sidecar_path.exists() {
-        return Err("ERR_MISSING_OCR_PACKAGE".to_string());
-    }
-
-    ocr.ensure_sidecar_version_compatible(&sidecar_path)?;
-
     if is_base64 {
         return Err(
             "OCR sidecar is path-only. Pass a stored CAS path instead of base64 data.".to_string(),
         );
     }
 
-    let resolved_image_path = resolve_attachment_path_buf(&image_data)?;
+    let min_confidence = min_confidence.filter(|v| v.is_finite() && *v > 0.0);
+    let drop_low_confidence = drop_low_confidence.unwrap_or(false);
 
-    let rec_model_dir_override = ocr.resolve_rec_model_dir_override(model_name.as_deref());
+    let on_region = stream.unwrap_or(false).then(|| {
+        let handle = app.clone();
+        let image_path = image_data.clone();
+        let chat_id = chat_id.clone();
+        Box::new(
+            move |index: usize, detected: Option<usize>, region: &OcrBox| {
+                let mut region = region.clone();
+                if let Some(min_confidence) = min_confidence {
+                    region.low_confidence = region.confidence < min_confidence;
+                    if region.low_confidence && drop_low_confidence {
+                        return;
+                    }
+                }
+                emit_ocr_progress(
+                    &handle,
+                    &image_path,
+                    chat_id.as_deref(),
+                    index,
+                    detected,
+                    &region,
+                );
+            },
+        ) as OcrRegionCallback
+    });
 
     let result = ocr
-        .run_ocr(OcrRequest {
-            sidecar_path,
-            runtime_dir,
-            image_path: resolved_image_path,
-            rec_model_dir_override,
+        .recognize(
+            &app,
+            resolve_attachment_path_buf(&image_data)?,
+            chat_id,
+            model_name.as_deref(),
+            on_region,
+        )
+        .await;
+    metrics::record_use(
+        &app,
+        "ocr",
+        matches!(&result, Err(e) if e != OCR_CANCELLED_ERROR),
+    );
+    let boxes = result?;
+    let message = match boxes.len() {
+        0 => "Text recognition finished, no text found".to_string(),
+        1 => "Text recognition finished, 1 region found".to_string(),
+        count => format!("Text recognition finished, {} regions found", count),
+    };
+    a11y::polite(&app, "ocr", &message);
+
+    let Some(min_confidence) = min_confidence else {
+        return Ok(boxes);
+    };
+
+    let (boxes, summary) = apply_min_confidence(boxes, min_confidence, drop_low_confidence);
+    if let Some(described) = summary.describe() {
+        log::info!(
+            "OCR: {} (min_confidence={}, total={})",
+            described,
+            min_confidence,
+            summary.total_regions
+        );
+    }
+
+    Ok(boxes)
+}
+
+/// Run the formula recognition pass over a chat's stored OCR regions.
+/// Regions that look like equations get a LaTeX transcription attached and
+/// the updated frame is written back. Returns the updated regions.
+#[tauri::command]
+pub async fn ocr_formulas(
+    app: tauri::AppHandle,
+    ocr: tauri::State<'_, DesktopOcrService>,
+    chat_id: String,
+    model_id: String,
+) -> Result<Vec<OcrRegion>, String> {
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    let mut regions = storage
+        .get_ocr_data(&chat_id, &model_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No OCR data for this model yet".to_string())?;
+
+    let candidates = select_formula_candidates(&regions);
+    if candidates.is_empty() {
+        return Ok(regions);
+    }
+
+    let chat = storage.load_chat(&chat_id).map_err(|e| e.to_string())?;
+    let image_path = storage
+        .chat_image_path(&chat.metadata)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| IMAGE_DISCARDED_ERROR.to_string())?;
+
+    let results = ocr
+        .run_formula_pass(FormulaRequest {
+            sidecar_path: resolve_formula_sidecar_path(),
+            image_path: image_path.into(),
+            regions: candidates,
             timeout_secs: None,
+            priority: crate::services::priority::sidecar_priority(&app, SidecarRole::Background),
         })
         .await?;
 
-    Ok(result.boxes)
+    if apply_formula_results(&mut regions, &results) > 0 {
+        storage
+            .save_ocr_data(&chat_id, &model_id, &regions)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(regions)
+}
+
+/// Read a native window's text through the platform accessibility API.
+/// An empty result means the window exposes nothing and the caller should
+/// fall back to `ocr_image`.
+#[tauri::command]
+pub async fn grab_window_text(window_id: u64) -> Result<sys_accessible_text::WindowText, String> {
+    tauri::async_runtime::spawn_blocking(move || sys_accessible_text::grab_window_text(window_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_ocr_limits(app: tauri::AppHandle) -> OcrLimits {
+    crate::services::ocr::ocr_limits(&app)
+}
+
+/// Validate and save OCR resource limits. They apply from the next job.
+#[tauri::command]
+pub fn set_ocr_limits(app: tauri::AppHandle, limits: OcrLimits) -> Result<OcrLimits, String> {
+    crate::services::ocr::save_ocr_limits(&app, &limits)?;
+    Ok(limits)
 }
 
-/// Cancel the currently running OCR job.
-/// Kills the sidecar process and waits briefly for shutdown.
+/// Cancel the OCR job of `chat_id`, or without one all OCR jobs but those
+/// of new captures. Kills the sidecars and waits briefly for shutdown.
 /// This is fire-and-forget from the frontend's perspective.
 #[tauri::command]
-pub async fn cancel_ocr_job(ocr: tauri::State<'_, DesktopOcrService>) -> Result<(), String> {
-    ocr.cancel_ocr_job().await
+pub async fn cancel_ocr_job(
+    ocr: tauri::State<'_, DesktopOcrService>,
+    chat_id: Option<String>,
+) -> Result<(), String> {
+    let cancelled = ocr.cancel_ocr_job(chat_id.as_deref()).await;
+    log::info!("OCR: cancelled {} job(s)", cancelled);
+    Ok(())
+}
+
+/// Whether the chat's OCR after capture is still running; its result
+/// arrives as `ocr-ready`.
+#[tauri::command]
+pub fn is_chat_ocr_pending(chat_id: String) -> bool {
+    crate::services::ocr::is_capture_ocr_pending(&chat_id)
+}
+
+/// OCR every chat that has no regions for `model_id` yet, typically right
+/// after the model was downloaded. Runs in the background; progress arrives
+/// as `ocr-backfill-progress` events and the end as `ocr-backfill-done`.
+#[tauri::command]
+pub fn backfill_ocr(app: tauri::AppHandle, model_id: String) -> Result<(), String> {
+    crate::services::ocr_backfill::start(&app, model_id)
+}
+
+/// Stop the OCR backfill, cancelling the scan in progress.
+#[tauri::command]
+pub async fn stop_ocr_backfill(app: tauri::AppHandle) {
+    crate::services::ocr_backfill::stop(&app).await;
 }
@@ -0,0 +1,49 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Post-capture plugin Tauri commands.
+
+use crate::services::plugins::{self, PluginInfo, PluginRegistry};
+use tauri::{AppHandle, Manager, State};
+
+/// Rescan the plugins directory and list what is installed.
+#[tauri::command]
+pub async fn list_plugins(app: AppHandle) -> Result<Vec<PluginInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || app.state::<PluginRegistry>().rescan(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Enable or disable a plugin. Disabled plugins never run.
+#[tauri::command]
+pub fn set_plugin_enabled(
+    app: AppHandle,
+    registry: State<'_, PluginRegistry>,
+    id: String,
+    enabled: bool,
+) -> Result<Vec<PluginInfo>, String> {
+    plugins::set_plugin_enabled(&app, &id, enabled)?;
+    Ok(registry.list(&app))
+}
+
+/// The plugins directory, created if missing so it can be opened.
+#[tauri::command]
+pub fn get_plugins_dir(app: AppHandle) -> Result<String, String> {
+    let dir = plugins::plugins_dir(&app);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.to_string_lossy().into_owned())
+}
+
+/// Run the enabled plugins on a chat again, with its current OCR result.
+#[tauri::command]
+pub fn run_chat_plugins(app: AppHandle, chat_id: String) -> Result<(), String> {
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    let chat = storage.load_chat(&chat_id).map_err(|e| e.to_string())?;
+    let ocr_model = chat.metadata.ocr_lang;
+    let regions = ocr_model
+        .as_deref()
+        .and_then(|model_id| chat.ocr_data.get(model_id).cloned().flatten())
+        .unwrap_or_default();
+    plugins::run_for_chat(&app, chat_id, ocr_model, regions);
+    Ok(())
+}
@@ -3,7 +3,12 @@
 
 //! Profile management Tauri commands.
 
-use ops_profile_store::{Profile, ProfileStore};
+use chrono::{DateTime, Utc};
+use ops_chat_storage::EncryptionReport;
+use ops_profile_store::{
+    ExportConnectorsConfig, GlossaryEntry, GlossaryEntryInput, LlmAuditEntry, Profile,
+    ProfileStore, VertexConfig, WebhookConfig,
+};
 use serde::Serialize;
 
 /// Profile data returned to frontend.
@@ -109,3 +114,322 @@ pub async fn get_profile_count() -> Result<usize, String> {
     .await
     .map_err(|e| e.to_string())?
 }
+
+fn active_profile_id(store: &ProfileStore) -> Result<String, String> {
+    store
+        .get_active_profile_id()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No active profile".to_string())
+}
+
+/// List the active profile's glossary.
+#[tauri::command]
+pub async fn list_glossary() -> Result<Vec<GlossaryEntry>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store.list_glossary(&profile_id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Add a term to the active profile's glossary.
+#[tauri::command]
+pub async fn add_glossary_entry(entry: GlossaryEntryInput) -> Result<GlossaryEntry, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .add_glossary_entry(&profile_id, entry)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Edit a glossary entry. Renaming the term changes its ID.
+#[tauri::command]
+pub async fn update_glossary_entry(
+    id: String,
+    entry: GlossaryEntryInput,
+) -> Result<GlossaryEntry, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .update_glossary_entry(&profile_id, &id, entry)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Remove a glossary entry.
+#[tauri::command]
+pub async fn delete_glossary_entry(id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .delete_glossary_entry(&profile_id, &id)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// The active profile's model fallback chain.
+#[tauri::command]
+pub async fn get_model_fallbacks() -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile = store
+            .get_active_profile()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "No active profile".to_string())?;
+        Ok(profile.model_fallbacks)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Replace the active profile's model fallback chain. Returns the stored,
+/// normalized list.
+#[tauri::command]
+pub async fn set_model_fallbacks(models: Vec<String>) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .set_model_fallbacks(&profile_id, models)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Whether the active profile records outbound LLM calls.
+#[tauri::command]
+pub async fn get_llm_audit_enabled() -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile = store
+            .get_active_profile()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "No active profile".to_string())?;
+        Ok(profile.llm_audit_enabled)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Opt the active profile in or out of the LLM audit log.
+#[tauri::command]
+pub async fn set_llm_audit_enabled(enabled: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .set_llm_audit_enabled(&profile_id, enabled)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// The active profile's audited LLM calls in `[from, to)`, oldest first.
+#[tauri::command]
+pub async fn list_llm_audit(
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<Vec<LlmAuditEntry>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .list_llm_audit(&profile_id, from, to)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Delete audit entries older than `before`, or all of them. Returns how
+/// many were removed.
+#[tauri::command]
+pub async fn purge_llm_audit(before: Option<DateTime<Utc>>) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .purge_llm_audit(&profile_id, before)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Whether the active profile strips metadata from images as they are
+/// stored. Uploads are stripped regardless.
+#[tauri::command]
+pub async fn get_strip_image_metadata() -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile = store
+            .get_active_profile()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "No active profile".to_string())?;
+        Ok(profile.strip_image_metadata)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Turn store-time metadata stripping on or off for the active profile.
+/// Images already stored keep their metadata.
+#[tauri::command]
+pub async fn set_strip_image_metadata(enabled: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .set_strip_image_metadata(&profile_id, enabled)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// The active profile's automation webhook settings. The signing secret is
+/// never returned, only whether one is stored.
+#[tauri::command]
+pub async fn get_webhook_config() -> Result<WebhookConfig, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .get_webhook_config(&profile_id)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Save the active profile's webhook settings. A `secret` replaces the
+/// signing secret; an empty one removes it and `None` keeps it.
+#[tauri::command]
+pub async fn set_webhook_config(
+    config: WebhookConfig,
+    secret: Option<String>,
+) -> Result<WebhookConfig, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .set_webhook_config(&profile_id, config, secret.as_deref())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// The active profile's Vertex AI settings. The credentials are never
+/// returned; `hasCredentials` tells whether some are stored.
+#[tauri::command]
+pub async fn get_vertex_config() -> Result<VertexConfig, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .get_vertex_config(&profile_id)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Save the active profile's Vertex AI settings. `credentials`, a service
+/// account or user credentials JSON, replaces the stored one; an empty one
+/// removes it and `None` keeps it.
+#[tauri::command]
+pub async fn set_vertex_config(
+    config: VertexConfig,
+    credentials: Option<String>,
+) -> Result<VertexConfig, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        let config = store
+            .set_vertex_config(&profile_id, config, credentials.as_deref())
+            .map_err(|e| e.to_string())?;
+        crate::services::vertex::forget();
+        Ok(config)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Whether the active profile's chats are encrypted at rest.
+#[tauri::command]
+pub async fn get_chat_encryption_enabled() -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        Ok(store.is_chat_encryption_enabled(&profile_id))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Encrypt the active profile's chats at rest, including the ones it
+/// already has. Running it again finishes an interrupted migration.
+#[tauri::command]
+pub async fn encrypt_chat_storage() -> Result<EncryptionReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .enable_chat_encryption(&profile_id)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// The active profile's chat export connector settings.
+#[tauri::command]
+pub async fn get_export_connectors() -> Result<ExportConnectorsConfig, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .get_export_connectors(&profile_id)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Save the active profile's chat export connector settings.
+#[tauri::command]
+pub async fn set_export_connectors(
+    config: ExportConnectorsConfig,
+) -> Result<ExportConnectorsConfig, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let profile_id = active_profile_id(&store)?;
+        store
+            .set_export_connectors(&profile_id, config)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Post a single `ping` event to the saved webhook URL, without retries.
+/// Returns the HTTP status.
+#[tauri::command]
+pub async fn test_webhook() -> Result<u16, String> {
+    crate::services::webhook::ping().await
+}
@@ -0,0 +1,19 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gemini Live realtime session commands. Events arrive on `realtime_event`.
+
+use tauri::AppHandle;
+
+/// Start a realtime voice session, sharing the chat's screenshot as screen
+/// context when `chat_id` is given.
+#[tauri::command]
+pub async fn start_realtime_session(app: AppHandle, chat_id: Option<String>) -> Result<(), String> {
+    crate::services::realtime::start(&app, chat_id).await
+}
+
+#[tauri::command]
+pub async fn stop_realtime_session(app: AppHandle) -> Result<(), String> {
+    crate::services::realtime::stop(&app).await;
+    Ok(())
+}
@@ -22,6 +22,7 @@ pub async fn encrypt_and_save(
     tauri::async_runtime::spawn_blocking(move || {
         let store = ProfileStore::new().map_err(|err| err.to_string())?;
         let provider = ApiKeyProvider::from_str(&provider).map_err(|err| err.to_string())?;
+        crate::services::policy::check_provider(provider)?;
         ops_profile_store::security::encrypt_and_save_key(&store, &profile_id, provider, &plaintext)
             .map(|_| ())
             .map_err(|err| err.to_string())
@@ -0,0 +1,19 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Session restore commands.
+
+use crate::services::session::{LastSession, SessionState, SessionUpdate};
+use tauri::State;
+
+/// Where the previous run left off, or `null` on first launch.
+#[tauri::command]
+pub fn get_last_session(session: State<'_, SessionState>) -> Option<LastSession> {
+    session.last_session()
+}
+
+/// Record the active chat and scroll position; called by the renderer on change.
+#[tauri::command]
+pub fn update_session_state(session: State<'_, SessionState>, update: SessionUpdate) {
+    session.update(update);
+}
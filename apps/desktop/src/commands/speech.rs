@@ -7,20 +7,41 @@
 
 use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
 
-use svc_speech_engine::{SpeechEngine, SttEvent};
+use crate::services::brain::DesktopBrainService;
+use crate::services::conversation;
+use crate::services::voice_commands::{self, VoiceCommandInfo, VoiceCommandSettings};
+use ops_squigit_brain::service::CleanTranscriptRequest;
+use svc_speech_engine::postprocess::CleanupFuture;
+use svc_speech_engine::{
+    PostProcessConfig, SpeechEngine, SttEvent, TranscriptCleaner, VocabularyEntry,
+};
+use sys_process_priority::SidecarRole;
+
+const STT_PUNCTUATION_PREF: &str = "sttPunctuation";
+const STT_LLM_CLEANUP_PREF: &str = "sttLlmCleanup";
 
 /// Shared speech engine state
 pub struct SpeechState {
     pub engine: Arc<Mutex<Option<SpeechEngine>>>,
+    /// Settings of the running engine, kept to restart it after resume.
+    session: Arc<Mutex<Option<SpeechSession>>>,
+}
+
+#[derive(Clone)]
+struct SpeechSession {
+    binary_path: PathBuf,
+    model: String,
+    lang: String,
 }
 
 impl Default for SpeechState {
     fn default() -> Self {
         Self {
             engine: Arc::new(Mutex::new(None)),
+            session: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -112,8 +133,22 @@ pub async fn start_stt(
     if engine_guard.is_some() {
         return Err("STT already running".to_string());
     }
+    start_engine(&app, &state, &mut engine_guard, model, language).await
+}
 
-    let (binary_path, _) = resolve_sidecar_path(&app)?;
+async fn start_engine(
+    app: &AppHandle,
+    state: &SpeechState,
+    engine_guard: &mut Option<SpeechEngine>,
+    model: Option<String>,
+    language: Option<String>,
+) -> Result<(), String> {
+    let (binary_path, _) = resolve_sidecar_path(app)?;
+    crate::services::integrity::ensure_sidecar_intact(
+        app,
+        crate::services::integrity::SidecarKind::Whisper,
+        &binary_path,
+    )?;
 
     // Version locking
     check_stt_version(&binary_path)?;
@@ -128,19 +163,47 @@ pub async fn start_stt(
         lang
     );
 
-    // Create and start engine
-    let mut engine = SpeechEngine::new(binary_path);
+    let session = SpeechSession {
+        binary_path,
+        model: model_name,
+        lang,
+    };
+    *engine_guard = Some(launch_engine(app, &session).await?);
+    *state.session.lock().await = Some(session);
+
+    Ok(())
+}
+
+/// Start the engine and forward its events to the frontend.
+async fn launch_engine(app: &AppHandle, session: &SpeechSession) -> Result<SpeechEngine, String> {
+    let mut engine = SpeechEngine::new(session.binary_path.clone()).with_priority(
+        crate::services::priority::sidecar_priority(app, SidecarRole::Streaming),
+    );
     let mut rx = engine
-        .start(model_name, lang)
+        .start(
+            session.model.clone(),
+            session.lang.clone(),
+            post_processing_config(app),
+        )
         .await
         .map_err(|e| format!("Failed to start engine: {}", e))?;
 
-    *engine_guard = Some(engine);
-
     // Spawn event forwarding task
     let app_handle = app.clone();
+    let commands = voice_commands::settings(app);
     tokio::spawn(async move {
         while let Some(event) = rx.recv().await {
+            if let SttEvent::Transcription { text, is_final } = &event {
+                if *is_final {
+                    if let Some(action) = commands.match_transcript(text) {
+                        log::info!("Voice command: {}", action);
+                        voice_commands::dispatch(&app_handle, action, text.clone());
+                        continue;
+                    }
+                }
+                conversation::on_transcript(&app_handle, text, *is_final);
+            }
+
             let payload = match &event {
                 SttEvent::Transcription { text, is_final } => {
                     serde_json::json!({
@@ -155,6 +218,26 @@ pub async fn start_stt(
                         "status": status
                     })
                 }
+                SttEvent::Level { rms } => {
+                    serde_json::json!({
+                        "type": "level",
+                        "rms": rms
+                    })
+                }
+                SttEvent::DeviceInfo {
+                    name,
+                    index,
+                    sample_rate,
+                    channels,
+                } => {
+                    serde_json::json!({
+                        "type": "device_info",
+                        "name": name,
+                        "index": index,
+                        "sample_rate": sample_rate,
+                        "channels": channels
+                    })
+                }
                 SttEvent::Error { message } => {
                     serde_json::json!({
                         "type": "error",
@@ -170,7 +253,100 @@ pub async fn start_stt(
         }
     });
 
-    Ok(())
+    Ok(engine)
+}
+
+/// Transcript post-processing from preferences: rule-based punctuation
+/// (on by default), the profile glossary as vocabulary, and an opt-in
+/// Gemini cleanup of final transcripts.
+fn post_processing_config(app: &AppHandle) -> PostProcessConfig {
+    let prefs_file =
+        crate::utils::get_app_config_dir(app).join(crate::constants::PREFERENCES_FILE_NAME);
+    let prefs = std::fs::read_to_string(prefs_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .unwrap_or_default();
+    let flag = |key: &str| prefs.get(key).and_then(|value| value.as_bool());
+
+    let vocabulary = crate::services::brain::active_glossary()
+        .into_iter()
+        .map(|entry| VocabularyEntry {
+            term: entry.term,
+            variants: entry.variants,
+        })
+        .collect();
+
+    let cleanup = if flag(STT_LLM_CLEANUP_PREF).unwrap_or(false) {
+        match crate::services::brain::resolve_credentials() {
+            Ok(credentials) => Some(Arc::new(GeminiTranscriptCleaner {
+                app: app.clone(),
+                api_key: credentials.api_key,
+            }) as Arc<dyn TranscriptCleaner>),
+            Err(e) => {
+                log::warn!("Transcript cleanup disabled: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    PostProcessConfig {
+        punctuation: flag(STT_PUNCTUATION_PREF).unwrap_or(true),
+        vocabulary,
+        cleanup,
+    }
+}
+
+struct GeminiTranscriptCleaner {
+    app: AppHandle,
+    api_key: String,
+}
+
+impl TranscriptCleaner for GeminiTranscriptCleaner {
+    fn clean<'a>(&'a self, text: &'a str) -> CleanupFuture<'a> {
+        Box::pin(async move {
+            self.app
+                .state::<DesktopBrainService>()
+                .clean_transcript(CleanTranscriptRequest {
+                    api_key: self.api_key.clone(),
+                    model: crate::constants::DEFAULT_MODEL.to_string(),
+                    transcript: text.to_string(),
+                })
+                .await
+        })
+    }
+}
+
+/// Restart a running engine after the system resumed, since the audio
+/// device it captured from is usually gone. No-op when STT is idle.
+pub async fn restart_after_resume(app: &AppHandle) {
+    let state = app.state::<SpeechState>();
+    let mut engine_guard = state.engine.lock().await;
+    let Some(mut engine) = engine_guard.take() else {
+        return;
+    };
+    if let Err(e) = engine.stop().await {
+        log::warn!("Failed to stop STT engine after resume: {}", e);
+    }
+
+    let Some(session) = state.session.lock().await.clone() else {
+        return;
+    };
+    log::info!("Restarting STT after resume: model={:?}", session.model);
+    match launch_engine(app, &session).await {
+        Ok(engine) => *engine_guard = Some(engine),
+        Err(e) => {
+            log::error!("Failed to restart STT after resume: {}", e);
+            let _ = app.emit(
+                "stt_event",
+                serde_json::json!({
+                    "type": "error",
+                    "message": e
+                }),
+            );
+        }
+    }
 }
 
 #[tauri::command]
@@ -183,10 +359,71 @@ pub async fn stop_stt(state: State<'_, SpeechState>) -> Result<(), String> {
             .await
             .map_err(|e| format!("Failed to stop engine: {}", e))?;
     }
+    *state.session.lock().await = None;
 
     Ok(())
 }
 
+/// Talk about a chat hands-free: final transcriptions are sent as turns and
+/// replies are spoken. Starts STT with default settings if it is not running.
+#[tauri::command]
+pub async fn start_voice_conversation(
+    app: AppHandle,
+    state: State<'_, SpeechState>,
+    chat_id: String,
+) -> Result<(), String> {
+    let lookup_id = chat_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        ops_squigit_brain::context::media::get_active_storage()?
+            .load_chat(&lookup_id)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let mut engine_guard = state.engine.lock().await;
+    let started_stt = engine_guard.is_none();
+    if started_stt {
+        start_engine(&app, &state, &mut engine_guard, None, None).await?;
+    }
+    conversation::start(&app, chat_id, started_stt);
+    Ok(())
+}
+
+/// End the voice conversation, and STT too if the conversation started it.
+#[tauri::command]
+pub async fn stop_voice_conversation(
+    app: AppHandle,
+    state: State<'_, SpeechState>,
+) -> Result<(), String> {
+    if conversation::stop(&app) {
+        stop_stt(state).await?;
+    }
+    Ok(())
+}
+
+/// Voice commands with their phrases and per-command enable flags.
+#[tauri::command]
+pub fn list_voice_commands(app: AppHandle) -> Vec<VoiceCommandInfo> {
+    voice_commands::list(&app)
+}
+
+#[tauri::command]
+pub fn get_voice_command_settings(app: AppHandle) -> VoiceCommandSettings {
+    voice_commands::settings(&app)
+}
+
+/// Save voice command settings. They apply the next time STT starts.
+#[tauri::command]
+pub fn set_voice_command_settings(
+    app: AppHandle,
+    settings: VoiceCommandSettings,
+) -> Result<VoiceCommandSettings, String> {
+    voice_commands::save_settings(&app, &settings)?;
+    Ok(settings)
+}
+
 const REQUIRED_STT_VERSION: &str = "1.2.0";
 
 fn check_stt_version(sidecar_path: &std::path::Path) -> Result<(), String> {
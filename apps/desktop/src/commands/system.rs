@@ -3,7 +3,16 @@
 
 //! System level commands for orchestrating sidecars and OS environment checks
 
+use crate::services::autostart::{self, AutostartStatus};
+use crate::services::battery::{BatteryState, PowerStatus};
+use crate::services::integration::{self, DesktopIntegrationStatus};
+use crate::services::metrics;
 use crate::services::ocr::DesktopOcrService;
+use crate::services::permissions::{self, PlatformPermission, PlatformPermissions};
+use crate::services::policy::{self, EffectivePolicy};
+use crate::services::shortcut::{GlobalShortcutState, GlobalShortcutStatus};
+use ops_profile_store::{AnonymizedMetrics, LocalMetrics};
+use sys_process_priority::PowerProfile;
 use tauri::Manager;
 
 #[tauri::command]
@@ -53,3 +62,160 @@ pub async fn get_linux_package_manager() -> Result<String, String> {
     }
     Ok("unknown".to_string())
 }
+
+/// Whether the native global shortcut is active. `errorCode` is
+/// `ERR_SHORTCUT_ALREADY_REGISTERED` when another app owns the combo.
+#[tauri::command]
+pub fn get_global_shortcut_status(
+    shortcut: tauri::State<'_, GlobalShortcutState>,
+) -> GlobalShortcutStatus {
+    shortcut.status()
+}
+
+/// Screen Recording, Microphone and Accessibility status. Everything is
+/// `not_applicable` outside macOS.
+#[tauri::command]
+pub fn check_platform_permissions() -> PlatformPermissions {
+    permissions::check_platform_permissions()
+}
+
+/// Deep-link into the System Settings pane for `permission`
+/// (`screen_recording`, `microphone` or `accessibility`). Changes are
+/// reported through `platform-permissions-changed`.
+#[tauri::command]
+pub fn open_permission_settings(app: tauri::AppHandle, permission: String) -> Result<(), String> {
+    let permission = PlatformPermission::parse(&permission)?;
+    permissions::open_permission_settings(&app, permission)
+}
+
+/// Login item status. A stale entry (moved binary, old arguments) is
+/// rewritten as part of the check; `repaired` reports that.
+#[tauri::command]
+pub async fn get_autostart_enabled(app: tauri::AppHandle) -> Result<AutostartStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || autostart::get_status(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn set_autostart_enabled(
+    app: tauri::AppHandle,
+    enabled: bool,
+    background: bool,
+) -> Result<AutostartStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || autostart::set_enabled(&app, enabled, background))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Rebind the Linux desktop shortcut (GNOME/KDE/XFCE) after the shortcut
+/// preference changes. No-op on other platforms, where the hotkey is
+/// registered natively.
+#[tauri::command]
+pub async fn update_linux_shortcut(app: tauri::AppHandle, trigger: String) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(move || {
+            crate::services::shortcut::update_linux_trigger(&app, &trigger)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (app, trigger);
+        Ok(())
+    }
+}
+
+/// Remove the desktop shortcut, autostart entry and launcher files before
+/// uninstalling. Returns the files removed; empty outside Linux.
+#[tauri::command]
+pub async fn remove_desktop_integration(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let config_dir = crate::utils::get_app_config_dir(&app);
+        tauri::async_runtime::spawn_blocking(move || {
+            crate::services::startup::remove_linux_integration(&config_dir)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = app;
+        Ok(Vec::new())
+    }
+}
+
+/// Which desktop backends have the capture shortcut configured, and where.
+/// `null` outside Linux.
+#[tauri::command]
+pub async fn get_shortcut_install_status() -> Result<serde_json::Value, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = tauri::async_runtime::spawn_blocking(|| {
+            sys_global_shortcut::linux_shortcut_install_status(crate::constants::APP_NAME)
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        serde_json::to_value(status).map_err(|e| e.to_string())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(serde_json::Value::Null)
+    }
+}
+
+/// Which tray backend is active, whether the capture shortcut can fire, and
+/// what the desktop is missing when either degraded.
+#[tauri::command]
+pub async fn get_desktop_integration_status(
+    app: tauri::AppHandle,
+) -> Result<DesktopIntegrationStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || integration::status(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_power_profile(app: tauri::AppHandle) -> PowerProfile {
+    crate::services::priority::power_profile(&app)
+}
+
+/// Save the performance vs battery preference. Sidecars pick it up the
+/// next time they start.
+#[tauri::command]
+pub fn set_power_profile(app: tauri::AppHandle, profile: PowerProfile) -> Result<(), String> {
+    crate::services::priority::save_power_profile(&app, profile)
+}
+
+#[tauri::command]
+pub fn get_power_status(battery: tauri::State<'_, BatteryState>) -> PowerStatus {
+    battery.status()
+}
+
+/// The admin policy in force, so the UI can hide or lock restricted
+/// settings.
+#[tauri::command]
+pub fn get_effective_policy() -> EffectivePolicy {
+    policy::current().clone()
+}
+
+/// Feature use and error counts kept on this device.
+#[tauri::command]
+pub async fn get_local_metrics() -> Result<LocalMetrics, String> {
+    metrics::load().await
+}
+
+/// The anonymized copy of the local metrics, for the user to review and
+/// share if they choose. Nothing is sent from here.
+#[tauri::command]
+pub async fn export_local_metrics(app: tauri::AppHandle) -> Result<AnonymizedMetrics, String> {
+    metrics::anonymized(&app).await
+}
+
+#[tauri::command]
+pub async fn reset_local_metrics() -> Result<(), String> {
+    metrics::reset().await
+}
@@ -7,5 +7,7 @@ pub const DEFAULT_THEME: &str = "system";
 pub const DEFAULT_PROMPT: &str = ops_squigit_brain::constants::DEFAULT_PROMPT;
 pub const PREFERENCES_FILE_NAME: &str = "preferences.json";
 pub const DEFAULT_CAPTURE_TYPE: &str = "rectangular";
+/// Show the capture overlay on every display.
+pub const DEFAULT_CAPTURE_DISPLAY: &str = "all";
 pub const DEFAULT_OCR_LANGUAGE: &str = "pp-ocr-v5-en";
 pub const DEFAULT_ACTIVE_ACCOUNT: &str = "Guest";
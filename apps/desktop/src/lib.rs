@@ -11,38 +11,73 @@ pub mod commands;
 pub mod constants;
 pub mod services;
 
+use commands::actions::{invoke_action, list_actions};
 use commands::audio::play_ui_sound;
 use commands::auth::{cache_avatar, cancel_google_auth, get_api_key, logout, start_google_auth};
 use commands::brain::{
-    cancel_request, compress_conversation, generate_chat_title, generate_image_brief,
-    quick_answer_request, stream_chat,
+    backfill_chat_titles, cancel_request, clear_caches, code_from_screenshot,
+    compress_conversation, extract_events, extract_structured, generate_chat_title,
+    generate_image_brief, get_response_languages, get_resumable_chats, list_available_models,
+    list_extraction_profiles, preview_chat, quick_answer_request, resume_generation,
+    save_events_ics, send_chat_preview, stop_title_backfill, stream_chat, translate_ocr_region,
+};
+use commands::capture::{
+    capture_active_window, capture_monitor, capture_with_delay, list_displays, list_monitors,
+    recapture_last_region, spawn_capture, spawn_capture_to_input,
 };
-use commands::capture::{spawn_capture, spawn_capture_to_input};
 use commands::chat::{
-    append_chat_message, create_chat, delete_chat, detect_image_tone, get_image_path,
-    get_imgbb_url, get_ocr_data, get_ocr_frame, init_ocr_frame, list_chats, load_chat,
-    overwrite_chat_messages, read_attachment_text, resolve_attachment_path, reveal_in_file_manager,
+    add_chat_attachment, append_chat_message, create_chat, delete_chat, delete_message,
+    detect_image_tone, edit_message, export_artifact, export_chat, export_chat_as_llm_json,
+    export_chat_to_vault, export_extractions_csv, export_gallery, find_similar_captures, fork_chat,
+    get_attachment_info, get_chat_analytics, get_image_path, get_imgbb_url, get_message_history,
+    get_ocr_data, get_ocr_frame, get_ocr_text, get_storage_stats, import_chat, init_ocr_frame,
+    list_artifacts, list_attachments, list_chats, list_recent_attachments, list_tags, load_chat,
+    merge_chats, move_chat_to_folder, overwrite_chat_messages, preview_retention,
+    read_artifact_text, read_attachment_text, remove_chat_attachment, resolve_attachment_path,
+    restore_trashed_chat, reveal_in_file_manager, run_retention, run_storage_gc, save_artifact,
     save_image_brief, save_image_tone, save_imgbb_url, save_ocr_data, search_chats,
-    store_file_from_path, store_image_bytes, store_image_from_path, update_chat_metadata,
+    set_chat_ocr_language, set_chat_tags, store_file_from_path, store_image_bytes,
+    store_image_from_path, sync_system_search_index, update_chat_metadata,
 };
 use commands::clipboard::{
-    copy_image_from_path_to_clipboard, copy_image_to_clipboard, read_clipboard_image,
-    read_clipboard_text,
+    copy_image_from_path_to_clipboard, copy_image_to_clipboard, copy_last_answer,
+    read_clipboard_image, read_clipboard_text,
 };
 use commands::constants::get_app_constants;
+use commands::hud::{is_hud_running, start_hud, stop_hud};
 use commands::image::{
     copy_image_to_path, get_initial_image, process_image_path, read_image_file,
     upload_image_to_imgbb,
 };
+use commands::local_api::{
+    get_local_api_status, regenerate_local_api_token, set_local_api_enabled,
+};
 use commands::models::{download_ocr_model, get_model_path, list_downloaded_models};
-use commands::ocr::{cancel_ocr_job, ocr_image};
+use commands::ocr::{
+    backfill_ocr, cancel_ocr_job, get_ocr_limits, grab_window_text, is_chat_ocr_pending,
+    ocr_formulas, ocr_image, set_ocr_limits, stop_ocr_backfill,
+};
+use commands::plugins::{get_plugins_dir, list_plugins, run_chat_plugins, set_plugin_enabled};
 use commands::profile::{
-    delete_profile, get_active_profile, get_active_profile_id, get_profile_count, has_profiles,
-    list_profiles, set_active_profile,
+    add_glossary_entry, delete_glossary_entry, delete_profile, encrypt_chat_storage,
+    get_active_profile, get_active_profile_id, get_chat_encryption_enabled, get_export_connectors,
+    get_llm_audit_enabled, get_model_fallbacks, get_profile_count, get_strip_image_metadata,
+    get_vertex_config, get_webhook_config, has_profiles, list_glossary, list_llm_audit,
+    list_profiles, purge_llm_audit, set_active_profile, set_export_connectors,
+    set_llm_audit_enabled, set_model_fallbacks, set_strip_image_metadata, set_vertex_config,
+    set_webhook_config, test_webhook, update_glossary_entry,
 };
 use commands::security::{check_file_exists, encrypt_and_save, has_agreed_flag, set_agreed_flag};
+use commands::session::{get_last_session, update_session_state};
 use commands::speech::SpeechState;
-use commands::system::{get_linux_package_manager, run_sidecar_version};
+use commands::system::{
+    check_platform_permissions, export_local_metrics, get_autostart_enabled,
+    get_desktop_integration_status, get_effective_policy, get_global_shortcut_status,
+    get_linux_package_manager, get_local_metrics, get_power_profile, get_power_status,
+    get_shortcut_install_status, open_permission_settings, remove_desktop_integration,
+    reset_local_metrics, run_sidecar_version, set_autostart_enabled, set_power_profile,
+    update_linux_shortcut,
+};
 use commands::window::{
     close_window, get_always_on_top, maximize_window, minimize_window, open_external_url,
     reload_window, set_always_on_top, set_background_color, show_window,
@@ -52,20 +87,63 @@ use state::AppState;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let startup = services::startup::StartupState::new();
+
     #[cfg(target_os = "linux")]
     std::env::set_var("GDK_BACKEND", "x11");
 
+    #[cfg(target_os = "linux")]
+    if std::env::args().any(|arg| arg == services::shortcut::REMOVE_SHORTCUT_ARG) {
+        if let Err(e) = services::shortcut::remove_linux_binding() {
+            eprintln!("Failed to remove desktop shortcut: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    if std::env::args().any(|arg| arg == services::startup::REMOVE_INTEGRATION_ARG) {
+        let config_dir = dirs::config_dir()
+            .unwrap_or_default()
+            .join(crate::constants::APP_NAME.to_lowercase());
+        match services::startup::remove_linux_integration(&config_dir) {
+            Ok(removed) => removed.iter().for_each(|file| println!("Removed {}", file)),
+            Err(e) => {
+                eprintln!("Failed to remove desktop integration: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let context = tauri::generate_context!();
+
+    if let Some(args) = services::batch::subcommand_args() {
+        std::process::exit(services::batch::run_cli(&args, context));
+    }
+
     Builder::default()
         .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            // Deep links are forwarded to the deep-link plugin, which decides
+            // whether the window is needed.
             let wants_background =
-                crate::utils::args_request_background(args.iter().map(String::as_str));
-            if !wants_background {
+                crate::utils::args_request_background(args.iter().map(String::as_str))
+                    || args
+                        .iter()
+                        .any(|arg| arg == services::autostart::AUTOSTART_ARG)
+                    || args
+                        .iter()
+                        .any(|arg| services::deep_link::is_deep_link(arg));
+            if let Some(path) = crate::utils::cli_image_arg(args.iter().skip(1)) {
+                services::image::open_external_image(app, path);
+            } else if !wants_background {
                 services::tray::show_window(app);
             }
         }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
-            Some(vec!["--background"]),
+            Some(vec![services::autostart::AUTOSTART_ARG]),
         ))
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
@@ -73,11 +151,28 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(AppState::new())
         .manage(services::brain::DesktopBrainService::new())
         .manage(services::audio::UiSoundPlayer::new())
         .manage(SpeechState::default())
+        .manage(services::hud::HudState::default())
+        .manage(services::shortcut::GlobalShortcutState::default())
+        .manage(services::idle::IdleGovernorState::default())
+        .manage(services::power::PowerEventsState::default())
+        .manage(services::integration::DesktopIntegrationState::default())
+        .manage(services::session::SessionState::default())
+        .manage(services::recovery::RecoveryState::default())
+        .manage(services::conversation::ConversationState::default())
+        .manage(services::realtime::RealtimeState::default())
+        .manage(services::battery::BatteryState::default())
+        .manage(services::memory::MemoryPressureState::default())
+        .manage(services::retention::RetentionState::default())
+        .manage(services::plugins::PluginRegistry::default())
+        .manage(services::local_api::LocalApiState::default())
+        .manage(startup)
         .manage(services::ocr::DesktopOcrService::new().expect("Failed to init OCR service"))
+        .manage(services::ocr_backfill::OcrBackfillState::default())
         .invoke_handler(tauri::generate_handler![
             // Image processing
             process_image_path,
@@ -89,6 +184,7 @@ pub fn run() {
             read_clipboard_text,
             copy_image_to_clipboard,
             copy_image_from_path_to_clipboard,
+            copy_last_answer,
             // Security
             encrypt_and_save,
             check_file_exists,
@@ -102,11 +198,26 @@ pub fn run() {
             cache_avatar,
             // Brain
             stream_chat,
+            preview_chat,
+            send_chat_preview,
             generate_chat_title,
             generate_image_brief,
+            translate_ocr_region,
+            extract_events,
+            save_events_ics,
+            list_extraction_profiles,
+            extract_structured,
+            code_from_screenshot,
             compress_conversation,
             cancel_request,
             quick_answer_request,
+            clear_caches,
+            backfill_chat_titles,
+            stop_title_backfill,
+            get_resumable_chats,
+            resume_generation,
+            get_response_languages,
+            list_available_models,
             // Window
             open_external_url,
             set_background_color,
@@ -122,9 +233,41 @@ pub fn run() {
             get_app_constants,
             // OCR
             ocr_image,
+            ocr_formulas,
+            grab_window_text,
             cancel_ocr_job,
+            is_chat_ocr_pending,
+            backfill_ocr,
+            stop_ocr_backfill,
+            get_ocr_limits,
+            set_ocr_limits,
+            // Local API
+            get_local_api_status,
+            set_local_api_enabled,
+            regenerate_local_api_token,
+            // Plugins
+            list_plugins,
+            set_plugin_enabled,
+            get_plugins_dir,
+            run_chat_plugins,
             run_sidecar_version,
             get_linux_package_manager,
+            get_global_shortcut_status,
+            check_platform_permissions,
+            get_effective_policy,
+            get_local_metrics,
+            export_local_metrics,
+            reset_local_metrics,
+            open_permission_settings,
+            get_autostart_enabled,
+            set_autostart_enabled,
+            update_linux_shortcut,
+            get_shortcut_install_status,
+            remove_desktop_integration,
+            get_desktop_integration_status,
+            get_power_profile,
+            set_power_profile,
+            get_power_status,
             // Model Management
             download_ocr_model,
             commands::models::cancel_download_ocr_model,
@@ -134,6 +277,9 @@ pub fn run() {
             store_image_bytes,
             store_image_from_path,
             store_file_from_path,
+            list_attachments,
+            list_recent_attachments,
+            get_attachment_info,
             get_image_path,
             resolve_attachment_path,
             detect_image_tone,
@@ -141,17 +287,47 @@ pub fn run() {
             reveal_in_file_manager,
             // Chat Storage
             create_chat,
+            fork_chat,
+            merge_chats,
             load_chat,
             list_chats,
+            get_chat_analytics,
+            preview_retention,
+            run_retention,
+            run_storage_gc,
+            get_storage_stats,
+            restore_trashed_chat,
             search_chats,
+            find_similar_captures,
+            sync_system_search_index,
+            export_chat,
+            export_gallery,
+            export_chat_as_llm_json,
+            export_chat_to_vault,
+            import_chat,
+            export_extractions_csv,
+            save_artifact,
+            list_artifacts,
+            read_artifact_text,
+            export_artifact,
             delete_chat,
             update_chat_metadata,
+            set_chat_ocr_language,
+            add_chat_attachment,
+            remove_chat_attachment,
+            set_chat_tags,
+            list_tags,
+            move_chat_to_folder,
             append_chat_message,
             overwrite_chat_messages,
+            edit_message,
+            delete_message,
+            get_message_history,
             commands::chat::validate_text_file,
             // OCR Storage
             save_ocr_data,
             get_ocr_data,
+            get_ocr_text,
             get_ocr_frame,
             init_ocr_frame,
             // ImgBB Storage
@@ -172,158 +348,79 @@ pub fn run() {
             delete_profile,
             has_profiles,
             get_profile_count,
+            list_glossary,
+            add_glossary_entry,
+            update_glossary_entry,
+            delete_glossary_entry,
+            get_model_fallbacks,
+            set_model_fallbacks,
+            get_llm_audit_enabled,
+            set_llm_audit_enabled,
+            list_llm_audit,
+            purge_llm_audit,
+            get_strip_image_metadata,
+            set_strip_image_metadata,
+            get_webhook_config,
+            set_webhook_config,
+            test_webhook,
+            get_vertex_config,
+            set_vertex_config,
+            get_chat_encryption_enabled,
+            encrypt_chat_storage,
+            get_export_connectors,
+            set_export_connectors,
             // Theme
             commands::theme::get_system_theme,
             // Speech
             commands::speech::start_stt,
             commands::speech::stop_stt,
+            commands::speech::start_voice_conversation,
+            commands::speech::stop_voice_conversation,
+            commands::realtime::start_realtime_session,
+            commands::realtime::stop_realtime_session,
+            commands::speech::list_voice_commands,
+            commands::speech::get_voice_command_settings,
+            commands::speech::set_voice_command_settings,
             // Capture
             spawn_capture,
             spawn_capture_to_input,
+            capture_with_delay,
+            recapture_last_region,
+            list_displays,
+            capture_monitor,
+            list_monitors,
+            capture_active_window,
+            // HUD
+            start_hud,
+            stop_hud,
+            is_hud_running,
+            // Actions
+            list_actions,
+            invoke_action,
+            // Session
+            get_last_session,
+            update_session_state,
         ])
         .setup(move |app| {
             let handle = app.handle().clone();
-            let ocr_service = app.state::<services::ocr::DesktopOcrService>();
-            ocr_service.start_monitor();
-
-            let start_in_background = crate::utils::launched_in_background();
-            let launch_args: Vec<String> = std::env::args().skip(1).collect();
-            let has_cli_image = launch_args.iter().find(|arg| !arg.starts_with('-'));
+            let startup = app.state::<services::startup::StartupState>();
+            startup.phase("plugins");
+            services::llm::use_gemini_endpoint_preferences(&handle);
+            // Left behind by a crash or by another profile's session.
+            if let Err(e) = ops_chat_storage::clear_all_decrypted_copies() {
+                log::warn!("Failed to clear decrypted copies: {}", e);
+            }
+            app.state::<services::session::SessionState>().load(&handle);
 
-            if let Some(path) = has_cli_image {
+            let start_in_background = crate::utils::launched_in_background()
+                || (crate::utils::launched_from_autostart()
+                    && services::autostart::start_in_background(&handle));
+            if let Some(path) = crate::utils::cli_image_arg(std::env::args().skip(1)) {
                 println!("CLI Image argument detected: {}", path);
                 let state = handle.state::<AppState>();
-                let _ = process_and_store_image(path.clone(), &state);
-            }
-
-            #[cfg(target_os = "linux")]
-            {
-                let app_local_data = handle.path().app_local_data_dir().expect("Failed to get local data dir");
-                let target_sidecar_dir = app_local_data.join("qt-capture-runtime");
-                let config_dir = crate::utils::get_app_config_dir(&handle);
-                let capture_installed_marker = config_dir.join(".capture_installed");
-
-                if !capture_installed_marker.exists() && !target_sidecar_dir.exists() {
-                    log::info!("First launch: Extracting Qt capture sidecar...");
-                    std::fs::create_dir_all(&target_sidecar_dir).unwrap();
-
-                    if let Ok(resource_dir) = handle.path().resource_dir() {
-                        let tar_path = resource_dir
-                            .join("binaries")
-                            .join("qt-capture-x86_64-unknown-linux-gnu")
-                            .join("runtime.tar.gz");
-
-                        if tar_path.exists() {
-                            use std::fs::File;
-                            use flate2::read::GzDecoder;
-                            use tar::Archive;
-
-                            let tar_gz = File::open(&tar_path).expect("Failed to open sidecar tarball");
-                            let tar = GzDecoder::new(tar_gz);
-                            let mut archive = Archive::new(tar);
-                            if let Err(e) = archive.unpack(&target_sidecar_dir) {
-                                log::error!("Failed to unpack sidecar: {}", e);
-                            } else {
-                                // Ensure the binary is executable
-                                let bin_path = target_sidecar_dir.join("_internal/usr/bin/capture-bin");
-                                if bin_path.exists() {
-                                    use std::os::unix::fs::PermissionsExt;
-                                    let mut perms = std::fs::metadata(&bin_path).unwrap().permissions();
-                                    perms.set_mode(0o755);
-                                    std::fs::set_permissions(&bin_path, perms).unwrap();
-                                }
-                                let _ = std::fs::write(&capture_installed_marker, "1");
-                            }
-                        } else {
-                            log::error!("Sidecar tarball not found at {}", tar_path.display());
-                        }
-                    }
-                }
-
-                if let (Ok(appimage_path), Ok(_appdir_path)) = (std::env::var("APPIMAGE"), std::env::var("APPDIR")) {
-                    let appimage_path = std::path::PathBuf::from(appimage_path);
-                    if let Some(home_dir) = dirs::home_dir() {
-                        let applications_dir = home_dir.join("Applications");
-
-                        if !appimage_path.starts_with(&applications_dir) && !appimage_path.starts_with("/usr") && !appimage_path.starts_with("/opt") {
-                            log::info!("AppImage running from temporary location: {}. Migrating...", appimage_path.display());
-
-                            let _ = std::fs::create_dir_all(&applications_dir);
-                            let target_appimage = applications_dir.join("Squigit.AppImage");
-
-                            if std::fs::rename(&appimage_path, &target_appimage).is_err()
-                                && std::fs::copy(&appimage_path, &target_appimage).is_ok()
-                            {
-                                let _ = std::fs::remove_file(&appimage_path);
-                            }
-
-                            let target_icon_dir = home_dir.join(".local/share/icons/hicolor/512x512/apps");
-                            let _ = std::fs::create_dir_all(&target_icon_dir);
-                            let target_icon = target_icon_dir.join("squigit.png");
-
-                            let _ = std::fs::write(&target_icon, include_bytes!("../icons/icon.png"));
-
-                            let target_desktop_dir = home_dir.join(".local/share/applications");
-                            let _ = std::fs::create_dir_all(&target_desktop_dir);
-                            let target_desktop = target_desktop_dir.join("squigit.desktop");
-                            let desktop_content = format!(
-r#"[Desktop Entry]
-Name=Squigit
-Comment=AI and contextual analysis module for screenshot data
-Exec="{}" %u
-Icon=squigit
-Terminal=false
-Type=Application
-Categories=Utility;"#,
-                                target_appimage.display()
-                            );
-                            let _ = std::fs::write(&target_desktop, desktop_content);
-
-                            let _ = std::process::Command::new("update-desktop-database")
-                                .arg(home_dir.join(".local/share/applications"))
-                                .status();
-
-                            log::info!("AppImage permanently installed to ~/Applications.");
-                        }
-                    }
-                }
-
-                let marker_file = config_dir.join(".shortcut_installed");
-                const SHORTCUT_MARKER_VERSION: &str = "2";
-
-                let installed_version = std::fs::read_to_string(&marker_file)
-                    .ok()
-                    .map(|s| s.trim().to_string())
-                    .unwrap_or_default();
-
-                if installed_version != SHORTCUT_MARKER_VERSION {
-                    log::info!(
-                        "Linux shortcut install/migration required: target marker version {}, current '{}'",
-                        SHORTCUT_MARKER_VERSION,
-                        installed_version
-                    );
-                    if let Ok(exe) = std::env::current_exe() {
-                        let bin = exe.to_string_lossy();
-                        match sys_global_shortcut::install_linux_shortcut(
-                            &bin,
-                            "SUPER+SHIFT+a",
-                            crate::constants::APP_NAME,
-                        ) {
-                            Ok(_) => {
-                                log::info!("Successfully installed Linux global shortcut");
-                                if let Err(e) =
-                                    std::fs::write(&marker_file, SHORTCUT_MARKER_VERSION)
-                                {
-                                    log::error!("Failed to create shortcut marker file: {}", e);
-                                }
-                            }
-                            Err(e) => {
-                                log::error!("Failed to install Linux global shortcut: {}", e);
-                            }
-                        }
-                    }
-                }
+                let _ = process_and_store_image(path, &state);
             }
+            startup.phase("session");
 
             let (base_w, base_h) = (1030.0, 690.0);
 
@@ -341,6 +438,7 @@ Categories=Utility;"#,
                     "ocrEnabled": true,
                     "autoExpandOCR": true,
                     "captureType": crate::constants::DEFAULT_CAPTURE_TYPE,
+                    "captureDisplay": crate::constants::DEFAULT_CAPTURE_DISPLAY,
                     "ocrLanguage": crate::constants::DEFAULT_OCR_LANGUAGE,
                     "activeAccount": crate::constants::DEFAULT_ACTIVE_ACCOUNT
                 });
@@ -354,40 +452,51 @@ Categories=Utility;"#,
                     log::error!("Failed to create default preferences.json: {}", e);
                 }
             }
+            startup.phase("preferences");
 
+            handle
+                .state::<services::integration::DesktopIntegrationState>()
+                .detect();
             services::tray::setup_tray(&handle).expect("Failed to setup tray icon");
-
-            services::window::spawn_app_window(
-                &handle,
-                "main",
-                "index.html",
-                base_w,
-                base_h,
-                "",
-                !start_in_background,
-            )
-            .expect("Failed to spawn main window");
-
-            let shortcut_handle = handle.clone();
-            let _shortcut = sys_global_shortcut::ShortcutHandle::register(
-                sys_global_shortcut::ShortcutConfig {
-                    linux_trigger: "SUPER+SHIFT+a".into(),
-                    linux_description: format!("{} Capture", crate::constants::APP_NAME),
-                    windows_modifiers: 0x0008 | 0x0004, // MOD_WIN | MOD_SHIFT
-                    windows_vk: 0x41,                   // VK_A
-                    macos_modifiers: 0x0100 | 0x0200,   // cmdKey | shiftKey
-                    macos_keycode: 0x00,                // kVK_ANSI_A
-                },
-                move || services::tray::capture_screen_with_source(&shortcut_handle, "hotkey"),
-            );
-
-            match &_shortcut {
-                Ok(_) => log::info!("Global shortcut registered successfully"),
-                Err(e) => log::warn!("Global shortcut registration failed (non-fatal): {}", e),
+            #[cfg(target_os = "windows")]
+            services::windows_shell::setup(&handle);
+            #[cfg(target_os = "macos")]
+            services::macos_services::register(&handle);
+            #[cfg(target_os = "linux")]
+            services::gnome_search::start(&handle);
+            startup.phase("tray");
+
+            // In the background the window is created when first shown.
+            if !start_in_background {
+                services::window::spawn_app_window(
+                    &handle,
+                    "main",
+                    "index.html",
+                    base_w,
+                    base_h,
+                    "",
+                    true,
+                )
+                .expect("Failed to spawn main window");
             }
+            services::session::set_window_visible(&handle, !start_in_background);
+            startup.phase("window");
+
+            services::deep_link::start(&handle);
+            services::startup::schedule_deferred(&handle, start_in_background);
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                app.state::<services::session::SessionState>()
+                    .mark_clean_exit();
+                // Decrypted attachment copies must not outlive the session.
+                if let Ok(storage) = ops_squigit_brain::context::media::get_active_storage() {
+                    let _ = storage.clear_decrypted_copies();
+                }
+            }
+        });
 }
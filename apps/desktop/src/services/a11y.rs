@@ -0,0 +1,115 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Screen reader announcements for backend state changes.
+//!
+//! Subsystems report changes the user cannot see happen (a capture
+//! starting, a reply starting to stream, OCR finishing, a download
+//! reaching a milestone) as `a11y-announce` events. The frontend routes
+//! them to a polite or assertive ARIA live region by `politeness`, so
+//! every subsystem is read out the same way.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+pub const A11Y_ANNOUNCE_EVENT: &str = "a11y-announce";
+
+/// Download progress is announced at these percentages.
+const DOWNLOAD_MILESTONES: [u8; 3] = [25, 50, 75];
+/// Error of a download cancelled with `cancel_download_ocr_model`.
+const DOWNLOAD_CANCELLED: &str = "Download cancelled";
+
+/// How urgently the screen reader should speak.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Politeness {
+    /// Waits until the reader is idle.
+    Polite,
+    /// Interrupts; reserved for failures and things that need action.
+    Assertive,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Announcement<'a> {
+    /// Subsystem that announced, e.g. `capture`, for filtering.
+    source: &'a str,
+    message: &'a str,
+    politeness: Politeness,
+}
+
+pub fn announce(app: &AppHandle, source: &str, politeness: Politeness, message: &str) {
+    let _ = app.emit(
+        A11Y_ANNOUNCE_EVENT,
+        Announcement {
+            source,
+            message,
+            politeness,
+        },
+    );
+}
+
+pub fn polite(app: &AppHandle, source: &str, message: &str) {
+    announce(app, source, Politeness::Polite, message);
+}
+
+pub fn assertive(app: &AppHandle, source: &str, message: &str) {
+    announce(app, source, Politeness::Assertive, message);
+}
+
+/// Turns a model download's progress reports into a few announcements
+/// instead of one per chunk.
+pub struct DownloadAnnouncer {
+    app: AppHandle,
+    last_milestone: u8,
+    extracting: bool,
+}
+
+impl DownloadAnnouncer {
+    pub fn new(app: &AppHandle) -> Self {
+        Self {
+            app: app.clone(),
+            last_milestone: 0,
+            extracting: false,
+        }
+    }
+
+    pub fn progress(&mut self, status: &str, progress: u8) {
+        match status {
+            "downloading" => {
+                let Some(milestone) = DOWNLOAD_MILESTONES
+                    .into_iter()
+                    .filter(|milestone| progress >= *milestone)
+                    .max()
+                else {
+                    return;
+                };
+                if milestone > self.last_milestone {
+                    self.last_milestone = milestone;
+                    polite(
+                        &self.app,
+                        "download",
+                        &format!("Download {}% complete", milestone),
+                    );
+                }
+            }
+            "extracting" if !self.extracting => {
+                self.extracting = true;
+                polite(&self.app, "download", "Download complete, installing");
+            }
+            _ => {}
+        }
+    }
+
+    /// A cancellation was asked for by the user, so only a real failure
+    /// interrupts.
+    pub fn finished(&self, result: &Result<impl Sized, String>) {
+        match result {
+            Ok(_) => polite(&self.app, "download", "Download installed"),
+            Err(e) if e == DOWNLOAD_CANCELLED => {
+                polite(&self.app, "download", "Download cancelled")
+            }
+            Err(_) => assertive(&self.app, "download", "Download failed"),
+        }
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Registry of every user-invokable action.
+//!
+//! The command palette, tray and external integrations all list and invoke
+//! actions through here, so IDs, names and shortcut bindings have one source
+//! of truth. Actions that only the renderer can perform (navigation, chat
+//! state) are forwarded to it through `action-invoked`; every successful
+//! invocation emits that event so the UI can follow along.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const ACTION_INVOKED_EVENT: &str = "action-invoked";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionCategory {
+    Capture,
+    Chat,
+    Ocr,
+    Profile,
+    Window,
+    App,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub category: ActionCategory,
+    /// Accelerator in the renderer's notation (`Mod` = Cmd on macOS, Ctrl
+    /// elsewhere), or the desktop trigger for global shortcuts.
+    pub shortcut: Option<String>,
+    /// The shortcut works while the app is unfocused.
+    pub global: bool,
+    /// Name of the required argument, if any.
+    pub argument: Option<&'static str>,
+}
+
+type NativeHandler = fn(&AppHandle, Option<&str>) -> Result<(), String>;
+
+enum Handler {
+    Native(NativeHandler),
+    /// Performed by the renderer; the main window is shown first.
+    Renderer,
+}
+
+struct ActionDef {
+    id: &'static str,
+    name: &'static str,
+    category: ActionCategory,
+    shortcut: Option<&'static str>,
+    argument: Option<&'static str>,
+    handler: Handler,
+}
+
+const ACTIONS: &[ActionDef] = &[
+    ActionDef {
+        id: "capture.screen",
+        name: "Capture Screen",
+        category: ActionCategory::Capture,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Native(|app, _| {
+            crate::services::tray::capture_screen_with_source(app, "action");
+            Ok(())
+        }),
+    },
+    ActionDef {
+        id: "capture.to_input",
+        name: "Capture Into Chat Input",
+        category: ActionCategory::Capture,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Native(|app, _| {
+            crate::services::capture::spawn_capture_to_input(app);
+            Ok(())
+        }),
+    },
+    ActionDef {
+        id: "capture.recapture_last_region",
+        name: "Re-capture Last Region",
+        category: ActionCategory::Capture,
+        shortcut: Some("Mod+Shift+R"),
+        argument: None,
+        handler: Handler::Native(|app, _| crate::services::capture::recapture_last_region(app)),
+    },
+    ActionDef {
+        id: "chat.new",
+        name: "New Chat",
+        category: ActionCategory::Chat,
+        shortcut: Some("Mod+Shift+O"),
+        argument: None,
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "chat.ask",
+        name: "Ask About Text",
+        category: ActionCategory::Chat,
+        shortcut: None,
+        argument: Some("text"),
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "chat.open",
+        name: "Open Chat",
+        category: ActionCategory::Chat,
+        shortcut: None,
+        argument: Some("chatId"),
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "chat.read_back",
+        name: "Read Last Reply Aloud",
+        category: ActionCategory::Chat,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "chat.copy_last_answer",
+        name: "Copy Last Answer",
+        category: ActionCategory::Chat,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Native(|app, _| {
+            crate::services::clipboard::copy_last_answer(app).map(|_| ())
+        }),
+    },
+    ActionDef {
+        id: "chat.extract_events",
+        name: "Extract Calendar Events",
+        category: ActionCategory::Chat,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "chat.code_from_screenshot",
+        name: "Copy Code from Screenshot",
+        category: ActionCategory::Chat,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "chat.search",
+        name: "Search Chats",
+        category: ActionCategory::Chat,
+        shortcut: Some("Mod+K"),
+        argument: None,
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "ocr.toggle",
+        name: "Toggle OCR",
+        category: ActionCategory::Ocr,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "profile.switch",
+        name: "Switch Profile",
+        category: ActionCategory::Profile,
+        shortcut: None,
+        argument: Some("profileId"),
+        handler: Handler::Native(|_, profile_id| {
+            let profile_id = profile_id.ok_or("ERR_MISSING_ACTION_ARGUMENT")?;
+            let store = ops_profile_store::ProfileStore::new().map_err(|e| e.to_string())?;
+            store
+                .set_active_profile_id(profile_id)
+                .map_err(|e| e.to_string())
+        }),
+    },
+    ActionDef {
+        id: "settings.open",
+        name: "Open Settings",
+        category: ActionCategory::App,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Renderer,
+    },
+    ActionDef {
+        id: "window.show",
+        name: "Show Window",
+        category: ActionCategory::Window,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Native(|app, _| {
+            crate::services::tray::show_window(app);
+            Ok(())
+        }),
+    },
+    ActionDef {
+        id: "window.toggle",
+        name: "Toggle Window",
+        category: ActionCategory::Window,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Native(|app, _| {
+            crate::services::tray::toggle_window(app);
+            Ok(())
+        }),
+    },
+    ActionDef {
+        id: "app.quit",
+        name: "Quit",
+        category: ActionCategory::App,
+        shortcut: None,
+        argument: None,
+        handler: Handler::Native(|app, _| {
+            app.exit(0);
+            Ok(())
+        }),
+    },
+];
+
+pub fn list(app: &AppHandle) -> Vec<ActionInfo> {
+    ACTIONS
+        .iter()
+        .map(|action| {
+            let global = global_shortcut(app, action.id);
+            ActionInfo {
+                id: action.id,
+                name: action.name,
+                category: action.category,
+                global: global.is_some(),
+                shortcut: global.or_else(|| action.shortcut.map(str::to_string)),
+                argument: action.argument,
+            }
+        })
+        .collect()
+}
+
+pub fn invoke(app: &AppHandle, id: &str, argument: Option<&str>) -> Result<(), String> {
+    let action = ACTIONS
+        .iter()
+        .find(|action| action.id == id)
+        .ok_or_else(|| format!("ERR_UNKNOWN_ACTION: {}", id))?;
+
+    let handled = match action.handler {
+        Handler::Native(run) => {
+            run(app, argument)?;
+            true
+        }
+        Handler::Renderer => {
+            crate::services::tray::show_window(app);
+            false
+        }
+    };
+
+    log::info!("Action invoked: {} (handled natively: {})", id, handled);
+    let _ = app.emit(
+        ACTION_INVOKED_EVENT,
+        serde_json::json!({
+            "id": action.id,
+            "argument": argument,
+            "handled": handled,
+        }),
+    );
+    Ok(())
+}
+
+/// Binding of the OS-level hotkey for an action: the capture hotkey, or a
+/// registered action shortcut.
+fn global_shortcut(app: &AppHandle, id: &str) -> Option<String> {
+    use crate::services::shortcut::{GlobalShortcutState, ACTION_SHORTCUTS};
+
+    let keys = if id == "capture.screen" {
+        if cfg!(target_os = "linux") {
+            return Some(crate::services::shortcut::linux_trigger(app));
+        }
+        "Shift+A"
+    } else {
+        if !app.state::<GlobalShortcutState>().has_action(id) {
+            return None;
+        }
+        ACTION_SHORTCUTS
+            .iter()
+            .find(|shortcut| shortcut.action == id)?
+            .keys
+    };
+    if cfg!(target_os = "macos") {
+        Some(format!("Mod+{}", keys))
+    } else {
+        Some(format!("Meta+{}", keys))
+    }
+}
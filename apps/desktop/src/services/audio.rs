@@ -2,18 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    io::Cursor,
+    fs::File,
+    io::{BufReader, Cursor},
+    path::PathBuf,
     sync::mpsc::{self, Receiver, Sender},
 };
 
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use rodio::{buffer::SamplesBuffer, Decoder, OutputStream, OutputStreamHandle, Sink};
 
 const DIALOG_WARNING_SOUND: &[u8] =
     include_bytes!("../../renderer/src/assets/sounds/dialog-warning.mp3");
 
-#[derive(Debug, Clone, Copy)]
+const SHUTTER_SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Debug, Clone)]
 pub enum UiSoundEffect {
     DialogWarning,
+    /// Camera shutter played after a capture.
+    CaptureShutter,
+    /// A user-chosen audio file.
+    File(PathBuf),
 }
 
 impl UiSoundEffect {
@@ -25,6 +33,7 @@ impl UiSoundEffect {
             .as_str()
         {
             "dialog-warning" | "dialog_warning" | "warning" => Ok(Self::DialogWarning),
+            "capture-shutter" | "capture_shutter" | "shutter" => Ok(Self::CaptureShutter),
             other => Err(format!("Unsupported ui sound effect: {}", other)),
         }
     }
@@ -109,20 +118,54 @@ impl AudioBackend {
             return;
         };
 
-        let source_bytes = match effect {
-            UiSoundEffect::DialogWarning => DIALOG_WARNING_SOUND,
-        };
-
-        let cursor = Cursor::new(source_bytes);
-        let Ok(decoder) = Decoder::new(cursor) else {
-            log::warn!("Failed to decode ui sound payload");
-            return;
-        };
         let Ok(sink) = Sink::try_new(handle) else {
             log::warn!("Failed to initialize ui sound sink");
             return;
         };
-        sink.append(decoder);
+        match effect {
+            UiSoundEffect::DialogWarning => {
+                let Ok(decoder) = Decoder::new(Cursor::new(DIALOG_WARNING_SOUND)) else {
+                    log::warn!("Failed to decode ui sound payload");
+                    return;
+                };
+                sink.append(decoder);
+            }
+            UiSoundEffect::CaptureShutter => sink.append(shutter_sound()),
+            UiSoundEffect::File(path) => {
+                let decoder = File::open(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|file| Decoder::new(BufReader::new(file)).map_err(|e| e.to_string()));
+                match decoder {
+                    Ok(decoder) => sink.append(decoder),
+                    Err(e) => {
+                        log::warn!("Failed to play sound file {}: {}", path.display(), e);
+                        return;
+                    }
+                }
+            }
+        }
         self.sinks.push(sink);
     }
 }
+
+/// Two short bursts of decaying noise, the curtain opening then closing.
+fn shutter_sound() -> SamplesBuffer<f32> {
+    let rate = SHUTTER_SAMPLE_RATE as usize;
+    let burst = rate * 35 / 1000;
+    let gap = rate * 45 / 1000;
+    let mut seed: u32 = 0x9e37_79b9;
+    let mut samples = Vec::with_capacity(burst * 2 + gap);
+    for (start, gain) in [(0, 0.5_f32), (burst + gap, 0.35)] {
+        samples.resize(start, 0.0);
+        for i in 0..burst {
+            // xorshift32, good enough for noise.
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let noise = seed as f32 / u32::MAX as f32 * 2.0 - 1.0;
+            let decay = (-(i as f32) / (burst as f32 / 5.0)).exp();
+            samples.push(noise * decay * gain);
+        }
+    }
+    SamplesBuffer::new(1, SHUTTER_SAMPLE_RATE, samples)
+}
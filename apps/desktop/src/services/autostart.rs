@@ -0,0 +1,166 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Login item management on top of `tauri_plugin_autostart`.
+//!
+//! The plugin only reports whether an entry exists, not whether it still
+//! launches this install. After an update moves the binary (AppImage renames,
+//! a new install location) the entry points at a dead path, so the status
+//! check also reads the entry itself (XDG autostart file, LaunchAgent plist,
+//! or `Run` registry value) and rewrites it when it is stale.
+//!
+//! The entry always launches with `--autostart`; whether that start stays in
+//! the tray is the `autostartBackground` preference, so toggling it does not
+//! need to touch the entry.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_autostart::ManagerExt;
+
+/// Argument written into the login item.
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+const BACKGROUND_PREF: &str = "autostartBackground";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartStatus {
+    pub enabled: bool,
+    /// Start hidden in the tray when launched at login.
+    pub background: bool,
+    /// The entry exists and launches the running install.
+    pub healthy: bool,
+    /// The entry was stale and has been rewritten during this check.
+    pub repaired: bool,
+    /// Where the entry lives, for display.
+    pub location: Option<String>,
+}
+
+pub fn get_status(app: &AppHandle) -> Result<AutostartStatus, String> {
+    let manager = app.autolaunch();
+    let enabled = manager.is_enabled().map_err(|e| e.to_string())?;
+    let mut status = AutostartStatus {
+        enabled,
+        background: start_in_background(app),
+        healthy: !enabled,
+        repaired: false,
+        location: None,
+    };
+    if !enabled {
+        return Ok(status);
+    }
+
+    let entry = read_entry(app);
+    status.location = entry.as_ref().map(|(location, _)| location.clone());
+    status.healthy = entry
+        .as_ref()
+        .is_some_and(|(_, contents)| entry_launches_current_install(app, contents));
+    if status.healthy {
+        return Ok(status);
+    }
+
+    log::warn!("Autostart entry is missing or stale, rewriting it");
+    let _ = manager.disable();
+    manager.enable().map_err(|e| e.to_string())?;
+    let entry = read_entry(app);
+    status.location = entry.as_ref().map(|(location, _)| location.clone());
+    status.healthy = entry
+        .as_ref()
+        .is_some_and(|(_, contents)| entry_launches_current_install(app, contents));
+    status.repaired = status.healthy;
+    Ok(status)
+}
+
+pub fn set_enabled(
+    app: &AppHandle,
+    enabled: bool,
+    background: bool,
+) -> Result<AutostartStatus, String> {
+    write_background_preference(app, background)?;
+
+    let manager = app.autolaunch();
+    if enabled {
+        // Always rewrite so an old entry picks up the current path and args.
+        let _ = manager.disable();
+        manager.enable().map_err(|e| e.to_string())?;
+    } else if manager.is_enabled().map_err(|e| e.to_string())? {
+        manager.disable().map_err(|e| e.to_string())?;
+    }
+
+    let status = get_status(app)?;
+    if enabled && !status.healthy {
+        return Err("Autostart entry could not be created".to_string());
+    }
+    Ok(status)
+}
+
+/// Whether a login-item launch should stay in the tray. Defaults to `true`.
+pub fn start_in_background(app: &AppHandle) -> bool {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(BACKGROUND_PREF)?.as_bool())
+        .unwrap_or(true)
+}
+
+fn write_background_preference(app: &AppHandle, background: bool) -> Result<(), String> {
+    crate::utils::write_preference(app, BACKGROUND_PREF, serde_json::Value::Bool(background))
+}
+
+/// The path the plugin writes into the entry for this install.
+fn launch_path(app: &AppHandle) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    if let Some(appimage) = app.env().appimage {
+        return Some(appimage.to_string_lossy().into_owned());
+    }
+    let _ = app;
+
+    let exe = std::env::current_exe().ok()?;
+    #[cfg(target_os = "macos")]
+    let exe = exe.canonicalize().ok()?;
+    Some(exe.display().to_string())
+}
+
+fn entry_launches_current_install(app: &AppHandle, contents: &str) -> bool {
+    let Some(path) = launch_path(app) else {
+        return false;
+    };
+    contents.contains(&path)
+        && contents.contains(AUTOSTART_ARG)
+        && std::path::Path::new(&path).exists()
+}
+
+/// (location, contents) of the login item written by the plugin.
+fn read_entry(app: &AppHandle) -> Option<(String, String)> {
+    let app_name = &app.package_info().name;
+
+    #[cfg(target_os = "linux")]
+    {
+        let path = dirs::home_dir()?
+            .join(".config")
+            .join("autostart")
+            .join(format!("{}.desktop", app_name));
+        let contents = std::fs::read_to_string(&path).ok()?;
+        Some((path.display().to_string(), contents))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let path = dirs::home_dir()?
+            .join("Library")
+            .join("LaunchAgents")
+            .join(format!("{}.plist", app_name));
+        let contents = std::fs::read_to_string(&path).ok()?;
+        Some((path.display().to_string(), contents))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+
+        let key_path = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+        let contents: String = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(key_path)
+            .ok()?
+            .get_value(app_name)
+            .ok()?;
+        Some((format!("HKCU\\{}\\{}", key_path, app_name), contents))
+    }
+}
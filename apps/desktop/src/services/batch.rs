@@ -0,0 +1,428 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Headless batch analysis.
+//!
+//! `squigit batch --dir ./screens --prompt "summarize"` runs OCR and a single
+//! model call on every image in a folder and writes the results next to
+//! each other as JSON and/or markdown. Each image becomes a regular chat in
+//! the active profile, titled with its file name. The app is built without
+//! windows or the single-instance lock, so a batch can run while the UI is
+//! open.
+
+use crate::services::brain::DesktopBrainService;
+use crate::services::ocr::DesktopOcrService;
+use futures_util::StreamExt;
+use ops_squigit_brain::events::NoopEventSink;
+use ops_squigit_brain::provider::gemini::attachments::is_image_path;
+use ops_squigit_brain::service::AnalyzeImageRequest;
+use ops_squigit_ocr::ocr::boxes_to_storage_regions;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+pub const BATCH_SUBCOMMAND: &str = "batch";
+
+const DEFAULT_CONCURRENCY: usize = 2;
+const MAX_CONCURRENCY: usize = 8;
+/// Model calls started per minute.
+const DEFAULT_RATE_PER_MINUTE: u32 = 20;
+const DEFAULT_OUT_DIR_NAME: &str = "squigit-batch";
+const SUMMARY_FILE_NAME: &str = "batch.json";
+const OCR_LANGUAGE_PREF: &str = "ocrLanguage";
+
+const USAGE: &str = "\
+Usage: squigit batch --dir <folder> --prompt <text> [options]
+
+Options:
+  --out <folder>        Where to write results (default: <folder>/squigit-batch)
+  --format <format>     json, markdown or both (default: both)
+  --concurrency <n>     Images processed at once, 1-8 (default: 2)
+  --rate <n>            Model calls started per minute (default: 20)
+  --model <id>          Model to use (default: the one chosen in settings)
+  --ocr-model <id>      OCR model (default: the one chosen in settings)
+  --no-ocr              Skip OCR and send only the image";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchFormat {
+    Json,
+    Markdown,
+    Both,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub dir: PathBuf,
+    pub prompt: String,
+    pub out_dir: PathBuf,
+    pub format: BatchFormat,
+    pub concurrency: usize,
+    pub rate_per_minute: u32,
+    pub model: Option<String>,
+    pub ocr_model: Option<String>,
+    pub ocr: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItem {
+    pub file: String,
+    pub chat_id: Option<String>,
+    /// The model that answered.
+    pub model: Option<String>,
+    pub ocr_model: Option<String>,
+    pub ocr_text: Option<String>,
+    pub response: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Arguments after the `batch` subcommand, or `None` when the app was not
+/// started with it.
+pub fn subcommand_args() -> Option<Vec<String>> {
+    let mut args = std::env::args().skip(1);
+    (args.next().as_deref() == Some(BATCH_SUBCOMMAND)).then(|| args.collect())
+}
+
+pub fn parse_args(args: &[String]) -> Result<BatchOptions, String> {
+    let mut dir = None;
+    let mut prompt = None;
+    let mut out_dir = None;
+    let mut format = BatchFormat::Both;
+    let mut concurrency = DEFAULT_CONCURRENCY;
+    let mut rate_per_minute = DEFAULT_RATE_PER_MINUTE;
+    let mut model = None;
+    let mut ocr_model = None;
+    let mut ocr = true;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--dir" => dir = Some(PathBuf::from(value()?)),
+            "--prompt" => prompt = Some(value()?),
+            "--out" => out_dir = Some(PathBuf::from(value()?)),
+            "--format" => {
+                format = match value()?.as_str() {
+                    "json" => BatchFormat::Json,
+                    "markdown" | "md" => BatchFormat::Markdown,
+                    "both" => BatchFormat::Both,
+                    other => return Err(format!("Unknown format: {}", other)),
+                }
+            }
+            "--concurrency" => {
+                concurrency = value()?
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| (1..=MAX_CONCURRENCY).contains(n))
+                    .ok_or_else(|| {
+                        format!("--concurrency must be between 1 and {}", MAX_CONCURRENCY)
+                    })?
+            }
+            "--rate" => {
+                rate_per_minute = value()?
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| "--rate must be a positive number".to_string())?
+            }
+            "--model" => model = Some(value()?),
+            "--ocr-model" => ocr_model = Some(value()?),
+            "--no-ocr" => ocr = false,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    let dir = dir.ok_or_else(|| "Missing --dir".to_string())?;
+    let prompt = prompt
+        .filter(|prompt| !prompt.trim().is_empty())
+        .ok_or_else(|| "Missing --prompt".to_string())?;
+    Ok(BatchOptions {
+        out_dir: out_dir.unwrap_or_else(|| dir.join(DEFAULT_OUT_DIR_NAME)),
+        dir,
+        prompt,
+        format,
+        concurrency,
+        rate_per_minute,
+        model,
+        ocr_model,
+        ocr,
+    })
+}
+
+/// Run the `batch` subcommand and return the process exit code.
+pub fn run_cli(args: &[String], context: tauri::Context<tauri::Wry>) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    // Release builds use the GUI subsystem; write to the calling terminal.
+    #[cfg(target_os = "windows")]
+    unsafe {
+        winapi::um::wincon::AttachConsole(winapi::um::wincon::ATTACH_PARENT_PROCESS);
+    }
+
+    let ocr = match DesktopOcrService::new() {
+        Ok(ocr) => ocr,
+        Err(e) => {
+            eprintln!("Failed to init OCR service: {}", e);
+            return 1;
+        }
+    };
+    let app = match tauri::Builder::default()
+        .manage(DesktopBrainService::new())
+        .manage(ocr)
+        .manage(crate::services::battery::BatteryState::default())
+        .build(context)
+    {
+        Ok(app) => app,
+        Err(e) => {
+            eprintln!("Failed to start: {}", e);
+            return 1;
+        }
+    };
+    crate::services::llm::use_gemini_endpoint_preferences(app.handle());
+
+    match tauri::async_runtime::block_on(run(app.handle(), &options)) {
+        Ok(items) => {
+            let failed = items.iter().filter(|item| item.error.is_some()).count();
+            eprintln!(
+                "Analyzed {} of {} image(s), results in {}",
+                items.len() - failed,
+                items.len(),
+                options.out_dir.display()
+            );
+            i32::from(failed > 0)
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// Analyze every image in `options.dir`. Failures are recorded per image
+/// and do not stop the batch.
+pub async fn run(app: &AppHandle, options: &BatchOptions) -> Result<Vec<BatchItem>, String> {
+    let images = list_images(&options.dir)?;
+    if images.is_empty() {
+        return Err(format!("No images found in {}", options.dir.display()));
+    }
+    std::fs::create_dir_all(&options.out_dir).map_err(|e| {
+        format!(
+            "Failed to create output folder {}: {}",
+            options.out_dir.display(),
+            e
+        )
+    })?;
+
+    let credentials = crate::services::brain::resolve_credentials()?;
+    let model = options
+        .model
+        .clone()
+        .unwrap_or_else(|| crate::services::brain::preferred_model(app));
+    let ocr_model = options.ocr.then(|| {
+        options
+            .ocr_model
+            .clone()
+            .unwrap_or_else(|| ocr_language(app))
+    });
+    let limiter = RateLimiter::new(options.rate_per_minute);
+    let total = images.len();
+
+    let mut items = futures_util::stream::iter(images.into_iter().enumerate())
+        .map(|(index, path)| {
+            let job = ImageJob {
+                app,
+                options,
+                api_key: &credentials.api_key,
+                model: &model,
+                ocr_model: ocr_model.as_deref(),
+                limiter: &limiter,
+            };
+            async move {
+                let item = job.process(index, &path).await;
+                match &item.error {
+                    Some(e) => eprintln!("[{}/{}] {}: {}", index + 1, total, item.file, e),
+                    None => eprintln!("[{}/{}] {}", index + 1, total, item.file),
+                }
+                if let Err(e) = write_item(&options.out_dir, options.format, &item) {
+                    eprintln!("Failed to write results for {}: {}", item.file, e);
+                }
+                item
+            }
+        })
+        .buffer_unordered(options.concurrency)
+        .collect::<Vec<_>>()
+        .await;
+
+    items.sort_by(|a, b| a.file.cmp(&b.file));
+    let summary = serde_json::to_string_pretty(&items).map_err(|e| e.to_string())?;
+    std::fs::write(options.out_dir.join(SUMMARY_FILE_NAME), summary)
+        .map_err(|e| format!("Failed to write {}: {}", SUMMARY_FILE_NAME, e))?;
+    Ok(items)
+}
+
+struct ImageJob<'a> {
+    app: &'a AppHandle,
+    options: &'a BatchOptions,
+    api_key: &'a str,
+    model: &'a str,
+    ocr_model: Option<&'a str>,
+    limiter: &'a RateLimiter,
+}
+
+impl ImageJob<'_> {
+    async fn process(&self, index: usize, path: &Path) -> BatchItem {
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut item = BatchItem {
+            file: file.clone(),
+            chat_id: None,
+            model: None,
+            ocr_model: self.ocr_model.map(str::to_string),
+            ocr_text: None,
+            response: None,
+            error: None,
+        };
+
+        let ocr_regions = match self.ocr_model {
+            Some(ocr_model) => match self
+                .app
+                .state::<DesktopOcrService>()
+                .recognize(self.app, path.to_path_buf(), None, Some(ocr_model), None)
+                .await
+            {
+                Ok(boxes) => Some(boxes_to_storage_regions(&boxes)),
+                Err(e) => {
+                    item.error = Some(format!("OCR failed: {}", e));
+                    return item;
+                }
+            },
+            None => None,
+        };
+        item.ocr_text = ocr_regions.as_deref().map(|regions| {
+            ops_chat_storage::ocr_text(regions, ops_chat_storage::OcrTextLayout::Plain)
+        });
+
+        self.limiter.wait().await;
+        let result = self
+            .app
+            .state::<DesktopBrainService>()
+            .analyze_image(
+                &NoopEventSink,
+                AnalyzeImageRequest {
+                    api_key: self.api_key.to_string(),
+                    model: self.model.to_string(),
+                    image_path: path.to_string_lossy().into_owned(),
+                    user_message: Some(self.options.prompt.clone()),
+                    channel_id: format!("batch-{}", index),
+                    user_name: None,
+                    user_email: None,
+                    user_instruction: None,
+                    ocr_lang: self.ocr_model.map(str::to_string),
+                    ocr_regions,
+                    redact_pii: crate::services::brain::redact_pii(self.app),
+                    response_language: crate::services::brain::response_language(self.app),
+                    glossary: crate::services::brain::active_glossary(),
+                    fallback_models: crate::services::brain::active_model_fallbacks(),
+                    title: Some(file),
+                    generate_brief: false,
+                },
+            )
+            .await;
+
+        match result {
+            Ok(result) => {
+                item.chat_id = Some(result.metadata.id);
+                item.model = Some(result.model);
+                item.response = Some(result.assistant_message);
+            }
+            Err(e) => item.error = Some(e),
+        }
+        item
+    }
+}
+
+/// Spaces out model calls so no more than `per_minute` start each minute.
+struct RateLimiter {
+    spacing: Duration,
+    next: tokio::sync::Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            spacing: Duration::from_secs(60) / per_minute.max(1),
+            next: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn wait(&self) {
+        let mut next = self.next.lock().await;
+        tokio::time::sleep_until((*next).into()).await;
+        *next = Instant::now() + self.spacing;
+    }
+}
+
+fn list_images(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut images: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_image_path(&path.to_string_lossy()))
+        .collect();
+    images.sort();
+    Ok(images)
+}
+
+fn write_item(out_dir: &Path, format: BatchFormat, item: &BatchItem) -> Result<(), String> {
+    if matches!(format, BatchFormat::Json | BatchFormat::Both) {
+        let json = serde_json::to_string_pretty(item).map_err(|e| e.to_string())?;
+        std::fs::write(out_dir.join(format!("{}.json", item.file)), json)
+            .map_err(|e| e.to_string())?;
+    }
+    if matches!(format, BatchFormat::Markdown | BatchFormat::Both) {
+        std::fs::write(out_dir.join(format!("{}.md", item.file)), markdown(item))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn markdown(item: &BatchItem) -> String {
+    let mut out = format!("# {}\n\n", item.file);
+    if let Some(model) = &item.model {
+        out.push_str(&format!("Model: {}\n\n", model));
+    }
+    match (&item.response, &item.error) {
+        (_, Some(error)) => out.push_str(&format!("**Error:** {}\n", error)),
+        (Some(response), None) => out.push_str(&format!("{}\n", response.trim())),
+        (None, None) => {}
+    }
+    if let Some(text) = item.ocr_text.as_deref().filter(|text| !text.is_empty()) {
+        out.push_str(&format!("\n## OCR text\n\n```text\n{}\n```\n", text));
+    }
+    out
+}
+
+fn ocr_language(app: &AppHandle) -> String {
+    let prefs_file =
+        crate::utils::get_app_config_dir(app).join(crate::constants::PREFERENCES_FILE_NAME);
+    std::fs::read_to_string(prefs_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|prefs| prefs.get(OCR_LANGUAGE_PREF)?.as_str().map(str::to_string))
+        .filter(|model| !model.trim().is_empty())
+        .unwrap_or_else(|| crate::constants::DEFAULT_OCR_LANGUAGE.to_string())
+}
@@ -0,0 +1,162 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Battery-aware behavior.
+//!
+//! The battery is polled in the background. While the machine runs on
+//! battery below the saver threshold, model downloads wait for external
+//! power and sidecars get the battery priority profile. Changes are
+//! announced with `power-status-changed` so background features can pause.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+pub const POWER_STATUS_CHANGED_EVENT: &str = "power-status-changed";
+pub const DOWNLOAD_DEFERRED_EVENT: &str = "download-deferred";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Poll interval while the app is idle in the background.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const THRESHOLD_PREF: &str = "batterySaverThreshold";
+/// Percent of charge below which the saver kicks in.
+const DEFAULT_THRESHOLD: u8 = 30;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    pub battery_present: bool,
+    pub on_battery: bool,
+    pub percent: Option<f32>,
+    pub threshold: u8,
+    /// On battery and below `threshold`.
+    pub saver_active: bool,
+}
+
+#[derive(Default)]
+pub struct BatteryState {
+    status: Mutex<PowerStatus>,
+    changed: Notify,
+    deferred_downloads: Mutex<HashSet<String>>,
+}
+
+impl BatteryState {
+    pub fn status(&self) -> PowerStatus {
+        self.status.lock().clone()
+    }
+
+    pub fn saver_active(&self) -> bool {
+        self.status.lock().saver_active
+    }
+}
+
+pub fn start(app: &AppHandle) {
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh(&handle).await;
+            let interval = if handle
+                .state::<crate::services::idle::IdleGovernorState>()
+                .is_idle()
+            {
+                IDLE_POLL_INTERVAL
+            } else {
+                POLL_INTERVAL
+            };
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
+
+/// Re-read the battery and announce a change.
+pub async fn refresh(app: &AppHandle) {
+    let battery = tauri::async_runtime::spawn_blocking(sys_power_events::battery_status)
+        .await
+        .ok()
+        .flatten();
+    let threshold = threshold(app);
+    let status = PowerStatus {
+        battery_present: battery.is_some(),
+        on_battery: battery.is_some_and(|battery| battery.on_battery),
+        percent: battery.and_then(|battery| battery.percent),
+        threshold,
+        saver_active: battery.is_some_and(|battery| {
+            battery.on_battery
+                && battery
+                    .percent
+                    .is_some_and(|percent| percent < threshold as f32)
+        }),
+    };
+
+    let state = app.state::<BatteryState>();
+    let saver_changed = {
+        let mut current = state.status.lock();
+        if *current == status {
+            return;
+        }
+        let saver_changed = current.saver_active != status.saver_active;
+        *current = status.clone();
+        saver_changed
+    };
+
+    if saver_changed {
+        log::info!(
+            "Battery saver {}",
+            if status.saver_active { "on" } else { "off" }
+        );
+        state.changed.notify_waiters();
+    }
+    let _ = app.emit(POWER_STATUS_CHANGED_EVENT, status);
+}
+
+/// Wait until a model download may start. Returns an error when the
+/// download is cancelled while deferred.
+pub async fn wait_for_download(app: &AppHandle, model_id: &str) -> Result<(), String> {
+    let state = app.state::<BatteryState>();
+    if !state.saver_active() {
+        return Ok(());
+    }
+
+    log::info!("Deferring download of {} until on external power", model_id);
+    state.deferred_downloads.lock().insert(model_id.to_string());
+    let _ = app.emit(
+        DOWNLOAD_DEFERRED_EVENT,
+        serde_json::json!({ "modelId": model_id }),
+    );
+
+    loop {
+        let changed = state.changed.notified();
+        if !state.deferred_downloads.lock().contains(model_id) {
+            return Err("Download cancelled".to_string());
+        }
+        if !state.saver_active() {
+            state.deferred_downloads.lock().remove(model_id);
+            return Ok(());
+        }
+        changed.await;
+    }
+}
+
+/// Drop a deferred download. Returns `false` when it was not deferred.
+pub fn cancel_deferred_download(app: &AppHandle, model_id: &str) -> bool {
+    let state = app.state::<BatteryState>();
+    let removed = state.deferred_downloads.lock().remove(model_id);
+    if removed {
+        state.changed.notify_waiters();
+    }
+    removed
+}
+
+fn threshold(app: &AppHandle) -> u8 {
+    let prefs_file =
+        crate::utils::get_app_config_dir(app).join(crate::constants::PREFERENCES_FILE_NAME);
+    std::fs::read_to_string(prefs_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|prefs| prefs.get(THRESHOLD_PREF)?.as_u64())
+        .map(|threshold| threshold.min(100) as u8)
+        .unwrap_or(DEFAULT_THRESHOLD)
+}
@@ -1,54 +1,310 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
-use ops_squigit_brain::events::BrainEventSink;
+use crate::services::{policy, webhook};
+use ops_chat_storage::Extraction;
+use ops_profile_store::security::ApiKeyProvider;
+use ops_profile_store::{GlossaryEntry, ProfileStore};
+use ops_squigit_brain::context::builder::response_language_name;
+use ops_squigit_brain::context::calendar::CalendarEvent;
+use ops_squigit_brain::context::code::CodeSnippet;
+use ops_squigit_brain::events::{BrainEventSink, CollectingEventSink};
+use ops_squigit_brain::provider::anthropic::AnthropicConfig;
+use ops_squigit_brain::provider::gemini::attachments::{
+    DEFAULT_ANIMATION_FRAMES, MAX_ANIMATION_FRAMES,
+};
+use ops_squigit_brain::provider::gemini::commands::models::ModelInfo;
+use ops_squigit_brain::provider::gemini::transport::types::GeminiPromptPreview;
+use ops_squigit_brain::provider::openai::OpenAiCompatibleConfig;
 use ops_squigit_brain::service::{
-    BrainService, CompressConversationRequest, GenerateChatTitleRequest,
-    GenerateImageBriefRequest, StreamChatRequest,
+    AnalyzeImageRequest, AnalyzeImageResult, BackfillChatTitlesRequest, BrainService,
+    CleanTranscriptRequest, CodeFromScreenshotRequest, CompressConversationRequest,
+    ExtractEventsRequest, ExtractStructuredRequest, GenerateChatTitleRequest,
+    GenerateImageBriefRequest, ListModelsRequest, OcrTranslation, PromptChatRequest,
+    PromptChatResult, ResumeChatRequest, StreamChatRequest, TranslateOcrRegionsRequest,
 };
+use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{AppHandle, Emitter};
 
 pub struct DesktopBrainService {
     inner: BrainService,
+    title_backfill_running: AtomicBool,
+    title_backfill_stop: AtomicBool,
 }
 
 impl DesktopBrainService {
     pub fn new() -> Self {
         Self {
             inner: BrainService::new(),
+            title_backfill_running: AtomicBool::new(false),
+            title_backfill_stop: AtomicBool::new(false),
         }
     }
 
     pub async fn stream_chat(
         &self,
         app: AppHandle,
+        mut request: StreamChatRequest,
+    ) -> Result<String, String> {
+        check_policy(&request.model)?;
+        retain_allowed(&mut request.fallback_models);
+        let sink = TauriEventSink { app };
+        if !request.is_initial_turn {
+            return self.inner.stream_chat(&sink, request).await;
+        }
+
+        let chat_id = request.chat_id.clone();
+        let collector = CollectingEventSink::new(Some(&sink));
+        let model = self.inner.stream_chat(&collector, request).await?;
+        notify_analysis_finished(chat_id, &model, &collector.current_text());
+        Ok(model)
+    }
+
+    /// Like [`Self::stream_chat`], from an OpenAI-compatible server.
+    pub async fn stream_openai_chat(
+        &self,
+        app: AppHandle,
+        config: &OpenAiCompatibleConfig,
+        mut request: StreamChatRequest,
+    ) -> Result<String, String> {
+        policy::check_provider(ApiKeyProvider::OpenAiCompatible)?;
+        policy::check_model(&request.model)?;
+        retain_allowed(&mut request.fallback_models);
+        let sink = TauriEventSink { app };
+        if !request.is_initial_turn {
+            return self.inner.stream_openai_chat(&sink, config, request).await;
+        }
+
+        let chat_id = request.chat_id.clone();
+        let collector = CollectingEventSink::new(Some(&sink));
+        let model = self
+            .inner
+            .stream_openai_chat(&collector, config, request)
+            .await?;
+        notify_analysis_finished(chat_id, &model, &collector.current_text());
+        Ok(model)
+    }
+
+    /// Like [`Self::stream_chat`], from Claude.
+    pub async fn stream_claude_chat(
+        &self,
+        app: AppHandle,
+        config: &AnthropicConfig,
+        mut request: StreamChatRequest,
+    ) -> Result<String, String> {
+        policy::check_provider(ApiKeyProvider::Anthropic)?;
+        policy::check_model(&request.model)?;
+        retain_allowed(&mut request.fallback_models);
+        let sink = TauriEventSink { app };
+        if !request.is_initial_turn {
+            return self.inner.stream_claude_chat(&sink, config, request).await;
+        }
+
+        let chat_id = request.chat_id.clone();
+        let collector = CollectingEventSink::new(Some(&sink));
+        let model = self
+            .inner
+            .stream_claude_chat(&collector, config, request)
+            .await?;
+        notify_analysis_finished(chat_id, &model, &collector.current_text());
+        Ok(model)
+    }
+
+    pub async fn resume_chat(
+        &self,
+        app: AppHandle,
+        mut request: ResumeChatRequest,
+    ) -> Result<String, String> {
+        check_policy(&request.model)?;
+        retain_allowed(&mut request.fallback_models);
+        let sink = TauriEventSink { app };
+        self.inner.resume_chat(&sink, request).await
+    }
+
+    /// Send one user message to a stored chat and append both turns. Events
+    /// go to `sink`, which lets callers observe the reply as it streams.
+    pub async fn prompt_chat(
+        &self,
+        sink: &dyn BrainEventSink,
+        mut request: PromptChatRequest,
+    ) -> Result<PromptChatResult, String> {
+        check_policy(&request.model)?;
+        retain_allowed(&mut request.fallback_models);
+        self.inner.prompt_chat(sink, request).await
+    }
+
+    /// Store an image as a new chat and answer its first turn.
+    pub async fn analyze_image(
+        &self,
+        sink: &dyn BrainEventSink,
+        mut request: AnalyzeImageRequest,
+    ) -> Result<AnalyzeImageResult, String> {
+        check_policy(&request.model)?;
+        retain_allowed(&mut request.fallback_models);
+        self.inner.analyze_image(sink, request).await
+    }
+
+    pub async fn preview_chat(
+        &self,
         request: StreamChatRequest,
-    ) -> Result<(), String> {
+    ) -> Result<GeminiPromptPreview, String> {
+        check_policy(&request.model)?;
+        self.inner.preview_chat(request).await
+    }
+
+    pub async fn send_prompt_preview(
+        &self,
+        app: AppHandle,
+        api_key: String,
+        channel_id: String,
+        preview: GeminiPromptPreview,
+    ) -> Result<String, String> {
+        check_policy(&preview.model)?;
         let sink = TauriEventSink { app };
-        self.inner.stream_chat(&sink, request).await
+        self.inner
+            .send_prompt_preview(&sink, api_key, channel_id, preview)
+            .await
     }
 
     pub async fn generate_chat_title(
         &self,
         request: GenerateChatTitleRequest,
     ) -> Result<String, String> {
+        check_policy(&request.model)?;
         self.inner.generate_chat_title(request).await
     }
 
+    pub async fn clean_transcript(
+        &self,
+        request: CleanTranscriptRequest,
+    ) -> Result<String, String> {
+        check_policy(&request.model)?;
+        self.inner.clean_transcript(request).await
+    }
+
     pub async fn generate_image_brief(
         &self,
         request: GenerateImageBriefRequest,
     ) -> Result<String, String> {
+        check_policy(
+            request
+                .model
+                .as_deref()
+                .unwrap_or(crate::constants::DEFAULT_MODEL),
+        )?;
         self.inner.generate_image_brief(request).await
     }
 
+    pub async fn translate_ocr_regions(
+        &self,
+        request: TranslateOcrRegionsRequest,
+    ) -> Result<OcrTranslation, String> {
+        check_policy(&request.model)?;
+        self.inner.translate_ocr_regions(request).await
+    }
+
+    pub async fn extract_events(
+        &self,
+        request: ExtractEventsRequest,
+    ) -> Result<Vec<CalendarEvent>, String> {
+        check_policy(&request.model)?;
+        self.inner.extract_events(request).await
+    }
+
+    pub async fn extract_structured(
+        &self,
+        request: ExtractStructuredRequest,
+    ) -> Result<Extraction, String> {
+        check_policy(&request.model)?;
+        self.inner.extract_structured(request).await
+    }
+
+    pub async fn code_from_screenshot(
+        &self,
+        request: CodeFromScreenshotRequest,
+    ) -> Result<CodeSnippet, String> {
+        check_policy(&request.model)?;
+        self.inner.code_from_screenshot(request).await
+    }
+
     pub async fn compress_conversation(
         &self,
         request: CompressConversationRequest,
     ) -> Result<String, String> {
+        check_policy(crate::constants::DEFAULT_MODEL)?;
         self.inner.compress_conversation(request).await
     }
 
+    pub async fn list_available_models(
+        &self,
+        request: ListModelsRequest,
+    ) -> Result<Vec<ModelInfo>, String> {
+        policy::check_provider(ApiKeyProvider::GoogleAiStudio)?;
+        let mut models = self.inner.list_available_models(request).await?;
+        models.retain(|model| policy::is_model_allowed(&model.id));
+        Ok(models)
+    }
+
+    pub async fn list_openai_models(
+        &self,
+        config: &OpenAiCompatibleConfig,
+    ) -> Result<Vec<ModelInfo>, String> {
+        policy::check_provider(ApiKeyProvider::OpenAiCompatible)?;
+        let mut models = self.inner.list_openai_models(config).await?;
+        models.retain(|model| policy::is_model_allowed(&model.id));
+        Ok(models)
+    }
+
+    pub async fn list_claude_models(
+        &self,
+        config: &AnthropicConfig,
+    ) -> Result<Vec<ModelInfo>, String> {
+        policy::check_provider(ApiKeyProvider::Anthropic)?;
+        let mut models = self.inner.list_claude_models(config).await?;
+        models.retain(|model| policy::is_model_allowed(&model.id));
+        Ok(models)
+    }
+
+    pub fn is_title_backfill_running(&self) -> bool {
+        self.title_backfill_running.load(Ordering::SeqCst)
+    }
+
+    /// Rename placeholder-titled chats, emitting `title-backfill-progress`
+    /// per chat and `title-backfill-done` at the end.
+    pub async fn backfill_chat_titles(
+        &self,
+        app: AppHandle,
+        request: BackfillChatTitlesRequest,
+    ) -> Result<usize, String> {
+        check_policy(&request.model)?;
+        if self.title_backfill_running.swap(true, Ordering::SeqCst) {
+            return Err("Title backfill is already running".to_string());
+        }
+        self.title_backfill_stop.store(false, Ordering::SeqCst);
+
+        let progress_app = app.clone();
+        let result = self
+            .inner
+            .backfill_chat_titles(request, &self.title_backfill_stop, &move |progress| {
+                let _ = progress_app.emit("title-backfill-progress", progress);
+            })
+            .await;
+        self.title_backfill_running.store(false, Ordering::SeqCst);
+
+        let _ = app.emit(
+            "title-backfill-done",
+            serde_json::json!({
+                "renamed": result.as_ref().ok(),
+                "error": result.as_ref().err(),
+            }),
+        );
+        result
+    }
+
+    pub fn stop_title_backfill(&self) {
+        self.title_backfill_stop.store(true, Ordering::SeqCst);
+    }
+
     pub async fn cancel_request(&self, channel_id: Option<String>) -> Result<(), String> {
         self.inner.cancel_request(channel_id).await
     }
@@ -56,6 +312,10 @@ impl DesktopBrainService {
     pub async fn quick_answer_request(&self, channel_id: String) -> Result<(), String> {
         self.inner.request_quick_answer(channel_id).await
     }
+
+    pub async fn clear_caches(&self) -> usize {
+        self.inner.clear_caches().await
+    }
 }
 
 impl Default for DesktopBrainService {
@@ -64,12 +324,192 @@ impl Default for DesktopBrainService {
     }
 }
 
-struct TauriEventSink {
+const RESPONSE_LANGUAGE_PREF: &str = "responseLanguage";
+const INCLUDE_OCR_IN_PROMPT_PREF: &str = "includeOcrInPrompt";
+const NEVER_SEND_PII_PREF: &str = "neverSendPiiToCloud";
+const ANIMATION_FRAMES_PREF: &str = "animationFrames";
+const MODEL_PREF: &str = "model";
+
+/// The saved reply language, if set and supported. An unsupported value is
+/// ignored so a stale preference never blocks chatting.
+pub fn response_language(app: &AppHandle) -> Option<String> {
+    let prefs = crate::utils::read_preferences(app)?;
+    let tag = prefs.get(RESPONSE_LANGUAGE_PREF)?.as_str()?.trim();
+    if tag.is_empty() {
+        return None;
+    }
+
+    if response_language_name(tag).is_none() {
+        log::warn!("Ignoring unsupported {}: {}", RESPONSE_LANGUAGE_PREF, tag);
+        return None;
+    }
+    Some(tag.to_string())
+}
+
+/// The model chosen in settings, or the default one.
+pub fn preferred_model(app: &AppHandle) -> String {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(MODEL_PREF)?.as_str().map(str::to_string))
+        .filter(|model| !model.trim().is_empty())
+        .unwrap_or_else(|| crate::constants::DEFAULT_MODEL.to_string())
+}
+
+/// Whether the initial turn should carry the chat's OCR transcript. Off
+/// unless the user opted in.
+pub fn include_ocr_in_prompt(app: &AppHandle) -> bool {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(INCLUDE_OCR_IN_PROMPT_PREF)?.as_bool())
+        .unwrap_or(false)
+}
+
+/// Whether personal data in OCR text is replaced with placeholders before
+/// it is sent to a model or a webhook. On when the user opted in or the
+/// admin policy forces redaction.
+pub fn redact_pii(app: &AppHandle) -> bool {
+    policy::current().policy.force_redaction
+        || crate::utils::read_preferences(app)
+            .and_then(|prefs| prefs.get(NEVER_SEND_PII_PREF)?.as_bool())
+            .unwrap_or(false)
+}
+
+/// Frames to sample from animated images, from settings or the default.
+/// `requested` overrides the setting for a single request.
+pub fn animation_frames(app: &AppHandle, requested: Option<usize>) -> usize {
+    requested
+        .or_else(|| {
+            crate::utils::read_preferences(app)
+                .and_then(|prefs| prefs.get(ANIMATION_FRAMES_PREF)?.as_u64())
+                .and_then(|frames| usize::try_from(frames).ok())
+        })
+        .unwrap_or(DEFAULT_ANIMATION_FRAMES)
+        .min(MAX_ANIMATION_FRAMES)
+}
+
+/// The active profile's glossary. Read failures are logged and yield an
+/// empty glossary so prompts and OCR still work.
+pub fn active_glossary() -> Vec<GlossaryEntry> {
+    let result = ProfileStore::new().and_then(|store| match store.get_active_profile_id()? {
+        Some(profile_id) => store.list_glossary(&profile_id),
+        None => Ok(Vec::new()),
+    });
+    result.unwrap_or_else(|e| {
+        log::warn!("Failed to load glossary: {}", e);
+        Vec::new()
+    })
+}
+
+/// The active profile's model fallback chain, tried in order when the
+/// chosen model is unavailable. Empty on read failures.
+pub fn active_model_fallbacks() -> Vec<String> {
+    let result = ProfileStore::new().and_then(|store| store.get_active_profile());
+    match result {
+        Ok(profile) => profile
+            .map(|profile| profile.model_fallbacks)
+            .unwrap_or_default(),
+        Err(e) => {
+            log::warn!("Failed to load model fallbacks: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Gemini calls need the provider and the model to be allowed by the
+/// admin policy.
+fn check_policy(model: &str) -> Result<(), String> {
+    policy::check_provider(ApiKeyProvider::GoogleAiStudio)?;
+    policy::check_model(model)
+}
+
+fn notify_analysis_finished(chat_id: Option<String>, model: &str, reply: &str) {
+    webhook::notify(
+        webhook::WEBHOOK_EVENT_ANALYSIS_FINISHED,
+        serde_json::json!({
+            "chatId": chat_id,
+            "model": model,
+            "summary": webhook::summarize(reply),
+        }),
+    );
+}
+
+/// Drop fallbacks the admin policy does not allow.
+fn retain_allowed(fallback_models: &mut Vec<String>) {
+    retain_allowed_by(&policy::current().policy, fallback_models);
+}
+
+fn retain_allowed_by(policy: &policy::Policy, fallback_models: &mut Vec<String>) {
+    fallback_models.retain(|model| policy.allows_model(model));
+}
+
+/// Google AI Studio key and identity of the active profile.
+pub struct Credentials {
+    pub api_key: String,
+    pub user_name: Option<String>,
+    pub user_email: Option<String>,
+}
+
+pub fn resolve_credentials() -> Result<Credentials, String> {
+    let store = ProfileStore::new().map_err(|e| e.to_string())?;
+    let profile = store
+        .get_active_profile()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No active profile. Please log in first.".to_string())?;
+    let api_key = ops_profile_store::security::get_decrypted_key(
+        &store,
+        ApiKeyProvider::GoogleAiStudio,
+        &profile.id,
+    )
+    .map_err(|e| e.to_string())?
+    .filter(|key| !key.is_empty());
+    // Vertex AI signs requests with the profile's Google Cloud credentials.
+    let api_key = match api_key {
+        Some(key) => key,
+        None if crate::services::vertex::is_enabled(&store, &profile.id) => String::new(),
+        None => return Err("ERR_MISSING_API_KEY".to_string()),
+    };
+
+    Ok(Credentials {
+        api_key,
+        user_name: Some(profile.name),
+        user_email: Some(profile.email),
+    })
+}
+
+/// Emits brain events to the frontend on the request's channel.
+pub(crate) struct TauriEventSink {
     app: AppHandle,
 }
 
+impl TauriEventSink {
+    pub(crate) fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
 impl BrainEventSink for TauriEventSink {
     fn emit(&self, channel_id: &str, event: ops_squigit_brain::provider::gemini::transport::types::GeminiEvent) {
         let _ = self.app.emit(channel_id, event);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallbacks_outside_the_policy_are_dropped() {
+        let policy = policy::Policy {
+            allowed_models: Some(vec!["gemini-2.5-flash*".to_string()]),
+            ..Default::default()
+        };
+        let mut fallbacks = vec![
+            "gemini-2.5-flash-lite".to_string(),
+            "gpt-4o".to_string(),
+            "models/gemini-2.5-flash".to_string(),
+        ];
+        retain_allowed_by(&policy, &mut fallbacks);
+        assert_eq!(
+            fallbacks,
+            ["gemini-2.5-flash-lite", "models/gemini-2.5-flash"]
+        );
+    }
+}
@@ -1,108 +1,484 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::services::audio::{UiSoundEffect, UiSoundPlayer};
+use crate::services::{a11y, metrics};
+use ops_chat_storage::DEFAULT_SIMILARITY_THRESHOLD;
+use parking_lot::Mutex;
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
-use tauri::{AppHandle, Emitter, Manager};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use sys_display_hotplug::DisplayGeometry;
+use sys_process_priority::SidecarRole;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+const CAPTURE_DENIED_ERROR: &str = "User denied screen capture permission.";
+/// Start of the errors for a sidecar that exited without a capture.
+const CAPTURE_NO_RESULT_PREFIX: &str = "Capture sidecar did not return";
+
+const FEEDBACK_NOTIFICATION_PREF: &str = "captureFeedbackNotification";
+const FEEDBACK_SOUND_PREF: &str = "captureFeedbackSound";
+const FEEDBACK_FLASH_PREF: &str = "captureFeedbackFlash";
+const SIMILAR_CAPTURE_HINT_PREF: &str = "similarCaptureHint";
+const FLASH_WINDOW_LABEL: &str = "capture-flash";
+const FLASH_DURATION: Duration = Duration::from_millis(120);
+
+/// A new capture looks like an earlier chat's image
+/// (`{ chatId, similar: { chat, distance } }`).
+pub const SIMILAR_CAPTURE_EVENT: &str = "similar-capture";
+
+/// A delayed capture ticked down (`{ remaining }` seconds); 0 as the
+/// overlay opens.
+pub const CAPTURE_COUNTDOWN_EVENT: &str = "capture-countdown";
+/// Longest delay [`capture_with_delay`] accepts.
+const MAX_CAPTURE_DELAY_SECS: u64 = 60;
+
+/// Bumped by each delayed capture, so starting one calls off the last.
+static COUNTDOWN_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Region of the last interactive capture, replayed by
+/// [`recapture_last_region`].
+static LAST_REGION: Mutex<Option<CaptureRegion>> = Mutex::new(None);
 
 pub fn spawn_capture(app: &AppHandle) {
+    spawn_chat_capture(app, CaptureMode::Chat);
+}
+
+/// Open the selection overlay after `seconds`, emitting `capture-countdown`
+/// every second before. Menus and hover states that close when the overlay
+/// takes focus can be opened in the meantime.
+pub fn capture_with_delay(app: &AppHandle, seconds: u64) -> Result<(), String> {
+    if seconds > MAX_CAPTURE_DELAY_SECS {
+        return Err("ERR_CAPTURE_DELAY_TOO_LONG".to_string());
+    }
+    let generation = COUNTDOWN_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    a11y::polite(app, "capture", &format!("Capturing in {} seconds", seconds));
     let handle = app.clone();
-    tauri::async_runtime::spawn_blocking(move || match run_capture(&handle, false) {
+    tauri::async_runtime::spawn(async move {
+        for remaining in (1..=seconds).rev() {
+            if COUNTDOWN_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let _ = handle.emit(
+                CAPTURE_COUNTDOWN_EVENT,
+                serde_json::json!({ "remaining": remaining }),
+            );
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        if COUNTDOWN_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let _ = handle.emit(
+            CAPTURE_COUNTDOWN_EVENT,
+            serde_json::json!({ "remaining": 0 }),
+        );
+        spawn_capture(&handle);
+    });
+    Ok(())
+}
+
+/// Capture the same area as the last interactive capture again, without
+/// the selection UI, into a new chat.
+pub fn recapture_last_region(app: &AppHandle) -> Result<(), String> {
+    let region = (*LAST_REGION.lock()).ok_or("ERR_NO_LAST_REGION")?;
+    spawn_chat_capture(app, CaptureMode::Region(region));
+    Ok(())
+}
+
+/// Grab the whole display at `index` in [`list_monitors`] into a new chat,
+/// without the selection UI. Fails with the capture's `ERR_` code.
+pub async fn capture_monitor(app: &AppHandle, index: usize) -> Result<(), String> {
+    capture_blocking(app, CaptureMode::Monitor(index)).await
+}
+
+/// Grab the focused window into a new chat, without the selection UI.
+/// Fails with the capture's `ERR_` code.
+pub async fn capture_active_window(app: &AppHandle) -> Result<(), String> {
+    capture_blocking(app, CaptureMode::ActiveWindow).await
+}
+
+async fn capture_blocking(app: &AppHandle, mode: CaptureMode) -> Result<(), String> {
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || run_chat_capture(&handle, mode))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn spawn_chat_capture(app: &AppHandle, mode: CaptureMode) {
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        // Failures reach the frontend as `capture-failed`.
+        let _ = run_chat_capture(&handle, mode);
+    });
+}
+
+/// Capture into a new chat and open it. Blocks until the sidecar exits.
+fn run_chat_capture(app: &AppHandle, mode: CaptureMode) -> Result<(), String> {
+    announce_started(app, mode);
+    match run_recorded(app, mode) {
         Ok(result) => {
-            if let Some(window) = handle.get_webview_window("main") {
-                let was_hidden =
-                    !window.is_visible().unwrap_or(true) || window.is_minimized().unwrap_or(false);
-
-                if was_hidden {
-                    if let Some(geo) = result.display_geo {
-                        let win_size = window.outer_size().unwrap_or(tauri::PhysicalSize {
-                            width: 1030,
-                            height: 690,
-                        });
-                        let center_x = geo.x + (geo.w as i32 - win_size.width as i32) / 2;
-                        let center_y = geo.y + (geo.h as i32 - win_size.height as i32) / 2;
-                        let _ =
-                            window.set_position(tauri::PhysicalPosition::new(center_x, center_y));
+            crate::services::webhook::notify(
+                crate::services::webhook::WEBHOOK_EVENT_CAPTURE_COMPLETE,
+                serde_json::json!({
+                    "chatId": result.chat_id,
+                    "imageHash": result.image_hash,
+                }),
+            );
+            crate::services::ocr::ocr_after_capture(app, &result.chat_id);
+            hint_similar_capture(app, &result.chat_id, &result.image_hash);
+            crate::services::startup::with_main_window(app, move |handle| {
+                if let Some(window) = handle.get_webview_window("main") {
+                    let was_hidden = !window.is_visible().unwrap_or(true)
+                        || window.is_minimized().unwrap_or(false);
+
+                    if was_hidden {
+                        if let Some(geo) = result.display_geo {
+                            let win_size = window.outer_size().unwrap_or(tauri::PhysicalSize {
+                                width: 1030,
+                                height: 690,
+                            });
+                            let (x, y) = geo.centered(win_size.width, win_size.height);
+                            let _ = window.set_position(tauri::PhysicalPosition::new(x, y));
+                        }
+                        let _ = window.unminimize();
+                        let _ = window.show();
                     }
-                    let _ = window.unminimize();
-                    let _ = window.show();
+                    let _ = window.set_focus();
                 }
-                let _ = window.set_focus();
-            }
 
-            let payload = serde_json::json!({
-                "chatId": result.chat_id,
-                "imageHash": result.image_hash,
+                crate::services::search_index::chat_saved(handle, &result.chat_id);
+                #[cfg(target_os = "windows")]
+                crate::services::windows_shell::notify_capture_complete(handle, &result.chat_id);
+                a11y::polite(handle, "capture", "Capture finished, new chat opened");
+                let payload = serde_json::json!({
+                    "chatId": result.chat_id,
+                    "imageHash": result.image_hash,
+                    "devicePixelRatio": result.device_pixel_ratio,
+                });
+                let _ = handle.emit("capture-complete", payload);
             });
-            let _ = handle.emit("capture-complete", payload);
+            Ok(())
         }
         Err(e) => {
-            let _ = handle.emit("capture-failed", serde_json::json!({ "reason": e }));
+            announce_failed(app, &e);
+            let _ = app.emit("capture-failed", serde_json::json!({ "reason": e }));
+            Err(capture_error_code(e))
         }
-    });
+    }
+}
+
+/// `reason` as an `ERR_` code; sidecar messages follow
+/// `ERR_CAPTURE_FAILED`.
+fn capture_error_code(reason: String) -> String {
+    if reason.starts_with("ERR_") {
+        reason
+    } else {
+        format!("ERR_CAPTURE_FAILED: {}", reason)
+    }
 }
 
 pub fn spawn_capture_to_input(app: &AppHandle) {
+    announce_started(app, CaptureMode::InputOnly);
     let handle = app.clone();
-    tauri::async_runtime::spawn_blocking(move || match run_capture(&handle, true) {
-        Ok(result) => {
-            if let Some(window) = handle.get_webview_window("main") {
-                let was_hidden =
-                    !window.is_visible().unwrap_or(true) || window.is_minimized().unwrap_or(false);
-
-                if was_hidden {
-                    if let Some(geo) = result.display_geo {
-                        let win_size = window.outer_size().unwrap_or(tauri::PhysicalSize {
-                            width: 1030,
-                            height: 690,
-                        });
-                        let center_x = geo.x + (geo.w as i32 - win_size.width as i32) / 2;
-                        let center_y = geo.y + (geo.h as i32 - win_size.height as i32) / 2;
-                        let _ =
-                            window.set_position(tauri::PhysicalPosition::new(center_x, center_y));
+    tauri::async_runtime::spawn_blocking(move || {
+        match run_recorded(&handle, CaptureMode::InputOnly) {
+            Ok(result) => {
+                if let Some(window) = handle.get_webview_window("main") {
+                    let was_hidden = !window.is_visible().unwrap_or(true)
+                        || window.is_minimized().unwrap_or(false);
+
+                    if was_hidden {
+                        if let Some(geo) = result.display_geo {
+                            let win_size = window.outer_size().unwrap_or(tauri::PhysicalSize {
+                                width: 1030,
+                                height: 690,
+                            });
+                            let (x, y) = geo.centered(win_size.width, win_size.height);
+                            let _ = window.set_position(tauri::PhysicalPosition::new(x, y));
+                        }
+                        let _ = window.unminimize();
+                        let _ = window.show();
                     }
-                    let _ = window.unminimize();
-                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+
+                if let Some(temp_path) = result.temp_path {
+                    a11y::polite(&handle, "capture", "Capture finished, image attached");
+                    let _ = handle.emit(
+                        "capture-to-input",
+                        serde_json::json!({
+                            "tempPath": temp_path,
+                            "devicePixelRatio": result.device_pixel_ratio,
+                        }),
+                    );
                 }
-                let _ = window.set_focus();
             }
+            Err(e) => {
+                announce_failed(&handle, &e);
+                let _ = handle.emit("capture-failed", serde_json::json!({ "reason": e }));
+            }
+        }
+    });
+}
 
-            if let Some(temp_path) = result.temp_path {
-                let _ = handle.emit(
-                    "capture-to-input",
-                    serde_json::json!({ "tempPath": temp_path }),
-                );
+/// With `similarCaptureHint` on, look for an earlier chat whose image looks
+/// like the new capture and announce the closest one with `similar-capture`,
+/// e.g. "similar to a chat from yesterday".
+fn hint_similar_capture(app: &AppHandle, chat_id: &str, image_hash: &str) {
+    let enabled = crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(SIMILAR_CAPTURE_HINT_PREF)?.as_bool())
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+    let handle = app.clone();
+    let chat_id = chat_id.to_string();
+    let image_hash = image_hash.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let similar = ops_squigit_brain::context::media::get_active_storage().and_then(|storage| {
+            storage
+                .find_similar_captures(&image_hash, DEFAULT_SIMILARITY_THRESHOLD)
+                .map_err(|e| e.to_string())
+        });
+        match similar {
+            Ok(similar) => {
+                if let Some(closest) = similar.into_iter().find(|found| found.chat.id != chat_id) {
+                    let _ = handle.emit(
+                        SIMILAR_CAPTURE_EVENT,
+                        serde_json::json!({ "chatId": chat_id, "similar": closest }),
+                    );
+                }
             }
+            Err(e) => log::warn!("Failed to look for similar captures: {}", e),
         }
-        Err(e) => {
-            let _ = handle.emit("capture-failed", serde_json::json!({ "reason": e }));
+    });
+}
+
+fn announce_started(app: &AppHandle, mode: CaptureMode) {
+    let message = match mode {
+        CaptureMode::Chat | CaptureMode::InputOnly => "Capture started, select a region",
+        CaptureMode::Region(_) => "Capturing the last region",
+        CaptureMode::Monitor(_) => "Capturing the display",
+        CaptureMode::ActiveWindow => "Capturing the active window",
+        CaptureMode::ActiveMonitor => return,
+    };
+    a11y::polite(app, "capture", message);
+}
+
+/// [`run_capture`], counted in the local metrics. Escaping the selection
+/// leaves the sidecar without a result, which is neither a use nor a
+/// failure.
+fn run_recorded(app: &AppHandle, mode: CaptureMode) -> Result<CaptureResult, String> {
+    let result = run_capture(app, mode);
+    match &result {
+        Ok(capture) => {
+            metrics::record_use(app, "capture", false);
+            CaptureFeedback::from_preferences(crate::utils::read_preferences(app).as_ref()).give(
+                app,
+                mode,
+                capture.display_geo,
+            );
+        }
+        Err(e) if e.starts_with(CAPTURE_NO_RESULT_PREFIX) => {}
+        Err(_) => metrics::record_use(app, "capture", true),
+    }
+    result
+}
+
+/// Escaping the selection also ends up here, so only a denied permission,
+/// which needs the user to act, interrupts.
+fn announce_failed(app: &AppHandle, reason: &str) {
+    if reason == CAPTURE_DENIED_ERROR {
+        a11y::assertive(app, "capture", "Screen capture permission denied");
+    } else {
+        a11y::polite(app, "capture", "No capture taken");
+    }
+}
+
+/// Confirmation that a capture happened, from the `captureFeedback*`
+/// preferences, all off by default: a system notification, a sound
+/// (`"shutter"` or the path of an audio file) and a white flash over the
+/// captured display.
+#[derive(Debug, Default)]
+struct CaptureFeedback {
+    notification: bool,
+    sound: Option<UiSoundEffect>,
+    flash: bool,
+}
+
+impl CaptureFeedback {
+    fn from_preferences(prefs: Option<&serde_json::Value>) -> Self {
+        let Some(prefs) = prefs else {
+            return Self::default();
+        };
+        let enabled = |key: &str| prefs.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        let sound = match prefs.get(FEEDBACK_SOUND_PREF).and_then(|v| v.as_str()) {
+            None | Some("") | Some("none") => None,
+            Some("shutter") => Some(UiSoundEffect::CaptureShutter),
+            Some(path) => Some(UiSoundEffect::File(path.into())),
+        };
+        Self {
+            notification: enabled(FEEDBACK_NOTIFICATION_PREF),
+            sound,
+            flash: enabled(FEEDBACK_FLASH_PREF),
         }
+    }
+
+    /// The sidecar has exited, so a shutter sound it suppressed is already
+    /// unmuted and the sound is heard.
+    fn give(self, app: &AppHandle, mode: CaptureMode, display: Option<DisplayGeometry>) {
+        if let Some(sound) = self.sound {
+            if let Err(e) = app.state::<UiSoundPlayer>().play(sound) {
+                log::warn!("{}", e);
+            }
+        }
+        if self.flash {
+            flash_display(app, display);
+        }
+        if self.notification {
+            notify_captured(app, mode);
+        }
+    }
+}
+
+/// Windows already raises a toast when the window is not in front.
+#[cfg(target_os = "windows")]
+fn notify_captured(_app: &AppHandle, _mode: CaptureMode) {}
+
+#[cfg(not(target_os = "windows"))]
+fn notify_captured(app: &AppHandle, mode: CaptureMode) {
+    use tauri_plugin_notification::NotificationExt;
+    let body = match mode {
+        CaptureMode::InputOnly => "The screenshot was attached to your message.",
+        _ => "The screenshot opened in a new chat.",
+    };
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("Capture taken")
+        .body(body)
+        .show()
+    {
+        log::warn!("Failed to show capture notification: {}", e);
+    }
+}
+
+/// Cover the captured display, or the primary one when the sidecar did not
+/// report it, with a white click-through window for a moment.
+fn flash_display(app: &AppHandle, display: Option<DisplayGeometry>) {
+    let (position, size) = match display {
+        Some(geo) => (
+            tauri::PhysicalPosition::new(geo.x, geo.y),
+            tauri::PhysicalSize::new(geo.width, geo.height),
+        ),
+        None => match app.primary_monitor() {
+            Ok(Some(monitor)) => (*monitor.position(), *monitor.size()),
+            _ => return,
+        },
+    };
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Some(window) = handle.get_webview_window(FLASH_WINDOW_LABEL) {
+            let _ = window.close();
+        }
+        let window = match WebviewWindowBuilder::new(
+            &handle,
+            FLASH_WINDOW_LABEL,
+            WebviewUrl::App("index.html?window=flash".into()),
+        )
+        .title("Capture flash")
+        .visible(false)
+        .resizable(false)
+        .decorations(false)
+        .shadow(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .background_color(tauri::window::Color(255, 255, 255, 255))
+        .build()
+        {
+            Ok(window) => window,
+            Err(e) => {
+                log::warn!("Failed to open the capture flash: {}", e);
+                return;
+            }
+        };
+        let _ = window.set_ignore_cursor_events(true);
+        let _ = window.set_position(position);
+        let _ = window.set_size(size);
+        let _ = window.show();
+        tokio::time::sleep(FLASH_DURATION).await;
+        let _ = window.close();
     });
 }
 
+/// A full-monitor frame grabbed without the selection UI.
+pub struct MonitorFrame {
+    pub path: String,
+    pub image_hash: String,
+}
+
+/// Grab the monitor under the cursor straight into CAS storage.
+/// Blocking; call from a blocking task.
+pub fn grab_active_monitor(app: &AppHandle) -> Result<MonitorFrame, String> {
+    let result = run_capture(app, CaptureMode::ActiveMonitor)?;
+    Ok(MonitorFrame {
+        path: result.temp_path.unwrap_or_default(),
+        image_hash: result.image_hash,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptureMode {
+    /// Interactive selection that creates a new chat.
+    Chat,
+    /// Interactive selection that only stores the image for the chat input.
+    InputOnly,
+    /// Non-interactive grab of the monitor under the cursor.
+    ActiveMonitor,
+    /// Non-interactive crop of a known region that creates a new chat.
+    Region(CaptureRegion),
+    /// Non-interactive grab of the display at this index in
+    /// [`list_monitors`] that creates a new chat.
+    Monitor(usize),
+    /// Non-interactive grab of the focused window that creates a new chat.
+    ActiveWindow,
+}
+
+/// A selection on a display, as reported by the sidecar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CaptureRegion {
+    display: DisplayGeometry,
+    /// Relative to the display, in logical pixels.
+    selection: DisplayGeometry,
+}
+
 struct CaptureResult {
     chat_id: String,
     image_hash: String,
     temp_path: Option<String>,
-    display_geo: Option<DisplayGeo>,
+    display_geo: Option<DisplayGeometry>,
+    /// Physical pixels per logical pixel of the captured display.
+    device_pixel_ratio: f64,
 }
 
-#[derive(Debug, Clone)]
-struct DisplayGeo {
-    x: i32,
-    y: i32,
-    w: u32,
-    h: u32,
-}
-
-fn run_capture(app: &AppHandle, input_only: bool) -> Result<CaptureResult, String> {
+fn run_capture(app: &AppHandle, mode: CaptureMode) -> Result<CaptureResult, String> {
     let sidecar_path = resolve_sidecar_path(app)?;
+    crate::services::integrity::ensure_sidecar_intact(
+        app,
+        crate::services::integrity::SidecarKind::Capture,
+        &sidecar_path,
+    )?;
+    crate::services::permissions::ensure_screen_recording(app)?;
+    let input_only = matches!(mode, CaptureMode::InputOnly | CaptureMode::ActiveMonitor);
 
     let mut args = Vec::new();
     if input_only {
         args.push("--input-only".to_string());
     }
     let mut is_freeshape = false;
+    let mut capture_display: Option<String> = None;
 
     if let Ok(config_dir) = app.path().app_config_dir() {
         let prefs_path = config_dir.join("preferences.json");
@@ -113,18 +489,44 @@ fn run_capture(app: &AppHandle, input_only: bool) -> Result<CaptureResult, Strin
                         is_freeshape = true;
                     }
                 }
+                capture_display = json
+                    .get("captureDisplay")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+                if let Some(secs) = json
+                    .get("captureTempRetentionSecs")
+                    .and_then(|v| v.as_u64())
+                {
+                    args.push("--temp-retention-secs".to_string());
+                    args.push(secs.to_string());
+                }
             }
         }
     }
 
-    if is_freeshape {
-        args.push("-f".to_string());
+    if let CaptureMode::Region(region) = mode {
+        args.push("--region".to_string());
+        args.push(region.selection.to_string());
+        args.push("--display".to_string());
+        args.push(region.display.to_string());
+    } else if mode == CaptureMode::ActiveMonitor {
+        args.push("-a".to_string());
+    } else if mode == CaptureMode::ActiveWindow {
+        args.push("--window".to_string());
+    } else if let CaptureMode::Monitor(index) = mode {
+        let monitor = list_monitors()
+            .into_iter()
+            .nth(index)
+            .ok_or_else(|| "ERR_NO_MONITOR".to_string())?;
+        args.push("--monitor".to_string());
+        args.push(monitor.display.name);
     } else {
-        args.push("-r".to_string());
+        args.push(if is_freeshape { "-f" } else { "-r" }.to_string());
+        args.extend(capture_display_args(capture_display.as_deref()));
     }
 
-    let mut child = Command::new(&sidecar_path)
-        .args(&args)
+    let mut cmd = Command::new(&sidecar_path);
+    cmd.args(&args)
         .env(
             "GIO_LAUNCHED_DESKTOP_APP_ID",
             crate::constants::APP_NAME.to_lowercase(),
@@ -134,10 +536,30 @@ fn run_capture(app: &AppHandle, input_only: bool) -> Result<CaptureResult, Strin
             crate::constants::APP_NAME.to_lowercase(),
         )
         .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
+        .stderr(Stdio::inherit());
+
+    let priority = crate::services::priority::sidecar_priority(app, SidecarRole::Interactive);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(sys_process_priority::priority_class(&priority));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: The hook only makes async-signal-safe system calls.
+        unsafe {
+            cmd.pre_exec(move || sys_process_priority::apply_to_current_process(&priority));
+        }
+    }
+
+    let mut child = cmd
         .spawn()
         .map_err(|e| format!("Failed to spawn capture sidecar: {}", e))?;
 
+    #[cfg(windows)]
+    let _ = sys_process_priority::apply_to_process(child.id(), &priority);
+
     let stdout = child
         .stdout
         .take()
@@ -147,7 +569,9 @@ fn run_capture(app: &AppHandle, input_only: bool) -> Result<CaptureResult, Strin
     let mut chat_id: Option<String> = None;
     let mut image_hash: Option<String> = None;
     let mut temp_path: Option<String> = None;
-    let mut display_geo: Option<DisplayGeo> = None;
+    let mut display_geo: Option<DisplayGeometry> = None;
+    let mut selection: Option<DisplayGeometry> = None;
+    let mut device_pixel_ratio = 1.0;
 
     for line in reader.lines() {
         match line {
@@ -160,9 +584,13 @@ fn run_capture(app: &AppHandle, input_only: bool) -> Result<CaptureResult, Strin
                 } else if let Some(path) = trimmed.strip_prefix("CAS_PATH:") {
                     temp_path = Some(path.to_string());
                 } else if let Some(geo_str) = trimmed.strip_prefix("DISPLAY_GEO:") {
-                    display_geo = parse_display_geo(geo_str);
+                    display_geo = DisplayGeometry::parse(geo_str);
+                } else if let Some(rect_str) = trimmed.strip_prefix("SELECTION_RECT:") {
+                    selection = DisplayGeometry::parse(rect_str);
+                } else if let Some(ratio) = trimmed.strip_prefix("DEVICE_PIXEL_RATIO:") {
+                    device_pixel_ratio = ratio.parse().unwrap_or(1.0);
                 } else if trimmed == "CAPTURE_DENIED" {
-                    return Err("User denied screen capture permission.".to_string());
+                    return Err(CAPTURE_DENIED_ERROR.to_string());
                 }
             }
             Err(_) => break,
@@ -171,18 +599,21 @@ fn run_capture(app: &AppHandle, input_only: bool) -> Result<CaptureResult, Strin
 
     let _ = child.wait();
 
+    if let (Some(display), Some(selection)) = (display_geo, selection) {
+        *LAST_REGION.lock() = Some(CaptureRegion { display, selection });
+    }
+
     if input_only {
-        let path =
-            temp_path.ok_or_else(|| "Capture sidecar did not return CAS_PATH".to_string())?;
+        let path = temp_path.ok_or_else(|| format!("{} CAS_PATH", CAPTURE_NO_RESULT_PREFIX))?;
         Ok(CaptureResult {
             chat_id: String::new(),
-            image_hash: String::new(),
+            image_hash: image_hash.unwrap_or_default(),
             temp_path: Some(path),
             display_geo,
+            device_pixel_ratio,
         })
     } else {
-        let chat_id =
-            chat_id.ok_or_else(|| "Capture sidecar did not return CHAT_ID".to_string())?;
+        let chat_id = chat_id.ok_or_else(|| format!("{} CHAT_ID", CAPTURE_NO_RESULT_PREFIX))?;
         let image_hash = image_hash.unwrap_or_default();
 
         Ok(CaptureResult {
@@ -190,25 +621,70 @@ fn run_capture(app: &AppHandle, input_only: bool) -> Result<CaptureResult, Strin
             image_hash,
             temp_path: None,
             display_geo,
+            device_pixel_ratio,
         })
     }
 }
 
-fn parse_display_geo(s: &str) -> Option<DisplayGeo> {
-    let parts: Vec<&str> = s.split(',').collect();
-    if parts.len() == 4 {
-        Some(DisplayGeo {
-            x: parts[0].parse().ok()?,
-            y: parts[1].parse().ok()?,
-            w: parts[2].parse().ok()?,
-            h: parts[3].parse().ok()?,
-        })
-    } else {
-        None
+/// Sidecar flags for the `captureDisplay` preference: "all" (the default),
+/// "cursor", or an output name from `list_displays`.
+fn capture_display_args(preference: Option<&str>) -> Vec<String> {
+    match preference.map(str::trim) {
+        None | Some("") | Some(crate::constants::DEFAULT_CAPTURE_DISPLAY) => Vec::new(),
+        Some("cursor") => vec!["--cursor-display".to_string()],
+        Some(name) => vec!["--display-name".to_string(), name.to_string()],
     }
 }
 
-fn resolve_sidecar_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+/// A connected display, for the capture display picker.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    /// Value to store in the `captureDisplay` preference.
+    pub name: String,
+    pub connector: Option<String>,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub primary: bool,
+    pub scale: Option<f64>,
+}
+
+pub fn list_displays() -> Vec<DisplayInfo> {
+    sys_display_hotplug::list_displays()
+        .into_iter()
+        .map(|display| DisplayInfo {
+            name: display.name,
+            connector: display.connector,
+            x: display.geometry.map(|g| g.x),
+            y: display.geometry.map(|g| g.y),
+            width: display.geometry.map(|g| g.width),
+            height: display.geometry.map(|g| g.height),
+            primary: display.primary,
+            scale: display.scale,
+        })
+        .collect()
+}
+
+/// A display [`capture_monitor`] can grab, with its position in the list.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    pub index: usize,
+    #[serde(flatten)]
+    pub display: DisplayInfo,
+}
+
+pub fn list_monitors() -> Vec<MonitorInfo> {
+    list_displays()
+        .into_iter()
+        .enumerate()
+        .map(|(index, display)| MonitorInfo { index, display })
+        .collect()
+}
+
+pub(crate) fn resolve_sidecar_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     let binary_name = format!("capture-engine{}", if cfg!(windows) { ".exe" } else { "" });
     let sidecar_dir_name = format!("qt-capture-{}", get_capture_target_triple());
 
@@ -0,0 +1,37 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clipboard writes that run without the renderer, so global shortcuts can
+//! use them while the window stays hidden.
+
+use tauri::{AppHandle, Manager};
+
+use crate::services::session::SessionState;
+
+/// Copy the latest assistant reply of the active chat to the clipboard.
+/// Returns the number of characters copied.
+pub fn copy_last_answer(app: &AppHandle) -> Result<usize, String> {
+    let chat_id = app
+        .state::<SessionState>()
+        .active_chat_id()
+        .ok_or("ERR_NO_ACTIVE_CHAT")?;
+
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    let chat = storage.load_chat(&chat_id).map_err(|e| e.to_string())?;
+    let answer = chat
+        .messages
+        .iter()
+        .rev()
+        .find(|message| message.role == "assistant" && !message.content.trim().is_empty())
+        .map(|message| message.content.trim().to_string())
+        .ok_or("ERR_NO_ANSWER")?;
+
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?;
+    clipboard
+        .set_text(answer.as_str())
+        .map_err(|e| format!("Failed to copy text: {}", e))?;
+
+    log::info!("Copied last answer of chat {} to the clipboard", chat_id);
+    Ok(answer.chars().count())
+}
@@ -0,0 +1,351 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hands-free voice conversation about one chat.
+//!
+//! Every final STT transcription becomes a user turn. The streamed reply is
+//! split into sentences that are spoken as they complete, so playback starts
+//! before generation ends. Speaking over a reply (barge-in) stops playback,
+//! cancels the stream, and the next final transcription starts a new turn.
+//!
+//! Progress is announced with `voice-conversation`:
+//! `{ chatId, state: "listening" | "thinking" | "speaking" | "stopped", channelId?, error? }`.
+//! Reply tokens are emitted on `channelId` like any other chat stream.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+
+use ops_squigit_brain::events::BrainEventSink;
+use ops_squigit_brain::provider::gemini::transport::types::GeminiEvent;
+use ops_squigit_brain::service::PromptChatRequest;
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::services::brain::{DesktopBrainService, TauriEventSink};
+use crate::services::session::SessionState;
+use crate::services::tts::TextToSpeech;
+
+pub const VOICE_CONVERSATION_EVENT: &str = "voice-conversation";
+
+const VOICE_STREAM_CHANNEL: &str = "voice-stream";
+
+/// Partial transcripts shorter than this do not interrupt a reply, so a
+/// cough or the tail of the synthesized voice is not taken as barge-in.
+const BARGE_IN_MIN_WORDS: usize = 2;
+
+#[derive(Default)]
+pub struct ConversationState {
+    current: Mutex<Option<Conversation>>,
+    /// Incremented for every turn and interruption; work tagged with an
+    /// older value is stale and dropped.
+    turn: Arc<AtomicU64>,
+}
+
+struct Conversation {
+    chat_id: String,
+    /// Whether STT was started for the conversation and should be stopped
+    /// with it.
+    started_stt: bool,
+    /// Set from the start of a turn until its last sentence was spoken.
+    replying: Arc<AtomicBool>,
+    channel_id: Option<String>,
+    speaker: mpsc::Sender<Utterance>,
+    tts: Arc<TextToSpeech>,
+}
+
+enum Utterance {
+    Sentence { turn: u64, text: String },
+    End { turn: u64, error: Option<String> },
+}
+
+/// Begin a conversation about `chat_id`, replacing any running one.
+pub fn start(app: &AppHandle, chat_id: String, started_stt: bool) {
+    stop(app);
+
+    let state = app.state::<ConversationState>();
+    let tts = Arc::new(TextToSpeech::default());
+    let replying = Arc::new(AtomicBool::new(false));
+    let (speaker, utterances) = mpsc::channel();
+    spawn_speaker(
+        app.clone(),
+        chat_id.clone(),
+        utterances,
+        tts.clone(),
+        state.turn.clone(),
+        replying.clone(),
+    );
+
+    *state.current.lock() = Some(Conversation {
+        chat_id: chat_id.clone(),
+        started_stt,
+        replying,
+        channel_id: None,
+        speaker,
+        tts,
+    });
+    emit_state(app, &chat_id, "listening", None);
+}
+
+/// End the conversation, silencing and cancelling any reply in flight.
+/// Returns whether STT was started by the conversation.
+pub fn stop(app: &AppHandle) -> bool {
+    let state = app.state::<ConversationState>();
+    let Some(conversation) = state.current.lock().take() else {
+        return false;
+    };
+    state.turn.fetch_add(1, Ordering::SeqCst);
+    interrupt(app, &conversation);
+    emit_state(app, &conversation.chat_id, "stopped", None);
+    conversation.started_stt
+}
+
+/// Feed an STT transcription to the running conversation, if any.
+pub fn on_transcript(app: &AppHandle, text: &str, is_final: bool) {
+    let state = app.state::<ConversationState>();
+    let mut guard = state.current.lock();
+    let Some(conversation) = guard.as_mut() else {
+        return;
+    };
+
+    let words = text.split_whitespace().count();
+    if conversation.replying.load(Ordering::SeqCst) && words >= BARGE_IN_MIN_WORDS {
+        log::info!("Voice conversation: barge-in");
+        state.turn.fetch_add(1, Ordering::SeqCst);
+        conversation.replying.store(false, Ordering::SeqCst);
+        interrupt(app, conversation);
+        conversation.channel_id = None;
+        if !is_final {
+            emit_state(app, &conversation.chat_id, "listening", None);
+        }
+    }
+
+    if is_final && words > 0 && !conversation.replying.load(Ordering::SeqCst) {
+        let turn = state.turn.fetch_add(1, Ordering::SeqCst) + 1;
+        start_turn(app, conversation, turn, text.trim().to_string());
+    }
+}
+
+fn interrupt(app: &AppHandle, conversation: &Conversation) {
+    conversation.tts.stop();
+    if let Some(channel_id) = conversation.channel_id.clone() {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = app
+                .state::<DesktopBrainService>()
+                .cancel_request(Some(channel_id))
+                .await;
+        });
+    }
+}
+
+fn start_turn(app: &AppHandle, conversation: &mut Conversation, turn: u64, text: String) {
+    let channel_id = format!("{}-{}", VOICE_STREAM_CHANNEL, turn);
+    conversation.channel_id = Some(channel_id.clone());
+    conversation.replying.store(true, Ordering::SeqCst);
+    emit_state(app, &conversation.chat_id, "thinking", Some(&channel_id));
+
+    let app = app.clone();
+    let chat_id = conversation.chat_id.clone();
+    let speaker = conversation.speaker.clone();
+    tauri::async_runtime::spawn(async move {
+        let sink = ConversationSink {
+            inner: TauriEventSink::new(app.clone()),
+            turn,
+            current_turn: app.state::<ConversationState>().turn.clone(),
+            speaker: speaker.clone(),
+            pending: Mutex::new(String::new()),
+        };
+        let result = run_turn(&app, &sink, chat_id.clone(), channel_id, text).await;
+        sink.flush();
+        if let Err(e) = &result {
+            log::warn!("Voice conversation turn failed: {}", e);
+        }
+        let _ = speaker.send(Utterance::End {
+            turn,
+            error: result.err(),
+        });
+    });
+}
+
+async fn run_turn(
+    app: &AppHandle,
+    sink: &ConversationSink,
+    chat_id: String,
+    channel_id: String,
+    user_message: String,
+) -> Result<(), String> {
+    let credentials =
+        tauri::async_runtime::spawn_blocking(crate::services::brain::resolve_credentials)
+            .await
+            .map_err(|e| e.to_string())??;
+
+    let session = app.state::<SessionState>();
+    session.stream_started(Some(chat_id.clone()), &channel_id);
+    let result = app
+        .state::<DesktopBrainService>()
+        .prompt_chat(
+            sink,
+            PromptChatRequest {
+                api_key: credentials.api_key,
+                model: crate::services::brain::preferred_model(app),
+                chat_id,
+                user_message,
+                channel_id: channel_id.clone(),
+                user_name: credentials.user_name,
+                user_email: credentials.user_email,
+                response_language: crate::services::brain::response_language(app),
+                glossary: crate::services::brain::active_glossary(),
+                fallback_models: crate::services::brain::active_model_fallbacks(),
+            },
+        )
+        .await;
+    session.stream_finished(&channel_id);
+    result.map(|_| ())
+}
+
+/// Speaks queued sentences in order, skipping those of interrupted turns.
+fn spawn_speaker(
+    app: AppHandle,
+    chat_id: String,
+    utterances: mpsc::Receiver<Utterance>,
+    tts: Arc<TextToSpeech>,
+    current_turn: Arc<AtomicU64>,
+    replying: Arc<AtomicBool>,
+) {
+    std::thread::spawn(move || {
+        let mut speaking_turn = None;
+        for utterance in utterances {
+            match utterance {
+                Utterance::Sentence { turn, text } => {
+                    if turn != current_turn.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                    if speaking_turn != Some(turn) {
+                        speaking_turn = Some(turn);
+                        emit_state(&app, &chat_id, "speaking", None);
+                    }
+                    if let Err(e) = tts.speak(&text) {
+                        log::warn!("Text-to-speech failed: {}", e);
+                    }
+                }
+                Utterance::End { turn, error } => {
+                    if turn != current_turn.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                    replying.store(false, Ordering::SeqCst);
+                    match error {
+                        Some(error) => emit_turn_error(&app, &chat_id, &error),
+                        None => emit_state(&app, &chat_id, "listening", None),
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Forwards stream events to the frontend and queues each completed
+/// sentence of the reply for speech.
+struct ConversationSink {
+    inner: TauriEventSink,
+    turn: u64,
+    current_turn: Arc<AtomicU64>,
+    speaker: mpsc::Sender<Utterance>,
+    pending: Mutex<String>,
+}
+
+impl ConversationSink {
+    fn is_stale(&self) -> bool {
+        self.current_turn.load(Ordering::SeqCst) != self.turn
+    }
+
+    fn say(&self, sentence: &str) {
+        let text = speakable(sentence);
+        if !text.is_empty() {
+            let _ = self.speaker.send(Utterance::Sentence {
+                turn: self.turn,
+                text,
+            });
+        }
+    }
+
+    /// Speak whatever is left once the reply has finished.
+    fn flush(&self) {
+        let rest = std::mem::take(&mut *self.pending.lock());
+        if !self.is_stale() {
+            self.say(&rest);
+        }
+    }
+}
+
+impl BrainEventSink for ConversationSink {
+    fn emit(&self, channel_id: &str, event: GeminiEvent) {
+        match &event {
+            GeminiEvent::Token { token } if !self.is_stale() => {
+                let mut pending = self.pending.lock();
+                pending.push_str(token);
+                while let Some(end) = sentence_end(&pending) {
+                    let sentence: String = pending.drain(..end).collect();
+                    self.say(&sentence);
+                }
+            }
+            GeminiEvent::Reset => self.pending.lock().clear(),
+            _ => {}
+        }
+        self.inner.emit(channel_id, event);
+    }
+}
+
+/// Byte offset just past the first complete sentence: terminal punctuation
+/// followed by whitespace, or a line break.
+fn sentence_end(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        if ch == '\n' {
+            return Some(index + 1);
+        }
+        if matches!(ch, '.' | '!' | '?') {
+            if let Some((next, after)) = chars.peek().copied() {
+                if after.is_whitespace() {
+                    return Some(next);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Drop markdown markup and code fences that would be read out literally.
+fn speakable(sentence: &str) -> String {
+    let line = sentence.trim();
+    if line.starts_with("```") || line.starts_with('|') {
+        return String::new();
+    }
+    let text: String = line
+        .trim_start_matches(['#', '>', '-', '*', ' '])
+        .chars()
+        .filter(|c| !matches!(c, '*' | '`' | '#' | '~'))
+        .collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn emit_state(app: &AppHandle, chat_id: &str, state: &str, channel_id: Option<&str>) {
+    let _ = app.emit(
+        VOICE_CONVERSATION_EVENT,
+        serde_json::json!({
+            "chatId": chat_id,
+            "state": state,
+            "channelId": channel_id,
+        }),
+    );
+}
+
+fn emit_turn_error(app: &AppHandle, chat_id: &str, error: &str) {
+    let _ = app.emit(
+        VOICE_CONVERSATION_EVENT,
+        serde_json::json!({
+            "chatId": chat_id,
+            "state": "listening",
+            "error": error,
+        }),
+    );
+}
@@ -0,0 +1,212 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! `snapllm://` deep links, for editors, scripts and browser extensions.
+//!
+//! - `snapllm://ask?text=…` starts a chat about the given text
+//! - `snapllm://capture` starts a screen capture
+//! - `snapllm://new-chat` starts a new chat
+//! - `snapllm://open-chat/<id>` opens a stored chat
+//!
+//! `squigit://` is accepted as well. Links opened while the app is running
+//! reach it through the single-instance plugin; a link that launched the
+//! app is read once setup is done. Each link becomes an action invocation,
+//! so the renderer sees it as `action-invoked` like any other action.
+
+use tauri::AppHandle;
+use tauri_plugin_deep_link::DeepLinkExt;
+use url::Url;
+
+/// URL schemes registered for the app.
+pub const SCHEMES: &[&str] = &["snapllm", "squigit"];
+
+/// Longest text accepted from `ask` links, in characters.
+const MAX_ASK_TEXT_CHARS: usize = 32_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Ask { text: String },
+    Capture,
+    NewChat,
+    OpenChat { chat_id: String },
+}
+
+impl DeepLink {
+    pub fn parse(url: &Url) -> Result<Self, String> {
+        if !SCHEMES.contains(&url.scheme()) {
+            return Err(format!("ERR_UNSUPPORTED_LINK: {}", url.scheme()));
+        }
+
+        // `snapllm://open-chat/<id>` puts the route in the host, while
+        // `snapllm:open-chat/<id>` only has a path, which `path_segments`
+        // does not split for such URLs.
+        let mut route = url
+            .host_str()
+            .into_iter()
+            .chain(url.path().split('/').filter(|segment| !segment.is_empty()));
+
+        match route.next() {
+            Some("ask") => {
+                let text = url
+                    .query_pairs()
+                    .find(|(key, _)| key == "text")
+                    .map(|(_, value)| value.trim().to_string())
+                    .filter(|text| !text.is_empty())
+                    .ok_or("ERR_MISSING_LINK_TEXT")?;
+                if text.chars().count() > MAX_ASK_TEXT_CHARS {
+                    return Err("ERR_LINK_TEXT_TOO_LONG".to_string());
+                }
+                Ok(Self::Ask { text })
+            }
+            Some("capture") => Ok(Self::Capture),
+            Some("new-chat") => Ok(Self::NewChat),
+            Some("open-chat") => {
+                let chat_id = route
+                    .next()
+                    .filter(|id| {
+                        id.chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                    })
+                    .ok_or("ERR_INVALID_LINK_CHAT_ID")?;
+                if route.next().is_some() {
+                    return Err("ERR_INVALID_LINK_CHAT_ID".to_string());
+                }
+                Ok(Self::OpenChat {
+                    chat_id: chat_id.to_string(),
+                })
+            }
+            other => Err(format!(
+                "ERR_UNSUPPORTED_LINK: {}",
+                other.unwrap_or_default()
+            )),
+        }
+    }
+}
+
+/// Whether a command-line argument is one of our links rather than a path.
+pub fn is_deep_link(arg: &str) -> bool {
+    Url::parse(arg).is_ok_and(|url| SCHEMES.contains(&url.scheme()))
+}
+
+/// Register the schemes where that happens at runtime, handle links opened
+/// from now on and the one the app was launched with, if any.
+pub fn start(app: &AppHandle) {
+    // Installers register the schemes on Windows and macOS reads them from
+    // the bundle; Linux and unpackaged Windows builds register here.
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    if let Err(e) = app.deep_link().register_all() {
+        log::warn!("Failed to register deep link schemes: {}", e);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            open(&handle, &url);
+        }
+    });
+
+    match app.deep_link().get_current() {
+        Ok(Some(urls)) => {
+            for url in urls {
+                open(app, &url);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to read launch deep link: {}", e),
+    }
+}
+
+fn open(app: &AppHandle, url: &Url) {
+    let link = match DeepLink::parse(url) {
+        Ok(link) => link,
+        Err(e) => {
+            log::warn!("Ignoring deep link {}: {}", url.scheme(), e);
+            return;
+        }
+    };
+    let (action, argument) = match link {
+        DeepLink::Capture => ("capture.screen", None),
+        DeepLink::NewChat => ("chat.new", None),
+        DeepLink::Ask { text } => ("chat.ask", Some(text)),
+        DeepLink::OpenChat { chat_id } => ("chat.open", Some(chat_id)),
+    };
+    log::info!("Deep link opened: {}", action);
+    let invoke = move |app: &AppHandle| {
+        if let Err(e) = super::actions::invoke(app, action, argument.as_deref()) {
+            log::warn!("Deep link action {} failed: {}", action, e);
+        }
+    };
+
+    // Captures need no window; renderer actions wait until it has loaded.
+    if action == "capture.screen" {
+        invoke(app);
+    } else {
+        super::startup::with_main_window(app, invoke);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(link: &str) -> Result<DeepLink, String> {
+        DeepLink::parse(&Url::parse(link).unwrap())
+    }
+
+    #[test]
+    fn ask_links_need_bounded_text() {
+        assert_eq!(
+            parse("snapllm://ask?text=What%20is%20this%3F"),
+            Ok(DeepLink::Ask {
+                text: "What is this?".to_string()
+            })
+        );
+        assert_eq!(
+            parse("snapllm://ask?text=%20%20"),
+            Err("ERR_MISSING_LINK_TEXT".to_string())
+        );
+        assert_eq!(
+            parse("snapllm://ask"),
+            Err("ERR_MISSING_LINK_TEXT".to_string())
+        );
+        let long = "a".repeat(MAX_ASK_TEXT_CHARS + 1);
+        assert_eq!(
+            parse(&format!("snapllm://ask?text={}", long)),
+            Err("ERR_LINK_TEXT_TOO_LONG".to_string())
+        );
+    }
+
+    #[test]
+    fn open_chat_links_take_host_and_path_forms() {
+        let expected = Ok(DeepLink::OpenChat {
+            chat_id: "0b1c-2d_3".to_string(),
+        });
+        assert_eq!(parse("snapllm://open-chat/0b1c-2d_3"), expected);
+        assert_eq!(parse("snapllm:open-chat/0b1c-2d_3"), expected);
+        assert_eq!(parse("squigit://open-chat/0b1c-2d_3/"), expected);
+        assert_eq!(parse("snapllm://capture"), Ok(DeepLink::Capture));
+    }
+
+    #[test]
+    fn rejects_bad_chat_ids_and_foreign_schemes() {
+        for link in [
+            "snapllm:open-chat/../x",
+            "snapllm://open-chat/a/b",
+            "snapllm://open-chat/",
+            "snapllm://open-chat/a%2Fb",
+        ] {
+            assert_eq!(
+                parse(link),
+                Err("ERR_INVALID_LINK_CHAT_ID".to_string()),
+                "{}",
+                link
+            );
+        }
+        assert_eq!(
+            parse("https://open-chat/abc"),
+            Err("ERR_UNSUPPORTED_LINK: https".to_string())
+        );
+        assert!(!is_deep_link("https://example.com"));
+        assert!(is_deep_link("snapllm://new-chat"));
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ephemeral captures.
+//!
+//! A chat's screenshot is deleted from storage after its first successful
+//! reply. The `ephemeralCaptures` preference (off by default) applies to
+//! every chat and a chat's own `ephemeral` flag overrides it.
+//! `ephemeralKeepOcr` (on by default) keeps the OCR text so later turns
+//! and search still have it.
+
+use std::time::Duration;
+
+use ops_chat_storage::ChatMetadata;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::services::ocr::DesktopOcrService;
+
+/// Emitted with the chat's updated metadata once its image is gone.
+pub const CHAT_IMAGE_DISCARDED_EVENT: &str = "chat-image-discarded";
+
+const ENABLED_PREF: &str = "ephemeralCaptures";
+const KEEP_OCR_PREF: &str = "ephemeralKeepOcr";
+/// How often to check whether the chat's OCR job has finished.
+const OCR_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Delete the chat's image in the background if the chat is ephemeral.
+pub fn first_reply_finished(app: &AppHandle, chat_id: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match discard_if_ephemeral(&app, &chat_id).await {
+            Ok(Some(metadata)) => {
+                log::info!("Discarded the image of ephemeral chat {}", chat_id);
+                crate::services::search_index::chat_saved(&app, &chat_id);
+                let _ = app.emit(CHAT_IMAGE_DISCARDED_EVENT, metadata);
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Failed to discard the image of chat {}: {}", chat_id, e),
+        }
+    });
+}
+
+/// Waits for the chat's OCR job, which reads the image, to finish first.
+async fn discard_if_ephemeral(
+    app: &AppHandle,
+    chat_id: &str,
+) -> Result<Option<ChatMetadata>, String> {
+    let prefs = crate::utils::read_preferences(app);
+    let default_enabled = pref_bool(prefs.as_ref(), ENABLED_PREF).unwrap_or(false);
+    let keep_ocr = pref_bool(prefs.as_ref(), KEEP_OCR_PREF).unwrap_or(true);
+
+    let ocr = app.state::<DesktopOcrService>();
+    while ocr.is_job_queued(chat_id).await {
+        tokio::time::sleep(OCR_POLL_INTERVAL).await;
+    }
+
+    let chat_id = chat_id.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let storage = ops_squigit_brain::context::media::get_active_storage()?;
+        let metadata = storage
+            .load_chat(&chat_id)
+            .map_err(|e| e.to_string())?
+            .metadata;
+        if !metadata.ephemeral.unwrap_or(default_enabled) {
+            return Ok(None);
+        }
+        storage
+            .discard_chat_image(&chat_id, keep_ocr)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn pref_bool(prefs: Option<&serde_json::Value>, key: &str) -> Option<bool> {
+    prefs?.get(key)?.as_bool()
+}
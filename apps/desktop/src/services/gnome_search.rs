@@ -0,0 +1,163 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! GNOME Shell search provider for chats.
+//!
+//! Serves `org.gnome.Shell.SearchProvider2` on the session bus, so typing
+//! in the overview lists chats matching the local chat search, with their
+//! best snippet. Activating a result opens the chat.
+//!
+//! GNOME only reads provider files from the system data directories, so
+//! packages install `packaging/linux/gnome-shell/squigit-search-provider.ini`
+//! into `/usr/share/gnome-shell/search-providers`. Results are served while
+//! the app runs, including in background mode.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ops_squigit_brain::context::media::get_active_storage;
+use ops_squigit_brain::tools::chat_search::search_local_chats;
+use tauri::AppHandle;
+use zbus::zvariant::{OwnedValue, Value};
+
+const BUS_NAME: &str = "com.squigit.app.SearchProvider";
+const OBJECT_PATH: &str = "/com/squigit/app/SearchProvider";
+
+/// Results handed to the shell per search. It shows only a few.
+const MAX_RESULTS: usize = 10;
+
+/// Keeps the bus name owned for the life of the app.
+static CONNECTION: tokio::sync::Mutex<Option<zbus::Connection>> =
+    tokio::sync::Mutex::const_new(None);
+
+struct ResultMeta {
+    title: String,
+    snippet: String,
+}
+
+struct SearchProvider {
+    app: AppHandle,
+    /// Metadata of the last results, read back by `GetResultMetas`.
+    metas: Mutex<HashMap<String, ResultMeta>>,
+}
+
+impl SearchProvider {
+    async fn search(&self, terms: Vec<String>) -> Vec<String> {
+        let query = terms.join(" ");
+        if query.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let rows = tauri::async_runtime::spawn_blocking(move || {
+            let storage = get_active_storage()?;
+            search_local_chats(&storage, &query, MAX_RESULTS)
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result);
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(e) => {
+                log::warn!("GNOME search failed: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut metas = self.metas.lock().unwrap_or_else(|e| e.into_inner());
+        metas.clear();
+        rows.into_iter()
+            .map(|row| {
+                metas.insert(
+                    row.chat_id.clone(),
+                    ResultMeta {
+                        title: row.chat_title,
+                        snippet: row.snippet,
+                    },
+                );
+                row.chat_id
+            })
+            .collect()
+    }
+}
+
+#[zbus::interface(name = "org.gnome.Shell.SearchProvider2")]
+impl SearchProvider {
+    async fn get_initial_result_set(&self, terms: Vec<String>) -> Vec<String> {
+        self.search(terms).await
+    }
+
+    /// Searching again is cheap enough and re-ranks for the longer query.
+    async fn get_subsearch_result_set(
+        &self,
+        _previous_results: Vec<String>,
+        terms: Vec<String>,
+    ) -> Vec<String> {
+        self.search(terms).await
+    }
+
+    fn get_result_metas(&self, identifiers: Vec<String>) -> Vec<HashMap<String, OwnedValue>> {
+        let metas = self.metas.lock().unwrap_or_else(|e| e.into_inner());
+        identifiers
+            .into_iter()
+            .filter_map(|id| {
+                let meta = metas.get(&id)?;
+                let name = if meta.title.trim().is_empty() {
+                    "Untitled chat"
+                } else {
+                    meta.title.as_str()
+                };
+                let mut entry = HashMap::new();
+                entry.insert("name".to_string(), owned(name)?);
+                entry.insert("description".to_string(), owned(&meta.snippet)?);
+                entry.insert("id".to_string(), owned(&id)?);
+                Some(entry)
+            })
+            .collect()
+    }
+
+    fn activate_result(&self, identifier: String, _terms: Vec<String>, _timestamp: u32) {
+        log::info!("Chat opened from GNOME search");
+        super::startup::with_main_window(&self.app, move |app| {
+            if let Err(e) = super::actions::invoke(app, "chat.open", Some(&identifier)) {
+                log::warn!("Failed to open chat from GNOME search: {}", e);
+            }
+        });
+    }
+
+    fn launch_search(&self, _terms: Vec<String>, _timestamp: u32) {
+        super::startup::with_main_window(&self.app, |app| {
+            if let Err(e) = super::actions::invoke(app, "chat.search", None) {
+                log::warn!("Failed to open chat search: {}", e);
+            }
+        });
+    }
+}
+
+fn owned(value: &str) -> Option<OwnedValue> {
+    Value::from(value).try_into().ok()
+}
+
+/// Claim the provider's bus name. Failures only cost the overview results.
+pub fn start(app: &AppHandle) {
+    let provider = SearchProvider {
+        app: app.clone(),
+        metas: Mutex::new(HashMap::new()),
+    };
+    tauri::async_runtime::spawn(async move {
+        let connection = async {
+            zbus::conn::Builder::session()?
+                .name(BUS_NAME)?
+                .serve_at(OBJECT_PATH, provider)?
+                .build()
+                .await
+        }
+        .await;
+        match connection {
+            Ok(connection) => {
+                *CONNECTION.lock().await = Some(connection);
+                log::info!("GNOME search provider registered");
+            }
+            Err(e) => log::warn!("GNOME search provider unavailable: {}", e),
+        }
+    });
+}
@@ -0,0 +1,285 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Live "explain my screen" HUD.
+//!
+//! A small always-on-top overlay that periodically grabs the monitor under the
+//! cursor, skips frames that did not change, and streams a short model summary
+//! into the overlay in place.
+
+use crate::services::brain::DesktopBrainService;
+use ops_squigit_brain::service::StreamChatRequest;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+pub const HUD_WINDOW_LABEL: &str = "hud";
+pub const HUD_STREAM_CHANNEL: &str = "hud-stream";
+
+const HUD_WIDTH: f64 = 380.0;
+const HUD_HEIGHT: f64 = 160.0;
+const HUD_MARGIN: f64 = 24.0;
+
+const DEFAULT_INTERVAL_SECS: u64 = 15;
+const MIN_INTERVAL_SECS: u64 = 5;
+const MAX_INTERVAL_SECS: u64 = 300;
+
+/// Side of the grayscale thumbnail used for the change gate.
+const SIGNATURE_SIDE: u32 = 32;
+/// Mean per-pixel luma difference (0-255) below which a frame counts as unchanged.
+const CHANGE_THRESHOLD: f64 = 6.0;
+
+const HUD_PROMPT: &str = "In at most two short sentences, explain what is on my screen right now. \
+No greetings, no markdown headings.";
+
+#[derive(Debug, Clone)]
+pub struct HudConfig {
+    pub api_key: String,
+    pub model: String,
+    pub interval_secs: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct HudState {
+    stop: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl HudState {
+    pub fn is_running(&self) -> bool {
+        self.stop
+            .lock()
+            .map(|guard| guard.is_some())
+            .unwrap_or(false)
+    }
+}
+
+pub fn start_hud(app: &AppHandle, config: HudConfig) -> Result<(), String> {
+    let state = app.state::<HudState>();
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let mut guard = state.stop.lock().map_err(|e| e.to_string())?;
+        if guard.is_some() {
+            return Ok(());
+        }
+        *guard = Some(stop.clone());
+    }
+
+    if let Err(e) = open_hud_window(app) {
+        if let Ok(mut guard) = state.stop.lock() {
+            *guard = None;
+        }
+        return Err(e);
+    }
+
+    let interval = Duration::from_secs(
+        config
+            .interval_secs
+            .unwrap_or(DEFAULT_INTERVAL_SECS)
+            .clamp(MIN_INTERVAL_SECS, MAX_INTERVAL_SECS),
+    );
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        run_hud_loop(handle, config, interval, stop).await;
+    });
+
+    Ok(())
+}
+
+pub async fn stop_hud(app: &AppHandle) {
+    let stop = app
+        .state::<HudState>()
+        .stop
+        .lock()
+        .ok()
+        .and_then(|mut guard| guard.take());
+    if let Some(stop) = stop {
+        stop.store(true, Ordering::SeqCst);
+    }
+
+    let brain = app.state::<DesktopBrainService>();
+    let _ = brain
+        .cancel_request(Some(HUD_STREAM_CHANNEL.to_string()))
+        .await;
+
+    if let Some(window) = app.get_webview_window(HUD_WINDOW_LABEL) {
+        let _ = window.close();
+    }
+}
+
+fn open_hud_window(app: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(HUD_WINDOW_LABEL) {
+        let _ = window.show();
+        return Ok(());
+    }
+
+    let (x, y) = top_right_of_cursor_monitor(app);
+    WebviewWindowBuilder::new(
+        app,
+        HUD_WINDOW_LABEL,
+        WebviewUrl::App("index.html?window=hud".into()),
+    )
+    .title("Squigit HUD")
+    .position(x, y)
+    .inner_size(HUD_WIDTH, HUD_HEIGHT)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .focused(false)
+    .build()
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+fn top_right_of_cursor_monitor(app: &AppHandle) -> (f64, f64) {
+    let cursor = app.cursor_position().ok();
+    let monitors = app.available_monitors().unwrap_or_default();
+    let monitor = monitors
+        .iter()
+        .find(|monitor| {
+            let Some(cursor) = cursor else {
+                return false;
+            };
+            let pos = monitor.position();
+            let size = monitor.size();
+            cursor.x >= pos.x as f64
+                && cursor.x < (pos.x + size.width as i32) as f64
+                && cursor.y >= pos.y as f64
+                && cursor.y < (pos.y + size.height as i32) as f64
+        })
+        .or_else(|| monitors.first());
+
+    let Some(monitor) = monitor else {
+        return (HUD_MARGIN, HUD_MARGIN);
+    };
+    let scale = monitor.scale_factor();
+    let work = monitor.work_area();
+    let x =
+        work.position.x as f64 / scale + work.size.width as f64 / scale - HUD_WIDTH - HUD_MARGIN;
+    let y = work.position.y as f64 / scale + HUD_MARGIN;
+    (x.max(0.0), y.max(0.0))
+}
+
+async fn run_hud_loop(
+    app: AppHandle,
+    config: HudConfig,
+    interval: Duration,
+    stop: Arc<AtomicBool>,
+) {
+    let mut last_hash: Option<String> = None;
+    let mut last_signature: Option<Vec<u8>> = None;
+
+    while !stop.load(Ordering::SeqCst) {
+        if app
+            .state::<crate::services::memory::MemoryPressureState>()
+            .background_work_paused()
+        {
+            tokio::time::sleep(interval).await;
+            continue;
+        }
+
+        let grab_handle = app.clone();
+        let frame = tauri::async_runtime::spawn_blocking(move || {
+            crate::services::capture::grab_active_monitor(&grab_handle)
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result);
+
+        match frame {
+            Ok(frame) if !frame.path.is_empty() => {
+                let duplicate = !frame.image_hash.is_empty()
+                    && last_hash.as_deref() == Some(frame.image_hash.as_str());
+                let signature = if duplicate {
+                    None
+                } else {
+                    frame_signature(&frame.path)
+                };
+                let changed = !duplicate
+                    && match (&last_signature, &signature) {
+                        (Some(previous), Some(current)) => {
+                            signature_distance(previous, current) >= CHANGE_THRESHOLD
+                        }
+                        _ => true,
+                    };
+
+                if changed && !stop.load(Ordering::SeqCst) {
+                    last_hash = Some(frame.image_hash.clone());
+                    last_signature = signature;
+                    let _ = app.emit(
+                        "hud-frame",
+                        serde_json::json!({ "imageHash": frame.image_hash }),
+                    );
+
+                    let brain = app.state::<DesktopBrainService>();
+                    let result = brain
+                        .stream_chat(
+                            app.clone(),
+                            StreamChatRequest {
+                                api_key: config.api_key.clone(),
+                                model: config.model.clone(),
+                                is_initial_turn: true,
+                                image_path: Some(frame.path),
+                                image_paths: Vec::new(),
+                                image_description: None,
+                                user_first_msg: None,
+                                history_log: None,
+                                rolling_summary: None,
+                                user_message: HUD_PROMPT.to_string(),
+                                channel_id: HUD_STREAM_CHANNEL.to_string(),
+                                chat_id: None,
+                                user_name: None,
+                                user_email: None,
+                                user_instruction: None,
+                                image_brief: None,
+                                response_language: crate::services::brain::response_language(&app),
+                                glossary: crate::services::brain::active_glossary(),
+                                fallback_models: crate::services::brain::active_model_fallbacks(),
+                                // HUD frames are not stored chats, so there is no OCR data.
+                                include_ocr_in_prompt: false,
+                                redact_pii: false,
+                                // Screen frames are never animated.
+                                animation_frames: 0,
+                            },
+                        )
+                        .await;
+                    if let Err(e) = result {
+                        let _ = app.emit("hud-error", serde_json::json!({ "reason": e }));
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                log::warn!("HUD capture failed: {}", e);
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Downscaled grayscale thumbnail used to detect meaningful screen changes.
+fn frame_signature(path: &str) -> Option<Vec<u8>> {
+    let image = image::open(path).ok()?;
+    let thumb = image
+        .resize_exact(
+            SIGNATURE_SIDE,
+            SIGNATURE_SIDE,
+            image::imageops::FilterType::Triangle,
+        )
+        .to_luma8();
+    Some(thumb.into_raw())
+}
+
+fn signature_distance(a: &[u8], b: &[u8]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return f64::MAX;
+    }
+    let total: u64 = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| (*x as i16 - *y as i16).unsigned_abs() as u64)
+        .sum();
+    total as f64 / a.len() as f64
+}
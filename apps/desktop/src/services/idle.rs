@@ -0,0 +1,104 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Idle resource governor for background mode.
+//!
+//! Once the main window has been hidden for a while (10 minutes by default,
+//! `backgroundIdleMinutes` in preferences, 0 to disable), the app winds
+//! down: network probes and battery polling slow down and rebuildable
+//! provider caches are dropped. Showing the window re-warms everything.
+//! Changes are announced with `idle-state-changed`.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const IDLE_STATE_CHANGED_EVENT: &str = "idle-state-changed";
+
+const IDLE_MINUTES_PREF: &str = "backgroundIdleMinutes";
+const DEFAULT_IDLE_MINUTES: u64 = 10;
+
+#[derive(Default)]
+pub struct IdleGovernorState {
+    idle: AtomicBool,
+    /// Bumped on every show and hide, so a pending countdown can tell it is
+    /// stale.
+    generation: AtomicU64,
+}
+
+impl IdleGovernorState {
+    /// Whether background polling should run at its slow rate.
+    pub fn is_idle(&self) -> bool {
+        self.idle.load(Ordering::SeqCst)
+    }
+}
+
+/// Start the idle countdown. Called whenever the main window is hidden.
+pub fn window_hidden(app: &AppHandle) {
+    let state = app.state::<IdleGovernorState>();
+    let generation = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+    let Some(delay) = idle_after(app) else {
+        return;
+    };
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        let state = handle.state::<IdleGovernorState>();
+        if state.generation.load(Ordering::SeqCst) != generation
+            || state.idle.swap(true, Ordering::SeqCst)
+        {
+            return;
+        }
+        enter_idle(&handle).await;
+    });
+}
+
+/// Cancel the countdown and re-warm if idle. Called whenever the main
+/// window is shown.
+pub fn window_shown(app: &AppHandle) {
+    let state = app.state::<IdleGovernorState>();
+    state.generation.fetch_add(1, Ordering::SeqCst);
+    if !state.idle.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        handle
+            .state::<crate::services::ocr::DesktopOcrService>()
+            .set_idle(false);
+        crate::services::battery::refresh(&handle).await;
+        let _ = handle.emit(
+            IDLE_STATE_CHANGED_EVENT,
+            serde_json::json!({ "idle": false }),
+        );
+        log::info!("Main window shown, leaving idle mode");
+    });
+}
+
+async fn enter_idle(app: &AppHandle) {
+    app.state::<crate::services::ocr::DesktopOcrService>()
+        .set_idle(true);
+    let dropped = crate::services::memory::clear_caches(app).await;
+    let _ = app.emit(
+        IDLE_STATE_CHANGED_EVENT,
+        serde_json::json!({ "idle": true }),
+    );
+    log::info!(
+        "Main window hidden, entering idle mode: polling slowed, dropped {} cache entries",
+        dropped
+    );
+}
+
+/// How long the window must stay hidden, or `None` when disabled.
+fn idle_after(app: &AppHandle) -> Option<Duration> {
+    let prefs_file =
+        crate::utils::get_app_config_dir(app).join(crate::constants::PREFERENCES_FILE_NAME);
+    let minutes = std::fs::read_to_string(prefs_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|prefs| prefs.get(IDLE_MINUTES_PREF)?.as_u64())
+        .unwrap_or(DEFAULT_IDLE_MINUTES);
+    (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+}
@@ -4,7 +4,30 @@
 use crate::services::tone::detect_image_tone_from_bytes;
 use crate::state::AppState;
 use ops_chat_storage::StoredImage;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
+
+/// Bounds for the longest side of a rasterized SVG, so icons come out
+/// readable and huge canvases stay cheap.
+const SVG_MIN_SIDE: f32 = 1024.0;
+const SVG_MAX_SIDE: f32 = 4096.0;
+
+/// Image formats the WebView cannot display and that are converted to PNG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ForeignFormat {
+    Heic,
+    Avif,
+    Svg,
+}
+
+impl ForeignFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Heic => "heic",
+            Self::Avif => "avif",
+            Self::Svg => "svg",
+        }
+    }
+}
 
 pub fn process_and_store_image(
     path: String,
@@ -14,6 +37,16 @@ pub fn process_and_store_image(
     process_bytes_internal(bytes, state)
 }
 
+/// Start a chat from an image handed over from outside the app (a second
+/// instance's command line, the macOS Services menu). The renderer treats
+/// `image-path` like an image passed at launch.
+pub fn open_external_image(app: &AppHandle, path: String) {
+    crate::services::startup::with_main_window(app, move |app| {
+        crate::services::tray::show_window(app);
+        let _ = app.emit("image-path", path);
+    });
+}
+
 pub fn process_bytes_internal(
     buffer: Vec<u8>,
     state: &State<AppState>,
@@ -22,6 +55,7 @@ pub fn process_bytes_internal(
         return Err("Empty image buffer".to_string());
     }
 
+    let buffer = normalize_image_bytes(buffer)?;
     let explicit_tone = detect_image_tone_from_bytes(&buffer);
     let stored = ops_squigit_brain::context::media::process_bytes_internal(buffer, explicit_tone)?;
 
@@ -31,6 +65,128 @@ pub fn process_bytes_internal(
     Ok(stored)
 }
 
+/// Convert HEIC, AVIF and SVG input to PNG for the WebView. The original is
+/// kept in CAS next to the converted image. Other data is returned as is.
+pub fn normalize_image_bytes(bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    let Some(format) = sniff_foreign_format(&bytes) else {
+        return Ok(bytes);
+    };
+
+    let png = match format {
+        ForeignFormat::Heic => decode_heic(&bytes)?,
+        ForeignFormat::Avif => decode_avif(&bytes)?,
+        ForeignFormat::Svg => rasterize_svg(&bytes)?,
+    };
+
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    storage
+        .store_file(&bytes, format.extension(), None)
+        .map_err(|e| format!("Failed to keep original image: {}", e))?;
+
+    Ok(png)
+}
+
+fn sniff_foreign_format(bytes: &[u8]) -> Option<ForeignFormat> {
+    // ISO-BMFF: `....ftyp<major brand>`.
+    if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        return match &bytes[8..12] {
+            b"avif" | b"avis" => Some(ForeignFormat::Avif),
+            b"heic" | b"heix" | b"heim" | b"heis" | b"hevc" | b"hevx" | b"mif1" | b"msf1" => {
+                Some(ForeignFormat::Heic)
+            }
+            _ => None,
+        };
+    }
+
+    let head = &bytes[..bytes.len().min(1024)];
+    let text = String::from_utf8_lossy(head);
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let is_markup = text.starts_with("<svg") || text.starts_with("<?xml") || text.starts_with("<!");
+    (is_markup && text.contains("<svg")).then_some(ForeignFormat::Svg)
+}
+
+fn encode_png(image: &image::DynamicImage) -> Result<Vec<u8>, String> {
+    let mut png = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(png.into_inner())
+}
+
+#[cfg(feature = "heif")]
+fn decode_heic(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    let decode_err = |e: libheif_rs::HeifError| format!("Failed to decode HEIC image: {}", e);
+    let context = HeifContext::read_from_bytes(bytes).map_err(decode_err)?;
+    let handle = context.primary_image_handle().map_err(decode_err)?;
+    let decoded = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgba), None)
+        .map_err(decode_err)?;
+
+    let plane = decoded
+        .planes()
+        .interleaved
+        .ok_or("Failed to decode HEIC image: no RGBA plane")?;
+    let (width, height) = (plane.width, plane.height);
+    let row_len = width as usize * 4;
+    let mut rgba = Vec::with_capacity(row_len * height as usize);
+    for row in plane.data.chunks(plane.stride).take(height as usize) {
+        rgba.extend_from_slice(&row[..row_len]);
+    }
+
+    let image = image::RgbaImage::from_raw(width, height, rgba)
+        .ok_or("Failed to decode HEIC image: bad dimensions")?;
+    encode_png(&image::DynamicImage::ImageRgba8(image))
+}
+
+#[cfg(not(feature = "heif"))]
+fn decode_heic(_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    Err("HEIC images are not supported in this build".to_string())
+}
+
+#[cfg(feature = "avif")]
+fn decode_avif(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::Avif)
+        .map_err(|e| format!("Failed to decode AVIF image: {}", e))?;
+    encode_png(&image)
+}
+
+#[cfg(not(feature = "avif"))]
+fn decode_avif(_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    Err("AVIF images are not supported in this build".to_string())
+}
+
+#[cfg(feature = "svg")]
+fn rasterize_svg(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    use resvg::{tiny_skia, usvg};
+
+    let tree = usvg::Tree::from_data(bytes, &usvg::Options::default())
+        .map_err(|e| format!("Failed to parse SVG image: {}", e))?;
+    let size = tree.size();
+    let longest = size.width().max(size.height());
+    let scale = longest.clamp(SVG_MIN_SIDE, SVG_MAX_SIDE) / longest;
+    let width = (size.width() * scale).ceil().max(1.0) as u32;
+    let height = (size.height() * scale).ceil().max(1.0) as u32;
+
+    let mut pixmap =
+        tiny_skia::Pixmap::new(width, height).ok_or("Failed to allocate SVG canvas")?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+        .encode_png()
+        .map_err(|e| format!("Failed to encode SVG image: {}", e))
+}
+
+#[cfg(not(feature = "svg"))]
+fn rasterize_svg(_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    Err("SVG images are not supported in this build".to_string())
+}
+
 pub async fn upload_image_to_imgbb(image_path: &str, api_key: &str) -> Result<String, String> {
+    crate::services::policy::check_image_hosting()?;
     ops_squigit_brain::context::media::upload_image_to_imgbb(image_path, api_key).await
 }
@@ -0,0 +1,234 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Startup capability detection for tray and shortcut integration.
+//!
+//! When a desktop lacks a StatusNotifierWatcher or the session bus is
+//! unusable, the tray silently disappears. The detected capabilities and the
+//! tray backend actually in use are recorded here so the UI can explain what
+//! is missing instead of leaving users with "no tray icon".
+
+use crate::services::shortcut::{GlobalShortcutState, GlobalShortcutStatus};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::Manager;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayBackend {
+    /// Tauri tray icon (macOS/Windows).
+    Native,
+    /// Our own StatusNotifierItem over D-Bus (Linux).
+    StatusNotifier,
+    /// libappindicator via Tauri; falls back to an XEmbed icon when no
+    /// watcher is running (Linux).
+    AppIndicator,
+    #[default]
+    None,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopCapabilities {
+    /// `XDG_CURRENT_DESKTOP`, Linux only.
+    pub desktop: Option<String>,
+    pub wayland: bool,
+    pub session_bus: bool,
+    pub status_notifier_watcher: bool,
+    pub global_shortcuts_portal: bool,
+    /// libayatana-appindicator/libappindicator can be loaded for the
+    /// fallback tray. Tauri panics when it cannot.
+    pub app_indicator_library: bool,
+}
+
+#[derive(Default)]
+pub struct DesktopIntegrationState {
+    capabilities: Mutex<DesktopCapabilities>,
+    tray: Mutex<(TrayBackend, Option<String>)>,
+}
+
+impl DesktopIntegrationState {
+    pub fn detect(&self) {
+        let capabilities = detect_capabilities();
+        log::info!("Desktop capabilities: {:?}", capabilities);
+        *self.capabilities.lock() = capabilities;
+    }
+
+    pub fn capabilities(&self) -> DesktopCapabilities {
+        self.capabilities.lock().clone()
+    }
+
+    pub fn set_tray(&self, backend: TrayBackend, error: Option<String>) {
+        *self.tray.lock() = (backend, error);
+    }
+
+    pub fn tray(&self) -> (TrayBackend, Option<String>) {
+        self.tray.lock().clone()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopIntegrationStatus {
+    pub capabilities: DesktopCapabilities,
+    pub tray: TrayBackend,
+    /// Why the preferred tray backend was not used.
+    pub tray_error: Option<String>,
+    pub shortcut: GlobalShortcutStatus,
+    /// The capture D-Bus endpoint is reachable (always true off Linux).
+    pub shortcut_endpoint: bool,
+    /// Desktop-level shortcut bindings, `null` outside Linux.
+    pub shortcut_install: serde_json::Value,
+    /// Machine-readable problems, e.g. `no_status_notifier_watcher`.
+    pub issues: Vec<&'static str>,
+}
+
+/// Snapshot of tray and shortcut integration. Makes blocking D-Bus calls
+/// and runs the desktop config tools on Linux.
+pub fn status(app: &tauri::AppHandle) -> DesktopIntegrationStatus {
+    let state = app.state::<DesktopIntegrationState>();
+    let capabilities = state.capabilities();
+    let (tray, tray_error) = state.tray();
+    let shortcut = app.state::<GlobalShortcutState>().status();
+    let shortcut_endpoint = shortcut_endpoint_reachable();
+
+    let mut issues = issues(&capabilities, tray, shortcut.registered, shortcut_endpoint);
+
+    #[cfg(target_os = "linux")]
+    let shortcut_install = {
+        let install =
+            sys_global_shortcut::linux_shortcut_install_status(crate::constants::APP_NAME);
+        if install.detected.is_none() {
+            issues.push("shortcut_desktop_unsupported");
+        } else if !install.backends.iter().any(|backend| backend.configured) {
+            issues.push("shortcut_not_configured");
+        }
+        serde_json::to_value(install).unwrap_or_default()
+    };
+    #[cfg(not(target_os = "linux"))]
+    let shortcut_install = serde_json::Value::Null;
+
+    DesktopIntegrationStatus {
+        capabilities,
+        tray,
+        tray_error,
+        shortcut,
+        shortcut_endpoint,
+        shortcut_install,
+        issues,
+    }
+}
+
+fn issues(
+    capabilities: &DesktopCapabilities,
+    tray: TrayBackend,
+    shortcut_registered: bool,
+    shortcut_endpoint: bool,
+) -> Vec<&'static str> {
+    let mut issues = Vec::new();
+    if cfg!(target_os = "linux") {
+        if !capabilities.session_bus {
+            issues.push("no_session_bus");
+        } else if !capabilities.status_notifier_watcher {
+            issues.push("no_status_notifier_watcher");
+        }
+        if tray != TrayBackend::StatusNotifier && !capabilities.app_indicator_library {
+            issues.push("no_app_indicator_library");
+        }
+    }
+    match tray {
+        TrayBackend::None => issues.push("tray_unavailable"),
+        TrayBackend::AppIndicator => issues.push("tray_fallback"),
+        TrayBackend::Native | TrayBackend::StatusNotifier => {}
+    }
+    if !shortcut_registered || !shortcut_endpoint {
+        issues.push("shortcut_unavailable");
+    }
+    issues
+}
+
+#[cfg(target_os = "linux")]
+fn detect_capabilities() -> DesktopCapabilities {
+    let mut capabilities = DesktopCapabilities {
+        desktop: std::env::var("XDG_CURRENT_DESKTOP")
+            .ok()
+            .filter(|value| !value.trim().is_empty()),
+        wayland: std::env::var_os("WAYLAND_DISPLAY").is_some()
+            || std::env::var("XDG_SESSION_TYPE").is_ok_and(|value| value == "wayland"),
+        app_indicator_library: app_indicator_library_available(),
+        ..Default::default()
+    };
+
+    let Ok(connection) = zbus::blocking::Connection::session() else {
+        return capabilities;
+    };
+    capabilities.session_bus = true;
+    capabilities.status_notifier_watcher =
+        name_has_owner(&connection, "org.kde.StatusNotifierWatcher");
+    capabilities.global_shortcuts_portal = global_shortcuts_portal_version(&connection) > 0;
+    capabilities
+}
+
+#[cfg(not(target_os = "linux"))]
+fn detect_capabilities() -> DesktopCapabilities {
+    DesktopCapabilities::default()
+}
+
+#[cfg(target_os = "linux")]
+fn name_has_owner(connection: &zbus::blocking::Connection, name: &str) -> bool {
+    let Ok(proxy) = zbus::blocking::fdo::DBusProxy::new(connection) else {
+        return false;
+    };
+    let Ok(name) = zbus::names::BusName::try_from(name) else {
+        return false;
+    };
+    proxy.name_has_owner(name).unwrap_or(false)
+}
+
+/// Same candidates, in the same order, as `libappindicator-sys`.
+#[cfg(target_os = "linux")]
+fn app_indicator_library_available() -> bool {
+    const CANDIDATES: [&std::ffi::CStr; 2] =
+        [c"libayatana-appindicator3.so.1", c"libappindicator3.so.1"];
+    CANDIDATES.iter().any(|name| {
+        // SAFETY: Probing only; the handle is closed right away.
+        unsafe {
+            let handle = libc::dlopen(name.as_ptr(), libc::RTLD_LAZY);
+            if handle.is_null() {
+                return false;
+            }
+            libc::dlclose(handle);
+            true
+        }
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn global_shortcuts_portal_version(connection: &zbus::blocking::Connection) -> u32 {
+    let proxy = zbus::blocking::Proxy::new(
+        connection,
+        "org.freedesktop.portal.Desktop",
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.GlobalShortcuts",
+    );
+    proxy
+        .ok()
+        .and_then(|proxy| proxy.get_property::<u32>("version").ok())
+        .unwrap_or(0)
+}
+
+/// Whether our capture D-Bus endpoint is up, i.e. desktop shortcut daemons
+/// can actually reach the app. Always true outside Linux.
+fn shortcut_endpoint_reachable() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        let name = format!("com.{}.app", crate::constants::APP_NAME.to_lowercase());
+        zbus::blocking::Connection::session()
+            .map(|connection| name_has_owner(&connection, &name))
+            .unwrap_or(false)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        true
+    }
+}
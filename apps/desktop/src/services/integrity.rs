@@ -0,0 +1,126 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sidecar integrity checks against the checksum manifest shipped in
+//! `resources/binaries`. A corrupt sidecar emits `sidecar-corrupt` with a
+//! repair action so the UI can offer a reinstall instead of a spawn error.
+
+use ops_sidecar_integrity::{verify_file, IntegrityStatus, SidecarManifest};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const SIDECAR_CORRUPT_EVENT: &str = "sidecar-corrupt";
+pub const SIDECAR_CORRUPT_ERROR: &str = "ERR_SIDECAR_CORRUPT";
+
+const REPAIR_URL: &str = "https://github.com/a7mddra/squigit/releases/latest";
+
+#[derive(Debug, Clone, Copy)]
+pub enum SidecarKind {
+    Capture,
+    Ocr,
+    Whisper,
+}
+
+impl SidecarKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Capture => "capture",
+            Self::Ocr => "ocr",
+            Self::Whisper => "whisper",
+        }
+    }
+}
+
+/// (modified, len) of a file when it was last hashed, with the result.
+type CacheEntry = (Option<SystemTime>, u64, IntegrityStatus);
+
+lazy_static::lazy_static! {
+    static ref STATUS_CACHE: Mutex<HashMap<PathBuf, CacheEntry>> = Mutex::new(HashMap::new());
+}
+
+/// Verify a sidecar before spawning it. Sidecars outside the bundled
+/// `binaries/` directory (PATH, brew, winget installs) are not covered by the
+/// manifest and pass through.
+pub fn ensure_sidecar_intact(
+    app: &AppHandle,
+    kind: SidecarKind,
+    path: &Path,
+) -> Result<(), String> {
+    let status = check_sidecar(app, path);
+    if !status.is_corrupt() {
+        return Ok(());
+    }
+
+    log::error!(
+        "Sidecar integrity check failed for {} ({}): {:?}",
+        kind.as_str(),
+        path.display(),
+        status
+    );
+    let _ = app.emit(
+        SIDECAR_CORRUPT_EVENT,
+        serde_json::json!({
+            "sidecar": kind.as_str(),
+            "path": path.to_string_lossy(),
+            "integrity": status,
+            "repair": {
+                "action": "reinstall",
+                "label": "Reinstall Squigit",
+                "url": REPAIR_URL,
+            },
+        }),
+    );
+    Err(SIDECAR_CORRUPT_ERROR.to_string())
+}
+
+/// Startup pass over the bundled capture sidecar. It is the only sidecar
+/// packaged under `binaries/` and listed in the manifest: the OCR and
+/// Whisper sidecars are installed separately, so they are checked before
+/// each spawn and pass as unlisted unless they resolve into `binaries/`.
+pub fn verify_bundled_sidecars(app: &AppHandle) {
+    if let Ok(path) = crate::services::capture::resolve_sidecar_path(app) {
+        let _ = ensure_sidecar_intact(app, SidecarKind::Capture, &path);
+    }
+}
+
+fn check_sidecar(app: &AppHandle, path: &Path) -> IntegrityStatus {
+    let Ok(resource_dir) = app.path().resource_dir() else {
+        return IntegrityStatus::Unlisted;
+    };
+    let binaries_dir = resource_dir.join("binaries");
+    if !path.starts_with(&binaries_dir) {
+        return IntegrityStatus::Unlisted;
+    }
+
+    let fingerprint = std::fs::metadata(path)
+        .ok()
+        .map(|meta| (meta.modified().ok(), meta.len()));
+    if let Some((modified, len)) = fingerprint {
+        if let Ok(cache) = STATUS_CACHE.lock() {
+            if let Some((cached_modified, cached_len, status)) = cache.get(path) {
+                if *cached_modified == modified && *cached_len == len {
+                    return status.clone();
+                }
+            }
+        }
+    }
+
+    let status = match SidecarManifest::load(&binaries_dir) {
+        Ok(Some(manifest)) => verify_file(&manifest, &binaries_dir, path),
+        Ok(None) => IntegrityStatus::Unlisted,
+        Err(e) => {
+            log::warn!("Sidecar manifest unusable: {}", e);
+            IntegrityStatus::Unlisted
+        }
+    };
+
+    if let Some((modified, len)) = fingerprint {
+        if let Ok(mut cache) = STATUS_CACHE.lock() {
+            cache.insert(path.to_path_buf(), (modified, len, status.clone()));
+        }
+    }
+    status
+}
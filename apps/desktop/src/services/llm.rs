@@ -0,0 +1,195 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chat providers behind the `stream_chat` command.
+//!
+//! `gemini`, the default, goes through the brain service with its tools
+//! and uploads. `openai-compatible` streams from any server speaking the
+//! OpenAI chat completions API (Ollama, LM Studio, vLLM) at the
+//! `openAiCompatibleBaseUrl` preference, with the profile's stored
+//! OpenAI-compatible key, if any. `anthropic` streams from Claude with the
+//! profile's stored Anthropic key. All emit the same events on the
+//! request's channel.
+//!
+//! Gemini requests go to the public API unless the `geminiBaseUrl` and
+//! `geminiApiVersion` preferences point them at a proxy or another
+//! version; see [`use_gemini_endpoint_preferences`]. A profile signed in
+//! to Vertex AI sends them to its Google Cloud project instead.
+
+use std::str::FromStr;
+
+use ops_profile_store::security::ApiKeyProvider;
+use ops_profile_store::ProfileStore;
+use ops_squigit_brain::provider::anthropic::AnthropicConfig;
+use ops_squigit_brain::provider::gemini::client::{set_endpoint_source, GeminiEndpoint};
+use ops_squigit_brain::provider::openai::{OpenAiCompatibleConfig, DEFAULT_BASE_URL};
+use ops_squigit_brain::service::StreamChatRequest;
+use tauri::AppHandle;
+
+use crate::services::brain::DesktopBrainService;
+
+const BASE_URL_PREF: &str = "openAiCompatibleBaseUrl";
+const GEMINI_BASE_URL_PREF: &str = "geminiBaseUrl";
+const GEMINI_API_VERSION_PREF: &str = "geminiApiVersion";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LlmProvider {
+    #[default]
+    Gemini,
+    OpenAiCompatible,
+    Claude,
+}
+
+/// Accepts the names of the matching key providers.
+impl FromStr for LlmProvider {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match ApiKeyProvider::from_str(value) {
+            Ok(ApiKeyProvider::GoogleAiStudio) => Ok(Self::Gemini),
+            Ok(ApiKeyProvider::OpenAiCompatible) => Ok(Self::OpenAiCompatible),
+            Ok(ApiKeyProvider::Anthropic) => Ok(Self::Claude),
+            _ => Err(format!("ERR_UNSUPPORTED_PROVIDER: {}", value)),
+        }
+    }
+}
+
+/// A backend that streams chat replies as brain events.
+pub(crate) trait ChatProvider {
+    /// Stream the reply to `request` on its channel and return the model
+    /// that answered.
+    async fn stream_chat(&self, request: StreamChatRequest) -> Result<String, String>;
+}
+
+struct Gemini<'a> {
+    app: AppHandle,
+    brain: &'a DesktopBrainService,
+}
+
+impl ChatProvider for Gemini<'_> {
+    async fn stream_chat(&self, request: StreamChatRequest) -> Result<String, String> {
+        self.brain.stream_chat(self.app.clone(), request).await
+    }
+}
+
+struct OpenAiCompatible<'a> {
+    app: AppHandle,
+    brain: &'a DesktopBrainService,
+    config: OpenAiCompatibleConfig,
+}
+
+impl ChatProvider for OpenAiCompatible<'_> {
+    async fn stream_chat(&self, request: StreamChatRequest) -> Result<String, String> {
+        self.brain
+            .stream_openai_chat(self.app.clone(), &self.config, request)
+            .await
+    }
+}
+
+struct Claude<'a> {
+    app: AppHandle,
+    brain: &'a DesktopBrainService,
+    config: AnthropicConfig,
+}
+
+impl ChatProvider for Claude<'_> {
+    async fn stream_chat(&self, request: StreamChatRequest) -> Result<String, String> {
+        self.brain
+            .stream_claude_chat(self.app.clone(), &self.config, request)
+            .await
+    }
+}
+
+/// Stream a reply from `provider`. Returns the model that answered.
+pub async fn stream_chat(
+    app: &AppHandle,
+    brain: &DesktopBrainService,
+    provider: LlmProvider,
+    request: StreamChatRequest,
+) -> Result<String, String> {
+    match provider {
+        LlmProvider::Gemini => {
+            Gemini {
+                app: app.clone(),
+                brain,
+            }
+            .stream_chat(request)
+            .await
+        }
+        LlmProvider::OpenAiCompatible => {
+            OpenAiCompatible {
+                app: app.clone(),
+                brain,
+                config: openai_compatible_config(app).await?,
+            }
+            .stream_chat(request)
+            .await
+        }
+        LlmProvider::Claude => {
+            Claude {
+                app: app.clone(),
+                brain,
+                config: claude_config().await?,
+            }
+            .stream_chat(request)
+            .await
+        }
+    }
+}
+
+/// The configured server and the active profile's key for it.
+pub async fn openai_compatible_config(app: &AppHandle) -> Result<OpenAiCompatibleConfig, String> {
+    let base_url = crate::utils::read_preferences(app)
+        .and_then(|prefs| {
+            let url = prefs.get(BASE_URL_PREF)?.as_str()?.trim().to_string();
+            (!url.is_empty()).then_some(url)
+        })
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    let api_key =
+        tauri::async_runtime::spawn_blocking(|| stored_key(ApiKeyProvider::OpenAiCompatible))
+            .await
+            .map_err(|e| e.to_string())??;
+    Ok(OpenAiCompatibleConfig { base_url, api_key })
+}
+
+/// Build Gemini request URLs from the saved preferences and the active
+/// profile's Vertex AI settings. They are read for every request, so a
+/// change in settings applies to the next one.
+pub fn use_gemini_endpoint_preferences(app: &AppHandle) {
+    let app = app.clone();
+    set_endpoint_source(move || {
+        let prefs = crate::utils::read_preferences(&app);
+        let pref = |key: &str| prefs.as_ref()?.get(key)?.as_str().map(str::to_string);
+        let endpoint = GeminiEndpoint::new(
+            pref(GEMINI_BASE_URL_PREF).as_deref(),
+            pref(GEMINI_API_VERSION_PREF).as_deref(),
+        );
+        match crate::services::vertex::active_auth() {
+            Ok(Some(auth)) => endpoint.with_vertex(auth),
+            Ok(None) => endpoint,
+            Err(e) => {
+                log::warn!("Vertex AI sign-in unavailable: {}", e);
+                endpoint
+            }
+        }
+    });
+}
+
+/// The active profile's Anthropic key. Claude needs one.
+pub async fn claude_config() -> Result<AnthropicConfig, String> {
+    let api_key = tauri::async_runtime::spawn_blocking(|| stored_key(ApiKeyProvider::Anthropic))
+        .await
+        .map_err(|e| e.to_string())??
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| "ERR_MISSING_API_KEY".to_string())?;
+    Ok(AnthropicConfig::new(api_key))
+}
+
+fn stored_key(provider: ApiKeyProvider) -> Result<Option<String>, String> {
+    let store = ProfileStore::new().map_err(|e| e.to_string())?;
+    let Some(profile) = store.get_active_profile().map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    ops_profile_store::security::get_decrypted_key(&store, provider, &profile.id)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,204 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local API for companion apps, off until enabled in settings.
+//!
+//! The browser extension posts page screenshots to it; each becomes a chat
+//! with the page URL and selected text attached, opened like a screen
+//! capture. Companions authenticate with a token kept in the config
+//! directory, which the user copies into the extension when pairing.
+
+use ops_chat_storage::{ChatData, ChatMetadata, WebSource};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::Arc;
+use svc_local_api::{BrowserCapture, LocalApiConfig, LocalApiHandler, LocalApiServer};
+use tauri::{AppHandle, Emitter, Manager};
+
+const ENABLED_PREF: &str = "localApiEnabled";
+const PORT_PREF: &str = "localApiPort";
+const TOKEN_FILE_NAME: &str = "local_api_token";
+const CAPTURE_TYPE: &str = "browser";
+
+#[derive(Default)]
+pub struct LocalApiState {
+    server: Mutex<Option<LocalApiServer>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    /// For pairing companions.
+    pub token: String,
+}
+
+struct DesktopHandler {
+    app: AppHandle,
+}
+
+impl LocalApiHandler for DesktopHandler {
+    fn browser_capture(&self, capture: BrowserCapture) -> Result<String, String> {
+        let tone = super::tone::detect_image_tone_from_bytes(&capture.image);
+        let stored =
+            ops_squigit_brain::context::media::process_bytes_internal(capture.image, tone)?;
+
+        let title = capture
+            .title
+            .clone()
+            .or_else(|| {
+                url::Url::parse(&capture.url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+            })
+            .unwrap_or_else(|| capture.url.clone());
+        let storage = ops_squigit_brain::context::media::get_active_storage()?;
+        let mut metadata = ChatMetadata::new(title, stored.hash.clone(), None);
+        metadata.image_tone = stored.tone;
+        metadata.capture_type = Some(CAPTURE_TYPE.to_string());
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .map_err(|e| e.to_string())?;
+        storage
+            .save_web_source(
+                &metadata.id,
+                &WebSource {
+                    url: capture.url,
+                    title: capture.title,
+                    selected_text: capture.selected_text,
+                },
+            )
+            .map_err(|e| e.to_string())?;
+
+        let payload = serde_json::json!({
+            "chatId": metadata.id,
+            "imageHash": metadata.image_hash,
+        });
+        super::webhook::notify(
+            super::webhook::WEBHOOK_EVENT_CAPTURE_COMPLETE,
+            payload.clone(),
+        );
+        super::search_index::chat_saved(&self.app, &metadata.id);
+        super::ocr::ocr_after_capture(&self.app, &metadata.id);
+        #[cfg(target_os = "windows")]
+        super::windows_shell::notify_capture_complete(&self.app, &metadata.id);
+        super::startup::with_main_window(&self.app, move |app| {
+            super::tray::show_window(app);
+            super::a11y::polite(app, "capture", "Web capture received, new chat opened");
+            let _ = app.emit("capture-complete", payload);
+        });
+        Ok(metadata.id)
+    }
+}
+
+/// Start the server if it is enabled.
+pub fn start(app: &AppHandle) {
+    if !is_enabled(app) {
+        return;
+    }
+    if let Err(e) = restart(app) {
+        log::warn!("Failed to start local API: {}", e);
+    }
+}
+
+pub fn status(app: &AppHandle) -> Result<LocalApiStatus, String> {
+    let running_port = app
+        .state::<LocalApiState>()
+        .server
+        .lock()
+        .as_ref()
+        .map(LocalApiServer::port);
+    Ok(LocalApiStatus {
+        enabled: is_enabled(app),
+        running: running_port.is_some(),
+        port: running_port.unwrap_or_else(|| configured_port(app)),
+        token: load_or_create_token(app)?,
+    })
+}
+
+/// Turn the server on or off and remember the choice.
+pub fn set_enabled(app: &AppHandle, enabled: bool) -> Result<LocalApiStatus, String> {
+    crate::utils::write_preference(app, ENABLED_PREF, serde_json::json!(enabled))?;
+    if enabled {
+        restart(app)?;
+    } else if let Some(server) = app.state::<LocalApiState>().server.lock().take() {
+        server.stop();
+    }
+    status(app)
+}
+
+/// Replace the token, unpairing every companion.
+pub fn regenerate_token(app: &AppHandle) -> Result<LocalApiStatus, String> {
+    write_token(app, &new_token())?;
+    if app.state::<LocalApiState>().server.lock().is_some() {
+        restart(app)?;
+    }
+    status(app)
+}
+
+fn restart(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<LocalApiState>();
+    let mut server = state.server.lock();
+    if let Some(previous) = server.take() {
+        previous.stop();
+    }
+
+    let config = LocalApiConfig {
+        port: configured_port(app),
+        token: load_or_create_token(app)?,
+    };
+    let handler = Arc::new(DesktopHandler { app: app.clone() });
+    *server = Some(LocalApiServer::start(config, handler).map_err(|e| e.to_string())?);
+    Ok(())
+}
+
+fn is_enabled(app: &AppHandle) -> bool {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(ENABLED_PREF).and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+fn configured_port(app: &AppHandle) -> u16 {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(PORT_PREF).and_then(|v| v.as_u64()))
+        .and_then(|port| u16::try_from(port).ok())
+        .filter(|port| *port != 0)
+        .unwrap_or(svc_local_api::DEFAULT_PORT)
+}
+
+fn new_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+fn load_or_create_token(app: &AppHandle) -> Result<String, String> {
+    let path = crate::utils::get_app_config_dir(app).join(TOKEN_FILE_NAME);
+    if let Ok(token) = std::fs::read_to_string(&path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            return Ok(token.to_string());
+        }
+    }
+    let token = new_token();
+    write_token(app, &token)?;
+    Ok(token)
+}
+
+fn write_token(app: &AppHandle, token: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let config_dir = crate::utils::get_app_config_dir(app);
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(config_dir.join(TOKEN_FILE_NAME))
+        .map_err(|e| e.to_string())?;
+    file.write_all(token.as_bytes()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,136 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! "Analyze with SnapLLM" in the macOS Services menu.
+//!
+//! `Info.plist` declares the service for image files (Finder) and image
+//! data (Preview and other apps). The provider registered here receives the
+//! pasteboard and opens the image like one passed on the command line.
+
+use std::ffi::{c_void, CStr, CString};
+use std::sync::OnceLock;
+
+use objc::declare::ClassDecl;
+use objc::runtime::{Class, Object, Sel};
+use objc::{class, msg_send, sel, sel_impl};
+use tauri::{AppHandle, Manager};
+
+use crate::state::AppState;
+
+/// Pasteboard type Finder uses for file paths.
+const FILENAMES_PBOARD_TYPE: &str = "NSFilenamesPboardType";
+/// Image data types accepted when no file is sent, in order of preference.
+const IMAGE_DATA_TYPES: &[&str] = &["public.png", "public.tiff"];
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    fn NSUpdateDynamicServices();
+}
+
+/// `analyzeImage:userData:error:`; the error out-parameter is an `NSString **`.
+type ServiceMethod = extern "C" fn(&Object, Sel, *mut Object, *mut Object, *mut c_void);
+
+/// Install the services provider. Must run on the main thread.
+pub fn register(app: &AppHandle) {
+    if APP.set(app.clone()).is_err() {
+        return;
+    }
+    let Some(class) = provider_class() else {
+        log::warn!("Services provider class is already registered");
+        return;
+    };
+    unsafe {
+        let provider: *mut Object = msg_send![class, new];
+        let ns_app: *mut Object = msg_send![class!(NSApplication), sharedApplication];
+        let () = msg_send![ns_app, setServicesProvider: provider];
+        NSUpdateDynamicServices();
+    }
+    log::info!("macOS Services provider registered");
+}
+
+fn provider_class() -> Option<&'static Class> {
+    let mut decl = ClassDecl::new("SquigitServicesProvider", class!(NSObject))?;
+    unsafe {
+        // Selector named by `NSMessage` in Info.plist.
+        decl.add_method(
+            sel!(analyzeImage:userData:error:),
+            analyze_image as ServiceMethod,
+        );
+    }
+    Some(decl.register())
+}
+
+extern "C" fn analyze_image(
+    _this: &Object,
+    _cmd: Sel,
+    pasteboard: *mut Object,
+    _user_data: *mut Object,
+    _error: *mut c_void,
+) {
+    let Some(app) = APP.get() else {
+        return;
+    };
+    match unsafe { image_from_pasteboard(app, pasteboard) } {
+        Ok(path) => {
+            log::info!("Image received from the Services menu");
+            crate::services::image::open_external_image(app, path);
+        }
+        Err(e) => log::warn!("Services request without a usable image: {}", e),
+    }
+}
+
+/// Path of the first image file on the pasteboard, or of the image data
+/// after storing it.
+unsafe fn image_from_pasteboard(
+    app: &AppHandle,
+    pasteboard: *mut Object,
+) -> Result<String, String> {
+    if pasteboard.is_null() {
+        return Err("ERR_NO_PASTEBOARD".to_string());
+    }
+
+    let files: *mut Object =
+        msg_send![pasteboard, propertyListForType: ns_string(FILENAMES_PBOARD_TYPE)];
+    if !files.is_null() {
+        let count: usize = msg_send![files, count];
+        if count > 0 {
+            let first: *mut Object = msg_send![files, objectAtIndex: 0usize];
+            if let Some(path) = rust_string(first) {
+                return Ok(path);
+            }
+        }
+    }
+
+    for data_type in IMAGE_DATA_TYPES {
+        let data: *mut Object = msg_send![pasteboard, dataForType: ns_string(data_type)];
+        if data.is_null() {
+            continue;
+        }
+        let len: usize = msg_send![data, length];
+        let bytes: *const u8 = msg_send![data, bytes];
+        if bytes.is_null() || len == 0 {
+            continue;
+        }
+        let bytes = std::slice::from_raw_parts(bytes, len).to_vec();
+        let stored =
+            crate::services::image::process_bytes_internal(bytes, &app.state::<AppState>())?;
+        return Ok(stored.path);
+    }
+
+    Err("ERR_NO_IMAGE".to_string())
+}
+
+unsafe fn ns_string(value: &str) -> *mut Object {
+    let value = CString::new(value).unwrap_or_default();
+    msg_send![class!(NSString), stringWithUTF8String: value.as_ptr()]
+}
+
+unsafe fn rust_string(value: *mut Object) -> Option<String> {
+    if value.is_null() {
+        return None;
+    }
+    let utf8: *const std::os::raw::c_char = msg_send![value, UTF8String];
+    (!utf8.is_null()).then(|| CStr::from_ptr(utf8).to_string_lossy().into_owned())
+}
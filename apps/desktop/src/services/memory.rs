@@ -0,0 +1,75 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Memory pressure handling.
+//!
+//! When the system reports memory pressure, rebuildable caches (provider
+//! file handles, the model list) are dropped and background work such as
+//! the HUD pauses until pressure is back to normal. Level changes are
+//! announced with `memory-pressure-changed`.
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use sys_memory_pressure::{MemoryPressure, MemoryPressureWatcher};
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const MEMORY_PRESSURE_CHANGED_EVENT: &str = "memory-pressure-changed";
+
+#[derive(Default)]
+pub struct MemoryPressureState {
+    watcher: Mutex<Option<MemoryPressureWatcher>>,
+    under_pressure: AtomicBool,
+}
+
+impl MemoryPressureState {
+    /// Whether background work should hold off for now.
+    pub fn background_work_paused(&self) -> bool {
+        self.under_pressure.load(Ordering::SeqCst)
+    }
+}
+
+pub fn start(app: &AppHandle) {
+    let handle = app.clone();
+    let watcher = MemoryPressureWatcher::start(move |level| {
+        let under_pressure = level != MemoryPressure::Normal;
+        handle
+            .state::<MemoryPressureState>()
+            .under_pressure
+            .store(under_pressure, Ordering::SeqCst);
+        let _ = handle.emit(
+            MEMORY_PRESSURE_CHANGED_EVENT,
+            serde_json::json!({
+                "level": format!("{:?}", level).to_lowercase(),
+                "backgroundPaused": under_pressure,
+            }),
+        );
+
+        if under_pressure {
+            let handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let dropped = clear_caches(&handle).await;
+                log::info!(
+                    "Memory pressure {:?}: dropped {} cache entries, background work paused",
+                    level,
+                    dropped
+                );
+            });
+        } else {
+            log::info!("Memory pressure back to normal, resuming background work");
+        }
+    });
+
+    match watcher {
+        Ok(watcher) => {
+            *app.state::<MemoryPressureState>().watcher.lock() = Some(watcher);
+        }
+        Err(e) => log::warn!("Memory pressure monitor unavailable (non-fatal): {}", e),
+    }
+}
+
+/// Drop every rebuildable cache and return how many entries were removed.
+pub async fn clear_caches(app: &AppHandle) -> usize {
+    app.state::<crate::services::brain::DesktopBrainService>()
+        .clear_caches()
+        .await
+}
@@ -0,0 +1,76 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local usage metrics.
+//!
+//! Subsystems count each use of a feature and whether it failed. Counts
+//! stay in the profile store's `metrics.json` and are never sent: the
+//! only way out is `export_local_metrics`, which hands the user an
+//! anonymized copy to read before they share it themselves. Setting the
+//! `localUsageMetrics` preference to `false` stops counting altogether.
+
+use std::sync::Mutex;
+
+use ops_profile_store::{AnonymizedMetrics, LocalMetrics, ProfileStore};
+use tauri::AppHandle;
+
+const ENABLED_PREF: &str = "localUsageMetrics";
+/// Error of a stream or job stopped by the user.
+const CANCELLED: &str = "CANCELLED";
+
+/// `record_local_metric` reads then rewrites the file.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Count one use of `feature`, failed if `result` is an error other than
+/// a cancellation. Runs in the background and never fails the caller.
+pub fn record<T>(app: &AppHandle, feature: &'static str, result: &Result<T, String>) {
+    let failed = matches!(result, Err(e) if e != CANCELLED);
+    record_use(app, feature, failed);
+}
+
+pub fn record_use(app: &AppHandle, feature: &'static str, failed: bool) {
+    if !enabled(app) {
+        return;
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let result =
+            ProfileStore::new().and_then(|store| store.record_local_metric(feature, failed));
+        if let Err(e) = result {
+            log::warn!("Failed to record {} metric: {}", feature, e);
+        }
+    });
+}
+
+pub async fn load() -> Result<LocalMetrics, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        store.load_local_metrics().map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// The counters with dates dropped, tagged with the app version and OS.
+pub async fn anonymized(app: &AppHandle) -> Result<AnonymizedMetrics, String> {
+    let version = app.package_info().version.to_string();
+    Ok(load()
+        .await?
+        .anonymized(&version, std::env::consts::OS, chrono::Utc::now()))
+}
+
+pub async fn reset() -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        store.reset_local_metrics().map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(ENABLED_PREF)?.as_bool())
+        .unwrap_or(true)
+}
@@ -1,12 +1,52 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod a11y;
+pub mod actions;
 pub mod audio;
+pub mod autostart;
+pub mod batch;
+pub mod battery;
 pub mod brain;
 pub mod capture;
+pub mod clipboard;
+pub mod conversation;
+pub mod deep_link;
+pub mod ephemeral;
+#[cfg(target_os = "linux")]
+pub mod gnome_search;
+pub mod hud;
+pub mod idle;
 pub mod image;
+pub mod integration;
+pub mod integrity;
+pub mod llm;
+pub mod local_api;
+#[cfg(target_os = "macos")]
+pub mod macos_services;
+pub mod memory;
+pub mod metrics;
 pub mod ocr;
+pub mod ocr_backfill;
+pub mod permissions;
+pub mod plugins;
+pub mod policy;
+pub mod power;
+pub mod priority;
+pub mod realtime;
+pub mod recovery;
+pub mod retention;
+pub mod search_index;
+pub mod session;
+pub mod shortcut;
+pub mod startup;
 pub mod theme;
 pub mod tone;
 pub mod tray;
+pub mod tts;
+pub mod vertex;
+pub mod voice_commands;
+pub mod webhook;
 pub mod window;
+#[cfg(target_os = "windows")]
+pub mod windows_shell;
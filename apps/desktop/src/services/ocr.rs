@@ -1,13 +1,50 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::services::a11y::DownloadAnnouncer;
+use crate::services::integrity::{ensure_sidecar_intact, SidecarKind};
+use crate::services::webhook;
+use ops_chat_storage::{
+    AUTO_OCR_DISABLED_MODEL_ID, OcrRegion, OcrTextLayout, is_supported_ocr_model_id, ocr_text,
+};
+use ops_squigit_ocr::formula::{FormulaRequest, FormulaResult, run_formula_pass};
+use ops_squigit_ocr::glossary::{GlossaryTerm, apply_glossary};
 use ops_squigit_ocr::models::{DownloadProgressPayload, ModelError, ModelManager};
-use ops_squigit_ocr::ocr::{OcrExecutionResult, OcrRequest, OcrRuntime, OcrRuntimeError};
+use ops_squigit_ocr::ocr::{
+    OcrBox, OcrExecutionResult, OcrLimits, OcrRegionCallback, OcrRequest, OcrRuntime,
+    OcrRuntimeError, boxes_to_storage_regions,
+};
 use ops_squigit_ocr::sidecar::{
     DEFAULT_OCR_VERSION_REQUIREMENT, SidecarError, check_ocr_version_requirement,
     read_sidecar_version, resolve_sidecar_path,
 };
+use parking_lot::Mutex;
 use std::path::{Path, PathBuf};
+use sys_process_priority::SidecarRole;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// One region from a streaming `ocr_image` call or a capture's background
+/// OCR (`{ imagePath, chatId, index, detected, region }`).
+pub const OCR_PROGRESS_EVENT: &str = "ocr-progress";
+/// Error of a job stopped with `cancel_ocr_job` or replaced by a newer one.
+pub const OCR_CANCELLED_ERROR: &str = "OCR job was cancelled";
+/// The chat's image was discarded, so there is nothing to scan again.
+pub const IMAGE_DISCARDED_ERROR: &str = "ERR_IMAGE_DISCARDED";
+/// A chat's OCR for a newly chosen language finished or failed
+/// (`{ chatId, modelId, error }`).
+pub const CHAT_OCR_UPDATED_EVENT: &str = "chat-ocr-updated";
+/// A new capture's background OCR finished (`{ chatId, modelId, regions }`),
+/// or failed (`{ chatId, modelId, error }`).
+pub const OCR_READY_EVENT: &str = "ocr-ready";
+
+const OCR_LIMITS_PREF: &str = "ocrLimits";
+const OCR_ENABLED_PREF: &str = "ocrEnabled";
+const OCR_LANGUAGE_PREF: &str = "ocrLanguage";
+const DEFAULT_OCR_MODEL_ID: &str = "pp-ocr-v5-en";
+
+/// Chats whose OCR after capture is queued or running. Cancelling all
+/// jobs leaves these alone; only cancelling the chat's job stops one.
+static CAPTURE_OCR_CHATS: Mutex<Vec<String>> = Mutex::new(Vec::new());
 
 pub struct DesktopOcrService {
     model_manager: ModelManager,
@@ -26,6 +63,14 @@ impl DesktopOcrService {
         self.model_manager.start_monitor();
     }
 
+    pub fn handle_resume(&self) {
+        self.model_manager.handle_resume();
+    }
+
+    pub fn set_idle(&self, idle: bool) {
+        self.model_manager.set_idle(idle);
+    }
+
     pub async fn download_model<F>(
         &self,
         url: &str,
@@ -86,19 +131,363 @@ impl DesktopOcrService {
         read_sidecar_version(sidecar_path).map_err(map_sidecar_error)
     }
 
-    pub async fn run_ocr(&self, request: OcrRequest) -> Result<OcrExecutionResult, String> {
-        self.runtime
-            .run(request)
-            .await
-            .map_err(map_ocr_runtime_error)
+    /// Check the sidecar, then OCR `image_path` in the background priority
+    /// class and apply the active glossary. With `on_region`, the sidecar
+    /// streams regions as it goes; those are passed on uncorrected. A
+    /// `job_id` (usually the chat ID) makes the job cancellable on its own
+    /// and replaces a job still running under the same ID.
+    pub async fn recognize(
+        &self,
+        app: &AppHandle,
+        image_path: PathBuf,
+        job_id: Option<String>,
+        model_name: Option<&str>,
+        on_region: Option<OcrRegionCallback>,
+    ) -> Result<Vec<OcrBox>, String> {
+        let resource_dir = app
+            .path()
+            .resource_dir()
+            .map_err(|e| format!("Failed to get resource dir: {}", e))?;
+
+        let (sidecar_path, runtime_dir) = self.resolve_sidecar_path(&resource_dir);
+
+        if sidecar_path.is_absolute() && !sidecar_path.exists() {
+            return Err("ERR_MISSING_OCR_PACKAGE".to_string());
+        }
+
+        ensure_sidecar_intact(app, SidecarKind::Ocr, &sidecar_path)?;
+        self.ensure_sidecar_version_compatible(&sidecar_path)?;
+
+        let image_label = image_path.to_string_lossy().into_owned();
+        let request = OcrRequest {
+            job_id,
+            sidecar_path,
+            runtime_dir,
+            image_path,
+            rec_model_dir_override: self.resolve_rec_model_dir_override(model_name),
+            limits: ocr_limits(app),
+            priority: crate::services::priority::sidecar_priority(app, SidecarRole::Background),
+        };
+        let result = self.run_ocr(request, on_region).await?;
+
+        let mut boxes = result.boxes;
+        let glossary: Vec<GlossaryTerm> = crate::services::brain::active_glossary()
+            .into_iter()
+            .map(|entry| GlossaryTerm {
+                term: entry.term,
+                variants: entry.variants,
+            })
+            .collect();
+        let corrected = apply_glossary(&mut boxes, &glossary);
+        if corrected > 0 {
+            log::info!("OCR: glossary corrected {} word(s)", corrected);
+        }
+
+        let mut text = ocr_text(&boxes_to_storage_regions(&boxes), OcrTextLayout::Plain);
+        if crate::services::brain::redact_pii(app) {
+            text = ops_redaction::redact(&text);
+        }
+        webhook::notify(
+            webhook::WEBHOOK_EVENT_OCR_DONE,
+            serde_json::json!({
+                "imagePath": image_label,
+                "model": model_name,
+                "regionCount": boxes.len(),
+                "text": text,
+            }),
+        );
+        Ok(boxes)
+    }
+
+    pub async fn run_ocr(
+        &self,
+        request: OcrRequest,
+        on_region: Option<OcrRegionCallback>,
+    ) -> Result<OcrExecutionResult, String> {
+        let result = match on_region {
+            Some(on_region) => self.runtime.run_streaming(request, on_region).await,
+            None => self.runtime.run(request).await,
+        };
+        result.map_err(map_ocr_runtime_error)
     }
 
-    pub async fn cancel_ocr_job(&self) -> Result<(), String> {
-        self.runtime
-            .cancel_current_job()
+    pub async fn run_formula_pass(
+        &self,
+        request: FormulaRequest,
+    ) -> Result<Vec<FormulaResult>, String> {
+        run_formula_pass(request)
             .await
             .map_err(map_ocr_runtime_error)
     }
+
+    /// Whether a job with this ID is queued or running.
+    pub async fn is_job_queued(&self, job_id: &str) -> bool {
+        self.runtime.job_ids().await.iter().any(|id| id == job_id)
+    }
+
+    /// Cancel one job, or every queued and running job except the OCR of
+    /// new captures without an ID. Returns how many were cancelled.
+    pub async fn cancel_ocr_job(&self, job_id: Option<&str>) -> usize {
+        match job_id {
+            Some(job_id) => usize::from(self.runtime.cancel_job(job_id).await),
+            None if CAPTURE_OCR_CHATS.lock().is_empty() => self.runtime.cancel_all_jobs().await,
+            None => {
+                let mut cancelled = 0;
+                for job_id in self.runtime.job_ids().await {
+                    if !is_capture_ocr_pending(&job_id) {
+                        cancelled += usize::from(self.runtime.cancel_job(&job_id).await);
+                    }
+                }
+                cancelled
+            }
+        }
+    }
+}
+
+/// OCR a chat's image with `model_id` in the background and save the
+/// regions under it, downloading the model first if it is not installed.
+/// A chat that already has regions for the model keeps them. Emits
+/// `chat-ocr-updated` when done.
+pub fn rerun_chat_ocr(app: &AppHandle, chat_id: String, model_id: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = run_chat_ocr(&app, &chat_id, &model_id).await;
+        if let Err(e) = &result {
+            log::warn!("OCR of chat {} with {} failed: {}", chat_id, model_id, e);
+        }
+        let _ = app.emit(
+            CHAT_OCR_UPDATED_EVENT,
+            serde_json::json!({
+                "chatId": chat_id,
+                "modelId": model_id,
+                "error": result.err(),
+            }),
+        );
+    });
+}
+
+async fn run_chat_ocr(app: &AppHandle, chat_id: &str, model_id: &str) -> Result<(), String> {
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    if storage
+        .get_ocr_data(chat_id, model_id)
+        .map_err(|e| e.to_string())?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    let ocr = app.state::<DesktopOcrService>();
+    if !ocr.is_model_installed(model_id) {
+        crate::services::battery::wait_for_download(app, model_id).await?;
+        let mut announcer = DownloadAnnouncer::new(app);
+        let result = ocr
+            .download_model("", model_id, |payload| {
+                announcer.progress(&payload.status, payload.progress);
+                let _ = app.emit("download-progress", payload);
+            })
+            .await;
+        announcer.finished(&result);
+        result?;
+    }
+
+    let chat = storage.load_chat(chat_id).map_err(|e| e.to_string())?;
+    let image_path = storage
+        .chat_image_path(&chat.metadata)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| IMAGE_DISCARDED_ERROR.to_string())?;
+    let boxes = ocr
+        .recognize(
+            app,
+            image_path.into(),
+            Some(chat_id.to_string()),
+            Some(model_id),
+            None,
+        )
+        .await?;
+
+    let regions = boxes_to_storage_regions(&boxes);
+    storage
+        .save_ocr_data(chat_id, model_id, &regions)
+        .map_err(|e| e.to_string())?;
+    crate::services::plugins::ocr_saved(app, chat_id, model_id, &regions);
+    Ok(())
+}
+
+/// Start OCR of a newly captured chat with the `ocrLanguage` preference in
+/// the background, so the text is usually there before the OCR panel is
+/// opened. With `ocrEnabled` off, records that automatic OCR is off for the
+/// chat instead. A model that is not installed is left for the OCR panel
+/// to download. Emits `ocr-ready` once the regions are saved, without
+/// running OCR again when the chat already has them.
+///
+/// Call before announcing the capture: the chat counts as pending from
+/// here on, see [`is_capture_ocr_pending`].
+pub fn ocr_after_capture(app: &AppHandle, chat_id: &str) {
+    let prefs = crate::utils::read_preferences(app);
+    let enabled = prefs
+        .as_ref()
+        .and_then(|prefs| prefs.get(OCR_ENABLED_PREF))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(true);
+    if !enabled {
+        let result = ops_squigit_brain::context::media::get_active_storage().and_then(|storage| {
+            storage
+                .save_ocr_data(chat_id, AUTO_OCR_DISABLED_MODEL_ID, &[])
+                .map_err(|e| e.to_string())
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to turn off OCR for chat {}: {}", chat_id, e);
+        }
+        return;
+    }
+
+    let model_id = prefs
+        .as_ref()
+        .and_then(|prefs| prefs.get(OCR_LANGUAGE_PREF))
+        .and_then(serde_json::Value::as_str)
+        .filter(|id| is_supported_ocr_model_id(id))
+        .unwrap_or(DEFAULT_OCR_MODEL_ID)
+        .to_string();
+    if !app
+        .state::<DesktopOcrService>()
+        .is_model_installed(&model_id)
+    {
+        return;
+    }
+
+    let chat_id = chat_id.to_string();
+    CAPTURE_OCR_CHATS.lock().push(chat_id.clone());
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = capture_ocr(&app, &chat_id, &model_id).await;
+        CAPTURE_OCR_CHATS.lock().retain(|id| *id != chat_id);
+        let (regions, error) = match result {
+            Ok(regions) => (Some(regions), None),
+            Err(e) => {
+                if e != OCR_CANCELLED_ERROR {
+                    log::warn!("OCR of captured chat {} failed: {}", chat_id, e);
+                }
+                (None, Some(e))
+            }
+        };
+        let _ = app.emit(
+            OCR_READY_EVENT,
+            serde_json::json!({
+                "chatId": chat_id,
+                "modelId": model_id,
+                "regions": regions,
+                "error": error,
+            }),
+        );
+    });
+}
+
+/// Whether the chat's OCR after capture is queued or running.
+pub fn is_capture_ocr_pending(chat_id: &str) -> bool {
+    CAPTURE_OCR_CHATS.lock().iter().any(|id| id == chat_id)
+}
+
+async fn capture_ocr(
+    app: &AppHandle,
+    chat_id: &str,
+    model_id: &str,
+) -> Result<Vec<OcrRegion>, String> {
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    if let Some(regions) = storage
+        .get_ocr_data(chat_id, model_id)
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(regions);
+    }
+
+    let chat = storage.load_chat(chat_id).map_err(|e| e.to_string())?;
+    let image_path = storage
+        .chat_image_path(&chat.metadata)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| IMAGE_DISCARDED_ERROR.to_string())?;
+    let on_region: OcrRegionCallback = {
+        let handle = app.clone();
+        let image_path = image_path.clone();
+        let chat_id = chat_id.to_string();
+        Box::new(
+            move |index: usize, detected: Option<usize>, region: &OcrBox| {
+                emit_ocr_progress(
+                    &handle,
+                    &image_path,
+                    Some(&chat_id),
+                    index,
+                    detected,
+                    region,
+                );
+            },
+        )
+    };
+    let boxes = app
+        .state::<DesktopOcrService>()
+        .recognize(
+            app,
+            image_path.into(),
+            Some(chat_id.to_string()),
+            Some(model_id),
+            Some(on_region),
+        )
+        .await?;
+
+    let regions = boxes_to_storage_regions(&boxes);
+    storage
+        .save_ocr_data(chat_id, model_id, &regions)
+        .map_err(|e| e.to_string())?;
+    crate::services::plugins::ocr_saved(app, chat_id, model_id, &regions);
+    Ok(regions)
+}
+
+/// Emit one streamed region as `ocr-progress`. `detected` is the number of
+/// text lines found, once known; fewer regions may follow.
+pub fn emit_ocr_progress(
+    app: &AppHandle,
+    image_path: &str,
+    chat_id: Option<&str>,
+    index: usize,
+    detected: Option<usize>,
+    region: &OcrBox,
+) {
+    let _ = app.emit(
+        OCR_PROGRESS_EVENT,
+        serde_json::json!({
+            "imagePath": image_path,
+            "chatId": chat_id,
+            "index": index,
+            "detected": detected,
+            "region": region,
+        }),
+    );
+}
+
+/// The saved OCR resource limits. Missing fields use the defaults, and an
+/// invalid saved value is ignored as a whole.
+pub fn ocr_limits(app: &AppHandle) -> OcrLimits {
+    let Some(value) = crate::utils::read_preferences(app)
+        .and_then(|mut prefs| prefs.get_mut(OCR_LIMITS_PREF).map(serde_json::Value::take))
+    else {
+        return OcrLimits::default();
+    };
+    match serde_json::from_value::<OcrLimits>(value) {
+        Ok(limits) if limits.validate().is_ok() => limits,
+        _ => {
+            log::warn!("Ignoring invalid {} preference", OCR_LIMITS_PREF);
+            OcrLimits::default()
+        }
+    }
+}
+
+pub fn save_ocr_limits(app: &AppHandle, limits: &OcrLimits) -> Result<(), String> {
+    limits.validate()?;
+
+    crate::utils::write_preference(
+        app,
+        OCR_LIMITS_PREF,
+        serde_json::to_value(limits).map_err(|e| e.to_string())?,
+    )
 }
 
 fn map_sidecar_error(error: SidecarError) -> String {
@@ -112,7 +501,7 @@ fn map_sidecar_error(error: SidecarError) -> String {
 fn map_ocr_runtime_error(error: OcrRuntimeError) -> String {
     match error {
         OcrRuntimeError::MissingPackage => "ERR_MISSING_OCR_PACKAGE".to_string(),
-        OcrRuntimeError::Cancelled => "OCR job was cancelled".to_string(),
+        OcrRuntimeError::Cancelled => OCR_CANCELLED_ERROR.to_string(),
         OcrRuntimeError::Message(message) => message,
     }
 }
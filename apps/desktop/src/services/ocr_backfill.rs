@@ -0,0 +1,210 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! OCR backfill for a newly installed model.
+//!
+//! Chats captured before a model was installed have no regions for it. The
+//! backfill scans them one at a time in the background priority class and
+//! emits `ocr-backfill-progress` per chat and `ocr-backfill-done` at the end.
+//! The model is recorded in `ocr_backfill.json` until the run ends, so a run
+//! cut short by quitting the app picks up again at the next start. Chats
+//! scanned before that already have their regions and are skipped.
+
+use crate::services::ocr::{DesktopOcrService, OCR_CANCELLED_ERROR};
+use ops_chat_storage::{is_supported_ocr_model_id, ChatMetadata, ChatStorage};
+use ops_squigit_ocr::ocr::boxes_to_storage_regions;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// One chat was scanned or failed, see [`OcrBackfillProgress`].
+pub const OCR_BACKFILL_PROGRESS_EVENT: &str = "ocr-backfill-progress";
+/// The run ended (`{ modelId, scanned, error }`).
+pub const OCR_BACKFILL_DONE_EVENT: &str = "ocr-backfill-done";
+
+const STATE_FILE_NAME: &str = "ocr_backfill.json";
+/// Keeps backfill jobs apart from the chat's own OCR job, which uses the
+/// bare chat ID.
+const JOB_ID_PREFIX: &str = "ocr-backfill:";
+
+#[derive(Default)]
+pub struct OcrBackfillState {
+    running: AtomicBool,
+    stop: AtomicBool,
+    current_job: Mutex<Option<String>>,
+}
+
+/// Progress for one processed chat.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrBackfillProgress {
+    pub model_id: String,
+    pub chat_id: String,
+    pub region_count: Option<usize>,
+    pub error: Option<String>,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Start scanning every chat without regions for `model_id`. Fails when
+/// the model is not installed or a backfill is already running.
+pub fn start(app: &AppHandle, model_id: String) -> Result<(), String> {
+    if !is_supported_ocr_model_id(&model_id) {
+        return Err(format!("Unsupported OCR model: {}", model_id));
+    }
+    if !app
+        .state::<DesktopOcrService>()
+        .is_model_installed(&model_id)
+    {
+        return Err(format!("OCR model is not installed: {}", model_id));
+    }
+    let state = app.state::<OcrBackfillState>();
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Err("OCR backfill is already running".to_string());
+    }
+    state.stop.store(false, Ordering::SeqCst);
+    if let Err(e) = write_pending(app, Some(&model_id)) {
+        log::warn!("Failed to record OCR backfill: {}", e);
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = run(&app, &model_id).await;
+        if let Err(e) = write_pending(&app, None) {
+            log::warn!("Failed to clear OCR backfill record: {}", e);
+        }
+        app.state::<OcrBackfillState>()
+            .running
+            .store(false, Ordering::SeqCst);
+        match &result {
+            Ok(scanned) => log::info!("OCR backfill with {}: {} chat(s)", model_id, scanned),
+            Err(e) => log::warn!("OCR backfill with {} failed: {}", model_id, e),
+        }
+        let _ = app.emit(
+            OCR_BACKFILL_DONE_EVENT,
+            serde_json::json!({
+                "modelId": model_id,
+                "scanned": result.as_ref().ok(),
+                "error": result.as_ref().err(),
+            }),
+        );
+    });
+    Ok(())
+}
+
+/// Stop after the chat being scanned, whose OCR job is cancelled.
+pub async fn stop(app: &AppHandle) {
+    let state = app.state::<OcrBackfillState>();
+    state.stop.store(true, Ordering::SeqCst);
+    let job_id = state.current_job.lock().clone();
+    if let Some(job_id) = job_id {
+        app.state::<DesktopOcrService>()
+            .cancel_ocr_job(Some(&job_id))
+            .await;
+    }
+}
+
+/// Pick up a backfill that was running when the app quit.
+pub fn resume(app: &AppHandle) {
+    let Some(model_id) = read_pending(app) else {
+        return;
+    };
+    log::info!("Resuming OCR backfill with {}", model_id);
+    if let Err(e) = start(app, model_id) {
+        log::warn!("Failed to resume OCR backfill: {}", e);
+        let _ = write_pending(app, None);
+    }
+}
+
+async fn run(app: &AppHandle, model_id: &str) -> Result<usize, String> {
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    let pending = storage
+        .chats_missing_ocr(model_id)
+        .map_err(|e| e.to_string())?;
+    let state = app.state::<OcrBackfillState>();
+
+    let total = pending.len();
+    let mut scanned = 0usize;
+    for (index, metadata) in pending.into_iter().enumerate() {
+        if state.stop.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let mut progress = OcrBackfillProgress {
+            model_id: model_id.to_string(),
+            chat_id: metadata.id.clone(),
+            region_count: None,
+            error: None,
+            completed: index + 1,
+            total,
+        };
+        match scan_chat(app, &storage, &metadata, model_id).await {
+            Ok(region_count) => {
+                progress.region_count = Some(region_count);
+                scanned += 1;
+            }
+            // Cancelled with the other OCR jobs, e.g. from the OCR panel.
+            Err(e) if e == OCR_CANCELLED_ERROR => break,
+            Err(e) => progress.error = Some(e),
+        }
+        let _ = app.emit(OCR_BACKFILL_PROGRESS_EVENT, &progress);
+    }
+    Ok(scanned)
+}
+
+async fn scan_chat(
+    app: &AppHandle,
+    storage: &ChatStorage,
+    metadata: &ChatMetadata,
+    model_id: &str,
+) -> Result<usize, String> {
+    // The chat may have been scanned from the OCR panel since the run began.
+    if let Some(regions) = storage
+        .get_ocr_data(&metadata.id, model_id)
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(regions.len());
+    }
+
+    let image_path = storage
+        .get_image_path(&metadata.image_hash)
+        .map_err(|e| e.to_string())?;
+    let job_id = format!("{}{}", JOB_ID_PREFIX, metadata.id);
+    let state = app.state::<OcrBackfillState>();
+    *state.current_job.lock() = Some(job_id.clone());
+    let boxes = app
+        .state::<DesktopOcrService>()
+        .recognize(app, image_path.into(), Some(job_id), Some(model_id), None)
+        .await;
+    *state.current_job.lock() = None;
+
+    let regions = boxes_to_storage_regions(&boxes?);
+    storage
+        .save_ocr_data(&metadata.id, model_id, &regions)
+        .map_err(|e| e.to_string())?;
+    crate::services::plugins::ocr_saved(app, &metadata.id, model_id, &regions);
+    Ok(regions.len())
+}
+
+fn read_pending(app: &AppHandle) -> Option<String> {
+    let path = crate::utils::get_app_config_dir(app).join(STATE_FILE_NAME);
+    let content = std::fs::read_to_string(path).ok()?;
+    let state: serde_json::Value = serde_json::from_str(&content).ok()?;
+    state.get("modelId")?.as_str().map(str::to_string)
+}
+
+fn write_pending(app: &AppHandle, model_id: Option<&str>) -> Result<(), String> {
+    let config_dir = crate::utils::get_app_config_dir(app);
+    let path = config_dir.join(STATE_FILE_NAME);
+    let Some(model_id) = model_id else {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    };
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&serde_json::json!({ "modelId": model_id }))
+        .map_err(|e| e.to_string())?;
+    std::fs::write(path, content).map_err(|e| e.to_string())
+}
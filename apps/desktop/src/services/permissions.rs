@@ -0,0 +1,244 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Platform permission pre-flight.
+//!
+//! On macOS, capture silently returns a wallpaper-only image without Screen
+//! Recording permission, STT records silence without Microphone permission,
+//! and window text grabbing needs Accessibility. The UI checks these up front
+//! and deep-links into System Settings; while the user is there, a watcher
+//! re-checks and emits `platform-permissions-changed` once anything flips.
+//! Other platforms report every permission as not applicable.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+pub const PERMISSIONS_CHANGED_EVENT: &str = "platform-permissions-changed";
+pub const PERMISSION_REQUIRED_EVENT: &str = "platform-permission-required";
+pub const SCREEN_RECORDING_ERROR: &str = "ERR_SCREEN_RECORDING_PERMISSION";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionState {
+    Granted,
+    Denied,
+    NotDetermined,
+    Restricted,
+    NotApplicable,
+}
+
+impl PermissionState {
+    fn is_satisfied(self) -> bool {
+        matches!(self, Self::Granted | Self::NotApplicable)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlatformPermission {
+    ScreenRecording,
+    Microphone,
+    Accessibility,
+}
+
+impl PlatformPermission {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "screen_recording" => Ok(Self::ScreenRecording),
+            "microphone" => Ok(Self::Microphone),
+            "accessibility" => Ok(Self::Accessibility),
+            other => Err(format!("Unknown permission: {}", other)),
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn settings_url(self) -> &'static str {
+        match self {
+            Self::ScreenRecording => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+            }
+            Self::Microphone => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone"
+            }
+            Self::Accessibility => {
+                "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformPermissions {
+    pub screen_recording: PermissionState,
+    pub microphone: PermissionState,
+    pub accessibility: PermissionState,
+    /// macOS only reports a Screen Recording grant to a process started after
+    /// the grant, so the watcher cannot see it flip; the UI offers a relaunch.
+    pub screen_recording_requires_restart: bool,
+}
+
+impl PlatformPermissions {
+    pub fn all_satisfied(&self) -> bool {
+        self.screen_recording.is_satisfied()
+            && self.microphone.is_satisfied()
+            && self.accessibility.is_satisfied()
+    }
+}
+
+pub fn check_platform_permissions() -> PlatformPermissions {
+    #[cfg(target_os = "macos")]
+    {
+        macos::check()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        PlatformPermissions {
+            screen_recording: PermissionState::NotApplicable,
+            microphone: PermissionState::NotApplicable,
+            accessibility: PermissionState::NotApplicable,
+            screen_recording_requires_restart: false,
+        }
+    }
+}
+
+/// Fail a capture early instead of letting it return a wallpaper-only image.
+/// Emits `platform-permission-required` so the UI can show the guide.
+pub fn ensure_screen_recording(app: &AppHandle) -> Result<(), String> {
+    let status = check_platform_permissions();
+    if status.screen_recording.is_satisfied() {
+        return Ok(());
+    }
+    let _ = app.emit(
+        PERMISSION_REQUIRED_EVENT,
+        serde_json::json!({
+            "permission": "screen_recording",
+            "status": status,
+        }),
+    );
+    Err(SCREEN_RECORDING_ERROR.to_string())
+}
+
+/// Open the System Settings pane for `permission` and start re-checking in
+/// the background.
+pub fn open_permission_settings(
+    app: &AppHandle,
+    permission: PlatformPermission,
+) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        if permission == PlatformPermission::ScreenRecording {
+            // Adds the app to the Screen Recording list (and prompts once),
+            // so the user has an entry to toggle.
+            macos::request_screen_capture_access();
+        }
+        std::process::Command::new("open")
+            .arg(permission.settings_url())
+            .spawn()
+            .map_err(|e| format!("Failed to open System Settings: {}", e))?;
+        watch_for_changes(app.clone());
+        Ok(())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app, permission);
+        Ok(())
+    }
+}
+
+/// Poll until the permission state changes, everything is granted, or the
+/// timeout passes. Only one watcher runs at a time.
+#[cfg(target_os = "macos")]
+fn watch_for_changes(app: AppHandle) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    const RECHECK_INTERVAL: Duration = Duration::from_secs(2);
+    const RECHECK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+    static WATCHING: AtomicBool = AtomicBool::new(false);
+
+    if WATCHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        let mut last = check_platform_permissions();
+        while started.elapsed() < RECHECK_TIMEOUT {
+            std::thread::sleep(RECHECK_INTERVAL);
+            let current = check_platform_permissions();
+            if current != last {
+                log::info!("Platform permissions changed: {:?}", current);
+                let _ = app.emit(PERMISSIONS_CHANGED_EVENT, &current);
+                last = current;
+            }
+            if last.all_satisfied() {
+                break;
+            }
+        }
+        WATCHING.store(false, Ordering::SeqCst);
+    });
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{PermissionState, PlatformPermissions};
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+        fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> u8;
+    }
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeAudio: *mut Object;
+    }
+
+    pub(super) fn check() -> PlatformPermissions {
+        let screen_recording = if unsafe { CGPreflightScreenCaptureAccess() } {
+            PermissionState::Granted
+        } else {
+            // Preflight cannot tell "never asked" from "denied".
+            PermissionState::Denied
+        };
+
+        let accessibility = if unsafe { AXIsProcessTrusted() } != 0 {
+            PermissionState::Granted
+        } else {
+            PermissionState::Denied
+        };
+
+        PlatformPermissions {
+            screen_recording,
+            microphone: microphone_state(),
+            accessibility,
+            screen_recording_requires_restart: screen_recording != PermissionState::Granted,
+        }
+    }
+
+    pub(super) fn request_screen_capture_access() {
+        unsafe {
+            let _ = CGRequestScreenCaptureAccess();
+        }
+    }
+
+    fn microphone_state() -> PermissionState {
+        // AVAuthorizationStatus: 0 notDetermined, 1 restricted, 2 denied, 3 authorized.
+        let status: isize = unsafe {
+            msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: AVMediaTypeAudio]
+        };
+        match status {
+            0 => PermissionState::NotDetermined,
+            1 => PermissionState::Restricted,
+            2 => PermissionState::Denied,
+            _ => PermissionState::Granted,
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Post-capture processor plugins.
+//!
+//! Plugins are installed as subdirectories of `<config>/plugins` and are
+//! off until the user enables them. Once a chat's OCR result is saved, the
+//! enabled plugins run one after another on its image and OCR regions, and
+//! what they return is stored with the chat. The initial turn includes it
+//! when it is ready in time.
+//!
+//! Each run is sandboxed by [`PluginLimits`] from the `pluginLimits`
+//! preference, at background priority. Plugins are skipped while memory
+//! pressure pauses background work.
+
+use ops_chat_storage::{OcrRegion, PluginNote, AUTO_OCR_DISABLED_MODEL_ID};
+use ops_plugin_host::{discover, run_plugin, Plugin, PluginInput, PluginKind, PluginLimits};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::path::PathBuf;
+use sys_process_priority::SidecarRole;
+use tauri::{AppHandle, Emitter, Manager};
+
+const PLUGINS_DIR_NAME: &str = "plugins";
+const ENABLED_PLUGINS_PREF: &str = "enabledPlugins";
+const PLUGIN_LIMITS_PREF: &str = "pluginLimits";
+
+/// Installed plugins, as of the last scan.
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Mutex<Vec<Plugin>>,
+    /// Held while plugins run so chats are processed one at a time.
+    running: tokio::sync::Mutex<()>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub version: Option<String>,
+    pub kind: PluginKind,
+    pub dir: String,
+    pub enabled: bool,
+}
+
+impl PluginRegistry {
+    /// Re-read the plugins directory.
+    pub fn rescan(&self, app: &AppHandle) -> Result<Vec<PluginInfo>, String> {
+        let plugins = discover(&plugins_dir(app)).map_err(|e| e.to_string())?;
+        *self.plugins.lock() = plugins;
+        Ok(self.list(app))
+    }
+
+    pub fn list(&self, app: &AppHandle) -> Vec<PluginInfo> {
+        let enabled = enabled_plugin_ids(app);
+        self.plugins
+            .lock()
+            .iter()
+            .map(|plugin| PluginInfo {
+                id: plugin.manifest.id.clone(),
+                name: plugin.manifest.name.clone(),
+                description: plugin.manifest.description.clone(),
+                version: plugin.manifest.version.clone(),
+                kind: plugin.kind,
+                dir: plugin.dir.to_string_lossy().into_owned(),
+                enabled: enabled.contains(&plugin.manifest.id),
+            })
+            .collect()
+    }
+
+    fn enabled(&self, app: &AppHandle) -> Vec<Plugin> {
+        let enabled = enabled_plugin_ids(app);
+        self.plugins
+            .lock()
+            .iter()
+            .filter(|plugin| enabled.contains(&plugin.manifest.id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Where plugins are installed.
+pub fn plugins_dir(app: &AppHandle) -> PathBuf {
+    crate::utils::get_app_config_dir(app).join(PLUGINS_DIR_NAME)
+}
+
+pub fn start(app: &AppHandle) {
+    if let Err(e) = app.state::<PluginRegistry>().rescan(app) {
+        log::warn!("Failed to scan plugins: {}", e);
+    }
+}
+
+/// Enable or disable a plugin by ID.
+pub fn set_plugin_enabled(app: &AppHandle, id: &str, enabled: bool) -> Result<(), String> {
+    let mut ids = enabled_plugin_ids(app);
+    ids.retain(|existing| existing != id);
+    if enabled {
+        ids.push(id.to_string());
+    }
+
+    crate::utils::write_preference(app, ENABLED_PLUGINS_PREF, serde_json::json!(ids))
+}
+
+/// Called when OCR for a chat is saved. The auto-OCR-disabled marker counts
+/// as OCR without regions.
+pub fn ocr_saved(app: &AppHandle, chat_id: &str, model_id: &str, regions: &[OcrRegion]) {
+    let (ocr_model, ocr) = if model_id == AUTO_OCR_DISABLED_MODEL_ID {
+        (None, Vec::new())
+    } else {
+        (Some(model_id.to_string()), regions.to_vec())
+    };
+    run_for_chat(app, chat_id.to_string(), ocr_model, ocr);
+}
+
+/// Run the enabled plugins on a chat in the background. Each note is saved
+/// as soon as its plugin finishes and announced with `plugin-note-added`.
+pub fn run_for_chat(
+    app: &AppHandle,
+    chat_id: String,
+    ocr_model: Option<String>,
+    ocr: Vec<OcrRegion>,
+) {
+    let plugins = app.state::<PluginRegistry>().enabled(app);
+    if plugins.is_empty() {
+        return;
+    }
+    if app
+        .state::<super::memory::MemoryPressureState>()
+        .background_work_paused()
+    {
+        log::info!("Skipping plugins for {}: memory pressure", chat_id);
+        return;
+    }
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let registry = handle.state::<PluginRegistry>();
+        let _running = registry.running.lock().await;
+
+        let storage = match ops_squigit_brain::context::media::get_active_storage() {
+            Ok(storage) => storage,
+            Err(e) => {
+                log::warn!("Skipping plugins for {}: {}", chat_id, e);
+                return;
+            }
+        };
+        let image_path = match storage
+            .load_chat(&chat_id)
+            .and_then(|chat| storage.get_image_path(&chat.metadata.image_hash))
+        {
+            Ok(path) => path,
+            Err(e) => {
+                log::warn!("Skipping plugins for {}: {}", chat_id, e);
+                return;
+            }
+        };
+
+        let input = PluginInput::new(chat_id.clone(), image_path, ocr_model, ocr);
+        let limits = plugin_limits(&handle);
+        let priority = super::priority::sidecar_priority(&handle, SidecarRole::Background);
+        for plugin in plugins {
+            let id = plugin.manifest.id.clone();
+            let output = match run_plugin(&plugin, &input, &limits, priority).await {
+                Ok(output) if output.is_empty() => continue,
+                Ok(output) => output,
+                Err(e) => {
+                    log::warn!("Plugin {} failed on {}: {}", id, chat_id, e);
+                    continue;
+                }
+            };
+
+            let note = PluginNote {
+                plugin_id: id.clone(),
+                plugin_name: plugin.manifest.name.clone(),
+                context: output.context,
+                annotations: output.annotations,
+                created_at: chrono::Utc::now(),
+            };
+            if let Err(e) = storage.save_plugin_note(&chat_id, &note) {
+                log::warn!("Failed to save note from plugin {}: {}", id, e);
+                continue;
+            }
+            let _ = handle.emit(
+                "plugin-note-added",
+                serde_json::json!({ "chatId": chat_id, "note": note }),
+            );
+        }
+    });
+}
+
+fn enabled_plugin_ids(app: &AppHandle) -> Vec<String> {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(ENABLED_PLUGINS_PREF).cloned())
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+fn plugin_limits(app: &AppHandle) -> PluginLimits {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(PLUGIN_LIMITS_PREF).cloned())
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,199 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admin-deployed policy file.
+//!
+//! Organizations can push a `policy.json` to a machine-wide location to
+//! restrict what the app may do:
+//!
+//! ```json
+//! {
+//!   "allowedProviders": ["google ai studio"],
+//!   "allowedModels": ["gemini-2.5-flash", "gemini-2.5-flash-lite*"],
+//!   "disableImageHosting": true,
+//!   "forceRedaction": true,
+//!   "disableClipboardWatcher": true
+//! }
+//! ```
+//!
+//! Omitted fields do not restrict anything. The file is read once per run.
+//! `SQUIGIT_POLICY_FILE` points at a policy to try out, and is only read
+//! when no machine-wide file exists, so users cannot swap it for their own.
+//! A file that exists but cannot be parsed locks everything down rather
+//! than silently lifting the restrictions.
+
+use ops_profile_store::ApiKeyProvider;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+const POLICY_FILE_ENV: &str = "SQUIGIT_POLICY_FILE";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Policy {
+    /// Providers keys may be stored and used for. All when `None`.
+    pub allowed_providers: Option<Vec<String>>,
+    /// Model IDs that may be called; a trailing `*` matches a prefix. All
+    /// when `None`.
+    pub allowed_models: Option<Vec<String>>,
+    /// Block uploads to image hosts (ImgBB).
+    pub disable_image_hosting: bool,
+    /// Require redaction of detected sensitive content before it is sent.
+    pub force_redaction: bool,
+    /// Keep clipboard monitoring off.
+    pub disable_clipboard_watcher: bool,
+}
+
+impl Policy {
+    /// Whether `model` matches the model allowlist.
+    pub fn allows_model(&self, model: &str) -> bool {
+        let Some(allowed) = &self.allowed_models else {
+            return true;
+        };
+        let model = model.trim();
+        let model = model.strip_prefix("models/").unwrap_or(model);
+        allowed
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => model.starts_with(prefix),
+                None => pattern == model,
+            })
+    }
+
+    fn locked_down() -> Self {
+        Self {
+            allowed_providers: Some(Vec::new()),
+            allowed_models: Some(Vec::new()),
+            disable_image_hosting: true,
+            force_redaction: true,
+            disable_clipboard_watcher: true,
+        }
+    }
+}
+
+/// The policy in force and where it came from, for the settings UI.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectivePolicy {
+    #[serde(flatten)]
+    pub policy: Policy,
+    /// Whether a policy file was found.
+    pub managed: bool,
+    pub source: Option<String>,
+    /// Why the file could not be read, when it was locked down instead.
+    pub error: Option<String>,
+}
+
+/// The effective policy, loaded on first use.
+pub fn current() -> &'static EffectivePolicy {
+    static POLICY: OnceLock<EffectivePolicy> = OnceLock::new();
+    POLICY.get_or_init(load)
+}
+
+/// Fails with `ERR_POLICY_PROVIDER` when the policy does not allow
+/// `provider`.
+pub fn check_provider(provider: ApiKeyProvider) -> Result<(), String> {
+    let Some(allowed) = &current().policy.allowed_providers else {
+        return Ok(());
+    };
+    let permitted = allowed
+        .iter()
+        .any(|entry| ApiKeyProvider::from_str(entry).is_ok_and(|allowed| allowed == provider));
+    if permitted {
+        Ok(())
+    } else {
+        Err(format!("ERR_POLICY_PROVIDER: {}", provider.display_name()))
+    }
+}
+
+/// Fails with `ERR_POLICY_MODEL` when the policy does not allow `model`.
+pub fn check_model(model: &str) -> Result<(), String> {
+    if is_model_allowed(model) {
+        Ok(())
+    } else {
+        Err(format!("ERR_POLICY_MODEL: {}", model))
+    }
+}
+
+/// Whether `model` matches the policy's model allowlist.
+pub fn is_model_allowed(model: &str) -> bool {
+    current().policy.allows_model(model)
+}
+
+/// Fails with `ERR_POLICY_IMAGE_HOSTING` when uploads are disabled.
+pub fn check_image_hosting() -> Result<(), String> {
+    if current().policy.disable_image_hosting {
+        return Err("ERR_POLICY_IMAGE_HOSTING".to_string());
+    }
+    check_provider(ApiKeyProvider::ImgBb)
+}
+
+fn load() -> EffectivePolicy {
+    let Some(path) = policy_path().filter(|path| path.exists()) else {
+        return EffectivePolicy {
+            policy: Policy::default(),
+            managed: false,
+            source: None,
+            error: None,
+        };
+    };
+
+    let source = Some(path.to_string_lossy().into_owned());
+    let parsed = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<Policy>(&content).map_err(|e| e.to_string()));
+    match parsed {
+        Ok(policy) => {
+            log::info!("Applying policy from {}", path.display());
+            EffectivePolicy {
+                policy,
+                managed: true,
+                source,
+                error: None,
+            }
+        }
+        Err(e) => {
+            log::error!("Invalid policy file {}: {}", path.display(), e);
+            EffectivePolicy {
+                policy: Policy::locked_down(),
+                managed: true,
+                source,
+                error: Some(e),
+            }
+        }
+    }
+}
+
+/// The machine-wide file when there is one, else `SQUIGIT_POLICY_FILE`.
+fn policy_path() -> Option<PathBuf> {
+    if let Some(path) = system_policy_path().filter(|path| path.exists()) {
+        return Some(path);
+    }
+    std::env::var_os(POLICY_FILE_ENV)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+fn system_policy_path() -> Option<PathBuf> {
+    #[cfg(target_os = "linux")]
+    {
+        Some(PathBuf::from("/etc/squigit/policy.json"))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Some(PathBuf::from(
+            "/Library/Application Support/Squigit/policy.json",
+        ))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("ProgramData")
+            .map(|dir| PathBuf::from(dir).join("Squigit").join("policy.json"))
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        None
+    }
+}
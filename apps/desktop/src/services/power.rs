@@ -0,0 +1,73 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-initializes long-running subsystems after the system wakes from sleep.
+//!
+//! Sessions opened before suspend (the portal shortcut session and SNI tray
+//! on Linux, network probes, in-flight model downloads, the STT audio
+//! capture) often come back stale, so each is refreshed on resume.
+
+use parking_lot::Mutex;
+use std::time::Duration;
+use sys_power_events::{PowerEvent, PowerWatcher};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Give the network stack and session services time to come back up.
+const RESUME_SETTLE_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+pub struct PowerEventsState {
+    watcher: Mutex<Option<PowerWatcher>>,
+}
+
+pub fn start(app: &AppHandle) {
+    let handle = app.clone();
+    let watcher = PowerWatcher::start(move |event| match event {
+        PowerEvent::Suspending => {
+            let _ = handle.emit("system-suspending", serde_json::json!({}));
+        }
+        PowerEvent::Resumed => {
+            let handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(RESUME_SETTLE_DELAY).await;
+                handle_resume(&handle).await;
+            });
+        }
+    });
+
+    match watcher {
+        Ok(watcher) => {
+            *app.state::<PowerEventsState>().watcher.lock() = Some(watcher);
+        }
+        Err(e) => log::warn!("Power event listener unavailable (non-fatal): {}", e),
+    }
+}
+
+async fn handle_resume(app: &AppHandle) {
+    log::info!("System resumed, re-initializing subsystems");
+
+    // Windows re-registers its hotkey from the backend's own resume handler
+    // and Carbon hotkeys survive sleep, so only the portal session needs it.
+    #[cfg(target_os = "linux")]
+    {
+        let handle = app.clone();
+        let _ = tauri::async_runtime::spawn_blocking(move || {
+            handle
+                .state::<crate::services::shortcut::GlobalShortcutState>()
+                .clear();
+            // Let the portal close the old session before opening a new one.
+            std::thread::sleep(Duration::from_millis(300));
+            crate::services::shortcut::register_global_shortcut(&handle);
+        })
+        .await;
+
+        crate::services::tray::refresh_tray(app).await;
+    }
+
+    app.state::<crate::services::ocr::DesktopOcrService>()
+        .handle_resume();
+    crate::commands::speech::restart_after_resume(app).await;
+    crate::services::battery::refresh(app).await;
+
+    let _ = app.emit("system-resumed", serde_json::json!({}));
+}
@@ -0,0 +1,40 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! The "performance vs battery" preference and the scheduling policy it
+//! gives each sidecar. The battery profile is forced while the battery
+//! saver is active. Changes apply to sidecars started afterwards.
+
+use crate::services::battery::BatteryState;
+use sys_process_priority::{PowerProfile, PriorityPolicy, SidecarRole};
+use tauri::{AppHandle, Manager};
+
+const POWER_PROFILE_PREF: &str = "powerProfile";
+
+pub fn power_profile(app: &AppHandle) -> PowerProfile {
+    crate::utils::read_preferences(app)
+        .and_then(|mut prefs| {
+            prefs
+                .get_mut(POWER_PROFILE_PREF)
+                .map(serde_json::Value::take)
+        })
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn sidecar_priority(app: &AppHandle, role: SidecarRole) -> PriorityPolicy {
+    let profile = if app.state::<BatteryState>().saver_active() {
+        PowerProfile::Battery
+    } else {
+        power_profile(app)
+    };
+    profile.policy(role)
+}
+
+pub fn save_power_profile(app: &AppHandle, profile: PowerProfile) -> Result<(), String> {
+    crate::utils::write_preference(
+        app,
+        POWER_PROFILE_PREF,
+        serde_json::to_value(profile).map_err(|e| e.to_string())?,
+    )
+}
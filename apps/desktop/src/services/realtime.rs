@@ -0,0 +1,307 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gemini Live voice sessions.
+//!
+//! The default microphone streams to the model and its spoken replies play
+//! on the default output device. When started for a chat, the chat's
+//! screenshot is shared as screen context. Transcripts and session state
+//! reach the frontend as `realtime_event` payloads:
+//! `{ type: "ready" | "input_transcript" | "output_transcript" | "text" |
+//! "interrupted" | "turn_complete" | "go_away" | "closed", ... }`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc as std_mpsc;
+
+use ops_profile_store::ApiKeyProvider;
+use rodio::buffer::SamplesBuffer;
+use rodio::cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use rodio::cpal::{FromSample, Sample, SizedSample};
+use rodio::{OutputStream, Sink};
+use svc_realtime::{
+    InputResampler, RealtimeConfig, RealtimeEvent, RealtimeSender, RealtimeSession,
+};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{mpsc, Mutex};
+
+pub const REALTIME_EVENT: &str = "realtime_event";
+
+const REALTIME_MODEL_PREF: &str = "realtimeModel";
+const REALTIME_VOICE_PREF: &str = "realtimeVoice";
+
+const SYSTEM_INSTRUCTION: &str = "You are Squigit, a voice assistant helping the user \
+understand what is on their screen. Answer conversationally and briefly, as your replies \
+are spoken aloud. Refer to the shared screenshot when it is relevant.";
+
+#[derive(Default)]
+pub struct RealtimeState {
+    current: Mutex<Option<ActiveSession>>,
+    next_id: AtomicU64,
+}
+
+struct ActiveSession {
+    id: u64,
+    session: RealtimeSession,
+    /// Dropping these stops the microphone and playback threads.
+    _microphone: std_mpsc::Sender<()>,
+    _player: std_mpsc::Sender<Playback>,
+}
+
+enum Playback {
+    Audio { pcm: Vec<i16>, sample_rate: u32 },
+    Clear,
+}
+
+/// Connect to the Live API and start streaming the microphone. Replaces a
+/// running session.
+pub async fn start(app: &AppHandle, chat_id: Option<String>) -> Result<(), String> {
+    stop(app).await;
+    crate::services::policy::check_provider(ApiKeyProvider::GoogleAiStudio)?;
+
+    let credentials =
+        tauri::async_runtime::spawn_blocking(crate::services::brain::resolve_credentials)
+            .await
+            .map_err(|e| e.to_string())??;
+    let screen = match chat_id {
+        Some(chat_id) => Some(
+            tauri::async_runtime::spawn_blocking(move || load_chat_image(&chat_id))
+                .await
+                .map_err(|e| e.to_string())??,
+        ),
+        None => None,
+    };
+
+    let prefs = crate::utils::read_preferences(app).unwrap_or_default();
+    let pref = |key: &str| {
+        prefs
+            .get(key)
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let model =
+        pref(REALTIME_MODEL_PREF).unwrap_or_else(|| svc_realtime::DEFAULT_MODEL.to_string());
+    crate::services::policy::check_model(&model)?;
+    let mut config = RealtimeConfig::new(credentials.api_key, model);
+    config.voice = pref(REALTIME_VOICE_PREF);
+    config.system_instruction = Some(SYSTEM_INSTRUCTION.to_string());
+
+    let (session, events) = RealtimeSession::connect(config)
+        .await
+        .map_err(|e| format!("ERR_REALTIME_CONNECT: {}", e))?;
+    let input = session.sender();
+    if let Some((data, mime_type)) = screen {
+        input
+            .send_image(&data, &mime_type)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let microphone = match start_microphone(input) {
+        Ok(microphone) => microphone,
+        Err(e) => {
+            session.close().await;
+            return Err(e);
+        }
+    };
+    let player = start_player();
+
+    let state = app.state::<RealtimeState>();
+    let id = state.next_id.fetch_add(1, Ordering::SeqCst);
+    spawn_event_forwarder(app.clone(), id, events, player.clone());
+    *state.current.lock().await = Some(ActiveSession {
+        id,
+        session,
+        _microphone: microphone,
+        _player: player,
+    });
+    Ok(())
+}
+
+/// Stop the running session, if any.
+pub async fn stop(app: &AppHandle) {
+    let state = app.state::<RealtimeState>();
+    let active = state.current.lock().await.take();
+    if let Some(active) = active {
+        active.session.close().await;
+    }
+}
+
+fn spawn_event_forwarder(
+    app: AppHandle,
+    id: u64,
+    mut events: mpsc::Receiver<RealtimeEvent>,
+    player: std_mpsc::Sender<Playback>,
+) {
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = events.recv().await {
+            let payload = match event {
+                RealtimeEvent::Audio { pcm, sample_rate } => {
+                    let _ = player.send(Playback::Audio { pcm, sample_rate });
+                    continue;
+                }
+                RealtimeEvent::Ready => serde_json::json!({ "type": "ready" }),
+                RealtimeEvent::InputTranscript { text } => {
+                    serde_json::json!({ "type": "input_transcript", "text": text })
+                }
+                RealtimeEvent::OutputTranscript { text } => {
+                    serde_json::json!({ "type": "output_transcript", "text": text })
+                }
+                RealtimeEvent::Text { text } => serde_json::json!({ "type": "text", "text": text }),
+                RealtimeEvent::Interrupted => {
+                    let _ = player.send(Playback::Clear);
+                    serde_json::json!({ "type": "interrupted" })
+                }
+                RealtimeEvent::TurnComplete => serde_json::json!({ "type": "turn_complete" }),
+                RealtimeEvent::GoAway { time_left } => {
+                    serde_json::json!({ "type": "go_away", "time_left": time_left })
+                }
+                RealtimeEvent::Closed { reason } => {
+                    serde_json::json!({ "type": "closed", "reason": reason })
+                }
+            };
+            if let Err(e) = app.emit(REALTIME_EVENT, payload) {
+                log::error!("Failed to emit realtime_event: {}", e);
+            }
+        }
+
+        // The server ended the session; release the microphone.
+        let state = app.state::<RealtimeState>();
+        let mut current = state.current.lock().await;
+        if current.as_ref().is_some_and(|active| active.id == id) {
+            *current = None;
+        }
+    });
+}
+
+/// Capture the default input device on its own thread (cpal streams are not
+/// `Send`) and pump 16 kHz audio into the session until the returned sender
+/// is dropped.
+fn start_microphone(input: RealtimeSender) -> Result<std_mpsc::Sender<()>, String> {
+    let (stop_tx, stop_rx) = std_mpsc::channel::<()>();
+    let (ready_tx, ready_rx) = std_mpsc::channel::<Result<(), String>>();
+    let (chunk_tx, mut chunk_rx) = mpsc::channel::<Vec<f32>>(64);
+
+    std::thread::spawn(move || {
+        let stream = match open_input_stream(chunk_tx) {
+            Ok((stream, resampler)) => {
+                let _ = ready_tx.send(Ok(()));
+                tauri::async_runtime::spawn(async move {
+                    let mut resampler = resampler;
+                    while let Some(chunk) = chunk_rx.recv().await {
+                        let pcm = resampler.process(&chunk);
+                        if !pcm.is_empty() && input.send_audio(&pcm).await.is_err() {
+                            break;
+                        }
+                    }
+                    let _ = input.end_audio_stream().await;
+                });
+                stream
+            }
+            Err(e) => {
+                let _ = ready_tx.send(Err(e));
+                return;
+            }
+        };
+        // Blocks until the session drops its sender.
+        let _ = stop_rx.recv();
+        drop(stream);
+    });
+
+    ready_rx
+        .recv()
+        .map_err(|e| e.to_string())?
+        .map(|()| stop_tx)
+}
+
+fn open_input_stream(
+    chunks: mpsc::Sender<Vec<f32>>,
+) -> Result<(rodio::cpal::Stream, InputResampler), String> {
+    let device = rodio::cpal::default_host()
+        .default_input_device()
+        .ok_or_else(|| "ERR_NO_MICROPHONE".to_string())?;
+    let supported = device
+        .default_input_config()
+        .map_err(|e| format!("ERR_NO_MICROPHONE: {}", e))?;
+    let config = supported.config();
+    let resampler = InputResampler::new(config.sample_rate.0, config.channels);
+
+    let stream = match supported.sample_format() {
+        rodio::cpal::SampleFormat::F32 => build_input::<f32>(&device, &config, chunks),
+        rodio::cpal::SampleFormat::I16 => build_input::<i16>(&device, &config, chunks),
+        rodio::cpal::SampleFormat::U16 => build_input::<u16>(&device, &config, chunks),
+        other => Err(format!("Unsupported microphone sample format: {}", other)),
+    }?;
+    stream.play().map_err(|e| e.to_string())?;
+    Ok((stream, resampler))
+}
+
+fn build_input<T>(
+    device: &rodio::cpal::Device,
+    config: &rodio::cpal::StreamConfig,
+    chunks: mpsc::Sender<Vec<f32>>,
+) -> Result<rodio::cpal::Stream, String>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _| {
+                // Drop audio rather than block the capture callback.
+                let _ = chunks.try_send(data.iter().map(|&sample| sample.to_sample()).collect());
+            },
+            |e| log::warn!("Microphone stream error: {}", e),
+            None,
+        )
+        .map_err(|e| e.to_string())
+}
+
+/// Play reply audio on the default output device. `Clear` drops whatever
+/// is still queued, for barge-in.
+fn start_player() -> std_mpsc::Sender<Playback> {
+    let (tx, rx) = std_mpsc::channel::<Playback>();
+    std::thread::spawn(move || {
+        let (_stream, handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(e) => {
+                log::warn!("Realtime audio output is unavailable: {}", e);
+                return;
+            }
+        };
+        let mut sink = Sink::try_new(&handle).ok();
+        while let Ok(playback) = rx.recv() {
+            match playback {
+                Playback::Audio { pcm, sample_rate } => {
+                    if let Some(sink) = &sink {
+                        sink.append(SamplesBuffer::new(1, sample_rate, pcm));
+                    }
+                }
+                Playback::Clear => {
+                    if let Some(sink) = sink.take() {
+                        sink.stop();
+                    }
+                    sink = Sink::try_new(&handle).ok();
+                }
+            }
+        }
+    });
+    tx
+}
+
+/// The chat's screenshot and its MIME type.
+fn load_chat_image(chat_id: &str) -> Result<(Vec<u8>, String), String> {
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    let chat = storage.load_chat(chat_id).map_err(|e| e.to_string())?;
+    let path = storage
+        .get_image_path(&chat.metadata.image_hash)
+        .map_err(|e| e.to_string())?;
+    let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+    let data = ops_chat_storage::without_image_metadata(data);
+    let mime_type = mime_guess::from_path(&path)
+        .first_or(mime_guess::mime::IMAGE_PNG)
+        .to_string();
+    Ok((data, mime_type))
+}
@@ -0,0 +1,104 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recovery of replies lost when the app quit mid-generation.
+//!
+//! At startup, chats whose last message is a recent unanswered user turn are
+//! collected and announced with `resume-available`. `resume_generation`
+//! replays such a turn from the stored context and appends the reply.
+
+use crate::services::brain::{preferred_model, resolve_credentials, DesktopBrainService};
+use crate::services::session::SessionState;
+use ops_chat_storage::DanglingUserTurn;
+use ops_squigit_brain::service::ResumeChatRequest;
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const RESUME_AVAILABLE_EVENT: &str = "resume-available";
+
+/// Older unanswered turns are treated as abandoned rather than interrupted.
+const RESUME_WINDOW_HOURS: i64 = 6;
+
+#[derive(Default)]
+pub struct RecoveryState {
+    pending: Mutex<Vec<DanglingUserTurn>>,
+}
+
+impl RecoveryState {
+    pub fn pending(&self) -> Vec<DanglingUserTurn> {
+        self.pending.lock().clone()
+    }
+
+    fn remove(&self, chat_id: &str) {
+        self.pending.lock().retain(|turn| turn.chat_id != chat_id);
+    }
+}
+
+/// Look for dangling user turns in the active profile's chats.
+pub fn scan(app: &AppHandle) {
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let since = chrono::Utc::now() - chrono::Duration::hours(RESUME_WINDOW_HOURS);
+        let turns =
+            match ops_squigit_brain::context::media::get_active_storage().and_then(|storage| {
+                storage
+                    .find_dangling_user_turns(since)
+                    .map_err(|e| e.to_string())
+            }) {
+                Ok(turns) => turns,
+                Err(e) => {
+                    log::debug!("Skipping pending-stream scan: {}", e);
+                    return;
+                }
+            };
+        if turns.is_empty() {
+            return;
+        }
+
+        log::info!("Found {} chat(s) with an interrupted reply", turns.len());
+        *handle.state::<RecoveryState>().pending.lock() = turns.clone();
+        let _ = handle.emit(
+            RESUME_AVAILABLE_EVENT,
+            serde_json::json!({ "chats": turns }),
+        );
+    });
+}
+
+pub async fn resume(
+    app: &AppHandle,
+    chat_id: String,
+    channel_id: String,
+) -> Result<String, String> {
+    let credentials = tauri::async_runtime::spawn_blocking(resolve_credentials)
+        .await
+        .map_err(|e| e.to_string())??;
+    let model = preferred_model(app);
+
+    let session = app.state::<SessionState>();
+    session.stream_started(Some(chat_id.clone()), &channel_id);
+    let result = app
+        .state::<DesktopBrainService>()
+        .resume_chat(
+            app.clone(),
+            ResumeChatRequest {
+                api_key: credentials.api_key,
+                model,
+                chat_id: chat_id.clone(),
+                channel_id: channel_id.clone(),
+                user_name: credentials.user_name,
+                user_email: credentials.user_email,
+                response_language: crate::services::brain::response_language(app),
+                glossary: crate::services::brain::active_glossary(),
+                include_ocr_in_prompt: crate::services::brain::include_ocr_in_prompt(app),
+                redact_pii: crate::services::brain::redact_pii(app),
+                fallback_models: crate::services::brain::active_model_fallbacks(),
+            },
+        )
+        .await;
+    session.stream_finished(&channel_id);
+
+    if result.is_ok() {
+        app.state::<RecoveryState>().remove(&chat_id);
+    }
+    result
+}
@@ -0,0 +1,104 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scheduled chat retention.
+//!
+//! The policy comes from preferences and is re-read before every pass, so
+//! settings changes apply without a restart. Everything is off until the
+//! user sets an age limit or a storage cap; trash purging and upload-cache
+//! cleanup run regardless. Each pass ends with a `retention-complete` or
+//! `retention-failed` event so open chat lists can refresh.
+
+use ops_chat_storage::{MaintenanceTask, RetentionAction, RetentionPolicy, RetentionReport};
+use parking_lot::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const PASS_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const MAX_AGE_DAYS_PREF: &str = "retentionMaxAgeDays";
+const ACTION_PREF: &str = "retentionAction";
+const KEEP_STARRED_PREF: &str = "retentionKeepStarred";
+const MAX_STORAGE_GB_PREF: &str = "retentionMaxStorageGb";
+const TRASH_DAYS_PREF: &str = "retentionTrashDays";
+
+#[derive(Default)]
+pub struct RetentionState {
+    task: Mutex<Option<MaintenanceTask>>,
+}
+
+pub fn start(app: &AppHandle) {
+    let handle = app.clone();
+    let source = move || match ops_squigit_brain::context::media::get_active_storage() {
+        Ok(storage) => Some((storage, policy(&handle))),
+        Err(e) => {
+            log::warn!("Skipping retention pass: {}", e);
+            None
+        }
+    };
+    let report_app = app.clone();
+    let on_report = move |report: ops_chat_storage::Result<RetentionReport>| {
+        emit_report(&report_app, report.map_err(|e| e.to_string()));
+    };
+
+    match MaintenanceTask::spawn(PASS_INTERVAL, source, on_report) {
+        Ok(task) => *app.state::<RetentionState>().task.lock() = Some(task),
+        Err(e) => log::warn!("Failed to start retention task: {}", e),
+    }
+}
+
+/// Log the outcome of a retention pass and tell the frontend.
+pub fn emit_report(app: &AppHandle, report: Result<RetentionReport, String>) {
+    match report {
+        Ok(report) => {
+            log::info!(
+                "Retention pass: {} trashed, {} deleted, {} purged, {} bytes reclaimed",
+                report.trashed.len(),
+                report.deleted.len(),
+                report.purged.len(),
+                report.reclaimed_bytes
+            );
+            let _ = app.emit("retention-complete", report);
+        }
+        Err(e) => {
+            log::warn!("Retention pass failed: {}", e);
+            let _ = app.emit("retention-failed", serde_json::json!({ "reason": e }));
+        }
+    }
+}
+
+/// The retention policy saved in preferences.
+pub fn policy(app: &AppHandle) -> RetentionPolicy {
+    let prefs_file =
+        crate::utils::get_app_config_dir(app).join(crate::constants::PREFERENCES_FILE_NAME);
+    let prefs = std::fs::read_to_string(prefs_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .unwrap_or_default();
+    let defaults = RetentionPolicy::default();
+
+    RetentionPolicy {
+        max_age_days: prefs
+            .get(MAX_AGE_DAYS_PREF)
+            .and_then(|days| days.as_u64())
+            .filter(|days| *days > 0)
+            .map(|days| days.min(u64::from(u32::MAX)) as u32),
+        age_action: match prefs.get(ACTION_PREF).and_then(|action| action.as_str()) {
+            Some("delete") => RetentionAction::Delete,
+            _ => defaults.age_action,
+        },
+        keep_starred: prefs
+            .get(KEEP_STARRED_PREF)
+            .and_then(|keep| keep.as_bool())
+            .unwrap_or(defaults.keep_starred),
+        max_storage_bytes: prefs
+            .get(MAX_STORAGE_GB_PREF)
+            .and_then(|gb| gb.as_f64())
+            .filter(|gb| *gb > 0.0)
+            .map(|gb| (gb * 1024.0 * 1024.0 * 1024.0) as u64),
+        trash_days: prefs
+            .get(TRASH_DAYS_PREF)
+            .and_then(|days| days.as_u64())
+            .map(|days| days.min(u64::from(u32::MAX)) as u32)
+            .unwrap_or(defaults.trash_days),
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chat stubs for Spotlight and Windows Search.
+//!
+//! With `systemSearchIndex` on in preferences, every chat has a small stub
+//! file (title, dates, summary) in a folder the system search indexes:
+//! `~/Library/Caches/Metadata/Squigit` on macOS, where Spotlight expects
+//! app metadata stubs, and `Documents\Squigit\Chats` on Windows. Stubs
+//! follow chat saves and deletes; a full sync runs at startup and when the
+//! setting changes. Opening a stub opens the chat. Linux has the GNOME
+//! search provider instead.
+
+use ops_chat_export::SearchIndexStubs;
+use ops_squigit_brain::context::media::get_active_storage;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+const SEARCH_INDEX_PREF: &str = "systemSearchIndex";
+
+/// Folder the stubs live in, on platforms with an indexed one.
+fn stubs_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        dirs::home_dir().map(|home| home.join("Library/Caches/Metadata/Squigit"))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        dirs::document_dir().map(|documents| documents.join("Squigit").join("Chats"))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+fn is_enabled(app: &AppHandle) -> bool {
+    crate::utils::read_preferences(app)
+        .and_then(|prefs| prefs.get(SEARCH_INDEX_PREF)?.as_bool())
+        .unwrap_or(false)
+}
+
+/// Stubs, when the platform has a place for them and the user opted in.
+fn stubs(app: &AppHandle) -> Option<SearchIndexStubs> {
+    let dir = stubs_dir()?;
+    is_enabled(app).then(|| SearchIndexStubs::new(dir))
+}
+
+/// Write or update the stub of a saved chat.
+pub fn chat_saved(app: &AppHandle, chat_id: &str) {
+    let Some(stubs) = stubs(app) else {
+        return;
+    };
+    let chat_id = chat_id.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = get_active_storage().and_then(|storage| {
+            let chat = storage.load_chat(&chat_id).map_err(|e| e.to_string())?;
+            stubs.write(&chat.metadata).map_err(|e| e.to_string())
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to update search stub: {}", e);
+        }
+    });
+}
+
+/// Remove the stub of a deleted chat.
+pub fn chat_deleted(app: &AppHandle, chat_id: &str) {
+    let Some(stubs) = stubs(app) else {
+        return;
+    };
+    let chat_id = chat_id.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = stubs.remove(&chat_id) {
+            log::warn!("Failed to remove search stub: {}", e);
+        }
+    });
+}
+
+/// Bring the stubs in line with the stored chats, or remove them all when
+/// the setting is off. Returns the number of stubs written.
+pub async fn sync(app: &AppHandle) -> Result<usize, String> {
+    let Some(dir) = stubs_dir() else {
+        return Ok(0);
+    };
+    let enabled = is_enabled(app);
+    tauri::async_runtime::spawn_blocking(move || {
+        let stubs = SearchIndexStubs::new(dir);
+        let chats = if enabled {
+            let mut chats = get_active_storage()?
+                .list_chats()
+                .map_err(|e| e.to_string())?;
+            chats.retain(|chat| !chat.id.starts_with("__system_"));
+            chats
+        } else {
+            Vec::new()
+        };
+        let removed = stubs.sync(&chats).map_err(|e| e.to_string())?;
+        log::info!(
+            "Search stubs synced: {} written, {} removed",
+            chats.len(),
+            removed
+        );
+        Ok(chats.len())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
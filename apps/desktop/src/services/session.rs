@@ -0,0 +1,209 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lightweight session state persisted on every change, so the app reopens
+//! where the user left off after a restart or crash.
+//!
+//! The snapshot from the previous run is read once at startup and served by
+//! `get_last_session`. A reply that was still streaming when the process
+//! died is reported as `interruptedStream`, and `crashed` is set when the
+//! previous run never reached a clean exit.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+const SESSION_FILE_NAME: &str = "session.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingReply {
+    pub chat_id: Option<String>,
+    pub channel_id: String,
+    /// Unix milliseconds.
+    pub started_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionSnapshot {
+    pub active_chat_id: Option<String>,
+    /// Renderer-defined scroll anchor (message index or offset).
+    pub scroll_hint: Option<f64>,
+    pub window_visible: bool,
+    pub streaming: Option<StreamingReply>,
+    pub clean_exit: bool,
+    /// Unix milliseconds.
+    pub updated_at: i64,
+}
+
+/// Fields the renderer reports. `None` leaves the stored value unchanged;
+/// `activeChatId: ""` clears the active chat.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionUpdate {
+    pub active_chat_id: Option<String>,
+    pub scroll_hint: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastSession {
+    pub active_chat_id: Option<String>,
+    pub scroll_hint: Option<f64>,
+    pub window_visible: bool,
+    pub interrupted_stream: Option<StreamingReply>,
+    pub crashed: bool,
+    pub updated_at: i64,
+}
+
+#[derive(Default)]
+pub struct SessionState {
+    path: Mutex<Option<PathBuf>>,
+    current: Mutex<SessionSnapshot>,
+    last: Mutex<Option<LastSession>>,
+}
+
+impl SessionState {
+    /// Read the previous run's snapshot and start a new one from it.
+    pub fn load(&self, app: &AppHandle) {
+        let path = crate::utils::get_app_config_dir(app).join(SESSION_FILE_NAME);
+        let previous = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<SessionSnapshot>(&content).ok());
+
+        if let Some(previous) = &previous {
+            if !previous.clean_exit {
+                log::warn!("Previous session did not exit cleanly");
+            }
+            *self.last.lock() = Some(LastSession {
+                active_chat_id: previous.active_chat_id.clone(),
+                scroll_hint: previous.scroll_hint,
+                window_visible: previous.window_visible,
+                interrupted_stream: previous.streaming.clone(),
+                crashed: !previous.clean_exit,
+                updated_at: previous.updated_at,
+            });
+        }
+
+        *self.path.lock() = Some(path);
+        let mut current = self.current.lock();
+        *current = SessionSnapshot {
+            active_chat_id: previous.and_then(|previous| previous.active_chat_id),
+            ..Default::default()
+        };
+        self.persist(&mut current);
+    }
+
+    pub fn last_session(&self) -> Option<LastSession> {
+        self.last.lock().clone()
+    }
+
+    /// The chat open in the main window, if any.
+    pub fn active_chat_id(&self) -> Option<String> {
+        self.current.lock().active_chat_id.clone()
+    }
+
+    pub fn update(&self, update: SessionUpdate) {
+        let mut current = self.current.lock();
+        if let Some(chat_id) = update.active_chat_id {
+            let chat_id = Some(chat_id).filter(|id| !id.is_empty());
+            if current.active_chat_id != chat_id {
+                current.active_chat_id = chat_id;
+                // A scroll hint belongs to the chat it was reported for.
+                current.scroll_hint = None;
+            }
+        }
+        if let Some(scroll_hint) = update.scroll_hint {
+            current.scroll_hint = Some(scroll_hint);
+        }
+        self.persist(&mut current);
+    }
+
+    pub fn set_window_visible(&self, visible: bool) {
+        let mut current = self.current.lock();
+        if current.window_visible != visible {
+            current.window_visible = visible;
+            self.persist(&mut current);
+        }
+    }
+
+    pub fn stream_started(&self, chat_id: Option<String>, channel_id: &str) {
+        let mut current = self.current.lock();
+        current.streaming = Some(StreamingReply {
+            chat_id,
+            channel_id: channel_id.to_string(),
+            started_at: now_millis(),
+        });
+        self.persist(&mut current);
+    }
+
+    pub fn stream_finished(&self, channel_id: &str) {
+        let mut current = self.current.lock();
+        if current
+            .streaming
+            .as_ref()
+            .is_some_and(|streaming| streaming.channel_id == channel_id)
+        {
+            current.streaming = None;
+            self.persist(&mut current);
+        }
+    }
+
+    pub fn mark_clean_exit(&self) {
+        let mut current = self.current.lock();
+        current.clean_exit = true;
+        self.persist(&mut current);
+    }
+
+    fn persist(&self, snapshot: &mut SessionSnapshot) {
+        let Some(path) = self.path.lock().clone() else {
+            return;
+        };
+        snapshot.updated_at = now_millis();
+        if let Err(e) = write_json_atomic(&path, snapshot) {
+            log::warn!("Failed to persist session state: {}", e);
+        }
+    }
+}
+
+/// Track main window visibility for the session snapshot and the idle
+/// governor.
+pub fn set_window_visible(app: &AppHandle, visible: bool) {
+    app.state::<SessionState>().set_window_visible(visible);
+    if visible {
+        crate::services::idle::window_shown(app);
+    } else {
+        crate::services::idle::window_hidden(app);
+        // Picks up chats created since the last refresh.
+        #[cfg(target_os = "windows")]
+        crate::services::windows_shell::refresh_jump_list(app);
+    }
+}
+
+fn write_json_atomic(path: &Path, value: &SessionSnapshot) -> std::io::Result<()> {
+    let json = serde_json::to_vec_pretty(value)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = path.with_file_name(format!(".{}.tmp", SESSION_FILE_NAME));
+    {
+        let mut temp_file = File::create(&temp_path)?;
+        temp_file.write_all(&json)?;
+        temp_file.sync_all()?;
+    }
+
+    #[cfg(windows)]
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    fs::rename(&temp_path, path)
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
@@ -0,0 +1,229 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Owns the native global shortcut registration and remembers why it failed,
+//! so the UI can suggest a different binding when the combo is taken. On
+//! Linux it also keeps the desktop-level binding in sync with the preference.
+//!
+//! Besides capture, some actions get their own hotkey on Windows and macOS
+//! ([`ACTION_SHORTCUTS`]). Linux desktop bindings only reach the capture
+//! D-Bus method, so those actions stay palette-only there.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use sys_global_shortcut::{ShortcutConfig, ShortcutError, ShortcutHandle};
+use tauri::Manager;
+
+#[derive(Default)]
+pub struct GlobalShortcutState {
+    handle: Mutex<Option<ShortcutHandle>>,
+    error: Mutex<Option<ShortcutError>>,
+    /// Registered [`ACTION_SHORTCUTS`], by action ID.
+    actions: Mutex<HashMap<&'static str, ShortcutHandle>>,
+}
+
+/// A global hotkey that invokes an action from the registry.
+pub struct ActionShortcut {
+    pub action: &'static str,
+    /// Keys after the platform modifier (`Mod` on macOS, `Meta` elsewhere),
+    /// in the renderer's notation.
+    pub keys: &'static str,
+    pub windows_modifiers: u32,
+    pub windows_vk: u32,
+    pub macos_modifiers: u32,
+    pub macos_keycode: u32,
+}
+
+pub const ACTION_SHORTCUTS: &[ActionShortcut] = &[ActionShortcut {
+    action: "chat.copy_last_answer",
+    keys: "Alt+C",
+    windows_modifiers: 0x0008 | 0x0001, // MOD_WIN | MOD_ALT
+    windows_vk: 0x43,                   // VK_C
+    macos_modifiers: 0x0100 | 0x0800,   // cmdKey | optionKey
+    macos_keycode: 0x08,                // kVK_ANSI_C
+}];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalShortcutStatus {
+    pub registered: bool,
+    pub error_code: Option<&'static str>,
+    pub error: Option<String>,
+}
+
+impl GlobalShortcutState {
+    pub fn set_result(&self, result: Result<ShortcutHandle, ShortcutError>) {
+        match result {
+            Ok(handle) => {
+                *self.handle.lock() = Some(handle);
+                *self.error.lock() = None;
+            }
+            Err(e) => {
+                *self.handle.lock() = None;
+                *self.error.lock() = Some(e);
+            }
+        }
+    }
+
+    /// Drop the current registration, if any.
+    #[cfg(target_os = "linux")]
+    pub fn clear(&self) {
+        if let Some(handle) = self.handle.lock().take() {
+            handle.unregister();
+        }
+    }
+
+    /// Whether the hotkey for `action` is registered.
+    pub fn has_action(&self, action: &str) -> bool {
+        self.actions.lock().contains_key(action)
+    }
+
+    pub fn status(&self) -> GlobalShortcutStatus {
+        let error = self.error.lock();
+        GlobalShortcutStatus {
+            registered: self.handle.lock().is_some(),
+            error_code: error.as_ref().map(ShortcutError::code),
+            error: error.as_ref().map(ToString::to_string),
+        }
+    }
+
+    /// Re-register the hotkey after the OS may have dropped it (resume from
+    /// sleep). No-op when nothing is registered.
+    #[cfg(target_os = "windows")]
+    pub fn reregister(&self) -> Result<(), ShortcutError> {
+        for (action, handle) in self.actions.lock().iter() {
+            if let Err(e) = handle.reregister() {
+                log::warn!("Failed to re-register shortcut for {}: {}", action, e);
+            }
+        }
+        let handle = self.handle.lock();
+        let Some(handle) = handle.as_ref() else {
+            return Ok(());
+        };
+        let result = handle.reregister();
+        *self.error.lock() = result.as_ref().err().cloned();
+        result
+    }
+}
+
+/// Register the capture hotkey and record the outcome in
+/// [`GlobalShortcutState`]. Failure is non-fatal; the tray still works.
+pub fn register_global_shortcut(app: &tauri::AppHandle) {
+    let shortcut_handle = app.clone();
+    let shortcut = ShortcutHandle::register(
+        ShortcutConfig {
+            linux_trigger: "SUPER+SHIFT+a".into(),
+            linux_description: format!("{} Capture", crate::constants::APP_NAME),
+            windows_modifiers: 0x0008 | 0x0004, // MOD_WIN | MOD_SHIFT
+            windows_vk: 0x41,                   // VK_A
+            macos_modifiers: 0x0100 | 0x0200,   // cmdKey | shiftKey
+            macos_keycode: 0x00,                // kVK_ANSI_A
+        },
+        move || crate::services::tray::capture_screen_with_source(&shortcut_handle, "hotkey"),
+    );
+
+    match &shortcut {
+        Ok(_) => log::info!("Global shortcut registered successfully"),
+        Err(e) => log::warn!(
+            "Global shortcut registration failed (non-fatal, {}): {}",
+            e.code(),
+            e
+        ),
+    }
+    app.state::<GlobalShortcutState>().set_result(shortcut);
+
+    #[cfg(not(target_os = "linux"))]
+    register_action_shortcuts(app);
+}
+
+/// Register [`ACTION_SHORTCUTS`]. Each one is optional: a combo taken by
+/// another app is logged and the action stays reachable from the palette.
+#[cfg(not(target_os = "linux"))]
+fn register_action_shortcuts(app: &tauri::AppHandle) {
+    let state = app.state::<GlobalShortcutState>();
+    for shortcut in ACTION_SHORTCUTS {
+        if state.has_action(shortcut.action) {
+            continue;
+        }
+        let handle = app.clone();
+        let action = shortcut.action;
+        let result = ShortcutHandle::register(
+            ShortcutConfig {
+                linux_trigger: String::new(),
+                linux_description: String::new(),
+                windows_modifiers: shortcut.windows_modifiers,
+                windows_vk: shortcut.windows_vk,
+                macos_modifiers: shortcut.macos_modifiers,
+                macos_keycode: shortcut.macos_keycode,
+            },
+            move || {
+                if let Err(e) = crate::services::actions::invoke(&handle, action, None) {
+                    log::warn!("Shortcut action {} failed: {}", action, e);
+                }
+            },
+        );
+        match result {
+            Ok(registered) => {
+                state.actions.lock().insert(action, registered);
+            }
+            Err(e) => log::warn!(
+                "Shortcut for {} not registered (non-fatal, {}): {}",
+                action,
+                e.code(),
+                e
+            ),
+        }
+    }
+}
+
+/// Default desktop-level trigger installed on Linux.
+pub const DEFAULT_LINUX_TRIGGER: &str = "SUPER+SHIFT+a";
+
+/// CLI flag used by package uninstall hooks to drop the desktop binding.
+pub const REMOVE_SHORTCUT_ARG: &str = "--remove-shortcut";
+
+const LINUX_TRIGGER_PREF: &str = "linuxShortcutTrigger";
+
+/// The trigger saved in preferences, or the default.
+pub fn linux_trigger(app: &tauri::AppHandle) -> String {
+    let prefs_file =
+        crate::utils::get_app_config_dir(app).join(crate::constants::PREFERENCES_FILE_NAME);
+    std::fs::read_to_string(prefs_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|prefs| prefs.get(LINUX_TRIGGER_PREF)?.as_str().map(str::to_string))
+        .filter(|trigger| !trigger.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_LINUX_TRIGGER.to_string())
+}
+
+/// Rebind the desktop shortcut to `trigger` and save it as the preference.
+/// The previous binding is removed so it stops firing.
+#[cfg(target_os = "linux")]
+pub fn update_linux_trigger(app: &tauri::AppHandle, trigger: &str) -> Result<(), String> {
+    let trigger = trigger.trim();
+    if trigger.is_empty() {
+        return Err("Shortcut trigger is empty".to_string());
+    }
+    sys_global_shortcut::update_linux_shortcut(trigger, crate::constants::APP_NAME)?;
+
+    let config_dir = crate::utils::get_app_config_dir(app);
+    let prefs_file = config_dir.join(crate::constants::PREFERENCES_FILE_NAME);
+    let mut prefs = std::fs::read_to_string(&prefs_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .filter(serde_json::Value::is_object)
+        .unwrap_or_else(|| serde_json::json!({}));
+    prefs[LINUX_TRIGGER_PREF] = serde_json::Value::String(trigger.to_string());
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&prefs).map_err(|e| e.to_string())?;
+    std::fs::write(prefs_file, content).map_err(|e| e.to_string())
+}
+
+/// Remove the desktop binding for uninstall. Runs before the Tauri app is
+/// built, so it only needs the app name.
+#[cfg(target_os = "linux")]
+pub fn remove_linux_binding() -> Result<(), String> {
+    sys_global_shortcut::remove_linux_shortcut(crate::constants::APP_NAME)
+}
@@ -0,0 +1,369 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Startup sequencing.
+//!
+//! Setup only does what the first window needs: preferences, the session,
+//! the tray and the window itself. The remaining subsystems start once the
+//! main window has loaded, or right away when launched in the background,
+//! where no window is created until the user opens one. Phase timings are
+//! logged under the `startup` target.
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Start deferred subsystems anyway if the window never reports a load.
+const FIRST_PAINT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// CLI flag used by package uninstall hooks to run
+/// [`remove_linux_integration`].
+pub const REMOVE_INTEGRATION_ARG: &str = "--remove-desktop-integration";
+
+/// Records which version of the desktop shortcut is installed.
+#[cfg(target_os = "linux")]
+const SHORTCUT_MARKER_FILE: &str = ".shortcut_installed";
+
+type PendingAction = Box<dyn FnOnce(&AppHandle) + Send>;
+
+pub struct StartupState {
+    launched: Instant,
+    last_phase: Mutex<Instant>,
+    painted: AtomicBool,
+    deferred_started: AtomicBool,
+    /// Run once the main window has loaded, e.g. events for its page.
+    pending: Mutex<Vec<PendingAction>>,
+}
+
+impl StartupState {
+    /// Call as early as possible; durations are measured from here.
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            launched: now,
+            last_phase: Mutex::new(now),
+            painted: AtomicBool::new(false),
+            deferred_started: AtomicBool::new(false),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Log the end of a startup phase.
+    pub fn phase(&self, name: &str) {
+        let now = Instant::now();
+        let elapsed = now - std::mem::replace(&mut *self.last_phase.lock(), now);
+        log::info!(
+            target: "startup",
+            "{} took {} ms ({} ms since launch)",
+            name,
+            elapsed.as_millis(),
+            (now - self.launched).as_millis()
+        );
+    }
+}
+
+impl Default for StartupState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Start the deferred subsystems now in the background, or after the main
+/// window's first load otherwise.
+pub fn schedule_deferred(app: &AppHandle, start_in_background: bool) {
+    if start_in_background {
+        start_deferred(app);
+        return;
+    }
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_PAINT_TIMEOUT).await;
+        if !handle
+            .state::<StartupState>()
+            .painted
+            .load(Ordering::SeqCst)
+        {
+            log::warn!(target: "startup", "No first paint after {:?}", FIRST_PAINT_TIMEOUT);
+            start_deferred(&handle);
+        }
+    });
+}
+
+/// Called when the main window finished loading its page.
+pub fn main_window_loaded(app: &AppHandle) {
+    let state = app.state::<StartupState>();
+    let pending = {
+        let mut pending = state.pending.lock();
+        if !state.painted.swap(true, Ordering::SeqCst) {
+            state.phase("first paint");
+        }
+        std::mem::take(&mut *pending)
+    };
+    start_deferred(app);
+    for action in pending {
+        action(app);
+    }
+}
+
+/// Run `action` once the main window's page is loaded, creating the window
+/// (hidden) when it does not exist yet, as after a background launch.
+pub fn with_main_window<F>(app: &AppHandle, action: F)
+where
+    F: FnOnce(&AppHandle) + Send + 'static,
+{
+    if app.get_webview_window("main").is_none() {
+        if let Err(e) =
+            super::window::spawn_app_window(app, "main", "index.html", 1030.0, 690.0, "", false)
+        {
+            log::error!("Failed to spawn main window: {}", e);
+            return;
+        }
+    }
+
+    let state = app.state::<StartupState>();
+    let mut pending = state.pending.lock();
+    if state.painted.load(Ordering::SeqCst) {
+        drop(pending);
+        action(app);
+    } else {
+        pending.push(Box::new(action));
+    }
+}
+
+fn start_deferred(app: &AppHandle) {
+    if app
+        .state::<StartupState>()
+        .deferred_started
+        .swap(true, Ordering::SeqCst)
+    {
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let handle = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            install_linux_integration(&handle);
+            handle.state::<StartupState>().phase("linux integration");
+        });
+    }
+
+    let handle = app.clone();
+    let _ = app.run_on_main_thread(move || {
+        super::shortcut::register_global_shortcut(&handle);
+        super::power::start(&handle);
+        super::battery::start(&handle);
+        super::memory::start(&handle);
+        super::retention::start(&handle);
+        super::plugins::start(&handle);
+        super::local_api::start(&handle);
+        super::recovery::scan(&handle);
+        super::ocr_backfill::resume(&handle);
+
+        let ocr_handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            ocr_handle
+                .state::<super::ocr::DesktopOcrService>()
+                .start_monitor();
+        });
+
+        let integrity_handle = handle.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            super::integrity::verify_bundled_sidecars(&integrity_handle);
+        });
+
+        let index_handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = super::search_index::sync(&index_handle).await {
+                log::warn!("Search stub sync failed: {}", e);
+            }
+        });
+        handle.state::<StartupState>().phase("deferred subsystems");
+    });
+}
+
+/// Unpack the capture sidecar, move a downloaded AppImage into
+/// `~/Applications` and install the desktop shortcut, each only when needed.
+#[cfg(target_os = "linux")]
+fn install_linux_integration(handle: &AppHandle) {
+    let app_local_data = handle
+        .path()
+        .app_local_data_dir()
+        .expect("Failed to get local data dir");
+    let target_sidecar_dir = app_local_data.join("qt-capture-runtime");
+    let config_dir = crate::utils::get_app_config_dir(handle);
+    let capture_installed_marker = config_dir.join(".capture_installed");
+
+    if !capture_installed_marker.exists() && !target_sidecar_dir.exists() {
+        log::info!("First launch: Extracting Qt capture sidecar...");
+        std::fs::create_dir_all(&target_sidecar_dir).unwrap();
+
+        if let Ok(resource_dir) = handle.path().resource_dir() {
+            let tar_path = resource_dir
+                .join("binaries")
+                .join("qt-capture-x86_64-unknown-linux-gnu")
+                .join("runtime.tar.gz");
+
+            if tar_path.exists() {
+                use flate2::read::GzDecoder;
+                use std::fs::File;
+                use tar::Archive;
+
+                let tar_gz = File::open(&tar_path).expect("Failed to open sidecar tarball");
+                let tar = GzDecoder::new(tar_gz);
+                let mut archive = Archive::new(tar);
+                if let Err(e) = archive.unpack(&target_sidecar_dir) {
+                    log::error!("Failed to unpack sidecar: {}", e);
+                } else {
+                    // Ensure the binary is executable
+                    let bin_path = target_sidecar_dir.join("_internal/usr/bin/capture-bin");
+                    if bin_path.exists() {
+                        use std::os::unix::fs::PermissionsExt;
+                        let mut perms = std::fs::metadata(&bin_path).unwrap().permissions();
+                        perms.set_mode(0o755);
+                        std::fs::set_permissions(&bin_path, perms).unwrap();
+                    }
+                    let _ = std::fs::write(&capture_installed_marker, "1");
+                }
+            } else {
+                log::error!("Sidecar tarball not found at {}", tar_path.display());
+            }
+        }
+    }
+
+    if let (Ok(appimage_path), Ok(_appdir_path)) =
+        (std::env::var("APPIMAGE"), std::env::var("APPDIR"))
+    {
+        let appimage_path = std::path::PathBuf::from(appimage_path);
+        if let Some(home_dir) = dirs::home_dir() {
+            let applications_dir = home_dir.join("Applications");
+
+            if !appimage_path.starts_with(&applications_dir)
+                && !appimage_path.starts_with("/usr")
+                && !appimage_path.starts_with("/opt")
+            {
+                log::info!(
+                    "AppImage running from temporary location: {}. Migrating...",
+                    appimage_path.display()
+                );
+
+                let _ = std::fs::create_dir_all(&applications_dir);
+                let target_appimage = applications_dir.join("Squigit.AppImage");
+
+                if std::fs::rename(&appimage_path, &target_appimage).is_err()
+                    && std::fs::copy(&appimage_path, &target_appimage).is_ok()
+                {
+                    let _ = std::fs::remove_file(&appimage_path);
+                }
+
+                let target_icon_dir = home_dir.join(".local/share/icons/hicolor/512x512/apps");
+                let _ = std::fs::create_dir_all(&target_icon_dir);
+                let target_icon = target_icon_dir.join("squigit.png");
+
+                let _ = std::fs::write(&target_icon, include_bytes!("../../icons/icon.png"));
+
+                let target_desktop_dir = home_dir.join(".local/share/applications");
+                let _ = std::fs::create_dir_all(&target_desktop_dir);
+                let target_desktop = target_desktop_dir.join("squigit.desktop");
+                let desktop_content = format!(
+                    r#"[Desktop Entry]
+Name=Squigit
+Comment=AI and contextual analysis module for screenshot data
+Exec="{}" %u
+Icon=squigit
+Terminal=false
+Type=Application
+Categories=Utility;"#,
+                    target_appimage.display()
+                );
+                let _ = std::fs::write(&target_desktop, desktop_content);
+
+                let _ = std::process::Command::new("update-desktop-database")
+                    .arg(home_dir.join(".local/share/applications"))
+                    .status();
+
+                log::info!("AppImage permanently installed to ~/Applications.");
+            }
+        }
+    }
+
+    let marker_file = config_dir.join(SHORTCUT_MARKER_FILE);
+    const SHORTCUT_MARKER_VERSION: &str = "2";
+
+    let installed_version = std::fs::read_to_string(&marker_file)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+
+    if installed_version != SHORTCUT_MARKER_VERSION {
+        log::info!(
+            "Linux shortcut install/migration required: target marker version {}, current '{}'",
+            SHORTCUT_MARKER_VERSION,
+            installed_version
+        );
+        if let Ok(exe) = std::env::current_exe() {
+            let bin = exe.to_string_lossy();
+            match sys_global_shortcut::install_linux_shortcut(
+                &bin,
+                &super::shortcut::linux_trigger(handle),
+                crate::constants::APP_NAME,
+            ) {
+                Ok(_) => {
+                    log::info!("Successfully installed Linux global shortcut");
+                    if let Err(e) = std::fs::write(&marker_file, SHORTCUT_MARKER_VERSION) {
+                        log::error!("Failed to create shortcut marker file: {}", e);
+                    }
+                }
+                Err(e) => {
+                    log::error!("Failed to install Linux global shortcut: {}", e);
+                }
+            }
+        }
+    }
+}
+
+/// Undo what the app installed into the desktop, for uninstall: the
+/// shortcut binding and its helper script, the shortcut marker (so a
+/// reinstall binds again), the autostart entry, and the launcher entry and
+/// icon written for a migrated AppImage. Runs without a Tauri app, so the
+/// config directory is passed in. Returns the files removed.
+#[cfg(target_os = "linux")]
+pub fn remove_linux_integration(config_dir: &std::path::Path) -> Result<Vec<String>, String> {
+    let mut errors = Vec::new();
+    if let Err(e) = sys_global_shortcut::uninstall_linux_shortcut(crate::constants::APP_NAME) {
+        errors.push(e);
+    }
+
+    let mut files = vec![config_dir.join(SHORTCUT_MARKER_FILE)];
+    if let Some(home_dir) = dirs::home_dir() {
+        files.push(
+            home_dir
+                .join(".config/autostart")
+                .join(format!("{}.desktop", crate::constants::APP_NAME)),
+        );
+        files.push(home_dir.join(".local/share/applications/squigit.desktop"));
+        files.push(home_dir.join(".local/share/icons/hicolor/512x512/apps/squigit.png"));
+    }
+
+    let mut removed = Vec::new();
+    for file in files {
+        match std::fs::remove_file(&file) {
+            Ok(()) => removed.push(file.display().to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => errors.push(format!("{}: {}", file.display(), e)),
+        }
+    }
+    if let Some(home_dir) = dirs::home_dir() {
+        let _ = std::process::Command::new("update-desktop-database")
+            .arg(home_dir.join(".local/share/applications"))
+            .status();
+    }
+
+    if errors.is_empty() {
+        Ok(removed)
+    } else {
+        Err(errors.join("; "))
+    }
+}
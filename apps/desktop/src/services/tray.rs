@@ -1,10 +1,12 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::services::integration::{DesktopIntegrationState, TrayBackend};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
 pub fn show_window(app: &AppHandle) {
+    crate::services::idle::window_shown(app);
     if app.get_webview_window("main").is_none() {
         let (base_w, base_h) = (1030.0, 690.0);
         if let Err(e) =
@@ -33,6 +35,7 @@ pub fn toggle_window(app: &AppHandle) {
     if let Some(window) = app.get_webview_window("main") {
         if window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(false) {
             let _ = window.hide();
+            crate::services::session::set_window_visible(app, false);
         } else {
             let (x, y, _, _) = super::window::center_on_cursor_monitor(app, 1030.0, 690.0);
             let _ = window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
@@ -43,6 +46,8 @@ pub fn toggle_window(app: &AppHandle) {
             let _ = window.show();
             let _ = window.set_focus();
         }
+    } else {
+        show_window(app);
     }
 }
 
@@ -59,15 +64,67 @@ pub fn capture_screen_with_source(app: &AppHandle, source: &str) {
     });
 }
 
+/// Grab a whole display from its tray item, once the menu has closed.
+pub fn capture_monitor(app: &AppHandle, index: usize) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        // Failures reach the frontend as `capture-failed`.
+        let _ = crate::services::capture::capture_monitor(&app_handle, index).await;
+    });
+}
+
+/// Grab the window that was focused before the tray menu opened, once the
+/// menu has closed.
+pub fn capture_active_window(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let _ = crate::services::capture::capture_active_window(&app_handle).await;
+    });
+}
+
+/// "Capture Display N" labels for the connected displays, by monitor index.
+/// Empty with a single display, where "Capture" covers it.
+fn monitor_items() -> Vec<(usize, String)> {
+    let monitors = crate::services::capture::list_monitors();
+    if monitors.len() < 2 {
+        return Vec::new();
+    }
+    monitors
+        .into_iter()
+        .map(|monitor| {
+            (
+                monitor.index,
+                format!("Capture Display {}", monitor.index + 1),
+            )
+        })
+        .collect()
+}
+
 // ──────────────────────────────────────────────────────────────
 //  macOS / Windows — Tauri native TrayIconBuilder
+//  Also the Linux fallback when no StatusNotifierWatcher is running:
+//  libappindicator then degrades to an XEmbed icon.
 // ──────────────────────────────────────────────────────────────
 #[cfg(not(target_os = "linux"))]
 pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    setup_native_tray(app)?;
+    app.state::<DesktopIntegrationState>()
+        .set_tray(TrayBackend::Native, None);
+    Ok(())
+}
+
+/// Menu item IDs of the "Capture Display N" entries, followed by the index.
+const MONITOR_ITEM_PREFIX: &str = "capture_monitor:";
+
+fn setup_native_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
     use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
 
     let capture_i = MenuItem::with_id(app, "capture", "Capture", true, None::<&str>)?;
+    let capture_window_i =
+        MenuItem::with_id(app, "capture_window", "Capture Window", true, None::<&str>)?;
     let show_i = MenuItem::with_id(
         app,
         "show_ui",
@@ -77,7 +134,10 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     )?;
     let sep = PredefinedMenuItem::separator(app)?;
     let exit_i = MenuItem::with_id(app, "exit", "Exit", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&show_i, &capture_i, &sep, &exit_i])?;
+    let menu = Menu::with_items(
+        app,
+        &[&show_i, &capture_i, &capture_window_i, &sep, &exit_i],
+    )?;
 
     let img = image::load_from_memory(include_bytes!("../../icons/64x64.png"))?;
     let rgba = img.into_rgba8();
@@ -97,9 +157,17 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .show_menu_on_left_click(false)
         .on_menu_event(|app, event| match event.id.as_ref() {
             "capture" => capture_screen_with_source(app, "tray"),
+            "capture_window" => capture_active_window(app),
             "show_ui" => show_window(app),
             "exit" => app.exit(0),
-            _ => {}
+            id => {
+                if let Some(index) = id
+                    .strip_prefix(MONITOR_ITEM_PREFIX)
+                    .and_then(|index| index.parse().ok())
+                {
+                    capture_monitor(app, index);
+                }
+            }
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click {
@@ -113,6 +181,19 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         })
         .build(app)?;
 
+    // Enumerating displays can shell out, so their items come in afterwards.
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        for (position, (index, label)) in monitor_items().into_iter().enumerate() {
+            let id = format!("{}{}", MONITOR_ITEM_PREFIX, index);
+            let added = MenuItem::with_id(&handle, id, label, true, None::<&str>)
+                .and_then(|item| menu.insert(&item, 3 + position));
+            if let Err(e) = added {
+                log::warn!("Failed to add a display to the tray menu: {}", e);
+            }
+        }
+    });
+
     Ok(())
 }
 
@@ -132,6 +213,9 @@ mod sni {
     type DbusLayout = (u32, DbusMenuNode);
     type DbusGroupProps = Vec<(i32, HashMap<String, OwnedValue>)>;
 
+    /// ID of the "Capture Display 1" item; the others follow by index.
+    const MONITOR_ITEM_BASE: i32 = 100;
+
     fn load_icon_argb() -> (i32, i32, Vec<u8>) {
         let img = image::load_from_memory_with_format(
             include_bytes!("../../icons/32x32.png"),
@@ -200,12 +284,29 @@ mod sni {
             let sep_item: DbusMenuNode = (3, sep_props, vec![]);
             let exit_item: DbusMenuNode = (4, exit_props, vec![]);
 
-            let children: Vec<OwnedValue> = vec![
+            let mut window_props: HashMap<String, OwnedValue> = HashMap::new();
+            window_props.insert(
+                "label".into(),
+                Value::from("Capture Window").try_into().unwrap(),
+            );
+            window_props.insert("enabled".into(), Value::from(true).try_into().unwrap());
+            let window_item: DbusMenuNode = (5, window_props, vec![]);
+
+            let mut children: Vec<OwnedValue> = vec![
                 Value::from(show_ui_item).try_into().unwrap(),
                 Value::from(show_item).try_into().unwrap(),
-                Value::from(sep_item).try_into().unwrap(),
-                Value::from(exit_item).try_into().unwrap(),
+                Value::from(window_item).try_into().unwrap(),
             ];
+            // Built on every open, so the list follows hotplugged displays.
+            for (index, label) in super::monitor_items() {
+                let mut props: HashMap<String, OwnedValue> = HashMap::new();
+                props.insert("label".into(), Value::from(label).try_into().unwrap());
+                props.insert("enabled".into(), Value::from(true).try_into().unwrap());
+                let item: DbusMenuNode = (MONITOR_ITEM_BASE + index as i32, props, vec![]);
+                children.push(Value::from(item).try_into().unwrap());
+            }
+            children.push(Value::from(sep_item).try_into().unwrap());
+            children.push(Value::from(exit_item).try_into().unwrap());
 
             let mut root_props: HashMap<String, OwnedValue> = HashMap::new();
             root_props.insert(
@@ -236,6 +337,10 @@ mod sni {
                     2 => super::show_window(&self.app_handle), // Squigit
                     1 => super::capture_screen_with_source(&self.app_handle, "tray"), // Capture
                     4 => self.app_handle.exit(0),              // Exit
+                    5 => super::capture_active_window(&self.app_handle), // Capture Window
+                    id if id >= MONITOR_ITEM_BASE => {
+                        super::capture_monitor(&self.app_handle, (id - MONITOR_ITEM_BASE) as usize)
+                    }
                     _ => {}
                 }
             }
@@ -351,23 +456,79 @@ mod sni {
 
 #[cfg(target_os = "linux")]
 pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    let handle = app.clone();
+    let capabilities = app.state::<DesktopIntegrationState>().capabilities();
+    if !capabilities.status_notifier_watcher {
+        log::warn!("No StatusNotifierWatcher on the session bus, using fallback tray");
+        setup_fallback_tray(app, "ERR_NO_STATUS_NOTIFIER_WATCHER".to_string());
+        return Ok(());
+    }
 
+    let handle = app.clone();
     tauri::async_runtime::spawn(async move {
-        match setup_sni_tray(handle).await {
-            Ok(()) => {}
-            Err(e) => eprintln!("SNI tray setup failed: {}", e),
+        match setup_sni_tray(handle.clone()).await {
+            Ok(()) => handle
+                .state::<DesktopIntegrationState>()
+                .set_tray(TrayBackend::StatusNotifier, None),
+            Err(e) => {
+                log::error!("SNI tray setup failed, using fallback tray: {}", e);
+                let error = e.to_string();
+                let fallback_handle = handle.clone();
+                let _ = handle.run_on_main_thread(move || {
+                    setup_fallback_tray(&fallback_handle, error);
+                });
+            }
         }
     });
 
     Ok(())
 }
 
+/// Tauri tray through libappindicator. `reason` records why SNI was skipped.
+#[cfg(target_os = "linux")]
+fn setup_fallback_tray(app: &AppHandle, reason: String) {
+    let state = app.state::<DesktopIntegrationState>();
+    if !state.capabilities().app_indicator_library {
+        log::error!("No appindicator library available, running without a tray icon");
+        state.set_tray(
+            TrayBackend::None,
+            Some(format!("{}; ERR_NO_APP_INDICATOR_LIBRARY", reason)),
+        );
+        return;
+    }
+    match setup_native_tray(app) {
+        Ok(()) => state.set_tray(TrayBackend::AppIndicator, Some(reason)),
+        Err(e) => {
+            log::error!("Fallback tray setup failed: {}", e);
+            state.set_tray(TrayBackend::None, Some(format!("{}; {}", reason, e)));
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+static SNI_CONNECTION: tokio::sync::Mutex<Option<zbus::Connection>> =
+    tokio::sync::Mutex::const_new(None);
+
+#[cfg(target_os = "linux")]
+fn sni_service_name() -> String {
+    format!("org.kde.StatusNotifierItem-{}-1", std::process::id())
+}
+
 #[cfg(target_os = "linux")]
 async fn setup_sni_tray(app: AppHandle) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    use zbus::conn::Builder;
+    let mut slot = SNI_CONNECTION.lock().await;
+    // Release the bus name before claiming it again on a fresh connection.
+    slot.take();
+    let connection = build_sni_connection(&app).await?;
+    sni::register_with_watcher(&connection, &sni_service_name()).await?;
+    *slot = Some(connection);
+    Ok(())
+}
 
-    let service_name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+#[cfg(target_os = "linux")]
+async fn build_sni_connection(
+    app: &AppHandle,
+) -> Result<zbus::Connection, Box<dyn std::error::Error + Send + Sync>> {
+    use zbus::conn::Builder;
 
     let sni_item = sni::StatusNotifierItem::new(app.clone());
     let dbus_menu = sni::DbusMenu {
@@ -375,14 +536,35 @@ async fn setup_sni_tray(app: AppHandle) -> Result<(), Box<dyn std::error::Error
     };
 
     let connection = Builder::session()?
-        .name(service_name.as_str())?
+        .name(sni_service_name())?
         .serve_at("/StatusNotifierItem", sni_item)?
         .serve_at("/MenuBar", dbus_menu)?
         .build()
         .await?;
+    Ok(connection)
+}
+
+/// Re-announce the tray item after resume. The watcher (or the whole
+/// session bus connection) may have been restarted while asleep, so the
+/// connection is rebuilt when re-registering on it fails.
+#[cfg(target_os = "linux")]
+pub async fn refresh_tray(app: &AppHandle) {
+    let (backend, _) = app.state::<DesktopIntegrationState>().tray();
+    if backend != TrayBackend::StatusNotifier {
+        return;
+    }
+
+    {
+        let slot = SNI_CONNECTION.lock().await;
+        if let Some(connection) = slot.as_ref() {
+            match sni::register_with_watcher(connection, &sni_service_name()).await {
+                Ok(()) => return,
+                Err(e) => log::warn!("SNI re-registration failed, rebuilding tray: {}", e),
+            }
+        }
+    }
 
-    sni::register_with_watcher(&connection, &service_name).await?;
-    loop {
-        tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+    if let Err(e) = setup_sni_tray(app.clone()).await {
+        log::error!("SNI tray rebuild failed: {}", e);
     }
 }
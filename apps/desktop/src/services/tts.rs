@@ -0,0 +1,123 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Text-to-speech through the platform synthesizer: `say` on macOS,
+//! speech-dispatcher or eSpeak on Linux, SAPI via PowerShell on Windows.
+//! One utterance plays at a time and `stop` cuts it off.
+
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+
+use parking_lot::Mutex;
+
+#[derive(Default)]
+pub struct TextToSpeech {
+    current: Mutex<Option<Child>>,
+}
+
+impl TextToSpeech {
+    /// Speak `text` and block until it finishes or `stop` is called.
+    pub fn speak(&self, text: &str) -> Result<(), String> {
+        let text = text.trim().trim_start_matches('-');
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let mut child = spawn_synthesizer(text)?;
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        *self.current.lock() = Some(child);
+
+        // Poll rather than wait() so `stop` can take the child and kill it.
+        loop {
+            let mut guard = self.current.lock();
+            let Some(child) = guard.as_mut() else {
+                return Ok(());
+            };
+            match child.try_wait() {
+                Ok(Some(_)) => {
+                    *guard = None;
+                    return Ok(());
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    *guard = None;
+                    return Err(e.to_string());
+                }
+            }
+            drop(guard);
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+
+    /// Interrupt the utterance being spoken, if any.
+    pub fn stop(&self) {
+        if let Some(mut child) = self.current.lock().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+            // speech-dispatcher keeps playing after its client exits.
+            #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+            if which::which("spd-say").is_ok() {
+                let _ = Command::new("spd-say").arg("--cancel").status();
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn spawn_synthesizer(_text: &str) -> Result<Child, String> {
+    // `say` reads the text from stdin when given none.
+    Command::new("say")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("ERR_TTS_UNAVAILABLE: {}", e))
+}
+
+#[cfg(target_os = "windows")]
+fn spawn_synthesizer(_text: &str) -> Result<Child, String> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Add-Type -AssemblyName System.Speech; \
+             (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak([Console]::In.ReadToEnd())",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("ERR_TTS_UNAVAILABLE: {}", e))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn spawn_synthesizer(text: &str) -> Result<Child, String> {
+    let mut command = if which::which("spd-say").is_ok() {
+        let mut command = Command::new("spd-say");
+        command.args(["--wait", text]);
+        command
+    } else if let Some(espeak) = ["espeak-ng", "espeak"]
+        .into_iter()
+        .find(|name| which::which(name).is_ok())
+    {
+        let mut command = Command::new(espeak);
+        command.arg(text);
+        command
+    } else {
+        return Err("ERR_TTS_UNAVAILABLE: install speech-dispatcher or espeak-ng".to_string());
+    };
+
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("ERR_TTS_UNAVAILABLE: {}", e))
+}
@@ -0,0 +1,78 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Vertex AI sign-in for Gemini.
+//!
+//! A profile with Vertex AI enabled sends its Gemini requests to its
+//! Google Cloud project instead of the public API, authorized by the
+//! stored service account or user credentials, or by the machine's
+//! application default credentials when none are stored. The signed-in
+//! client is kept until the settings change, so credentials are decrypted
+//! and tokens fetched once rather than per request.
+
+use std::sync::{Arc, Mutex};
+
+use ops_profile_store::{ProfileStore, VertexConfig};
+use ops_squigit_brain::provider::gemini::vertex::VertexAuth;
+
+/// Profile ID and settings the cached client was made from.
+type AuthKey = (String, String, String, bool);
+
+static ACTIVE_AUTH: Mutex<Option<(AuthKey, Arc<VertexAuth>)>> = Mutex::new(None);
+
+/// The active profile's Vertex AI client, if it enabled Vertex AI.
+pub fn active_auth() -> Result<Option<Arc<VertexAuth>>, String> {
+    let store = ProfileStore::new().map_err(|e| e.to_string())?;
+    let Some(profile_id) = store.get_active_profile_id().map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let config = store
+        .get_vertex_config(&profile_id)
+        .map_err(|e| e.to_string())?;
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let key = auth_key(&profile_id, &config);
+    let mut cached = ACTIVE_AUTH.lock().map_err(|e| e.to_string())?;
+    if let Some((cached_key, auth)) = cached.as_ref() {
+        if *cached_key == key {
+            return Ok(Some(auth.clone()));
+        }
+    }
+
+    let auth = match store
+        .get_vertex_credentials(&profile_id)
+        .map_err(|e| e.to_string())?
+    {
+        Some(credentials) => VertexAuth::new(&credentials, &config.project_id, &config.location)?,
+        None => VertexAuth::application_default(&config.project_id, &config.location)?,
+    };
+    let auth = Arc::new(auth);
+    *cached = Some((key, auth.clone()));
+    Ok(Some(auth))
+}
+
+/// Whether the profile sends its Gemini requests to Vertex AI.
+pub fn is_enabled(store: &ProfileStore, profile_id: &str) -> bool {
+    store
+        .get_vertex_config(profile_id)
+        .map(|config| config.enabled)
+        .unwrap_or(false)
+}
+
+/// Drop the cached client, e.g. after the credentials were replaced.
+pub fn forget() {
+    if let Ok(mut cached) = ACTIVE_AUTH.lock() {
+        *cached = None;
+    }
+}
+
+fn auth_key(profile_id: &str, config: &VertexConfig) -> AuthKey {
+    (
+        profile_id.to_string(),
+        config.project_id.clone(),
+        config.location.clone(),
+        config.has_credentials,
+    )
+}
@@ -0,0 +1,174 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Voice commands: final STT transcriptions that are exactly one of a small
+//! set of phrases run an action from the registry instead of being typed
+//! into the chat input. Off by default; each command can be disabled.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+pub const VOICE_COMMAND_EVENT: &str = "voice-command";
+
+const VOICE_COMMANDS_PREF: &str = "voiceCommands";
+
+/// Words that may surround a command without changing it.
+const FILLER_WORDS: &[&str] = &["please", "hey", "ok", "okay", "squigit", "now"];
+
+struct VoiceCommand {
+    action: &'static str,
+    phrases: &'static [&'static str],
+}
+
+const COMMANDS: &[VoiceCommand] = &[
+    VoiceCommand {
+        action: "capture.screen",
+        phrases: &[
+            "take a screenshot",
+            "take screenshot",
+            "capture the screen",
+            "capture screen",
+        ],
+    },
+    VoiceCommand {
+        action: "chat.new",
+        phrases: &["new chat", "start a new chat", "open a new chat"],
+    },
+    VoiceCommand {
+        action: "chat.read_back",
+        phrases: &["read that back", "read it back", "read that again"],
+    },
+    VoiceCommand {
+        action: "chat.search",
+        phrases: &["search chats", "search my chats"],
+    },
+    VoiceCommand {
+        action: "settings.open",
+        phrases: &["open settings"],
+    },
+];
+
+/// Saved voice command preferences.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceCommandSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Per-action flags; actions not listed are enabled.
+    #[serde(default)]
+    pub commands: BTreeMap<String, bool>,
+}
+
+impl VoiceCommandSettings {
+    fn command_enabled(&self, action: &str) -> bool {
+        self.commands.get(action).copied().unwrap_or(true)
+    }
+
+    /// Action for a transcript that is exactly a known, enabled phrase.
+    pub fn match_transcript(&self, text: &str) -> Option<&'static str> {
+        if !self.enabled {
+            return None;
+        }
+        let normalized = normalize(text);
+        if normalized.is_empty() {
+            return None;
+        }
+        COMMANDS
+            .iter()
+            .filter(|command| self.command_enabled(command.action))
+            .find(|command| command.phrases.contains(&normalized.as_str()))
+            .map(|command| command.action)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoiceCommandInfo {
+    pub action: &'static str,
+    pub phrases: &'static [&'static str],
+    pub enabled: bool,
+}
+
+/// Lowercase, drop punctuation and strip filler words from both ends.
+fn normalize(text: &str) -> String {
+    let cleaned: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    let is_content = |word: &&str| !FILLER_WORDS.contains(word);
+    let Some(start) = words.iter().position(is_content) else {
+        return String::new();
+    };
+    let end = words.iter().rposition(is_content).unwrap_or(start);
+    words[start..=end].join(" ")
+}
+
+pub fn settings(app: &AppHandle) -> VoiceCommandSettings {
+    crate::utils::read_preferences(app)
+        .and_then(|mut prefs| {
+            prefs
+                .get_mut(VOICE_COMMANDS_PREF)
+                .map(serde_json::Value::take)
+        })
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn list(app: &AppHandle) -> Vec<VoiceCommandInfo> {
+    let settings = settings(app);
+    COMMANDS
+        .iter()
+        .map(|command| VoiceCommandInfo {
+            action: command.action,
+            phrases: command.phrases,
+            enabled: settings.command_enabled(command.action),
+        })
+        .collect()
+}
+
+pub fn save_settings(app: &AppHandle, settings: &VoiceCommandSettings) -> Result<(), String> {
+    let is_known = |action: &str| COMMANDS.iter().any(|command| command.action == action);
+    if let Some(unknown) = settings
+        .commands
+        .keys()
+        .find(|action| !is_known(action.as_str()))
+    {
+        return Err(format!("ERR_UNKNOWN_ACTION: {}", unknown));
+    }
+
+    crate::utils::write_preference(
+        app,
+        VOICE_COMMANDS_PREF,
+        serde_json::to_value(settings).map_err(|e| e.to_string())?,
+    )
+}
+
+/// Run the action for a recognized command and announce it with
+/// `voice-command`. The transcript is not forwarded as text.
+pub fn dispatch(app: &AppHandle, action: &'static str, transcript: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = crate::services::actions::invoke(&app, action, None);
+        if let Err(e) = &result {
+            log::warn!("Voice command {} failed: {}", action, e);
+        }
+        let _ = app.emit(
+            VOICE_COMMAND_EVENT,
+            serde_json::json!({
+                "action": action,
+                "transcript": transcript,
+                "error": result.err(),
+            }),
+        );
+    });
+}
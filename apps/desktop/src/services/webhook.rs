@@ -0,0 +1,180 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Outbound automation webhook.
+//!
+//! Events are posted as JSON to the active profile's webhook when it is
+//! enabled and subscribed to them:
+//!
+//! ```json
+//! { "id": "…", "event": "ocr.done", "timestamp": "2026-01-01T00:00:00Z", "data": { … } }
+//! ```
+//!
+//! With a secret set, requests carry `X-Squigit-Signature: sha256=<hex>`,
+//! an HMAC of `"{X-Squigit-Timestamp}.{body}"`. Network errors, 408, 429
+//! and 5xx responses are retried with exponential backoff.
+
+use ops_profile_store::{sign_webhook_payload, ProfileStore, WebhookConfig};
+use std::time::Duration;
+
+pub use ops_profile_store::webhook::{
+    WEBHOOK_EVENT_ANALYSIS_FINISHED, WEBHOOK_EVENT_CAPTURE_COMPLETE, WEBHOOK_EVENT_OCR_DONE,
+};
+
+/// Sent by `test_webhook` regardless of the subscribed events.
+const PING_EVENT: &str = "ping";
+const MAX_ATTEMPTS: u32 = 5;
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(2);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest reply excerpt sent as `summary` with `analysis.finished`.
+const SUMMARY_MAX_CHARS: usize = 500;
+
+struct Target {
+    url: String,
+    secret: Option<String>,
+}
+
+/// Deliver `event` in the background if the active profile subscribed to it.
+pub fn notify(event: &'static str, data: serde_json::Value) {
+    tauri::async_runtime::spawn(async move {
+        let loaded = tauri::async_runtime::spawn_blocking(move || load_target(event))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result);
+        let target = match loaded {
+            Ok(Some(target)) => target,
+            Ok(None) => return,
+            Err(e) => {
+                log::warn!("Webhook settings unavailable: {}", e);
+                return;
+            }
+        };
+
+        let body = payload(event, data);
+        let mut delay = FIRST_RETRY_DELAY;
+        for attempt in 1..=MAX_ATTEMPTS {
+            match post(&target, event, &body).await {
+                Ok(_) => return,
+                Err((e, retryable)) => {
+                    if !retryable || attempt == MAX_ATTEMPTS {
+                        log::warn!(
+                            "Webhook {} failed after {} attempt(s): {}",
+                            event,
+                            attempt,
+                            e
+                        );
+                        return;
+                    }
+                    log::info!("Webhook {} attempt {} failed: {}", event, attempt, e);
+                }
+            }
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    });
+}
+
+/// Send a `ping` to the configured URL once. Returns the response status.
+pub async fn ping() -> Result<u16, String> {
+    let target = tauri::async_runtime::spawn_blocking(|| -> Result<Target, String> {
+        let store = ProfileStore::new().map_err(|e| e.to_string())?;
+        let (config, secret) = active_config(&store)?;
+        if config.url.is_empty() {
+            return Err("No webhook URL configured".to_string());
+        }
+        Ok(Target {
+            url: config.url,
+            secret,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let body = payload(PING_EVENT, serde_json::json!({}));
+    post(&target, PING_EVENT, &body).await.map_err(|(e, _)| e)
+}
+
+/// The first paragraph of a reply, cut to a readable length.
+pub fn summarize(reply: &str) -> String {
+    let first = reply.trim().split("\n\n").next().unwrap_or_default().trim();
+    if first.chars().count() <= SUMMARY_MAX_CHARS {
+        return first.to_string();
+    }
+    let cut: String = first.chars().take(SUMMARY_MAX_CHARS).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn load_target(event: &str) -> Result<Option<Target>, String> {
+    let store = ProfileStore::new().map_err(|e| e.to_string())?;
+    let Ok((config, secret)) = active_config(&store) else {
+        return Ok(None);
+    };
+    Ok(config.wants(event).then_some(Target {
+        url: config.url,
+        secret,
+    }))
+}
+
+fn active_config(store: &ProfileStore) -> Result<(WebhookConfig, Option<String>), String> {
+    let profile_id = store
+        .get_active_profile_id()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No active profile".to_string())?;
+    let config = store
+        .get_webhook_config(&profile_id)
+        .map_err(|e| e.to_string())?;
+    let secret = if config.has_secret {
+        store
+            .get_webhook_secret(&profile_id)
+            .map_err(|e| e.to_string())?
+    } else {
+        None
+    };
+    Ok((config, secret))
+}
+
+fn payload(event: &str, data: serde_json::Value) -> Vec<u8> {
+    let body = serde_json::json!({
+        "id": format!("{:032x}", rand::random::<u128>()),
+        "event": event,
+        "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "data": data,
+    });
+    serde_json::to_vec(&body).unwrap_or_default()
+}
+
+/// Post once. Errors carry whether the delivery is worth retrying.
+async fn post(target: &Target, event: &str, body: &[u8]) -> Result<u16, (String, bool)> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| (e.to_string(), false))?;
+
+    let timestamp = chrono::Utc::now().timestamp();
+    let mut request = client
+        .post(&target.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(
+            reqwest::header::USER_AGENT,
+            format!("Squigit/{}", env!("CARGO_PKG_VERSION")),
+        )
+        .header("X-Squigit-Event", event)
+        .header("X-Squigit-Timestamp", timestamp.to_string())
+        .body(body.to_vec());
+    if let Some(secret) = &target.secret {
+        request = request.header(
+            "X-Squigit-Signature",
+            format!("sha256={}", sign_webhook_payload(secret, timestamp, body)),
+        );
+    }
+
+    let response = request.send().await.map_err(|e| (e.to_string(), true))?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(status.as_u16());
+    }
+    let retryable = status.is_server_error()
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    Err((format!("HTTP {}", status), retryable))
+}
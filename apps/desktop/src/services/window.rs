@@ -1,6 +1,7 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use tauri::webview::PageLoadEvent;
 use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
 
 /// Returns the initial background color based on the saved theme preference,
@@ -156,6 +157,11 @@ pub fn spawn_app_window(
         .decorations(false)
         .initialization_script(theme_bootstrap_script(app))
         .background_color(initial_bg_color(app))
+        .on_page_load(|window, payload| {
+            if window.label() == "main" && payload.event() == PageLoadEvent::Finished {
+                crate::services::startup::main_window_loaded(window.app_handle());
+            }
+        })
         .build()
         .map_err(|e| e.to_string())?;
 
@@ -164,6 +170,12 @@ pub fn spawn_app_window(
         WindowEvent::CloseRequested { api, .. } => {
             let _ = window_clone.hide();
             api.prevent_close();
+            if window_clone.label() == "main" {
+                crate::services::session::set_window_visible(window_clone.app_handle(), false);
+            }
+        }
+        WindowEvent::Focused(true) if window_clone.label() == "main" => {
+            crate::services::session::set_window_visible(window_clone.app_handle(), true);
         }
         WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
             if let Some(first_path) = paths.first() {
@@ -0,0 +1,112 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Windows taskbar integration.
+//!
+//! The jump list offers "Capture region", "New chat" and the most recent
+//! chats; each entry relaunches the app with a deep link, which the
+//! single-instance plugin forwards to the running app. Finished captures
+//! raise a toast with "Open chat" and "Capture again" buttons when the main
+//! window did not make it to the front, which Windows often refuses a
+//! background app.
+
+use std::time::Duration;
+use sys_windows_shell::{JumpList, JumpListLink, Toast, ToastAction};
+use tauri::{AppHandle, Manager};
+
+/// Recent chats listed in the jump list.
+const RECENT_CHATS: usize = 5;
+/// Time for the main window to take focus before deciding on a toast.
+const FOCUS_SETTLE: Duration = Duration::from_millis(600);
+
+const CAPTURE_LINK: &str = "snapllm://capture";
+const NEW_CHAT_LINK: &str = "snapllm://new-chat";
+
+/// Claim the app's AppUserModelID, so the taskbar button, jump list and
+/// toasts share it, and fill the jump list. Runs during setup, before the
+/// main window exists.
+pub fn setup(app: &AppHandle) {
+    if let Err(e) = sys_windows_shell::set_app_id(&app.config().identifier) {
+        log::warn!("Failed to set AppUserModelID: {}", e);
+    }
+    refresh_jump_list(app);
+}
+
+/// Rebuild the jump list from the most recently updated chats.
+pub fn refresh_jump_list(app: &AppHandle) {
+    let app_id = app.config().identifier.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let recent = match recent_chat_links() {
+            Ok(links) => links,
+            Err(e) => {
+                log::warn!("Jump list without recent chats: {}", e);
+                Vec::new()
+            }
+        };
+        let list = JumpList {
+            tasks: vec![
+                JumpListLink::new("Capture region", CAPTURE_LINK),
+                JumpListLink::new("New chat", NEW_CHAT_LINK),
+            ],
+            category: "Recent chats".to_string(),
+            category_links: recent,
+        };
+        if let Err(e) = sys_windows_shell::set_jump_list(&app_id, &list) {
+            log::warn!("{}", e);
+        }
+    });
+}
+
+/// Refresh the jump list after a capture and raise a toast if the main
+/// window is not in front shortly after.
+pub fn notify_capture_complete(app: &AppHandle, chat_id: &str) {
+    refresh_jump_list(app);
+
+    let handle = app.clone();
+    let open_chat = format!("snapllm://open-chat/{}", chat_id);
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FOCUS_SETTLE).await;
+        let focused = handle
+            .get_webview_window("main")
+            .and_then(|window| window.is_focused().ok())
+            .unwrap_or(false);
+        if focused {
+            return;
+        }
+
+        let app_id = handle.config().identifier.clone();
+        let toast = Toast {
+            title: "Capture ready".to_string(),
+            body: "Your screenshot is waiting in a new chat.".to_string(),
+            launch_uri: Some(open_chat.clone()),
+            actions: vec![
+                ToastAction::new("Open chat", open_chat),
+                ToastAction::new("Capture again", CAPTURE_LINK),
+            ],
+        };
+        let _ = tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = sys_windows_shell::show_toast(&app_id, &toast) {
+                log::warn!("{}", e);
+            }
+        })
+        .await;
+    });
+}
+
+fn recent_chat_links() -> Result<Vec<JumpListLink>, String> {
+    let storage = ops_squigit_brain::context::media::get_active_storage()?;
+    let mut chats = storage.list_chats().map_err(|e| e.to_string())?;
+    chats.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    Ok(chats
+        .into_iter()
+        .take(RECENT_CHATS)
+        .map(|chat| {
+            let title = if chat.title.trim().is_empty() {
+                "Untitled chat".to_string()
+            } else {
+                chat.title
+            };
+            JumpListLink::new(title, format!("snapllm://open-chat/{}", chat.id))
+        })
+        .collect())
+}
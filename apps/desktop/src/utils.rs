@@ -13,16 +13,59 @@ where
         .any(|arg| matches!(arg.as_ref(), "--background" | "-b"))
 }
 
+/// The first argument that is neither a flag nor a deep link: an image
+/// path to open.
+pub fn cli_image_arg<I, S>(args: I) -> Option<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter()
+        .map(|arg| arg.as_ref().to_string())
+        .find(|arg| !arg.starts_with('-') && !crate::services::deep_link::is_deep_link(arg))
+}
+
 pub fn launched_in_background() -> bool {
     args_request_background(std::env::args().skip(1))
 }
 
+pub fn launched_from_autostart() -> bool {
+    std::env::args()
+        .skip(1)
+        .any(|arg| arg == crate::services::autostart::AUTOSTART_ARG)
+}
+
 pub fn get_app_config_dir(app: &AppHandle) -> PathBuf {
     app.path()
         .app_config_dir()
         .expect("Could not resolve app config dir")
 }
 
+/// The parsed preferences file; `None` when it is missing or not JSON.
+pub fn read_preferences(app: &AppHandle) -> Option<serde_json::Value> {
+    let prefs_file = get_app_config_dir(app).join(crate::constants::PREFERENCES_FILE_NAME);
+    let content = std::fs::read_to_string(prefs_file).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Set one top-level preference, keeping the rest of the file.
+pub fn write_preference(
+    app: &AppHandle,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    let config_dir = get_app_config_dir(app);
+    let prefs_file = config_dir.join(crate::constants::PREFERENCES_FILE_NAME);
+    let mut prefs = read_preferences(app)
+        .filter(serde_json::Value::is_object)
+        .unwrap_or_else(|| serde_json::json!({}));
+    prefs[key] = value;
+
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&prefs).map_err(|e| e.to_string())?;
+    std::fs::write(prefs_file, content).map_err(|e| e.to_string())
+}
+
 pub fn open_url(url: &str) -> Result<(), String> {
     #[cfg(target_os = "linux")]
     {
@@ -22,6 +22,48 @@ export interface ChatMetadata {
   pinned_at: string | null;
   ocr_lang?: string;
   image_tone?: string | null;
+  /** Physical pixels per logical pixel of the capture's display. */
+  device_pixel_ratio?: number;
+  /** The chat this one was forked from. */
+  parent_chat_id?: string | null;
+  /** Further stored objects attached to the chat, in the order added. */
+  attachments?: ChatAttachment[];
+  tags?: string[];
+  /** `/`-separated folder path, or null when the chat is in no folder. */
+  folder?: string | null;
+}
+
+/** Criteria for `listChats` (matches Rust ChatFilter). Omitted fields match everything. */
+export interface ChatFilter {
+  /** Case-insensitive tag. */
+  tag?: string;
+  /** Folder path; an empty string matches chats in no folder. */
+  folder?: string;
+  pinned?: boolean;
+  starred?: boolean;
+  /** Creation time window, `[from, to)`, as ISO timestamps. */
+  created?: { from?: string | null; to?: string | null };
+}
+
+/** A tag in use and how many chats carry it. */
+export interface TagCount {
+  tag: string;
+  count: number;
+}
+
+/** A chat whose image looks like another image (matches Rust SimilarCapture). */
+export interface SimilarCapture {
+  chat: ChatMetadata;
+  /** Differing perceptual-hash bits out of 64; 0 for the same image. */
+  distance: number;
+}
+
+/** A stored object attached to a chat (matches Rust ChatAttachment). */
+export interface ChatAttachment {
+  hash: string;
+  mime: string;
+  added_at: string;
+  label?: string | null;
 }
 
 /** A single chat message (matches Rust ChatMessage). */
@@ -45,17 +87,36 @@ export interface ChatToolStep {
 
 /** A single chat message (matches Rust ChatMessage). */
 export interface ChatMessage {
+  /** Stable within the chat; assigned by storage when missing. */
+  id?: string;
   role: "user" | "assistant";
   content: string;
   timestamp: string;
   citations?: ChatCitation[];
   tool_steps?: ChatToolStep[];
+  model?: string | null;
+  revisions?: MessageRevision[];
+}
+
+/** A replaced version of a message (matches Rust MessageRevision). */
+export interface MessageRevision {
+  content: string;
+  timestamp: string;
+  model?: string | null;
+  replaced_at: string;
+  /** Messages dropped when the edit regenerated from this point. */
+  following?: ChatMessage[];
 }
 
+/** Kind of personal data detected in an OCR region. */
+export type PiiKind = "email" | "phone" | "credential" | "address";
+
 /** OCR data for an image region (matches Rust OcrRegion). */
 export interface OcrRegion {
   text: string;
   bbox: number[][];
+  /** Personal data found in `text`, labeled when the region is saved. */
+  pii?: PiiKind[];
 }
 
 /** OCR frame: keyed by model_id, each value is cached results or null (not scanned). */
@@ -96,6 +157,12 @@ export interface StoredImage {
   path: string;
 }
 
+/** Files encrypted by `encryptChatStorage` (matches Rust EncryptionReport). */
+export interface EncryptionReport {
+  chatFiles: number;
+  objects: number;
+}
+
 /** Result from clipboard image read. */
 export interface ImageResult {
   hash: string;
@@ -134,14 +201,70 @@ export async function createChat(
   return getStoragePort().createChat(title, imageHash, ocrLang);
 }
 
+/**
+ * Start a new chat about the same image from the messages up to and
+ * including `fromMessageIndex`.
+ */
+export async function forkChat(
+  chatId: string,
+  fromMessageIndex: number,
+): Promise<ChatMetadata> {
+  return getStoragePort().forkChat(chatId, fromMessageIndex);
+}
+
+/**
+ * Fold the `sourceIds` chats into `targetId`: messages are interleaved by
+ * time, OCR and attachments unioned, and the sources moved to the trash.
+ */
+export async function mergeChats(
+  targetId: string,
+  sourceIds: string[],
+): Promise<ChatMetadata> {
+  return getStoragePort().mergeChats(targetId, sourceIds);
+}
+
 /** Load a chat by ID (full data including messages). */
 export async function loadChat(chatId: string): Promise<ChatData> {
   return getStoragePort().loadChat(chatId);
 }
 
-/** List all chats (metadata only). */
-export async function listChats(): Promise<ChatMetadata[]> {
-  return getStoragePort().listChats();
+/** List chats (metadata only), optionally narrowed by `filter`. */
+export async function listChats(
+  filter?: ChatFilter,
+): Promise<ChatMetadata[]> {
+  return getStoragePort().listChats(filter);
+}
+
+/** Replace a chat's tags. */
+export async function setChatTags(
+  chatId: string,
+  tags: string[],
+): Promise<ChatMetadata> {
+  return getStoragePort().setChatTags(chatId, tags);
+}
+
+/** Every tag in use with its chat count, alphabetically. */
+export async function listTags(): Promise<TagCount[]> {
+  return getStoragePort().listTags();
+}
+
+/** File a chat under a folder path; `null` takes it out of any folder. */
+export async function moveChatToFolder(
+  chatId: string,
+  folder: string | null,
+): Promise<ChatMetadata> {
+  return getStoragePort().moveChatToFolder(chatId, folder);
+}
+
+/**
+ * Chats whose image looks like the image with `hash`, closest first.
+ * `threshold` is how many of 64 hash bits may differ (8 by default).
+ */
+export async function findSimilarCaptures(
+  hash: string,
+  threshold?: number,
+): Promise<SimilarCapture[]> {
+  return getStoragePort().findSimilarCaptures(hash, threshold);
 }
 
 /** Search local chats with ranking, fuzzy matching, and regex filtering. */
@@ -164,6 +287,36 @@ export async function updateChatMetadata(
   return getStoragePort().updateChatMetadata(metadata);
 }
 
+/**
+ * OCR one chat in another language, leaving the global setting alone. The
+ * model is downloaded if needed and the chat re-OCR'd in the background;
+ * listen for `chat-ocr-updated` to pick up the result.
+ */
+export async function setChatOcrLanguage(
+  chatId: string,
+  lang: string,
+): Promise<ChatMetadata> {
+  return getStoragePort().setChatOcrLanguage(chatId, lang);
+}
+
+/** Attach a stored object to a chat, after the ones it already has. */
+export async function addChatAttachment(
+  chatId: string,
+  hash: string,
+  mime?: string | null,
+  label?: string | null,
+): Promise<ChatMetadata> {
+  return getStoragePort().addChatAttachment(chatId, hash, mime, label);
+}
+
+/** Detach a stored object from a chat. */
+export async function removeChatAttachment(
+  chatId: string,
+  hash: string,
+): Promise<ChatMetadata> {
+  return getStoragePort().removeChatAttachment(chatId, hash);
+}
+
 /** Append a message to a chat. */
 export async function appendChatMessage(
   chatId: string,
@@ -181,6 +334,42 @@ export async function overwriteChatMessages(
   return getStoragePort().overwriteChatMessages(chatId, messages);
 }
 
+/**
+ * Replace a message's content, keeping the old version in its history.
+ * With `truncate` the messages after it are dropped, for regenerating.
+ */
+export async function editMessage(
+  chatId: string,
+  messageId: string,
+  content: string,
+  truncate: boolean,
+  model?: string | null,
+): Promise<ChatMessage> {
+  return getStoragePort().editMessage(
+    chatId,
+    messageId,
+    content,
+    truncate,
+    model,
+  );
+}
+
+/** Remove a single message. */
+export async function deleteMessage(
+  chatId: string,
+  messageId: string,
+): Promise<void> {
+  return getStoragePort().deleteMessage(chatId, messageId);
+}
+
+/** Earlier versions of a message, oldest first. */
+export async function getMessageHistory(
+  chatId: string,
+  messageId: string,
+): Promise<MessageRevision[]> {
+  return getStoragePort().getMessageHistory(chatId, messageId);
+}
+
 // =============================================================================
 // OCR Commands
 // =============================================================================
@@ -215,15 +404,23 @@ export async function initOcrFrame(
   return getStoragePort().initOcrFrame(chatId, modelIds);
 }
 
-/** Cancel the currently running OCR job. */
-export async function cancelOcrJob(): Promise<void> {
+/**
+ * Cancel the running OCR jobs, or only the one of `chatId`. Without a chat
+ * ID, the background OCR of new captures keeps running.
+ */
+export async function cancelOcrJob(chatId?: string | null): Promise<void> {
   try {
-    await getStoragePort().cancelOcrJob();
+    await getStoragePort().cancelOcrJob(chatId);
   } catch {
     // Ignore cancellation races (no active job, late teardown, etc.).
   }
 }
 
+/** Whether a new capture's background OCR is still running for the chat. */
+export async function isChatOcrPending(chatId: string): Promise<boolean> {
+  return getStoragePort().isChatOcrPending(chatId);
+}
+
 // =============================================================================
 // ImgBB Commands
 // =============================================================================
@@ -270,6 +467,19 @@ export async function saveImageBrief(
   return getStoragePort().saveImageBrief(chatId, brief);
 }
 
+/** Whether the active profile's chats are encrypted at rest. */
+export async function getChatEncryptionEnabled(): Promise<boolean> {
+  return getStoragePort().getChatEncryptionEnabled();
+}
+
+/**
+ * Encrypt the active profile's chats at rest, including existing ones.
+ * Safe to call again to finish an interrupted migration.
+ */
+export async function encryptChatStorage(): Promise<EncryptionReport> {
+  return getStoragePort().encryptChatStorage();
+}
+
 // =============================================================================
 // Helpers
 // =============================================================================
@@ -16,14 +16,21 @@ export {
 
 export type {
   ChatMetadata,
+  ChatAttachment,
+  ChatFilter,
+  TagCount,
+  SimilarCapture,
   ChatCitation,
   ChatToolStep,
   ChatMessage,
+  MessageRevision,
+  PiiKind,
   OcrRegion,
   OcrFrame,
   ChatData,
   ChatSearchResult,
   StoredImage,
+  EncryptionReport,
   ImageResult,
 } from "./chat-storage.ts";
 
@@ -33,23 +40,38 @@ export {
   storeImageFromPath,
   getImagePath,
   createChat,
+  forkChat,
+  mergeChats,
   loadChat,
   listChats,
+  setChatTags,
+  listTags,
+  moveChatToFolder,
   searchChats,
+  findSimilarCaptures,
   deleteChat,
   updateChatMetadata,
+  setChatOcrLanguage,
+  addChatAttachment,
+  removeChatAttachment,
   appendChatMessage,
   overwriteChatMessages,
+  editMessage,
+  deleteMessage,
+  getMessageHistory,
   saveOcrData,
   getOcrData,
   getOcrFrame,
   initOcrFrame,
   cancelOcrJob,
+  isChatOcrPending,
   saveImgbbUrl,
   getImgbbUrl,
   saveRollingSummary,
   saveImageTone,
   saveImageBrief,
+  getChatEncryptionEnabled,
+  encryptChatStorage,
   groupChatsByDate,
 } from "./chat-storage.ts";
 
@@ -6,12 +6,17 @@
 
 import type {
   ChatData,
+  ChatFilter,
   ChatMessage,
   ChatMetadata,
   ChatSearchResult,
+  EncryptionReport,
+  MessageRevision,
   OcrFrame,
   OcrRegion,
+  SimilarCapture,
   StoredImage,
+  TagCount,
 } from "../config/chat-storage";
 
 export interface StoragePort {
@@ -23,27 +28,62 @@ export interface StoragePort {
     imageHash: string,
     ocrLang?: string | null,
   ): Promise<ChatMetadata>;
+  forkChat(chatId: string, fromMessageIndex: number): Promise<ChatMetadata>;
+  mergeChats(targetId: string, sourceIds: string[]): Promise<ChatMetadata>;
   loadChat(chatId: string): Promise<ChatData>;
-  listChats(): Promise<ChatMetadata[]>;
+  listChats(filter?: ChatFilter): Promise<ChatMetadata[]>;
+  setChatTags(chatId: string, tags: string[]): Promise<ChatMetadata>;
+  listTags(): Promise<TagCount[]>;
+  moveChatToFolder(
+    chatId: string,
+    folder: string | null,
+  ): Promise<ChatMetadata>;
   searchChats(query: string, limit: number): Promise<ChatSearchResult[]>;
+  findSimilarCaptures(
+    hash: string,
+    threshold?: number,
+  ): Promise<SimilarCapture[]>;
   deleteChat(chatId: string): Promise<void>;
   updateChatMetadata(metadata: ChatMetadata): Promise<void>;
+  setChatOcrLanguage(chatId: string, lang: string): Promise<ChatMetadata>;
+  addChatAttachment(
+    chatId: string,
+    hash: string,
+    mime?: string | null,
+    label?: string | null,
+  ): Promise<ChatMetadata>;
+  removeChatAttachment(chatId: string, hash: string): Promise<ChatMetadata>;
   appendChatMessage(
     chatId: string,
     role: "user" | "assistant",
     content: string,
   ): Promise<void>;
   overwriteChatMessages(chatId: string, messages: ChatMessage[]): Promise<void>;
+  editMessage(
+    chatId: string,
+    messageId: string,
+    content: string,
+    truncate: boolean,
+    model?: string | null,
+  ): Promise<ChatMessage>;
+  deleteMessage(chatId: string, messageId: string): Promise<void>;
+  getMessageHistory(
+    chatId: string,
+    messageId: string,
+  ): Promise<MessageRevision[]>;
   saveOcrData(chatId: string, modelId: string, ocrData: OcrRegion[]): Promise<void>;
   getOcrData(chatId: string, modelId: string): Promise<OcrRegion[] | null>;
   getOcrFrame(chatId: string): Promise<OcrFrame>;
   initOcrFrame(chatId: string, modelIds: string[]): Promise<void>;
-  cancelOcrJob(): Promise<void>;
+  cancelOcrJob(chatId?: string | null): Promise<void>;
+  isChatOcrPending(chatId: string): Promise<boolean>;
   saveImgbbUrl(chatId: string, url: string): Promise<void>;
   getImgbbUrl(chatId: string): Promise<string | null>;
   saveRollingSummary(chatId: string, summary: string): Promise<void>;
   saveImageTone(chatId: string, tone: string): Promise<void>;
   saveImageBrief(chatId: string, brief: string): Promise<void>;
+  getChatEncryptionEnabled(): Promise<boolean>;
+  encryptChatStorage(): Promise<EncryptionReport>;
 }
 
 let storagePort: StoragePort | null = null;
@@ -0,0 +1,282 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Single-file chat exports.
+//!
+//! A chat is written from its [`ChatArchive`]: as JSON or a ZIP as storage
+//! writes it, or rendered as a Markdown file or an HTML page with the
+//! images inlined as data URLs, so every format stands on its own without
+//! the app's storage. JSON and ZIP exports can be imported back.
+
+use std::path::Path;
+
+use ops_chat_storage::{ocr_text, ChatArchive, ChatData, ChatStorage, OcrRegion, OcrTextLayout};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::{escape_html, file_stem};
+
+/// Longest suggested file name, in characters, before the extension.
+const MAX_FILE_NAME_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatExportFormat {
+    Markdown,
+    Json,
+    Html,
+    Zip,
+}
+
+impl ChatExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Html => "html",
+            Self::Zip => "zip",
+        }
+    }
+}
+
+/// Write a chat to `destination` as `format`, creating missing parent
+/// directories.
+pub fn export_chat(
+    storage: &ChatStorage,
+    chat_id: &str,
+    format: ChatExportFormat,
+    destination: &Path,
+) -> Result<()> {
+    let render = match format {
+        ChatExportFormat::Json => return Ok(storage.write_chat_archive(chat_id, destination)?),
+        ChatExportFormat::Zip => return Ok(storage.write_chat_zip(chat_id, destination)?),
+        ChatExportFormat::Markdown => render_markdown,
+        ChatExportFormat::Html => render_html,
+    };
+    let archive = storage.chat_archive(chat_id)?;
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(destination, render(&archive))?;
+    Ok(())
+}
+
+/// A file name for the chat's export: its title without characters that
+/// are invalid on some platform.
+pub fn suggested_file_name(chat: &ChatData, format: ChatExportFormat) -> String {
+    format!(
+        "{}.{}",
+        file_stem(&chat.metadata.title, "chat", MAX_FILE_NAME_CHARS),
+        format.extension()
+    )
+}
+
+/// The OCR scan shown in exports: the chat's OCR language, else the first
+/// model with text.
+fn export_ocr_regions(chat: &ChatData) -> Option<&[OcrRegion]> {
+    let preferred = chat
+        .metadata
+        .ocr_lang
+        .as_deref()
+        .and_then(|model_id| chat.ocr_data.get(model_id)?.as_deref())
+        .filter(|regions| !regions.is_empty());
+    preferred.or_else(|| {
+        let mut model_ids: Vec<_> = chat.ocr_data.keys().collect();
+        model_ids.sort();
+        model_ids
+            .into_iter()
+            .filter_map(|model_id| chat.ocr_data.get(model_id)?.as_deref())
+            .find(|regions| !regions.is_empty())
+    })
+}
+
+pub(crate) fn speaker(role: &str, model: Option<&str>) -> String {
+    match (role, model) {
+        ("user", _) => "You".to_string(),
+        (_, Some(model)) => format!("Assistant ({})", model),
+        _ => "Assistant".to_string(),
+    }
+}
+
+/// The chat as Markdown with its images as data URLs.
+pub fn render_markdown(archive: &ChatArchive) -> String {
+    let chat = &archive.chat;
+    let mut out = format!(
+        "# {}\n\n_Created {}_\n",
+        chat.metadata.title,
+        chat.metadata.created_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    let (captures, attachments): (Vec<_>, Vec<_>) = archive
+        .images
+        .iter()
+        .partition(|image| image.name.is_none());
+    for image in captures {
+        out.push_str(&format!("\n![Capture]({})\n", image.data_url()));
+    }
+    if let Some(regions) = export_ocr_regions(chat) {
+        out.push_str("\n## OCR text\n\n");
+        out.push_str(ocr_text(regions, OcrTextLayout::Markdown).trim_end());
+        out.push('\n');
+    }
+
+    if !chat.messages.is_empty() {
+        out.push_str("\n## Conversation\n");
+        for message in &chat.messages {
+            out.push_str(&format!(
+                "\n### {}\n\n{}\n",
+                speaker(&message.role, message.model.as_deref()),
+                message.content.trim_end()
+            ));
+        }
+    }
+
+    if !attachments.is_empty() {
+        out.push_str("\n## Attachments\n");
+        for image in attachments {
+            let name = image.name.as_deref().unwrap_or_default();
+            out.push_str(&format!("\n![{}]({})\n", name, image.data_url()));
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:52rem;margin:2rem auto;padding:0 1rem;line-height:1.5;color:#1f1f1f}\
+img{max-width:100%;border-radius:8px}\
+.meta{color:#666}\
+.message{margin:1rem 0;padding:.75rem 1rem;border-radius:8px;background:#f4f4f4}\
+.message.user{background:#e8f0fe}\
+.speaker{font-weight:600;margin-bottom:.25rem}\
+.content,.ocr{white-space:pre-wrap;overflow-wrap:anywhere}";
+
+/// The chat as one HTML page with its images inlined. Message text is
+/// shown as written, Markdown unrendered.
+pub fn render_html(archive: &ChatArchive) -> String {
+    let chat = &archive.chat;
+    let title = escape_html(&chat.metadata.title);
+    let mut body = format!(
+        "<h1>{}</h1>\n<p class=\"meta\">Created {}</p>\n",
+        title,
+        chat.metadata.created_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    let (captures, attachments): (Vec<_>, Vec<_>) = archive
+        .images
+        .iter()
+        .partition(|image| image.name.is_none());
+    for image in captures {
+        body.push_str(&format!(
+            "<img src=\"{}\" alt=\"Capture\">\n",
+            image.data_url()
+        ));
+    }
+    if let Some(regions) = export_ocr_regions(chat) {
+        body.push_str(&format!(
+            "<h2>OCR text</h2>\n<div class=\"ocr\">{}</div>\n",
+            escape_html(ocr_text(regions, OcrTextLayout::Lines).trim_end())
+        ));
+    }
+
+    if !chat.messages.is_empty() {
+        body.push_str("<h2>Conversation</h2>\n");
+        for message in &chat.messages {
+            let class = if message.role == "user" {
+                "user"
+            } else {
+                "assistant"
+            };
+            body.push_str(&format!(
+                "<div class=\"message {}\"><div class=\"speaker\">{}</div><div class=\"content\">{}</div></div>\n",
+                class,
+                escape_html(&speaker(&message.role, message.model.as_deref())),
+                escape_html(message.content.trim_end())
+            ));
+        }
+    }
+
+    if !attachments.is_empty() {
+        body.push_str("<h2>Attachments</h2>\n");
+        for image in attachments {
+            let name = escape_html(image.name.as_deref().unwrap_or_default());
+            body.push_str(&format!(
+                "<figure><img src=\"{}\" alt=\"{}\"><figcaption>{}</figcaption></figure>\n",
+                image.data_url(),
+                name,
+                name
+            ));
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        title, HTML_STYLE, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ops_chat_storage::{ChatMessage, ChatMetadata};
+    use tempfile::tempdir;
+
+    #[test]
+    fn exports_are_self_contained() {
+        let dir = tempdir().unwrap();
+        let storage = ChatStorage::with_base_dir(dir.path().join("storage")).unwrap();
+
+        let image = storage.store_image(b"capture", None).unwrap();
+        let metadata = ChatMetadata::new(
+            "Error: <build>".to_string(),
+            image.hash.clone(),
+            Some("pp-ocr-v5-en".to_string()),
+        );
+        let mut chat = ChatData::new(metadata.clone());
+        chat.messages = vec![
+            ChatMessage::user("What does this mean?".to_string()),
+            ChatMessage::assistant("The <build> step failed.".to_string()).with_model("gemini"),
+        ];
+        storage.save_chat(&chat).unwrap();
+        let region = OcrRegion {
+            text: "cargo build".to_string(),
+            bbox: vec![vec![0, 0], vec![10, 0], vec![10, 5], vec![0, 5]],
+            confidence: None,
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        };
+        storage
+            .save_ocr_data(&metadata.id, "pp-ocr-v5-en", &[region])
+            .unwrap();
+
+        let out_dir = dir.path().join("out");
+        let markdown_path = out_dir.join("chat.md");
+        export_chat(
+            &storage,
+            &metadata.id,
+            ChatExportFormat::Markdown,
+            &markdown_path,
+        )
+        .unwrap();
+        let markdown = std::fs::read_to_string(&markdown_path).unwrap();
+        assert!(markdown.starts_with("# Error: <build>\n"));
+        assert!(markdown.contains("![Capture](data:image/png;base64,"));
+        assert!(markdown.contains("## OCR text\n\ncargo build\n"));
+        assert!(markdown.contains("### Assistant (gemini)\n\nThe <build> step failed.\n"));
+
+        let html_path = out_dir.join("chat.html");
+        export_chat(&storage, &metadata.id, ChatExportFormat::Html, &html_path).unwrap();
+        let html = std::fs::read_to_string(&html_path).unwrap();
+        assert!(html.contains("<title>Error: &lt;build&gt;</title>"));
+        assert!(html.contains("The &lt;build&gt; step failed."));
+        assert!(html.contains("<img src=\"data:image/png;base64,"));
+
+        let json_path = out_dir.join("chat.json");
+        export_chat(&storage, &metadata.id, ChatExportFormat::Json, &json_path).unwrap();
+        assert!(storage.import_chat(&json_path).is_ok());
+
+        assert_eq!(
+            suggested_file_name(&chat, ChatExportFormat::Markdown),
+            "Error build.md"
+        );
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    /// The destination is missing or not usable, e.g. no vault at the path.
+    #[error("Invalid export target: {0}")]
+    InvalidTarget(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] ops_chat_storage::StorageError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ExportError>;
@@ -0,0 +1,605 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Static HTML galleries of chats.
+//!
+//! [`export_gallery`] writes the chats matching a filter into
+//! a folder that can be published as is or kept as an archive: an
+//! `index.html` listing them by their captures, a page per chat under
+//! `chats/` with the conversation rendered from Markdown and fenced code
+//! highlighted, and the images as plain files under `images/`, each written
+//! once however many chats show it. The pages are read-only: raw HTML in
+//! messages is shown as text and there are no scripts. OCR text is left
+//! out, as it may hold more of the screen than the conversation does.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use base64::Engine;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+use ops_chat_storage::export::image_extension;
+use ops_chat_storage::{ChatArchive, ChatFilter, ChatMetadata, ChatStorage};
+
+use crate::chat_file::speaker;
+use crate::error::Result;
+use crate::escape_html;
+
+const INDEX_FILE: &str = "index.html";
+const STYLE_FILE: &str = "style.css";
+const CHATS_DIR: &str = "chats";
+const IMAGES_DIR: &str = "images";
+
+const GALLERY_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5;color:#1f1f1f}
+a{color:#1a56c4}
+img{max-width:100%;border-radius:8px}
+.meta{color:#666}
+.cards{list-style:none;padding:0;display:grid;grid-template-columns:repeat(auto-fill,minmax(16rem,1fr));gap:1rem}
+.cards a{display:block;text-decoration:none;color:inherit;border:1px solid #ddd;border-radius:10px;overflow:hidden}
+.cards img{display:block;width:100%;height:10rem;object-fit:cover;border-radius:0;background:#f4f4f4}
+.cards .title{display:block;font-weight:600;padding:.5rem .75rem 0}
+.cards .meta{display:block;padding:0 .75rem .5rem;font-size:.875rem}
+.message{margin:1rem 0;padding:.75rem 1rem;border-radius:8px;background:#f4f4f4;overflow-wrap:anywhere}
+.message.user{background:#e8f0fe}
+.speaker{font-weight:600;margin-bottom:.25rem}
+pre{background:#1e1e2e;color:#e0e0e0;padding:.75rem 1rem;border-radius:6px;overflow-x:auto}
+code{font-family:ui-monospace,monospace;font-size:.9em}
+.tok-keyword{color:#c792ea}
+.tok-string{color:#c3e88d}
+.tok-number{color:#f78c6c}
+.tok-comment{color:#7f848e;font-style:italic}
+table{border-collapse:collapse}
+th,td{border:1px solid #ccc;padding:.25rem .5rem}
+";
+
+/// What [`export_gallery`] wrote.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GalleryReport {
+    /// Chat pages written.
+    pub chats: usize,
+    /// Distinct image files written.
+    pub images: usize,
+    /// The gallery's `index.html`.
+    pub index_path: String,
+}
+
+/// Write the chats matching `filter` as a static site into `dest_dir`,
+/// creating it if missing. Pages from an earlier export into the same
+/// folder are overwritten.
+pub fn export_gallery(
+    storage: &ChatStorage,
+    dest_dir: &Path,
+    filter: &ChatFilter,
+) -> Result<GalleryReport> {
+    let chats = storage.list_chats_filtered(filter)?;
+    fs::create_dir_all(dest_dir.join(CHATS_DIR))?;
+    fs::create_dir_all(dest_dir.join(IMAGES_DIR))?;
+    fs::write(dest_dir.join(STYLE_FILE), GALLERY_STYLE)?;
+
+    let mut written = HashSet::new();
+    let mut cards = Vec::new();
+    for metadata in &chats {
+        let archive = storage.chat_archive(&metadata.id)?;
+        let mut files = Vec::with_capacity(archive.images.len());
+        for image in &archive.images {
+            let file = format!("{}.{}", image.hash, image_extension(&image.mime_type));
+            if written.insert(file.clone()) {
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(&image.data)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                fs::write(dest_dir.join(IMAGES_DIR).join(&file), bytes)?;
+            }
+            files.push(file);
+        }
+        let page = render_chat_page(&archive, &files);
+        fs::write(
+            dest_dir
+                .join(CHATS_DIR)
+                .join(format!("{}.html", metadata.id)),
+            page,
+        )?;
+        // The capture comes first when the chat still has it.
+        let thumbnail = archive
+            .images
+            .first()
+            .filter(|image| image.name.is_none())
+            .and(files.first().cloned());
+        cards.push((metadata, thumbnail));
+    }
+
+    let index_path = dest_dir.join(INDEX_FILE);
+    fs::write(&index_path, render_index(&cards))?;
+    Ok(GalleryReport {
+        chats: chats.len(),
+        images: written.len(),
+        index_path: index_path.to_string_lossy().into_owned(),
+    })
+}
+
+fn render_index(cards: &[(&ChatMetadata, Option<String>)]) -> String {
+    let mut body = format!(
+        "<h1>Gallery</h1>\n<p class=\"meta\">{} chat{}</p>\n<ul class=\"cards\">\n",
+        cards.len(),
+        if cards.len() == 1 { "" } else { "s" }
+    );
+    for (metadata, thumbnail) in cards {
+        let image = match thumbnail {
+            Some(file) => format!("<img src=\"{}/{}\" alt=\"\">", IMAGES_DIR, file),
+            None => String::new(),
+        };
+        body.push_str(&format!(
+            "<li><a href=\"{}/{}.html\">{}<span class=\"title\">{}</span><span class=\"meta\">{}</span></a></li>\n",
+            CHATS_DIR,
+            metadata.id,
+            image,
+            escape_html(&metadata.title),
+            page_meta(metadata)
+        ));
+    }
+    body.push_str("</ul>\n");
+    page("Gallery", STYLE_FILE, &body)
+}
+
+/// A chat's page; `files` are the image file names in `archive.images`
+/// order.
+fn render_chat_page(archive: &ChatArchive, files: &[String]) -> String {
+    let chat = &archive.chat;
+    let mut body = format!(
+        "<nav><a href=\"../{}\">All chats</a></nav>\n<h1>{}</h1>\n<p class=\"meta\">{}</p>\n",
+        INDEX_FILE,
+        escape_html(&chat.metadata.title),
+        page_meta(&chat.metadata)
+    );
+
+    let images: Vec<_> = archive.images.iter().zip(files).collect();
+    for (_, file) in images.iter().filter(|(image, _)| image.name.is_none()) {
+        body.push_str(&format!(
+            "<img src=\"../{}/{}\" alt=\"Capture\">\n",
+            IMAGES_DIR, file
+        ));
+    }
+
+    for message in &chat.messages {
+        let class = if message.role == "user" {
+            "user"
+        } else {
+            "assistant"
+        };
+        body.push_str(&format!(
+            "<div class=\"message {}\"><div class=\"speaker\">{}</div>\n{}</div>\n",
+            class,
+            escape_html(&speaker(&message.role, message.model.as_deref())),
+            render_message(&message.content)
+        ));
+    }
+
+    let attachments: Vec<_> = images
+        .iter()
+        .filter_map(|(image, file)| Some((image.name.as_deref()?, file)))
+        .collect();
+    if !attachments.is_empty() {
+        body.push_str("<h2>Attachments</h2>\n");
+        for (name, file) in attachments {
+            let name = escape_html(name);
+            body.push_str(&format!(
+                "<figure><img src=\"../{}/{}\" alt=\"{}\"><figcaption>{}</figcaption></figure>\n",
+                IMAGES_DIR, file, name, name
+            ));
+        }
+    }
+
+    page(&chat.metadata.title, &format!("../{}", STYLE_FILE), &body)
+}
+
+/// Creation date and tags.
+fn page_meta(metadata: &ChatMetadata) -> String {
+    let mut meta = metadata.created_at.format("%Y-%m-%d").to_string();
+    if !metadata.tags.is_empty() {
+        meta.push_str(" · ");
+        meta.push_str(&escape_html(&metadata.tags.join(", ")));
+    }
+    meta
+}
+
+fn page(title: &str, stylesheet: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"{}\">\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        stylesheet,
+        body
+    )
+}
+
+/// Message Markdown as HTML, with fenced code highlighted. Raw HTML is
+/// escaped and script links are dropped, so a message cannot run anything.
+fn render_message(markdown: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut events = Vec::new();
+    // Language and text of the code block being read.
+    let mut code: Option<(String, String)> = None;
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_lowercase(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code = Some((language, String::new()));
+            }
+            Event::Text(text) if code.is_some() => {
+                if let Some((_, buffer)) = &mut code {
+                    buffer.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((language, text)) = code.take() {
+                    let class = if language.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" class=\"language-{}\"", escape_html(&language))
+                    };
+                    events.push(Event::Html(CowStr::from(format!(
+                        "<pre><code{}>{}</code></pre>\n",
+                        class,
+                        highlight_code(&text, &language)
+                    ))));
+                }
+            }
+            Event::Html(html) | Event::InlineHtml(html) => events.push(Event::Text(html)),
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) if !is_safe_url(&dest_url) => events.push(Event::Start(Tag::Link {
+                link_type,
+                dest_url: CowStr::Borrowed("#"),
+                title,
+                id,
+            })),
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) if !is_safe_url(&dest_url) => events.push(Event::Start(Tag::Image {
+                link_type,
+                dest_url: CowStr::Borrowed(""),
+                title,
+                id,
+            })),
+            event => events.push(event),
+        }
+    }
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    html
+}
+
+fn is_safe_url(url: &str) -> bool {
+    let scheme = url.trim_start().to_ascii_lowercase();
+    !["javascript:", "vbscript:", "data:"]
+        .iter()
+        .any(|unsafe_scheme| scheme.starts_with(unsafe_scheme))
+}
+
+/// How [`highlight_code`] reads a language.
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+}
+
+const C_LIKE_KEYWORDS: &[&str] = &[
+    "auto",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "func",
+    "go",
+    "if",
+    "import",
+    "interface",
+    "let",
+    "namespace",
+    "new",
+    "null",
+    "nullptr",
+    "override",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "struct",
+    "super",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "using",
+    "var",
+    "virtual",
+    "void",
+    "while",
+];
+
+fn syntax_for(language: &str) -> Option<Syntax> {
+    let syntax = match language {
+        "rust" | "rs" => Syntax {
+            keywords: &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+                "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+                "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+            line_comments: &["//"],
+            block_comment: Some(("/*", "*/")),
+            // Single quotes also start lifetimes.
+            quotes: &['"'],
+        },
+        "python" | "py" => Syntax {
+            keywords: &[
+                "and", "as", "assert", "async", "await", "break", "class", "continue", "def",
+                "del", "elif", "else", "except", "False", "finally", "for", "from", "global", "if",
+                "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise",
+                "return", "True", "try", "while", "with", "yield",
+            ],
+            line_comments: &["#"],
+            block_comment: None,
+            quotes: &['"', '\''],
+        },
+        "javascript" | "js" | "jsx" | "typescript" | "ts" | "tsx" => Syntax {
+            keywords: &[
+                "async",
+                "await",
+                "break",
+                "case",
+                "catch",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "delete",
+                "do",
+                "else",
+                "export",
+                "extends",
+                "false",
+                "finally",
+                "for",
+                "from",
+                "function",
+                "if",
+                "import",
+                "in",
+                "instanceof",
+                "interface",
+                "let",
+                "new",
+                "null",
+                "of",
+                "return",
+                "super",
+                "switch",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "type",
+                "typeof",
+                "undefined",
+                "var",
+                "void",
+                "while",
+                "yield",
+            ],
+            line_comments: &["//"],
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '\'', '`'],
+        },
+        "c" | "h" | "cpp" | "c++" | "hpp" | "cs" | "csharp" | "java" | "go" | "kotlin" | "kt"
+        | "swift" => Syntax {
+            keywords: C_LIKE_KEYWORDS,
+            line_comments: &["//"],
+            block_comment: Some(("/*", "*/")),
+            quotes: &['"', '\''],
+        },
+        "sh" | "bash" | "zsh" | "shell" | "console" => Syntax {
+            keywords: &[
+                "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function",
+                "if", "in", "local", "return", "then", "until", "while",
+            ],
+            line_comments: &["#"],
+            block_comment: None,
+            quotes: &['"', '\''],
+        },
+        "sql" => Syntax {
+            keywords: &[
+                "and", "as", "by", "create", "delete", "from", "group", "having", "insert", "into",
+                "join", "left", "limit", "not", "null", "on", "or", "order", "select", "set",
+                "table", "update", "values", "where", "AND", "AS", "BY", "CREATE", "DELETE",
+                "FROM", "GROUP", "HAVING", "INSERT", "INTO", "JOIN", "LEFT", "LIMIT", "NOT",
+                "NULL", "ON", "OR", "ORDER", "SELECT", "SET", "TABLE", "UPDATE", "VALUES", "WHERE",
+            ],
+            line_comments: &["--"],
+            block_comment: Some(("/*", "*/")),
+            quotes: &['\'', '"'],
+        },
+        "json" => Syntax {
+            keywords: &["true", "false", "null"],
+            line_comments: &[],
+            block_comment: None,
+            quotes: &['"'],
+        },
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+/// Escaped `code` with keywords, strings, numbers and comments wrapped in
+/// `tok-*` spans. Languages without a [`Syntax`] are only escaped.
+fn highlight_code(code: &str, language: &str) -> String {
+    let Some(syntax) = syntax_for(language) else {
+        return escape_html(code);
+    };
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+    while let Some(c) = rest.chars().next() {
+        let (class, len) = if syntax
+            .line_comments
+            .iter()
+            .any(|start| rest.starts_with(start))
+        {
+            (Some("comment"), rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some((open, close)) = syntax
+            .block_comment
+            .filter(|(open, _)| rest.starts_with(open))
+        {
+            let len = rest[open.len()..]
+                .find(close)
+                .map_or(rest.len(), |end| open.len() + end + close.len());
+            (Some("comment"), len)
+        } else if syntax.quotes.contains(&c) {
+            (Some("string"), quoted_len(rest, c))
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+                .unwrap_or(rest.len());
+            (Some("number"), len)
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let keyword = syntax.keywords.contains(&&rest[..len]);
+            (keyword.then_some("keyword"), len)
+        } else {
+            (None, c.len_utf8())
+        };
+
+        let (token, tail) = rest.split_at(len);
+        match class {
+            Some(class) => out.push_str(&format!(
+                "<span class=\"tok-{}\">{}</span>",
+                class,
+                escape_html(token)
+            )),
+            None => out.push_str(&escape_html(token)),
+        }
+        rest = tail;
+    }
+    out
+}
+
+/// Length of the string literal at the start of `text`, up to its closing
+/// `quote` or, for an unterminated one, the end of the line.
+fn quoted_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return index + c.len_utf8();
+        } else if c == '\n' && quote != '`' {
+            return index;
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ops_chat_storage::{ChatData, ChatMessage};
+    use tempfile::tempdir;
+
+    #[test]
+    fn gallery_has_an_index_and_a_page_per_chat() {
+        let base_dir = tempdir().unwrap();
+        let storage = ChatStorage::with_base_dir(base_dir.path().join("storage")).unwrap();
+        let image = storage.store_image(b"capture", None).unwrap();
+
+        let mut starred =
+            ChatMetadata::new("Fix <the> build".to_string(), image.hash.clone(), None);
+        starred.is_starred = true;
+        let mut chat = ChatData::new(starred.clone());
+        chat.messages = vec![
+            ChatMessage::user("Why <script>alert(1)</script>?".to_string()),
+            ChatMessage::assistant(
+                "Use this:\n\n```rust\nlet n = 42; // answer\n```\n\n[docs](javascript:alert(1))"
+                    .to_string(),
+            ),
+        ];
+        storage.save_chat(&chat).unwrap();
+        let other = ChatMetadata::new("Other".to_string(), image.hash.clone(), None);
+        storage.save_chat(&ChatData::new(other.clone())).unwrap();
+
+        let dest = base_dir.path().join("gallery");
+        let filter = ChatFilter {
+            starred: Some(true),
+            ..ChatFilter::default()
+        };
+        let report = export_gallery(&storage, &dest, &filter).unwrap();
+        assert_eq!(report.chats, 1);
+        assert_eq!(report.images, 1);
+
+        let index = fs::read_to_string(dest.join(INDEX_FILE)).unwrap();
+        assert!(index.contains(&format!("chats/{}.html", starred.id)));
+        assert!(index.contains("Fix &lt;the&gt; build"));
+        assert!(!index.contains(&other.id));
+        assert!(dest
+            .join(IMAGES_DIR)
+            .join(format!("{}.png", image.hash))
+            .is_file());
+
+        let page =
+            fs::read_to_string(dest.join(CHATS_DIR).join(format!("{}.html", starred.id))).unwrap();
+        assert!(page.contains(&format!("src=\"../images/{}.png\"", image.hash)));
+        assert!(page.contains("&lt;script&gt;"));
+        assert!(!page.contains("<script>"));
+        assert!(!page.contains("javascript:"));
+        assert!(page.contains("<span class=\"tok-keyword\">let</span>"));
+        assert!(page.contains("<span class=\"tok-comment\">// answer</span>"));
+    }
+
+    #[test]
+    fn code_is_highlighted_by_language() {
+        assert_eq!(
+            highlight_code("x = \"a\\\"b\" # hi", "python"),
+            "x = <span class=\"tok-string\">&quot;a\\&quot;b&quot;</span> <span class=\"tok-comment\"># hi</span>"
+        );
+        assert_eq!(
+            highlight_code("fn f<'a>() {}", "rust"),
+            "<span class=\"tok-keyword\">fn</span> f&lt;&#39;a&gt;() {}"
+        );
+        assert_eq!(highlight_code("if <b>", "text"), "if &lt;b&gt;");
+    }
+}
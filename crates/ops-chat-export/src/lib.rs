@@ -0,0 +1,98 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chat exports.
+//!
+//! [`export_chat`] writes a chat as one Markdown, JSON, HTML or ZIP file,
+//! and [`export_gallery`] writes chats as a static HTML site. A connector
+//! writes a stored chat somewhere outside the app, as a note
+//! built from a [`template`]. [`ObsidianVault`] is the first one: it writes
+//! Markdown notes with front matter into a vault folder and copies the chat
+//! image next to them. [`SearchIndexStubs`] writes small stubs the system
+//! search (Spotlight, Windows Search) indexes.
+//!
+//! Usage:
+//! ```ignore
+//! let vault = ObsidianVault::new("/home/me/Notes");
+//! let note_path = vault.export(&ExportSource { chat: &chat, image_path: Some(&image) })?;
+//! ```
+
+pub mod chat_file;
+pub mod error;
+pub mod gallery;
+pub mod obsidian;
+pub mod search_index;
+pub mod template;
+
+use std::path::{Path, PathBuf};
+
+use ops_chat_storage::ChatData;
+
+pub use chat_file::{
+    export_chat, render_html, render_markdown, suggested_file_name, ChatExportFormat,
+};
+pub use error::{ExportError, Result};
+pub use gallery::{export_gallery, GalleryReport};
+pub use obsidian::ObsidianVault;
+pub use search_index::SearchIndexStubs;
+pub use template::DEFAULT_NOTE_TEMPLATE;
+
+/// A chat to export.
+pub struct ExportSource<'a> {
+    pub chat: &'a ChatData,
+    /// The chat image in storage, if it is still there.
+    pub image_path: Option<&'a Path>,
+}
+
+pub trait ExportConnector {
+    /// Stable identifier, matching the connector's settings key.
+    fn id(&self) -> &'static str;
+
+    /// Write the chat and return the path of the created or updated note.
+    /// Exporting the same chat again updates its note.
+    fn export(&self, source: &ExportSource<'_>) -> Result<PathBuf>;
+}
+
+/// `title` as a file name without extension: characters that are invalid
+/// on some platform or special to note apps become spaces, and leading dots
+/// are dropped. Falls back to `fallback` when nothing is left.
+pub(crate) fn file_stem(title: &str, fallback: &str, max_chars: usize) -> String {
+    let stem = title
+        .chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let stem = stem
+        .trim_start_matches('.')
+        .chars()
+        .take(max_chars)
+        .collect::<String>()
+        .trim_end()
+        .to_string();
+    if stem.is_empty() {
+        fallback.to_string()
+    } else {
+        stem
+    }
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,274 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Obsidian vault connector.
+//!
+//! Notes go to `<vault>/<folder>/<title>.md` with the chat image copied to
+//! `<folder>/attachments/` and embedded as `![[…]]`. Front matter records
+//! the chat ID, so exporting a chat again rewrites its note instead of
+//! adding another, and notes of different chats with the same title get a
+//! numbered name.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::error::{ExportError, Result};
+use crate::template::{chat_fields, render, DEFAULT_NOTE_TEMPLATE};
+use crate::{file_stem, ExportConnector, ExportSource};
+
+/// Folder inside the vault used when none is configured.
+pub const DEFAULT_FOLDER: &str = "Squigit";
+/// Tag every exported note carries.
+pub const DEFAULT_TAG: &str = "squigit";
+
+const ATTACHMENTS_FOLDER: &str = "attachments";
+/// Front-matter key holding the chat ID.
+const CHAT_ID_KEY: &str = "squigit-chat";
+/// Longest note file name, in characters, before `.md`.
+const MAX_NOTE_NAME_CHARS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct ObsidianVault {
+    pub vault_path: PathBuf,
+    /// Folder inside the vault, relative to it.
+    pub folder: String,
+    pub template: String,
+    /// Front-matter tags besides [`DEFAULT_TAG`].
+    pub tags: Vec<String>,
+}
+
+impl ObsidianVault {
+    pub fn new(vault_path: impl Into<PathBuf>) -> Self {
+        Self {
+            vault_path: vault_path.into(),
+            folder: DEFAULT_FOLDER.to_string(),
+            template: DEFAULT_NOTE_TEMPLATE.to_string(),
+            tags: Vec::new(),
+        }
+    }
+
+    fn notes_dir(&self) -> Result<PathBuf> {
+        if !self.vault_path.is_dir() {
+            return Err(ExportError::InvalidTarget(format!(
+                "vault not found: {}",
+                self.vault_path.display()
+            )));
+        }
+        let folder = Path::new(&self.folder);
+        if !folder
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(ExportError::InvalidTarget(format!(
+                "folder must stay inside the vault: {:?}",
+                self.folder
+            )));
+        }
+        Ok(self.vault_path.join(folder))
+    }
+
+    fn front_matter(&self, source: &ExportSource<'_>) -> String {
+        let metadata = &source.chat.metadata;
+        let mut front = format!(
+            "---\ntitle: {}\ncreated: {}\n{}: {}\n",
+            yaml_string(&metadata.title),
+            metadata
+                .created_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            CHAT_ID_KEY,
+            yaml_string(&metadata.id)
+        );
+        if let Some(web_source) = &source.chat.web_source {
+            front.push_str(&format!("source: {}\n", yaml_string(&web_source.url)));
+        }
+
+        let mut tags: Vec<String> = Vec::new();
+        for tag in std::iter::once(DEFAULT_TAG).chain(self.tags.iter().map(String::as_str)) {
+            let tag = tag_name(tag);
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        front.push_str("tags:\n");
+        for tag in tags {
+            front.push_str(&format!("  - {}\n", tag));
+        }
+        front.push_str("---\n\n");
+        front
+    }
+}
+
+impl ExportConnector for ObsidianVault {
+    fn id(&self) -> &'static str {
+        "obsidian"
+    }
+
+    fn export(&self, source: &ExportSource<'_>) -> Result<PathBuf> {
+        let notes_dir = self.notes_dir()?;
+        std::fs::create_dir_all(&notes_dir)?;
+        let metadata = &source.chat.metadata;
+
+        let image = match source.image_path.filter(|path| path.is_file()) {
+            Some(image_path) => {
+                let extension = image_path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("png");
+                let hash_prefix: String = metadata.image_hash.chars().take(16).collect();
+                let name = format!("squigit-{}.{}", hash_prefix, extension);
+                let attachments_dir = notes_dir.join(ATTACHMENTS_FOLDER);
+                let target = attachments_dir.join(&name);
+                if !target.exists() {
+                    std::fs::create_dir_all(&attachments_dir)?;
+                    std::fs::copy(image_path, &target)?;
+                }
+                format!("![[{}]]", name)
+            }
+            None => String::new(),
+        };
+
+        let body = render(&self.template, &chat_fields(source.chat, &image));
+        let note_path = note_path(&notes_dir, &metadata.title, &metadata.id);
+        std::fs::write(&note_path, format!("{}{}", self.front_matter(source), body))?;
+        Ok(note_path)
+    }
+}
+
+/// The chat's existing note, or a free file name based on its title.
+fn note_path(notes_dir: &Path, title: &str, chat_id: &str) -> PathBuf {
+    let base = file_stem(title, chat_id, MAX_NOTE_NAME_CHARS);
+
+    let marker = format!("{}: {}", CHAT_ID_KEY, yaml_string(chat_id));
+    for n in 1.. {
+        let name = if n == 1 {
+            format!("{}.md", base)
+        } else {
+            format!("{} ({}).md", base, n)
+        };
+        let path = notes_dir.join(name);
+        let is_ours = || {
+            std::fs::read_to_string(&path)
+                .is_ok_and(|content| content.lines().any(|line| line == marker))
+        };
+        if !path.exists() || is_ours() {
+            return path;
+        }
+    }
+    unreachable!("note names are unbounded")
+}
+
+/// A double-quoted YAML scalar on one line.
+fn yaml_string(value: &str) -> String {
+    let escaped: String = value
+        .chars()
+        .flat_map(|c| match c {
+            '"' => vec!['\\', '"'],
+            '\\' => vec!['\\', '\\'],
+            c if c.is_control() => vec![' '],
+            c => vec![c],
+        })
+        .collect();
+    format!("\"{}\"", escaped)
+}
+
+/// Obsidian tags may hold letters, digits, `-`, `_` and `/`.
+fn tag_name(tag: &str) -> String {
+    tag.trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ops_chat_storage::{ChatData, ChatMessage, ChatMetadata, WebSource};
+    use tempfile::tempdir;
+
+    fn chat(title: &str) -> ChatData {
+        let mut chat = ChatData::new(ChatMetadata::new(title.to_string(), "ab".repeat(32), None));
+        chat.messages.push(ChatMessage::assistant(
+            "A receipt from the café.".to_string(),
+        ));
+        chat
+    }
+
+    #[test]
+    fn export_writes_note_and_image_and_updates_on_reexport() {
+        let vault = tempdir().unwrap();
+        let image = vault.path().join("source.png");
+        std::fs::write(&image, b"png").unwrap();
+
+        let mut connector = ObsidianVault::new(vault.path());
+        connector.tags = vec!["#inbox".to_string(), "work notes".to_string()];
+        let mut first = chat("Lunch: receipt?");
+        first.web_source = Some(WebSource {
+            url: "https://example.com/\"menu\"".to_string(),
+            title: None,
+            selected_text: None,
+        });
+
+        let path = connector
+            .export(&ExportSource {
+                chat: &first,
+                image_path: Some(&image),
+            })
+            .unwrap();
+        assert_eq!(path, vault.path().join("Squigit/Lunch receipt.md"));
+        let note = std::fs::read_to_string(&path).unwrap();
+        assert!(note.starts_with("---\ntitle: \"Lunch: receipt?\"\n"));
+        assert!(note.contains("source: \"https://example.com/\\\"menu\\\"\"\n"));
+        assert!(note.contains("tags:\n  - squigit\n  - inbox\n  - work-notes\n---\n"));
+        assert!(note.contains(&format!("![[squigit-{}.png]]", "ab".repeat(8))));
+        assert!(note.contains("### Assistant\n\nA receipt from the café."));
+        assert!(vault
+            .path()
+            .join(format!(
+                "Squigit/attachments/squigit-{}.png",
+                "ab".repeat(8)
+            ))
+            .is_file());
+
+        let again = connector
+            .export(&ExportSource {
+                chat: &first,
+                image_path: Some(&image),
+            })
+            .unwrap();
+        assert_eq!(again, path);
+
+        let other = connector
+            .export(&ExportSource {
+                chat: &chat("Lunch: receipt?"),
+                image_path: None,
+            })
+            .unwrap();
+        assert_eq!(other, vault.path().join("Squigit/Lunch receipt (2).md"));
+    }
+
+    #[test]
+    fn export_rejects_missing_vault_and_escaping_folder() {
+        let vault = tempdir().unwrap();
+        let chat = chat("Note");
+        let source = ExportSource {
+            chat: &chat,
+            image_path: None,
+        };
+
+        let missing = ObsidianVault::new(vault.path().join("missing"));
+        assert!(matches!(
+            missing.export(&source),
+            Err(ExportError::InvalidTarget(_))
+        ));
+
+        let mut escaping = ObsidianVault::new(vault.path());
+        escaping.folder = "../outside".to_string();
+        assert!(matches!(
+            escaping.export(&source),
+            Err(ExportError::InvalidTarget(_))
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Metadata stubs for the system search.
+//!
+//! Each chat gets a small HTML file named after its title, holding the
+//! title, dates and summary, with its modification time set to the chat's
+//! last update. Spotlight and Windows Search index such files by name and
+//! content; opening one redirects to the app's link for the chat. A
+//! `<meta>` line records the chat ID, so a renamed chat replaces its stub
+//! and [`SearchIndexStubs::sync`] can drop stubs of deleted chats.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use ops_chat_storage::ChatMetadata;
+
+use crate::error::Result;
+use crate::{escape_html, file_stem, ExportConnector, ExportSource};
+
+/// Link opened for a chat, followed by its ID.
+pub const DEFAULT_LINK_PREFIX: &str = "snapllm://open-chat/";
+
+const STUB_EXTENSION: &str = "html";
+/// `<meta>` name holding the chat ID.
+const CHAT_ID_META: &str = "squigit-chat";
+/// Longest stub file name, in characters, before the extension.
+const MAX_STUB_NAME_CHARS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct SearchIndexStubs {
+    /// Folder holding nothing but the stubs.
+    pub dir: PathBuf,
+    pub link_prefix: String,
+}
+
+impl SearchIndexStubs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            link_prefix: DEFAULT_LINK_PREFIX.to_string(),
+        }
+    }
+
+    /// Write or update the stub of one chat and return its path.
+    pub fn write(&self, metadata: &ChatMetadata) -> Result<PathBuf> {
+        let existing = self.stubs()?.remove(&metadata.id);
+        self.write_stub(metadata, existing)
+    }
+
+    /// Remove the stub of a chat. Returns whether there was one.
+    pub fn remove(&self, chat_id: &str) -> Result<bool> {
+        match self.stubs()?.remove(chat_id) {
+            Some(path) => {
+                std::fs::remove_file(path)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Write a stub for every chat in `chats` and remove all others.
+    /// Returns the number of stubs removed.
+    pub fn sync(&self, chats: &[ChatMetadata]) -> Result<usize> {
+        let mut stubs = self.stubs()?;
+        for metadata in chats {
+            let existing = stubs.remove(&metadata.id);
+            self.write_stub(metadata, existing)?;
+        }
+        for path in stubs.values() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(stubs.len())
+    }
+
+    /// Unchanged stubs are left alone, so the indexer has nothing to redo.
+    fn write_stub(&self, metadata: &ChatMetadata, existing: Option<PathBuf>) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.free_path(&metadata.title, &metadata.id, existing.as_deref());
+        let content = self.render(metadata);
+        match existing {
+            Some(old) if old != path => std::fs::remove_file(old)?,
+            Some(_) if std::fs::read_to_string(&path).is_ok_and(|c| c == content) => {
+                return Ok(path)
+            }
+            _ => {}
+        }
+
+        std::fs::write(&path, content)?;
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(SystemTime::from(metadata.updated_at))?;
+        Ok(path)
+    }
+
+    /// Stub paths by chat ID.
+    fn stubs(&self) -> Result<HashMap<String, PathBuf>> {
+        let mut stubs = HashMap::new();
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(stubs),
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(STUB_EXTENSION) {
+                continue;
+            }
+            if let Some(chat_id) = std::fs::read_to_string(&path)
+                .ok()
+                .as_deref()
+                .and_then(stub_chat_id)
+            {
+                stubs.insert(chat_id, path);
+            }
+        }
+        Ok(stubs)
+    }
+
+    /// A file name based on `title` that no other chat's stub uses.
+    fn free_path(&self, title: &str, chat_id: &str, existing: Option<&Path>) -> PathBuf {
+        let base = file_stem(title, chat_id, MAX_STUB_NAME_CHARS);
+        for n in 1.. {
+            let name = if n == 1 {
+                format!("{}.{}", base, STUB_EXTENSION)
+            } else {
+                format!("{} ({}).{}", base, n, STUB_EXTENSION)
+            };
+            let path = self.dir.join(name);
+            if !path.exists() || existing == Some(path.as_path()) {
+                return path;
+            }
+        }
+        unreachable!("stub names are unbounded")
+    }
+
+    fn render(&self, metadata: &ChatMetadata) -> String {
+        let title = escape_line(&metadata.title);
+        let summary = escape_line(metadata.summary.as_deref().unwrap_or_default());
+        let link = escape_line(&format!("{}{}", self.link_prefix, metadata.id));
+        let created = metadata
+            .created_at
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let updated = metadata
+            .updated_at
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"{meta}\" content=\"{id}\">\n\
+             <meta name=\"description\" content=\"{summary}\">\n\
+             <meta name=\"created\" content=\"{created}\">\n\
+             <meta name=\"modified\" content=\"{updated}\">\n\
+             <meta http-equiv=\"refresh\" content=\"0; url={link}\">\n\
+             <title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n\
+             <p>{summary}</p>\n<p><a href=\"{link}\">Open chat</a></p>\n</body>\n</html>\n",
+            meta = CHAT_ID_META,
+            id = escape_line(&metadata.id),
+        )
+    }
+}
+
+impl ExportConnector for SearchIndexStubs {
+    fn id(&self) -> &'static str {
+        "search_index"
+    }
+
+    fn export(&self, source: &ExportSource<'_>) -> Result<PathBuf> {
+        self.write(&source.chat.metadata)
+    }
+}
+
+/// The chat ID recorded in a stub.
+fn stub_chat_id(content: &str) -> Option<String> {
+    let prefix = format!("<meta name=\"{}\" content=\"", CHAT_ID_META);
+    content.lines().find_map(|line| {
+        let id = line.strip_prefix(&prefix)?.strip_suffix("\">")?;
+        Some(unescape_html(id))
+    })
+}
+
+/// `text` escaped for one line of a stub, which is read back line by line.
+fn escape_line(text: &str) -> String {
+    escape_html(&text.replace(char::is_control, " "))
+}
+
+fn unescape_html(text: &str) -> String {
+    text.replace("&#39;", "'")
+        .replace("&quot;", "\"")
+        .replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn metadata(title: &str) -> ChatMetadata {
+        let mut metadata = ChatMetadata::new(title.to_string(), "ab".repeat(32), None);
+        metadata.summary = Some("Totals for <lunch> & tip".to_string());
+        metadata
+    }
+
+    #[test]
+    fn write_renames_and_removes_stubs_by_chat_id() {
+        let dir = tempdir().unwrap();
+        let stubs = SearchIndexStubs::new(dir.path().join("stubs"));
+
+        let mut chat = metadata("Receipt: lunch");
+        let path = stubs.write(&chat).unwrap();
+        assert_eq!(path, dir.path().join("stubs/Receipt lunch.html"));
+        let stub = std::fs::read_to_string(&path).unwrap();
+        assert!(stub.contains("<title>Receipt: lunch</title>"));
+        assert!(stub.contains("<p>Totals for &lt;lunch&gt; &amp; tip</p>"));
+        assert!(stub.contains(&format!("url=snapllm://open-chat/{}\"", chat.id)));
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().modified().unwrap(),
+            SystemTime::from(chat.updated_at)
+        );
+
+        let twin = metadata("Receipt: lunch");
+        let twin_path = stubs.write(&twin).unwrap();
+        assert_eq!(twin_path, dir.path().join("stubs/Receipt lunch (2).html"));
+
+        chat.title = "Dinner".to_string();
+        let renamed = stubs.write(&chat).unwrap();
+        assert_eq!(renamed, dir.path().join("stubs/Dinner.html"));
+        assert!(!path.exists());
+
+        assert!(stubs.remove(&chat.id).unwrap());
+        assert!(!renamed.exists());
+        assert!(!stubs.remove(&chat.id).unwrap());
+    }
+
+    #[test]
+    fn sync_drops_stubs_of_other_chats() {
+        let dir = tempdir().unwrap();
+        let stubs = SearchIndexStubs::new(dir.path());
+        let kept = metadata("Kept");
+        let gone = metadata("Gone");
+        stubs.write(&gone).unwrap();
+        std::fs::write(dir.path().join("notes.html"), "<p>not a stub</p>").unwrap();
+
+        assert_eq!(stubs.sync(std::slice::from_ref(&kept)).unwrap(), 1);
+        assert!(dir.path().join("Kept.html").is_file());
+        assert!(!dir.path().join("Gone.html").exists());
+        assert!(dir.path().join("notes.html").is_file());
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Note templates.
+//!
+//! Templates are Markdown with `{{name}}` placeholders:
+//!
+//! | Placeholder        | Value                                         |
+//! |--------------------|-----------------------------------------------|
+//! | `{{title}}`        | Chat title                                    |
+//! | `{{date}}`         | Creation date, `YYYY-MM-DD`                   |
+//! | `{{created}}`      | Creation time, RFC 3339                       |
+//! | `{{chat_id}}`      | Chat ID                                       |
+//! | `{{image}}`        | The connector's image embed                   |
+//! | `{{summary}}`      | One-line chat summary                         |
+//! | `{{url}}`          | Page URL, for browser captures                |
+//! | `{{selection}}`    | Text selected on the page                     |
+//! | `{{ocr}}`          | Text recognized in the image                  |
+//! | `{{conversation}}` | Every message, under a heading per speaker    |
+//!
+//! Placeholders without a value render empty; unknown ones are kept as
+//! written.
+
+use ops_chat_storage::ChatData;
+
+pub const DEFAULT_NOTE_TEMPLATE: &str = "# {{title}}
+
+{{image}}
+
+{{summary}}
+
+{{conversation}}
+";
+
+/// Placeholder values for `chat`, with `image` as the image embed.
+pub fn chat_fields(chat: &ChatData, image: &str) -> Vec<(&'static str, String)> {
+    let metadata = &chat.metadata;
+    let web_source = chat.web_source.as_ref();
+    let ocr = metadata
+        .ocr_lang
+        .as_deref()
+        .and_then(|model_id| chat.ocr_data.get(model_id))
+        .and_then(|regions| regions.as_deref())
+        .map(|regions| {
+            regions
+                .iter()
+                .map(|region| region.text.trim())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+
+    vec![
+        ("title", metadata.title.clone()),
+        ("date", metadata.created_at.format("%Y-%m-%d").to_string()),
+        (
+            "created",
+            metadata
+                .created_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        ),
+        ("chat_id", metadata.id.clone()),
+        ("image", image.to_string()),
+        ("summary", metadata.summary.clone().unwrap_or_default()),
+        (
+            "url",
+            web_source
+                .map(|source| source.url.clone())
+                .unwrap_or_default(),
+        ),
+        (
+            "selection",
+            web_source
+                .and_then(|source| source.selected_text.clone())
+                .unwrap_or_default(),
+        ),
+        ("ocr", ocr),
+        ("conversation", conversation(chat)),
+    ]
+}
+
+/// Replace `{{name}}` placeholders with `fields`. A template line holding
+/// nothing but an empty placeholder is dropped with the blank line after it.
+pub fn render(template: &str, fields: &[(&str, String)]) -> String {
+    let value = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| *field == name.trim())
+            .map(|(_, value)| value.as_str())
+    };
+
+    let mut out = String::with_capacity(template.len());
+    let mut skip_blank = false;
+    for line in template.lines() {
+        if skip_blank && line.trim().is_empty() {
+            skip_blank = false;
+            continue;
+        }
+        skip_blank = false;
+
+        let lone_placeholder = line
+            .trim()
+            .strip_prefix("{{")
+            .and_then(|rest| rest.strip_suffix("}}"))
+            .filter(|name| !name.contains("{{") && !name.contains("}}"));
+        if lone_placeholder
+            .and_then(value)
+            .is_some_and(|value| value.trim().is_empty())
+        {
+            skip_blank = true;
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                rest = &rest[start..];
+                break;
+            };
+            match value(&after[..end]) {
+                Some(value) => out.push_str(value),
+                None => out.push_str(&rest[start..start + end + 4]),
+            }
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        out.push('\n');
+    }
+    out
+}
+
+fn conversation(chat: &ChatData) -> String {
+    chat.messages
+        .iter()
+        .filter(|message| !message.content.trim().is_empty())
+        .map(|message| {
+            let speaker = if message.role == "user" {
+                "User"
+            } else {
+                "Assistant"
+            };
+            format!("### {}\n\n{}", speaker, message.content.trim())
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_fills_known_placeholders_only() {
+        let fields = vec![
+            ("title", "Receipt".to_string()),
+            ("summary", String::new()),
+            ("conversation", "```\na\n\n\nb\n```".to_string()),
+        ];
+        let rendered = render(
+            "# {{ title }}\n\n{{summary}}\n\n{{conversation}}\n{{unknown}} and {{",
+            &fields,
+        );
+        assert_eq!(
+            rendered,
+            "# Receipt\n\n```\na\n\n\nb\n```\n{{unknown}} and {{\n"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Usage statistics computed from local chat data, for the stats dashboard.
+//!
+//! Nothing is tracked for this: chat metadata gives creation days, capture
+//! types and OCR languages, message timestamps give response latency, and
+//! CAS object write times give storage growth. Days are local calendar
+//! days.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::storage::ChatStorage;
+use crate::types::ChatMessage;
+
+/// Time window for [`ChatStorage::chat_analytics`]: `[from, to)`, either
+/// bound open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DateRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        !self.from.is_some_and(|from| at < from) && !self.to.is_some_and(|to| at >= to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyCount {
+    pub date: NaiveDate,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyStorage {
+    pub date: NaiveDate,
+    /// Bytes of CAS objects written that day.
+    pub bytes_added: u64,
+    /// CAS size at the end of the day.
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatAnalytics {
+    pub range: DateRange,
+    /// Chats created in the range.
+    pub total_chats: usize,
+    /// Days without chats are omitted.
+    pub chats_per_day: Vec<DailyCount>,
+    /// Chats per capture type. Chats from before capture types were
+    /// recorded are not counted.
+    pub capture_types: BTreeMap<String, usize>,
+    pub most_used_capture_type: Option<String>,
+    /// Mean time from a user message to the assistant reply, over replies
+    /// written in the range.
+    pub average_response_latency_ms: Option<u64>,
+    pub response_count: usize,
+    /// Chats per OCR model/language.
+    pub ocr_languages: BTreeMap<String, usize>,
+    /// Days without writes are omitted.
+    pub storage_growth: Vec<DailyStorage>,
+}
+
+impl ChatStorage {
+    /// Statistics over chats created (and replies written) within `range`.
+    /// Chats that fail to load are left out of the latency figures.
+    pub fn chat_analytics(&self, range: DateRange) -> Result<ChatAnalytics> {
+        let all_chats = self.list_chats()?;
+        let chats: Vec<_> = all_chats
+            .iter()
+            .filter(|chat| range.contains(chat.created_at))
+            .collect();
+
+        let mut chats_per_day: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        let mut capture_types: BTreeMap<String, usize> = BTreeMap::new();
+        let mut ocr_languages: BTreeMap<String, usize> = BTreeMap::new();
+        for chat in &chats {
+            *chats_per_day.entry(local_day(chat.created_at)).or_default() += 1;
+            if let Some(capture_type) = chat.capture_type.as_deref() {
+                *capture_types.entry(capture_type.to_string()).or_default() += 1;
+            }
+            if let Some(lang) = chat.ocr_lang.as_deref() {
+                *ocr_languages.entry(lang.to_string()).or_default() += 1;
+            }
+        }
+        // Ties go to the alphabetically first type, for a stable answer.
+        let most_used_capture_type = capture_types
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(capture_type, _)| capture_type.clone());
+
+        let mut latencies = Vec::new();
+        for chat in &all_chats {
+            // Chats untouched since before the range cannot hold replies in it.
+            if range.from.is_some_and(|from| chat.updated_at < from) {
+                continue;
+            }
+            if let Ok(data) = self.load_chat(&chat.id) {
+                latencies.extend(response_latencies_ms(&data.messages, &range));
+            }
+        }
+        let average_response_latency_ms =
+            (!latencies.is_empty()).then(|| latencies.iter().sum::<u64>() / latencies.len() as u64);
+
+        Ok(ChatAnalytics {
+            range,
+            total_chats: chats.len(),
+            chats_per_day: chats_per_day
+                .into_iter()
+                .map(|(date, count)| DailyCount { date, count })
+                .collect(),
+            capture_types,
+            most_used_capture_type,
+            average_response_latency_ms,
+            response_count: latencies.len(),
+            ocr_languages,
+            storage_growth: self.storage_growth(&range)?,
+        })
+    }
+
+    fn storage_growth(&self, range: &DateRange) -> Result<Vec<DailyStorage>> {
+        let mut before = 0;
+        let mut per_day: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+        for (written_at, size) in object_writes(self.objects_dir())? {
+            if range.from.is_some_and(|from| written_at < from) {
+                before += size;
+            } else if range.contains(written_at) {
+                *per_day.entry(local_day(written_at)).or_default() += size;
+            }
+        }
+
+        let mut total = before;
+        Ok(per_day
+            .into_iter()
+            .map(|(date, bytes_added)| {
+                total += bytes_added;
+                DailyStorage {
+                    date,
+                    bytes_added,
+                    total_bytes: total,
+                }
+            })
+            .collect())
+    }
+}
+
+fn local_day(at: DateTime<Utc>) -> NaiveDate {
+    at.with_timezone(&Local).date_naive()
+}
+
+/// Latency of each assistant reply in `range` that directly follows a
+/// user message.
+fn response_latencies_ms(messages: &[ChatMessage], range: &DateRange) -> Vec<u64> {
+    messages
+        .windows(2)
+        .filter(|pair| pair[0].role == "user" && pair[1].role == "assistant")
+        .filter(|pair| range.contains(pair[1].timestamp))
+        .filter_map(|pair| {
+            let latency = pair[1].timestamp - pair[0].timestamp;
+            u64::try_from(latency.num_milliseconds()).ok()
+        })
+        .collect()
+}
+
+/// Write time and size of every CAS object. Objects are written once, so
+/// the modification time is when they were added.
+fn object_writes(objects_dir: &Path) -> Result<Vec<(DateTime<Utc>, u64)>> {
+    let mut writes = Vec::new();
+    if !objects_dir.exists() {
+        return Ok(writes);
+    }
+    for prefix in fs::read_dir(objects_dir)? {
+        let prefix = prefix?.path();
+        if !prefix.is_dir() {
+            continue;
+        }
+        for object in fs::read_dir(&prefix)? {
+            let object = object?;
+            if object.path().extension().is_some_and(|ext| ext == "tone") {
+                continue;
+            }
+            let metadata = object.metadata()?;
+            if let Ok(modified) = metadata.modified() {
+                writes.push((DateTime::<Utc>::from(modified), metadata.len()));
+            }
+        }
+    }
+    Ok(writes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatData, ChatMetadata};
+    use chrono::Duration;
+
+    #[test]
+    fn analytics_summarize_chats_latency_and_storage() {
+        let (storage, _dir) = test_storage();
+        let image = storage.store_image(b"fake screenshot", None).unwrap();
+
+        let now = Utc::now();
+        for (capture_type, ocr_lang) in [
+            (Some("squiggle"), Some("pp-ocr-v5-en")),
+            (Some("rectangular"), Some("pp-ocr-v5-en")),
+            (Some("squiggle"), None),
+            (None, Some("pp-ocr-v5-latin")),
+        ] {
+            let mut metadata = ChatMetadata::new(
+                "Chat".to_string(),
+                image.hash.clone(),
+                ocr_lang.map(str::to_string),
+            );
+            metadata.capture_type = capture_type.map(str::to_string);
+            let mut chat = ChatData::new(metadata);
+            let mut question = ChatMessage::user("What is this?".to_string());
+            question.timestamp = now - Duration::seconds(10);
+            let mut answer = ChatMessage::assistant("A chart.".to_string());
+            answer.timestamp = now - Duration::seconds(8);
+            chat.messages = vec![question, answer];
+            storage.save_chat(&chat).unwrap();
+        }
+
+        let analytics = storage.chat_analytics(DateRange::default()).unwrap();
+        assert_eq!(analytics.total_chats, 4);
+        assert_eq!(
+            analytics
+                .chats_per_day
+                .iter()
+                .map(|d| d.count)
+                .sum::<usize>(),
+            4
+        );
+        assert_eq!(
+            analytics.most_used_capture_type.as_deref(),
+            Some("squiggle")
+        );
+        assert_eq!(analytics.capture_types.values().sum::<usize>(), 3);
+        assert_eq!(analytics.ocr_languages.get("pp-ocr-v5-en"), Some(&2));
+        assert_eq!(analytics.response_count, 4);
+        assert_eq!(analytics.average_response_latency_ms, Some(2000));
+        let last = analytics.storage_growth.last().unwrap();
+        assert_eq!(last.total_bytes, b"fake screenshot".len() as u64);
+
+        let future = DateRange {
+            from: Some(now + Duration::hours(1)),
+            to: None,
+        };
+        let empty = storage.chat_analytics(future).unwrap();
+        assert_eq!(empty.total_chats, 0);
+        assert_eq!(empty.average_response_latency_ms, None);
+        assert!(empty.storage_growth.is_empty());
+    }
+}
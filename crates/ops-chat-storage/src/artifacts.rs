@@ -0,0 +1,293 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Files generated for a chat, such as code, CSV or calendar files.
+//!
+//! Artifact content is stored in the CAS like images and attachments; each
+//! chat keeps `artifacts.json` with every artifact and its versions. Saving
+//! under a name the chat already has adds a version to that artifact,
+//! unless the content is unchanged. Retention keeps the objects alive for as
+//! long as the chat references their hashes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::attachments::mime_type_for_extension;
+use crate::error::{Result, StorageError};
+use crate::storage::{write_atomic, ChatStorage};
+
+/// Artifact index filename inside a chat directory.
+const ARTIFACTS_FILE: &str = "artifacts.json";
+const MAX_NAME_CHARS: usize = 200;
+
+/// One saved version of an artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactVersion {
+    /// Starts at 1.
+    pub version: u32,
+    /// BLAKE3 hash of the content.
+    pub hash: String,
+    pub size: u64,
+    /// Model that produced the content, if any.
+    #[serde(default)]
+    pub model: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A generated file and its versions, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    pub id: String,
+    /// File name, e.g. `main.rs`; unique within the chat.
+    pub name: String,
+    pub mime_type: String,
+    pub versions: Vec<ArtifactVersion>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Artifact {
+    pub fn latest(&self) -> Option<&ArtifactVersion> {
+        self.versions.last()
+    }
+
+    /// The given version, or the latest one for `None`.
+    pub fn version(&self, version: Option<u32>) -> Option<&ArtifactVersion> {
+        match version {
+            Some(version) => self.versions.iter().find(|v| v.version == version),
+            None => self.latest(),
+        }
+    }
+
+    fn extension(&self) -> &str {
+        Path::new(&self.name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("bin")
+    }
+}
+
+impl ChatStorage {
+    /// Store `content` as artifact `name` of a chat and return the artifact.
+    /// An existing artifact of that name gets a new version unless its
+    /// latest version already holds the same content.
+    pub fn save_artifact(
+        &self,
+        chat_id: &str,
+        name: &str,
+        content: &[u8],
+        model: Option<&str>,
+    ) -> Result<Artifact> {
+        let chat_dir = self.chat_dir(chat_id);
+        if !chat_dir.exists() {
+            return Err(StorageError::ChatNotFound(chat_id.to_string()));
+        }
+        let name = artifact_name(name)?;
+
+        let mut artifacts = self.list_artifacts(chat_id)?;
+        let now = Utc::now();
+        let index = match artifacts.iter().position(|artifact| artifact.name == name) {
+            Some(index) => index,
+            None => {
+                let extension = Path::new(&name)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("");
+                let mime_type = match mime_type_for_extension(extension) {
+                    "application/octet-stream" if std::str::from_utf8(content).is_ok() => {
+                        "text/plain"
+                    }
+                    mime_type => mime_type,
+                };
+                artifacts.push(Artifact {
+                    id: Uuid::new_v4().to_string(),
+                    name,
+                    mime_type: mime_type.to_string(),
+                    versions: Vec::new(),
+                    created_at: now,
+                });
+                artifacts.len() - 1
+            }
+        };
+
+        let artifact = &mut artifacts[index];
+        let hash = blake3::hash(content).to_hex().to_string();
+        if artifact.latest().is_some_and(|latest| latest.hash == hash) {
+            return Ok(artifact.clone());
+        }
+        let object_path = self.artifact_object_path(&hash, artifact.extension());
+        if !object_path.exists() {
+            fs::create_dir_all(object_path.parent().unwrap_or(self.objects_dir()))?;
+            self.write_sealed(&object_path, content)?;
+        }
+        artifact.versions.push(ArtifactVersion {
+            version: artifact.latest().map_or(1, |latest| latest.version + 1),
+            hash,
+            size: content.len() as u64,
+            model: model.map(str::to_string),
+            created_at: now,
+        });
+        let artifact = artifact.clone();
+
+        let json = serde_json::to_string_pretty(&artifacts)?;
+        write_atomic(chat_dir.join(ARTIFACTS_FILE), json)?;
+        Ok(artifact)
+    }
+
+    /// A chat's artifacts, oldest first.
+    pub fn list_artifacts(&self, chat_id: &str) -> Result<Vec<Artifact>> {
+        let path = self.chat_dir(chat_id).join(ARTIFACTS_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let json = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn get_artifact(&self, chat_id: &str, artifact_id: &str) -> Result<Artifact> {
+        self.list_artifacts(chat_id)?
+            .into_iter()
+            .find(|artifact| artifact.id == artifact_id)
+            .ok_or_else(|| StorageError::ArtifactNotFound(artifact_id.to_string()))
+    }
+
+    /// Content of an artifact version, the latest for `None`.
+    pub fn read_artifact(
+        &self,
+        chat_id: &str,
+        artifact_id: &str,
+        version: Option<u32>,
+    ) -> Result<Vec<u8>> {
+        let path = self.artifact_version_path(chat_id, artifact_id, version)?;
+        self.read_object(&path)
+    }
+
+    /// Copy an artifact version, the latest for `None`, to `destination`.
+    /// An existing directory gets a file named after the artifact. Returns
+    /// the written path.
+    pub fn export_artifact(
+        &self,
+        chat_id: &str,
+        artifact_id: &str,
+        version: Option<u32>,
+        destination: &Path,
+    ) -> Result<PathBuf> {
+        let source = self.artifact_version_path(chat_id, artifact_id, version)?;
+        let target = if destination.is_dir() {
+            destination.join(self.get_artifact(chat_id, artifact_id)?.name)
+        } else {
+            destination.to_path_buf()
+        };
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, self.read_object(&source)?)?;
+        Ok(target)
+    }
+
+    fn artifact_version_path(
+        &self,
+        chat_id: &str,
+        artifact_id: &str,
+        version: Option<u32>,
+    ) -> Result<PathBuf> {
+        let artifact = self.get_artifact(chat_id, artifact_id)?;
+        let stored = artifact.version(version).ok_or_else(|| {
+            StorageError::ArtifactNotFound(format!("{} v{}", artifact_id, version.unwrap_or(0)))
+        })?;
+        let path = self.artifact_object_path(&stored.hash, artifact.extension());
+        if !path.exists() {
+            return Err(StorageError::ArtifactNotFound(stored.hash.clone()));
+        }
+        Ok(path)
+    }
+
+    fn artifact_object_path(&self, hash: &str, extension: &str) -> PathBuf {
+        self.objects_dir()
+            .join(&hash[..2])
+            .join(format!("{}.{}", hash, extension))
+    }
+}
+
+/// A plain file name: no directories, not empty, of bounded length.
+fn artifact_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name.chars().count() <= MAX_NAME_CHARS
+        && !name.contains(['/', '\\'])
+        && !name.chars().any(char::is_control);
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(StorageError::InvalidArtifactName(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatData, ChatMetadata};
+
+    #[test]
+    fn saving_under_same_name_adds_versions() {
+        let (storage, base_dir) = test_storage();
+        let metadata = ChatMetadata::new("Code".to_string(), "0".repeat(64), None);
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+
+        let first = storage
+            .save_artifact(&metadata.id, "main.rs", b"fn main() {}", Some("model-a"))
+            .unwrap();
+        assert_eq!(first.mime_type, "text/plain");
+        let same = storage
+            .save_artifact(&metadata.id, "main.rs", b"fn main() {}", None)
+            .unwrap();
+        assert_eq!(same.versions.len(), 1);
+        let second = storage
+            .save_artifact(&metadata.id, "main.rs", b"fn main() { run() }", None)
+            .unwrap();
+        assert_eq!(second.id, first.id);
+        assert_eq!(second.latest().unwrap().version, 2);
+        storage
+            .save_artifact(&metadata.id, "totals.csv", b"a,b\n", None)
+            .unwrap();
+
+        let artifacts = storage.load_chat(&metadata.id).unwrap().artifacts;
+        let names: Vec<_> = artifacts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["main.rs", "totals.csv"]);
+        assert_eq!(artifacts[1].mime_type, "text/csv");
+        assert_eq!(
+            storage
+                .read_artifact(&metadata.id, &first.id, Some(1))
+                .unwrap(),
+            b"fn main() {}"
+        );
+
+        let out_dir = base_dir.path().join("out");
+        fs::create_dir_all(&out_dir).unwrap();
+        let exported = storage
+            .export_artifact(&metadata.id, &first.id, None, &out_dir)
+            .unwrap();
+        assert_eq!(exported, out_dir.join("main.rs"));
+        assert_eq!(fs::read(&exported).unwrap(), b"fn main() { run() }");
+
+        assert!(storage
+            .read_artifact(&metadata.id, &first.id, Some(3))
+            .is_err());
+        assert!(storage
+            .save_artifact(&metadata.id, "../escape.rs", b"x", None)
+            .is_err());
+        assert!(storage
+            .save_artifact("missing", "a.txt", b"x", None)
+            .is_err());
+    }
+}
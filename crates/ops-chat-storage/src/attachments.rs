@@ -0,0 +1,418 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Index of files stored in the CAS through `store_file`.
+//!
+//! Objects are named by hash only, so the index keeps what the bytes do
+//! not say: the MIME type, size, the name the file had when it was added
+//! and the first chat that referenced it. It backs the "all files" view
+//! and lets cleanup tell which objects are attachments. Every store bumps
+//! the entry's last-used time, which orders the composer's recent picker.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, StorageError};
+use crate::storage::{write_atomic, ChatStorage};
+
+/// Attachment index filename inside the storage base directory.
+const ATTACHMENTS_FILE: &str = "attachments.json";
+
+/// One indexed attachment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentInfo {
+    /// BLAKE3 hash of the content.
+    pub hash: String,
+    pub mime_type: String,
+    pub size: u64,
+    /// File name the attachment was added under, when known.
+    #[serde(default)]
+    pub original_name: Option<String>,
+    pub extension: String,
+    /// Absolute path of the CAS object.
+    pub path: String,
+    /// Chat that referenced the attachment first.
+    #[serde(default)]
+    pub first_seen_chat_id: Option<String>,
+    pub first_seen_at: DateTime<Utc>,
+    /// Last time the content was stored again, e.g. re-attached.
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl AttachmentInfo {
+    /// When the attachment was last stored.
+    pub fn last_used(&self) -> DateTime<Utc> {
+        self.last_used_at.unwrap_or(self.first_seen_at)
+    }
+
+    fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+}
+
+/// Coarse attachment kind for the recent picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Image,
+    /// Anything that is not an image.
+    File,
+}
+
+/// Criteria for [`ChatStorage::list_attachments`]. Empty fields match
+/// everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AttachmentFilter {
+    /// MIME type prefix, e.g. `image/` or `application/pdf`.
+    pub mime_prefix: Option<String>,
+    /// Only attachments first seen in this chat.
+    pub chat_id: Option<String>,
+    /// Case-insensitive substring of the original name.
+    pub name_contains: Option<String>,
+}
+
+impl AttachmentFilter {
+    fn matches(&self, info: &AttachmentInfo) -> bool {
+        let mime_ok = !self
+            .mime_prefix
+            .as_deref()
+            .is_some_and(|prefix| !info.mime_type.starts_with(prefix));
+        let chat_ok = !self
+            .chat_id
+            .as_deref()
+            .is_some_and(|chat_id| info.first_seen_chat_id.as_deref() != Some(chat_id));
+        let name_ok = !self.name_contains.as_deref().is_some_and(|needle| {
+            !info
+                .original_name
+                .as_deref()
+                .is_some_and(|name| name.to_lowercase().contains(&needle.to_lowercase()))
+        });
+        mime_ok && chat_ok && name_ok
+    }
+}
+
+type AttachmentIndex = BTreeMap<String, AttachmentInfo>;
+
+/// MIME type for a file extension, `application/octet-stream` when unknown.
+pub fn mime_type_for_extension(extension: &str) -> &'static str {
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "heic" => "image/heic",
+        "avif" => "image/avif",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "csv" => "text/csv",
+        "ics" => "text/calendar",
+        "html" | "htm" => "text/html",
+        "xml" => "text/xml",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
+
+impl ChatStorage {
+    /// Attachments matching `filter`, most recently added first.
+    pub fn list_attachments(&self, filter: &AttachmentFilter) -> Result<Vec<AttachmentInfo>> {
+        let mut attachments: Vec<AttachmentInfo> = self
+            .load_attachment_index()?
+            .into_values()
+            .filter(|info| filter.matches(info))
+            .collect();
+        attachments.sort_by_key(|info| std::cmp::Reverse(info.first_seen_at));
+        Ok(attachments)
+    }
+
+    /// Index entry for an attachment by its content hash.
+    pub fn get_attachment_info(&self, hash: &str) -> Result<AttachmentInfo> {
+        self.load_attachment_index()?
+            .remove(hash)
+            .ok_or_else(|| StorageError::AttachmentNotFound(hash.to_string()))
+    }
+
+    /// The `limit` most recently used attachments of `kind` (any when
+    /// `None`) whose objects still exist.
+    pub fn list_recent_attachments(
+        &self,
+        kind: Option<AttachmentKind>,
+        limit: usize,
+    ) -> Result<Vec<AttachmentInfo>> {
+        let mut attachments: Vec<AttachmentInfo> = self
+            .load_attachment_index()?
+            .into_values()
+            .filter(|info| match kind {
+                Some(AttachmentKind::Image) => info.is_image(),
+                Some(AttachmentKind::File) => !info.is_image(),
+                None => true,
+            })
+            .filter(|info| Path::new(&info.path).exists())
+            .collect();
+        attachments.sort_by_key(|info| std::cmp::Reverse(info.last_used()));
+        attachments.truncate(limit);
+        Ok(attachments)
+    }
+
+    /// Rebuild the index from the attachment registries of all chats.
+    /// Entries for objects no chat references are kept.
+    pub fn rebuild_attachment_index(&self) -> Result<()> {
+        let mut index = self.read_attachment_index()?.unwrap_or_default();
+        index.retain(|_, info| Path::new(&info.path).exists());
+        for metadata in self.list_chats()? {
+            let Ok(chat) = self.load_chat(&metadata.id) else {
+                continue;
+            };
+            for record in chat.attachment_registry.values() {
+                let Some(info) = index_entry_for_path(&record.cas_path) else {
+                    continue;
+                };
+                let info = index.entry(info.hash.clone()).or_insert(AttachmentInfo {
+                    original_name: Some(record.display_name.clone()),
+                    first_seen_at: record.last_seen_at,
+                    ..info
+                });
+                if info.first_seen_chat_id.is_none() {
+                    info.first_seen_chat_id = Some(metadata.id.clone());
+                }
+            }
+        }
+        self.write_attachment_index(&index)
+    }
+
+    /// Add a freshly stored object to the index, or mark an existing entry
+    /// as used. An existing entry keeps its first-seen data and only gains
+    /// a name if it had none.
+    pub(crate) fn record_attachment(&self, path: &str, original_name: Option<&str>) -> Result<()> {
+        let Some(info) = index_entry_for_path(path) else {
+            return Ok(());
+        };
+        let mut index = self.load_attachment_index()?;
+        match index.get_mut(&info.hash) {
+            Some(existing) => {
+                existing.last_used_at = Some(Utc::now());
+                if existing.original_name.is_none() {
+                    existing.original_name = original_name.map(str::to_string);
+                }
+            }
+            None => {
+                let info = AttachmentInfo {
+                    original_name: original_name.map(str::to_string),
+                    ..info
+                };
+                index.insert(info.hash.clone(), info);
+            }
+        }
+        self.write_attachment_index(&index)
+    }
+
+    /// Set the first-seen chat of the given CAS paths where it is unset.
+    pub(crate) fn link_attachments<'a>(
+        &self,
+        chat_id: &str,
+        paths: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        let mut index = self.load_attachment_index()?;
+        let mut changed = false;
+        for path in paths {
+            let Some(info) = index_entry_for_path(path) else {
+                continue;
+            };
+            let info = index.entry(info.hash.clone()).or_insert(info);
+            if info.first_seen_chat_id.is_none() {
+                info.first_seen_chat_id = Some(chat_id.to_string());
+                changed = true;
+            }
+        }
+        if changed {
+            self.write_attachment_index(&index)?;
+        }
+        Ok(())
+    }
+
+    /// The index, built from the chats on first use.
+    fn load_attachment_index(&self) -> Result<AttachmentIndex> {
+        if let Some(index) = self.read_attachment_index()? {
+            return Ok(index);
+        }
+        self.rebuild_attachment_index()?;
+        Ok(self.read_attachment_index()?.unwrap_or_default())
+    }
+
+    fn read_attachment_index(&self) -> Result<Option<AttachmentIndex>> {
+        let path = self.base_dir().join(ATTACHMENTS_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        // An unreadable index is rebuilt like a missing one.
+        let json = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&json).ok())
+    }
+
+    fn write_attachment_index(&self, index: &AttachmentIndex) -> Result<()> {
+        let json = serde_json::to_string_pretty(index)?;
+        write_atomic(self.base_dir().join(ATTACHMENTS_FILE), json)?;
+        Ok(())
+    }
+}
+
+/// Entry for an existing CAS object at `path`, without name or chat.
+fn index_entry_for_path(path: &str) -> Option<AttachmentInfo> {
+    let path = Path::new(path);
+    let hash = path.file_stem()?.to_str()?;
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("bin");
+    let size = fs::metadata(path).ok()?.len();
+    Some(AttachmentInfo {
+        hash: hash.to_string(),
+        mime_type: mime_type_for_extension(extension).to_string(),
+        size,
+        original_name: None,
+        extension: extension.to_string(),
+        path: path.to_string_lossy().into_owned(),
+        first_seen_chat_id: None,
+        first_seen_at: Utc::now(),
+        last_used_at: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatAttachmentKind, ChatAttachmentRecord, ChatData, ChatMetadata};
+
+    #[test]
+    fn stored_files_are_indexed_and_linked_to_their_first_chat() {
+        let (storage, base_dir) = test_storage();
+        let source = base_dir.path().join("Quarterly Report.pdf");
+        fs::write(&source, b"%PDF-1.7 report").unwrap();
+        let stored = storage
+            .store_file_from_path(source.to_str().unwrap(), None)
+            .unwrap();
+        storage.store_file(b"plain notes", "txt", None).unwrap();
+
+        let info = storage.get_attachment_info(&stored.hash).unwrap();
+        assert_eq!(info.mime_type, "application/pdf");
+        assert_eq!(info.size, 15);
+        assert_eq!(info.original_name.as_deref(), Some("Quarterly Report.pdf"));
+        assert_eq!(info.first_seen_chat_id, None);
+
+        for title in ["First", "Second"] {
+            let mut chat =
+                ChatData::new(ChatMetadata::new(title.to_string(), "0".repeat(64), None));
+            chat.attachment_registry.insert(
+                stored.path.clone(),
+                ChatAttachmentRecord {
+                    cas_path: stored.path.clone(),
+                    display_name: "Quarterly Report.pdf".to_string(),
+                    kind: ChatAttachmentKind::DocumentUpload,
+                    mime_type: "application/pdf".to_string(),
+                    source_path: None,
+                    provider_file: None,
+                    last_seen_at: Utc::now(),
+                    last_recalled_at: None,
+                },
+            );
+            storage.save_chat(&chat).unwrap();
+            if title == "First" {
+                let info = storage.get_attachment_info(&stored.hash).unwrap();
+                assert_eq!(info.first_seen_chat_id, Some(chat.metadata.id));
+            }
+        }
+
+        let documents = storage
+            .list_attachments(&AttachmentFilter {
+                mime_prefix: Some("application/".to_string()),
+                name_contains: Some("report".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!(
+            storage
+                .list_attachments(&AttachmentFilter::default())
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(matches!(
+            storage.get_attachment_info(&"f".repeat(64)),
+            Err(StorageError::AttachmentNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn recent_attachments_follow_the_last_store() {
+        let (storage, _dir) = test_storage();
+        let png = storage.store_image(b"\x89PNG fake pixels", None).unwrap();
+        let notes = storage.store_file(b"meeting notes", "md", None).unwrap();
+        let pdf = storage.store_file(b"%PDF-1.7", "pdf", None).unwrap();
+        // Re-attaching the notes moves them to the front.
+        storage.store_file(b"meeting notes", "md", None).unwrap();
+
+        let recent = storage.list_recent_attachments(None, 2).unwrap();
+        let hashes: Vec<&str> = recent.iter().map(|info| info.hash.as_str()).collect();
+        assert_eq!(hashes, vec![notes.hash.as_str(), pdf.hash.as_str()]);
+
+        let images = storage
+            .list_recent_attachments(Some(AttachmentKind::Image), 10)
+            .unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].hash, png.hash);
+        assert_eq!(
+            storage
+                .list_recent_attachments(Some(AttachmentKind::File), 10)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn missing_index_is_rebuilt_from_chat_registries() {
+        let (storage, base_dir) = test_storage();
+        let stored = storage.store_file(b"col,val\n1,2\n", "csv", None).unwrap();
+        let mut chat = ChatData::new(ChatMetadata::new("Data".to_string(), "0".repeat(64), None));
+        chat.attachment_registry.insert(
+            stored.path.clone(),
+            ChatAttachmentRecord {
+                cas_path: stored.path.clone(),
+                display_name: "data.csv".to_string(),
+                kind: ChatAttachmentKind::TextLocal,
+                mime_type: "text/csv".to_string(),
+                source_path: None,
+                provider_file: None,
+                last_seen_at: Utc::now(),
+                last_recalled_at: None,
+            },
+        );
+        storage.save_chat(&chat).unwrap();
+        fs::remove_file(base_dir.path().join(ATTACHMENTS_FILE)).unwrap();
+
+        let info = storage.get_attachment_info(&stored.hash).unwrap();
+        assert_eq!(info.mime_type, "text/csv");
+        assert_eq!(info.original_name.as_deref(), Some("data.csv"));
+        assert_eq!(info.first_seen_chat_id, Some(chat.metadata.id));
+    }
+}
@@ -0,0 +1,475 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional encryption at rest for chat transcripts, OCR text, what was
+//! derived from them and CAS objects.
+//!
+//! With a [`StorageKey`] set through [`ChatStorage::with_encryption`], the
+//! transcript (`messages.json`, `messages.md`), `ocr_frame.json`, the image
+//! brief, rolling summary, plugin notes, web source and extractions, and
+//! stored objects are written as XChaCha20-Poly1305 ciphertext:
+//!
+//! ```text
+//! "SQCE1\n" | 24-byte nonce | ciphertext and tag
+//! ```
+//!
+//! Reads recognize the header, so plaintext files from before encryption
+//! was turned on keep loading until [`ChatStorage::encrypt_existing`]
+//! converts them. Tools that need an object as a file, like the OCR
+//! sidecar, get a decrypted copy from [`ChatStorage::readable_path`]. The
+//! copies live under the user's runtime directory, or their cache directory
+//! where there is none, never in the shared temporary directory, and the
+//! app sweeps them with [`clear_all_decrypted_copies`] at every start.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::Serialize;
+
+use crate::error::{Result, StorageError};
+use crate::storage::{write_atomic, ChatStorage};
+
+const MAGIC: &[u8] = b"SQCE1\n";
+const NONCE_LEN: usize = 24;
+
+/// Directory under the per-user root holding every storage's copies.
+const DECRYPTED_DIR_NAME: &str = "squigit-decrypted";
+
+/// Chat files that are encrypted, besides the objects. `meta.json` stays
+/// readable for the chat list and search.
+const SEALED_CHAT_FILES: &[&str] = &[
+    "messages.json",
+    "messages.md",
+    "ocr_frame.json",
+    "image_brief.txt",
+    "rolling_summary.txt",
+    "plugin_notes.json",
+    "web_source.json",
+    "extractions.json",
+];
+
+/// A profile's chat storage key.
+#[derive(Clone)]
+pub struct StorageKey([u8; 32]);
+
+impl StorageKey {
+    pub fn generate() -> Self {
+        Self(XChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+impl std::fmt::Debug for StorageKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StorageKey(..)")
+    }
+}
+
+/// Files converted by [`ChatStorage::encrypt_existing`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionReport {
+    pub chat_files: usize,
+    pub objects: usize,
+}
+
+/// Whether `data` was written by [`encrypt`].
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn encrypt(key: &StorageKey, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = key
+        .cipher()
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| StorageError::Encryption("Encryption failed".to_string()))?;
+    let mut sealed = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    sealed.extend_from_slice(MAGIC);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+pub fn decrypt(key: &StorageKey, sealed: &[u8]) -> Result<Vec<u8>> {
+    let body = sealed
+        .strip_prefix(MAGIC)
+        .filter(|body| body.len() >= NONCE_LEN)
+        .ok_or_else(|| StorageError::Encryption("Not an encrypted file".to_string()))?;
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    key.cipher()
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| StorageError::Encryption("Wrong key or corrupted file".to_string()))
+}
+
+impl ChatStorage {
+    /// Encrypt transcripts, OCR text, what was derived from them and objects
+    /// written from now on, and decrypt them on load.
+    pub fn with_encryption(mut self, key: Option<StorageKey>) -> Self {
+        self.encryption = key;
+        self
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    /// Read a file that may be encrypted.
+    pub(crate) fn read_sealed(&self, path: &Path) -> Result<Vec<u8>> {
+        let data = fs::read(path)?;
+        if !is_encrypted(&data) {
+            return Ok(data);
+        }
+        match &self.encryption {
+            Some(key) => decrypt(key, &data),
+            None => Err(StorageError::Encryption(format!(
+                "{} is encrypted and no key is set",
+                path.display()
+            ))),
+        }
+    }
+
+    pub(crate) fn read_sealed_string(&self, path: &Path) -> Result<String> {
+        String::from_utf8(self.read_sealed(path)?)
+            .map_err(|e| StorageError::Encryption(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Write a file, encrypted when a key is set.
+    pub(crate) fn write_sealed(&self, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+        match &self.encryption {
+            Some(key) => write_atomic(path, encrypt(key, contents.as_ref())?),
+            None => write_atomic(path, contents),
+        }
+    }
+
+    /// The content of a stored object, decrypted.
+    pub fn read_object(&self, path: &Path) -> Result<Vec<u8>> {
+        self.read_sealed(path)
+    }
+
+    /// A path a tool can read `path` from: the file itself, or for an
+    /// encrypted object a decrypted copy in a directory only the user can
+    /// read. The copy is written again on every call rather than trusted
+    /// from an earlier one. Remove the copies with [`clear_decrypted_copies`].
+    ///
+    /// [`clear_decrypted_copies`]: ChatStorage::clear_decrypted_copies
+    pub fn readable_path(&self, path: &Path) -> Result<PathBuf> {
+        if !file_is_encrypted(path)? {
+            return Ok(path.to_path_buf());
+        }
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| StorageError::ImageNotFound(path.display().to_string()))?;
+        let copy = self.decrypted_dir()?.join(file_name);
+        write_atomic(&copy, self.read_sealed(path)?)?;
+        Ok(copy)
+    }
+
+    /// Delete the decrypted copies made by [`ChatStorage::readable_path`].
+    pub fn clear_decrypted_copies(&self) -> Result<()> {
+        let dir = self.decrypted_copies_dir()?;
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Encrypt the chat files and objects still stored as plaintext, e.g.
+    /// after encryption was turned on for a profile with chats.
+    pub fn encrypt_existing(&self) -> Result<EncryptionReport> {
+        let Some(key) = &self.encryption else {
+            return Err(StorageError::Encryption("No key is set".to_string()));
+        };
+        let mut report = EncryptionReport::default();
+
+        for chat in self.list_chats()? {
+            let chat_dir = self.chat_dir(&chat.id);
+            for name in SEALED_CHAT_FILES {
+                if encrypt_in_place(key, &chat_dir.join(name))? {
+                    report.chat_files += 1;
+                }
+            }
+        }
+
+        // Only objects chats refer to: a profile avatar is stored here too.
+        let live = self.live_hashes()?;
+        for (hash, (files, _)) in crate::retention::object_files(self.objects_dir())? {
+            if !live.contains(&hash) {
+                continue;
+            }
+            // Tone caches are not content.
+            let objects = files
+                .iter()
+                .filter(|path| path.extension().is_none_or(|ext| ext != "tone"));
+            for path in objects {
+                if encrypt_in_place(key, path)? {
+                    report.objects += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Where [`readable_path`] puts decrypted copies for this storage.
+    ///
+    /// [`readable_path`]: ChatStorage::readable_path
+    pub fn decrypted_copies_dir(&self) -> Result<PathBuf> {
+        let scope = blake3::hash(self.base_dir().to_string_lossy().as_bytes()).to_hex();
+        Ok(decrypted_copies_root()?.join(&scope.as_str()[..16]))
+    }
+
+    /// [`ChatStorage::decrypted_copies_dir`], created private to the user.
+    fn decrypted_dir(&self) -> Result<PathBuf> {
+        let dir = self.decrypted_copies_dir()?;
+        if let Some(root) = dir.parent() {
+            create_private_dir(root)?;
+        }
+        create_private_dir(&dir)?;
+        Ok(dir)
+    }
+}
+
+/// Delete the decrypted copies of every storage, e.g. those left behind by
+/// a crash or by a profile that is no longer active.
+pub fn clear_all_decrypted_copies() -> Result<()> {
+    let root = decrypted_copies_root()?;
+    if root.exists() {
+        fs::remove_dir_all(root)?;
+    }
+    Ok(())
+}
+
+/// The per-user parent of the decrypted copies.
+fn decrypted_copies_root() -> Result<PathBuf> {
+    let base = std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::cache_dir)
+        .ok_or(StorageError::NoDataDir)?;
+    Ok(base.join(DECRYPTED_DIR_NAME))
+}
+
+/// Create `dir` readable only by the user, or make an existing one so.
+/// A symlink in its place is refused rather than followed.
+fn create_private_dir(dir: &Path) -> Result<()> {
+    match fs::symlink_metadata(dir) {
+        Ok(metadata) if !metadata.is_dir() => {
+            return Err(StorageError::Encryption(format!(
+                "{} is not a directory",
+                dir.display()
+            )));
+        }
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut builder = fs::DirBuilder::new();
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::DirBuilderExt;
+                builder.mode(0o700);
+            }
+            builder.create(dir)?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+fn file_is_encrypted(path: &Path) -> Result<bool> {
+    use std::io::Read;
+
+    let mut header = [0u8; MAGIC.len()];
+    let mut file = fs::File::open(path)?;
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(is_encrypted(&header)),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Returns whether the file was converted.
+fn encrypt_in_place(key: &StorageKey, path: &Path) -> Result<bool> {
+    if !path.is_file() {
+        return Ok(false);
+    }
+    let data = fs::read(path)?;
+    if is_encrypted(&data) {
+        return Ok(false);
+    }
+    write_atomic(path, encrypt(key, &data)?)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{
+        ChatData, ChatMessage, ChatMetadata, Extraction, OcrRegion, PluginNote, WebSource,
+    };
+
+    fn region(text: &str) -> OcrRegion {
+        OcrRegion {
+            text: text.to_string(),
+            bbox: vec![vec![0, 0], vec![10, 0], vec![10, 10], vec![0, 10]],
+            confidence: Some(0.9),
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn round_trips_and_rejects_the_wrong_key() {
+        let key = StorageKey::generate();
+        let sealed = encrypt(&key, b"secret").unwrap();
+        assert!(is_encrypted(&sealed));
+        assert_eq!(decrypt(&key, &sealed).unwrap(), b"secret");
+        assert!(decrypt(&StorageKey::generate(), &sealed).is_err());
+    }
+
+    #[test]
+    fn encrypted_chats_load_transparently() {
+        let (storage, base_dir) = test_storage();
+        let key = StorageKey::generate();
+        let storage = storage.with_encryption(Some(key.clone()));
+
+        let image = storage.store_image(b"not really a png", None).unwrap();
+        let mut chat = ChatData::new(ChatMetadata::new(
+            "Secret".to_string(),
+            image.hash.clone(),
+            None,
+        ));
+        chat.messages
+            .push(ChatMessage::user("the password is 1234".to_string()));
+        storage.save_chat(&chat).unwrap();
+        storage
+            .save_ocr_data(&chat.metadata.id, "pp-ocr-v5-en", &[region("hunter2")])
+            .unwrap();
+        let chat_id = &chat.metadata.id;
+        storage
+            .save_image_brief(chat_id, "A bank statement")
+            .unwrap();
+        storage
+            .save_rolling_summary(chat_id, "Asked about the balance")
+            .unwrap();
+        let note = PluginNote {
+            plugin_id: "ocr-fixer".to_string(),
+            plugin_name: "OCR fixer".to_string(),
+            context: Some("Account 1234".to_string()),
+            annotations: Vec::new(),
+            created_at: chrono::Utc::now(),
+        };
+        storage.save_plugin_note(chat_id, &note).unwrap();
+        let source = WebSource {
+            url: "https://bank.example/statement".to_string(),
+            title: None,
+            selected_text: None,
+        };
+        storage.save_web_source(chat_id, &source).unwrap();
+        let extraction = Extraction {
+            kind: "receipt".to_string(),
+            data: serde_json::json!({ "total": 42.0 }),
+            model: None,
+            created_at: chrono::Utc::now(),
+        };
+        storage.save_extraction(chat_id, &extraction).unwrap();
+
+        let chat_dir = base_dir.path().join(&chat.metadata.id);
+        for name in SEALED_CHAT_FILES {
+            let raw = fs::read(chat_dir.join(name)).unwrap();
+            assert!(is_encrypted(&raw), "{} is plaintext", name);
+        }
+        assert!(is_encrypted(&fs::read(&image.path).unwrap()));
+
+        let loaded = storage.load_chat(&chat.metadata.id).unwrap();
+        assert_eq!(loaded.messages[0].content, "the password is 1234");
+        let regions = storage
+            .get_ocr_data(&chat.metadata.id, "pp-ocr-v5-en")
+            .unwrap()
+            .unwrap();
+        assert_eq!(regions[0].text, "hunter2");
+        assert_eq!(loaded.image_brief.as_deref(), Some("A bank statement"));
+        assert_eq!(
+            loaded.rolling_summary.as_deref(),
+            Some("Asked about the balance")
+        );
+        assert_eq!(loaded.plugin_notes, [note]);
+        assert_eq!(loaded.web_source, Some(source));
+        assert_eq!(loaded.extractions, [extraction]);
+
+        let readable = storage
+            .readable_path(Path::new(&storage.get_image_path(&image.hash).unwrap()))
+            .unwrap();
+        assert_eq!(fs::read(&readable).unwrap(), b"not really a png");
+        assert!(!readable.starts_with(std::env::temp_dir().join(DECRYPTED_DIR_NAME)));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            for dir in [
+                readable.parent().unwrap(),
+                readable.parent().unwrap().parent().unwrap(),
+            ] {
+                let mode = fs::metadata(dir).unwrap().permissions().mode();
+                assert_eq!(mode & 0o777, 0o700, "{}", dir.display());
+            }
+        }
+        // A stale or tampered copy is replaced, not trusted.
+        fs::write(&readable, b"tampered").unwrap();
+        let again = storage
+            .readable_path(Path::new(&storage.get_image_path(&image.hash).unwrap()))
+            .unwrap();
+        assert_eq!(fs::read(&again).unwrap(), b"not really a png");
+
+        let locked = ChatStorage::with_base_dir(base_dir.path().to_path_buf()).unwrap();
+        assert!(matches!(
+            locked.load_chat(&chat.metadata.id),
+            Err(StorageError::Encryption(_))
+        ));
+
+        storage.clear_decrypted_copies().unwrap();
+    }
+
+    #[test]
+    fn existing_plaintext_chats_are_migrated() {
+        let (plain, _dir) = test_storage();
+        let image = plain.store_image(b"plain image", None).unwrap();
+        // Not referenced by any chat, like a profile avatar.
+        let avatar = plain.store_image(b"avatar", None).unwrap();
+        let mut chat = ChatData::new(ChatMetadata::new("Old".to_string(), image.hash, None));
+        chat.messages.push(ChatMessage::user("hello".to_string()));
+        plain.save_chat(&chat).unwrap();
+
+        let storage = plain.with_encryption(Some(StorageKey::generate()));
+        let report = storage.encrypt_existing().unwrap();
+        assert_eq!(report.chat_files, 3);
+        assert_eq!(report.objects, 1);
+        assert_eq!(
+            storage.encrypt_existing().unwrap(),
+            EncryptionReport::default()
+        );
+        assert_eq!(
+            storage.load_chat(&chat.metadata.id).unwrap().messages[0].content,
+            "hello"
+        );
+        assert_eq!(fs::read(&avatar.path).unwrap(), b"avatar");
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ephemeral captures.
+//!
+//! Once a chat's screenshot has been analyzed it can be dropped from the
+//! CAS. The chat keeps its messages, image description and, optionally,
+//! its OCR text, and is flagged [`ChatMetadata::image_discarded`] so the UI
+//! shows it without an image.
+
+use std::fs;
+
+use crate::error::Result;
+use crate::retention::referenced_hashes;
+use crate::storage::ChatStorage;
+use crate::types::ChatMetadata;
+
+impl ChatStorage {
+    /// Path of the chat's image, or `None` once it has been discarded and
+    /// the chat carries on text-only.
+    pub fn chat_image_path(&self, metadata: &ChatMetadata) -> Result<Option<String>> {
+        if metadata.image_discarded {
+            return Ok(None);
+        }
+        self.get_image_path(&metadata.image_hash).map(Some)
+    }
+
+    /// Delete a chat's image and flag the chat image-less. The CAS object
+    /// stays while another chat, trashed ones included, references it.
+    /// Without `keep_ocr` the OCR frame is deleted too. Returns the updated
+    /// metadata, or `None` if the image was already gone.
+    pub fn discard_chat_image(
+        &self,
+        chat_id: &str,
+        keep_ocr: bool,
+    ) -> Result<Option<ChatMetadata>> {
+        let mut metadata = self.load_chat(chat_id)?.metadata;
+        if metadata.image_discarded {
+            return Ok(None);
+        }
+        let hash = std::mem::take(&mut metadata.image_hash);
+        metadata.image_discarded = true;
+        self.update_chat_metadata(&metadata)?;
+
+        if !keep_ocr {
+            let frame_path = self.chat_dir(chat_id).join("ocr_frame.json");
+            if frame_path.exists() {
+                fs::remove_file(frame_path)?;
+            }
+        }
+
+        if !hash.is_empty() && !self.is_referenced_elsewhere(&hash, chat_id)? {
+            self.remove_object(&hash)?;
+            self.rebuild_attachment_index()?;
+        }
+        Ok(Some(metadata))
+    }
+
+    /// Whether a chat other than `chat_id` mentions the object.
+    fn is_referenced_elsewhere(&self, hash: &str, chat_id: &str) -> Result<bool> {
+        let mut dirs: Vec<_> = self
+            .list_chats()?
+            .into_iter()
+            .filter(|chat| chat.id != chat_id)
+            .map(|chat| self.chat_dir(&chat.id))
+            .collect();
+        let trash_dir = self.trash_dir();
+        if trash_dir.exists() {
+            for entry in fs::read_dir(&trash_dir)? {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() {
+                    dirs.push(entry.path());
+                }
+            }
+        }
+
+        for dir in dirs.into_iter().filter(|dir| dir.is_dir()) {
+            if referenced_hashes(&dir, self.encryption.as_ref())?.contains(hash) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Delete an object's files, tone sidecar included.
+    fn remove_object(&self, hash: &str) -> Result<()> {
+        let Some(prefix) = hash.get(..2) else {
+            return Ok(());
+        };
+        let dir = self.objects_dir().join(prefix);
+        if !dir.exists() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.file_stem().and_then(|stem| stem.to_str()) == Some(hash) {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatData, OcrRegion};
+
+    #[test]
+    fn discarding_keeps_shared_objects_and_optional_ocr() {
+        let (storage, _dir) = test_storage();
+
+        let image = storage.store_image(b"shared", None).unwrap();
+        let first = ChatMetadata::new("First".to_string(), image.hash.clone(), None);
+        let second = ChatMetadata::new("Second".to_string(), image.hash.clone(), None);
+        let region = OcrRegion {
+            text: "Hello".to_string(),
+            bbox: vec![vec![0, 0], vec![1, 0], vec![1, 1], vec![0, 1]],
+            confidence: None,
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        };
+        for metadata in [&first, &second] {
+            storage.save_chat(&ChatData::new(metadata.clone())).unwrap();
+            storage
+                .save_ocr_data(&metadata.id, "pp-ocr-v5-en", std::slice::from_ref(&region))
+                .unwrap();
+        }
+
+        let discarded = storage
+            .discard_chat_image(&first.id, true)
+            .unwrap()
+            .unwrap();
+        assert!(discarded.image_discarded && discarded.image_hash.is_empty());
+        assert!(storage.chat_image_path(&discarded).unwrap().is_none());
+        assert!(storage.chat_image_path(&second).unwrap().is_some());
+        assert!(storage.get_image_path(&image.hash).is_ok());
+        assert!(!storage.get_ocr_frame(&first.id).unwrap().is_empty());
+        assert!(storage
+            .discard_chat_image(&first.id, true)
+            .unwrap()
+            .is_none());
+
+        storage.discard_chat_image(&second.id, false).unwrap();
+        assert!(storage.get_image_path(&image.hash).is_err());
+        assert!(storage.get_ocr_frame(&second.id).unwrap().is_empty());
+        assert!(
+            storage
+                .load_chat(&second.id)
+                .unwrap()
+                .metadata
+                .image_discarded
+        );
+    }
+}
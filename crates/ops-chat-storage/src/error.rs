@@ -36,9 +36,41 @@ pub enum StorageError {
     #[error("Chat not found: {0}")]
     ChatNotFound(String),
 
+    /// Message not in the chat.
+    #[error("Message not found: {0}")]
+    MessageNotFound(String),
+
+    /// Attachment not in the attachment index.
+    #[error("Attachment not found: {0}")]
+    AttachmentNotFound(String),
+
+    /// Artifact or artifact version not found.
+    #[error("Artifact not found: {0}")]
+    ArtifactNotFound(String),
+
+    /// Artifact name that is not a plain file name.
+    #[error("Invalid artifact name: {0:?}")]
+    InvalidArtifactName(String),
+
+    /// Chat archive that cannot be imported.
+    #[error("Invalid chat archive: {0}")]
+    InvalidArchive(String),
+
+    /// ZIP archive error.
+    #[error("ZIP error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    /// Encrypted data without the key, or that does not decrypt.
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
     /// Unsupported OCR model/frame key.
     #[error("Unsupported OCR model id: {0}")]
     InvalidOcrModel(String),
+
+    /// Merge without sources, or with the target among them.
+    #[error("Invalid merge: {0}")]
+    InvalidMerge(String),
 }
 
 /// Result type alias for storage operations.
@@ -0,0 +1,229 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chat archives.
+//!
+//! A chat is collected into a [`ChatArchive`]: the full chat data plus the
+//! CAS images it references (the capture and uploaded image attachments),
+//! base64-encoded, so it stands on its own without the app's storage. It
+//! is written as JSON as is, or as a ZIP holding it as `chat.json` with the
+//! images as plain files beside it. Both can be imported back, see
+//! [`crate::import`]; rendered exports are built from the archive by the
+//! `ops-chat-export` crate.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Seek, Write};
+use std::path::Path;
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::attachments::mime_type_for_extension;
+use crate::error::Result;
+use crate::storage::ChatStorage;
+use crate::types::{ChatAttachmentKind, ChatData};
+
+/// Version of the [`ChatArchive`] layout.
+pub const ARCHIVE_SCHEMA: u32 = 1;
+/// The archive inside a ZIP export.
+pub(crate) const ZIP_CHAT_ENTRY: &str = "chat.json";
+/// Folder of the images inside a ZIP export.
+pub(crate) const ZIP_IMAGES_DIR: &str = "images";
+
+/// A chat with its images, as written by [`ChatStorage::write_chat_archive`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatArchive {
+    pub schema: u32,
+    pub exported_at: DateTime<Utc>,
+    pub chat: ChatData,
+    /// The capture first, then image attachments. Objects missing from
+    /// storage are left out.
+    #[serde(default)]
+    pub images: Vec<ArchivedImage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedImage {
+    pub hash: String,
+    pub mime_type: String,
+    /// Attachment display name; `None` for the capture.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Standard base64. Empty in a ZIP export, where the image is the
+    /// [`ArchivedImage::zip_entry`] file.
+    #[serde(default)]
+    pub data: String,
+}
+
+impl ArchivedImage {
+    pub fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.mime_type, self.data)
+    }
+
+    pub(crate) fn zip_entry(&self) -> String {
+        format!(
+            "{}/{}.{}",
+            ZIP_IMAGES_DIR,
+            self.hash,
+            image_extension(&self.mime_type)
+        )
+    }
+}
+
+/// File extension for an archived image's MIME type.
+pub fn image_extension(mime_type: &str) -> &'static str {
+    match mime_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/webp" => "webp",
+        "image/gif" => "gif",
+        "image/bmp" => "bmp",
+        "image/svg+xml" => "svg",
+        "image/heic" => "heic",
+        "image/avif" => "avif",
+        _ => "bin",
+    }
+}
+
+impl ChatStorage {
+    /// Collect a chat and the images it references.
+    pub fn chat_archive(&self, chat_id: &str) -> Result<ChatArchive> {
+        let chat = self.load_chat(chat_id)?;
+        let mut images = Vec::new();
+        let mut seen = HashSet::new();
+
+        if let Ok(path) = self.get_image_path(&chat.metadata.image_hash) {
+            if let Some(image) = archived_image(self, Path::new(&path), None)? {
+                seen.insert(image.hash.clone());
+                images.push(image);
+            }
+        }
+        for record in chat.attachment_registry.values() {
+            if record.kind != ChatAttachmentKind::ImageUpload {
+                continue;
+            }
+            let path = Path::new(&record.cas_path);
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(image) = archived_image(self, path, Some(&record.display_name))? {
+                if seen.insert(image.hash.clone()) {
+                    images.push(image);
+                }
+            }
+        }
+
+        Ok(ChatArchive {
+            schema: ARCHIVE_SCHEMA,
+            exported_at: Utc::now(),
+            chat,
+            images,
+        })
+    }
+
+    /// Write a chat's archive to `destination` as JSON, creating missing
+    /// parent directories.
+    pub fn write_chat_archive(&self, chat_id: &str, destination: &Path) -> Result<()> {
+        let archive = self.chat_archive(chat_id)?;
+        create_parent_dir(destination)?;
+        fs::write(destination, serde_json::to_string_pretty(&archive)?)?;
+        Ok(())
+    }
+
+    /// Write a chat's archive to `destination` as a ZIP, creating missing
+    /// parent directories.
+    pub fn write_chat_zip(&self, chat_id: &str, destination: &Path) -> Result<()> {
+        let archive = self.chat_archive(chat_id)?;
+        create_parent_dir(destination)?;
+        write_zip(archive, File::create(destination)?)
+    }
+}
+
+fn create_parent_dir(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(())
+}
+
+/// The archive as `chat.json`, with each image moved out of it into its
+/// own stored entry: they are compressed already.
+fn write_zip(mut archive: ChatArchive, writer: impl Write + Seek) -> Result<()> {
+    let mut zip = zip::ZipWriter::new(writer);
+    let stored =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for image in &mut archive.images {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(std::mem::take(&mut image.data))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        zip.start_file(image.zip_entry(), stored)?;
+        zip.write_all(&bytes)?;
+    }
+    zip.start_file(
+        ZIP_CHAT_ENTRY,
+        zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated),
+    )?;
+    zip.write_all(serde_json::to_string_pretty(&archive)?.as_bytes())?;
+    zip.finish()?;
+    Ok(())
+}
+
+/// `None` when the file name is not a CAS hash.
+fn archived_image(
+    storage: &ChatStorage,
+    path: &Path,
+    name: Option<&str>,
+) -> Result<Option<ArchivedImage>> {
+    let Some(hash) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return Ok(None);
+    };
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(None);
+    }
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    Ok(Some(ArchivedImage {
+        hash: hash.to_string(),
+        mime_type: mime_type_for_extension(extension).to_string(),
+        name: name.map(str::to_string),
+        data: base64::engine::general_purpose::STANDARD.encode(storage.read_object(path)?),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatMessage, ChatMetadata};
+
+    #[test]
+    fn archives_hold_the_chat_and_its_images() {
+        let (storage, base_dir) = test_storage();
+
+        let image = storage.store_image(b"capture", None).unwrap();
+        let metadata = ChatMetadata::new("Error: <build>".to_string(), image.hash.clone(), None);
+        let mut chat = ChatData::new(metadata.clone());
+        chat.messages = vec![
+            ChatMessage::user("What does this mean?".to_string()),
+            ChatMessage::assistant("The <build> step failed.".to_string()).with_model("gemini"),
+        ];
+        storage.save_chat(&chat).unwrap();
+
+        let json_path = base_dir.path().join("out").join("chat.json");
+        storage
+            .write_chat_archive(&metadata.id, &json_path)
+            .unwrap();
+        let archive: ChatArchive =
+            serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(archive.schema, ARCHIVE_SCHEMA);
+        assert_eq!(archive.chat.messages.len(), 2);
+        assert_eq!(archive.images.len(), 1);
+        assert_eq!(archive.images[0].hash, image.hash);
+        assert_eq!(archive.images[0].name, None);
+        assert!(archive.images[0]
+            .data_url()
+            .starts_with("data:image/png;base64,"));
+    }
+}
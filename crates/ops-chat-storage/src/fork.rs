@@ -0,0 +1,111 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Forking a chat at a message.
+//!
+//! A fork is a new chat about the same image: it shares the CAS objects
+//! and copies what was derived from the image (OCR, brief, extractions,
+//! plugin notes) along with the messages up to the branch point. The
+//! rolling summary and artifacts may cover later turns, so they stay with
+//! the original.
+
+use crate::error::{Result, StorageError};
+use crate::storage::ChatStorage;
+use crate::types::{ChatData, ChatMetadata};
+
+impl ChatStorage {
+    /// Create a chat from `chat_id`'s messages up to and including the one
+    /// at `from_message_index`. Returns the new chat's metadata.
+    pub fn fork_chat(&self, chat_id: &str, from_message_index: usize) -> Result<ChatMetadata> {
+        let source = self.load_chat(chat_id)?;
+        if from_message_index >= source.messages.len() {
+            return Err(StorageError::MessageNotFound(format!(
+                "#{} in {}",
+                from_message_index, chat_id
+            )));
+        }
+
+        let parent = &source.metadata;
+        let mut metadata = ChatMetadata::new(
+            parent.title.clone(),
+            parent.image_hash.clone(),
+            parent.ocr_lang.clone(),
+        );
+        metadata.image_tone = parent.image_tone.clone();
+        metadata.capture_type = parent.capture_type.clone();
+        metadata.ephemeral = parent.ephemeral;
+        metadata.image_discarded = parent.image_discarded;
+        metadata.device_pixel_ratio = parent.device_pixel_ratio;
+        metadata.parent_chat_id = Some(parent.id.clone());
+        metadata.tags = parent.tags.clone();
+        metadata.folder = parent.folder.clone();
+
+        let mut chat = ChatData::new(metadata);
+        chat.messages = source.messages[..=from_message_index].to_vec();
+        chat.ocr_data = source.ocr_data;
+        chat.imgbb_url = source.imgbb_url;
+        chat.attachment_registry = source.attachment_registry;
+
+        let fork_id = chat.metadata.id.clone();
+        self.save_chat(&chat)?;
+        if let Some(brief) = &source.image_brief {
+            self.save_image_brief(&fork_id, brief)?;
+        }
+        for note in &source.plugin_notes {
+            self.save_plugin_note(&fork_id, note)?;
+        }
+        if let Some(web_source) = &source.web_source {
+            self.save_web_source(&fork_id, web_source)?;
+        }
+        for extraction in &source.extractions {
+            self.save_extraction(&fork_id, extraction)?;
+        }
+        Ok(chat.metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::ChatMessage;
+
+    #[test]
+    fn fork_copies_messages_up_to_the_branch_point() {
+        let (storage, _dir) = test_storage();
+
+        let image = storage.store_image(b"capture", None).unwrap();
+        let metadata = ChatMetadata::new("Receipt".to_string(), image.hash.clone(), None);
+        let parent_id = metadata.id.clone();
+        let mut chat = ChatData::new(metadata);
+        chat.messages = vec![
+            ChatMessage::user("What is this?".to_string()),
+            ChatMessage::assistant("A receipt.".to_string()),
+            ChatMessage::user("Total?".to_string()),
+            ChatMessage::assistant("$12.40".to_string()),
+        ];
+        storage.save_chat(&chat).unwrap();
+        storage.save_image_brief(&parent_id, "A receipt").unwrap();
+        storage
+            .save_rolling_summary(&parent_id, "Asked for the total")
+            .unwrap();
+
+        let fork = storage.fork_chat(&parent_id, 1).unwrap();
+        assert_ne!(fork.id, parent_id);
+        assert_eq!(fork.parent_chat_id.as_deref(), Some(parent_id.as_str()));
+        assert_eq!(fork.image_hash, image.hash);
+
+        let forked = storage.load_chat(&fork.id).unwrap();
+        let contents: Vec<&str> = forked.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["What is this?", "A receipt."]);
+        assert_eq!(forked.image_brief.as_deref(), Some("A receipt"));
+        assert!(forked.rolling_summary.is_none());
+        assert_eq!(storage.load_chat(&parent_id).unwrap().messages.len(), 4);
+        assert_eq!(storage.list_chats().unwrap().len(), 2);
+
+        assert!(matches!(
+            storage.fork_chat(&parent_id, 4),
+            Err(StorageError::MessageNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! CAS garbage collection.
+//!
+//! Deleting a chat leaves its objects behind, as do captures and uploads
+//! that never made it into a chat. A collection pass builds the set of
+//! hashes mentioned by every chat directory, trashed chats included, and
+//! removes the other objects. Objects written in the last hour are kept:
+//! a capture or upload is stored before the chat that references it is
+//! saved.
+
+use std::collections::HashSet;
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::retention::{object_files, referenced_hashes};
+use crate::storage::ChatStorage;
+
+/// Objects younger than this are never collected.
+const MIN_ORPHAN_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcReport {
+    pub dry_run: bool,
+    /// Hashes of the unreferenced objects, removed unless `dry_run`.
+    pub orphans: Vec<String>,
+    /// Bytes the orphans take up, tone sidecars included.
+    pub reclaimable_bytes: u64,
+    /// Objects kept, referenced or too new.
+    pub live_objects: usize,
+}
+
+impl ChatStorage {
+    /// Remove objects no chat references. With `dry_run` nothing is
+    /// removed and the report says what would be.
+    pub fn gc(&self, dry_run: bool) -> Result<GcReport> {
+        self.gc_at(dry_run, SystemTime::now())
+    }
+
+    fn gc_at(&self, dry_run: bool, now: SystemTime) -> Result<GcReport> {
+        let live = self.live_hashes()?;
+        let mut report = GcReport {
+            dry_run,
+            ..Default::default()
+        };
+        let mut removable = Vec::new();
+        for (hash, (files, size)) in object_files(self.objects_dir())? {
+            let newest = files
+                .iter()
+                .filter_map(|path| path.metadata().ok()?.modified().ok())
+                .max();
+            let too_new = newest.is_none_or(|modified| {
+                now.duration_since(modified).unwrap_or_default() < MIN_ORPHAN_AGE
+            });
+            if live.contains(&hash) || too_new {
+                report.live_objects += 1;
+                continue;
+            }
+            report.reclaimable_bytes += size;
+            report.orphans.push(hash);
+            removable.extend(files);
+        }
+        report.orphans.sort();
+
+        if dry_run || removable.is_empty() {
+            return Ok(report);
+        }
+        for path in &removable {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            // Drop the prefix directory once its last object is gone.
+            if let Some(prefix) = path.parent() {
+                let _ = fs::remove_dir(prefix);
+            }
+        }
+        self.rebuild_attachment_index()?;
+        Ok(report)
+    }
+
+    /// Hashes mentioned by any chat directory, live or trashed. Directories
+    /// are scanned rather than the index so a chat missing from it keeps
+    /// its objects.
+    pub(crate) fn live_hashes(&self) -> Result<HashSet<String>> {
+        let mut live = HashSet::new();
+        let mut dirs = vec![self.base_dir().clone()];
+        let trash_dir = self.trash_dir();
+        if trash_dir.is_dir() {
+            dirs.push(trash_dir);
+        }
+        for parent in dirs {
+            for entry in fs::read_dir(&parent)? {
+                let path = entry?.path();
+                if !path.is_dir() || &path == self.objects_dir() || path == self.trash_dir() {
+                    continue;
+                }
+                live.extend(referenced_hashes(&path, self.encryption.as_ref())?);
+            }
+        }
+        Ok(live)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatData, ChatMetadata};
+
+    #[test]
+    fn gc_removes_only_old_unreferenced_objects() {
+        let (storage, _dir) = test_storage();
+
+        let kept = storage.store_image(b"kept", None).unwrap();
+        let deleted = storage.store_image(b"deleted", None).unwrap();
+        let stray = storage.store_file(b"stray", "pdf", None).unwrap();
+        let chat = ChatMetadata::new("Kept".to_string(), kept.hash.clone(), None);
+        storage.save_chat(&ChatData::new(chat)).unwrap();
+        let gone = ChatMetadata::new("Gone".to_string(), deleted.hash.clone(), None);
+        storage.save_chat(&ChatData::new(gone.clone())).unwrap();
+        storage.delete_chat(&gone.id).unwrap();
+
+        let fresh = storage.gc(true).unwrap();
+        assert!(fresh.orphans.is_empty());
+        assert_eq!(fresh.live_objects, 3);
+
+        let later = SystemTime::now() + MIN_ORPHAN_AGE * 2;
+        let preview = storage.gc_at(true, later).unwrap();
+        let mut expected = vec![deleted.hash.clone(), stray.hash.clone()];
+        expected.sort();
+        assert_eq!(preview.orphans, expected);
+        assert!(preview.reclaimable_bytes >= 12);
+        assert!(storage.get_image_path(&deleted.hash).is_ok());
+
+        let report = storage.gc_at(false, later).unwrap();
+        assert_eq!(report.orphans, expected);
+        assert!(storage.get_image_path(&kept.hash).is_ok());
+        assert!(storage.get_image_path(&deleted.hash).is_err());
+        assert!(!std::path::Path::new(&stray.path).exists());
+        assert_eq!(report.live_objects, 1);
+    }
+}
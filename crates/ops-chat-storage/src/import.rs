@@ -0,0 +1,229 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Restoring chats from JSON and ZIP exports.
+//!
+//! Images are stored again, so their hashes are recomputed by this
+//! storage, and the chat is rewritten to point at the new objects. The
+//! chat keeps its ID unless this storage already has a chat with it.
+//! Attachments other than images and artifact contents are not part of an
+//! export and are dropped.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use base64::Engine;
+
+use crate::error::{Result, StorageError};
+use crate::export::{image_extension, ChatArchive, ARCHIVE_SCHEMA, ZIP_CHAT_ENTRY};
+use crate::storage::ChatStorage;
+use crate::types::ChatMetadata;
+
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+impl ChatStorage {
+    /// Import a file written by [`ChatStorage::write_chat_archive`] or
+    /// [`ChatStorage::write_chat_zip`]. Returns the imported chat's metadata.
+    pub fn import_chat(&self, source: &Path) -> Result<ChatMetadata> {
+        let bytes = fs::read(source)?;
+        let archive = if bytes.starts_with(ZIP_MAGIC) {
+            read_zip(&bytes)?
+        } else {
+            serde_json::from_slice(&bytes)?
+        };
+        self.import_chat_archive(archive)
+    }
+
+    pub fn import_chat_archive(&self, archive: ChatArchive) -> Result<ChatMetadata> {
+        if archive.schema > ARCHIVE_SCHEMA {
+            return Err(StorageError::InvalidArchive(format!(
+                "schema {} is newer than this version supports",
+                archive.schema
+            )));
+        }
+        let ChatArchive {
+            mut chat, images, ..
+        } = archive;
+
+        // Archived hash -> stored object, for everything but the capture.
+        let mut attachments = HashMap::new();
+        let mut capture_hash = String::new();
+        for image in &images {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(&image.data)
+                .map_err(|e| {
+                    StorageError::InvalidArchive(format!("image {}: {}", image.hash, e))
+                })?;
+            match &image.name {
+                None => {
+                    capture_hash = self
+                        .store_image(&bytes, chat.metadata.image_tone.clone())?
+                        .hash;
+                }
+                Some(name) => {
+                    let stored = self.store_file_named(
+                        &bytes,
+                        image_extension(&image.mime_type),
+                        None,
+                        Some(name),
+                    )?;
+                    attachments.insert(image.hash.clone(), stored.path);
+                }
+            }
+        }
+
+        if !is_plain_chat_id(&chat.metadata.id) || self.chat_dir(&chat.metadata.id).exists() {
+            chat.metadata.id = ChatMetadata::generate_id(chrono::Utc::now());
+        }
+        chat.metadata.image_hash = capture_hash;
+        let registry = std::mem::take(&mut chat.attachment_registry);
+        for mut record in registry.into_values() {
+            let archived_hash = Path::new(&record.cas_path)
+                .file_stem()
+                .and_then(|stem| stem.to_str());
+            let Some(path) = archived_hash.and_then(|hash| attachments.get(hash)) else {
+                continue;
+            };
+            record.cas_path = path.clone();
+            chat.attachment_registry.insert(path.clone(), record);
+        }
+        chat.artifacts.clear();
+
+        let chat_id = chat.metadata.id.clone();
+        self.save_chat(&chat)?;
+        if let Some(summary) = &chat.rolling_summary {
+            self.save_rolling_summary(&chat_id, summary)?;
+        }
+        if let Some(brief) = &chat.image_brief {
+            self.save_image_brief(&chat_id, brief)?;
+        }
+        for note in &chat.plugin_notes {
+            self.save_plugin_note(&chat_id, note)?;
+        }
+        if let Some(source) = &chat.web_source {
+            self.save_web_source(&chat_id, source)?;
+        }
+        for extraction in &chat.extractions {
+            self.save_extraction(&chat_id, extraction)?;
+        }
+        Ok(chat.metadata)
+    }
+}
+
+/// The archive from `chat.json` with the image entries read back in.
+fn read_zip(bytes: &[u8]) -> Result<ChatArchive> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut archive: ChatArchive = serde_json::from_reader(zip.by_name(ZIP_CHAT_ENTRY)?)?;
+    for image in &mut archive.images {
+        if !image.data.is_empty() {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        zip.by_name(&image.zip_entry())?.read_to_end(&mut bytes)?;
+        image.data = base64::engine::general_purpose::STANDARD.encode(bytes);
+    }
+    Ok(archive)
+}
+
+/// The ID names the chat's directory, so anything that could leave the
+/// storage directory is replaced.
+fn is_plain_chat_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        ChatAttachmentKind, ChatAttachmentRecord, ChatData, ChatMessage, OcrRegion,
+    };
+
+    #[test]
+    fn exports_import_into_another_storage() {
+        let root = tempfile::tempdir().unwrap();
+        let source = ChatStorage::with_base_dir(root.path().join("source")).unwrap();
+        let target = ChatStorage::with_base_dir(root.path().join("target")).unwrap();
+
+        let capture = source.store_image(b"capture", None).unwrap();
+        let upload = source.store_file(b"upload", "png", None).unwrap();
+        let document = source.store_file(b"%PDF", "pdf", None).unwrap();
+        let mut chat = ChatData::new(ChatMetadata::new(
+            "Imported".to_string(),
+            capture.hash.clone(),
+            None,
+        ));
+        chat.messages = vec![
+            ChatMessage::user("Hi".to_string()),
+            ChatMessage::assistant("Hello".to_string()),
+        ];
+        for (stored, kind) in [
+            (&upload, ChatAttachmentKind::ImageUpload),
+            (&document, ChatAttachmentKind::DocumentUpload),
+        ] {
+            chat.attachment_registry.insert(
+                stored.path.clone(),
+                ChatAttachmentRecord {
+                    cas_path: stored.path.clone(),
+                    display_name: "file".to_string(),
+                    kind,
+                    mime_type: "image/png".to_string(),
+                    source_path: None,
+                    provider_file: None,
+                    last_seen_at: chrono::Utc::now(),
+                    last_recalled_at: None,
+                },
+            );
+        }
+        source.save_chat(&chat).unwrap();
+        source
+            .save_image_brief(&chat.metadata.id, "A greeting")
+            .unwrap();
+        let region = OcrRegion {
+            text: "Hi".to_string(),
+            bbox: vec![],
+            confidence: None,
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        };
+        source
+            .save_ocr_data(&chat.metadata.id, "pp-ocr-v5-en", &[region])
+            .unwrap();
+
+        for extension in ["json", "zip"] {
+            let path = root.path().join(format!("chat.{}", extension));
+            if extension == "zip" {
+                source.write_chat_zip(&chat.metadata.id, &path).unwrap();
+            } else {
+                source.write_chat_archive(&chat.metadata.id, &path).unwrap();
+            }
+            let imported = target.import_chat(&path).unwrap();
+
+            let restored = target.load_chat(&imported.id).unwrap();
+            assert_eq!(restored.messages.len(), 2);
+            assert_eq!(restored.image_brief.as_deref(), Some("A greeting"));
+            assert!(!restored.ocr_data.is_empty());
+            assert!(target.get_image_path(&restored.metadata.image_hash).is_ok());
+            let records: Vec<_> = restored.attachment_registry.values().collect();
+            assert_eq!(records.len(), 1);
+            assert!(records[0]
+                .cas_path
+                .starts_with(target.objects_dir().to_str().unwrap()));
+            assert!(Path::new(&records[0].cas_path).exists());
+        }
+        // The second import found the ID taken.
+        assert_eq!(target.list_chats().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn unsafe_ids_are_replaced() {
+        assert!(is_plain_chat_id("20260101-120000-abcd1234"));
+        assert!(!is_plain_chat_id("../escape"));
+        assert!(!is_plain_chat_id(""));
+    }
+}
@@ -26,13 +26,46 @@
 //! storage.save_chat(&chat).unwrap();
 //! ```
 
+pub mod analytics;
+pub mod artifacts;
+pub mod attachments;
+pub mod encryption;
+pub mod ephemeral;
 pub mod error;
+pub mod export;
+pub mod fork;
+pub mod gc;
+pub mod import;
+pub mod merge;
+pub mod messages;
+pub mod metadata;
+pub mod ocr_text;
+pub mod organize;
+pub mod retention;
+pub mod similarity;
+pub mod stats;
 pub mod storage;
+#[cfg(test)]
+mod test_support;
 pub mod types;
 
+pub use analytics::{ChatAnalytics, DailyCount, DailyStorage, DateRange};
+pub use artifacts::{Artifact, ArtifactVersion};
+pub use attachments::{AttachmentFilter, AttachmentInfo, AttachmentKind};
+pub use encryption::{clear_all_decrypted_copies, EncryptionReport, StorageKey};
 pub use error::{Result, StorageError};
-pub use storage::ChatStorage;
+pub use export::{ArchivedImage, ChatArchive, ARCHIVE_SCHEMA};
+pub use gc::GcReport;
+pub use metadata::{strip_image_metadata, without_image_metadata};
+pub use ocr_text::{ocr_text, OcrTextLayout};
+pub use organize::{ChatFilter, TagCount};
+pub use retention::{MaintenanceTask, RetentionAction, RetentionPolicy, RetentionReport};
+pub use similarity::{SimilarCapture, DEFAULT_SIMILARITY_THRESHOLD};
+pub use stats::{ChatStorageStats, StorageStats};
+pub use storage::{is_supported_ocr_model_id, ChatStorage, AUTO_OCR_DISABLED_MODEL_ID};
 pub use types::{
-    AttachmentRegistry, ChatAttachmentKind, ChatAttachmentProviderFile, ChatAttachmentRecord,
-    ChatData, ChatMessage, ChatMetadata, OcrFrame, OcrRegion, StoredImage,
+    AttachmentRegistry, ChatAttachment, ChatAttachmentKind, ChatAttachmentProviderFile,
+    ChatAttachmentRecord, ChatData, ChatMessage, ChatMetadata, DanglingUserTurn, Extraction,
+    MessageRevision, OcrConfidenceSummary, OcrFrame, OcrRegion, PluginAnnotation, PluginNote,
+    StoredImage, WebSource,
 };
@@ -0,0 +1,203 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Merging chats into one.
+//!
+//! Several chats about the same problem can be folded into a target chat:
+//! their messages are interleaved by time, OCR frames and attachments are
+//! unioned, and each source's image becomes an attachment of the target so
+//! it stays referenced. Image briefs, plugin notes and extractions describe
+//! a particular image, so the target keeps its own. The sources go to the
+//! trash, where they can still be restored.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+
+use crate::attachments::mime_type_for_extension;
+use crate::error::{Result, StorageError};
+use crate::storage::ChatStorage;
+use crate::types::{ChatAttachment, ChatMetadata};
+
+impl ChatStorage {
+    /// Merge the chats in `source_ids` into `target_id` and move the sources
+    /// to the trash. Returns the target's updated metadata.
+    pub fn merge_chats(&self, target_id: &str, source_ids: &[String]) -> Result<ChatMetadata> {
+        let mut seen = HashSet::new();
+        let source_ids: Vec<&String> = source_ids.iter().filter(|id| seen.insert(*id)).collect();
+        if source_ids.is_empty() {
+            return Err(StorageError::InvalidMerge("no chats to merge".to_string()));
+        }
+        if source_ids.iter().any(|id| *id == target_id) {
+            return Err(StorageError::InvalidMerge(format!(
+                "{} cannot be merged into itself",
+                target_id
+            )));
+        }
+
+        let mut target = self.load_chat(target_id)?;
+        let sources = source_ids
+            .iter()
+            .map(|id| self.load_chat(id))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut image_paths = Vec::new();
+        for source in sources {
+            let metadata = source.metadata;
+            let mut messages = source.messages;
+            // IDs are only unique within a chat.
+            let ids: HashSet<String> = target.messages.iter().map(|m| m.id.clone()).collect();
+            for message in &mut messages {
+                if ids.contains(&message.id) {
+                    message.id.clear();
+                }
+            }
+            target.messages.extend(messages);
+
+            for (model_id, regions) in source.ocr_data {
+                if regions.is_none() {
+                    continue;
+                }
+                let entry = target.ocr_data.entry(model_id).or_default();
+                if entry.is_none() {
+                    *entry = regions;
+                }
+            }
+            for (key, record) in source.attachment_registry {
+                target.attachment_registry.entry(key).or_insert(record);
+            }
+
+            let target_meta = &mut target.metadata;
+            if !metadata.image_hash.is_empty() && metadata.image_hash != target_meta.image_hash {
+                if let Ok(path) = self.object_path(&metadata.image_hash) {
+                    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+                    let mime = mime_type_for_extension(extension).to_string();
+                    image_paths.push(path.to_string_lossy().into_owned());
+                    target_meta.attachments.push(ChatAttachment {
+                        hash: metadata.image_hash.clone(),
+                        mime,
+                        added_at: metadata.created_at,
+                        label: Some(metadata.title.clone()),
+                    });
+                }
+            }
+            for attachment in metadata.attachments {
+                if let Ok(path) = self.object_path(&attachment.hash) {
+                    image_paths.push(path.to_string_lossy().into_owned());
+                    target_meta.attachments.push(attachment);
+                }
+            }
+            for tag in metadata.tags {
+                if !target_meta
+                    .tags
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(&tag))
+                {
+                    target_meta.tags.push(tag);
+                }
+            }
+            target_meta.is_starred |= metadata.is_starred;
+        }
+
+        let target_meta = &mut target.metadata;
+        let image_hash = target_meta.image_hash.clone();
+        let mut hashes = HashSet::new();
+        target_meta.attachments.retain(|attachment| {
+            attachment.hash != image_hash && hashes.insert(attachment.hash.clone())
+        });
+        // Stable, so messages sent at the same moment keep their order.
+        target.messages.sort_by_key(|message| message.timestamp);
+        target_meta.updated_at = Utc::now();
+
+        self.save_chat(&target)?;
+        self.link_attachments(target_id, image_paths.iter().map(String::as_str))?;
+        let now = Utc::now();
+        for id in source_ids {
+            self.move_to_trash(id, now)?;
+        }
+        Ok(target.metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatData, ChatMessage, OcrRegion};
+    use chrono::Duration;
+
+    #[test]
+    fn merge_interleaves_messages_and_keeps_source_images() {
+        let (storage, _dir) = test_storage();
+        let start = Utc::now() - Duration::hours(1);
+        let save = |title: &str, image: &[u8], contents: &[(&str, i64)]| {
+            let image = storage.store_image(image, None).unwrap();
+            let mut metadata = ChatMetadata::new(title.to_string(), image.hash, None);
+            metadata.tags = vec![title.to_lowercase()];
+            let mut chat = ChatData::new(metadata);
+            chat.messages = contents
+                .iter()
+                .map(|(content, minutes)| {
+                    let mut message = ChatMessage::user(content.to_string());
+                    message.timestamp = start + Duration::minutes(*minutes);
+                    message
+                })
+                .collect();
+            storage.save_chat(&chat).unwrap();
+            chat
+        };
+        let target = save("Invoice", b"invoice", &[("first", 0), ("third", 2)]);
+        let mut source = save("Receipt", b"receipt", &[("second", 1), ("fourth", 3)]);
+        let region = OcrRegion {
+            text: "Total".to_string(),
+            bbox: vec![vec![0, 0], vec![10, 0], vec![10, 5], vec![0, 5]],
+            confidence: None,
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        };
+        source
+            .ocr_data
+            .insert("pp-ocr-v5-en".to_string(), Some(vec![region]));
+        storage.save_chat(&source).unwrap();
+
+        let merged = storage
+            .merge_chats(
+                &target.metadata.id,
+                std::slice::from_ref(&source.metadata.id),
+            )
+            .unwrap();
+        assert_eq!(merged.tags, ["invoice", "receipt"]);
+        assert_eq!(merged.attachments.len(), 1);
+        assert_eq!(merged.attachments[0].hash, source.metadata.image_hash);
+        assert_eq!(merged.attachments[0].label.as_deref(), Some("Receipt"));
+
+        let chat = storage.load_chat(&target.metadata.id).unwrap();
+        let contents: Vec<&str> = chat.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["first", "second", "third", "fourth"]);
+        assert!(
+            matches!(chat.ocr_data.get("pp-ocr-v5-en"), Some(Some(regions)) if regions.len() == 1)
+        );
+
+        let ids: Vec<String> = storage
+            .list_chats()
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, [target.metadata.id.as_str()]);
+        storage.restore_trashed_chat(&source.metadata.id).unwrap();
+
+        assert!(matches!(
+            storage.merge_chats(
+                &target.metadata.id,
+                std::slice::from_ref(&target.metadata.id)
+            ),
+            Err(StorageError::InvalidMerge(_))
+        ));
+        assert!(matches!(
+            storage.merge_chats(&target.metadata.id, &[]),
+            Err(StorageError::InvalidMerge(_))
+        ));
+    }
+}
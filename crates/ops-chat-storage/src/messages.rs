@@ -0,0 +1,137 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Editing and deleting single messages.
+//!
+//! An edit keeps the replaced content as a revision of the message, so the
+//! frontend can page through earlier versions. Editing a user turn to
+//! regenerate from it also drops the replies after it; they are kept with
+//! the revision they answered rather than lost.
+
+use chrono::Utc;
+
+use crate::error::{Result, StorageError};
+use crate::storage::ChatStorage;
+use crate::types::{ChatMessage, MessageRevision};
+
+impl ChatStorage {
+    /// Replace a message's content and return the edited message. `model`
+    /// is the model that wrote a regenerated reply. With `truncate` the
+    /// messages after it are dropped, for regenerating from this point.
+    pub fn edit_message(
+        &self,
+        chat_id: &str,
+        message_id: &str,
+        content: String,
+        model: Option<String>,
+        truncate: bool,
+    ) -> Result<ChatMessage> {
+        let (mut messages, index) = self.find_message(chat_id, message_id)?;
+        let following = if truncate {
+            messages.split_off(index + 1)
+        } else {
+            Vec::new()
+        };
+
+        let message = &mut messages[index];
+        let now = Utc::now();
+        let previous = std::mem::replace(&mut message.content, content);
+        message.revisions.push(MessageRevision {
+            content: previous,
+            timestamp: message.timestamp,
+            model: std::mem::replace(&mut message.model, model),
+            replaced_at: now,
+            following,
+        });
+        message.timestamp = now;
+        let edited = message.clone();
+
+        self.write_messages(&self.chat_dir(chat_id), &messages)?;
+        self.touch_chat(chat_id)?;
+        Ok(edited)
+    }
+
+    /// Remove one message. The messages around it are kept.
+    pub fn delete_message(&self, chat_id: &str, message_id: &str) -> Result<()> {
+        let (mut messages, index) = self.find_message(chat_id, message_id)?;
+        messages.remove(index);
+        self.write_messages(&self.chat_dir(chat_id), &messages)?;
+        self.touch_chat(chat_id)
+    }
+
+    /// Earlier versions of a message, oldest first. The current version is
+    /// the message itself.
+    pub fn get_message_history(
+        &self,
+        chat_id: &str,
+        message_id: &str,
+    ) -> Result<Vec<MessageRevision>> {
+        let (mut messages, index) = self.find_message(chat_id, message_id)?;
+        Ok(messages.swap_remove(index).revisions)
+    }
+
+    /// The chat's messages and the position of `message_id` in them.
+    fn find_message(&self, chat_id: &str, message_id: &str) -> Result<(Vec<ChatMessage>, usize)> {
+        let chat_dir = self.chat_dir(chat_id);
+        if !chat_dir.exists() {
+            return Err(StorageError::ChatNotFound(chat_id.to_string()));
+        }
+        let messages = self.read_messages(&chat_dir)?;
+        let index = messages
+            .iter()
+            .position(|message| message.id == message_id)
+            .ok_or_else(|| StorageError::MessageNotFound(message_id.to_string()))?;
+        Ok((messages, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::{ChatData, ChatMetadata};
+
+    #[test]
+    fn editing_a_user_turn_keeps_the_old_version_and_its_replies() {
+        let (storage, _dir) = test_storage();
+
+        let metadata = ChatMetadata::new("Edits".to_string(), "0".repeat(64), None);
+        let chat_id = metadata.id.clone();
+        let mut chat = ChatData::new(metadata);
+        chat.messages = vec![
+            ChatMessage::user("What is this?".to_string()),
+            ChatMessage::assistant("A receipt.".to_string()).with_model("gemini-2.5-flash"),
+            ChatMessage::user("Total?".to_string()),
+        ];
+        storage.save_chat(&chat).unwrap();
+        let ids: Vec<String> = chat.messages.iter().map(|m| m.id.clone()).collect();
+
+        let edited = storage
+            .edit_message(
+                &chat_id,
+                &ids[0],
+                "What is this receipt for?".to_string(),
+                None,
+                true,
+            )
+            .unwrap();
+        assert_eq!(edited.id, ids[0]);
+
+        let loaded = storage.load_chat(&chat_id).unwrap();
+        assert_eq!(loaded.messages.len(), 1);
+        assert_eq!(loaded.messages[0].content, "What is this receipt for?");
+
+        let history = storage.get_message_history(&chat_id, &ids[0]).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].content, "What is this?");
+        let dropped: Vec<&str> = history[0].following.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(dropped, [ids[1].as_str(), ids[2].as_str()]);
+
+        storage.delete_message(&chat_id, &ids[0]).unwrap();
+        assert!(storage.load_chat(&chat_id).unwrap().messages.is_empty());
+        assert!(matches!(
+            storage.delete_message(&chat_id, &ids[0]),
+            Err(StorageError::MessageNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,360 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lossless removal of image metadata (EXIF, XMP, IPTC, text chunks).
+//!
+//! Dropped photos can carry GPS coordinates, device names and capture
+//! times. Metadata segments are cut out without re-encoding, so pixels and
+//! color profiles are unchanged. A JPEG's EXIF orientation is kept in a
+//! minimal EXIF segment of its own, so photos still display upright. JPEG,
+//! PNG and WebP are handled; other data is returned as is.
+
+use std::borrow::Cow;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Header of an EXIF APP1 segment's data.
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+/// TIFF tag of the EXIF orientation.
+const ORIENTATION_TAG: u16 = 0x0112;
+
+/// PNG chunks that only describe the image.
+const PNG_METADATA_CHUNKS: &[&[u8; 4]] = &[b"eXIf", b"tEXt", b"zTXt", b"iTXt", b"tIME"];
+
+/// `bytes` without metadata. Borrowed when there was nothing to strip or
+/// the format is unknown or malformed.
+pub fn strip_image_metadata(bytes: &[u8]) -> Cow<'_, [u8]> {
+    let stripped = if bytes.starts_with(&[0xFF, 0xD8]) {
+        strip_jpeg(bytes)
+    } else if bytes.starts_with(PNG_SIGNATURE) {
+        strip_png(bytes)
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        strip_webp(bytes)
+    } else {
+        None
+    };
+    match stripped {
+        Some(stripped) => Cow::Owned(stripped),
+        None => Cow::Borrowed(bytes),
+    }
+}
+
+/// Owned variant of [`strip_image_metadata`] that avoids a copy when
+/// nothing is stripped.
+pub fn without_image_metadata(bytes: Vec<u8>) -> Vec<u8> {
+    match strip_image_metadata(&bytes) {
+        Cow::Owned(stripped) => stripped,
+        Cow::Borrowed(_) => bytes,
+    }
+}
+
+/// Drops APP1 (EXIF, XMP), APP13 (IPTC) and comment segments before the
+/// scan data. An EXIF orientation other than upright is written back as
+/// the only tag of a new EXIF segment.
+fn strip_jpeg(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&bytes[..2]);
+    let mut pos = 2;
+    let mut stripped = false;
+    let mut kept_orientation = false;
+
+    loop {
+        if pos + 2 > bytes.len() || bytes[pos] != 0xFF {
+            return None;
+        }
+        let marker = bytes[pos + 1];
+        match marker {
+            // Fill byte before a marker.
+            0xFF => {
+                pos += 1;
+                continue;
+            }
+            // Start of scan or end of image: the rest is image data.
+            0xDA | 0xD9 => {
+                out.extend_from_slice(&bytes[pos..]);
+                break;
+            }
+            // Markers without a length.
+            0x01 | 0xD0..=0xD7 => {
+                out.extend_from_slice(&bytes[pos..pos + 2]);
+                pos += 2;
+                continue;
+            }
+            _ => {}
+        }
+
+        if pos + 4 > bytes.len() {
+            return None;
+        }
+        let length = usize::from(u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]));
+        let end = pos + 2 + length;
+        if length < 2 || end > bytes.len() {
+            return None;
+        }
+        let segment = &bytes[pos..end];
+        if marker == 0xE1 && !kept_orientation {
+            if let Some(exif) = exif_orientation(&segment[4..]).map(orientation_segment) {
+                kept_orientation = true;
+                stripped |= exif != segment;
+                out.extend_from_slice(&exif);
+                pos = end;
+                continue;
+            }
+        }
+        if matches!(marker, 0xE1 | 0xED | 0xFE) {
+            stripped = true;
+        } else {
+            out.extend_from_slice(segment);
+        }
+        pos = end;
+    }
+
+    stripped.then_some(out)
+}
+
+/// The orientation in EXIF segment data, when it is not upright.
+fn exif_orientation(data: &[u8]) -> Option<(bool, u16)> {
+    let tiff = data.strip_prefix(EXIF_HEADER)?;
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let u16_at = |at: usize| -> Option<u16> {
+        let raw = tiff.get(at..at.checked_add(2)?)?.try_into().ok()?;
+        Some(if little_endian {
+            u16::from_le_bytes(raw)
+        } else {
+            u16::from_be_bytes(raw)
+        })
+    };
+    let u32_at = |at: usize| -> Option<u32> {
+        let raw = tiff.get(at..at.checked_add(4)?)?.try_into().ok()?;
+        Some(if little_endian {
+            u32::from_le_bytes(raw)
+        } else {
+            u32::from_be_bytes(raw)
+        })
+    };
+    if u16_at(2)? != 42 {
+        return None;
+    }
+
+    let ifd = usize::try_from(u32_at(4)?).ok()?;
+    for index in 0..usize::from(u16_at(ifd)?) {
+        let entry = ifd + 2 + index * 12;
+        // A single SHORT, stored in the entry itself.
+        if u16_at(entry)? == ORIENTATION_TAG && u16_at(entry + 2)? == 3 {
+            let orientation = u16_at(entry + 8)?;
+            return (2..=8)
+                .contains(&orientation)
+                .then_some((little_endian, orientation));
+        }
+    }
+    None
+}
+
+/// An APP1 segment whose EXIF holds only `orientation`.
+fn orientation_segment((little_endian, orientation): (bool, u16)) -> Vec<u8> {
+    let u16_bytes = |value: u16| {
+        if little_endian {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        }
+    };
+    let u32_bytes = |value: u32| {
+        if little_endian {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        }
+    };
+
+    let mut tiff = Vec::with_capacity(26);
+    tiff.extend_from_slice(if little_endian { b"II" } else { b"MM" });
+    tiff.extend_from_slice(&u16_bytes(42));
+    // IFD0 right after the header, with one entry and no next IFD.
+    tiff.extend_from_slice(&u32_bytes(8));
+    tiff.extend_from_slice(&u16_bytes(1));
+    tiff.extend_from_slice(&u16_bytes(ORIENTATION_TAG));
+    tiff.extend_from_slice(&u16_bytes(3));
+    tiff.extend_from_slice(&u32_bytes(1));
+    tiff.extend_from_slice(&u16_bytes(orientation));
+    tiff.extend_from_slice(&[0, 0]);
+    tiff.extend_from_slice(&u32_bytes(0));
+
+    let length = (2 + EXIF_HEADER.len() + tiff.len()) as u16;
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&length.to_be_bytes());
+    segment.extend_from_slice(EXIF_HEADER);
+    segment.extend_from_slice(&tiff);
+    segment
+}
+
+/// Drops EXIF, text and timestamp chunks.
+fn strip_png(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(PNG_SIGNATURE);
+    let mut pos = PNG_SIGNATURE.len();
+    let mut stripped = false;
+
+    while pos < bytes.len() {
+        if pos + 8 > bytes.len() {
+            return None;
+        }
+        let length = u32::from_be_bytes(bytes[pos..pos + 4].try_into().ok()?) as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        // Length, type, data and CRC.
+        let end = pos.checked_add(12)?.checked_add(length)?;
+        if end > bytes.len() {
+            return None;
+        }
+        if PNG_METADATA_CHUNKS.iter().any(|chunk| &chunk[..] == kind) {
+            stripped = true;
+        } else {
+            out.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+        if kind == b"IEND" {
+            break;
+        }
+    }
+
+    stripped.then_some(out)
+}
+
+/// Drops `EXIF` and `XMP ` chunks and clears their flags in `VP8X`.
+fn strip_webp(bytes: &[u8]) -> Option<Vec<u8>> {
+    const EXIF_FLAG: u8 = 0x08;
+    const XMP_FLAG: u8 = 0x04;
+
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&bytes[..12]);
+    let mut pos = 12;
+    let mut stripped = false;
+
+    while pos + 8 <= bytes.len() {
+        let kind = &bytes[pos..pos + 4];
+        let length = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().ok()?) as usize;
+        // Chunks are padded to an even size.
+        let end = pos
+            .checked_add(8)?
+            .checked_add(length)?
+            .checked_add(length % 2)?
+            .min(bytes.len());
+        match kind {
+            b"EXIF" | b"XMP " => stripped = true,
+            b"VP8X" if length >= 1 => {
+                let start = out.len();
+                out.extend_from_slice(&bytes[pos..end]);
+                out[start + 8] &= !(EXIF_FLAG | XMP_FLAG);
+            }
+            _ => out.extend_from_slice(&bytes[pos..end]),
+        }
+        pos = end;
+    }
+
+    if !stripped {
+        return None;
+    }
+    let riff_size = u32::try_from(out.len() - 8).ok()?;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_image_metadata;
+
+    fn png_chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        // The CRC is copied, not checked.
+        chunk.extend_from_slice(&[0; 4]);
+        chunk
+    }
+
+    #[test]
+    fn jpeg_exif_and_comments_are_removed() {
+        let jfif = [0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46];
+        let exif = [0xFF, 0xE1, 0x00, 0x06, b'E', b'x', b'i', b'f'];
+        let comment = [0xFF, 0xFE, 0x00, 0x03, b'x'];
+        let scan = [0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9];
+        let jpeg = [&[0xFF, 0xD8][..], &jfif, &exif, &comment, &scan].concat();
+
+        let stripped = strip_image_metadata(&jpeg);
+        assert_eq!(
+            stripped.as_ref(),
+            [&[0xFF, 0xD8][..], &jfif, &scan].concat().as_slice()
+        );
+        // Nothing left to strip.
+        assert!(matches!(
+            strip_image_metadata(&stripped),
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn jpeg_orientation_survives_stripping() {
+        // Big-endian TIFF with Make = "Sony" and Orientation = 6 (90° CW).
+        let mut tiff = b"MM\0\x2a\0\0\0\x08\0\x02".to_vec();
+        tiff.extend_from_slice(&[0x01, 0x0F, 0, 2, 0, 0, 0, 4]);
+        tiff.extend_from_slice(b"Sony");
+        tiff.extend_from_slice(&[0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0]);
+        tiff.extend_from_slice(&[0, 0, 0, 0]);
+        let data = [super::EXIF_HEADER, &tiff].concat();
+        let mut exif = vec![0xFF, 0xE1];
+        exif.extend_from_slice(&((data.len() + 2) as u16).to_be_bytes());
+        exif.extend_from_slice(&data);
+        let scan = [0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9];
+        let jpeg = [&[0xFF, 0xD8][..], &exif, &scan].concat();
+
+        let stripped = strip_image_metadata(&jpeg);
+        assert!(!stripped.windows(4).any(|window| window == b"Sony"));
+        assert_eq!(
+            super::exif_orientation(&stripped[6..stripped.len() - scan.len()]),
+            Some((false, 6))
+        );
+        assert!(stripped.ends_with(&scan));
+        assert!(matches!(
+            strip_image_metadata(&stripped),
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn png_text_chunks_are_removed() {
+        let ihdr = png_chunk(b"IHDR", &[0; 13]);
+        let idat = png_chunk(b"IDAT", &[1, 2, 3]);
+        let iend = png_chunk(b"IEND", &[]);
+        let png = [
+            super::PNG_SIGNATURE,
+            &ihdr,
+            &png_chunk(b"tEXt", b"Author\0me"),
+            &png_chunk(b"eXIf", &[0; 8]),
+            &idat,
+            &iend,
+        ]
+        .concat();
+
+        let stripped = strip_image_metadata(&png);
+        assert_eq!(
+            stripped.as_ref(),
+            [super::PNG_SIGNATURE, &ihdr, &idat, &iend]
+                .concat()
+                .as_slice()
+        );
+    }
+
+    #[test]
+    fn unknown_and_malformed_data_is_untouched() {
+        assert!(matches!(
+            strip_image_metadata(b"GIF89a..."),
+            std::borrow::Cow::Borrowed(_)
+        ));
+        let truncated = [0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x40];
+        assert_eq!(strip_image_metadata(&truncated).as_ref(), &truncated);
+    }
+}
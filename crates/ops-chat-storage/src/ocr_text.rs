@@ -0,0 +1,408 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Assembly of stored OCR regions into copyable text.
+//!
+//! The OCR engine returns boxes in detection order, which is not reading
+//! order for multi-column or slightly skewed text. Regions are grouped into
+//! visual lines by clustering their vertical centers, then ordered left to
+//! right within each line.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::storage::ChatStorage;
+use crate::types::OcrRegion;
+
+/// Vertical gap, in line heights, that starts a new paragraph.
+const PARAGRAPH_GAP: f64 = 0.8;
+/// Cap on the spaces inserted for a horizontal gap or indent.
+const MAX_GAP_SPACES: usize = 24;
+
+/// How [`ocr_text`] lays out the regions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrTextLayout {
+    /// Reflowed paragraphs: a line's regions joined by spaces, lines of a
+    /// paragraph joined by spaces, paragraphs by a blank line.
+    #[default]
+    Plain,
+    /// One output line per visual line, with indentation and wide gaps
+    /// kept as spaces (code, aligned columns).
+    Lines,
+    /// Paragraphs of visual lines, with aligned rows turned into Markdown
+    /// tables and formulas as `$$...$$`.
+    Markdown,
+}
+
+impl ChatStorage {
+    /// Text of a chat's OCR scan for `model_id`, laid out as `layout`.
+    /// `None` if that model has not scanned the image.
+    pub fn get_ocr_text(
+        &self,
+        chat_id: &str,
+        model_id: &str,
+        layout: OcrTextLayout,
+    ) -> Result<Option<String>> {
+        Ok(self
+            .get_ocr_data(chat_id, model_id)?
+            .map(|regions| ocr_text(&regions, layout)))
+    }
+}
+
+/// `regions` as text in reading order.
+pub fn ocr_text(regions: &[OcrRegion], layout: OcrTextLayout) -> String {
+    let lines = group_lines(regions, layout);
+    match layout {
+        OcrTextLayout::Plain => plain_text(&lines),
+        OcrTextLayout::Lines => spaced_lines(&lines),
+        OcrTextLayout::Markdown => markdown_text(&lines),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    left: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+}
+
+impl Bounds {
+    fn of(bbox: &[Vec<i32>]) -> Option<Self> {
+        let mut points = bbox.iter().filter(|point| point.len() >= 2);
+        let first = points.next()?;
+        let (x, y) = (f64::from(first[0]), f64::from(first[1]));
+        let mut bounds = Bounds {
+            left: x,
+            top: y,
+            right: x,
+            bottom: y,
+        };
+        for point in points {
+            let (x, y) = (f64::from(point[0]), f64::from(point[1]));
+            bounds.left = bounds.left.min(x);
+            bounds.top = bounds.top.min(y);
+            bounds.right = bounds.right.max(x);
+            bounds.bottom = bounds.bottom.max(y);
+        }
+        Some(bounds)
+    }
+
+    fn height(&self) -> f64 {
+        (self.bottom - self.top).max(1.0)
+    }
+
+    fn center_y(&self) -> f64 {
+        (self.top + self.bottom) / 2.0
+    }
+
+    fn overlaps_x(&self, other: &Bounds) -> bool {
+        self.left <= other.right && other.left <= self.right
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Cell {
+    text: String,
+    bounds: Bounds,
+}
+
+impl Cell {
+    fn char_width(&self) -> f64 {
+        let chars = self.text.chars().count().max(1) as f64;
+        ((self.bounds.right - self.bounds.left) / chars).max(1.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Line {
+    cells: Vec<Cell>,
+    bounds: Bounds,
+}
+
+impl Line {
+    fn joined(&self) -> String {
+        self.cells
+            .iter()
+            .map(|cell| cell.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Clusters regions into lines: a region joins the current line when its
+/// vertical center falls inside the line's span. Regions without a box
+/// follow as lines of their own, in stored order.
+fn group_lines(regions: &[OcrRegion], layout: OcrTextLayout) -> Vec<Line> {
+    let mut placed = Vec::new();
+    let mut unplaced = Vec::new();
+    for region in regions {
+        let text = match layout {
+            OcrTextLayout::Markdown => region.export_text(),
+            _ => region.text.clone(),
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            continue;
+        }
+        match Bounds::of(&region.bbox) {
+            Some(bounds) => placed.push(Cell { text, bounds }),
+            None => unplaced.push(text),
+        }
+    }
+    placed.sort_by(|a, b| a.bounds.center_y().total_cmp(&b.bounds.center_y()));
+
+    let mut lines: Vec<Line> = Vec::new();
+    for cell in placed {
+        match lines.last_mut() {
+            Some(line)
+                if cell.bounds.center_y() >= line.bounds.top
+                    && cell.bounds.center_y() <= line.bounds.bottom =>
+            {
+                line.bounds.left = line.bounds.left.min(cell.bounds.left);
+                line.bounds.right = line.bounds.right.max(cell.bounds.right);
+                line.cells.push(cell);
+            }
+            _ => lines.push(Line {
+                bounds: cell.bounds,
+                cells: vec![cell],
+            }),
+        }
+    }
+    for line in &mut lines {
+        line.cells
+            .sort_by(|a, b| a.bounds.left.total_cmp(&b.bounds.left));
+        line.bounds.top = line
+            .cells
+            .iter()
+            .map(|c| c.bounds.top)
+            .fold(f64::MAX, f64::min);
+        line.bounds.bottom = line
+            .cells
+            .iter()
+            .map(|c| c.bounds.bottom)
+            .fold(f64::MIN, f64::max);
+    }
+
+    let left = lines
+        .iter()
+        .map(|line| line.bounds.left)
+        .min_by(f64::total_cmp)
+        .unwrap_or(0.0);
+    let (bottom, height) = lines
+        .last()
+        .map(|line| (line.bounds.bottom, line.bounds.height()))
+        .unwrap_or((0.0, 1.0));
+    for (index, text) in unplaced.into_iter().enumerate() {
+        // Stack them below the rest, a paragraph apart.
+        let top = bottom + height * (2.0 * index as f64 + 2.0);
+        let bounds = Bounds {
+            left,
+            top,
+            right: left,
+            bottom: top + height,
+        };
+        lines.push(Line {
+            cells: vec![Cell { text, bounds }],
+            bounds,
+        });
+    }
+    lines
+}
+
+fn starts_paragraph(previous: &Line, line: &Line) -> bool {
+    let gap = line.bounds.top - previous.bounds.bottom;
+    let height = (previous.bounds.height() + line.bounds.height()) / 2.0;
+    gap > height * PARAGRAPH_GAP
+}
+
+fn plain_text(lines: &[Line]) -> String {
+    let mut text = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            let separator = if starts_paragraph(&lines[index - 1], line) {
+                "\n\n"
+            } else {
+                " "
+            };
+            text.push_str(separator);
+        }
+        text.push_str(&line.joined());
+    }
+    text
+}
+
+fn spaced_lines(lines: &[Line]) -> String {
+    let Some(margin) = lines
+        .iter()
+        .map(|line| line.bounds.left)
+        .min_by(f64::total_cmp)
+    else {
+        return String::new();
+    };
+    let gap_spaces = |gap: f64, char_width: f64| {
+        ((gap / char_width).round().max(0.0) as usize).min(MAX_GAP_SPACES)
+    };
+
+    let mut text = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            text.push('\n');
+            if starts_paragraph(&lines[index - 1], line) {
+                text.push('\n');
+            }
+        }
+        let first = &line.cells[0];
+        text.push_str(&" ".repeat(gap_spaces(first.bounds.left - margin, first.char_width())));
+        text.push_str(&first.text);
+        for pair in line.cells.windows(2) {
+            let gap = pair[1].bounds.left - pair[0].bounds.right;
+            let spaces = gap_spaces(gap, pair[0].char_width()).max(1);
+            text.push_str(&" ".repeat(spaces));
+            text.push_str(&pair[1].text);
+        }
+    }
+    text
+}
+
+/// Whether `line` has the same columns as the table's first row.
+fn matches_columns(header: &Line, line: &Line) -> bool {
+    line.cells.len() == header.cells.len()
+        && header
+            .cells
+            .iter()
+            .zip(&line.cells)
+            .all(|(column, cell)| column.bounds.overlaps_x(&cell.bounds))
+}
+
+fn markdown_text(lines: &[Line]) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = &lines[index];
+        let table_len = if line.cells.len() >= 2 {
+            1 + lines[index + 1..]
+                .iter()
+                .take_while(|next| matches_columns(line, next))
+                .count()
+        } else {
+            1
+        };
+
+        if table_len >= 2 {
+            if !paragraph.is_empty() {
+                blocks.push(paragraph.join("\n"));
+                paragraph.clear();
+            }
+            blocks.push(markdown_table(&lines[index..index + table_len]));
+            index += table_len;
+            continue;
+        }
+
+        if index > 0 && !paragraph.is_empty() && starts_paragraph(&lines[index - 1], line) {
+            blocks.push(paragraph.join("\n"));
+            paragraph.clear();
+        }
+        paragraph.push(line.joined());
+        index += 1;
+    }
+    if !paragraph.is_empty() {
+        blocks.push(paragraph.join("\n"));
+    }
+    blocks.join("\n\n")
+}
+
+fn markdown_table(rows: &[Line]) -> String {
+    let row = |line: &Line| {
+        let cells = line
+            .cells
+            .iter()
+            .map(|cell| cell.text.replace('|', "\\|"))
+            .collect::<Vec<_>>();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut table = vec![row(&rows[0])];
+    table.push(format!("|{}", " --- |".repeat(rows[0].cells.len())));
+    table.extend(rows[1..].iter().map(row));
+    table.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(text: &str, x: i32, y: i32, w: i32, h: i32) -> OcrRegion {
+        OcrRegion {
+            text: text.to_string(),
+            bbox: vec![
+                vec![x, y],
+                vec![x + w, y],
+                vec![x + w, y + h],
+                vec![x, y + h],
+            ],
+            confidence: None,
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn regions_are_ordered_into_lines_and_paragraphs() {
+        // Detection order is scrambled and the second word sits 3px lower.
+        let regions = vec![
+            region("world", 60, 13, 50, 20),
+            region("again", 0, 40, 50, 20),
+            region("Hello", 0, 10, 50, 20),
+            region("New paragraph", 0, 100, 130, 20),
+        ];
+        assert_eq!(
+            ocr_text(&regions, OcrTextLayout::Plain),
+            "Hello world again\n\nNew paragraph"
+        );
+    }
+
+    #[test]
+    fn lines_layout_keeps_indentation_and_gaps() {
+        let regions = vec![
+            region("fn", 0, 0, 20, 20),
+            region("main()", 30, 0, 60, 20),
+            region("body", 40, 25, 40, 20),
+            region("x", 0, 50, 10, 20),
+            region("1", 100, 50, 10, 20),
+        ];
+        assert_eq!(
+            ocr_text(&regions, OcrTextLayout::Lines),
+            "fn main()\n    body\nx         1"
+        );
+    }
+
+    #[test]
+    fn markdown_layout_builds_tables_from_aligned_rows() {
+        let mut formula = region("E=mc2", 0, 200, 50, 20);
+        formula.latex = Some("E = mc^2".to_string());
+        let regions = vec![
+            region("Results", 0, 0, 70, 20),
+            region("Name", 0, 50, 40, 20),
+            region("Score", 200, 50, 50, 20),
+            region("Ada", 0, 75, 30, 20),
+            region("9|10", 205, 75, 40, 20),
+            formula,
+        ];
+        assert_eq!(
+            ocr_text(&regions, OcrTextLayout::Markdown),
+            "Results\n\n| Name | Score |\n| --- | --- |\n| Ada | 9\\|10 |\n\n$$E = mc^2$$"
+        );
+    }
+
+    #[test]
+    fn regions_without_boxes_come_last() {
+        let mut loose = region("loose", 0, 0, 0, 0);
+        loose.bbox.clear();
+        let regions = vec![loose, region("boxed", 0, 0, 50, 20)];
+        assert_eq!(ocr_text(&regions, OcrTextLayout::Plain), "boxed\n\nloose");
+        assert_eq!(ocr_text(&[], OcrTextLayout::Lines), "");
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tags and folders for chats.
+//!
+//! Both live on [`ChatMetadata`] and so in the chat index, which lets
+//! [`ChatStorage::list_chats_filtered`] narrow hundreds of chats without
+//! opening their directories. Tags compare case-insensitively and keep
+//! the spelling they were first given; a folder is a `/`-separated path.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::DateRange;
+use crate::error::Result;
+use crate::storage::ChatStorage;
+use crate::types::ChatMetadata;
+
+/// Criteria for [`ChatStorage::list_chats_filtered`]. Empty fields match
+/// everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChatFilter {
+    /// Chats with this tag, in any letter case.
+    pub tag: Option<String>,
+    /// Chats directly in this folder. An empty string matches chats that
+    /// are in no folder.
+    pub folder: Option<String>,
+    pub pinned: Option<bool>,
+    pub starred: Option<bool>,
+    /// Chats created in this window.
+    pub created: DateRange,
+}
+
+impl ChatFilter {
+    pub fn matches(&self, metadata: &ChatMetadata) -> bool {
+        let tag_ok = !self.tag.as_deref().is_some_and(|tag| {
+            !metadata
+                .tags
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(tag.trim()))
+        });
+        let folder_ok = !self
+            .folder
+            .as_deref()
+            .is_some_and(|folder| metadata.folder != normalize_folder(folder));
+        let pinned_ok = !self
+            .pinned
+            .is_some_and(|pinned| metadata.is_pinned != pinned);
+        let starred_ok = !self
+            .starred
+            .is_some_and(|starred| metadata.is_starred != starred);
+        tag_ok && folder_ok && pinned_ok && starred_ok && self.created.contains(metadata.created_at)
+    }
+}
+
+/// A tag and how many chats carry it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+impl ChatStorage {
+    /// Chats from the index that match `filter`, most recently updated first.
+    pub fn list_chats_filtered(&self, filter: &ChatFilter) -> Result<Vec<ChatMetadata>> {
+        Ok(self
+            .list_chats()?
+            .into_iter()
+            .filter(|metadata| filter.matches(metadata))
+            .collect())
+    }
+
+    /// Replace a chat's tags. Blank tags and repeats are dropped.
+    pub fn set_chat_tags(&self, chat_id: &str, tags: &[String]) -> Result<ChatMetadata> {
+        let tags = normalize_tags(tags);
+        self.modify_metadata(chat_id, |metadata| {
+            metadata.tags = tags;
+            Ok(())
+        })
+    }
+
+    /// Every tag in use, alphabetically. Spellings that differ only in
+    /// case count as one tag, shown the way it was first seen.
+    pub fn list_tags(&self) -> Result<Vec<TagCount>> {
+        let mut tags: BTreeMap<String, TagCount> = BTreeMap::new();
+        for metadata in self.list_chats()? {
+            for tag in &metadata.tags {
+                tags.entry(tag.to_lowercase())
+                    .or_insert_with(|| TagCount {
+                        tag: tag.clone(),
+                        count: 0,
+                    })
+                    .count += 1;
+            }
+        }
+        Ok(tags.into_values().collect())
+    }
+
+    /// Move a chat into `folder`, or out of any folder with `None` or a
+    /// blank path.
+    pub fn move_chat_to_folder(&self, chat_id: &str, folder: Option<&str>) -> Result<ChatMetadata> {
+        let folder = folder.and_then(normalize_folder);
+        self.modify_metadata(chat_id, |metadata| {
+            metadata.folder = folder;
+            Ok(())
+        })
+    }
+}
+
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|seen| seen.eq_ignore_ascii_case(tag)) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// `folder` with blank segments and surrounding whitespace removed, so
+/// `" Work//Invoices/ "` and `"Work/Invoices"` are the same folder.
+fn normalize_folder(folder: &str) -> Option<String> {
+    let path = folder
+        .split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    (!path.is_empty()).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::ChatData;
+
+    fn save(storage: &ChatStorage, title: &str) -> String {
+        let chat = ChatData::new(ChatMetadata::new(title.to_string(), String::new(), None));
+        storage.save_chat(&chat).unwrap();
+        chat.metadata.id
+    }
+
+    #[test]
+    fn chats_are_filtered_by_tag_and_folder() {
+        let (storage, _dir) = test_storage();
+        let invoice = save(&storage, "Invoice");
+        let receipt = save(&storage, "Receipt");
+        let meme = save(&storage, "Meme");
+
+        let tags = [
+            "Finance".to_string(),
+            " finance ".to_string(),
+            String::new(),
+        ];
+        let metadata = storage.set_chat_tags(&invoice, &tags).unwrap();
+        assert_eq!(metadata.tags, vec!["Finance"]);
+        storage
+            .set_chat_tags(&receipt, &["finance".to_string(), "2026".to_string()])
+            .unwrap();
+        let metadata = storage
+            .move_chat_to_folder(&invoice, Some(" Work//Invoices/ "))
+            .unwrap();
+        assert_eq!(metadata.folder.as_deref(), Some("Work/Invoices"));
+
+        let by_tag = ChatFilter {
+            tag: Some("FINANCE".to_string()),
+            ..Default::default()
+        };
+        let mut found: Vec<String> = storage
+            .list_chats_filtered(&by_tag)
+            .unwrap()
+            .into_iter()
+            .map(|metadata| metadata.id)
+            .collect();
+        found.sort();
+        let mut expected = vec![invoice.clone(), receipt.clone()];
+        expected.sort();
+        assert_eq!(found, expected);
+
+        let by_folder = ChatFilter {
+            folder: Some("Work/Invoices".to_string()),
+            ..Default::default()
+        };
+        let found = storage.list_chats_filtered(&by_folder).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, invoice);
+
+        let unfiled = ChatFilter {
+            folder: Some(String::new()),
+            tag: Some("2026".to_string()),
+            ..Default::default()
+        };
+        let found = storage.list_chats_filtered(&unfiled).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, receipt);
+
+        let tags = storage.list_tags().unwrap();
+        assert_eq!(
+            tags,
+            vec![
+                TagCount {
+                    tag: "2026".to_string(),
+                    count: 1
+                },
+                TagCount {
+                    tag: "Finance".to_string(),
+                    count: 2
+                },
+            ]
+        );
+
+        let metadata = storage.move_chat_to_folder(&invoice, None).unwrap();
+        assert!(metadata.folder.is_none());
+        assert!(storage
+            .list_chats_filtered(&ChatFilter::default())
+            .unwrap()
+            .iter()
+            .any(|metadata| metadata.id == meme));
+    }
+}
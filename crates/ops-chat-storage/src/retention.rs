@@ -0,0 +1,580 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retention policy and scheduled storage maintenance.
+//!
+//! A maintenance pass applies a [`RetentionPolicy`]: chats past the age
+//! limit move to `trash/` or are deleted, trashed chats past their grace
+//! period are purged, and while storage is over the cap the oldest chats
+//! are dropped. CAS objects that only removed chats referenced go with
+//! them, and provider uploads past their expiry are forgotten. Pinned
+//! chats are never touched. A dry run returns the same report without
+//! changing anything.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::encryption::{decrypt, is_encrypted, StorageKey};
+use crate::error::{Result, StorageError};
+use crate::storage::{write_atomic, ChatStorage};
+use crate::types::{AttachmentRegistry, ChatMetadata};
+
+/// Trash directory inside the storage base directory.
+const TRASH_DIR: &str = "trash";
+/// File in a trashed chat's directory holding when it was trashed.
+const TRASHED_AT_FILE: &str = "trashed_at.txt";
+const ATTACHMENT_REGISTRY_FILE: &str = "attachment_registry.json";
+/// Days a chat stays in the trash unless the policy says otherwise.
+pub const DEFAULT_TRASH_DAYS: u32 = 7;
+/// Wait before the first scheduled pass, to keep out of startup's way.
+const FIRST_PASS_DELAY: Duration = Duration::from_secs(120);
+
+/// What happens to chats past the age limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RetentionAction {
+    /// Move them to the trash, where they can be restored until purged.
+    #[default]
+    Trash,
+    /// Delete them right away.
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionPolicy {
+    /// Remove chats not updated for this many days, as `age_action` says.
+    pub max_age_days: Option<u32>,
+    pub age_action: RetentionAction,
+    /// Exempt starred chats from the age limit and the storage cap.
+    pub keep_starred: bool,
+    /// Drop the oldest chats while storage exceeds this many bytes.
+    pub max_storage_bytes: Option<u64>,
+    /// Days a trashed chat is kept before it is purged.
+    pub trash_days: u32,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age_days: None,
+            age_action: RetentionAction::Trash,
+            keep_starred: true,
+            max_storage_bytes: None,
+            trash_days: DEFAULT_TRASH_DAYS,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    fn protects(&self, chat: &ChatMetadata) -> bool {
+        chat.is_pinned || (self.keep_starred && chat.is_starred)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    /// Chats moved to the trash for their age.
+    pub trashed: Vec<ChatMetadata>,
+    /// Chats deleted outright: past the age limit when the policy deletes
+    /// them, or to get under the storage cap, where moving them to the trash
+    /// would free nothing.
+    pub deleted: Vec<ChatMetadata>,
+    /// Ids of trashed chats purged.
+    pub purged: Vec<String>,
+    /// CAS objects no remaining chat references.
+    pub removed_objects: usize,
+    /// Bytes freed by deleted and purged chats and their objects.
+    pub reclaimed_bytes: u64,
+    /// Expired provider uploads dropped from attachment registries.
+    pub expired_uploads: usize,
+    /// Storage size after the pass.
+    pub total_bytes: u64,
+}
+
+/// Chat directories and the CAS objects they reference, for working out
+/// what removing a chat frees.
+struct References {
+    /// Directory size and referenced object hashes per chat id.
+    chats: HashMap<String, (u64, HashSet<String>)>,
+    /// Chats referencing each object hash.
+    counts: HashMap<String, usize>,
+    /// Files and total size per object hash, tone sidecars included.
+    objects: HashMap<String, (Vec<PathBuf>, u64)>,
+    /// Objects whose last reference was released.
+    released: Vec<PathBuf>,
+}
+
+impl References {
+    fn add(&mut self, chat_id: &str, dir: &Path, key: Option<&StorageKey>) -> Result<()> {
+        let hashes: HashSet<String> = referenced_hashes(dir, key)?
+            .into_iter()
+            .filter(|hash| self.objects.contains_key(hash))
+            .collect();
+        for hash in &hashes {
+            *self.counts.entry(hash.clone()).or_default() += 1;
+        }
+        self.chats
+            .insert(chat_id.to_string(), (dir_size(dir)?, hashes));
+        Ok(())
+    }
+
+    /// Forget a chat and return the bytes freed by removing it.
+    fn release(&mut self, chat_id: &str) -> u64 {
+        let Some((mut freed, hashes)) = self.chats.remove(chat_id) else {
+            return 0;
+        };
+        for hash in hashes {
+            let Some(count) = self.counts.get_mut(&hash) else {
+                continue;
+            };
+            *count -= 1;
+            if *count == 0 {
+                if let Some((files, size)) = self.objects.remove(&hash) {
+                    freed += size;
+                    self.released.extend(files);
+                }
+            }
+        }
+        freed
+    }
+}
+
+impl ChatStorage {
+    pub(crate) fn trash_dir(&self) -> PathBuf {
+        self.base_dir().join(TRASH_DIR)
+    }
+
+    /// Apply `policy` once. With `dry_run` nothing is changed and the report
+    /// says what a real pass would do.
+    pub fn apply_retention(
+        &self,
+        policy: &RetentionPolicy,
+        dry_run: bool,
+    ) -> Result<RetentionReport> {
+        let now = Utc::now();
+        let chats = self.list_chats()?;
+        let trash = self.trashed_chats()?;
+
+        let mut refs = References {
+            chats: HashMap::new(),
+            counts: HashMap::new(),
+            objects: object_files(self.objects_dir())?,
+            released: Vec::new(),
+        };
+        for chat in &chats {
+            refs.add(&chat.id, &self.chat_dir(&chat.id), self.encryption.as_ref())?;
+        }
+        for (chat_id, _) in &trash {
+            refs.add(
+                chat_id,
+                &self.trash_dir().join(chat_id),
+                self.encryption.as_ref(),
+            )?;
+        }
+
+        let mut report = RetentionReport {
+            dry_run,
+            total_bytes: dir_size(self.base_dir())?,
+            ..Default::default()
+        };
+
+        let purge_before = now - chrono::Duration::days(i64::from(policy.trash_days));
+        let (expired, mut kept_trash): (Vec<_>, Vec<_>) = trash
+            .into_iter()
+            .partition(|(_, trashed_at)| *trashed_at <= purge_before);
+        for (chat_id, _) in expired {
+            report.reclaimed_bytes += refs.release(&chat_id);
+            report.purged.push(chat_id);
+        }
+
+        let mut candidates: Vec<&ChatMetadata> =
+            chats.iter().filter(|chat| !policy.protects(chat)).collect();
+        candidates.sort_by_key(|chat| chat.updated_at);
+        if let Some(days) = policy.max_age_days {
+            let cutoff = now - chrono::Duration::days(i64::from(days));
+            let expired: Vec<ChatMetadata> = candidates
+                .iter()
+                .take_while(|chat| chat.updated_at < cutoff)
+                .map(|chat| (*chat).clone())
+                .collect();
+            match policy.age_action {
+                RetentionAction::Trash => report.trashed = expired,
+                RetentionAction::Delete => {
+                    for chat in &expired {
+                        report.reclaimed_bytes += refs.release(&chat.id);
+                    }
+                    report.deleted = expired;
+                }
+            }
+        }
+
+        if let Some(cap) = policy.max_storage_bytes {
+            let over_cap = |report: &RetentionReport| {
+                report.total_bytes.saturating_sub(report.reclaimed_bytes) > cap
+            };
+            kept_trash.sort_by_key(|(_, trashed_at)| *trashed_at);
+            for (chat_id, _) in kept_trash {
+                if !over_cap(&report) {
+                    break;
+                }
+                report.reclaimed_bytes += refs.release(&chat_id);
+                report.purged.push(chat_id);
+            }
+            for chat in candidates {
+                if !over_cap(&report) {
+                    break;
+                }
+                if report.deleted.iter().any(|deleted| deleted.id == chat.id) {
+                    continue;
+                }
+                report.reclaimed_bytes += refs.release(&chat.id);
+                report.trashed.retain(|trashed| trashed.id != chat.id);
+                report.deleted.push(chat.clone());
+            }
+        }
+        report.total_bytes = report.total_bytes.saturating_sub(report.reclaimed_bytes);
+        report.removed_objects = refs
+            .released
+            .iter()
+            .filter(|path| !path.extension().is_some_and(|ext| ext == "tone"))
+            .count();
+
+        let removed: HashSet<&str> = report
+            .trashed
+            .iter()
+            .chain(&report.deleted)
+            .map(|chat| chat.id.as_str())
+            .collect();
+        for chat in chats
+            .iter()
+            .filter(|chat| !removed.contains(chat.id.as_str()))
+        {
+            report.expired_uploads += self.expire_uploads(&chat.id, now, dry_run)?;
+        }
+
+        if dry_run {
+            return Ok(report);
+        }
+        for chat_id in &report.purged {
+            fs::remove_dir_all(self.trash_dir().join(chat_id))?;
+        }
+        for chat in &report.deleted {
+            self.delete_chat(&chat.id)?;
+        }
+        for chat in &report.trashed {
+            self.move_to_trash(&chat.id, now)?;
+        }
+        for path in &refs.released {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        if !refs.released.is_empty() {
+            self.rebuild_attachment_index()?;
+        }
+        Ok(report)
+    }
+
+    /// Put a trashed chat back into the chat list.
+    pub fn restore_trashed_chat(&self, chat_id: &str) -> Result<ChatMetadata> {
+        let trashed = self.trash_dir().join(chat_id);
+        if !trashed.is_dir() {
+            return Err(StorageError::ChatNotFound(chat_id.to_string()));
+        }
+        let _ = fs::remove_file(trashed.join(TRASHED_AT_FILE));
+        let chat_dir = self.chat_dir(chat_id);
+        fs::rename(&trashed, &chat_dir)?;
+
+        let metadata: ChatMetadata =
+            serde_json::from_str(&fs::read_to_string(chat_dir.join("meta.json"))?)?;
+        self.update_index(&metadata)?;
+        Ok(metadata)
+    }
+
+    /// Ids and trash times of trashed chats.
+    fn trashed_chats(&self) -> Result<Vec<(String, DateTime<Utc>)>> {
+        let trash_dir = self.trash_dir();
+        if !trash_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut trashed = Vec::new();
+        for entry in fs::read_dir(&trash_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let Some(chat_id) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            // Fall back to the directory time if the marker is unreadable.
+            let trashed_at = fs::read_to_string(entry.path().join(TRASHED_AT_FILE))
+                .ok()
+                .and_then(|at| DateTime::parse_from_rfc3339(at.trim()).ok())
+                .map(|at| at.with_timezone(&Utc))
+                .or_else(|| Some(entry.metadata().ok()?.modified().ok()?.into()))
+                .unwrap_or_else(Utc::now);
+            trashed.push((chat_id, trashed_at));
+        }
+        Ok(trashed)
+    }
+
+    pub(crate) fn move_to_trash(&self, chat_id: &str, now: DateTime<Utc>) -> Result<()> {
+        let trash_dir = self.trash_dir();
+        fs::create_dir_all(&trash_dir)?;
+        let trashed = trash_dir.join(chat_id);
+        if trashed.exists() {
+            fs::remove_dir_all(&trashed)?;
+        }
+        fs::rename(self.chat_dir(chat_id), &trashed)?;
+        write_atomic(trashed.join(TRASHED_AT_FILE), now.to_rfc3339())?;
+        self.remove_from_index(chat_id)
+    }
+
+    /// Drop provider file handles past their expiry from a chat's registry
+    /// and return how many there were.
+    fn expire_uploads(&self, chat_id: &str, now: DateTime<Utc>, dry_run: bool) -> Result<usize> {
+        let path = self.chat_dir(chat_id).join(ATTACHMENT_REGISTRY_FILE);
+        if !path.exists() {
+            return Ok(0);
+        }
+        let mut registry: AttachmentRegistry = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        let mut expired = 0;
+        for record in registry.values_mut() {
+            if record
+                .provider_file
+                .as_ref()
+                .is_some_and(|file| file.expires_at <= now)
+            {
+                record.provider_file = None;
+                expired += 1;
+            }
+        }
+        if expired > 0 && !dry_run {
+            write_atomic(&path, serde_json::to_string_pretty(&registry)?)?;
+        }
+        Ok(expired)
+    }
+}
+
+/// Runs maintenance passes on a background thread. The thread stops when
+/// the task is dropped, after any pass in progress.
+pub struct MaintenanceTask {
+    _stop: mpsc::Sender<()>,
+}
+
+impl MaintenanceTask {
+    /// Run a pass every `interval`, the first one shortly after start.
+    /// `source` is asked for the storage and policy before each pass so
+    /// profile and settings changes are picked up; `None` skips the pass.
+    pub fn spawn<S, R>(interval: Duration, mut source: S, mut on_report: R) -> Result<Self>
+    where
+        S: FnMut() -> Option<(ChatStorage, RetentionPolicy)> + Send + 'static,
+        R: FnMut(Result<RetentionReport>) + Send + 'static,
+    {
+        let (stop, stopped) = mpsc::channel::<()>();
+        thread::Builder::new()
+            .name("chat-storage-maintenance".to_string())
+            .spawn(move || {
+                let mut wait = FIRST_PASS_DELAY.min(interval);
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(wait) {
+                    if let Some((storage, policy)) = source() {
+                        on_report(storage.apply_retention(&policy, false));
+                    }
+                    wait = interval;
+                }
+            })?;
+        Ok(Self { _stop: stop })
+    }
+}
+
+/// Files of every CAS object, grouped by hash.
+pub(crate) fn object_files(objects_dir: &Path) -> Result<HashMap<String, (Vec<PathBuf>, u64)>> {
+    let mut objects: HashMap<String, (Vec<PathBuf>, u64)> = HashMap::new();
+    if !objects_dir.exists() {
+        return Ok(objects);
+    }
+    for prefix in fs::read_dir(objects_dir)? {
+        let prefix = prefix?.path();
+        if !prefix.is_dir() {
+            continue;
+        }
+        for object in fs::read_dir(&prefix)? {
+            let object = object?;
+            let path = object.path();
+            let Some(hash) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if !is_hash(hash) {
+                continue;
+            }
+            let entry = objects.entry(hash.to_string()).or_default();
+            entry.1 += object.metadata()?.len();
+            entry.0.push(path);
+        }
+    }
+    Ok(objects)
+}
+
+/// Object hashes mentioned in a chat directory's files: the image hash in
+/// its metadata, CAS paths in messages and the attachment registry.
+/// Encrypted files are an error without `key`, so no object is taken for
+/// unreferenced just because its reference could not be read.
+pub(crate) fn referenced_hashes(dir: &Path, key: Option<&StorageKey>) -> Result<HashSet<String>> {
+    let mut hashes = HashSet::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let mut data = fs::read(&path)?;
+        if is_encrypted(&data) {
+            let key = key.ok_or_else(|| {
+                StorageError::Encryption(format!(
+                    "{} is encrypted and no key is set",
+                    path.display()
+                ))
+            })?;
+            data = decrypt(key, &data)?;
+        }
+        let Ok(text) = String::from_utf8(data) else {
+            continue;
+        };
+        hashes.extend(
+            text.split(|c: char| !c.is_ascii_hexdigit())
+                .filter(|word| is_hash(word))
+                .map(str::to_ascii_lowercase),
+        );
+    }
+    Ok(hashes)
+}
+
+fn is_hash(word: &str) -> bool {
+    word.len() == 64 && word.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub(crate) fn dir_size(dir: &Path) -> Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::ChatData;
+
+    fn save_chat(storage: &ChatStorage, image: &[u8], age_days: i64) -> ChatMetadata {
+        let image = storage.store_image(image, None).unwrap();
+        let mut metadata = ChatMetadata::new("Chat".to_string(), image.hash, None);
+        metadata.updated_at = Utc::now() - chrono::Duration::days(age_days);
+        storage.save_chat(&ChatData::new(metadata.clone())).unwrap();
+        metadata
+    }
+
+    #[test]
+    fn old_chats_are_trashed_unless_protected_and_can_be_restored() {
+        let (storage, _dir) = test_storage();
+        let old = save_chat(&storage, b"old", 40);
+        let mut starred = save_chat(&storage, b"starred", 40);
+        starred.is_starred = true;
+        storage.update_chat_metadata(&starred).unwrap();
+        let recent = save_chat(&storage, b"recent", 1);
+
+        let policy = RetentionPolicy {
+            max_age_days: Some(30),
+            ..Default::default()
+        };
+        let preview = storage.apply_retention(&policy, true).unwrap();
+        assert_eq!(
+            preview.trashed.iter().map(|c| &c.id).collect::<Vec<_>>(),
+            vec![&old.id]
+        );
+        assert_eq!(storage.list_chats().unwrap().len(), 3);
+
+        let report = storage.apply_retention(&policy, false).unwrap();
+        assert_eq!(report.trashed.len(), 1);
+        assert_eq!(report.removed_objects, 0);
+        let ids: Vec<_> = storage
+            .list_chats()
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert!(ids.contains(&starred.id) && ids.contains(&recent.id));
+        assert!(!ids.contains(&old.id));
+
+        storage.restore_trashed_chat(&old.id).unwrap();
+        assert_eq!(storage.list_chats().unwrap().len(), 3);
+        assert!(storage.load_chat(&old.id).is_ok());
+    }
+
+    #[test]
+    fn old_chats_can_be_deleted_instead_of_trashed() {
+        let (storage, _dir) = test_storage();
+        let old = save_chat(&storage, b"old", 40);
+        let mut pinned = save_chat(&storage, b"pinned", 40);
+        pinned.is_pinned = true;
+        storage.update_chat_metadata(&pinned).unwrap();
+
+        let policy = RetentionPolicy {
+            max_age_days: Some(30),
+            age_action: RetentionAction::Delete,
+            ..Default::default()
+        };
+        let report = storage.apply_retention(&policy, false).unwrap();
+        assert!(report.trashed.is_empty());
+        assert_eq!(
+            report.deleted.iter().map(|c| &c.id).collect::<Vec<_>>(),
+            vec![&old.id]
+        );
+        assert_eq!(report.removed_objects, 1);
+        assert!(report.reclaimed_bytes > 0);
+        assert!(storage.get_image_path(&old.image_hash).is_err());
+        assert!(storage.restore_trashed_chat(&old.id).is_err());
+        assert_eq!(storage.list_chats().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn storage_cap_drops_oldest_chats_and_their_unshared_objects() {
+        let (storage, _dir) = test_storage();
+        let oldest = save_chat(&storage, b"oldest image", 3);
+        let shared = save_chat(&storage, b"shared image", 2);
+        let mut sharing = ChatMetadata::new("Copy".to_string(), shared.image_hash.clone(), None);
+        sharing.updated_at = Utc::now() - chrono::Duration::days(2);
+        storage.save_chat(&ChatData::new(sharing.clone())).unwrap();
+
+        let total = dir_size(storage.base_dir()).unwrap();
+        let policy = RetentionPolicy {
+            max_storage_bytes: Some(total - 1),
+            ..Default::default()
+        };
+        let report = storage.apply_retention(&policy, false).unwrap();
+        assert_eq!(
+            report.deleted.iter().map(|c| &c.id).collect::<Vec<_>>(),
+            vec![&oldest.id]
+        );
+        assert_eq!(report.removed_objects, 1);
+        assert!(storage.get_image_path(&oldest.image_hash).is_err());
+        assert!(storage.get_image_path(&shared.image_hash).is_ok());
+        assert_eq!(storage.list_chats().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Perceptual hashes for finding near-duplicate captures.
+//!
+//! BLAKE3 only matches identical bytes, so two captures of the same
+//! dashboard a minute apart never dedupe. Next to it, each stored image gets
+//! a 64-bit DCT perceptual hash (pHash) that changes little when a clock,
+//! cursor or a few pixels change. Hashes live in `phash_index.json`, keyed
+//! by the image's BLAKE3 hash; images stored before the index existed are
+//! hashed the first time a search needs them.
+
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::storage::{write_atomic, ChatStorage};
+use crate::types::ChatMetadata;
+
+const PHASH_INDEX_FILE: &str = "phash_index.json";
+/// Side of the grayscale thumbnail the DCT runs on.
+const SAMPLE_SIDE: usize = 32;
+/// Side of the low-frequency block of DCT coefficients kept.
+const HASH_SIDE: usize = 8;
+/// Differing bits, out of 64, up to which two images count as similar.
+pub const DEFAULT_SIMILARITY_THRESHOLD: u32 = 8;
+
+/// A chat whose image is close to the one searched for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarCapture {
+    pub chat: ChatMetadata,
+    /// Differing hash bits; 0 for the same or an identical-looking image.
+    pub distance: u32,
+}
+
+/// BLAKE3 hash to pHash, the latter as 16 hex digits.
+type PhashIndex = BTreeMap<String, String>;
+
+impl ChatStorage {
+    /// Chats whose image is within `threshold` differing bits of the image
+    /// with BLAKE3 `hash`, closest first and then most recently updated.
+    /// Chats with that exact image are included at distance 0.
+    pub fn find_similar_captures(&self, hash: &str, threshold: u32) -> Result<Vec<SimilarCapture>> {
+        let chats: Vec<ChatMetadata> = self
+            .list_chats()?
+            .into_iter()
+            .filter(|chat| !chat.image_hash.is_empty())
+            .collect();
+
+        let mut index = self.load_phash_index();
+        let before = index.clone();
+        // Drop entries of images no chat uses any more.
+        index.retain(|image_hash, _| {
+            image_hash == hash || chats.iter().any(|chat| chat.image_hash == *image_hash)
+        });
+        let target = self.indexed_phash(&mut index, hash);
+        let mut similar = Vec::new();
+        if let Some(target) = target {
+            for chat in chats {
+                let Some(phash) = self.indexed_phash(&mut index, &chat.image_hash) else {
+                    continue;
+                };
+                let distance = (target ^ phash).count_ones();
+                if distance <= threshold {
+                    similar.push(SimilarCapture { chat, distance });
+                }
+            }
+        }
+        if index != before {
+            self.save_phash_index(&index)?;
+        }
+
+        similar.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then(b.chat.updated_at.cmp(&a.chat.updated_at))
+        });
+        Ok(similar)
+    }
+
+    /// Hash a newly stored image into the index. Images that do not decode
+    /// are left out.
+    pub(crate) fn index_phash(&self, hash: &str, bytes: &[u8]) {
+        let Some(phash) = perceptual_hash(bytes) else {
+            return;
+        };
+        let mut index = self.load_phash_index();
+        if index
+            .insert(hash.to_string(), format_phash(phash))
+            .is_none()
+        {
+            let _ = self.save_phash_index(&index);
+        }
+    }
+
+    /// The pHash of the image with `hash`, computed and added to `index` if
+    /// missing. `None` when the image is gone or does not decode.
+    fn indexed_phash(&self, index: &mut PhashIndex, hash: &str) -> Option<u64> {
+        if let Some(phash) = index.get(hash).and_then(|phash| parse_phash(phash)) {
+            return Some(phash);
+        }
+        let prefix = hash.get(..2)?;
+        let path = self
+            .objects_dir()
+            .join(prefix)
+            .join(format!("{}.png", hash));
+        let phash = perceptual_hash(&self.read_object(&path).ok()?)?;
+        index.insert(hash.to_string(), format_phash(phash));
+        Some(phash)
+    }
+
+    fn phash_index_path(&self) -> PathBuf {
+        self.base_dir().join(PHASH_INDEX_FILE)
+    }
+
+    /// The saved index; a missing or unreadable one is rebuilt on demand.
+    fn load_phash_index(&self) -> PhashIndex {
+        fs::read_to_string(self.phash_index_path())
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_phash_index(&self, index: &PhashIndex) -> Result<()> {
+        write_atomic(self.phash_index_path(), serde_json::to_string(index)?)
+    }
+}
+
+/// 64-bit perceptual hash of an encoded image: the signs, relative to their
+/// median, of the lowest 8×8 DCT frequencies of a 32×32 grayscale
+/// thumbnail. `None` when `bytes` is not an image.
+pub fn perceptual_hash(bytes: &[u8]) -> Option<u64> {
+    let side = SAMPLE_SIDE as u32;
+    let thumbnail = image::load_from_memory(bytes)
+        .ok()?
+        .thumbnail_exact(side, side)
+        .to_luma8();
+    let pixels: Vec<f64> = thumbnail
+        .pixels()
+        .map(|pixel| f64::from(pixel[0]))
+        .collect();
+
+    // cos((2x + 1)uπ / 2N) for the kept frequencies u.
+    let basis: Vec<Vec<f64>> = (0..HASH_SIDE)
+        .map(|u| {
+            (0..SAMPLE_SIDE)
+                .map(|x| ((2 * x + 1) as f64 * u as f64 * PI / (2 * SAMPLE_SIDE) as f64).cos())
+                .collect()
+        })
+        .collect();
+    // Separable 2D DCT: rows first, then columns.
+    let rows: Vec<Vec<f64>> = (0..SAMPLE_SIDE)
+        .map(|y| {
+            let row = &pixels[y * SAMPLE_SIDE..(y + 1) * SAMPLE_SIDE];
+            basis
+                .iter()
+                .map(|cosines| row.iter().zip(cosines).map(|(p, c)| p * c).sum())
+                .collect()
+        })
+        .collect();
+    let mut coefficients = Vec::with_capacity(HASH_SIDE * HASH_SIDE);
+    for cosines in &basis {
+        for u in 0..HASH_SIDE {
+            coefficients.push(
+                rows.iter()
+                    .zip(cosines)
+                    .map(|(row, c)| row[u] * c)
+                    .sum::<f64>(),
+            );
+        }
+    }
+
+    // The DC term is the overall brightness and would skew the median.
+    let mut ac = coefficients[1..].to_vec();
+    ac.sort_by(f64::total_cmp);
+    let median = ac[ac.len() / 2];
+    Some(
+        coefficients
+            .iter()
+            .enumerate()
+            .filter(|(_, coefficient)| **coefficient > median)
+            .fold(0u64, |hash, (bit, _)| hash | (1 << bit)),
+    )
+}
+
+fn format_phash(phash: u64) -> String {
+    format!("{:016x}", phash)
+}
+
+fn parse_phash(phash: &str) -> Option<u64> {
+    u64::from_str_radix(phash, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_storage;
+    use crate::types::ChatData;
+    use image::{ImageBuffer, ImageFormat, Rgb};
+    use std::io::Cursor;
+
+    /// A 200×120 "dashboard": a gradient with a dark bar whose width is
+    /// `bar`, and a few changed pixels standing in for a clock when `tick`.
+    fn dashboard(bar: u32, tick: bool) -> Vec<u8> {
+        let image = ImageBuffer::from_fn(200, 120, |x, y| {
+            if tick && (180..184).contains(&x) && (4..8).contains(&y) {
+                return Rgb([255, 0, 0]);
+            }
+            if y > 60 && x < bar {
+                return Rgb([20, 20, 40]);
+            }
+            let shade = (x + y) as u8;
+            Rgb([shade, shade, 200])
+        });
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        png
+    }
+
+    #[test]
+    fn near_identical_captures_are_found_and_different_ones_are_not() {
+        let (storage, _dir) = test_storage();
+        let save = |png: &[u8]| {
+            let image = storage.store_image(png, None).unwrap();
+            let metadata = ChatMetadata::new("Dashboard".to_string(), image.hash, None);
+            storage.save_chat(&ChatData::new(metadata.clone())).unwrap();
+            metadata
+        };
+        let first = save(&dashboard(150, false));
+        let again = save(&dashboard(150, true));
+        let other = save(&dashboard(30, false));
+        assert_ne!(first.image_hash, again.image_hash);
+
+        let similar = storage
+            .find_similar_captures(&first.image_hash, DEFAULT_SIMILARITY_THRESHOLD)
+            .unwrap();
+        let ids: Vec<&str> = similar.iter().map(|found| found.chat.id.as_str()).collect();
+        assert_eq!(ids, vec![first.id.as_str(), again.id.as_str()]);
+        assert_eq!(similar[0].distance, 0);
+        assert!(!ids.contains(&other.id.as_str()));
+
+        // Entries missing from the index are computed again.
+        fs::remove_file(storage.phash_index_path()).unwrap();
+        let similar = storage
+            .find_similar_captures(&again.image_hash, DEFAULT_SIMILARITY_THRESHOLD)
+            .unwrap();
+        assert_eq!(similar.len(), 2);
+        assert!(storage.load_phash_index().contains_key(&other.image_hash));
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Storage usage of a profile's chats.
+//!
+//! Chats share CAS objects, so a chat's footprint is reported twice: every
+//! object it references, and only what deleting it would free. Objects are
+//! counted once in the totals; what sharing saves is reported separately.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::retention::{dir_size, object_files, referenced_hashes};
+use crate::storage::ChatStorage;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageStats {
+    /// Everything under the storage directory.
+    pub total_bytes: u64,
+    /// CAS objects, tone sidecars included.
+    pub cas_bytes: u64,
+    pub object_count: usize,
+    /// Bytes that copies of objects shared between chats would take up.
+    pub dedup_saved_bytes: u64,
+    /// Trashed chat directories, not counting their objects.
+    pub trash_bytes: u64,
+    /// Chats in the index, largest footprint first.
+    pub chats: Vec<ChatStorageStats>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatStorageStats {
+    pub chat_id: String,
+    pub title: String,
+    /// The chat's directory: messages, OCR, summaries and artifacts.
+    pub data_bytes: u64,
+    /// Objects the chat references, shared ones included.
+    pub object_bytes: u64,
+    pub object_count: usize,
+    /// What deleting the chat frees: its directory and the objects no
+    /// other chat, trashed ones included, references.
+    pub exclusive_bytes: u64,
+}
+
+impl ChatStorage {
+    /// Sizes of the CAS and of every chat in the index.
+    pub fn stats(&self) -> Result<StorageStats> {
+        let objects = object_files(self.objects_dir())?;
+        let mut stats = StorageStats {
+            total_bytes: dir_size(self.base_dir())?,
+            cas_bytes: objects.values().map(|(_, size)| size).sum(),
+            object_count: objects.len(),
+            ..Default::default()
+        };
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut count_references = |hashes: &HashSet<String>| {
+            for hash in hashes {
+                if objects.contains_key(hash) {
+                    *counts.entry(hash.clone()).or_default() += 1;
+                }
+            }
+        };
+
+        let trash_dir = self.trash_dir();
+        if trash_dir.is_dir() {
+            for entry in fs::read_dir(&trash_dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    stats.trash_bytes += dir_size(&path)?;
+                    count_references(&referenced_hashes(&path, self.encryption.as_ref())?);
+                }
+            }
+        }
+
+        let mut chats = Vec::new();
+        for metadata in self.list_chats()? {
+            let dir = self.chat_dir(&metadata.id);
+            if !dir.is_dir() {
+                continue;
+            }
+            let hashes = referenced_hashes(&dir, self.encryption.as_ref())?;
+            count_references(&hashes);
+            chats.push((metadata, dir_size(&dir)?, hashes));
+        }
+
+        for (hash, refs) in &counts {
+            if let Some((_, size)) = objects.get(hash) {
+                stats.dedup_saved_bytes += size * (*refs as u64 - 1);
+            }
+        }
+
+        for (metadata, data_bytes, hashes) in chats {
+            let mut chat = ChatStorageStats {
+                chat_id: metadata.id,
+                title: metadata.title,
+                data_bytes,
+                exclusive_bytes: data_bytes,
+                ..Default::default()
+            };
+            for hash in &hashes {
+                let Some((_, size)) = objects.get(hash) else {
+                    continue;
+                };
+                chat.object_bytes += size;
+                chat.object_count += 1;
+                if counts.get(hash) == Some(&1) {
+                    chat.exclusive_bytes += size;
+                }
+            }
+            stats.chats.push(chat);
+        }
+        stats
+            .chats
+            .sort_by_key(|chat| Reverse(chat.data_bytes + chat.object_bytes));
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::test_storage;
+    use crate::types::{ChatData, ChatMessage, ChatMetadata};
+
+    #[test]
+    fn shared_objects_count_once_and_are_not_exclusive() {
+        let (storage, _dir) = test_storage();
+
+        let shared = storage.store_image(&[1; 1000], None).unwrap();
+        let own = storage.store_image(&[2; 300], None).unwrap();
+        let first = ChatMetadata::new("First".to_string(), shared.hash.clone(), None);
+        storage.save_chat(&ChatData::new(first.clone())).unwrap();
+        let second = ChatMetadata::new("Second".to_string(), own.hash.clone(), None);
+        let mut chat = ChatData::new(second.clone());
+        chat.messages
+            .push(ChatMessage::user(format!("see {}", shared.path)));
+        storage.save_chat(&chat).unwrap();
+
+        let stats = storage.stats().unwrap();
+        assert_eq!(stats.object_count, 2);
+        assert!(stats.cas_bytes >= 1300);
+        assert!(stats.dedup_saved_bytes >= 1000 && stats.dedup_saved_bytes < 1300);
+        assert!(stats.total_bytes > stats.cas_bytes);
+        assert_eq!(stats.chats.len(), 2);
+        assert_eq!(stats.chats[0].chat_id, second.id);
+
+        let second_stats = &stats.chats[0];
+        assert_eq!(second_stats.object_count, 2);
+        assert_eq!(
+            second_stats.exclusive_bytes - second_stats.data_bytes,
+            stats.cas_bytes - stats.dedup_saved_bytes
+        );
+        let first_stats = &stats.chats[1];
+        assert_eq!(first_stats.exclusive_bytes, first_stats.data_bytes);
+    }
+}
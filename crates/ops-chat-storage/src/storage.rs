@@ -3,19 +3,60 @@
 
 //! Content Addressable Storage (CAS) implementation for images and chat data.
 
+use std::borrow::Cow;
 use std::fs::{self, File};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::attachments::mime_type_for_extension;
+use crate::encryption::StorageKey;
 use crate::error::{Result, StorageError};
+use crate::metadata::strip_image_metadata;
 use crate::types::{
-    AttachmentRegistry, ChatData, ChatMessage, ChatMetadata, OcrFrame, OcrRegion, StoredImage,
+    AttachmentRegistry, ChatAttachment, ChatData, ChatMessage, ChatMetadata, DanglingUserTurn,
+    Extraction, OcrFrame, OcrRegion, PluginNote, StoredImage, WebSource,
 };
 
 const DEFAULT_OCR_MODEL_ID: &str = "pp-ocr-v5-en";
-const AUTO_OCR_DISABLED_MODEL_ID: &str = "__meta_auto_ocr_disabled__";
+/// Marker frame key recording that automatic OCR was turned off for a chat.
+pub const AUTO_OCR_DISABLED_MODEL_ID: &str = "__meta_auto_ocr_disabled__";
+const PLUGIN_NOTES_FILE: &str = "plugin_notes.json";
+const WEB_SOURCE_FILE: &str = "web_source.json";
+const EXTRACTIONS_FILE: &str = "extractions.json";
+
+/// Write `contents` to a temporary file next to `path` and rename it into
+/// place, so a crash leaves either the old file or the new one.
+pub(crate) fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    let path = path.as_ref();
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let file_name = path
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("temp");
+    let temp_path = path.with_file_name(format!(
+        ".{}.tmp-{}-{}",
+        file_name,
+        std::process::id(),
+        suffix
+    ));
+    {
+        let mut temp_file = File::create(&temp_path)?;
+        temp_file.write_all(contents.as_ref())?;
+        temp_file.sync_all()?;
+    }
+    if let Err(e) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
 
-fn is_supported_ocr_model_id(model_id: &str) -> bool {
+/// Whether `model_id` names one of the OCR models chats can use.
+pub fn is_supported_ocr_model_id(model_id: &str) -> bool {
     matches!(
         model_id,
         "pp-ocr-v5-en"
@@ -67,6 +108,16 @@ fn retain_supported_ocr_frame_ids(frame: &mut OcrFrame) -> bool {
     changed
 }
 
+/// Give messages saved without an ID one. Returns whether any changed.
+fn assign_message_ids(messages: &mut [ChatMessage]) -> bool {
+    let mut changed = false;
+    for message in messages.iter_mut().filter(|message| message.id.is_empty()) {
+        message.id = ChatMessage::generate_id();
+        changed = true;
+    }
+    changed
+}
+
 /// Main storage manager for chats and images.
 pub struct ChatStorage {
     /// Base directory for all storage.
@@ -75,6 +126,10 @@ pub struct ChatStorage {
     objects_dir: PathBuf,
     /// Path to the chat index file.
     index_path: PathBuf,
+    /// Strip EXIF and other metadata from images before storing them.
+    strip_metadata: bool,
+    /// Encrypt transcripts, OCR text and objects at rest.
+    pub(crate) encryption: Option<StorageKey>,
 }
 
 impl ChatStorage {
@@ -103,9 +158,18 @@ impl ChatStorage {
             base_dir,
             objects_dir,
             index_path,
+            strip_metadata: false,
+            encryption: None,
         })
     }
 
+    /// Strip image metadata at store time. Off by default, which keeps
+    /// originals byte-for-byte; uploads are stripped either way.
+    pub fn with_metadata_stripping(mut self, enabled: bool) -> Self {
+        self.strip_metadata = enabled;
+        self
+    }
+
     /// Create a new storage manager using the default location.
     ///
     /// Uses `~/.config/squigit/chats/` on Linux (and appropriate config dirs on other OSs).
@@ -147,6 +211,12 @@ impl ChatStorage {
         if bytes.is_empty() {
             return Err(StorageError::EmptyImage);
         }
+        let bytes = if self.strip_metadata {
+            strip_image_metadata(bytes)
+        } else {
+            Cow::Borrowed(bytes)
+        };
+        let bytes = bytes.as_ref();
 
         // Compute BLAKE3 hash
         let hash = blake3::hash(bytes).to_hex().to_string();
@@ -169,16 +239,16 @@ impl ChatStorage {
 
         // Only write if file doesn't exist (deduplication)
         if !file_path.exists() {
-            let mut file = File::create(&file_path)?;
-            file.write_all(bytes)?;
+            self.write_sealed(&file_path, bytes)?;
 
             // Cache explicit tone
-            let _ = fs::write(&tone_path, &tone);
+            let _ = write_atomic(&tone_path, &tone);
+            self.index_phash(&hash, bytes);
         } else if let Some(explicit) = explicit_tone {
             // If caller provided a tone for an existing deduplicated object,
             // prefer it over stale cached values.
             tone = explicit;
-            let _ = fs::write(&tone_path, &tone);
+            let _ = write_atomic(&tone_path, &tone);
         } else if tone_path.exists() {
             if let Ok(cached) = fs::read_to_string(&tone_path) {
                 let trimmed = cached.trim();
@@ -188,9 +258,12 @@ impl ChatStorage {
             }
         }
 
+        let path = file_path.to_string_lossy().to_string();
+        let _ = self.record_attachment(&path, None);
+
         Ok(StoredImage {
             hash,
-            path: file_path.to_string_lossy().to_string(),
+            path,
             tone: Some(tone),
         })
     }
@@ -216,6 +289,16 @@ impl ChatStorage {
         bytes: &[u8],
         extension: &str,
         explicit_tone: Option<String>,
+    ) -> Result<StoredImage> {
+        self.store_file_named(bytes, extension, explicit_tone, None)
+    }
+
+    pub(crate) fn store_file_named(
+        &self,
+        bytes: &[u8],
+        extension: &str,
+        explicit_tone: Option<String>,
+        original_name: Option<&str>,
     ) -> Result<StoredImage> {
         if bytes.is_empty() {
             return Err(StorageError::EmptyImage);
@@ -243,16 +326,15 @@ impl ChatStorage {
         let mut tone = explicit_tone.clone().unwrap_or_else(|| "d".to_string());
 
         if !file_path.exists() {
-            let mut file = File::create(&file_path)?;
-            file.write_all(bytes)?;
+            self.write_sealed(&file_path, bytes)?;
 
             if is_image_ext {
-                let _ = fs::write(&tone_path, &tone);
+                let _ = write_atomic(&tone_path, &tone);
             }
         } else if is_image_ext {
             if let Some(explicit) = explicit_tone {
                 tone = explicit;
-                let _ = fs::write(&tone_path, &tone);
+                let _ = write_atomic(&tone_path, &tone);
             } else if tone_path.exists() {
                 if let Ok(cached) = fs::read_to_string(&tone_path) {
                     let trimmed = cached.trim();
@@ -270,9 +352,14 @@ impl ChatStorage {
             }
         }
 
+        let path = file_path.to_string_lossy().to_string();
+        // The attachment index is derived data; failing to update it must
+        // not fail the store.
+        let _ = self.record_attachment(&path, original_name);
+
         Ok(StoredImage {
             hash,
-            path: file_path.to_string_lossy().to_string(),
+            path,
             tone: Some(tone),
         })
     }
@@ -293,21 +380,46 @@ impl ChatStorage {
         let mut file = File::open(path)?;
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer)?;
-        self.store_file(&buffer, &extension, explicit_tone)
+        let original_name = source.file_name().and_then(|name| name.to_str());
+        self.store_file_named(&buffer, &extension, explicit_tone, original_name)
     }
 
-    /// Get the path to a stored image by its hash.
+    /// Get the path to a stored image by its hash. For an encrypted
+    /// image this is a decrypted copy; see [`ChatStorage::readable_path`].
     pub fn get_image_path(&self, hash: &str) -> Result<String> {
         let prefix = hash.get(..2).ok_or(StorageError::InvalidHash)?;
         let file_path = self.objects_dir.join(prefix).join(format!("{}.png", hash));
 
         if file_path.exists() {
-            Ok(file_path.to_string_lossy().to_string())
+            let readable = self.readable_path(&file_path)?;
+            Ok(readable.to_string_lossy().to_string())
         } else {
             Err(StorageError::ImageNotFound(hash.to_string()))
         }
     }
 
+    /// Get the path of any stored object by its hash, whatever its
+    /// extension. Encrypted objects resolve to a decrypted copy.
+    pub fn get_object_path(&self, hash: &str) -> Result<String> {
+        let path = self.object_path(hash)?;
+        Ok(self.readable_path(&path)?.to_string_lossy().to_string())
+    }
+
+    /// CAS path of the object with `hash`; `.tone` sidecars are skipped.
+    pub(crate) fn object_path(&self, hash: &str) -> Result<PathBuf> {
+        let prefix = hash.get(..2).ok_or(StorageError::InvalidHash)?;
+        let not_found = || StorageError::ImageNotFound(hash.to_string());
+        let entries = fs::read_dir(self.objects_dir.join(prefix)).map_err(|_| not_found())?;
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| {
+                path.file_stem().and_then(|stem| stem.to_str()) == Some(hash)
+                    && path.extension().and_then(|ext| ext.to_str()) != Some("tone")
+            })
+            .ok_or_else(not_found)
+    }
+
     /// Get the cached tone for a stored image by its hash.
     pub fn get_image_tone(&self, hash: &str) -> Option<String> {
         let prefix = hash.get(..2)?;
@@ -328,7 +440,7 @@ impl ChatStorage {
     // =========================================================================
 
     /// Get the directory for a specific chat.
-    fn chat_dir(&self, chat_id: &str) -> PathBuf {
+    pub(crate) fn chat_dir(&self, chat_id: &str) -> PathBuf {
         self.base_dir.join(chat_id)
     }
 
@@ -340,34 +452,21 @@ impl ChatStorage {
         // Save metadata
         let meta_path = chat_dir.join("meta.json");
         let meta_json = serde_json::to_string_pretty(&chat.metadata)?;
-        fs::write(&meta_path, meta_json)?;
+        write_atomic(&meta_path, meta_json)?;
 
         // Always save OCR frame file
         let ocr_path = chat_dir.join("ocr_frame.json");
         let ocr_json = serde_json::to_string_pretty(&chat.ocr_data)?;
-        fs::write(&ocr_path, ocr_json)?;
+        self.write_sealed(&ocr_path, ocr_json)?;
 
-        // Save messages files.
-        // - messages.json: canonical structured source for metadata-aware rendering
-        // - messages.md: backward-compatible human-readable transcript
-        let messages_json_path = chat_dir.join("messages.json");
-        let messages_path = chat_dir.join("messages.md");
-        if !chat.messages.is_empty() {
-            let json_content = serde_json::to_string_pretty(&chat.messages)?;
-            fs::write(&messages_json_path, json_content)?;
-            let md_content = self.messages_to_markdown(&chat.messages);
-            fs::write(&messages_path, md_content)?;
-        } else if messages_path.exists() {
-            fs::remove_file(&messages_path)?;
-            if messages_json_path.exists() {
-                fs::remove_file(&messages_json_path)?;
-            }
-        }
+        let mut messages = chat.messages.clone();
+        assign_message_ids(&mut messages);
+        self.write_messages(&chat_dir, &messages)?;
 
         // Save imgbb URL if present
         if let Some(ref url) = chat.imgbb_url {
             let url_path = chat_dir.join("imgbb_url.txt");
-            fs::write(&url_path, url)?;
+            write_atomic(&url_path, url)?;
         } else {
             let url_path = chat_dir.join("imgbb_url.txt");
             if url_path.exists() {
@@ -378,7 +477,12 @@ impl ChatStorage {
         let attachment_registry_path = chat_dir.join("attachment_registry.json");
         if !chat.attachment_registry.is_empty() {
             let registry_json = serde_json::to_string_pretty(&chat.attachment_registry)?;
-            fs::write(&attachment_registry_path, registry_json)?;
+            write_atomic(&attachment_registry_path, registry_json)?;
+            let cas_paths = chat
+                .attachment_registry
+                .values()
+                .map(|record| record.cas_path.as_str());
+            let _ = self.link_attachments(&chat.metadata.id, cas_paths);
         } else if attachment_registry_path.exists() {
             fs::remove_file(&attachment_registry_path)?;
         }
@@ -415,7 +519,7 @@ impl ChatStorage {
         let old_frame_path = chat_dir.join("ocr.json");
         let mut frame_changed = false;
         let mut ocr_data: OcrFrame = if frame_path.exists() {
-            let json = fs::read_to_string(&frame_path)?;
+            let json = self.read_sealed_string(&frame_path)?;
             serde_json::from_str(&json)?
         } else if old_frame_path.exists() {
             // Convert old flat array into frame format keyed by default model id.
@@ -437,26 +541,20 @@ impl ChatStorage {
         }
         if frame_changed {
             let new_json = serde_json::to_string_pretty(&ocr_data)?;
-            fs::write(&frame_path, new_json)?;
+            self.write_sealed(&frame_path, new_json)?;
         }
         if metadata_changed {
             let new_meta = serde_json::to_string_pretty(&metadata)?;
-            fs::write(&meta_path, new_meta)?;
+            write_atomic(&meta_path, new_meta)?;
             self.update_index(&metadata)?;
         }
 
-        // Load messages (prefer structured JSON, fallback to legacy markdown)
-        let messages_json_path = chat_dir.join("messages.json");
-        let messages_path = chat_dir.join("messages.md");
-        let messages = if messages_json_path.exists() {
-            let json_content = fs::read_to_string(&messages_json_path)?;
-            serde_json::from_str::<Vec<ChatMessage>>(&json_content)?
-        } else if messages_path.exists() {
-            let md_content = fs::read_to_string(&messages_path)?;
-            self.markdown_to_messages(&md_content)
-        } else {
-            Vec::new()
-        };
+        let mut messages = self.read_messages(&chat_dir)?;
+        let from_markdown = !messages.is_empty() && !chat_dir.join("messages.json").exists();
+        if assign_message_ids(&mut messages) || from_markdown {
+            // Keep the IDs stable from now on.
+            self.write_messages(&chat_dir, &messages)?;
+        }
 
         // Load imgbb URL
         let url_path = chat_dir.join("imgbb_url.txt");
@@ -469,7 +567,7 @@ impl ChatStorage {
         // Load rolling summary
         let summary_path = chat_dir.join("rolling_summary.txt");
         let rolling_summary = if summary_path.exists() {
-            Some(fs::read_to_string(&summary_path)?)
+            Some(self.read_sealed_string(&summary_path)?)
         } else {
             None
         };
@@ -477,7 +575,7 @@ impl ChatStorage {
         // Load image brief
         let brief_path = chat_dir.join("image_brief.txt");
         let image_brief = if brief_path.exists() {
-            Some(fs::read_to_string(&brief_path)?)
+            Some(self.read_sealed_string(&brief_path)?)
         } else {
             None
         };
@@ -490,6 +588,11 @@ impl ChatStorage {
             AttachmentRegistry::new()
         };
 
+        let plugin_notes = self.get_plugin_notes(chat_id)?;
+        let web_source = self.get_web_source(chat_id)?;
+        let extractions = self.get_extractions(chat_id)?;
+        let artifacts = self.list_artifacts(chat_id)?;
+
         Ok(ChatData {
             metadata,
             messages,
@@ -498,6 +601,10 @@ impl ChatStorage {
             rolling_summary,
             attachment_registry,
             image_brief,
+            plugin_notes,
+            web_source,
+            extractions,
+            artifacts,
         })
     }
 
@@ -515,7 +622,7 @@ impl ChatStorage {
         metadata.updated_at = chrono::Utc::now();
 
         let new_meta = serde_json::to_string_pretty(&metadata)?;
-        fs::write(&meta_path, new_meta)?;
+        write_atomic(&meta_path, new_meta)?;
         self.update_index(&metadata)?;
 
         Ok(())
@@ -528,21 +635,150 @@ impl ChatStorage {
             return Err(StorageError::ChatNotFound(chat_id.to_string()));
         }
         let brief_path = chat_dir.join("image_brief.txt");
-        fs::write(&brief_path, brief)?;
+        self.write_sealed(&brief_path, brief)?;
         Ok(())
     }
 
-    /// List all chats (metadata only).
-    pub fn list_chats(&self) -> Result<Vec<ChatMetadata>> {
-        if !self.index_path.exists() {
+    /// Attach a plugin's output to a chat, replacing any earlier note from
+    /// the same plugin.
+    pub fn save_plugin_note(&self, chat_id: &str, note: &PluginNote) -> Result<()> {
+        let chat_dir = self.chat_dir(chat_id);
+        if !chat_dir.exists() {
+            return Err(StorageError::ChatNotFound(chat_id.to_string()));
+        }
+        let mut notes = self.get_plugin_notes(chat_id)?;
+        notes.retain(|existing| existing.plugin_id != note.plugin_id);
+        notes.push(note.clone());
+
+        let notes_json = serde_json::to_string_pretty(&notes)?;
+        self.write_sealed(&chat_dir.join(PLUGIN_NOTES_FILE), notes_json)?;
+        Ok(())
+    }
+
+    /// Plugin notes for a chat, oldest first.
+    pub fn get_plugin_notes(&self, chat_id: &str) -> Result<Vec<PluginNote>> {
+        let notes_path = self.chat_dir(chat_id).join(PLUGIN_NOTES_FILE);
+        if !notes_path.exists() {
             return Ok(Vec::new());
         }
+        let json = self.read_sealed_string(&notes_path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
 
-        let index_json = fs::read_to_string(&self.index_path)?;
-        let chats: Vec<ChatMetadata> = serde_json::from_str(&index_json)?;
+    /// Record the web page a chat's image was captured from.
+    pub fn save_web_source(&self, chat_id: &str, source: &WebSource) -> Result<()> {
+        let chat_dir = self.chat_dir(chat_id);
+        if !chat_dir.exists() {
+            return Err(StorageError::ChatNotFound(chat_id.to_string()));
+        }
+        let source_json = serde_json::to_string_pretty(source)?;
+        self.write_sealed(&chat_dir.join(WEB_SOURCE_FILE), source_json)?;
+        Ok(())
+    }
+
+    /// The web page a chat's image was captured from, if any.
+    pub fn get_web_source(&self, chat_id: &str) -> Result<Option<WebSource>> {
+        let source_path = self.chat_dir(chat_id).join(WEB_SOURCE_FILE);
+        if !source_path.exists() {
+            return Ok(None);
+        }
+        let json = self.read_sealed_string(&source_path)?;
+        Ok(Some(serde_json::from_str(&json)?))
+    }
+
+    /// Store structured data extracted from a chat's image, replacing any
+    /// earlier extraction of the same kind.
+    pub fn save_extraction(&self, chat_id: &str, extraction: &Extraction) -> Result<()> {
+        let chat_dir = self.chat_dir(chat_id);
+        if !chat_dir.exists() {
+            return Err(StorageError::ChatNotFound(chat_id.to_string()));
+        }
+        let mut extractions = self.get_extractions(chat_id)?;
+        extractions.retain(|existing| existing.kind != extraction.kind);
+        extractions.push(extraction.clone());
+
+        let extractions_json = serde_json::to_string_pretty(&extractions)?;
+        self.write_sealed(&chat_dir.join(EXTRACTIONS_FILE), extractions_json)?;
+        Ok(())
+    }
+
+    /// Structured extractions for a chat, oldest first.
+    pub fn get_extractions(&self, chat_id: &str) -> Result<Vec<Extraction>> {
+        let extractions_path = self.chat_dir(chat_id).join(EXTRACTIONS_FILE);
+        if !extractions_path.exists() {
+            return Ok(Vec::new());
+        }
+        let json = self.read_sealed_string(&extractions_path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// List all chats (metadata only). A missing or unreadable index is
+    /// rebuilt from the chat directories first.
+    pub fn list_chats(&self) -> Result<Vec<ChatMetadata>> {
+        let index = match fs::read_to_string(&self.index_path) {
+            Ok(json) => serde_json::from_str(&json).ok(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        match index {
+            Some(chats) => Ok(chats),
+            None => self.rebuild_index(),
+        }
+    }
+
+    /// Recreate the index from each chat directory's `meta.json`. Trashed
+    /// chats and directories without readable metadata are left out.
+    pub fn rebuild_index(&self) -> Result<Vec<ChatMetadata>> {
+        let mut chats = Vec::new();
+        for entry in fs::read_dir(&self.base_dir)? {
+            let path = entry?.path();
+            if !path.is_dir() || path == self.objects_dir || path == self.trash_dir() {
+                continue;
+            }
+            let Ok(json) = fs::read_to_string(path.join("meta.json")) else {
+                continue;
+            };
+            if let Ok(metadata) = serde_json::from_str::<ChatMetadata>(&json) {
+                chats.push(metadata);
+            }
+        }
+        chats.sort_by_key(|a| std::cmp::Reverse(a.updated_at));
+
+        let json = serde_json::to_string_pretty(&chats)?;
+        write_atomic(&self.index_path, json)?;
         Ok(chats)
     }
 
+    /// Chats updated since `since` whose last message is an unanswered user
+    /// turn sent after `since`. Chats that fail to load are skipped.
+    pub fn find_dangling_user_turns(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<DanglingUserTurn>> {
+        let mut turns = Vec::new();
+        for metadata in self.list_chats()? {
+            if metadata.updated_at < since {
+                continue;
+            }
+            let Ok(chat) = self.load_chat(&metadata.id) else {
+                continue;
+            };
+            let Some(last) = chat.messages.last() else {
+                continue;
+            };
+            if last.role == "user" && last.timestamp >= since {
+                turns.push(DanglingUserTurn {
+                    chat_id: metadata.id,
+                    title: metadata.title,
+                    user_message: last.content.clone(),
+                    timestamp: last.timestamp,
+                });
+            }
+        }
+        turns.sort_by_key(|turn| std::cmp::Reverse(turn.timestamp));
+        Ok(turns)
+    }
+
     /// Delete a chat by ID.
     pub fn delete_chat(&self, chat_id: &str) -> Result<()> {
         let chat_dir = self.chat_dir(chat_id);
@@ -568,7 +804,7 @@ impl ChatStorage {
         // Save updated metadata
         let meta_path = chat_dir.join("meta.json");
         let meta_json = serde_json::to_string_pretty(metadata)?;
-        fs::write(&meta_path, meta_json)?;
+        write_atomic(&meta_path, meta_json)?;
 
         // Update index
         self.update_index(metadata)?;
@@ -576,12 +812,105 @@ impl ChatStorage {
         Ok(())
     }
 
+    /// Set the OCR model for one chat, overriding the global OCR language.
+    /// Returns the updated metadata.
+    pub fn set_chat_ocr_lang(&self, chat_id: &str, model_id: &str) -> Result<ChatMetadata> {
+        let model_id = model_id.trim();
+        if !is_supported_ocr_model_id(model_id) {
+            return Err(StorageError::InvalidOcrModel(model_id.to_string()));
+        }
+        self.modify_metadata(chat_id, |metadata| {
+            metadata.ocr_lang = Some(model_id.to_string());
+            Ok(())
+        })
+    }
+
+    /// Add a stored object to the end of the chat's attachments. The MIME
+    /// type defaults to the one of the object's extension. Adding an
+    /// attachment the chat already has changes nothing. Returns the updated
+    /// metadata.
+    pub fn add_chat_attachment(
+        &self,
+        chat_id: &str,
+        hash: &str,
+        mime: Option<&str>,
+        label: Option<&str>,
+    ) -> Result<ChatMetadata> {
+        let path = self.object_path(hash)?;
+        let mime = match mime.map(str::trim).filter(|mime| !mime.is_empty()) {
+            Some(mime) => mime.to_string(),
+            None => {
+                let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+                mime_type_for_extension(extension).to_string()
+            }
+        };
+        let label = label
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(str::to_string);
+
+        let metadata = self.modify_metadata(chat_id, |metadata| {
+            if !metadata
+                .attachments
+                .iter()
+                .any(|existing| existing.hash == hash)
+            {
+                metadata.attachments.push(ChatAttachment {
+                    hash: hash.to_string(),
+                    mime,
+                    added_at: chrono::Utc::now(),
+                    label,
+                });
+            }
+            Ok(())
+        })?;
+        self.link_attachments(chat_id, [path.to_string_lossy().as_ref()])?;
+        Ok(metadata)
+    }
+
+    /// Remove an attachment from the chat. The object stays in the CAS until
+    /// garbage collection finds it unreferenced. Returns the updated
+    /// metadata.
+    pub fn remove_chat_attachment(&self, chat_id: &str, hash: &str) -> Result<ChatMetadata> {
+        self.modify_metadata(chat_id, |metadata| {
+            let count = metadata.attachments.len();
+            metadata
+                .attachments
+                .retain(|attachment| attachment.hash != hash);
+            if metadata.attachments.len() == count {
+                return Err(StorageError::AttachmentNotFound(hash.to_string()));
+            }
+            Ok(())
+        })
+    }
+
+    /// Read-modify-write of a chat's metadata that also bumps `updated_at`
+    /// and the index entry.
+    pub fn modify_metadata(
+        &self,
+        chat_id: &str,
+        modify: impl FnOnce(&mut ChatMetadata) -> Result<()>,
+    ) -> Result<ChatMetadata> {
+        let meta_path = self.chat_dir(chat_id).join("meta.json");
+        if !meta_path.exists() {
+            return Err(StorageError::ChatNotFound(chat_id.to_string()));
+        }
+
+        let mut metadata: ChatMetadata = serde_json::from_str(&fs::read_to_string(&meta_path)?)?;
+        modify(&mut metadata)?;
+        metadata.updated_at = chrono::Utc::now();
+        write_atomic(&meta_path, serde_json::to_string_pretty(&metadata)?)?;
+        self.update_index(&metadata)?;
+        Ok(metadata)
+    }
+
     // =========================================================================
     // OCR and ImgBB
     // =========================================================================
 
     /// Save OCR data for a specific model into the chat's OCR frame.
     /// Merges the data into the existing frame (read-modify-write).
+    /// Regions are labeled for personal data on the way in.
     pub fn save_ocr_data(
         &self,
         chat_id: &str,
@@ -595,17 +924,19 @@ impl ChatStorage {
 
         let frame_path = chat_dir.join("ocr_frame.json");
         let mut frame: OcrFrame = if frame_path.exists() {
-            let json = fs::read_to_string(&frame_path)?;
+            let json = self.read_sealed_string(&frame_path)?;
             serde_json::from_str(&json)?
         } else {
             OcrFrame::new()
         };
         retain_supported_ocr_frame_ids(&mut frame);
 
-        frame.insert(canonical_model_id.to_string(), Some(ocr_data.to_vec()));
+        let mut regions = ocr_data.to_vec();
+        regions.iter_mut().for_each(OcrRegion::label_pii);
+        frame.insert(canonical_model_id.to_string(), Some(regions));
 
         let json = serde_json::to_string_pretty(&frame)?;
-        fs::write(&frame_path, json)?;
+        self.write_sealed(&frame_path, json)?;
 
         Ok(())
     }
@@ -621,11 +952,11 @@ impl ChatStorage {
             return Ok(None);
         }
 
-        let json = fs::read_to_string(&frame_path)?;
+        let json = self.read_sealed_string(&frame_path)?;
         let mut frame: OcrFrame = serde_json::from_str(&json)?;
         if retain_supported_ocr_frame_ids(&mut frame) {
             let normalized = serde_json::to_string_pretty(&frame)?;
-            fs::write(&frame_path, normalized)?;
+            self.write_sealed(&frame_path, normalized)?;
         }
         Ok(frame.get(canonical_model_id).cloned().unwrap_or(None))
     }
@@ -638,15 +969,40 @@ impl ChatStorage {
             return Ok(OcrFrame::new());
         }
 
-        let json = fs::read_to_string(&frame_path)?;
+        let json = self.read_sealed_string(&frame_path)?;
         let mut frame: OcrFrame = serde_json::from_str(&json)?;
         if retain_supported_ocr_frame_ids(&mut frame) {
             let normalized = serde_json::to_string_pretty(&frame)?;
-            fs::write(&frame_path, normalized)?;
+            self.write_sealed(&frame_path, normalized)?;
         }
         Ok(frame)
     }
 
+    /// Chats with an image but no OCR regions for `model_id` yet, most
+    /// recently updated first. Chats with automatic OCR turned off are left
+    /// out.
+    pub fn chats_missing_ocr(&self, model_id: &str) -> Result<Vec<ChatMetadata>> {
+        let model_id = model_id.trim();
+        if !is_supported_ocr_model_id(model_id) {
+            return Err(StorageError::InvalidOcrModel(model_id.to_string()));
+        }
+
+        let mut missing = Vec::new();
+        for metadata in self.list_chats()? {
+            if metadata.image_hash.is_empty() {
+                continue;
+            }
+            let frame = self.get_ocr_frame(&metadata.id)?;
+            if frame.contains_key(AUTO_OCR_DISABLED_MODEL_ID) {
+                continue;
+            }
+            if !matches!(frame.get(model_id), Some(Some(_))) {
+                missing.push(metadata);
+            }
+        }
+        Ok(missing)
+    }
+
     /// Initialize an OCR frame with null values for all given model IDs.
     /// Only adds keys that don't already exist (won't overwrite cached data).
     pub fn init_ocr_frame(&self, chat_id: &str, model_ids: &[String]) -> Result<()> {
@@ -655,7 +1011,7 @@ impl ChatStorage {
 
         let frame_path = chat_dir.join("ocr_frame.json");
         let mut frame: OcrFrame = if frame_path.exists() {
-            let json = fs::read_to_string(&frame_path)?;
+            let json = self.read_sealed_string(&frame_path)?;
             serde_json::from_str(&json)?
         } else {
             OcrFrame::new()
@@ -669,7 +1025,7 @@ impl ChatStorage {
         }
 
         let json = serde_json::to_string_pretty(&frame)?;
-        fs::write(&frame_path, json)?;
+        self.write_sealed(&frame_path, json)?;
 
         Ok(())
     }
@@ -680,7 +1036,7 @@ impl ChatStorage {
         fs::create_dir_all(&chat_dir)?;
 
         let url_path = chat_dir.join("imgbb_url.txt");
-        fs::write(&url_path, url)?;
+        write_atomic(&url_path, url)?;
 
         Ok(())
     }
@@ -703,7 +1059,7 @@ impl ChatStorage {
         fs::create_dir_all(&chat_dir)?;
 
         let summary_path = chat_dir.join("rolling_summary.txt");
-        fs::write(&summary_path, summary)?;
+        self.write_sealed(&summary_path, summary)?;
 
         Ok(())
     }
@@ -716,7 +1072,7 @@ impl ChatStorage {
             return Ok(None);
         }
 
-        let summary = fs::read_to_string(&summary_path)?;
+        let summary = self.read_sealed_string(&summary_path)?;
         Ok(Some(summary))
     }
 
@@ -729,18 +1085,20 @@ impl ChatStorage {
         let messages_path = chat_dir.join("messages.md");
 
         // Keep a structured JSON transcript for metadata-aware rendering.
-        let mut json_messages: Vec<ChatMessage> = if messages_json_path.exists() {
-            let json = fs::read_to_string(&messages_json_path)?;
-            serde_json::from_str(&json)?
-        } else if messages_path.exists() {
-            // One-time migration path for older chats that only have markdown.
-            let md_content = fs::read_to_string(&messages_path)?;
-            self.markdown_to_messages(&md_content)
-        } else {
-            Vec::new()
-        };
+        // Older chats that only have markdown are migrated here.
+        let mut json_messages = self.read_messages(&chat_dir)?;
+        assign_message_ids(&mut json_messages);
+        let mut message = message.clone();
+        if message.id.is_empty() {
+            message.id = ChatMessage::generate_id();
+        }
         json_messages.push(message.clone());
-        fs::write(
+        if self.encryption.is_some() {
+            // Ciphertext cannot be appended to; rewrite both files.
+            self.write_messages(&chat_dir, &json_messages)?;
+            return self.touch_chat(chat_id);
+        }
+        write_atomic(
             &messages_json_path,
             serde_json::to_string_pretty(&json_messages)?,
         )?;
@@ -750,30 +1108,71 @@ impl ChatStorage {
             .create(true)
             .append(true)
             .open(&messages_path)?;
-        let md_entry = self.message_to_markdown(message);
+        let md_entry = self.message_to_markdown(&message);
         md_file.write_all(md_entry.as_bytes())?;
 
-        // Update the chat's updated_at timestamp
-        let meta_path = chat_dir.join("meta.json");
+        self.touch_chat(chat_id)
+    }
+
+    // =========================================================================
+    // Internal Helpers
+    // =========================================================================
+
+    /// The chat's messages, from the structured JSON or, for older chats,
+    /// the markdown transcript.
+    pub(crate) fn read_messages(&self, chat_dir: &Path) -> Result<Vec<ChatMessage>> {
+        let messages_json_path = chat_dir.join("messages.json");
+        let messages_path = chat_dir.join("messages.md");
+        if messages_json_path.exists() {
+            let json_content = self.read_sealed_string(&messages_json_path)?;
+            Ok(serde_json::from_str(&json_content)?)
+        } else if messages_path.exists() {
+            let md_content = self.read_sealed_string(&messages_path)?;
+            Ok(self.markdown_to_messages(&md_content))
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Write both message files.
+    /// - messages.json: canonical structured source for metadata-aware rendering
+    /// - messages.md: backward-compatible human-readable transcript
+    pub(crate) fn write_messages(&self, chat_dir: &Path, messages: &[ChatMessage]) -> Result<()> {
+        let messages_json_path = chat_dir.join("messages.json");
+        let messages_path = chat_dir.join("messages.md");
+        if !messages.is_empty() {
+            let json_content = serde_json::to_string_pretty(messages)?;
+            self.write_sealed(&messages_json_path, json_content)?;
+            let md_content = self.messages_to_markdown(messages);
+            self.write_sealed(&messages_path, md_content)?;
+        } else {
+            if messages_path.exists() {
+                fs::remove_file(&messages_path)?;
+            }
+            if messages_json_path.exists() {
+                fs::remove_file(&messages_json_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Bump the chat's `updated_at`, if it has metadata yet.
+    pub(crate) fn touch_chat(&self, chat_id: &str) -> Result<()> {
+        let meta_path = self.chat_dir(chat_id).join("meta.json");
         if meta_path.exists() {
             let meta_json = fs::read_to_string(&meta_path)?;
             let mut metadata: ChatMetadata = serde_json::from_str(&meta_json)?;
             metadata.updated_at = chrono::Utc::now();
             let updated_json = serde_json::to_string_pretty(&metadata)?;
-            fs::write(&meta_path, updated_json)?;
+            write_atomic(&meta_path, updated_json)?;
             self.update_index(&metadata)?;
         }
-
         Ok(())
     }
 
-    // =========================================================================
-    // Internal Helpers
-    // =========================================================================
-
     /// Update the index with chat metadata.
-    fn update_index(&self, metadata: &ChatMetadata) -> Result<()> {
-        let mut chats = self.list_chats().unwrap_or_default();
+    pub(crate) fn update_index(&self, metadata: &ChatMetadata) -> Result<()> {
+        let mut chats = self.list_chats()?;
 
         // Remove existing entry if present
         chats.retain(|c| c.id != metadata.id);
@@ -782,21 +1181,21 @@ impl ChatStorage {
         chats.push(metadata.clone());
 
         // Sort by updated_at descending
-        chats.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        chats.sort_by_key(|a| std::cmp::Reverse(a.updated_at));
 
         let json = serde_json::to_string_pretty(&chats)?;
-        fs::write(&self.index_path, json)?;
+        write_atomic(&self.index_path, json)?;
 
         Ok(())
     }
 
     /// Remove a chat from the index.
-    fn remove_from_index(&self, chat_id: &str) -> Result<()> {
-        let mut chats = self.list_chats().unwrap_or_default();
+    pub(crate) fn remove_from_index(&self, chat_id: &str) -> Result<()> {
+        let mut chats = self.list_chats()?;
         chats.retain(|c| c.id != chat_id);
 
         let json = serde_json::to_string_pretty(&chats)?;
-        fs::write(&self.index_path, json)?;
+        write_atomic(&self.index_path, json)?;
 
         Ok(())
     }
@@ -838,11 +1237,14 @@ impl ChatStorage {
                 // Save previous message if any
                 if let Some(role) = current_role.take() {
                     messages.push(ChatMessage {
+                        id: ChatMessage::generate_id(),
                         role,
                         content: current_content.trim().to_string(),
                         timestamp: current_timestamp.unwrap_or_else(chrono::Utc::now),
                         citations: Vec::new(),
                         tool_steps: Vec::new(),
+                        model: None,
+                        revisions: Vec::new(),
                     });
                 }
                 current_role = Some("user".to_string());
@@ -852,11 +1254,14 @@ impl ChatStorage {
                 // Save previous message if any
                 if let Some(role) = current_role.take() {
                     messages.push(ChatMessage {
+                        id: ChatMessage::generate_id(),
                         role,
                         content: current_content.trim().to_string(),
                         timestamp: current_timestamp.unwrap_or_else(chrono::Utc::now),
                         citations: Vec::new(),
                         tool_steps: Vec::new(),
+                        model: None,
+                        revisions: Vec::new(),
                     });
                 }
                 current_role = Some("assistant".to_string());
@@ -877,11 +1282,14 @@ impl ChatStorage {
         // Save last message
         if let Some(role) = current_role {
             messages.push(ChatMessage {
+                id: ChatMessage::generate_id(),
                 role,
                 content: current_content.trim().to_string(),
                 timestamp: current_timestamp.unwrap_or_else(chrono::Utc::now),
                 citations: Vec::new(),
                 tool_steps: Vec::new(),
+                model: None,
+                revisions: Vec::new(),
             });
         }
 
@@ -892,19 +1300,13 @@ impl ChatStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::test_storage;
     #[allow(unused_imports)]
     use crate::types::{ChatAttachmentKind, ChatAttachmentRecord};
 
-    fn make_test_storage() -> (ChatStorage, PathBuf) {
-        let base_dir =
-            std::env::temp_dir().join(format!("squigit-ocr-storage-test-{}", uuid::Uuid::new_v4()));
-        let storage = ChatStorage::with_base_dir(base_dir.clone()).expect("storage init");
-        (storage, base_dir)
-    }
-
     #[test]
     fn auto_ocr_disabled_key_is_preserved_and_does_not_overwrite_english() {
-        let (storage, base_dir) = make_test_storage();
+        let (storage, _dir) = test_storage();
         let metadata = ChatMetadata::new("Test".to_string(), "0".repeat(64), None);
         let chat = ChatData::new(metadata.clone());
         storage.save_chat(&chat).expect("save chat");
@@ -912,6 +1314,10 @@ mod tests {
         let en_regions = vec![OcrRegion {
             text: "hello".to_string(),
             bbox: vec![vec![0, 0], vec![10, 0], vec![10, 10], vec![0, 10]],
+            confidence: Some(0.98),
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
         }];
 
         storage
@@ -933,23 +1339,147 @@ mod tests {
             .and_then(|v| v.as_ref())
             .expect("auto-disable marker present");
         assert!(auto_disabled.is_empty());
+    }
+
+    #[test]
+    fn saved_ocr_regions_are_labeled_for_pii() {
+        let (storage, _dir) = test_storage();
+        let region = |text: &str| OcrRegion {
+            text: text.to_string(),
+            bbox: vec![vec![0, 0], vec![10, 0], vec![10, 10], vec![0, 10]],
+            confidence: Some(0.9),
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        };
+        let regions = vec![region("Invoice #42"), region("billing@example.com")];
+
+        storage
+            .save_ocr_data("chat-pii", DEFAULT_OCR_MODEL_ID, &regions)
+            .expect("save ocr");
+        let saved = storage
+            .get_ocr_data("chat-pii", DEFAULT_OCR_MODEL_ID)
+            .expect("load ocr")
+            .expect("ocr present");
+        assert!(saved[0].pii.is_empty());
+        assert_eq!(saved[1].pii, vec![ops_redaction::PiiKind::Email]);
+    }
+
+    #[test]
+    fn chats_missing_ocr_skip_scanned_and_opted_out_chats() {
+        let (storage, _dir) = test_storage();
+        let save = |title: &str| {
+            let metadata = ChatMetadata::new(title.to_string(), "0".repeat(64), None);
+            storage.save_chat(&ChatData::new(metadata.clone())).unwrap();
+            metadata.id
+        };
+        let scanned = save("Scanned");
+        let pending = save("Pending");
+        let opted_out = save("Opted out");
+        let text_only = ChatMetadata::new("Text".to_string(), String::new(), None);
+        storage.save_chat(&ChatData::new(text_only)).unwrap();
 
-        let _ = std::fs::remove_dir_all(base_dir);
+        storage
+            .save_ocr_data(&scanned, "pp-ocr-v5-latin", &[])
+            .unwrap();
+        storage
+            .init_ocr_frame(&pending, &["pp-ocr-v5-latin".to_string()])
+            .unwrap();
+        storage
+            .save_ocr_data(&opted_out, AUTO_OCR_DISABLED_MODEL_ID, &[])
+            .unwrap();
+
+        let missing: Vec<String> = storage
+            .chats_missing_ocr("pp-ocr-v5-latin")
+            .unwrap()
+            .into_iter()
+            .map(|metadata| metadata.id)
+            .collect();
+        assert_eq!(missing, vec![pending]);
+        assert!(matches!(
+            storage.chats_missing_ocr(AUTO_OCR_DISABLED_MODEL_ID),
+            Err(StorageError::InvalidOcrModel(_))
+        ));
     }
 
     #[test]
     fn invalid_ocr_model_id_returns_error() {
-        let (storage, base_dir) = make_test_storage();
+        let (storage, _dir) = test_storage();
         let result = storage.save_ocr_data("chat-1", "bogus-model", &[]);
 
         assert!(matches!(result, Err(StorageError::InvalidOcrModel(_))));
+    }
+
+    #[test]
+    fn chat_ocr_lang_override_is_validated_and_saved() {
+        let (storage, _dir) = test_storage();
+        let metadata = ChatMetadata::new("Test".to_string(), "0".repeat(64), None);
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+
+        let result = storage.set_chat_ocr_lang(&metadata.id, "bogus-model");
+        assert!(matches!(result, Err(StorageError::InvalidOcrModel(_))));
+
+        storage
+            .set_chat_ocr_lang(&metadata.id, "pp-ocr-v5-cjk")
+            .expect("set ocr lang");
+        let loaded = storage.load_chat(&metadata.id).expect("load chat");
+        assert_eq!(loaded.metadata.ocr_lang.as_deref(), Some("pp-ocr-v5-cjk"));
+    }
+
+    #[test]
+    fn chat_attachments_keep_their_order() {
+        let (storage, _dir) = test_storage();
+        let first = storage.store_image(b"first", None).expect("store image");
+        let second = storage.store_image(b"second", None).expect("store image");
+        let notes = storage
+            .store_file(b"notes", "pdf", None)
+            .expect("store file");
+        let metadata = ChatMetadata::new("Steps".to_string(), first.hash.clone(), None);
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+
+        storage
+            .add_chat_attachment(&metadata.id, &second.hash, None, Some(" After "))
+            .expect("add image");
+        storage
+            .add_chat_attachment(&metadata.id, &notes.hash, None, None)
+            .expect("add file");
+        let updated = storage
+            .add_chat_attachment(&metadata.id, &second.hash, None, None)
+            .expect("add again");
+        assert_eq!(updated.attachments.len(), 2);
+        assert_eq!(updated.attachments[0].label.as_deref(), Some("After"));
+        assert_eq!(updated.attachments[1].mime, "application/pdf");
+        assert_eq!(
+            updated.image_hashes().collect::<Vec<_>>(),
+            vec![
+                first.hash.as_str(),
+                second.hash.as_str(),
+                notes.hash.as_str()
+            ]
+        );
+        assert!(storage
+            .add_chat_attachment(&metadata.id, &"f".repeat(64), None, None)
+            .is_err());
 
-        let _ = std::fs::remove_dir_all(base_dir);
+        storage
+            .remove_chat_attachment(&metadata.id, &second.hash)
+            .expect("remove");
+        let loaded = storage.load_chat(&metadata.id).expect("load chat");
+        assert_eq!(loaded.metadata.attachments.len(), 1);
+        assert_eq!(loaded.metadata.attachments[0].hash, notes.hash);
+        assert!(matches!(
+            storage.remove_chat_attachment(&metadata.id, &second.hash),
+            Err(StorageError::AttachmentNotFound(_))
+        ));
     }
 
     #[test]
     fn attachment_registry_round_trips_via_sidecar() {
-        let (storage, base_dir) = make_test_storage();
+        let (storage, _dir) = test_storage();
         let metadata = ChatMetadata::new("Registry".to_string(), "0".repeat(64), None);
         let mut chat = ChatData::new(metadata.clone());
         chat.attachment_registry.insert(
@@ -973,7 +1503,228 @@ mod tests {
         assert!(loaded
             .attachment_registry
             .contains_key("/tmp/chats/objects/ab/file.pdf"));
+    }
+
+    #[test]
+    fn corrupt_index_is_rebuilt_from_chat_directories() {
+        let (storage, base_dir) = test_storage();
+        let first = ChatMetadata::new("First".to_string(), "0".repeat(64), None);
+        let second = ChatMetadata::new("Second".to_string(), "1".repeat(64), None);
+        for metadata in [&first, &second] {
+            storage.save_chat(&ChatData::new(metadata.clone())).unwrap();
+        }
+
+        std::fs::write(base_dir.path().join("index.json"), "[{\"id\": \"trunc").unwrap();
+        let third = ChatMetadata::new("Third".to_string(), "2".repeat(64), None);
+        storage.save_chat(&ChatData::new(third)).unwrap();
+        assert_eq!(storage.list_chats().unwrap().len(), 3);
+
+        std::fs::remove_file(base_dir.path().join("index.json")).unwrap();
+        let ids: Vec<_> = storage
+            .list_chats()
+            .unwrap()
+            .into_iter()
+            .map(|chat| chat.id)
+            .collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.contains(&first.id) && ids.contains(&second.id));
+
+        let leftovers = std::fs::read_dir(base_dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().contains(".tmp-"))
+            .count();
+        assert_eq!(leftovers, 0);
+    }
 
-        let _ = std::fs::remove_dir_all(base_dir);
+    #[test]
+    fn markdown_only_chats_get_stable_message_ids() {
+        let (storage, _dir) = test_storage();
+        let metadata = ChatMetadata::new("Legacy".to_string(), "0".repeat(64), None);
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+        let chat_dir = storage.chat_dir(&metadata.id);
+        std::fs::write(
+            chat_dir.join("messages.md"),
+            "## User\n<!-- 2026-01-01T00:00:00+00:00 -->\n\nHi\n\n",
+        )
+        .unwrap();
+
+        let first = storage.load_chat(&metadata.id).unwrap().messages;
+        assert_eq!(first.len(), 1);
+        assert!(!first[0].id.is_empty());
+        let second = storage.load_chat(&metadata.id).unwrap().messages;
+        assert_eq!(second[0].id, first[0].id);
+    }
+
+    #[test]
+    fn device_pixel_ratio_defaults_for_older_chats() {
+        let (storage, _dir) = test_storage();
+        let mut metadata = ChatMetadata::new("Retina".to_string(), "0".repeat(64), None);
+        metadata.device_pixel_ratio = 2.0;
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+        assert_eq!(
+            storage
+                .load_chat(&metadata.id)
+                .unwrap()
+                .metadata
+                .device_pixel_ratio,
+            2.0
+        );
+
+        let meta_path = storage.chat_dir(&metadata.id).join("meta.json");
+        let mut json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&meta_path).unwrap()).unwrap();
+        json.as_object_mut().unwrap().remove("device_pixel_ratio");
+        std::fs::write(&meta_path, json.to_string()).unwrap();
+        let legacy = storage.load_chat(&metadata.id).unwrap().metadata;
+        assert_eq!(legacy.device_pixel_ratio, 1.0);
+
+        let region = OcrRegion {
+            text: "hi".to_string(),
+            bbox: vec![vec![20, 40], vec![61, 40]],
+            confidence: None,
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        };
+        assert_eq!(region.logical_bbox(2.0), vec![vec![10, 20], vec![31, 20]]);
+        assert_eq!(region.logical_bbox(0.0), region.bbox);
+    }
+
+    #[test]
+    fn dangling_user_turns_only_include_recent_unanswered_chats() {
+        let (storage, _dir) = test_storage();
+        let since = chrono::Utc::now() - chrono::Duration::hours(1);
+
+        let pending = ChatMetadata::new("Pending".to_string(), "0".repeat(64), None);
+        let mut chat = ChatData::new(pending.clone());
+        chat.messages
+            .push(ChatMessage::user("What is this?".to_string()));
+        storage.save_chat(&chat).expect("save pending chat");
+
+        let answered = ChatMetadata::new("Answered".to_string(), "0".repeat(64), None);
+        let mut chat = ChatData::new(answered);
+        chat.messages.push(ChatMessage::user("Hi".to_string()));
+        chat.messages
+            .push(ChatMessage::assistant("Hello".to_string()));
+        storage.save_chat(&chat).expect("save answered chat");
+
+        let stale = ChatMetadata::new("Stale".to_string(), "0".repeat(64), None);
+        let mut chat = ChatData::new(stale);
+        let mut old_message = ChatMessage::user("Old".to_string());
+        old_message.timestamp = since - chrono::Duration::hours(1);
+        chat.messages.push(old_message);
+        storage.save_chat(&chat).expect("save stale chat");
+
+        let turns = storage.find_dangling_user_turns(since).expect("find turns");
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].chat_id, pending.id);
+        assert_eq!(turns[0].user_message, "What is this?");
+    }
+
+    #[test]
+    fn plugin_notes_replace_by_plugin_and_load_with_chat() {
+        let (storage, _dir) = test_storage();
+        let metadata = ChatMetadata::new("Plugins".to_string(), "0".repeat(64), None);
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+
+        let note = |plugin_id: &str, context: &str| PluginNote {
+            plugin_id: plugin_id.to_string(),
+            plugin_name: plugin_id.to_string(),
+            context: Some(context.to_string()),
+            annotations: Vec::new(),
+            created_at: chrono::Utc::now(),
+        };
+        storage
+            .save_plugin_note(&metadata.id, &note("invoice", "first"))
+            .expect("save note");
+        storage
+            .save_plugin_note(&metadata.id, &note("barcode", "code"))
+            .expect("save note");
+        storage
+            .save_plugin_note(&metadata.id, &note("invoice", "second"))
+            .expect("replace note");
+
+        let loaded = storage.load_chat(&metadata.id).expect("load chat");
+        let contexts: Vec<_> = loaded
+            .plugin_notes
+            .iter()
+            .map(|note| (note.plugin_id.as_str(), note.context.as_deref()))
+            .collect();
+        assert_eq!(
+            contexts,
+            vec![("barcode", Some("code")), ("invoice", Some("second"))]
+        );
+        assert!(storage
+            .save_plugin_note("missing", &note("invoice", "x"))
+            .is_err());
+    }
+
+    #[test]
+    fn extractions_replace_by_kind_and_load_with_chat() {
+        let (storage, _dir) = test_storage();
+        let metadata = ChatMetadata::new("Receipt".to_string(), "0".repeat(64), None);
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+
+        let extraction = |kind: &str, total: f64| Extraction {
+            kind: kind.to_string(),
+            data: serde_json::json!({ "total": total }),
+            model: None,
+            created_at: chrono::Utc::now(),
+        };
+        storage
+            .save_extraction(&metadata.id, &extraction("receipt", 4.5))
+            .expect("save extraction");
+        storage
+            .save_extraction(&metadata.id, &extraction("invoice", 100.0))
+            .expect("save extraction");
+        storage
+            .save_extraction(&metadata.id, &extraction("receipt", 5.0))
+            .expect("replace extraction");
+
+        let loaded = storage.load_chat(&metadata.id).expect("load chat");
+        let kinds: Vec<_> = loaded
+            .extractions
+            .iter()
+            .map(|extraction| (extraction.kind.as_str(), extraction.data["total"].as_f64()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![("invoice", Some(100.0)), ("receipt", Some(5.0))]
+        );
+        assert!(storage
+            .save_extraction("missing", &extraction("receipt", 1.0))
+            .is_err());
+    }
+
+    #[test]
+    fn web_source_loads_with_chat() {
+        let (storage, _dir) = test_storage();
+        let metadata = ChatMetadata::new("Page".to_string(), "0".repeat(64), None);
+        storage
+            .save_chat(&ChatData::new(metadata.clone()))
+            .expect("save chat");
+        assert_eq!(storage.load_chat(&metadata.id).unwrap().web_source, None);
+
+        let source = WebSource {
+            url: "https://example.com/docs".to_string(),
+            title: Some("Docs".to_string()),
+            selected_text: Some("install with cargo".to_string()),
+        };
+        storage
+            .save_web_source(&metadata.id, &source)
+            .expect("save source");
+        assert_eq!(
+            storage.load_chat(&metadata.id).unwrap().web_source,
+            Some(source)
+        );
     }
 }
@@ -0,0 +1,16 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fixtures shared by the unit tests.
+
+use tempfile::TempDir;
+
+use crate::storage::ChatStorage;
+
+/// A storage in a fresh temporary directory. The directory is removed when
+/// the returned guard drops, so keep it alive for the whole test.
+pub(crate) fn test_storage() -> (ChatStorage, TempDir) {
+    let dir = TempDir::with_prefix("squigit-storage-test-").expect("temp dir");
+    let storage = ChatStorage::with_base_dir(dir.path().to_path_buf()).expect("storage init");
+    (storage, dir)
+}
@@ -3,7 +3,9 @@
 
 //! Type definitions for chat storage.
 
+use crate::artifacts::Artifact;
 use chrono::{DateTime, Utc};
+use ops_redaction::PiiKind;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
@@ -34,20 +36,49 @@ pub struct ChatMetadata {
     /// Image tone detected upon upload (light/dark).
     #[serde(default)]
     pub image_tone: Option<String>,
+    /// One-line summary of the conversation, shown under the title.
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// How the chat's image was captured, e.g. "rectangular" or "squiggle".
+    #[serde(default)]
+    pub capture_type: Option<String>,
+    /// Delete the image after the first reply. `None` follows the
+    /// ephemeral captures preference.
+    #[serde(default)]
+    pub ephemeral: Option<bool>,
+    /// The image was deleted after analysis; `image_hash` is empty.
+    #[serde(default)]
+    pub image_discarded: bool,
+    /// Physical pixels per logical pixel of the display the image was
+    /// captured on. OCR boxes are in image pixels; divide by this to place
+    /// them over the display. 1.0 for uploads and older chats.
+    #[serde(default = "default_device_pixel_ratio")]
+    pub device_pixel_ratio: f64,
+    /// The chat this one was forked from, if any.
+    #[serde(default)]
+    pub parent_chat_id: Option<String>,
+    /// Files added after `image_hash`, e.g. follow-up screenshots, in the
+    /// order they were added.
+    #[serde(default)]
+    pub attachments: Vec<ChatAttachment>,
+    /// Labels for finding the chat later; see [`crate::organize`].
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `/`-separated folder the chat is filed under, if any.
+    #[serde(default)]
+    pub folder: Option<String>,
+}
+
+fn default_device_pixel_ratio() -> f64 {
+    1.0
 }
 
 impl ChatMetadata {
     /// Create new thread metadata with a generated ID.
     pub fn new(title: String, image_hash: String, ocr_lang: Option<String>) -> Self {
         let now = Utc::now();
-        // Generate ID: YYYYMMDD-HHMMSS-<UUID_SUFFIX>
-        // Use first 8 chars of a UUID for randomness
-        let date_part = now.format("%Y%m%d-%H%M%S").to_string();
-        let uuid_part = Uuid::new_v4().to_string();
-        let id = format!("{}-{}", date_part, &uuid_part[..8]);
-
         Self {
-            id,
+            id: Self::generate_id(now),
             title,
             created_at: now,
             updated_at: now,
@@ -57,13 +88,55 @@ impl ChatMetadata {
             pinned_at: None,
             ocr_lang,
             image_tone: None,
+            summary: None,
+            capture_type: None,
+            ephemeral: None,
+            image_discarded: false,
+            device_pixel_ratio: default_device_pixel_ratio(),
+            parent_chat_id: None,
+            attachments: Vec::new(),
+            tags: Vec::new(),
+            folder: None,
         }
     }
+
+    /// Hashes of the chat's image and attachments, in order.
+    pub fn image_hashes(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.image_hash.as_str())
+            .filter(|hash| !hash.is_empty())
+            .chain(
+                self.attachments
+                    .iter()
+                    .map(|attachment| attachment.hash.as_str()),
+            )
+    }
+
+    /// A new chat ID: `YYYYMMDD-HHMMSS-<first 8 chars of a UUID>`.
+    pub(crate) fn generate_id(now: DateTime<Utc>) -> String {
+        let date_part = now.format("%Y%m%d-%H%M%S").to_string();
+        let uuid_part = Uuid::new_v4().to_string();
+        format!("{}-{}", date_part, &uuid_part[..8])
+    }
+}
+
+/// A stored file added to a chat after its first image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatAttachment {
+    /// BLAKE3 hash of the CAS object.
+    pub hash: String,
+    pub mime: String,
+    pub added_at: DateTime<Utc>,
+    /// Short caption, e.g. "Settings page after the fix".
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 /// A single chat message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
+    /// Stable ID within the chat. Older transcripts get one when loaded.
+    #[serde(default)]
+    pub id: String,
     /// Role: "user" or "assistant".
     pub role: String,
     /// Message content (markdown).
@@ -76,6 +149,27 @@ pub struct ChatMessage {
     /// Optional tool call timeline metadata for this message.
     #[serde(default)]
     pub tool_steps: Vec<ToolStep>,
+    /// Model that wrote an assistant message, when known.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Earlier versions of this message, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<MessageRevision>,
+}
+
+/// A replaced version of a message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageRevision {
+    pub content: String,
+    /// When this version was written.
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// When it was replaced.
+    pub replaced_at: DateTime<Utc>,
+    /// Messages that followed this version and were dropped with it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub following: Vec<ChatMessage>,
 }
 
 /// Structured citation source metadata persisted with a message.
@@ -104,24 +198,40 @@ impl ChatMessage {
     /// Create a new user message.
     pub fn user(content: String) -> Self {
         Self {
+            id: Self::generate_id(),
             role: "user".to_string(),
             content,
             timestamp: Utc::now(),
             citations: Vec::new(),
             tool_steps: Vec::new(),
+            model: None,
+            revisions: Vec::new(),
         }
     }
 
     /// Create a new assistant message.
     pub fn assistant(content: String) -> Self {
         Self {
+            id: Self::generate_id(),
             role: "assistant".to_string(),
             content,
             timestamp: Utc::now(),
             citations: Vec::new(),
             tool_steps: Vec::new(),
+            model: None,
+            revisions: Vec::new(),
         }
     }
+
+    /// Record the model that wrote this message.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub(crate) fn generate_id() -> String {
+        format!("msg-{}", Uuid::new_v4().simple())
+    }
 }
 
 /// OCR data for an image region.
@@ -131,6 +241,83 @@ pub struct OcrRegion {
     pub text: String,
     /// Bounding box coordinates.
     pub bbox: Vec<Vec<i32>>,
+    /// Recognition confidence reported by the OCR engine (0.0 - 1.0).
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    /// Whether the region fell below the confidence threshold used for the scan.
+    #[serde(default)]
+    pub low_confidence: bool,
+    /// LaTeX source recovered by the formula recognition pass, if any.
+    #[serde(default)]
+    pub latex: Option<String>,
+    /// Kinds of personal data in `text`, labeled when the region is saved.
+    #[serde(default)]
+    pub pii: Vec<PiiKind>,
+}
+
+impl OcrRegion {
+    /// The bounding box in logical pixels of a display with
+    /// `device_pixel_ratio`, see [`ChatMetadata::device_pixel_ratio`].
+    pub fn logical_bbox(&self, device_pixel_ratio: f64) -> Vec<Vec<i32>> {
+        let ratio = if device_pixel_ratio > 0.0 {
+            device_pixel_ratio
+        } else {
+            1.0
+        };
+        self.bbox
+            .iter()
+            .map(|point| {
+                point
+                    .iter()
+                    .map(|&c| (f64::from(c) / ratio).round() as i32)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Text used when exporting the region: display math for formulas,
+    /// plain OCR text otherwise.
+    pub fn export_text(&self) -> String {
+        match self.latex.as_deref().map(str::trim) {
+            Some(latex) if !latex.is_empty() => format!("$${}$$", latex),
+            _ => self.text.clone(),
+        }
+    }
+
+    /// Label the personal data in `text`, replacing earlier labels.
+    pub fn label_pii(&mut self) {
+        self.pii = ops_redaction::classify(&self.text);
+    }
+}
+
+/// Summary of how many OCR regions were flagged as low-confidence.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrConfidenceSummary {
+    /// Total number of regions in the scan.
+    pub total_regions: usize,
+    /// Number of regions flagged as low-confidence.
+    pub low_confidence_regions: usize,
+}
+
+impl OcrConfidenceSummary {
+    /// Count flagged regions in a stored OCR result.
+    pub fn from_regions(regions: &[OcrRegion]) -> Self {
+        Self {
+            total_regions: regions.len(),
+            low_confidence_regions: regions.iter().filter(|r| r.low_confidence).count(),
+        }
+    }
+
+    /// Human-readable summary, e.g. "12 regions below threshold".
+    /// Returns `None` when nothing was flagged.
+    pub fn describe(&self) -> Option<String> {
+        match self.low_confidence_regions {
+            0 => None,
+            1 => Some("1 region below threshold".to_string()),
+            n => Some(format!("{} regions below threshold", n)),
+        }
+    }
 }
 
 /// OCR frame: a map of model_id → cached OCR results.
@@ -207,6 +394,18 @@ pub struct ChatData {
     /// Generated concise text description of the session's startup image.
     #[serde(default)]
     pub image_brief: Option<String>,
+    /// Output of post-capture processor plugins, one note per plugin.
+    #[serde(default)]
+    pub plugin_notes: Vec<PluginNote>,
+    /// The web page the image was captured from, for browser captures.
+    #[serde(default)]
+    pub web_source: Option<WebSource>,
+    /// Structured data extracted from the image, one per extraction kind.
+    #[serde(default)]
+    pub extractions: Vec<Extraction>,
+    /// Files generated for the chat.
+    #[serde(default)]
+    pub artifacts: Vec<Artifact>,
 }
 
 impl ChatData {
@@ -220,10 +419,64 @@ impl ChatData {
             rolling_summary: None,
             attachment_registry: BTreeMap::new(),
             image_brief: None,
+            plugin_notes: Vec::new(),
+            web_source: None,
+            extractions: Vec::new(),
+            artifacts: Vec::new(),
         }
     }
 }
 
+/// The page a browser capture came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSource {
+    pub url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Text the user had selected on the page.
+    #[serde(default)]
+    pub selected_text: Option<String>,
+}
+
+/// What a post-capture processor plugin attached to a chat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginNote {
+    /// ID of the plugin that produced the note.
+    pub plugin_id: String,
+    /// Display name of the plugin at the time it ran.
+    pub plugin_name: String,
+    /// Extra context for the model about the image.
+    #[serde(default)]
+    pub context: Option<String>,
+    /// Labeled findings shown alongside the chat.
+    #[serde(default)]
+    pub annotations: Vec<PluginAnnotation>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Structured data extracted from a chat's image, such as a receipt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Extraction {
+    /// Extraction profile ID, e.g. `receipt`.
+    pub kind: String,
+    /// Validated fields, keyed by the profile's field names.
+    pub data: serde_json::Value,
+    /// Model that produced the data.
+    #[serde(default)]
+    pub model: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A labeled finding returned by a plugin, e.g. `"Invoice total": "€42"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginAnnotation {
+    pub label: String,
+    pub text: String,
+}
+
 /// Result of storing an image.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredImage {
@@ -235,3 +488,15 @@ pub struct StoredImage {
     #[serde(default)]
     pub tone: Option<String>,
 }
+
+/// A chat whose last message is a user turn that never got a reply, e.g.
+/// because the app quit mid-generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DanglingUserTurn {
+    pub chat_id: String,
+    pub title: String,
+    /// The unanswered user message.
+    pub user_message: String,
+    /// When the user message was sent.
+    pub timestamp: DateTime<Utc>,
+}
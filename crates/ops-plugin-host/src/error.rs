@@ -0,0 +1,46 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Error types for plugin discovery and execution.
+
+use std::io;
+use thiserror::Error;
+
+/// Result type alias for plugin operations.
+pub type Result<T> = std::result::Result<T, PluginError>;
+
+/// Errors that can occur while loading or running a plugin.
+#[derive(Debug, Error)]
+pub enum PluginError {
+    /// `plugin.json` is missing a field or points outside the plugin.
+    #[error("Invalid plugin manifest: {0}")]
+    InvalidManifest(String),
+
+    /// WASM plugins need a WASI runtime on `PATH`.
+    #[error("WASM plugins need `{0}` on PATH")]
+    RuntimeMissing(&'static str),
+
+    /// The plugin did not finish within its time limit and was killed.
+    #[error("Plugin timed out after {0}s")]
+    TimedOut(u64),
+
+    /// The plugin wrote more than the allowed amount to stdout.
+    #[error("Plugin output exceeded {0} bytes")]
+    OutputTooLarge(usize),
+
+    /// The plugin exited unsuccessfully.
+    #[error("Plugin failed: {0}")]
+    Failed(String),
+
+    /// The plugin's stdout was not a valid response.
+    #[error("Invalid plugin output: {0}")]
+    InvalidOutput(String),
+
+    /// File system error.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    /// JSON serialization/deserialization error.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,35 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Post-capture processor plugins.
+//!
+//! Plugins are executables or WASI modules that look at a captured image
+//! and its OCR result and hand back extra context or annotations for the
+//! chat. They are discovered from a plugins directory, one subdirectory per
+//! plugin with a [`manifest::MANIFEST_FILE`], and run one at a time under
+//! [`PluginLimits`].
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use ops_plugin_host::{discover, run_plugin, PluginInput, PluginLimits};
+//! use sys_process_priority::{PowerProfile, SidecarRole};
+//!
+//! # async fn example() -> ops_plugin_host::Result<()> {
+//! let input = PluginInput::new("chat-id", "/path/to/image.png", None, Vec::new());
+//! let priority = PowerProfile::Balanced.policy(SidecarRole::Background);
+//! for plugin in discover("plugins".as_ref())? {
+//!     let output = run_plugin(&plugin, &input, &PluginLimits::default(), priority).await?;
+//!     println!("{}: {:?}", plugin.manifest.name, output.context);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod error;
+pub mod manifest;
+pub mod runner;
+
+pub use error::{PluginError, Result};
+pub use manifest::{discover, Plugin, PluginKind, PluginManifest};
+pub use runner::{run_plugin, PluginInput, PluginLimits, PluginOutput};
@@ -0,0 +1,234 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plugin manifests and discovery.
+//!
+//! Each plugin lives in its own directory under the plugins directory and
+//! describes itself in a `plugin.json`:
+//!
+//! ```json
+//! {
+//!   "id": "invoice-fields",
+//!   "name": "Invoice fields",
+//!   "description": "Pulls totals and due dates out of invoices",
+//!   "entry": "run.sh",
+//!   "args": ["--lang", "en"],
+//!   "timeoutSecs": 20
+//! }
+//! ```
+//!
+//! `entry` is relative to the plugin directory. Entries ending in `.wasm`
+//! run as WASI modules; anything else is executed directly.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PluginError, Result};
+
+/// Manifest filename inside a plugin directory.
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// Longest accepted plugin ID.
+const MAX_ID_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    /// Stable identifier; lowercase letters, digits, `-`, `_` and `.`.
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Executable or `.wasm` module, relative to the plugin directory.
+    pub entry: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Requested time limit. The host's limit still applies when lower.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PluginKind {
+    Executable,
+    Wasm,
+}
+
+/// A validated plugin found on disk.
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    pub manifest: PluginManifest,
+    pub dir: PathBuf,
+    pub entry_path: PathBuf,
+    pub kind: PluginKind,
+}
+
+impl Plugin {
+    /// Load and validate the plugin in `dir`.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest: PluginManifest =
+            serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)?;
+
+        if manifest.id.is_empty()
+            || manifest.id.len() > MAX_ID_LEN
+            || !manifest.id.chars().all(|c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.')
+            })
+        {
+            return Err(PluginError::InvalidManifest(format!(
+                "invalid id: {:?}",
+                manifest.id
+            )));
+        }
+        if manifest.name.trim().is_empty() {
+            return Err(PluginError::InvalidManifest("name is required".to_string()));
+        }
+
+        // The entry must stay inside the plugin directory.
+        let entry = Path::new(&manifest.entry);
+        if manifest.entry.is_empty()
+            || !entry
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(PluginError::InvalidManifest(format!(
+                "entry must be a relative path inside the plugin: {:?}",
+                manifest.entry
+            )));
+        }
+        let entry_path = dir.join(entry);
+        if !entry_path.is_file() {
+            return Err(PluginError::InvalidManifest(format!(
+                "entry not found: {}",
+                manifest.entry
+            )));
+        }
+
+        let kind = if entry
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"))
+        {
+            PluginKind::Wasm
+        } else {
+            PluginKind::Executable
+        };
+
+        Ok(Self {
+            manifest,
+            dir: dir.to_path_buf(),
+            entry_path,
+            kind,
+        })
+    }
+}
+
+/// Every valid plugin under `plugins_dir`, ordered by directory name.
+/// Invalid plugins and duplicate IDs are logged and skipped; a missing
+/// directory has no plugins.
+pub fn discover(plugins_dir: &Path) -> Result<Vec<Plugin>> {
+    if !plugins_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(plugins_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join(MANIFEST_FILE).is_file())
+        .collect();
+    dirs.sort();
+
+    let mut plugins: Vec<Plugin> = Vec::new();
+    for dir in dirs {
+        match Plugin::load(&dir) {
+            Ok(plugin) if plugins.iter().any(|p| p.manifest.id == plugin.manifest.id) => {
+                log::warn!(
+                    "Skipping plugin in {}: duplicate id {}",
+                    dir.display(),
+                    plugin.manifest.id
+                );
+            }
+            Ok(plugin) => plugins.push(plugin),
+            Err(e) => log::warn!("Skipping plugin in {}: {}", dir.display(), e),
+        }
+    }
+    Ok(plugins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_plugin(root: &Path, dir: &str, manifest: serde_json::Value, entry: Option<&str>) {
+        let dir = root.join(dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(MANIFEST_FILE), manifest.to_string()).unwrap();
+        if let Some(entry) = entry {
+            std::fs::write(dir.join(entry), b"").unwrap();
+        }
+    }
+
+    #[test]
+    fn discover_skips_invalid_and_duplicate_plugins() {
+        let root = tempdir().unwrap();
+        write_plugin(
+            root.path(),
+            "a-wasm",
+            serde_json::json!({ "id": "scan", "name": "Scan", "entry": "scan.wasm" }),
+            Some("scan.wasm"),
+        );
+        write_plugin(
+            root.path(),
+            "b-duplicate",
+            serde_json::json!({ "id": "scan", "name": "Scan again", "entry": "run.sh" }),
+            Some("run.sh"),
+        );
+        write_plugin(
+            root.path(),
+            "c-escape",
+            serde_json::json!({ "id": "escape", "name": "Escape", "entry": "../run.sh" }),
+            None,
+        );
+        write_plugin(
+            root.path(),
+            "d-missing-entry",
+            serde_json::json!({ "id": "missing", "name": "Missing", "entry": "run.sh" }),
+            None,
+        );
+        write_plugin(
+            root.path(),
+            "e-bad-id",
+            serde_json::json!({ "id": "Bad Id", "name": "Bad", "entry": "run.sh" }),
+            Some("run.sh"),
+        );
+        write_plugin(
+            root.path(),
+            "f-tool",
+            serde_json::json!({ "id": "tool", "name": "Tool", "entry": "bin/tool" }),
+            None,
+        );
+        std::fs::create_dir_all(root.path().join("f-tool/bin")).unwrap();
+        std::fs::write(root.path().join("f-tool/bin/tool"), b"").unwrap();
+
+        let plugins = discover(root.path()).unwrap();
+        let found: Vec<_> = plugins
+            .iter()
+            .map(|plugin| (plugin.manifest.id.as_str(), plugin.kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("scan", PluginKind::Wasm), ("tool", PluginKind::Executable)]
+        );
+    }
+
+    #[test]
+    fn missing_plugins_dir_has_no_plugins() {
+        let root = tempdir().unwrap();
+        assert!(discover(&root.path().join("plugins")).unwrap().is_empty());
+    }
+}
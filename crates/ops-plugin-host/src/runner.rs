@@ -0,0 +1,445 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Running a plugin once.
+//!
+//! The plugin gets a [`PluginInput`] as JSON on stdin and answers with a
+//! [`PluginOutput`] as JSON on stdout; empty stdout means it has nothing to
+//! add. It runs from its own directory with a minimal environment, at the
+//! background priority it is given, and is killed when it exceeds its time
+//! or output limits. On Linux its address space is capped as well.
+//!
+//! WASM modules run under `wasmtime` with no file system access except the
+//! image's directory, mounted at `/input`.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use ops_chat_storage::{OcrRegion, PluginAnnotation};
+use serde::{Deserialize, Serialize};
+use sys_process_priority::PriorityPolicy;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+#[cfg(unix)]
+use std::io;
+
+use crate::error::{PluginError, Result};
+use crate::manifest::{Plugin, PluginKind};
+
+/// Version of the stdin/stdout protocol, sent as `version`.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// WASI runtime used for `.wasm` plugins.
+const WASM_RUNTIME: &str = "wasmtime";
+
+/// Where the image directory is mounted for WASM plugins.
+const WASM_INPUT_DIR: &str = "/input";
+
+/// Stderr kept for error messages.
+const MAX_STDERR_BYTES: usize = 16 * 1024;
+
+/// Longest `context` kept from a plugin.
+const MAX_CONTEXT_CHARS: usize = 8_000;
+
+/// Most annotations kept from a plugin.
+const MAX_ANNOTATIONS: usize = 50;
+
+/// What a plugin receives on stdin.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInput {
+    pub version: u32,
+    pub chat_id: String,
+    pub image_path: PathBuf,
+    /// OCR model the regions came from, if OCR ran.
+    pub ocr_model: Option<String>,
+    pub ocr: Vec<OcrRegion>,
+}
+
+impl PluginInput {
+    pub fn new(
+        chat_id: impl Into<String>,
+        image_path: impl Into<PathBuf>,
+        ocr_model: Option<String>,
+        ocr: Vec<OcrRegion>,
+    ) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            chat_id: chat_id.into(),
+            image_path: image_path.into(),
+            ocr_model,
+            ocr,
+        }
+    }
+}
+
+/// What a plugin may answer on stdout.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginOutput {
+    /// Extra context for the model about the image.
+    pub context: Option<String>,
+    pub annotations: Vec<PluginAnnotation>,
+}
+
+impl PluginOutput {
+    /// Whether the plugin had nothing to add.
+    pub fn is_empty(&self) -> bool {
+        self.context.is_none() && self.annotations.is_empty()
+    }
+}
+
+/// Sandboxing limits applied to every plugin run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PluginLimits {
+    /// Upper bound on run time; manifests may only ask for less.
+    pub timeout_secs: u64,
+    pub max_output_bytes: usize,
+    /// Address space cap in MiB. Only enforced on Linux.
+    pub memory_limit_mb: Option<u64>,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            max_output_bytes: 256 * 1024,
+            memory_limit_mb: Some(1024),
+        }
+    }
+}
+
+/// Run `plugin` on `input` and return its cleaned-up output.
+pub async fn run_plugin(
+    plugin: &Plugin,
+    input: &PluginInput,
+    limits: &PluginLimits,
+    priority: PriorityPolicy,
+) -> Result<PluginOutput> {
+    let timeout_secs = plugin
+        .manifest
+        .timeout_secs
+        .map_or(limits.timeout_secs, |requested| {
+            requested.min(limits.timeout_secs)
+        })
+        .max(1);
+
+    let (mut cmd, payload) = match plugin.kind {
+        PluginKind::Executable => (
+            tokio::process::Command::new(&plugin.entry_path),
+            serde_json::to_vec(input)?,
+        ),
+        PluginKind::Wasm => wasm_command(plugin, input)?,
+    };
+    cmd.args(&plugin.manifest.args)
+        .current_dir(&plugin.dir)
+        .env_clear()
+        .envs(passthrough_env())
+        .env("SQUIGIT_PLUGIN_PROTOCOL", PROTOCOL_VERSION.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW | sys_process_priority::priority_class(&priority));
+    }
+
+    #[cfg(unix)]
+    {
+        #[cfg(target_os = "linux")]
+        let memory_limit_bytes = limits
+            .memory_limit_mb
+            .map(|limit| limit.saturating_mul(1024 * 1024));
+        unsafe {
+            cmd.pre_exec(move || {
+                if libc::setsid() == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                sys_process_priority::apply_to_current_process(&priority)?;
+
+                #[cfg(target_os = "linux")]
+                if let Some(bytes) = memory_limit_bytes {
+                    let limit = libc::rlimit {
+                        rlim_cur: bytes,
+                        rlim_max: bytes,
+                    };
+                    if libc::setrlimit(libc::RLIMIT_AS, &limit) == -1 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+    }
+
+    let mut child = cmd.spawn().map_err(|e| {
+        PluginError::Failed(format!(
+            "could not start {}: {}",
+            plugin.entry_path.display(),
+            e
+        ))
+    })?;
+
+    #[cfg(windows)]
+    if let Some(pid) = child.id() {
+        let _ = sys_process_priority::apply_to_process(pid, &priority);
+    }
+
+    let max_output = limits.max_output_bytes;
+    let stdin = child.stdin.take();
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let run = async {
+        // Plugins that ignore their input may close stdin early.
+        let write_input = async move {
+            if let Some(mut stdin) = stdin {
+                let _ = stdin.write_all(&payload).await;
+            }
+        };
+        let read_output = async {
+            let output = read_capped(stdout, max_output).await;
+            // Stop the plugin now; it would block writing the rest.
+            if output
+                .as_ref()
+                .is_ok_and(|output| output.len() > max_output)
+            {
+                kill_tree(&mut child);
+            }
+            output
+        };
+        let (_, stdout, stderr) = tokio::join!(
+            write_input,
+            read_output,
+            read_capped(stderr, MAX_STDERR_BYTES),
+        );
+        let stdout = stdout?;
+        if stdout.len() > max_output {
+            return Err(PluginError::OutputTooLarge(max_output));
+        }
+        let status = child.wait().await?;
+        Ok((status, stdout, stderr.unwrap_or_default()))
+    };
+
+    let (status, stdout, stderr) =
+        match tokio::time::timeout(Duration::from_secs(timeout_secs), run).await {
+            Ok(result) => result?,
+            Err(_) => {
+                kill_tree(&mut child);
+                return Err(PluginError::TimedOut(timeout_secs));
+            }
+        };
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        let stderr = stderr.trim();
+        return Err(PluginError::Failed(if stderr.is_empty() {
+            status.to_string()
+        } else {
+            stderr.to_string()
+        }));
+    }
+    parse_output(&stdout)
+}
+
+/// Parse and clean up a plugin's stdout.
+pub fn parse_output(stdout: &[u8]) -> Result<PluginOutput> {
+    let text = std::str::from_utf8(stdout)
+        .map_err(|_| PluginError::InvalidOutput("stdout is not UTF-8".to_string()))?
+        .trim();
+    if text.is_empty() {
+        return Ok(PluginOutput::default());
+    }
+
+    let output: PluginOutput =
+        serde_json::from_str(text).map_err(|e| PluginError::InvalidOutput(e.to_string()))?;
+
+    let context = output
+        .context
+        .map(|context| context.trim().chars().take(MAX_CONTEXT_CHARS).collect())
+        .filter(|context: &String| !context.is_empty());
+    let annotations = output
+        .annotations
+        .into_iter()
+        .map(|annotation| PluginAnnotation {
+            label: annotation.label.trim().to_string(),
+            text: annotation.text.trim().to_string(),
+        })
+        .filter(|annotation| !annotation.label.is_empty() && !annotation.text.is_empty())
+        .take(MAX_ANNOTATIONS)
+        .collect();
+    Ok(PluginOutput {
+        context,
+        annotations,
+    })
+}
+
+/// `wasmtime run` with only the image's directory preopened, and the input
+/// rewritten to the path the module sees.
+fn wasm_command(
+    plugin: &Plugin,
+    input: &PluginInput,
+) -> Result<(tokio::process::Command, Vec<u8>)> {
+    let runtime =
+        which::which(WASM_RUNTIME).map_err(|_| PluginError::RuntimeMissing(WASM_RUNTIME))?;
+    let image_dir = input.image_path.parent().unwrap_or(Path::new("."));
+    let file_name = input
+        .image_path
+        .file_name()
+        .ok_or_else(|| PluginError::Failed("image path has no file name".to_string()))?;
+
+    let mut guest_input = input.clone();
+    guest_input.image_path = Path::new(WASM_INPUT_DIR).join(file_name);
+
+    let mut cmd = tokio::process::Command::new(runtime);
+    cmd.arg("run")
+        .arg("--dir")
+        .arg(format!("{}::{}", image_dir.display(), WASM_INPUT_DIR))
+        .arg(&plugin.entry_path);
+    Ok((cmd, serde_json::to_vec(&guest_input)?))
+}
+
+/// Kill the plugin and, on Unix, everything it started; it leads its own
+/// session and process group.
+fn kill_tree(child: &mut tokio::process::Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+    }
+    let _ = child.start_kill();
+}
+
+/// The few variables plugins need to find system tools.
+fn passthrough_env() -> Vec<(String, String)> {
+    let names: &[&str] = if cfg!(windows) {
+        &["PATH", "SYSTEMROOT", "TEMP", "TMP"]
+    } else {
+        &["PATH", "TMPDIR", "LANG"]
+    };
+    names
+        .iter()
+        .filter_map(|name| {
+            std::env::var(name)
+                .ok()
+                .map(|value| (name.to_string(), value))
+        })
+        .collect()
+}
+
+/// Read up to `limit + 1` bytes so callers can tell the limit was exceeded.
+async fn read_capped<R: AsyncRead + Unpin>(pipe: Option<R>, limit: usize) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    if let Some(pipe) = pipe {
+        pipe.take(limit as u64 + 1).read_to_end(&mut buf).await?;
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_is_trimmed_and_empty_entries_dropped() {
+        let output = parse_output(
+            br#"{
+                "context": "  Invoice from ACME  ",
+                "annotations": [
+                    { "label": "Total", "text": " 42 EUR " },
+                    { "label": "", "text": "no label" },
+                    { "label": "Due", "text": "   " }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(output.context.as_deref(), Some("Invoice from ACME"));
+        assert_eq!(
+            output.annotations,
+            vec![PluginAnnotation {
+                label: "Total".to_string(),
+                text: "42 EUR".to_string(),
+            }]
+        );
+
+        assert!(parse_output(b"  \n").unwrap().is_empty());
+        assert!(parse_output(b"not json").is_err());
+    }
+
+    #[cfg(unix)]
+    mod process {
+        use super::*;
+        use crate::manifest::MANIFEST_FILE;
+        use std::os::unix::fs::PermissionsExt;
+        use sys_process_priority::{PowerProfile, SidecarRole};
+
+        fn script_plugin(root: &Path, script: &str, timeout_secs: Option<u64>) -> Plugin {
+            let manifest = serde_json::json!({
+                "id": "test",
+                "name": "Test",
+                "entry": "run.sh",
+                "timeoutSecs": timeout_secs,
+            });
+            std::fs::write(root.join(MANIFEST_FILE), manifest.to_string()).unwrap();
+            let entry = root.join("run.sh");
+            std::fs::write(&entry, format!("#!/bin/sh\n{}\n", script)).unwrap();
+            std::fs::set_permissions(&entry, std::fs::Permissions::from_mode(0o755)).unwrap();
+            Plugin::load(root).unwrap()
+        }
+
+        fn input() -> PluginInput {
+            PluginInput::new("chat-1", "/tmp/image.png", None, Vec::new())
+        }
+
+        fn priority() -> PriorityPolicy {
+            PowerProfile::Balanced.policy(SidecarRole::Background)
+        }
+
+        #[tokio::test]
+        async fn plugin_reads_input_and_answers() {
+            let root = tempfile::tempdir().unwrap();
+            let plugin = script_plugin(
+                root.path(),
+                r#"chat=$(cat | sed 's/.*"chatId":"\([^"]*\)".*/\1/')
+printf '{"context":"seen %s"}' "$chat""#,
+                None,
+            );
+            let output = run_plugin(&plugin, &input(), &PluginLimits::default(), priority())
+                .await
+                .unwrap();
+            assert_eq!(output.context.as_deref(), Some("seen chat-1"));
+        }
+
+        #[tokio::test]
+        async fn limits_are_enforced() {
+            let root = tempfile::tempdir().unwrap();
+            let slow = script_plugin(root.path(), "sleep 5", Some(1));
+            assert!(matches!(
+                run_plugin(&slow, &input(), &PluginLimits::default(), priority()).await,
+                Err(PluginError::TimedOut(1))
+            ));
+
+            let chatty = script_plugin(root.path(), "yes", None);
+            let limits = PluginLimits {
+                max_output_bytes: 1024,
+                ..Default::default()
+            };
+            assert!(matches!(
+                run_plugin(&chatty, &input(), &limits, priority()).await,
+                Err(PluginError::OutputTooLarge(1024))
+            ));
+
+            let failing = script_plugin(root.path(), "echo broken >&2; exit 3", None);
+            match run_plugin(&failing, &input(), &PluginLimits::default(), priority()).await {
+                Err(PluginError::Failed(message)) => assert_eq!(message, "broken"),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+}
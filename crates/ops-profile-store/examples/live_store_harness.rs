@@ -108,15 +108,11 @@ fn run() -> Result<(), String> {
                 .map_err(|err| err.to_string());
             cancel_notifier.store(true, Ordering::SeqCst);
 
-            match result {
-                Ok(payload) => {
-                    println!(
-                        "{}",
-                        serde_json::to_string(&payload).map_err(|err| err.to_string())?
-                    );
-                }
-                Err(err) => return Err(err),
-            }
+            let payload = result?;
+            println!(
+                "{}",
+                serde_json::to_string(&payload).map_err(|err| err.to_string())?
+            );
         }
         "auth-credentials-ok" => {
             let settings = AuthFlowSettings::new("Squigit", Arc::new(|_| Ok(())));
@@ -0,0 +1,214 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-profile audit log of outbound LLM calls.
+//!
+//! Each call is one JSON line with its timing, model and status. Prompts
+//! are never stored: only their size and a truncated hash, which is enough
+//! to match a call against a known prompt.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::store::ProfileStore;
+
+/// Audit log filename inside a profile directory.
+const AUDIT_FILE: &str = "llm_audit.jsonl";
+
+/// How an audited call ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmAuditStatus {
+    Ok,
+    Error,
+    Cancelled,
+}
+
+/// One outbound LLM call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmAuditEntry {
+    /// When the call started.
+    pub timestamp: DateTime<Utc>,
+
+    /// What the call was for, e.g. "chat" or "chat_title".
+    pub operation: String,
+
+    pub model: String,
+
+    /// Prompt length in characters.
+    pub prompt_chars: usize,
+
+    /// blake3 of the prompt, first 16 hex chars.
+    pub prompt_hash: String,
+
+    pub duration_ms: u64,
+
+    pub status: LlmAuditStatus,
+}
+
+impl LlmAuditEntry {
+    /// Describe a call of `operation` that started at `timestamp`.
+    pub fn new(
+        timestamp: DateTime<Utc>,
+        operation: &str,
+        model: &str,
+        prompt: &str,
+        duration_ms: u64,
+        status: LlmAuditStatus,
+    ) -> Self {
+        Self {
+            timestamp,
+            operation: operation.to_string(),
+            model: model.to_string(),
+            prompt_chars: prompt.chars().count(),
+            prompt_hash: blake3::hash(prompt.as_bytes()).to_hex()[..16].to_string(),
+            duration_ms,
+            status,
+        }
+    }
+}
+
+impl ProfileStore {
+    /// Get the audit log path for a profile.
+    ///
+    /// Returns `{base_dir}/{profile_id}/llm_audit.jsonl`
+    pub fn get_llm_audit_path(&self, profile_id: &str) -> PathBuf {
+        self.get_profile_dir(profile_id).join(AUDIT_FILE)
+    }
+
+    /// Append an entry to a profile's audit log.
+    pub fn append_llm_audit(&self, profile_id: &str, entry: &LlmAuditEntry) -> Result<()> {
+        let path = self.get_llm_audit_path(profile_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Entries whose timestamp is within `[from, to)`, oldest first. Either
+    /// bound may be open. Unreadable lines are skipped.
+    pub fn list_llm_audit(
+        &self,
+        profile_id: &str,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LlmAuditEntry>> {
+        let in_range = |entry: &LlmAuditEntry| {
+            !from.is_some_and(|from| entry.timestamp < from)
+                && !to.is_some_and(|to| entry.timestamp >= to)
+        };
+        Ok(self
+            .read_llm_audit(profile_id)?
+            .into_iter()
+            .filter(in_range)
+            .collect())
+    }
+
+    /// Delete entries older than `before`, or all entries when `None`.
+    /// Returns how many were removed.
+    pub fn purge_llm_audit(
+        &self,
+        profile_id: &str,
+        before: Option<DateTime<Utc>>,
+    ) -> Result<usize> {
+        let path = self.get_llm_audit_path(profile_id);
+        if !path.exists() {
+            return Ok(0);
+        }
+
+        let entries = self.read_llm_audit(profile_id)?;
+        let total = entries.len();
+        let Some(before) = before else {
+            fs::remove_file(&path)?;
+            return Ok(total);
+        };
+
+        let mut kept = String::new();
+        for entry in entries.iter().filter(|entry| entry.timestamp >= before) {
+            kept.push_str(&serde_json::to_string(entry)?);
+            kept.push('\n');
+        }
+        let removed = total - kept.lines().count();
+        if removed > 0 {
+            self.write_bytes_atomic(&path, kept.as_bytes())?;
+        }
+        Ok(removed)
+    }
+
+    fn read_llm_audit(&self, profile_id: &str) -> Result<Vec<LlmAuditEntry>> {
+        let path = self.get_llm_audit_path(profile_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn temp_store() -> ProfileStore {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::mem::forget(temp_dir);
+        ProfileStore::with_base_dir(root.join("Local Storage")).unwrap()
+    }
+
+    #[test]
+    fn test_llm_audit_range_and_purge() {
+        let store = temp_store();
+        let now = Utc::now();
+        for (hours_ago, status) in [(3, LlmAuditStatus::Ok), (1, LlmAuditStatus::Error)] {
+            let entry = LlmAuditEntry::new(
+                now - Duration::hours(hours_ago),
+                "chat",
+                "gemini-flash",
+                "What is on screen?",
+                1200,
+                status,
+            );
+            store.append_llm_audit("p1", &entry).unwrap();
+        }
+
+        let all = store.list_llm_audit("p1", None, None).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].prompt_chars, 18);
+        assert_eq!(all[0].prompt_hash.len(), 16);
+
+        let recent = store
+            .list_llm_audit("p1", Some(now - Duration::hours(2)), None)
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].status, LlmAuditStatus::Error);
+
+        assert_eq!(
+            store
+                .purge_llm_audit("p1", Some(now - Duration::hours(2)))
+                .unwrap(),
+            1
+        );
+        assert_eq!(store.list_llm_audit("p1", None, None).unwrap(), recent);
+        assert_eq!(store.purge_llm_audit("p1", None).unwrap(), 1);
+        assert!(store.list_llm_audit("p1", None, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in encryption of a profile's chats at rest.
+//!
+//! Turning it on creates a random chat storage key, kept encrypted like
+//! BYOK keys. Every [`ChatStorage`] opened through
+//! [`ProfileStore::open_chat_storage`] then encrypts what it writes and
+//! decrypts what it reads; see [`ops_chat_storage::encryption`]. The
+//! decrypted key is cached for the life of the process, since deriving the
+//! key that protects it is slow on purpose.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use ops_chat_storage::{ChatStorage, EncryptionReport, StorageKey};
+
+use crate::error::{ProfileError, Result};
+use crate::security::{read_encrypted_secret, write_encrypted_secret};
+use crate::store::ProfileStore;
+
+/// Encrypted chat storage key filename inside a profile directory.
+const CHAT_KEY_FILE: &str = "chat_key.json";
+
+static KEY_CACHE: Mutex<Option<HashMap<PathBuf, StorageKey>>> = Mutex::new(None);
+
+impl ProfileStore {
+    /// The profile's chat storage, encrypted if the profile turned that on.
+    pub fn open_chat_storage(&self, profile_id: &str) -> Result<ChatStorage> {
+        let key = self.get_chat_storage_key(profile_id)?;
+        ChatStorage::with_base_dir(self.get_chats_dir(profile_id))
+            .map(|storage| storage.with_encryption(key))
+            .map_err(|e| ProfileError::Security(format!("Failed to open chat storage: {}", e)))
+    }
+
+    pub fn is_chat_encryption_enabled(&self, profile_id: &str) -> bool {
+        self.chat_key_path(profile_id).exists()
+    }
+
+    /// The key chats are encrypted with; `None` while encryption is off.
+    pub fn get_chat_storage_key(&self, profile_id: &str) -> Result<Option<StorageKey>> {
+        let path = self.chat_key_path(profile_id);
+        if let Some(key) = cached_key(&path) {
+            return Ok(Some(key));
+        }
+        let Some(hex_key) = read_encrypted_secret(&path)? else {
+            return Ok(None);
+        };
+        let bytes: [u8; 32] = hex::decode(hex_key.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| ProfileError::Security("Invalid chat storage key".to_string()))?;
+        let key = StorageKey::from_bytes(bytes);
+        cache_key(path, &key);
+        Ok(Some(key))
+    }
+
+    /// Turn on chat encryption for the profile and encrypt the chats it
+    /// already has. Safe to run again, e.g. after an interrupted run.
+    pub fn enable_chat_encryption(&self, profile_id: &str) -> Result<EncryptionReport> {
+        if self.get_profile(profile_id)?.is_none() {
+            return Err(ProfileError::ProfileNotFound(profile_id.to_string()));
+        }
+        if !self.is_chat_encryption_enabled(profile_id) {
+            let key = StorageKey::generate();
+            let path = self.chat_key_path(profile_id);
+            write_encrypted_secret(self, &path, &hex::encode(key.as_bytes()))?;
+            cache_key(path, &key);
+        }
+        self.open_chat_storage(profile_id)?
+            .encrypt_existing()
+            .map_err(|e| ProfileError::Security(format!("Failed to encrypt chats: {}", e)))
+    }
+
+    fn chat_key_path(&self, profile_id: &str) -> PathBuf {
+        self.get_profile_dir(profile_id).join(CHAT_KEY_FILE)
+    }
+}
+
+fn cached_key(path: &PathBuf) -> Option<StorageKey> {
+    KEY_CACHE.lock().ok()?.as_ref()?.get(path).cloned()
+}
+
+fn cache_key(path: PathBuf, key: &StorageKey) {
+    if let Ok(mut cache) = KEY_CACHE.lock() {
+        cache
+            .get_or_insert_with(HashMap::new)
+            .insert(path, key.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Profile;
+    use ops_chat_storage::{ChatData, ChatMessage, ChatMetadata};
+    use tempfile::tempdir;
+
+    fn temp_store() -> (ProfileStore, String) {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::mem::forget(temp_dir);
+        let store = ProfileStore::with_base_dir(root.join("Local Storage")).unwrap();
+        let profile = Profile::new("vault@example.com", "Vault User", None, None);
+        store.upsert_profile(&profile).unwrap();
+        (store, profile.id)
+    }
+
+    #[test]
+    fn enabling_encrypts_existing_chats() {
+        let (store, profile_id) = temp_store();
+        let plain = store.open_chat_storage(&profile_id).unwrap();
+        assert!(!plain.is_encrypted());
+        let mut chat = ChatData::new(ChatMetadata::new("Notes".to_string(), String::new(), None));
+        chat.messages.push(ChatMessage::user("private".to_string()));
+        plain.save_chat(&chat).unwrap();
+
+        let report = store.enable_chat_encryption(&profile_id).unwrap();
+        assert_eq!(report.chat_files, 3);
+        assert!(store.is_chat_encryption_enabled(&profile_id));
+
+        let raw = std::fs::read_to_string(
+            store
+                .get_chats_dir(&profile_id)
+                .join(&chat.metadata.id)
+                .join("messages.json"),
+        )
+        .unwrap_or_default();
+        assert!(!raw.contains("private"));
+
+        let storage = store.open_chat_storage(&profile_id).unwrap();
+        assert!(storage.is_encrypted());
+        let loaded = storage.load_chat(&chat.metadata.id).unwrap();
+        assert_eq!(loaded.messages[0].content, "private");
+    }
+}
@@ -44,6 +44,34 @@ pub enum ProfileError {
     #[error("{0}")]
     Security(String),
 
+    /// Glossary entry is missing a term or collides with an existing one.
+    #[error("Invalid glossary entry: {0}")]
+    InvalidGlossaryEntry(String),
+
+    /// Glossary entry ID does not exist for the profile.
+    #[error("Glossary entry not found: {0}")]
+    GlossaryEntryNotFound(String),
+
+    /// Model fallback list contains an unusable model name.
+    #[error("Invalid fallback model: {0}")]
+    InvalidModelFallback(String),
+
+    /// Webhook settings have a bad URL or an unknown event.
+    #[error("Invalid webhook: {0}")]
+    InvalidWebhook(String),
+
+    /// Vertex AI settings or credentials JSON are unusable.
+    #[error("Invalid Vertex AI settings: {0}")]
+    InvalidVertexConfig(String),
+
+    /// Export connector settings are unusable, e.g. a relative vault path.
+    #[error("Invalid export connector: {0}")]
+    InvalidExportConnector(String),
+
+    /// Metric name is not a lowercase identifier.
+    #[error("Invalid metric: {0}")]
+    InvalidMetric(String),
+
     /// IO error during file operations.
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
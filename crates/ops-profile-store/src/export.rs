@@ -0,0 +1,180 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-profile chat export connectors.
+//!
+//! Each connector is a place chats can be exported to, such as an Obsidian
+//! vault. Only the settings live here; writing the export is up to the app.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ProfileError, Result};
+use crate::store::ProfileStore;
+
+/// Export connector settings filename inside a profile directory.
+const EXPORT_CONNECTORS_FILE: &str = "export_connectors.json";
+
+/// Every export connector's settings, as stored and shown in the UI.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportConnectorsConfig {
+    #[serde(default)]
+    pub obsidian: ObsidianConnectorConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObsidianConnectorConfig {
+    /// Absolute path of the vault; empty until the user picks one.
+    #[serde(default)]
+    pub vault_path: String,
+
+    /// Folder inside the vault that notes go to; empty means the exporter's
+    /// default.
+    #[serde(default)]
+    pub folder: String,
+
+    /// Note template; `None` uses the built-in one.
+    #[serde(default)]
+    pub template: Option<String>,
+
+    /// Extra front-matter tags for every exported note.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl ObsidianConnectorConfig {
+    fn normalize(mut self) -> Result<Self> {
+        self.vault_path = self.vault_path.trim().to_string();
+        if !self.vault_path.is_empty() && !Path::new(&self.vault_path).is_absolute() {
+            return Err(ProfileError::InvalidExportConnector(
+                "vault path must be absolute".to_string(),
+            ));
+        }
+
+        self.folder = self.folder.trim().trim_matches(['/', '\\']).to_string();
+        if !Path::new(&self.folder)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(ProfileError::InvalidExportConnector(format!(
+                "folder must stay inside the vault: {:?}",
+                self.folder
+            )));
+        }
+
+        self.template = self.template.filter(|template| !template.trim().is_empty());
+
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.tags {
+            let tag = tag.trim().trim_start_matches('#');
+            if !tag.is_empty() && !tags.iter().any(|known| known == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+        self.tags = tags;
+        Ok(self)
+    }
+}
+
+impl ProfileStore {
+    /// Get the export connector settings path for a profile.
+    pub fn get_export_connectors_path(&self, profile_id: &str) -> PathBuf {
+        self.get_profile_dir(profile_id)
+            .join(EXPORT_CONNECTORS_FILE)
+    }
+
+    /// The profile's export connector settings; empty when never saved.
+    pub fn get_export_connectors(&self, profile_id: &str) -> Result<ExportConnectorsConfig> {
+        let path = self.get_export_connectors_path(profile_id);
+        if !path.exists() {
+            return Ok(ExportConnectorsConfig::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(&path)?)?)
+    }
+
+    /// Save export connector settings. Returns the stored settings.
+    pub fn set_export_connectors(
+        &self,
+        profile_id: &str,
+        config: ExportConnectorsConfig,
+    ) -> Result<ExportConnectorsConfig> {
+        if self.get_profile(profile_id)?.is_none() {
+            return Err(ProfileError::ProfileNotFound(profile_id.to_string()));
+        }
+        let config = ExportConnectorsConfig {
+            obsidian: config.obsidian.normalize()?,
+        };
+        self.write_json_atomic(&self.get_export_connectors_path(profile_id), &config)?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Profile;
+    use tempfile::tempdir;
+
+    fn temp_store() -> (ProfileStore, String, PathBuf) {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::mem::forget(temp_dir);
+        let store = ProfileStore::with_base_dir(root.join("Local Storage")).unwrap();
+        let profile = Profile::new("export@example.com", "Export User", None, None);
+        store.upsert_profile(&profile).unwrap();
+        (store, profile.id, root)
+    }
+
+    #[test]
+    fn obsidian_config_round_trip() {
+        let (store, profile_id, root) = temp_store();
+        assert_eq!(
+            store.get_export_connectors(&profile_id).unwrap(),
+            ExportConnectorsConfig::default()
+        );
+
+        let vault = root.join("Vault").to_string_lossy().into_owned();
+        let config = ExportConnectorsConfig {
+            obsidian: ObsidianConnectorConfig {
+                vault_path: format!(" {} ", vault),
+                folder: "/Inbox/Screens/".to_string(),
+                template: Some("  ".to_string()),
+                tags: vec![
+                    "#screens".to_string(),
+                    "screens".to_string(),
+                    " ".to_string(),
+                ],
+            },
+        };
+        let saved = store.set_export_connectors(&profile_id, config).unwrap();
+        assert_eq!(saved.obsidian.vault_path, vault);
+        assert_eq!(saved.obsidian.folder, "Inbox/Screens");
+        assert_eq!(saved.obsidian.template, None);
+        assert_eq!(saved.obsidian.tags, vec!["screens".to_string()]);
+        assert_eq!(store.get_export_connectors(&profile_id).unwrap(), saved);
+    }
+
+    #[test]
+    fn rejects_relative_vault_and_escaping_folder() {
+        let (store, profile_id, root) = temp_store();
+        let relative = ExportConnectorsConfig {
+            obsidian: ObsidianConnectorConfig {
+                vault_path: "Vault".to_string(),
+                ..Default::default()
+            },
+        };
+        assert!(store.set_export_connectors(&profile_id, relative).is_err());
+
+        let escaping = ExportConnectorsConfig {
+            obsidian: ObsidianConnectorConfig {
+                vault_path: root.to_string_lossy().into_owned(),
+                folder: "../outside".to_string(),
+                ..Default::default()
+            },
+        };
+        assert!(store.set_export_connectors(&profile_id, escaping).is_err());
+    }
+}
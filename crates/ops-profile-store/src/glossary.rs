@@ -0,0 +1,273 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-profile glossary of domain terms.
+//!
+//! Entries hold a canonical term, an optional expansion (for acronyms or
+//! jargon) and known misspellings. They are used to correct OCR output and
+//! are injected into the system prompt.
+
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ProfileError, Result};
+use crate::store::ProfileStore;
+
+/// Glossary filename inside a profile directory.
+const GLOSSARY_FILE: &str = "glossary.json";
+
+/// Longest accepted term, expansion or variant, in characters.
+const MAX_FIELD_CHARS: usize = 200;
+
+/// A single glossary entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlossaryEntry {
+    /// Stable ID (blake3 of the lowercase term, first 16 hex chars).
+    pub id: String,
+
+    /// Preferred spelling of the term.
+    pub term: String,
+
+    /// What the term stands for or means.
+    #[serde(default)]
+    pub expansion: Option<String>,
+
+    /// Spellings that should be corrected to `term`.
+    #[serde(default)]
+    pub variants: Vec<String>,
+
+    /// When the entry was created or last edited.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Editable fields of a glossary entry, as sent by the UI.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlossaryEntryInput {
+    pub term: String,
+    #[serde(default)]
+    pub expansion: Option<String>,
+    #[serde(default)]
+    pub variants: Vec<String>,
+}
+
+impl GlossaryEntryInput {
+    /// Trim fields, drop empty or duplicate variants and check lengths.
+    fn normalize(self) -> Result<Self> {
+        let term = self.term.trim().to_string();
+        if term.is_empty() {
+            return Err(ProfileError::InvalidGlossaryEntry(
+                "term is empty".to_string(),
+            ));
+        }
+
+        let expansion = self
+            .expansion
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+
+        let mut variants: Vec<String> = Vec::new();
+        for variant in self.variants {
+            let variant = variant.trim();
+            if variant.is_empty()
+                || variant == term
+                || variants
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(variant))
+            {
+                continue;
+            }
+            variants.push(variant.to_string());
+        }
+
+        let fields = std::iter::once(&term)
+            .chain(expansion.iter())
+            .chain(variants.iter());
+        for field in fields {
+            if field.chars().count() > MAX_FIELD_CHARS {
+                return Err(ProfileError::InvalidGlossaryEntry(format!(
+                    "'{}…' is longer than {} characters",
+                    field.chars().take(24).collect::<String>(),
+                    MAX_FIELD_CHARS
+                )));
+            }
+        }
+
+        Ok(Self {
+            term,
+            expansion,
+            variants,
+        })
+    }
+}
+
+fn glossary_entry_id(term: &str) -> String {
+    let hash = blake3::hash(term.to_lowercase().as_bytes());
+    hash.to_hex()[..16].to_string()
+}
+
+impl ProfileStore {
+    /// Get the glossary file path for a profile.
+    ///
+    /// Returns `{base_dir}/{profile_id}/glossary.json`
+    pub fn get_glossary_path(&self, profile_id: &str) -> PathBuf {
+        self.get_profile_dir(profile_id).join(GLOSSARY_FILE)
+    }
+
+    /// List glossary entries for a profile, sorted by term.
+    pub fn list_glossary(&self, profile_id: &str) -> Result<Vec<GlossaryEntry>> {
+        let path = self.get_glossary_path(profile_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let mut entries: Vec<GlossaryEntry> = serde_json::from_str(&content)?;
+        entries.sort_by_key(|entry| entry.term.to_lowercase());
+        Ok(entries)
+    }
+
+    /// Add a glossary entry. Fails if the term already exists.
+    pub fn add_glossary_entry(
+        &self,
+        profile_id: &str,
+        input: GlossaryEntryInput,
+    ) -> Result<GlossaryEntry> {
+        let input = input.normalize()?;
+        let mut entries = self.list_glossary(profile_id)?;
+
+        let id = glossary_entry_id(&input.term);
+        if entries.iter().any(|entry| entry.id == id) {
+            return Err(ProfileError::InvalidGlossaryEntry(format!(
+                "'{}' already exists",
+                input.term
+            )));
+        }
+
+        let entry = GlossaryEntry {
+            id,
+            term: input.term,
+            expansion: input.expansion,
+            variants: input.variants,
+            updated_at: Utc::now(),
+        };
+        entries.push(entry.clone());
+        self.save_glossary(profile_id, &entries)?;
+        Ok(entry)
+    }
+
+    /// Replace the fields of an existing glossary entry.
+    ///
+    /// Renaming the term changes the entry ID.
+    pub fn update_glossary_entry(
+        &self,
+        profile_id: &str,
+        entry_id: &str,
+        input: GlossaryEntryInput,
+    ) -> Result<GlossaryEntry> {
+        let input = input.normalize()?;
+        let mut entries = self.list_glossary(profile_id)?;
+
+        let position = entries
+            .iter()
+            .position(|entry| entry.id == entry_id)
+            .ok_or_else(|| ProfileError::GlossaryEntryNotFound(entry_id.to_string()))?;
+
+        let id = glossary_entry_id(&input.term);
+        if id != entry_id && entries.iter().any(|entry| entry.id == id) {
+            return Err(ProfileError::InvalidGlossaryEntry(format!(
+                "'{}' already exists",
+                input.term
+            )));
+        }
+
+        let entry = GlossaryEntry {
+            id,
+            term: input.term,
+            expansion: input.expansion,
+            variants: input.variants,
+            updated_at: Utc::now(),
+        };
+        entries[position] = entry.clone();
+        self.save_glossary(profile_id, &entries)?;
+        Ok(entry)
+    }
+
+    /// Remove a glossary entry.
+    pub fn delete_glossary_entry(&self, profile_id: &str, entry_id: &str) -> Result<()> {
+        let mut entries = self.list_glossary(profile_id)?;
+        let before = entries.len();
+        entries.retain(|entry| entry.id != entry_id);
+        if entries.len() == before {
+            return Err(ProfileError::GlossaryEntryNotFound(entry_id.to_string()));
+        }
+        self.save_glossary(profile_id, &entries)
+    }
+
+    fn save_glossary(&self, profile_id: &str, entries: &[GlossaryEntry]) -> Result<()> {
+        self.write_json_atomic(&self.get_glossary_path(profile_id), &entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn temp_store() -> ProfileStore {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::mem::forget(temp_dir);
+        ProfileStore::with_base_dir(root.join("Local Storage")).unwrap()
+    }
+
+    fn input(term: &str, variants: &[&str]) -> GlossaryEntryInput {
+        GlossaryEntryInput {
+            term: term.to_string(),
+            expansion: None,
+            variants: variants.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_glossary_crud() {
+        let store = temp_store();
+        assert!(store.list_glossary("p1").unwrap().is_empty());
+
+        let entry = store
+            .add_glossary_entry("p1", input(" Kubernetes ", &["Kubemetes", "", "kubemetes"]))
+            .unwrap();
+        assert_eq!(entry.term, "Kubernetes");
+        assert_eq!(entry.variants, vec!["Kubemetes".to_string()]);
+
+        assert!(matches!(
+            store.add_glossary_entry("p1", input("kubernetes", &[])),
+            Err(ProfileError::InvalidGlossaryEntry(_))
+        ));
+
+        let updated = store
+            .update_glossary_entry("p1", &entry.id, input("K8s", &[]))
+            .unwrap();
+        assert_ne!(updated.id, entry.id);
+        assert_eq!(store.list_glossary("p1").unwrap(), vec![updated.clone()]);
+
+        store.delete_glossary_entry("p1", &updated.id).unwrap();
+        assert!(matches!(
+            store.delete_glossary_entry("p1", &updated.id),
+            Err(ProfileError::GlossaryEntryNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_glossary_rejects_empty_term() {
+        let store = temp_store();
+        assert!(matches!(
+            store.add_glossary_entry("p1", input("   ", &[])),
+            Err(ProfileError::InvalidGlossaryEntry(_))
+        ));
+    }
+}
@@ -14,10 +14,19 @@
 //! ├── preferences.json              # GLOBAL (shared across profiles)
 //! └── Local Storage/
 //!     ├── index.json                # Profile index + active profile
+//!     ├── metrics.json              # Local usage counters, never sent
 //!     └── {profile_id}/
 //!         ├── profile.json          # Google profile data
 //!         ├── {provider}_key.json   # Per-profile BYOK
 //!         ├── imgbb_key.json        # Per-profile BYOK
+//!         ├── glossary.json         # Per-profile terms and spellings
+//!         ├── llm_audit.jsonl       # Opt-in log of outbound LLM calls
+//!         ├── webhook.json          # Opt-in automation webhook
+//!         ├── webhook_secret.json   # Encrypted webhook signing secret
+//!         ├── vertex.json           # Opt-in Vertex AI sign-in for Gemini
+//!         ├── vertex_credentials.json # Encrypted service account JSON
+//!         ├── export_connectors.json # Chat export targets (Obsidian vault…)
+//!         ├── chat_key.json         # Encrypted chat storage key (opt-in)
 //!         └── chats/                # Per-profile chat storage
 //! ```
 //!
@@ -40,14 +49,27 @@
 //! store.set_active_profile_id(&profile.id).unwrap();
 //! ```
 
+pub mod audit;
 pub mod auth;
+pub mod chat_encryption;
 pub mod error;
+pub mod export;
+pub mod glossary;
+pub mod metrics;
 pub mod security;
 pub mod store;
 pub mod types;
+pub mod vertex;
+pub mod webhook;
 
+pub use audit::{LlmAuditEntry, LlmAuditStatus};
 pub use error::{ProfileError, Result};
+pub use export::{ExportConnectorsConfig, ObsidianConnectorConfig};
+pub use glossary::{GlossaryEntry, GlossaryEntryInput};
+pub use metrics::{AnonymizedMetrics, FeatureMetrics, LocalMetrics};
 pub use store::ProfileStore;
 pub use types::{Profile, ProfileIndex};
+pub use vertex::VertexConfig;
+pub use webhook::{sign_webhook_payload, WebhookConfig};
 pub use auth::{AuthFlowSettings, AuthSuccessData, BrowserOpener, CredentialsSource};
 pub use security::{ApiKeyProvider, validate_api_key};
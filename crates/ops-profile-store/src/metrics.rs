@@ -0,0 +1,205 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local usage metrics.
+//!
+//! Counts how often each feature is used and how often it fails, in a
+//! single file shared by all profiles. Nothing here is sent anywhere: the
+//! only way out is [`LocalMetrics::anonymized`], which the user reviews
+//! and shares themselves. Counters hold no chat, profile or model names,
+//! and the export drops dates in favour of a day count.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ProfileError, Result};
+use crate::store::ProfileStore;
+
+/// Metrics filename inside the storage directory.
+const METRICS_FILE: &str = "metrics.json";
+
+/// Bumped when the export's shape changes.
+const EXPORT_SCHEMA: u32 = 1;
+
+/// Longest accepted feature name.
+const MAX_FEATURE_CHARS: usize = 48;
+
+/// Counters for one feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureMetrics {
+    pub uses: u64,
+    /// Uses that ended in an error. Cancellations are not errors.
+    pub errors: u64,
+}
+
+/// Everything recorded since the last reset.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalMetrics {
+    /// First use recorded after the last reset.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Counters by feature, e.g. "capture" or "ocr".
+    #[serde(default)]
+    pub features: BTreeMap<String, FeatureMetrics>,
+}
+
+/// Per-feature figures in an export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizedFeature {
+    pub uses: u64,
+    /// Share of uses that failed, rounded to two decimals.
+    pub error_rate: f64,
+}
+
+/// What a user may choose to share, shown to them as-is first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizedMetrics {
+    pub schema: u32,
+    pub app_version: String,
+    /// OS family, e.g. "macos".
+    pub os: String,
+    /// Whole days covered, instead of dates.
+    pub days: i64,
+    pub features: BTreeMap<String, AnonymizedFeature>,
+}
+
+impl LocalMetrics {
+    /// The export for these counters, as of `now`.
+    pub fn anonymized(&self, app_version: &str, os: &str, now: DateTime<Utc>) -> AnonymizedMetrics {
+        let features = self
+            .features
+            .iter()
+            .filter(|(_, counts)| counts.uses > 0)
+            .map(|(name, counts)| {
+                let rate = counts.errors.min(counts.uses) as f64 / counts.uses as f64;
+                let feature = AnonymizedFeature {
+                    uses: counts.uses,
+                    error_rate: (rate * 100.0).round() / 100.0,
+                };
+                (name.clone(), feature)
+            })
+            .collect();
+
+        AnonymizedMetrics {
+            schema: EXPORT_SCHEMA,
+            app_version: app_version.to_string(),
+            os: os.to_string(),
+            days: self
+                .since
+                .map_or(0, |since| (now - since).num_days().max(0) + 1),
+            features,
+        }
+    }
+}
+
+/// Feature names are fixed by the app; anything else could carry user data.
+fn validate_feature(feature: &str) -> Result<()> {
+    let valid = !feature.is_empty()
+        && feature.len() <= MAX_FEATURE_CHARS
+        && feature
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ProfileError::InvalidMetric(feature.to_string()))
+    }
+}
+
+impl ProfileStore {
+    /// Get the metrics file path.
+    ///
+    /// Returns `{base_dir}/metrics.json`
+    pub fn get_metrics_path(&self) -> PathBuf {
+        self.base_dir().join(METRICS_FILE)
+    }
+
+    /// Load the counters. A missing or unreadable file counts as empty.
+    pub fn load_local_metrics(&self) -> Result<LocalMetrics> {
+        let path = self.get_metrics_path();
+        if !path.exists() {
+            return Ok(LocalMetrics::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content).unwrap_or_default())
+    }
+
+    /// Count one use of `feature`, and one error if `failed`.
+    ///
+    /// Not safe against concurrent writers; callers serialize.
+    pub fn record_local_metric(&self, feature: &str, failed: bool) -> Result<()> {
+        validate_feature(feature)?;
+
+        let mut metrics = self.load_local_metrics()?;
+        metrics.since.get_or_insert_with(Utc::now);
+        let counts = metrics.features.entry(feature.to_string()).or_default();
+        counts.uses += 1;
+        if failed {
+            counts.errors += 1;
+        }
+        self.write_json_atomic(&self.get_metrics_path(), &metrics)
+    }
+
+    /// Delete all counters.
+    pub fn reset_local_metrics(&self) -> Result<()> {
+        let path = self.get_metrics_path();
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn temp_store() -> ProfileStore {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::mem::forget(temp_dir);
+        ProfileStore::with_base_dir(root.join("Local Storage")).unwrap()
+    }
+
+    #[test]
+    fn test_local_metrics_record_export_and_reset() {
+        let store = temp_store();
+        for failed in [false, false, true] {
+            store.record_local_metric("ocr", failed).unwrap();
+        }
+        store.record_local_metric("capture", false).unwrap();
+        assert!(matches!(
+            store.record_local_metric("chat:abc123", false),
+            Err(ProfileError::InvalidMetric(_))
+        ));
+
+        let metrics = store.load_local_metrics().unwrap();
+        assert_eq!(
+            metrics.features["ocr"],
+            FeatureMetrics { uses: 3, errors: 1 }
+        );
+        let since = metrics.since.unwrap();
+
+        let export = metrics.anonymized("1.2.0", "linux", since + Duration::hours(30));
+        assert_eq!(export.days, 2);
+        assert_eq!(export.features["ocr"].error_rate, 0.33);
+        assert_eq!(export.features["capture"].error_rate, 0.0);
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(!json.contains(&since.to_rfc3339()));
+
+        store.reset_local_metrics().unwrap();
+        assert_eq!(store.load_local_metrics().unwrap(), LocalMetrics::default());
+    }
+}
@@ -11,6 +11,7 @@ use rand::{rngs::OsRng, RngCore};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::fs;
+use std::path::Path;
 use std::str::FromStr;
 
 use crate::{ProfileError, ProfileStore, Result};
@@ -19,6 +20,10 @@ use crate::{ProfileError, ProfileStore, Result};
 pub enum ApiKeyProvider {
     GoogleAiStudio,
     ImgBb,
+    /// Any server speaking the OpenAI chat completions API.
+    OpenAiCompatible,
+    /// Claude, via the Anthropic messages API.
+    Anthropic,
 }
 
 impl ApiKeyProvider {
@@ -26,6 +31,8 @@ impl ApiKeyProvider {
         match self {
             Self::GoogleAiStudio => "Google AI Studio",
             Self::ImgBb => "ImgBB",
+            Self::OpenAiCompatible => "OpenAI-compatible",
+            Self::Anthropic => "Anthropic",
         }
     }
 
@@ -33,6 +40,8 @@ impl ApiKeyProvider {
         match self {
             Self::GoogleAiStudio => "google ai studio",
             Self::ImgBb => "imgbb",
+            Self::OpenAiCompatible => "openai compatible",
+            Self::Anthropic => "anthropic",
         }
     }
 
@@ -44,6 +53,9 @@ impl ApiKeyProvider {
         match self {
             Self::GoogleAiStudio => key.starts_with("AIzaS") && key.len() == 39,
             Self::ImgBb => key.len() == 32,
+            // Servers choose their own key format.
+            Self::OpenAiCompatible => true,
+            Self::Anthropic => key.starts_with("sk-ant-"),
         }
     }
 
@@ -51,6 +63,8 @@ impl ApiKeyProvider {
         match self {
             Self::GoogleAiStudio => "Expected a key that starts with 'AIzaS' and is 39 characters long.",
             Self::ImgBb => "Expected a 32-character API key.",
+            Self::OpenAiCompatible => "Use the key the server expects, or none for local servers.",
+            Self::Anthropic => "Expected a key that starts with 'sk-ant-'.",
         }
     }
 }
@@ -64,6 +78,10 @@ impl FromStr for ApiKeyProvider {
                 Ok(Self::GoogleAiStudio)
             }
             "imgbb" => Ok(Self::ImgBb),
+            "openai compatible" | "openai_compatible" | "openai-compatible" | "openai" => {
+                Ok(Self::OpenAiCompatible)
+            }
+            "anthropic" | "claude" => Ok(Self::Anthropic),
             other => Err(ProfileError::InvalidProvider(other.to_string())),
         }
     }
@@ -96,6 +114,12 @@ pub fn get_decrypted_key(
     profile_id: &str,
 ) -> Result<Option<String>> {
     let file_path = store.get_provider_key_path(profile_id, provider.storage_key_name());
+    read_encrypted_secret(&file_path)
+}
+
+/// Decrypt a secret written by [`write_encrypted_secret`]. `None` when the
+/// file does not exist.
+pub(crate) fn read_encrypted_secret(file_path: &Path) -> Result<Option<String>> {
     if !file_path.exists() {
         return Ok(None);
     }
@@ -159,6 +183,19 @@ pub fn encrypt_and_save_key(
     let plaintext = plaintext.trim();
     validate_api_key(provider, plaintext)?;
 
+    let file_path = store.get_provider_key_path(profile_id, provider.storage_key_name());
+    write_encrypted_secret(store, &file_path, plaintext)?;
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// Encrypt `plaintext` with a key derived from the user's home directory
+/// and write it to `file_path`.
+pub(crate) fn write_encrypted_secret(
+    store: &ProfileStore,
+    file_path: &Path,
+    plaintext: &str,
+) -> Result<()> {
     let passphrase = get_stable_passphrase()?;
     let mut salt = [0u8; 16];
     let mut iv = [0u8; 12];
@@ -186,10 +223,7 @@ pub fn encrypt_and_save_key(
         "ciphertext": general_purpose::STANDARD.encode(ciphertext)
     });
 
-    let file_path = store.get_provider_key_path(profile_id, provider.storage_key_name());
-    store.write_json_atomic(&file_path, &payload)?;
-
-    Ok(file_path.to_string_lossy().to_string())
+    store.write_json_atomic(file_path, &payload)
 }
 
 pub fn validate_api_key(provider: ApiKeyProvider, plaintext: &str) -> Result<()> {
@@ -24,6 +24,9 @@ const INDEX_FILE: &str = "index.json";
 /// Individual profile metadata filename.
 const PROFILE_FILE: &str = "profile.json";
 
+/// Longest model fallback chain a profile may configure.
+const MAX_MODEL_FALLBACKS: usize = 4;
+
 /// Manager for profile storage operations.
 ///
 /// Handles CRUD operations for profiles, maintaining an index
@@ -205,6 +208,10 @@ impl ProfileStore {
             if stored_profile.original_avatar.is_none() {
                 stored_profile.original_avatar = existing_profile.original_avatar;
             }
+            // Identity updates never carry settings; see `set_model_fallbacks`.
+            stored_profile.model_fallbacks = existing_profile.model_fallbacks;
+            stored_profile.llm_audit_enabled = existing_profile.llm_audit_enabled;
+            stored_profile.strip_image_metadata = existing_profile.strip_image_metadata;
         }
 
         self.write_json_atomic(&profile_path, &stored_profile)?;
@@ -267,7 +274,7 @@ impl ProfileStore {
         }
 
         // Sort by last used (most recent first)
-        profiles.sort_by(|a, b| b.last_used_at.cmp(&a.last_used_at));
+        profiles.sort_by_key(|a| std::cmp::Reverse(a.last_used_at));
 
         Ok(profiles)
     }
@@ -298,6 +305,70 @@ impl ProfileStore {
         Ok(())
     }
 
+    /// Replace a profile's model fallback list.
+    ///
+    /// Names are trimmed, a `models/` prefix is dropped and duplicates are
+    /// removed. Returns the stored list.
+    pub fn set_model_fallbacks(
+        &self,
+        profile_id: &str,
+        models: Vec<String>,
+    ) -> Result<Vec<String>> {
+        let mut profile = self
+            .get_profile(profile_id)?
+            .ok_or_else(|| ProfileError::ProfileNotFound(profile_id.to_string()))?;
+
+        let mut fallbacks: Vec<String> = Vec::new();
+        for model in models {
+            let model = model.trim();
+            let model = model.strip_prefix("models/").unwrap_or(model);
+            if model.is_empty() {
+                continue;
+            }
+            let valid = model
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'));
+            if !valid {
+                return Err(ProfileError::InvalidModelFallback(model.to_string()));
+            }
+            if !fallbacks.iter().any(|known| known == model) {
+                fallbacks.push(model.to_string());
+            }
+        }
+        if fallbacks.len() > MAX_MODEL_FALLBACKS {
+            return Err(ProfileError::InvalidModelFallback(format!(
+                "at most {} fallbacks are allowed",
+                MAX_MODEL_FALLBACKS
+            )));
+        }
+
+        profile.model_fallbacks = fallbacks.clone();
+        let profile_path = self.get_profile_dir(profile_id).join(PROFILE_FILE);
+        self.write_json_atomic(&profile_path, &profile)?;
+        Ok(fallbacks)
+    }
+
+    /// Turn the profile's LLM audit log on or off. Existing entries are
+    /// kept either way; see `purge_llm_audit`.
+    pub fn set_llm_audit_enabled(&self, profile_id: &str, enabled: bool) -> Result<()> {
+        let mut profile = self
+            .get_profile(profile_id)?
+            .ok_or_else(|| ProfileError::ProfileNotFound(profile_id.to_string()))?;
+        profile.llm_audit_enabled = enabled;
+        let profile_path = self.get_profile_dir(profile_id).join(PROFILE_FILE);
+        self.write_json_atomic(&profile_path, &profile)
+    }
+
+    /// Turn metadata stripping of newly stored images on or off.
+    pub fn set_strip_image_metadata(&self, profile_id: &str, enabled: bool) -> Result<()> {
+        let mut profile = self
+            .get_profile(profile_id)?
+            .ok_or_else(|| ProfileError::ProfileNotFound(profile_id.to_string()))?;
+        profile.strip_image_metadata = enabled;
+        let profile_path = self.get_profile_dir(profile_id).join(PROFILE_FILE);
+        self.write_json_atomic(&profile_path, &profile)
+    }
+
     /// Check if any profiles exist.
     pub fn has_profiles(&self) -> Result<bool> {
         let index = self.load_index()?;
@@ -352,6 +423,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_model_fallbacks_survive_relogin() {
+        let store = temp_store();
+        let profile = Profile::new("test@gmail.com", "Test User", None, None);
+        store.upsert_profile(&profile).unwrap();
+
+        let stored = store
+            .set_model_fallbacks(
+                &profile.id,
+                vec![
+                    " models/gemini-2.5-flash ".to_string(),
+                    "gemini-2.5-flash".to_string(),
+                    "".to_string(),
+                    "gemini-2.5-flash-lite".to_string(),
+                ],
+            )
+            .unwrap();
+        assert_eq!(stored, vec!["gemini-2.5-flash", "gemini-2.5-flash-lite"]);
+
+        store.upsert_profile(&profile).unwrap();
+        let loaded = store.get_profile(&profile.id).unwrap().unwrap();
+        assert_eq!(loaded.model_fallbacks, stored);
+
+        assert!(matches!(
+            store.set_model_fallbacks(&profile.id, vec!["gemini/../x".to_string()]),
+            Err(ProfileError::InvalidModelFallback(_))
+        ));
+    }
+
     #[test]
     fn test_provider_key_path() {
         let store = temp_store();
@@ -35,6 +35,20 @@ pub struct Profile {
 
     /// Last time this profile was used/logged into.
     pub last_used_at: DateTime<Utc>,
+
+    /// Models to retry with, in order, when the chosen model is not found
+    /// or overloaded.
+    #[serde(default)]
+    pub model_fallbacks: Vec<String>,
+
+    /// Record outbound LLM calls in the profile's audit log. Off by default.
+    #[serde(default)]
+    pub llm_audit_enabled: bool,
+
+    /// Strip EXIF and other metadata from images when they are stored.
+    /// Uploads are always stripped.
+    #[serde(default)]
+    pub strip_image_metadata: bool,
 }
 
 impl Profile {
@@ -64,6 +78,9 @@ impl Profile {
             original_avatar,
             created_at: now,
             last_used_at: now,
+            model_fallbacks: Vec::new(),
+            llm_audit_enabled: false,
+            strip_image_metadata: false,
         }
     }
 
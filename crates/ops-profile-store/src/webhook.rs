@@ -0,0 +1,257 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-profile outbound webhook.
+//!
+//! When enabled, app events are posted as JSON to a user-supplied URL so
+//! they can drive automations. The signing secret is stored encrypted like
+//! BYOK keys, in its own file, and never returned to the UI.
+
+use std::path::PathBuf;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::error::{ProfileError, Result};
+use crate::security::{read_encrypted_secret, write_encrypted_secret};
+use crate::store::ProfileStore;
+
+/// Webhook settings filename inside a profile directory.
+const WEBHOOK_FILE: &str = "webhook.json";
+
+/// Encrypted signing secret filename inside a profile directory.
+const WEBHOOK_SECRET_FILE: &str = "webhook_secret.json";
+
+/// A screen capture was stored as a new chat.
+pub const WEBHOOK_EVENT_CAPTURE_COMPLETE: &str = "capture.complete";
+/// The first reply for an image finished.
+pub const WEBHOOK_EVENT_ANALYSIS_FINISHED: &str = "analysis.finished";
+/// OCR finished for an image.
+pub const WEBHOOK_EVENT_OCR_DONE: &str = "ocr.done";
+
+pub const WEBHOOK_EVENTS: &[&str] = &[
+    WEBHOOK_EVENT_CAPTURE_COMPLETE,
+    WEBHOOK_EVENT_ANALYSIS_FINISHED,
+    WEBHOOK_EVENT_OCR_DONE,
+];
+
+/// Webhook settings as stored and shown in the UI.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Endpoint receiving `POST` requests. Must be `http` or `https`.
+    #[serde(default)]
+    pub url: String,
+
+    /// Events to send; empty sends all of [`WEBHOOK_EVENTS`].
+    #[serde(default)]
+    pub events: Vec<String>,
+
+    /// Whether a signing secret is stored. Read-only.
+    #[serde(default, skip_deserializing)]
+    pub has_secret: bool,
+}
+
+impl WebhookConfig {
+    /// Whether `event` should be delivered.
+    pub fn wants(&self, event: &str) -> bool {
+        self.enabled
+            && !self.url.is_empty()
+            && (self.events.is_empty() || self.events.iter().any(|wanted| wanted == event))
+    }
+
+    fn normalize(mut self) -> Result<Self> {
+        self.url = self.url.trim().to_string();
+        if !self.url.is_empty() {
+            let parsed = url::Url::parse(&self.url)
+                .map_err(|e| ProfileError::InvalidWebhook(format!("invalid URL: {}", e)))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(ProfileError::InvalidWebhook(
+                    "URL must use http or https".to_string(),
+                ));
+            }
+        } else if self.enabled {
+            return Err(ProfileError::InvalidWebhook("URL is required".to_string()));
+        }
+
+        let mut events: Vec<String> = Vec::new();
+        for event in self.events {
+            let event = event.trim();
+            if !WEBHOOK_EVENTS.contains(&event) {
+                return Err(ProfileError::InvalidWebhook(format!(
+                    "unknown event: {}",
+                    event
+                )));
+            }
+            if !events.iter().any(|known| known == event) {
+                events.push(event.to_string());
+            }
+        }
+        self.events = events;
+        Ok(self)
+    }
+}
+
+impl ProfileStore {
+    /// Get the webhook settings path for a profile.
+    pub fn get_webhook_path(&self, profile_id: &str) -> PathBuf {
+        self.get_profile_dir(profile_id).join(WEBHOOK_FILE)
+    }
+
+    /// The profile's webhook settings; disabled defaults when never saved.
+    pub fn get_webhook_config(&self, profile_id: &str) -> Result<WebhookConfig> {
+        let path = self.get_webhook_path(profile_id);
+        let mut config = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)?
+        } else {
+            WebhookConfig::default()
+        };
+        config.has_secret = self.webhook_secret_path(profile_id).exists();
+        Ok(config)
+    }
+
+    /// Save webhook settings. `secret` replaces the stored signing secret
+    /// when given; an empty one removes it. Returns the stored settings.
+    pub fn set_webhook_config(
+        &self,
+        profile_id: &str,
+        config: WebhookConfig,
+        secret: Option<&str>,
+    ) -> Result<WebhookConfig> {
+        if self.get_profile(profile_id)?.is_none() {
+            return Err(ProfileError::ProfileNotFound(profile_id.to_string()));
+        }
+        let config = config.normalize()?;
+
+        match secret.map(str::trim) {
+            Some("") => {
+                let path = self.webhook_secret_path(profile_id);
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+            }
+            Some(secret) => {
+                write_encrypted_secret(self, &self.webhook_secret_path(profile_id), secret)?
+            }
+            None => {}
+        }
+
+        self.write_json_atomic(&self.get_webhook_path(profile_id), &config)?;
+        self.get_webhook_config(profile_id)
+    }
+
+    /// The decrypted signing secret, if one is stored.
+    pub fn get_webhook_secret(&self, profile_id: &str) -> Result<Option<String>> {
+        read_encrypted_secret(&self.webhook_secret_path(profile_id))
+    }
+
+    fn webhook_secret_path(&self, profile_id: &str) -> PathBuf {
+        self.get_profile_dir(profile_id).join(WEBHOOK_SECRET_FILE)
+    }
+}
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{body}"`, sent as
+/// `X-Squigit-Signature: sha256=<hex>`. Including the timestamp lets
+/// receivers reject replays.
+pub fn sign_webhook_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Profile;
+    use tempfile::tempdir;
+
+    fn temp_store() -> (ProfileStore, String) {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        std::mem::forget(temp_dir);
+        let store = ProfileStore::with_base_dir(root.join("Local Storage")).unwrap();
+        let profile = Profile::new("hooks@example.com", "Hook User", None, None);
+        store.upsert_profile(&profile).unwrap();
+        (store, profile.id)
+    }
+
+    #[test]
+    fn config_and_secret_round_trip() {
+        let (store, profile_id) = temp_store();
+        let config = WebhookConfig {
+            enabled: true,
+            url: " https://example.com/hook ".to_string(),
+            events: vec![
+                WEBHOOK_EVENT_OCR_DONE.to_string(),
+                WEBHOOK_EVENT_OCR_DONE.to_string(),
+            ],
+            has_secret: false,
+        };
+
+        let saved = store
+            .set_webhook_config(&profile_id, config, Some("s3cret"))
+            .unwrap();
+        assert_eq!(saved.url, "https://example.com/hook");
+        assert_eq!(saved.events, vec![WEBHOOK_EVENT_OCR_DONE.to_string()]);
+        assert!(saved.has_secret);
+        assert!(saved.wants(WEBHOOK_EVENT_OCR_DONE));
+        assert!(!saved.wants(WEBHOOK_EVENT_CAPTURE_COMPLETE));
+        assert_eq!(
+            store.get_webhook_secret(&profile_id).unwrap().as_deref(),
+            Some("s3cret")
+        );
+
+        let cleared = store
+            .set_webhook_config(&profile_id, saved, Some(""))
+            .unwrap();
+        assert!(!cleared.has_secret);
+        assert_eq!(store.get_webhook_secret(&profile_id).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let (store, profile_id) = temp_store();
+        let enabled_without_url = WebhookConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(store
+            .set_webhook_config(&profile_id, enabled_without_url, None)
+            .is_err());
+
+        let bad_event = WebhookConfig {
+            url: "https://example.com".to_string(),
+            events: vec!["chat.deleted".to_string()],
+            ..Default::default()
+        };
+        assert!(store
+            .set_webhook_config(&profile_id, bad_event, None)
+            .is_err());
+
+        let bad_scheme = WebhookConfig {
+            url: "file:///etc/passwd".to_string(),
+            ..Default::default()
+        };
+        assert!(store
+            .set_webhook_config(&profile_id, bad_scheme, None)
+            .is_err());
+    }
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let signature = sign_webhook_payload("key", 1_700_000_000, b"{}");
+        assert_eq!(signature.len(), 64);
+        assert_ne!(signature, sign_webhook_payload("key", 1_700_000_001, b"{}"));
+        assert_ne!(
+            signature,
+            sign_webhook_payload("other", 1_700_000_000, b"{}")
+        );
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integrity checks for sidecar binaries bundled under `binaries/`.
+//!
+//! Packaging writes a checksum manifest next to the sidecars; at runtime the
+//! app verifies a sidecar against it before spawning. A mismatch usually
+//! means antivirus quarantine or an interrupted update, and is reported as a
+//! structured result instead of surfacing as an opaque spawn failure.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use ops_sidecar_integrity::{verify_file, SidecarManifest, IntegrityStatus};
+//! use std::path::Path;
+//!
+//! let binaries = Path::new("/opt/squigit/binaries");
+//! if let Some(manifest) = SidecarManifest::load(binaries).unwrap() {
+//!     let sidecar = binaries.join("qt-capture-x86_64-unknown-linux-gnu/capture-engine");
+//!     match verify_file(&manifest, binaries, &sidecar) {
+//!         IntegrityStatus::Verified | IntegrityStatus::Unlisted => {}
+//!         status => eprintln!("sidecar corrupt: {:?}", status),
+//!     }
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Manifest file name inside the `binaries/` resource directory.
+pub const MANIFEST_FILE_NAME: &str = "sidecar-checksums.json";
+
+const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum IntegrityError {
+    #[error("Failed to read sidecar manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid sidecar manifest: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Unsupported sidecar manifest version {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// BLAKE3 checksums keyed by `/`-separated path relative to `binaries/`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SidecarManifest {
+    pub version: u32,
+    pub files: BTreeMap<String, String>,
+}
+
+impl SidecarManifest {
+    /// Load the manifest from `binaries_dir`. `Ok(None)` when no manifest is
+    /// shipped (dev builds), in which case nothing can be verified.
+    pub fn load(binaries_dir: &Path) -> Result<Option<Self>, IntegrityError> {
+        let path = binaries_dir.join(MANIFEST_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let manifest: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+        if manifest.version != MANIFEST_VERSION {
+            return Err(IntegrityError::UnsupportedVersion(manifest.version));
+        }
+        Ok(Some(manifest))
+    }
+
+    /// Hash every file under `binaries_dir` (except the manifest itself).
+    pub fn generate(binaries_dir: &Path) -> Result<Self, IntegrityError> {
+        let mut files = BTreeMap::new();
+        let mut stack = vec![binaries_dir.to_path_buf()];
+        while let Some(dir) = stack.pop() {
+            for entry in std::fs::read_dir(&dir)? {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                let path = entry.path();
+                if file_type.is_dir() {
+                    stack.push(path);
+                } else if file_type.is_file() {
+                    let Some(key) = manifest_key(binaries_dir, &path) else {
+                        continue;
+                    };
+                    if key == MANIFEST_FILE_NAME {
+                        continue;
+                    }
+                    files.insert(key, hash_file(&path)?);
+                }
+            }
+        }
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            files,
+        })
+    }
+
+    pub fn write(&self, binaries_dir: &Path) -> Result<PathBuf, IntegrityError> {
+        let path = binaries_dir.join(MANIFEST_FILE_NAME);
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+/// Result of checking one file against the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    Verified,
+    /// Not covered by the manifest (outside `binaries/` or installed system-wide).
+    Unlisted,
+    /// Listed in the manifest but absent on disk.
+    Missing,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The file exists but could not be read (often an AV lock).
+    Unreadable {
+        reason: String,
+    },
+}
+
+impl IntegrityStatus {
+    pub fn is_corrupt(&self) -> bool {
+        matches!(
+            self,
+            Self::Missing | Self::Mismatch { .. } | Self::Unreadable { .. }
+        )
+    }
+}
+
+/// Verify `path` against `manifest`, where `binaries_dir` is the directory
+/// the manifest keys are relative to.
+pub fn verify_file(
+    manifest: &SidecarManifest,
+    binaries_dir: &Path,
+    path: &Path,
+) -> IntegrityStatus {
+    let Some(key) = manifest_key(binaries_dir, path) else {
+        return IntegrityStatus::Unlisted;
+    };
+    let Some(expected) = manifest.files.get(&key) else {
+        return IntegrityStatus::Unlisted;
+    };
+    if !path.exists() {
+        return IntegrityStatus::Missing;
+    }
+    match hash_file(path) {
+        Ok(actual) if actual.eq_ignore_ascii_case(expected) => IntegrityStatus::Verified,
+        Ok(actual) => IntegrityStatus::Mismatch {
+            expected: expected.clone(),
+            actual,
+        },
+        Err(e) => IntegrityStatus::Unreadable {
+            reason: e.to_string(),
+        },
+    }
+}
+
+/// Streaming BLAKE3 hex digest of a file.
+pub fn hash_file(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+fn manifest_key(binaries_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(binaries_dir).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify_file, IntegrityStatus, SidecarManifest};
+    use tempfile::tempdir;
+
+    #[test]
+    fn generated_manifest_detects_tampering_and_removal() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let sidecar_dir = root.join("qt-capture-test");
+        std::fs::create_dir_all(&sidecar_dir).unwrap();
+        let binary = sidecar_dir.join("capture-engine");
+        std::fs::write(&binary, b"original").unwrap();
+
+        SidecarManifest::generate(root)
+            .unwrap()
+            .write(root)
+            .unwrap();
+        let manifest = SidecarManifest::load(root).unwrap().unwrap();
+        assert!(manifest
+            .files
+            .contains_key("qt-capture-test/capture-engine"));
+        assert_eq!(
+            verify_file(&manifest, root, &binary),
+            IntegrityStatus::Verified
+        );
+
+        std::fs::write(&binary, b"patched").unwrap();
+        assert!(matches!(
+            verify_file(&manifest, root, &binary),
+            IntegrityStatus::Mismatch { .. }
+        ));
+
+        std::fs::remove_file(&binary).unwrap();
+        assert_eq!(
+            verify_file(&manifest, root, &binary),
+            IntegrityStatus::Missing
+        );
+        assert_eq!(
+            verify_file(
+                &manifest,
+                root,
+                std::path::Path::new("/usr/bin/squigit-ocr")
+            ),
+            IntegrityStatus::Unlisted
+        );
+    }
+}
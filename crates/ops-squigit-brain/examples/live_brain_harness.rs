@@ -49,6 +49,13 @@ async fn run() -> Result<(), String> {
                         user_email: None,
                         user_instruction: None,
                         ocr_lang: None,
+                        ocr_regions: None,
+                        redact_pii: false,
+                        response_language: None,
+                        glossary: Vec::new(),
+                        fallback_models: Vec::new(),
+                        title: None,
+                        generate_brief: true,
                     },
                 )
                 .await?;
@@ -85,6 +92,9 @@ async fn run() -> Result<(), String> {
                         channel_id: format!("cli-prompt-{}", chrono::Utc::now().timestamp_millis()),
                         user_name: None,
                         user_email: None,
+                        response_language: None,
+                        glossary: Vec::new(),
+                        fallback_models: Vec::new(),
                     },
                 )
                 .await?;
@@ -0,0 +1,53 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records outbound LLM calls in the active profile's audit log, for
+//! profiles that opted in.
+
+use chrono::Utc;
+use ops_profile_store::{LlmAuditEntry, LlmAuditStatus, ProfileStore};
+use std::future::Future;
+use std::time::Instant;
+
+/// Run `call` and log it as `operation` against `model`. `prompt` is the
+/// caller-supplied text of the request (the user message, transcript or
+/// context), of which only the size and a hash are kept.
+pub(crate) async fn audited<T, F>(
+    operation: &str,
+    model: &str,
+    prompt: &str,
+    call: F,
+) -> Result<T, String>
+where
+    F: Future<Output = Result<T, String>>,
+{
+    let timestamp = Utc::now();
+    let started = Instant::now();
+    let result = call.await;
+
+    let status = match &result {
+        Ok(_) => LlmAuditStatus::Ok,
+        Err(error) if error == "CANCELLED" => LlmAuditStatus::Cancelled,
+        Err(_) => LlmAuditStatus::Error,
+    };
+    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    record(&LlmAuditEntry::new(
+        timestamp,
+        operation,
+        model,
+        prompt,
+        duration_ms,
+        status,
+    ));
+    result
+}
+
+fn record(entry: &LlmAuditEntry) {
+    let result = ProfileStore::new().and_then(|store| match store.get_active_profile()? {
+        Some(profile) if profile.llm_audit_enabled => store.append_llm_audit(&profile.id, entry),
+        _ => Ok(()),
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to write LLM audit entry: {}", e);
+    }
+}
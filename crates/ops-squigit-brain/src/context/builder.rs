@@ -7,8 +7,99 @@ use crate::context::loader::{
     interpolate, load_frame, load_image_brief_prompt, load_scenes, load_soul, load_system,
     load_title_prompt,
 };
+use ops_chat_storage::{OcrConfidenceSummary, OcrRegion, PluginNote, WebSource};
+use ops_profile_store::GlossaryEntry;
 use std::collections::HashMap;
 
+/// Languages accepted by the response language preference, as
+/// (BCP 47 tag, English name).
+pub const RESPONSE_LANGUAGES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("bn", "Bengali"),
+    ("cs", "Czech"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fa", "Persian"),
+    ("fr", "French"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("th", "Thai"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("ur", "Urdu"),
+    ("vi", "Vietnamese"),
+    ("zh-CN", "Simplified Chinese"),
+    ("zh-TW", "Traditional Chinese"),
+];
+
+/// English name of a supported response language tag (case-insensitive).
+pub fn response_language_name(tag: &str) -> Option<&'static str> {
+    let tag = tag.trim();
+    RESPONSE_LANGUAGES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(tag))
+        .map(|(_, name)| *name)
+}
+
+/// Build the instruction that pins the reply language.
+pub fn build_response_language_note(tag: &str) -> Result<String, String> {
+    let name = response_language_name(tag)
+        .ok_or_else(|| format!("ERR_UNSUPPORTED_RESPONSE_LANGUAGE: {}", tag))?;
+    Ok(format!(
+        "\n\n## Response Language\n\
+        Always reply in {}, whatever language the screenshot, attachments or \
+        quoted text are in. Keep code, commands and proper nouns as they are. \
+        Switch languages only when the user explicitly asks for it.",
+        name
+    ))
+}
+
+/// Most glossary entries listed in the system instruction.
+const MAX_GLOSSARY_NOTE_ENTRIES: usize = 100;
+
+/// Build the glossary section of the system instruction.
+/// Returns `None` when the profile has no glossary.
+pub fn build_glossary_note(entries: &[GlossaryEntry]) -> Option<String> {
+    if entries.is_empty() {
+        return None;
+    }
+
+    let mut note = String::from(
+        "\n\n## Glossary\n\
+        The user works with these terms. Use the spellings and meanings given here, \
+        and read screenshot text that resembles a listed spelling as that term.\n",
+    );
+    for entry in entries.iter().take(MAX_GLOSSARY_NOTE_ENTRIES) {
+        note.push_str(&format!("- **{}**", entry.term));
+        if let Some(expansion) = entry.expansion.as_deref() {
+            note.push_str(&format!(": {}", expansion));
+        }
+        if !entry.variants.is_empty() {
+            note.push_str(&format!(" (also seen as: {})", entry.variants.join(", ")));
+        }
+        note.push('\n');
+    }
+    if entries.len() > MAX_GLOSSARY_NOTE_ENTRIES {
+        note.push_str(&format!(
+            "- ...and {} more terms\n",
+            entries.len() - MAX_GLOSSARY_NOTE_ENTRIES
+        ));
+    }
+    Some(note)
+}
+
 /// Build the system prompt for the initial turn (with image).
 /// This includes the full soul identity and scenes knowledge base.
 pub fn build_initial_system_prompt() -> Result<String, String> {
@@ -200,6 +291,123 @@ fn get_os_version() -> String {
     std::env::consts::OS.to_string()
 }
 
+/// Build a short warning about unreliable OCR text.
+/// Returns `None` when no region fell below the confidence threshold.
+pub fn build_ocr_confidence_note(summary: &OcrConfidenceSummary) -> Option<String> {
+    let described = summary.describe()?;
+    Some(format!(
+        "\n## OCR Reliability\nThe on-screen text extraction reported {} (out of {}). \
+        Treat text from those regions as unreliable and read it from the image directly.",
+        described, summary.total_regions
+    ))
+}
+
+/// Longest OCR transcript embedded in the initial prompt, in characters.
+const MAX_OCR_TRANSCRIPT_CHARS: usize = 8000;
+
+/// Build the on-screen text block for the initial turn from stored OCR
+/// regions, in the engine's reading order. Low-confidence regions, blank
+/// lines and repeated lines are left out. With `redact_pii`, personal data
+/// is replaced by placeholders such as `[EMAIL]`. Returns `None` when
+/// nothing usable remains.
+pub fn build_ocr_transcript_block(regions: &[OcrRegion], redact_pii: bool) -> Option<String> {
+    let mut transcript = String::new();
+    let mut previous = String::new();
+    let mut truncated = false;
+
+    for region in regions.iter().filter(|region| !region.low_confidence) {
+        let line = region
+            .export_text()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let line = if redact_pii {
+            ops_redaction::redact(&line)
+        } else {
+            line
+        };
+        if line.is_empty() || line == previous {
+            continue;
+        }
+        if transcript.chars().count() + line.chars().count() > MAX_OCR_TRANSCRIPT_CHARS {
+            truncated = true;
+            break;
+        }
+        transcript.push_str(&line);
+        transcript.push('\n');
+        previous = line;
+    }
+
+    if transcript.is_empty() {
+        return None;
+    }
+    if truncated {
+        transcript.push_str("[...transcript truncated]\n");
+    }
+    Some(format!(
+        "\n## On-Screen Text (OCR)\n\
+        Text extracted from the screenshot in reading order. Use it for small or dense \
+        text, but trust the image where they disagree.\n```text\n{}```",
+        transcript
+    ))
+}
+
+/// Build the plugin findings block for the initial turn from the notes
+/// processor plugins attached to the chat. Returns `None` when no plugin
+/// added anything.
+pub fn build_plugin_context_block(notes: &[PluginNote]) -> Option<String> {
+    let mut block = String::new();
+    for note in notes {
+        if note.context.is_none() && note.annotations.is_empty() {
+            continue;
+        }
+        block.push_str(&format!("\n### {}\n", note.plugin_name));
+        if let Some(context) = note.context.as_deref() {
+            block.push_str(context);
+            block.push('\n');
+        }
+        for annotation in &note.annotations {
+            block.push_str(&format!("- {}: {}\n", annotation.label, annotation.text));
+        }
+    }
+
+    if block.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "\n## Plugin Findings\n\
+        Local tools the user installed analyzed the screenshot. Their findings may help, \
+        but they can be wrong; trust the image where they disagree.\n{}",
+        block
+    ))
+}
+
+/// Build the web page block for the initial turn of a chat captured by the
+/// browser extension, with the text the user had selected on the page.
+pub fn build_web_source_block(source: &WebSource) -> String {
+    let page = match source.title.as_deref().map(str::trim) {
+        Some(title) if !title.is_empty() => format!("\"{}\" <{}>", title, source.url),
+        _ => format!("<{}>", source.url),
+    };
+    let mut block = format!(
+        "\n## Web Page\n\
+        The screenshot was taken in the user's browser on {}.\n",
+        page
+    );
+    if let Some(selected) = source
+        .selected_text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+    {
+        block.push_str(&format!(
+            "The user selected this text on the page:\n```text\n{}\n```\n",
+            selected
+        ));
+    }
+    block
+}
+
 /// Format conversation history for the frame template.
 /// Takes the last N message pairs and formats them as markdown.
 pub fn format_history_log(messages: &[(String, String)], max_turns: usize) -> String {
@@ -233,6 +441,30 @@ mod tests {
         assert!(prompt.contains("Core Instructions"));
     }
 
+    #[test]
+    fn response_language_note_accepts_known_tags_only() {
+        let note = build_response_language_note("zh-cn").expect("known tag");
+        assert!(note.contains("Simplified Chinese"));
+        assert!(build_response_language_note("xx").is_err());
+        assert_eq!(response_language_name(" de "), Some("German"));
+    }
+
+    #[test]
+    fn test_glossary_note() {
+        assert!(build_glossary_note(&[]).is_none());
+
+        let entry = GlossaryEntry {
+            id: "abc".to_string(),
+            term: "SLO".to_string(),
+            expansion: Some("service level objective".to_string()),
+            variants: vec!["S.L.O".to_string()],
+            updated_at: chrono::Utc::now(),
+        };
+        let note = build_glossary_note(&[entry]).expect("note");
+        assert!(note.contains("## Glossary"));
+        assert!(note.contains("- **SLO**: service level objective (also seen as: S.L.O)"));
+    }
+
     #[test]
     fn test_build_turn_context() {
         let context = build_turn_context(
@@ -258,6 +490,104 @@ mod tests {
         assert!(context.contains("What next?"));
     }
 
+    #[test]
+    fn test_ocr_confidence_note() {
+        let clean = OcrConfidenceSummary {
+            total_regions: 40,
+            low_confidence_regions: 0,
+        };
+        assert!(build_ocr_confidence_note(&clean).is_none());
+
+        let noisy = OcrConfidenceSummary {
+            total_regions: 40,
+            low_confidence_regions: 12,
+        };
+        let note = build_ocr_confidence_note(&noisy).expect("note");
+        assert!(note.contains("12 regions below threshold"));
+        assert!(note.contains("out of 40"));
+    }
+
+    #[test]
+    fn test_ocr_transcript_block() {
+        let region = |text: &str, low_confidence: bool| OcrRegion {
+            text: text.to_string(),
+            bbox: vec![],
+            confidence: None,
+            low_confidence,
+            latex: None,
+            pii: Vec::new(),
+        };
+        assert!(build_ocr_transcript_block(&[region("  ", false)], false).is_none());
+
+        let block = build_ocr_transcript_block(
+            &[
+                region("error:  E0502", false),
+                region("error:  E0502", false),
+                region("g@rbled", true),
+                region("cannot borrow `x`", false),
+            ],
+            false,
+        )
+        .expect("block");
+        assert!(block.contains("```text\nerror: E0502\ncannot borrow `x`\n```"));
+        assert!(!block.contains("g@rbled"));
+
+        let block = build_ocr_transcript_block(&[region("mail jane@example.com", false)], true)
+            .expect("block");
+        assert!(block.contains("mail [EMAIL]"));
+        assert!(!block.contains("jane@example.com"));
+    }
+
+    #[test]
+    fn test_plugin_context_block() {
+        let note = |name: &str, context: Option<&str>, annotations: Vec<(&str, &str)>| PluginNote {
+            plugin_id: name.to_lowercase(),
+            plugin_name: name.to_string(),
+            context: context.map(str::to_string),
+            annotations: annotations
+                .into_iter()
+                .map(|(label, text)| ops_chat_storage::PluginAnnotation {
+                    label: label.to_string(),
+                    text: text.to_string(),
+                })
+                .collect(),
+            created_at: chrono::Utc::now(),
+        };
+        assert!(build_plugin_context_block(&[note("Empty", None, vec![])]).is_none());
+
+        let block = build_plugin_context_block(&[
+            note("Empty", None, vec![]),
+            note(
+                "Invoice",
+                Some("An invoice from ACME."),
+                vec![("Total", "42 EUR")],
+            ),
+        ])
+        .expect("block");
+        assert!(block.contains("## Plugin Findings"));
+        assert!(block.contains("### Invoice\nAn invoice from ACME.\n- Total: 42 EUR\n"));
+        assert!(!block.contains("### Empty"));
+    }
+
+    #[test]
+    fn test_web_source_block() {
+        let mut source = WebSource {
+            url: "https://example.com/post".to_string(),
+            title: Some("A post".to_string()),
+            selected_text: Some("  the quoted part \n".to_string()),
+        };
+        let block = build_web_source_block(&source);
+        assert!(block.contains("## Web Page"));
+        assert!(block.contains("\"A post\" <https://example.com/post>"));
+        assert!(block.contains("```text\nthe quoted part\n```"));
+
+        source.title = None;
+        source.selected_text = Some("   ".to_string());
+        let block = build_web_source_block(&source);
+        assert!(block.contains("on <https://example.com/post>."));
+        assert!(!block.contains("selected"));
+    }
+
     #[test]
     fn test_format_history() {
         let messages = vec![
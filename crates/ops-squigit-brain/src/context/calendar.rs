@@ -0,0 +1,308 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Calendar events extracted from a screenshot.
+//!
+//! The model answers with JSON matching [`events_response_schema`]; this
+//! module turns that into validated [`CalendarEvent`]s and writes them as
+//! an iCalendar file. Times are local wall-clock times and are written as
+//! floating iCalendar times, so calendars show them as read on screen.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Most events kept from one extraction.
+const MAX_EVENTS: usize = 20;
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_TEXT_CHARS: usize = 2_000;
+const ICS_PRODID: &str = "-//Squigit//Event Extraction//EN";
+/// iCalendar content lines are folded at 75 octets.
+const ICS_LINE_OCTETS: usize = 75;
+
+pub const EVENTS_PROMPT: &str = "List the calendar events, appointments, deadlines and \
+reminders shown in this screenshot. Today is {today}; resolve relative dates like \"next \
+Friday\" against it and assume the nearest future date when the year is missing. Use local \
+wall-clock times exactly as shown, as YYYY-MM-DDTHH:MM, or YYYY-MM-DD for events without a \
+time. Leave out end, location and description when the screenshot doesn't give them. Return \
+an empty list if there are no events.";
+
+/// Gemini `responseSchema` for [`EVENTS_PROMPT`].
+pub fn events_response_schema() -> Value {
+    json!({
+        "type": "OBJECT",
+        "properties": {
+            "events": {
+                "type": "ARRAY",
+                "items": {
+                    "type": "OBJECT",
+                    "properties": {
+                        "title": { "type": "STRING" },
+                        "start": { "type": "STRING" },
+                        "end": { "type": "STRING" },
+                        "location": { "type": "STRING" },
+                        "description": { "type": "STRING" }
+                    },
+                    "required": ["title", "start"]
+                }
+            }
+        },
+        "required": ["events"]
+    })
+}
+
+/// A validated event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    pub title: String,
+    /// Local start; midnight for all-day events.
+    pub start: NaiveDateTime,
+    /// Local end, after `start`. For all-day events, the last day.
+    #[serde(default)]
+    pub end: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub all_day: bool,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEvents {
+    #[serde(default)]
+    events: Vec<RawEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEvent {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    start: String,
+    #[serde(default)]
+    end: Option<String>,
+    #[serde(default)]
+    location: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Validate the model's JSON reply. Events without a title or a readable
+/// start are dropped, as are duplicates; the rest are sorted by start.
+pub fn parse_events(reply: &str) -> Result<Vec<CalendarEvent>, String> {
+    let reply = reply.trim();
+    let reply = reply
+        .strip_prefix("```json")
+        .or_else(|| reply.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .unwrap_or(reply);
+    let raw: RawEvents =
+        serde_json::from_str(reply).map_err(|e| format!("ERR_INVALID_EVENTS_REPLY: {}", e))?;
+
+    let mut events: Vec<CalendarEvent> = Vec::new();
+    for raw in raw.events {
+        let Some(title) = clean_text(&raw.title, MAX_TITLE_CHARS) else {
+            continue;
+        };
+        let Some((start, all_day)) = parse_time(&raw.start) else {
+            continue;
+        };
+        let end = raw
+            .end
+            .as_deref()
+            .and_then(parse_time)
+            .filter(|(end, _)| *end > start)
+            .map(|(end, _)| end);
+
+        let event = CalendarEvent {
+            title,
+            start,
+            end,
+            all_day,
+            location: raw
+                .location
+                .as_deref()
+                .and_then(|text| clean_text(text, MAX_TEXT_CHARS)),
+            description: raw
+                .description
+                .as_deref()
+                .and_then(|text| clean_text(text, MAX_TEXT_CHARS)),
+        };
+        if !events
+            .iter()
+            .any(|known| known.title == event.title && known.start == event.start)
+        {
+            events.push(event);
+        }
+    }
+
+    events.sort_by_key(|event| event.start);
+    events.truncate(MAX_EVENTS);
+    Ok(events)
+}
+
+/// Parse a date or date-time. Returns the local time and whether only a
+/// date was given. Times with an offset are converted to local time.
+fn parse_time(value: &str) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some((date.and_time(NaiveTime::MIN), true));
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some((time.with_timezone(&Local).naive_local(), false));
+    }
+    let value = value.replacen(' ', "T", 1);
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&value, format).ok())
+        .map(|time| (time, false))
+}
+
+fn clean_text(text: &str, max_chars: usize) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.chars().take(max_chars).collect())
+}
+
+/// Write `events` as an iCalendar file. `uid_prefix` keeps UIDs stable
+/// across exports of the same chat.
+pub fn events_to_ics(events: &[CalendarEvent], uid_prefix: &str, now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", ICS_PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    for (index, event) in events.iter().enumerate() {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!(
+            "UID:{}-{}-{}@squigit",
+            uid_prefix,
+            index,
+            event.start.format("%Y%m%dT%H%M")
+        ));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        if event.all_day {
+            lines.push(format!(
+                "DTSTART;VALUE=DATE:{}",
+                event.start.format("%Y%m%d")
+            ));
+            // DTEND is exclusive for dates.
+            let last_day = event.end.unwrap_or(event.start).date();
+            let end = last_day.succ_opt().unwrap_or(last_day);
+            lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+        } else {
+            lines.push(format!("DTSTART:{}", event.start.format("%Y%m%dT%H%M%S")));
+            if let Some(end) = event.end {
+                lines.push(format!("DTEND:{}", end.format("%Y%m%dT%H%M%S")));
+            }
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.title)));
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\n', '\r'], "\\n")
+}
+
+/// Split a content line into 75-octet pieces without breaking characters.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / ICS_LINE_OCTETS * 3);
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts.
+        if octets + c.len_utf8() > ICS_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    #[test]
+    fn parse_events_validates_and_sorts() {
+        let reply = r#"```json
+        {"events": [
+            {"title": "Dentist", "start": "2026-03-05 15:30", "end": "2026-03-05T15:00", "location": " Main St "},
+            {"title": "  ", "start": "2026-03-01"},
+            {"title": "Launch", "start": "next week"},
+            {"title": "Conference", "start": "2026-03-02", "end": "2026-03-04"},
+            {"title": "Conference", "start": "2026-03-02"}
+        ]}
+        ```"#;
+        let events = parse_events(reply).unwrap();
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(events[0].title, "Conference");
+        assert!(events[0].all_day);
+        assert_eq!(events[0].end, Some(at("2026-03-04T00:00")));
+
+        assert_eq!(events[1].title, "Dentist");
+        assert_eq!(events[1].start, at("2026-03-05T15:30"));
+        assert_eq!(events[1].end, None);
+        assert_eq!(events[1].location.as_deref(), Some("Main St"));
+
+        assert!(parse_events("not json").is_err());
+    }
+
+    #[test]
+    fn ics_escapes_and_folds() {
+        let events = vec![
+            CalendarEvent {
+                title: "Standup; daily, with team".to_string(),
+                start: at("2026-03-05T09:00"),
+                end: Some(at("2026-03-05T09:15")),
+                all_day: false,
+                location: None,
+                description: Some(format!("Line one\n{}", "x".repeat(100))),
+            },
+            CalendarEvent {
+                title: "Holiday".to_string(),
+                start: at("2026-03-06T00:00"),
+                end: None,
+                all_day: true,
+                location: None,
+                description: None,
+            },
+        ];
+        let ics = events_to_ics(&events, "chat-1", Utc::now());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("SUMMARY:Standup\\; daily\\, with team\r\n"));
+        assert!(ics.contains("DTSTART:20260305T090000\r\nDTEND:20260305T091500\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260306\r\nDTEND;VALUE=DATE:20260307\r\n"));
+        assert!(ics.contains("DESCRIPTION:Line one\\n"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= ICS_LINE_OCTETS));
+    }
+}
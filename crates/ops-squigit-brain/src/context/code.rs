@@ -0,0 +1,416 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Code reconstructed from a screenshot.
+//!
+//! The model rebuilds the code from the image and its line-preserving OCR
+//! text. The reply is cleaned up here (fences, editor line numbers, stray
+//! indentation), its language settled, and, when the matching formatter is
+//! installed locally, run through it. Missing or failing formatters leave
+//! the cleaned code as is.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+
+/// How long a local formatter may run.
+const FORMAT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const CODE_PROMPT: &str = "This screenshot shows source code. Reconstruct the code exactly \
+as shown: keep identifiers, strings, comments and indentation, fix characters the OCR text \
+below misread, and leave out editor chrome such as line numbers, tabs and status bars. If a \
+line is cut off at the edge of the image, keep what is visible. Name the language as a \
+lowercase identifier such as rust, python, typescript or go.";
+
+/// Gemini `responseSchema` for [`CODE_PROMPT`].
+pub fn code_response_schema() -> Value {
+    json!({
+        "type": "OBJECT",
+        "properties": {
+            "language": { "type": "STRING" },
+            "code": { "type": "STRING" }
+        },
+        "required": ["code"]
+    })
+}
+
+/// Code taken from a screenshot, ready to copy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeSnippet {
+    /// Language ID such as `rust`, or `None` if it couldn't be told.
+    pub language: Option<String>,
+    pub code: String,
+    /// Local tool that formatted the code, if any did.
+    pub formatter: Option<String>,
+    /// The chat artifact holding the code, once saved.
+    pub artifact_id: Option<String>,
+}
+
+impl CodeSnippet {
+    /// File name for the snippet when saved as an artifact.
+    pub fn file_name(&self) -> String {
+        let extension = match self.language.as_deref() {
+            Some("rust") => "rs",
+            Some("python") => "py",
+            Some("javascript") => "js",
+            Some("typescript") => "ts",
+            Some("kotlin") => "kt",
+            Some("csharp") => "cs",
+            Some("ruby") => "rb",
+            Some("shell") => "sh",
+            Some("markdown") => "md",
+            Some(language) => language,
+            None => "txt",
+        };
+        format!("snippet.{}", extension)
+    }
+
+    /// The snippet as a fenced Markdown code block.
+    pub fn to_markdown(&self) -> String {
+        let fence = if self.code.contains("```") {
+            "````"
+        } else {
+            "```"
+        };
+        format!(
+            "{}{}\n{}\n{}",
+            fence,
+            self.language.as_deref().unwrap_or_default(),
+            self.code,
+            fence
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCode {
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    code: String,
+}
+
+/// Parse and clean the model's reply. Formatting is left to
+/// [`format_snippet`].
+pub fn parse_code_reply(reply: &str) -> Result<CodeSnippet, String> {
+    let reply = reply.trim();
+    let reply = reply
+        .strip_prefix("```json")
+        .or_else(|| reply.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .unwrap_or(reply);
+    let raw: RawCode =
+        serde_json::from_str(reply).map_err(|e| format!("ERR_INVALID_CODE_REPLY: {}", e))?;
+
+    let code = cleanup_code(&raw.code);
+    if code.is_empty() {
+        return Err("ERR_NO_CODE_FOUND".to_string());
+    }
+    let language = raw
+        .language
+        .as_deref()
+        .and_then(normalize_language)
+        .or_else(|| detect_language(&code))
+        .map(str::to_string);
+    Ok(CodeSnippet {
+        language,
+        code,
+        formatter: None,
+        artifact_id: None,
+    })
+}
+
+/// Strip Markdown fences, editor line numbers, trailing whitespace, outer
+/// blank lines and indentation shared by every line.
+pub fn cleanup_code(code: &str) -> String {
+    let code = code.replace("\r\n", "\n").replace('\r', "\n");
+    let mut lines: Vec<&str> = code.lines().collect();
+
+    if lines
+        .first()
+        .is_some_and(|line| line.trim_start().starts_with("```"))
+    {
+        lines.remove(0);
+        if lines.last().is_some_and(|line| line.trim() == "```") {
+            lines.pop();
+        }
+    }
+
+    let mut lines: Vec<String> = strip_line_numbers(&lines)
+        .into_iter()
+        .map(|line| line.trim_end().replace('\t', "    "))
+        .collect();
+    while lines.first().is_some_and(|line| line.is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start_matches(' ').len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drop a gutter of line numbers when every non-blank line starts with one
+/// and they count up.
+fn strip_line_numbers<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let numbered: Option<Vec<(u64, &str)>> = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let digits = trimmed.len()
+                - trimmed
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+                    .len();
+            let number = trimmed[..digits].parse().ok()?;
+            let rest = &trimmed[digits..];
+            (rest.is_empty() || rest.starts_with([' ', '\t', '|', ':'])).then_some((number, rest))
+        })
+        .collect();
+    let Some(numbered) = numbered else {
+        return lines.to_vec();
+    };
+    if numbered.len() < 2 || numbered.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+        return lines.to_vec();
+    }
+
+    let mut numbered = numbered.into_iter();
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                return "";
+            }
+            let rest = numbered.next().map(|(_, rest)| rest).unwrap_or(line);
+            let rest = rest.trim_start().strip_prefix(['|', ':']).unwrap_or(rest);
+            // One space goes with the gutter; the rest is indentation.
+            rest.strip_prefix([' ', '\t']).unwrap_or(rest)
+        })
+        .collect()
+}
+
+/// Map a language name or alias to its ID.
+pub fn normalize_language(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_ascii_lowercase();
+    let id = match name.as_str() {
+        "rust" | "rs" => "rust",
+        "python" | "py" | "python3" => "python",
+        "javascript" | "js" | "jsx" | "node" => "javascript",
+        "typescript" | "ts" | "tsx" => "typescript",
+        "go" | "golang" => "go",
+        "java" => "java",
+        "kotlin" | "kt" => "kotlin",
+        "swift" => "swift",
+        "c" => "c",
+        "c++" | "cpp" | "cxx" => "cpp",
+        "c#" | "csharp" | "cs" => "csharp",
+        "ruby" | "rb" => "ruby",
+        "php" => "php",
+        "shell" | "bash" | "sh" | "zsh" => "shell",
+        "sql" => "sql",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "html" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "markdown" | "md" => "markdown",
+        _ => return None,
+    };
+    Some(id)
+}
+
+/// Guess the language from telltale tokens, for replies that don't name it.
+pub fn detect_language(code: &str) -> Option<&'static str> {
+    let has = |needle: &str| code.contains(needle);
+    let starts = |prefix: &str| {
+        code.lines()
+            .any(|line| line.trim_start().starts_with(prefix))
+    };
+
+    let trimmed = code.trim_start();
+    if trimmed.starts_with("#!") {
+        return Some(if has("python") { "python" } else { "shell" });
+    }
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<Value>(code).is_ok()
+    {
+        return Some("json");
+    }
+    if starts("fn ") || starts("pub fn ") || has("let mut ") || starts("use std::") || has("impl ")
+    {
+        return Some("rust");
+    }
+    if starts("package ") && has("func ") {
+        return Some("go");
+    }
+    if starts("def ") || (starts("import ") && !has(";") && !has("{")) || starts("from ") {
+        return Some("python");
+    }
+    if starts("#include") {
+        return Some(if has("std::") || has("class ") {
+            "cpp"
+        } else {
+            "c"
+        });
+    }
+    if starts("public class ") || has("System.out.") {
+        return Some("java");
+    }
+    if starts("interface ") || starts("export interface ") || has(": string") || has(": number") {
+        return Some("typescript");
+    }
+    if starts("function ") || starts("const ") || starts("export ") || has("=> {") {
+        return Some("javascript");
+    }
+    if starts("<!DOCTYPE") || starts("<html") {
+        return Some("html");
+    }
+    let upper = trimmed.to_ascii_uppercase();
+    if ["SELECT ", "INSERT ", "UPDATE ", "CREATE TABLE"]
+        .iter()
+        .any(|keyword| upper.starts_with(keyword))
+    {
+        return Some("sql");
+    }
+    None
+}
+
+/// The local formatter for a language: program and arguments reading the
+/// code from stdin and writing the result to stdout.
+fn formatter_command(language: &str) -> Option<(&'static str, Vec<&'static str>)> {
+    let prettier = if cfg!(windows) {
+        "prettier.cmd"
+    } else {
+        "prettier"
+    };
+    let command = match language {
+        "rust" => ("rustfmt", vec!["--edition", "2021", "--emit", "stdout"]),
+        "python" => ("black", vec!["--quiet", "-"]),
+        "go" => ("gofmt", vec![]),
+        "javascript" => (prettier, vec!["--stdin-filepath", "snippet.js"]),
+        "typescript" => (prettier, vec!["--stdin-filepath", "snippet.ts"]),
+        "json" => (prettier, vec!["--stdin-filepath", "snippet.json"]),
+        "yaml" => (prettier, vec!["--stdin-filepath", "snippet.yaml"]),
+        "html" => (prettier, vec!["--stdin-filepath", "snippet.html"]),
+        "css" => (prettier, vec!["--stdin-filepath", "snippet.css"]),
+        "scss" => (prettier, vec!["--stdin-filepath", "snippet.scss"]),
+        "markdown" => (prettier, vec!["--stdin-filepath", "snippet.md"]),
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Run the snippet through its language's local formatter. The snippet is
+/// returned unchanged when there is no formatter, it isn't installed, or it
+/// rejects the code.
+pub async fn format_snippet(mut snippet: CodeSnippet) -> CodeSnippet {
+    let Some((program, args)) = snippet.language.as_deref().and_then(formatter_command) else {
+        return snippet;
+    };
+    match run_formatter(program, &args, &snippet.code).await {
+        Ok(formatted) => {
+            snippet.code = formatted;
+            snippet.formatter = Some(program.trim_end_matches(".cmd").to_string());
+        }
+        Err(e) => log::debug!("[CodeSnippet] {} skipped: {}", program, e),
+    }
+    snippet
+}
+
+async fn run_formatter(program: &str, args: &[&str], code: &str) -> Result<String, String> {
+    let mut cmd = tokio::process::Command::new(program);
+    cmd.args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let mut stdin = child.stdin.take().ok_or("no stdin")?;
+    let input = format!("{}\n", code);
+    let output = tokio::time::timeout(FORMAT_TIMEOUT, async move {
+        stdin.write_all(input.as_bytes()).await?;
+        drop(stdin);
+        child.wait_with_output().await
+    })
+    .await
+    .map_err(|_| "timed out".to_string())?
+    .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let formatted = String::from_utf8(output.stdout).map_err(|e| e.to_string())?;
+    let formatted = formatted.trim_end();
+    if formatted.trim().is_empty() {
+        return Err("empty output".to_string());
+    }
+    Ok(formatted.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleanup_strips_fences_line_numbers_and_indent() {
+        let code = "```rust\n 9 |     fn main() {\n10 |         println!(\"hi\");   \n11 |\n12 |     }\n```";
+        assert_eq!(
+            cleanup_code(code),
+            "fn main() {\n    println!(\"hi\");\n\n}"
+        );
+
+        // Numbers that are part of the code stay.
+        let data = "1 2 3\n1 5 6";
+        assert_eq!(cleanup_code(data), data);
+    }
+
+    #[test]
+    fn parse_reply_normalizes_or_detects_language() {
+        let snippet =
+            parse_code_reply(r#"{"language": "TS", "code": "const a: number = 1;"}"#).unwrap();
+        assert_eq!(snippet.language.as_deref(), Some("typescript"));
+
+        let snippet = parse_code_reply(r#"{"code": "def main():\n    pass"}"#).unwrap();
+        assert_eq!(snippet.language.as_deref(), Some("python"));
+        assert_eq!(
+            snippet.to_markdown(),
+            "```python\ndef main():\n    pass\n```"
+        );
+        assert_eq!(snippet.file_name(), "snippet.py");
+
+        assert!(parse_code_reply(r#"{"code": "  \n"}"#).is_err());
+    }
+
+    #[test]
+    fn detect_language_spots_common_languages() {
+        assert_eq!(detect_language("use std::io;\nfn main() {}"), Some("rust"));
+        assert_eq!(
+            detect_language("package main\n\nfunc main() {}"),
+            Some("go")
+        );
+        assert_eq!(detect_language("{\"a\": [1, 2]}"), Some("json"));
+        assert_eq!(detect_language("#include <stdio.h>"), Some("c"));
+        assert_eq!(detect_language("select * from users"), Some("sql"));
+        assert_eq!(detect_language("hello world"), None);
+    }
+}
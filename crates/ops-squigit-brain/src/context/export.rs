@@ -0,0 +1,204 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export a stored chat as a raw LLM conversation payload.
+//!
+//! Produces the `contents` array the Gemini API expects, or the `messages`
+//! array of the OpenAI chat format, so a conversation can be replayed in
+//! AI Studio or any other client with the user's own key. The chat image is
+//! embedded inline (base64) in the first user turn because provider-hosted
+//! file URIs expire.
+
+use base64::Engine;
+use ops_chat_storage::ChatData;
+use serde_json::{json, Value};
+
+use crate::provider::gemini::attachments::mime_from_extension;
+
+/// Target format of [`export_chat_as_llm_json`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmExportSchema {
+    Gemini,
+    OpenAi,
+}
+
+impl LlmExportSchema {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gemini" => Ok(Self::Gemini),
+            "openai" => Ok(Self::OpenAi),
+            other => Err(format!("Unsupported export schema: {}", other)),
+        }
+    }
+}
+
+/// Inline image attached to the first user turn.
+pub struct ExportImage<'a> {
+    pub mime_type: &'a str,
+    pub bytes: &'a [u8],
+}
+
+/// Load a chat from the active profile and export it in `schema` format.
+pub fn export_chat_as_llm_json(chat_id: &str, schema: LlmExportSchema) -> Result<Value, String> {
+    let storage = crate::context::media::get_active_storage()?;
+    let chat = storage.load_chat(chat_id).map_err(|e| e.to_string())?;
+
+    let image_path = storage
+        .get_image_path(&chat.metadata.image_hash)
+        .ok()
+        .filter(|path| !path.is_empty());
+    let image_bytes = image_path
+        .as_deref()
+        .and_then(|path| std::fs::read(path).ok());
+    let mime_type = image_path
+        .as_deref()
+        .and_then(|path| std::path::Path::new(path).extension())
+        .and_then(|ext| ext.to_str())
+        .map(mime_from_extension)
+        .unwrap_or("image/png");
+
+    let image = image_bytes
+        .as_deref()
+        .map(|bytes| ExportImage { mime_type, bytes });
+    Ok(build_llm_export(&chat, image, schema))
+}
+
+/// Build the export payload from already-loaded chat data.
+pub fn build_llm_export(
+    chat: &ChatData,
+    image: Option<ExportImage<'_>>,
+    schema: LlmExportSchema,
+) -> Value {
+    let mut turns: Vec<(bool, &str)> = chat
+        .messages
+        .iter()
+        .filter(|message| !message.content.trim().is_empty())
+        .map(|message| (message.role == "user", message.content.as_str()))
+        .collect();
+
+    // The first analysis is often a model turn answering the bare image;
+    // replaying it needs a user turn to carry that image.
+    if image.is_some() && turns.first().is_none_or(|(is_user, _)| !is_user) {
+        turns.insert(0, (true, ""));
+    }
+
+    let encoded_image = image.map(|image| {
+        (
+            image.mime_type,
+            base64::engine::general_purpose::STANDARD.encode(image.bytes),
+        )
+    });
+
+    let mut image_pending = encoded_image.is_some();
+    let mut entries = Vec::with_capacity(turns.len());
+    for (is_user, text) in turns {
+        let inline = if is_user && image_pending {
+            image_pending = false;
+            encoded_image.as_ref()
+        } else {
+            None
+        };
+        entries.push(match schema {
+            LlmExportSchema::Gemini => gemini_turn(is_user, text, inline),
+            LlmExportSchema::OpenAi => openai_turn(is_user, text, inline),
+        });
+    }
+
+    Value::Array(entries)
+}
+
+fn gemini_turn(is_user: bool, text: &str, image: Option<&(&str, String)>) -> Value {
+    let mut parts = Vec::new();
+    if let Some((mime_type, data)) = image {
+        parts.push(json!({ "inlineData": { "mimeType": mime_type, "data": data } }));
+    }
+    if !text.is_empty() {
+        parts.push(json!({ "text": text }));
+    }
+    json!({
+        "role": if is_user { "user" } else { "model" },
+        "parts": parts,
+    })
+}
+
+fn openai_turn(is_user: bool, text: &str, image: Option<&(&str, String)>) -> Value {
+    let role = if is_user { "user" } else { "assistant" };
+    let Some((mime_type, data)) = image else {
+        return json!({ "role": role, "content": text });
+    };
+
+    let mut content = vec![json!({
+        "type": "image_url",
+        "image_url": { "url": format!("data:{};base64,{}", mime_type, data) },
+    })];
+    if !text.is_empty() {
+        content.push(json!({ "type": "text", "text": text }));
+    }
+    json!({ "role": role, "content": content })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_llm_export, ExportImage, LlmExportSchema};
+    use ops_chat_storage::{ChatData, ChatMessage, ChatMetadata};
+
+    fn chat_with(messages: &[(&str, &str)]) -> ChatData {
+        let mut chat = ChatData::new(ChatMetadata::new("t".to_string(), "h".to_string(), None));
+        chat.messages = messages
+            .iter()
+            .map(|(role, content)| match *role {
+                "user" => ChatMessage::user(content.to_string()),
+                _ => ChatMessage::assistant(content.to_string()),
+            })
+            .collect();
+        chat
+    }
+
+    #[test]
+    fn gemini_export_prepends_image_turn_before_model_analysis() {
+        let chat = chat_with(&[("assistant", "A chart."), ("user", "Why?")]);
+        let image = ExportImage {
+            mime_type: "image/png",
+            bytes: b"png",
+        };
+        let contents = build_llm_export(&chat, Some(image), LlmExportSchema::Gemini);
+
+        let turns = contents.as_array().unwrap();
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[0]["role"], "user");
+        assert_eq!(turns[0]["parts"][0]["inlineData"]["data"], "cG5n");
+        assert_eq!(turns[1]["role"], "model");
+        assert_eq!(turns[2]["parts"][0]["text"], "Why?");
+    }
+
+    #[test]
+    fn openai_export_uses_data_url_on_first_user_message() {
+        let chat = chat_with(&[("user", "What is this?"), ("assistant", "A cat.")]);
+        let image = ExportImage {
+            mime_type: "image/jpeg",
+            bytes: b"jpg",
+        };
+        let messages = build_llm_export(&chat, Some(image), LlmExportSchema::OpenAi);
+
+        let turns = messages.as_array().unwrap();
+        assert_eq!(turns.len(), 2);
+        assert_eq!(
+            turns[0]["content"][0]["image_url"]["url"],
+            "data:image/jpeg;base64,anBn"
+        );
+        assert_eq!(turns[0]["content"][1]["text"], "What is this?");
+        assert_eq!(
+            turns[1],
+            serde_json::json!({ "role": "assistant", "content": "A cat." })
+        );
+    }
+
+    #[test]
+    fn schema_parse_rejects_unknown_values() {
+        assert_eq!(
+            LlmExportSchema::parse("OpenAI"),
+            Ok(LlmExportSchema::OpenAi)
+        );
+        assert!(LlmExportSchema::parse("claude").is_err());
+    }
+}
@@ -0,0 +1,495 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured extraction profiles.
+//!
+//! A profile is a built-in schema (receipt, invoice, business card) that the
+//! model fills in JSON mode. Replies are checked against the profile's fields
+//! before they are stored on the chat, so every stored [`Extraction`] of a
+//! kind has the same shape and can be exported across chats as CSV.
+
+use chrono::NaiveDate;
+use ops_chat_storage::{ChatMetadata, Extraction};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+const MAX_TEXT_CHARS: usize = 500;
+const MAX_LIST_ITEMS: usize = 10;
+const MAX_LINE_ITEMS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionProfile {
+    Receipt,
+    Invoice,
+    BusinessCard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    Text,
+    /// A plain number; amounts carry no currency symbol.
+    Number,
+    /// `YYYY-MM-DD`.
+    Date,
+    TextList,
+    /// Objects with `description`, `quantity`, `unitPrice` and `amount`.
+    LineItems,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionField {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: FieldKind,
+    /// Replies without this field are rejected.
+    pub required: bool,
+}
+
+const fn field(name: &'static str, label: &'static str, kind: FieldKind) -> ExtractionField {
+    ExtractionField {
+        name,
+        label,
+        kind,
+        required: false,
+    }
+}
+
+const fn required(name: &'static str, label: &'static str, kind: FieldKind) -> ExtractionField {
+    ExtractionField {
+        name,
+        label,
+        kind,
+        required: true,
+    }
+}
+
+const RECEIPT_FIELDS: &[ExtractionField] = &[
+    required("merchant", "Merchant", FieldKind::Text),
+    field("date", "Date", FieldKind::Date),
+    field("currency", "Currency", FieldKind::Text),
+    field("items", "Items", FieldKind::LineItems),
+    field("subtotal", "Subtotal", FieldKind::Number),
+    field("tax", "Tax", FieldKind::Number),
+    field("tip", "Tip", FieldKind::Number),
+    required("total", "Total", FieldKind::Number),
+    field("paymentMethod", "Payment Method", FieldKind::Text),
+];
+
+const INVOICE_FIELDS: &[ExtractionField] = &[
+    required("invoiceNumber", "Invoice Number", FieldKind::Text),
+    required("vendor", "Vendor", FieldKind::Text),
+    field("customer", "Customer", FieldKind::Text),
+    field("issueDate", "Issue Date", FieldKind::Date),
+    field("dueDate", "Due Date", FieldKind::Date),
+    field("currency", "Currency", FieldKind::Text),
+    field("items", "Items", FieldKind::LineItems),
+    field("subtotal", "Subtotal", FieldKind::Number),
+    field("tax", "Tax", FieldKind::Number),
+    required("total", "Total", FieldKind::Number),
+];
+
+const BUSINESS_CARD_FIELDS: &[ExtractionField] = &[
+    required("name", "Name", FieldKind::Text),
+    field("jobTitle", "Job Title", FieldKind::Text),
+    field("company", "Company", FieldKind::Text),
+    field("emails", "Emails", FieldKind::TextList),
+    field("phones", "Phones", FieldKind::TextList),
+    field("website", "Website", FieldKind::Text),
+    field("address", "Address", FieldKind::Text),
+];
+
+/// A profile as listed in the UI.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionProfileInfo {
+    pub id: ExtractionProfile,
+    pub name: &'static str,
+    pub fields: &'static [ExtractionField],
+}
+
+impl ExtractionProfile {
+    pub const ALL: [ExtractionProfile; 3] = [Self::Receipt, Self::Invoice, Self::BusinessCard];
+
+    /// Stable ID, used as the stored [`Extraction::kind`].
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Receipt => "receipt",
+            Self::Invoice => "invoice",
+            Self::BusinessCard => "business_card",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.id() == id)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Receipt => "Receipt",
+            Self::Invoice => "Invoice",
+            Self::BusinessCard => "Business Card",
+        }
+    }
+
+    pub fn fields(self) -> &'static [ExtractionField] {
+        match self {
+            Self::Receipt => RECEIPT_FIELDS,
+            Self::Invoice => INVOICE_FIELDS,
+            Self::BusinessCard => BUSINESS_CARD_FIELDS,
+        }
+    }
+
+    pub fn info(self) -> ExtractionProfileInfo {
+        ExtractionProfileInfo {
+            id: self,
+            name: self.name(),
+            fields: self.fields(),
+        }
+    }
+
+    pub fn prompt(self) -> String {
+        let subject = match self {
+            Self::Receipt => "the receipt",
+            Self::Invoice => "the invoice",
+            Self::BusinessCard => "the contact details on the business card",
+        };
+        format!(
+            "Extract {} shown in this image. Copy names and text as written. Give amounts \
+             as plain numbers without currency symbols or thousands separators, the currency \
+             as an ISO 4217 code, and dates as YYYY-MM-DD. Leave out fields the image doesn't \
+             show.",
+            subject
+        )
+    }
+
+    /// Gemini `responseSchema` for the profile's fields.
+    pub fn response_schema(self) -> Value {
+        let mut properties = Map::new();
+        for field in self.fields() {
+            let schema = match field.kind {
+                FieldKind::Text => json!({ "type": "STRING" }),
+                FieldKind::Number => json!({ "type": "NUMBER" }),
+                FieldKind::Date => json!({ "type": "STRING", "description": "YYYY-MM-DD" }),
+                FieldKind::TextList => json!({ "type": "ARRAY", "items": { "type": "STRING" } }),
+                FieldKind::LineItems => json!({
+                    "type": "ARRAY",
+                    "items": {
+                        "type": "OBJECT",
+                        "properties": {
+                            "description": { "type": "STRING" },
+                            "quantity": { "type": "NUMBER" },
+                            "unitPrice": { "type": "NUMBER" },
+                            "amount": { "type": "NUMBER" }
+                        },
+                        "required": ["description"]
+                    }
+                }),
+            };
+            properties.insert(field.name.to_string(), schema);
+        }
+        let required: Vec<&str> = self
+            .fields()
+            .iter()
+            .filter(|field| field.required)
+            .map(|field| field.name)
+            .collect();
+        json!({
+            "type": "OBJECT",
+            "properties": properties,
+            "required": required,
+        })
+    }
+}
+
+/// Validate the model's JSON reply against `profile`. Unknown fields are
+/// dropped and unreadable values left out; a missing required field is an
+/// error.
+pub fn parse_extraction(profile: ExtractionProfile, reply: &str) -> Result<Value, String> {
+    let reply = reply.trim();
+    let reply = reply
+        .strip_prefix("```json")
+        .or_else(|| reply.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .unwrap_or(reply);
+    let raw: Value =
+        serde_json::from_str(reply).map_err(|e| format!("ERR_INVALID_EXTRACTION_REPLY: {}", e))?;
+    let Value::Object(raw) = raw else {
+        return Err("ERR_INVALID_EXTRACTION_REPLY: expected an object".to_string());
+    };
+
+    let mut data = Map::new();
+    for field in profile.fields() {
+        let value = raw
+            .get(field.name)
+            .and_then(|value| clean_value(field.kind, value));
+        match value {
+            Some(value) => {
+                data.insert(field.name.to_string(), value);
+            }
+            None if field.required => {
+                return Err(format!("ERR_EXTRACTION_MISSING_FIELD: {}", field.name));
+            }
+            None => {}
+        }
+    }
+    Ok(Value::Object(data))
+}
+
+fn clean_value(kind: FieldKind, value: &Value) -> Option<Value> {
+    match kind {
+        FieldKind::Text => clean_text(value).map(Value::String),
+        FieldKind::Number => clean_number(value).map(Value::from),
+        FieldKind::Date => value
+            .as_str()
+            .and_then(|text| NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok())
+            .map(|date| Value::String(date.format("%Y-%m-%d").to_string())),
+        FieldKind::TextList => {
+            let values = match value {
+                Value::Array(values) => values.as_slice(),
+                value => std::slice::from_ref(value),
+            };
+            let mut list: Vec<String> = Vec::new();
+            for text in values.iter().filter_map(clean_text) {
+                if !list.contains(&text) {
+                    list.push(text);
+                }
+            }
+            list.truncate(MAX_LIST_ITEMS);
+            (!list.is_empty()).then(|| json!(list))
+        }
+        FieldKind::LineItems => {
+            let items: Vec<Value> = value
+                .as_array()?
+                .iter()
+                .filter_map(|item| {
+                    let description = clean_text(item.get("description")?)?;
+                    let mut line = Map::new();
+                    line.insert("description".to_string(), Value::String(description));
+                    for key in ["quantity", "unitPrice", "amount"] {
+                        if let Some(number) = item.get(key).and_then(clean_number) {
+                            line.insert(key.to_string(), Value::from(number));
+                        }
+                    }
+                    Some(Value::Object(line))
+                })
+                .take(MAX_LINE_ITEMS)
+                .collect();
+            (!items.is_empty()).then_some(Value::Array(items))
+        }
+    }
+}
+
+fn clean_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    (!text.is_empty()).then(|| text.chars().take(MAX_TEXT_CHARS).collect())
+}
+
+/// A number, or a string holding one such as `"$1,234.50"` or `"12,99 €"`.
+fn clean_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => parse_amount(text),
+        _ => None,
+    }
+    .filter(|number| number.is_finite())
+}
+
+fn parse_amount(text: &str) -> Option<f64> {
+    let negative = text.trim_start().starts_with('-') || text.contains('(');
+    let digits: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | ','))
+        .collect();
+    if !digits.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // The last separator is the decimal point when two or fewer digits follow
+    // it; every other separator groups thousands.
+    let normalized = match digits.rfind(['.', ',']) {
+        Some(index) if digits.len() - index - 1 <= 2 => format!(
+            "{}.{}",
+            digits[..index].replace(['.', ','], ""),
+            &digits[index + 1..]
+        ),
+        _ => digits.replace(['.', ','], ""),
+    };
+    let amount: f64 = normalized.parse().ok()?;
+    Some(if negative { -amount } else { amount })
+}
+
+/// One row per extraction of `profile`'s kind, with the chat it came from.
+/// Rows of other kinds are skipped.
+pub fn extractions_to_csv(
+    profile: ExtractionProfile,
+    rows: &[(ChatMetadata, Extraction)],
+) -> String {
+    let mut header = vec!["Chat", "Chat ID", "Extracted At"];
+    header.extend(profile.fields().iter().map(|field| field.label));
+    let mut lines = vec![header
+        .into_iter()
+        .map(csv_cell)
+        .collect::<Vec<_>>()
+        .join(",")];
+
+    for (metadata, extraction) in rows {
+        if extraction.kind != profile.id() {
+            continue;
+        }
+        let mut cells = vec![
+            csv_cell(&metadata.title),
+            csv_cell(&metadata.id),
+            csv_cell(&extraction.created_at.to_rfc3339()),
+        ];
+        for field in profile.fields() {
+            let value = extraction.data.get(field.name).unwrap_or(&Value::Null);
+            cells.push(csv_cell(&field_text(field.kind, value)));
+        }
+        lines.push(cells.join(","));
+    }
+
+    lines.join("\r\n") + "\r\n"
+}
+
+fn field_text(kind: FieldKind, value: &Value) -> String {
+    match (kind, value) {
+        (_, Value::Null) => String::new(),
+        (FieldKind::TextList, Value::Array(values)) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("; "),
+        (FieldKind::LineItems, Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| {
+                let description = item.get("description")?.as_str()?;
+                let quantity = item
+                    .get("quantity")
+                    .and_then(Value::as_f64)
+                    .map(|quantity| format!("{} x ", quantity))
+                    .unwrap_or_default();
+                let amount = item
+                    .get("amount")
+                    .and_then(Value::as_f64)
+                    .map(|amount| format!(" = {}", amount))
+                    .unwrap_or_default();
+                Some(format!("{}{}{}", quantity, description, amount))
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        (_, Value::String(text)) => text.clone(),
+        (_, value) => value.to_string(),
+    }
+}
+
+/// Quote a CSV cell when needed. Cells that spreadsheets would read as a
+/// formula get a leading `'`, since the text comes from untrusted images.
+fn csv_cell(text: &str) -> String {
+    let text = if text.starts_with(['=', '+', '@', '\t', '\r'])
+        || (text.starts_with('-') && text.parse::<f64>().is_err())
+    {
+        format!("'{}", text)
+    } else {
+        text.to_string()
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extraction_normalizes_fields() {
+        let reply = r#"```json
+        {
+            "merchant": "  Corner Café ",
+            "date": "2026-03-05",
+            "items": [
+                {"description": "Latte", "quantity": 2, "amount": "€7,00"},
+                {"description": " ", "amount": 1}
+            ],
+            "total": "$1,234.50",
+            "tax": "n/a",
+            "extra": "ignored"
+        }
+        ```"#;
+        let data = parse_extraction(ExtractionProfile::Receipt, reply).unwrap();
+        assert_eq!(
+            data,
+            json!({
+                "merchant": "Corner Café",
+                "date": "2026-03-05",
+                "items": [{"description": "Latte", "quantity": 2.0, "amount": 7.0}],
+                "total": 1234.5
+            })
+        );
+
+        let card = parse_extraction(
+            ExtractionProfile::BusinessCard,
+            r#"{"name": "Ada", "emails": "ada@example.com", "phones": ["1", "1", ""]}"#,
+        )
+        .unwrap();
+        assert_eq!(card["emails"], json!(["ada@example.com"]));
+        assert_eq!(card["phones"], json!(["1"]));
+
+        assert_eq!(
+            parse_extraction(
+                ExtractionProfile::Invoice,
+                r#"{"vendor": "Acme", "total": 3}"#
+            ),
+            Err("ERR_EXTRACTION_MISSING_FIELD: invoiceNumber".to_string())
+        );
+        assert!(parse_extraction(ExtractionProfile::Receipt, "[]").is_err());
+    }
+
+    #[test]
+    fn parse_amount_reads_both_decimal_styles() {
+        assert_eq!(parse_amount("1.234,56 €"), Some(1234.56));
+        assert_eq!(parse_amount("1,234"), Some(1234.0));
+        assert_eq!(parse_amount("-12.5"), Some(-12.5));
+        assert_eq!(parse_amount("(3.00)"), Some(-3.0));
+        assert_eq!(parse_amount("free"), None);
+    }
+
+    #[test]
+    fn csv_escapes_cells_and_skips_other_kinds() {
+        let metadata = ChatMetadata::new("Lunch, Friday".to_string(), "0".repeat(64), None);
+        let extraction = |kind: &str, data: Value| Extraction {
+            kind: kind.to_string(),
+            data,
+            model: None,
+            created_at: chrono::Utc::now(),
+        };
+        let rows = vec![
+            (
+                metadata.clone(),
+                extraction(
+                    "receipt",
+                    json!({
+                        "merchant": "=HYPERLINK(\"x\")",
+                        "items": [{"description": "Soup", "quantity": 1.0, "amount": 4.5}],
+                        "total": 4.5
+                    }),
+                ),
+            ),
+            (metadata, extraction("invoice", json!({ "total": 1 }))),
+        ];
+        let csv = extractions_to_csv(ExtractionProfile::Receipt, &rows);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Chat,Chat ID,Extracted At,Merchant,Date,"));
+        assert!(lines[1].starts_with("\"Lunch, Friday\","));
+        assert!(lines[1].contains(",\"'=HYPERLINK(\"\"x\"\")\",,,1 x Soup = 4.5,,,,4.5,"));
+    }
+}
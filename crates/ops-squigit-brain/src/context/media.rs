@@ -8,12 +8,14 @@ use std::path::Path;
 
 pub fn get_active_storage() -> Result<ChatStorage, String> {
     let profile_store = ProfileStore::new().map_err(|e| e.to_string())?;
-    let active_id = profile_store
-        .get_active_profile_id()
+    let profile = profile_store
+        .get_active_profile()
         .map_err(|e| e.to_string())?
         .ok_or_else(|| "No active profile. Please log in first.".to_string())?;
-    let chats_dir = profile_store.get_chats_dir(&active_id);
-    ChatStorage::with_base_dir(chats_dir).map_err(|e| e.to_string())
+    profile_store
+        .open_chat_storage(&profile.id)
+        .map(|storage| storage.with_metadata_stripping(profile.strip_image_metadata))
+        .map_err(|e| e.to_string())
 }
 
 pub fn process_bytes_internal(
@@ -25,9 +27,15 @@ pub fn process_bytes_internal(
     }
 
     let storage = get_active_storage()?;
-    let stored = storage
+    let mut stored = storage
         .store_image(&buffer, explicit_tone)
         .map_err(|e| e.to_string())?;
+    // Callers display and OCR the returned path, so hand out plaintext.
+    stored.path = storage
+        .readable_path(Path::new(&stored.path))
+        .map_err(|e| e.to_string())?
+        .to_string_lossy()
+        .to_string();
 
     Ok(stored)
 }
@@ -76,6 +84,7 @@ pub async fn upload_image_to_imgbb(image_path: &str, api_key: &str) -> Result<St
     if bytes.is_empty() {
         return Err("Image file is empty".to_string());
     }
+    let bytes = ops_chat_storage::without_image_metadata(bytes);
 
     let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("image");
     let mime = mime_guess::from_path(path).first_or_octet_stream();
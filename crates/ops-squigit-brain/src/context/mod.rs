@@ -2,6 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod builder;
+pub mod calendar;
+pub mod code;
 pub mod compactor;
+pub mod export;
+pub mod extraction;
 pub mod loader;
 pub mod media;
+pub mod titles;
@@ -0,0 +1,236 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Title backfill — names old chats that never received a generated title.
+//!
+//! Chats keep their placeholder name when title generation failed or the app
+//! closed mid-stream. The backfill walks the index, generates a title and a
+//! one-line summary for each placeholder chat, and paces requests so a large
+//! history does not trip the API rate limit.
+
+use crate::audit::audited;
+use ops_chat_storage::ChatData;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Titles assigned before (or instead of) a generated one.
+const PLACEHOLDER_TITLES: &[&str] = &["New Chat", "New thread", "Untitled"];
+
+/// Pause between API calls; keeps the backfill under free-tier rate limits.
+pub const BACKFILL_REQUEST_DELAY: Duration = Duration::from_secs(4);
+
+/// Characters of each message kept in the title/summary context.
+const CONTEXT_CHARS_PER_MESSAGE: usize = 400;
+/// Messages considered when building the context.
+const CONTEXT_MESSAGES: usize = 4;
+
+/// Progress for one processed chat.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitleBackfillProgress {
+    pub chat_id: String,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub error: Option<String>,
+    pub completed: usize,
+    pub total: usize,
+}
+
+pub fn is_placeholder_title(title: &str) -> bool {
+    let title = title.trim();
+    title.is_empty()
+        || PLACEHOLDER_TITLES
+            .iter()
+            .any(|placeholder| placeholder.eq_ignore_ascii_case(title))
+}
+
+/// Text context for title and summary generation: the image brief followed
+/// by the first few messages, each clipped. `None` when there is nothing to
+/// name the chat after.
+pub fn build_title_context(chat: &ChatData) -> Option<String> {
+    let mut lines = Vec::new();
+    if let Some(brief) = chat
+        .image_brief
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+    {
+        lines.push(format!("Image: {}", brief.replace('\n', " ")));
+    }
+    for message in chat
+        .messages
+        .iter()
+        .filter(|message| !message.content.trim().is_empty())
+        .take(CONTEXT_MESSAGES)
+    {
+        let content: String = message
+            .content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(CONTEXT_CHARS_PER_MESSAGE)
+            .collect();
+        lines.push(format!("{}: {}", message.role, content));
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Generate titles and summaries for every chat still carrying a placeholder
+/// title, waiting `delay` between API calls. Chats without any text context
+/// are skipped. Stops early when `stop` is set. Returns the number of chats
+/// that were renamed.
+pub async fn backfill_chat_titles(
+    api_key: &str,
+    model: &str,
+    delay: Duration,
+    stop: &AtomicBool,
+    on_progress: &(dyn Fn(&TitleBackfillProgress) + Send + Sync),
+) -> Result<usize, String> {
+    use crate::provider::gemini::commands::generation::{
+        generate_chat_summary, generate_chat_title,
+    };
+
+    let storage = crate::context::media::get_active_storage()?;
+    let pending: Vec<String> = storage
+        .list_chats()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|meta| is_placeholder_title(&meta.title))
+        .map(|meta| meta.id)
+        .collect();
+
+    let total = pending.len();
+    let mut renamed = 0usize;
+    for (index, chat_id) in pending.into_iter().enumerate() {
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let mut progress = TitleBackfillProgress {
+            chat_id: chat_id.clone(),
+            title: None,
+            summary: None,
+            error: None,
+            completed: index + 1,
+            total,
+        };
+
+        let chat = match storage.load_chat(&chat_id) {
+            Ok(chat) => chat,
+            Err(e) => {
+                progress.error = Some(e.to_string());
+                on_progress(&progress);
+                continue;
+            }
+        };
+        let Some(context) = build_title_context(&chat) else {
+            on_progress(&progress);
+            continue;
+        };
+
+        let call = generate_chat_title(api_key.to_string(), model.to_string(), context.clone());
+        let title = audited("chat_title", model, &context, call)
+            .await
+            .map(|title| title.trim().trim_matches('"').to_string());
+        tokio::time::sleep(delay).await;
+        let title = match title {
+            Ok(title) if !is_placeholder_title(&title) => title,
+            Ok(_) => {
+                on_progress(&progress);
+                continue;
+            }
+            Err(e) => {
+                progress.error = Some(e);
+                on_progress(&progress);
+                continue;
+            }
+        };
+
+        // A missing summary should not block the rename.
+        let summary = if stop.load(Ordering::SeqCst) {
+            None
+        } else {
+            let call =
+                generate_chat_summary(api_key.to_string(), model.to_string(), context.clone());
+            let summary = audited("chat_summary", model, &context, call).await;
+            tokio::time::sleep(delay).await;
+            summary.ok().filter(|summary| !summary.is_empty())
+        };
+
+        // The chat was loaded before the calls above, so only the title and
+        // summary are written, and not over a title the user set since.
+        match storage.load_chat(&chat_id) {
+            Ok(current) if !is_placeholder_title(&current.metadata.title) => {
+                on_progress(&progress);
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                progress.error = Some(e.to_string());
+                on_progress(&progress);
+                continue;
+            }
+        }
+        let result = storage.modify_metadata(&chat_id, |metadata| {
+            metadata.title = title.clone();
+            if summary.is_some() {
+                metadata.summary = summary.clone();
+            }
+            Ok(())
+        });
+        match result {
+            Ok(_) => {
+                renamed += 1;
+                progress.title = Some(title);
+                progress.summary = summary;
+            }
+            Err(e) => progress.error = Some(e.to_string()),
+        }
+        on_progress(&progress);
+    }
+
+    Ok(renamed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_title_context, is_placeholder_title};
+    use ops_chat_storage::{ChatData, ChatMessage, ChatMetadata};
+
+    #[test]
+    fn placeholder_titles_match_case_insensitively() {
+        assert!(is_placeholder_title("New Chat"));
+        assert!(is_placeholder_title(" new thread "));
+        assert!(is_placeholder_title(""));
+        assert!(!is_placeholder_title("Rust borrow checker error"));
+    }
+
+    #[test]
+    fn title_context_uses_brief_and_clipped_messages() {
+        let mut chat = ChatData::new(ChatMetadata::new(
+            "New thread".to_string(),
+            "h".to_string(),
+            None,
+        ));
+        assert!(build_title_context(&chat).is_none());
+
+        chat.image_brief = Some("A terminal\nwith an error".to_string());
+        chat.messages = vec![
+            ChatMessage::user("why   does\nthis fail?".to_string()),
+            ChatMessage::assistant("x".repeat(1000)),
+        ];
+        let context = build_title_context(&chat).unwrap();
+        let lines: Vec<&str> = context.lines().collect();
+
+        assert_eq!(lines[0], "Image: A terminal with an error");
+        assert_eq!(lines[1], "user: why does this fail?");
+        assert_eq!(lines[2].len(), "assistant: ".len() + 400);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::provider::gemini::transport::types::GeminiEvent;
+use std::sync::Mutex;
 
 pub trait BrainEventSink: Send + Sync {
     fn emit(&self, channel_id: &str, event: GeminiEvent);
@@ -12,3 +13,42 @@ pub struct NoopEventSink;
 impl BrainEventSink for NoopEventSink {
     fn emit(&self, _channel_id: &str, _event: GeminiEvent) {}
 }
+
+/// Accumulates the reply text while forwarding every event to `delegate`.
+pub struct CollectingEventSink<'a> {
+    delegate: Option<&'a dyn BrainEventSink>,
+    text: Mutex<String>,
+}
+
+impl<'a> CollectingEventSink<'a> {
+    pub fn new(delegate: Option<&'a dyn BrainEventSink>) -> Self {
+        Self {
+            delegate,
+            text: Mutex::new(String::new()),
+        }
+    }
+
+    /// The reply so far; restarts when the stream is reset.
+    pub fn current_text(&self) -> String {
+        self.text
+            .lock()
+            .map(|value| value.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl BrainEventSink for CollectingEventSink<'_> {
+    fn emit(&self, channel_id: &str, event: GeminiEvent) {
+        if let Ok(mut text) = self.text.lock() {
+            match &event {
+                GeminiEvent::Token { token } => text.push_str(token),
+                GeminiEvent::Reset => text.clear(),
+                _ => {}
+            }
+        }
+
+        if let Some(delegate) = self.delegate {
+            delegate.emit(channel_id, event);
+        }
+    }
+}
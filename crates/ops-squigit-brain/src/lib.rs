@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod assets;
+mod audit;
 pub mod constants;
 pub mod context;
 pub mod events;
@@ -12,6 +13,6 @@ pub mod system;
 pub mod tools;
 
 pub use service::{
-    AnalyzeImageRequest, BrainService, CompressConversationRequest, GenerateChatTitleRequest,
-    GenerateImageBriefRequest, PromptChatRequest, StreamChatRequest,
+    AnalyzeImageRequest, BackfillChatTitlesRequest, BrainService, CompressConversationRequest,
+    GenerateChatTitleRequest, GenerateImageBriefRequest, PromptChatRequest, StreamChatRequest,
 };
@@ -0,0 +1,244 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streaming chat over `/messages`, with the image sent as a base64
+//! source block.
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use super::AnthropicConfig;
+use crate::events::BrainEventSink;
+use crate::provider::gemini::agent::request_control::{
+    register_request, remove_request, GeminiRequestControl,
+};
+use crate::provider::gemini::transport::types::GeminiEvent;
+use crate::provider::turn::{system_instruction, user_parts, UserPart};
+use crate::runtime::BrainRuntimeState;
+use crate::service::StreamChatRequest;
+
+const MAX_OUTPUT_TOKENS: usize = 2048;
+
+#[derive(Debug, Serialize)]
+struct MessagesRequest {
+    model: String,
+    system: String,
+    messages: Vec<Message>,
+    max_tokens: usize,
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    role: &'static str,
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlock {
+    Text { text: String },
+    Image { source: ImageSource },
+}
+
+#[derive(Debug, Serialize)]
+struct ImageSource {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    media_type: String,
+    data: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    ContentBlockDelta {
+        delta: Delta,
+    },
+    MessageStop,
+    Error {
+        error: ApiError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Delta {
+    TextDelta {
+        text: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+/// One line of the server-sent event stream. `event:` lines are skipped:
+/// each `data:` payload carries its own type.
+#[derive(Debug, PartialEq)]
+enum StreamLine {
+    Token(String),
+    Done,
+    Error(String),
+    Skip,
+}
+
+/// Stream a reply from `request.model` as `Token` events on the request's
+/// channel. `response_language` is the validated tag.
+pub async fn stream_claude_chat(
+    runtime: &BrainRuntimeState,
+    sink: &dyn BrainEventSink,
+    config: &AnthropicConfig,
+    request: &StreamChatRequest,
+    response_language: Option<&str>,
+) -> Result<(), String> {
+    let request_control = GeminiRequestControl::new();
+    register_request(runtime, request.channel_id.clone(), request_control.clone()).await;
+
+    let result = async {
+        let body = MessagesRequest {
+            model: request.model.clone(),
+            system: system_instruction(request, response_language)?,
+            messages: vec![Message {
+                role: "user",
+                content: user_parts(request)?
+                    .into_iter()
+                    .map(content_block)
+                    .collect(),
+            }],
+            max_tokens: MAX_OUTPUT_TOKENS,
+            stream: true,
+        };
+
+        let cancel_token = &request_control.cancel_token;
+        let send = config
+            .authorize(reqwest::Client::new().post(config.endpoint("messages")))
+            .json(&body)
+            .send();
+        let response = tokio::select! {
+            res = send => res.map_err(|e| format!("Failed to reach Anthropic: {}", e))?,
+            _ = cancel_token.cancelled() => return Err("CANCELLED".to_string()),
+        };
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("Anthropic API Error: {}", error_text));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        loop {
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = cancel_token.cancelled() => return Err("CANCELLED".to_string()),
+            };
+            match chunk {
+                Some(Ok(chunk)) => buffer.push_str(&String::from_utf8_lossy(&chunk)),
+                Some(Err(e)) => return Err(format!("Stream error: {}", e)),
+                None => return Ok(()),
+            }
+            while let Some(idx) = buffer.find('\n') {
+                let line: String = buffer.drain(..idx + 1).collect();
+                match parse_stream_line(&line) {
+                    StreamLine::Token(token) => {
+                        sink.emit(&request.channel_id, GeminiEvent::Token { token })
+                    }
+                    StreamLine::Done => return Ok(()),
+                    StreamLine::Error(message) => {
+                        return Err(format!("Anthropic API Error: {}", message))
+                    }
+                    StreamLine::Skip => {}
+                }
+            }
+        }
+    }
+    .await;
+
+    remove_request(runtime, &request.channel_id).await;
+    result
+}
+
+fn content_block(part: UserPart) -> ContentBlock {
+    match part {
+        UserPart::Image(image) => ContentBlock::Image {
+            source: ImageSource {
+                kind: "base64",
+                media_type: image.mime_type,
+                data: image.data,
+            },
+        },
+        UserPart::Text(text) => ContentBlock::Text { text },
+    }
+}
+
+fn parse_stream_line(line: &str) -> StreamLine {
+    let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+        return StreamLine::Skip;
+    };
+    match serde_json::from_str::<StreamEvent>(data) {
+        Ok(StreamEvent::ContentBlockDelta {
+            delta: Delta::TextDelta { text },
+        }) if !text.is_empty() => StreamLine::Token(text),
+        Ok(StreamEvent::MessageStop) => StreamLine::Done,
+        Ok(StreamEvent::Error { error }) => StreamLine::Error(error.message),
+        _ => StreamLine::Skip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_lines_yield_tokens_until_stop() {
+        assert_eq!(
+            parse_stream_line("event: content_block_delta"),
+            StreamLine::Skip
+        );
+        assert_eq!(
+            parse_stream_line(
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hel"}}"#
+            ),
+            StreamLine::Token("Hel".to_string())
+        );
+        assert_eq!(
+            parse_stream_line(r#"data: {"type":"ping"}"#),
+            StreamLine::Skip
+        );
+        assert_eq!(
+            parse_stream_line(
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{"}}"#
+            ),
+            StreamLine::Skip
+        );
+        assert_eq!(
+            parse_stream_line(
+                r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#
+            ),
+            StreamLine::Error("Overloaded".to_string())
+        );
+        assert_eq!(
+            parse_stream_line(r#"data: {"type":"message_stop"}"#),
+            StreamLine::Done
+        );
+    }
+
+    #[test]
+    fn images_are_base64_sources() {
+        let block = content_block(UserPart::Image(crate::provider::turn::InlineImage {
+            mime_type: "image/png".to_string(),
+            data: "AA==".to_string(),
+        }));
+        assert_eq!(
+            serde_json::to_value(&block).unwrap(),
+            serde_json::json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": "image/png", "data": "AA=="},
+            })
+        );
+    }
+}
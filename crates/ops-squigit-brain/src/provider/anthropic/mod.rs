@@ -0,0 +1,38 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Claude, via the Anthropic messages API.
+
+pub mod chat;
+pub mod models;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.anthropic.com/v1";
+
+/// Sent as `anthropic-version` on every request.
+const API_VERSION: &str = "2023-06-01";
+
+#[derive(Debug, Clone)]
+pub struct AnthropicConfig {
+    /// API root including the version.
+    pub base_url: String,
+    pub api_key: String,
+}
+
+impl AnthropicConfig {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key,
+        }
+    }
+
+    pub(crate) fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim().trim_end_matches('/'), path)
+    }
+
+    pub(crate) fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        request
+            .header("x-api-key", self.api_key.trim())
+            .header("anthropic-version", API_VERSION)
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Claude models available to a key, for the model picker.
+
+use serde::Deserialize;
+
+use super::AnthropicConfig;
+use crate::provider::gemini::commands::models::ModelInfo;
+
+/// Largest page `/models` serves.
+const PAGE_LIMIT: usize = 1000;
+
+#[derive(Debug, Default, Deserialize)]
+struct ModelListPage {
+    #[serde(default)]
+    data: Vec<RemoteModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteModel {
+    id: String,
+    display_name: Option<String>,
+}
+
+/// Query `/models`, newest first. Every Claude model takes images.
+pub async fn list_claude_models(config: &AnthropicConfig) -> Result<Vec<ModelInfo>, String> {
+    let response = config
+        .authorize(reqwest::Client::new().get(config.endpoint("models")))
+        .query(&[("limit", PAGE_LIMIT)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Anthropic: {}", e))?;
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Anthropic API Error: {}", error_text));
+    }
+    let page: ModelListPage = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse model list: {}", e))?;
+    Ok(to_model_infos(page))
+}
+
+fn to_model_infos(page: ModelListPage) -> Vec<ModelInfo> {
+    page.data
+        .into_iter()
+        .filter(|model| !model.id.trim().is_empty())
+        .map(|model| ModelInfo {
+            display_name: model
+                .display_name
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| model.id.clone()),
+            id: model.id,
+            description: None,
+            input_token_limit: None,
+            output_token_limit: None,
+        })
+        .collect()
+}
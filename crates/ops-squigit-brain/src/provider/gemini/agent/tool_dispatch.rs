@@ -26,9 +26,7 @@ pub(crate) struct ToolDispatchContext<'a> {
     pub(crate) model: &'a str,
     pub(crate) chat_id: Option<&'a str>,
     pub(crate) gemini_file_cache: &'a std::sync::Arc<
-        tokio::sync::Mutex<
-            HashMap<String, crate::provider::gemini::attachments::GeminiFileRef>,
-        >,
+        tokio::sync::Mutex<crate::provider::gemini::attachments::ProviderFileCache>,
     >,
     pub(crate) request_control: &'a GeminiRequestControl,
     pub(crate) web_state: &'a mut WebToolDispatchState,
@@ -549,7 +547,9 @@ mod tests {
         let client = reqwest::Client::new();
         let request_control = GeminiRequestControl::new();
         let mut web_state = WebToolDispatchState::default();
-        let gemini_file_cache = std::sync::Arc::new(tokio::sync::Mutex::new(HashMap::new()));
+        let gemini_file_cache = std::sync::Arc::new(tokio::sync::Mutex::new(
+            crate::provider::gemini::attachments::ProviderFileCache::default(),
+        ));
         let mut context = ToolDispatchContext {
             client: &client,
             api_key: "k",
@@ -584,7 +584,9 @@ mod tests {
         let client = reqwest::Client::new();
         let request_control = GeminiRequestControl::new();
         let mut web_state = WebToolDispatchState::default();
-        let gemini_file_cache = std::sync::Arc::new(tokio::sync::Mutex::new(HashMap::new()));
+        let gemini_file_cache = std::sync::Arc::new(tokio::sync::Mutex::new(
+            crate::provider::gemini::attachments::ProviderFileCache::default(),
+        ));
         let mut context = ToolDispatchContext {
             client: &client,
             api_key: "k",
@@ -619,7 +621,9 @@ mod tests {
         let client = reqwest::Client::new();
         let request_control = GeminiRequestControl::new();
         let mut web_state = WebToolDispatchState::default();
-        let gemini_file_cache = std::sync::Arc::new(tokio::sync::Mutex::new(HashMap::new()));
+        let gemini_file_cache = std::sync::Arc::new(tokio::sync::Mutex::new(
+            crate::provider::gemini::attachments::ProviderFileCache::default(),
+        ));
         let mut context = ToolDispatchContext {
             client: &client,
             api_key: "k",
@@ -110,6 +110,8 @@ pub(crate) fn build_system_instruction_with_tool_policy(
     user_name: &str,
     user_email: &str,
     image_brief: &str,
+    response_language: Option<&str>,
+    glossary: &[ops_profile_store::GlossaryEntry],
     tools_enabled: bool,
 ) -> Result<String, String> {
     let mut instruction = crate::context::builder::build_system_instruction(
@@ -118,6 +120,14 @@ pub(crate) fn build_system_instruction_with_tool_policy(
         image_brief,
     )?;
 
+    if let Some(tag) = response_language {
+        instruction.push_str(&crate::context::builder::build_response_language_note(tag)?);
+    }
+
+    if let Some(note) = crate::context::builder::build_glossary_note(glossary) {
+        instruction.push_str(&note);
+    }
+
     if tools_enabled {
         instruction.push_str(
             "\n\n## Tool Usage Policy\n\
@@ -351,7 +361,7 @@ mod tests {
     #[test]
     fn tool_policy_mentions_rereading_prior_local_attachments() {
         let instruction =
-            build_system_instruction_with_tool_policy("", "", "", true).expect("policy");
+            build_system_instruction_with_tool_policy("", "", "", None, &[], true).expect("policy");
         assert!(instruction.contains("prior local code/text attachment"));
         assert!(instruction.contains("`read_local_attachment_context` again"));
         assert!(instruction.contains("`text_local`"));
@@ -4,16 +4,104 @@
 use std::collections::HashMap;
 use tokio::sync::Mutex;
 
-use super::{mime_from_extension, upload_file_to_gemini, GeminiFileRef};
+use super::{local_file_ref, mime_from_extension, upload_file_to_gemini, GeminiFileRef};
+use crate::provider::gemini::client::{endpoint, local_file_path};
+
+/// Uploaded file handles kept before the least recently used are dropped.
+pub const PROVIDER_FILE_CACHE_CAPACITY: usize = 256;
 
 fn is_uri_expired(file_ref: &GeminiFileRef) -> bool {
     chrono::Utc::now() >= file_ref.expires_at
 }
 
+/// Provider file handles by cache key, bounded to a number of entries with
+/// least-recently-used eviction. Expired handles are dropped first.
+#[derive(Debug)]
+pub struct ProviderFileCache {
+    capacity: usize,
+    entries: HashMap<String, (GeminiFileRef, u64)>,
+    /// Use counter; an entry's stamp is its last use.
+    clock: u64,
+    /// Hand out local file references instead of uploading.
+    local_only: bool,
+}
+
+impl ProviderFileCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            clock: 0,
+            local_only: false,
+        }
+    }
+
+    /// A cache for dry runs: [`ensure_file_uploaded`] refers to files by
+    /// their local `file://` URI and never calls the Files API.
+    pub fn local_only() -> Self {
+        Self {
+            local_only: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn get(&mut self, key: &str) -> Option<GeminiFileRef> {
+        self.clock += 1;
+        let (file_ref, used) = self.entries.get_mut(key)?;
+        *used = self.clock;
+        Some(file_ref.clone())
+    }
+
+    pub fn insert(&mut self, key: String, file_ref: GeminiFileRef) {
+        self.clock += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.entries
+                .retain(|_, (file_ref, _)| !is_uri_expired(file_ref));
+        }
+        while !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(key, (file_ref, self.clock));
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<GeminiFileRef> {
+        self.entries.remove(key).map(|(file_ref, _)| file_ref)
+    }
+
+    /// Drop every entry and return how many there were.
+    pub fn clear(&mut self) -> usize {
+        let count = self.entries.len();
+        self.entries.clear();
+        count
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for ProviderFileCache {
+    fn default() -> Self {
+        Self::with_capacity(PROVIDER_FILE_CACHE_CAPACITY)
+    }
+}
+
 pub async fn ensure_file_uploaded(
     api_key: &str,
     cas_path: &str,
-    cache: &Mutex<HashMap<String, GeminiFileRef>>,
+    cache: &Mutex<ProviderFileCache>,
 ) -> Result<GeminiFileRef, String> {
     let resolved_path =
         crate::provider::gemini::attachments::paths::resolve_attachment_path_internal(
@@ -27,24 +115,28 @@ pub async fn ensure_file_uploaded(
         .to_string();
 
     let cache_key = format!("{}_{}", cas_hash, &api_key[api_key.len().saturating_sub(6)..]);
-
-    {
-        let cache_lock = cache.lock().await;
-        if let Some(file_ref) = cache_lock.get(&cache_key) {
-            if !is_uri_expired(file_ref) {
-                return Ok(file_ref.clone());
-            }
-        }
-    }
-
     let ext = resolved_path
         .extension()
         .and_then(|s| s.to_str())
         .unwrap_or("");
     let mime_type = mime_from_extension(ext);
     let display_name = format!("{}.{}", cas_hash.chars().take(8).collect::<String>(), ext);
-
     let resolved_str = resolved_path.to_string_lossy().to_string();
+
+    if cache.lock().await.local_only {
+        return local_file_ref(&resolved_str, mime_type, &display_name).await;
+    }
+
+    {
+        let mut cache_lock = cache.lock().await;
+        if let Some(file_ref) = cache_lock.get(&cache_key) {
+            let local = local_file_path(&file_ref.file_uri).is_some();
+            if !is_uri_expired(&file_ref) && local != endpoint().uses_files_api() {
+                return Ok(file_ref);
+            }
+        }
+    }
+
     let new_ref = upload_file_to_gemini(api_key, &resolved_str, mime_type, &display_name).await?;
 
     let mut cache_lock = cache.lock().await;
@@ -52,3 +144,44 @@ pub async fn ensure_file_uploaded(
 
     Ok(new_ref)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_ref(name: &str) -> GeminiFileRef {
+        let now = chrono::Utc::now();
+        GeminiFileRef {
+            file_uri: format!("https://example.invalid/{}", name),
+            file_name: name.to_string(),
+            mime_type: "image/png".to_string(),
+            display_name: name.to_string(),
+            uploaded_at: now,
+            expires_at: now + chrono::Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = ProviderFileCache::with_capacity(2);
+        cache.insert("a".to_string(), file_ref("a"));
+        cache.insert("b".to_string(), file_ref("b"));
+        assert!(cache.get("a").is_some());
+        cache.insert("c".to_string(), file_ref("c"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn clear_reports_dropped_entries() {
+        let mut cache = ProviderFileCache::default();
+        cache.insert("a".to_string(), file_ref("a"));
+        cache.insert("b".to_string(), file_ref("b"));
+
+        assert_eq!(cache.clear(), 2);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Frame sampling for animated GIF/APNG attachments.
+//!
+//! Gemini only looks at the first frame of an animated image. Instead of
+//! the animation, evenly spaced frames are stored in CAS as PNGs and sent
+//! as separate image parts, after a note giving each frame's timestamp.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use futures_util::future::join_all;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::{AnimationDecoder, Frame, ImageFormat};
+use tokio::sync::Mutex;
+
+use super::{ensure_file_uploaded, is_image_path, ProviderFileCache};
+use crate::provider::gemini::transport::types::{GeminiFileData, GeminiPart};
+
+/// Frames sampled from an animation when the request does not say.
+pub const DEFAULT_ANIMATION_FRAMES: usize = 6;
+/// Upper bound on sampled frames, to keep uploads and prompt size in check.
+pub const MAX_ANIMATION_FRAMES: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SampledFrame {
+    /// Start of the frame from the beginning of the animation.
+    timestamp_ms: u64,
+    /// CAS path of the frame as a PNG.
+    path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SampledAnimation {
+    total_frames: usize,
+    duration_ms: u64,
+    frames: Vec<SampledFrame>,
+}
+
+/// Image parts for an animated attachment: a note, then `frames` evenly
+/// spaced frames. `None` when the file is not an animation or sampling is
+/// off (`frames` below 2), so the caller sends the file as is.
+pub(crate) async fn animated_image_parts(
+    api_key: &str,
+    path: &str,
+    display_name: Option<&str>,
+    frames: usize,
+    cache: &Mutex<ProviderFileCache>,
+) -> Result<Option<Vec<GeminiPart>>, String> {
+    if frames < 2 || !is_image_path(path) {
+        return Ok(None);
+    }
+    let resolved = super::paths::resolve_attachment_path_internal(path)?;
+    let count = frames.min(MAX_ANIMATION_FRAMES);
+    let Some(animation) =
+        tokio::task::spawn_blocking(move || sample_animation_frames(&resolved, count))
+            .await
+            .map_err(|e| format!("Frame sampling task failed: {}", e))??
+    else {
+        return Ok(None);
+    };
+
+    let uploads = join_all(
+        animation
+            .frames
+            .iter()
+            .map(|frame| ensure_file_uploaded(api_key, &frame.path, cache)),
+    )
+    .await;
+
+    let name = display_name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| {
+            Path::new(path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(path)
+        });
+    let mut parts = vec![GeminiPart {
+        text: Some(animation_note(name, &animation)),
+        ..Default::default()
+    }];
+    for upload in uploads {
+        let file_ref = upload?;
+        parts.push(GeminiPart {
+            file_data: Some(GeminiFileData {
+                mime_type: file_ref.mime_type,
+                file_uri: file_ref.file_uri,
+            }),
+            ..Default::default()
+        });
+    }
+    Ok(Some(parts))
+}
+
+fn animation_note(name: &str, animation: &SampledAnimation) -> String {
+    let timestamps = animation
+        .frames
+        .iter()
+        .map(|frame| format_seconds(frame.timestamp_ms))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "[Animated image `{}`: {} frames over {}. The next {} images are frames sampled evenly \
+         from it, in order, at {}. Treat them as one animation and describe what changes.]",
+        name,
+        animation.total_frames,
+        format_seconds(animation.duration_ms),
+        animation.frames.len(),
+        timestamps
+    )
+}
+
+fn format_seconds(ms: u64) -> String {
+    format!("{:.1}s", ms as f64 / 1000.0)
+}
+
+/// Samples `count` frames of a GIF or APNG into CAS. `None` for still
+/// images and other formats.
+fn sample_animation_frames(path: &Path, count: usize) -> Result<Option<SampledAnimation>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read image: {}", e))?;
+    // CAS names every stored image `.png`, so go by content.
+    let Some(format) = image::guess_format(&bytes).ok() else {
+        return Ok(None);
+    };
+    if !is_animated(&bytes, format) {
+        return Ok(None);
+    }
+
+    // Two passes: the frame count is only known after decoding them all, and
+    // keeping every frame of a long GIF in memory is wasteful.
+    let delays = decode_frames(&bytes, format)?
+        .map(|frame| frame.map(|frame| frame_delay_ms(&frame)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to decode animation: {}", e))?;
+    if delays.len() < 2 {
+        return Ok(None);
+    }
+    let starts = frame_starts_ms(&delays);
+    let indices = sample_indices(delays.len(), count);
+
+    let storage = super::paths::get_active_storage()?;
+    let mut frames = Vec::with_capacity(indices.len());
+    let mut wanted = indices.iter().peekable();
+    for (index, frame) in decode_frames(&bytes, format)?.enumerate() {
+        if wanted.peek() != Some(&&index) {
+            continue;
+        }
+        wanted.next();
+        let frame = frame.map_err(|e| format!("Failed to decode animation: {}", e))?;
+        let mut png = Cursor::new(Vec::new());
+        frame
+            .into_buffer()
+            .write_to(&mut png, ImageFormat::Png)
+            .map_err(|e| format!("Failed to encode frame: {}", e))?;
+        let stored = storage
+            .store_image(png.get_ref(), None)
+            .map_err(|e| e.to_string())?;
+        frames.push(SampledFrame {
+            timestamp_ms: starts[index],
+            path: stored.path,
+        });
+        if wanted.peek().is_none() {
+            break;
+        }
+    }
+
+    Ok(Some(SampledAnimation {
+        total_frames: delays.len(),
+        duration_ms: delays.iter().sum(),
+        frames,
+    }))
+}
+
+fn is_animated(bytes: &[u8], format: ImageFormat) -> bool {
+    match format {
+        ImageFormat::Gif => true,
+        ImageFormat::Png => PngDecoder::new(Cursor::new(bytes))
+            .and_then(|decoder| decoder.is_apng())
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+fn decode_frames(
+    bytes: &[u8],
+    format: ImageFormat,
+) -> Result<Box<dyn Iterator<Item = image::ImageResult<Frame>> + '_>, String> {
+    let decode_err = |e: image::ImageError| format!("Failed to decode animation: {}", e);
+    let frames = match format {
+        ImageFormat::Gif => GifDecoder::new(Cursor::new(bytes))
+            .map_err(decode_err)?
+            .into_frames(),
+        _ => PngDecoder::new(Cursor::new(bytes))
+            .and_then(|decoder| decoder.apng())
+            .map_err(decode_err)?
+            .into_frames(),
+    };
+    Ok(Box::new(frames))
+}
+
+fn frame_delay_ms(frame: &Frame) -> u64 {
+    let (numer, denom) = frame.delay().numer_denom_ms();
+    if denom == 0 {
+        0
+    } else {
+        u64::from(numer) / u64::from(denom)
+    }
+}
+
+fn frame_starts_ms(delays: &[u64]) -> Vec<u64> {
+    delays
+        .iter()
+        .scan(0, |elapsed, delay| {
+            let start = *elapsed;
+            *elapsed += delay;
+            Some(start)
+        })
+        .collect()
+}
+
+/// `count` indices spread evenly over `total` frames, always including the
+/// first and last frame.
+fn sample_indices(total: usize, count: usize) -> Vec<usize> {
+    let count = count.min(total);
+    match count {
+        0 => Vec::new(),
+        1 => vec![0],
+        _ => (0..count)
+            .map(|i| (i * (total - 1) + (count - 1) / 2) / (count - 1))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_indices_spread_evenly_and_keep_ends() {
+        assert_eq!(sample_indices(10, 4), vec![0, 3, 6, 9]);
+        assert_eq!(sample_indices(48, 6), vec![0, 9, 19, 28, 38, 47]);
+        assert_eq!(sample_indices(3, 6), vec![0, 1, 2]);
+        assert_eq!(sample_indices(5, 1), vec![0]);
+        assert!(sample_indices(0, 4).is_empty());
+    }
+
+    #[test]
+    fn frame_starts_accumulate_delays() {
+        assert_eq!(
+            frame_starts_ms(&[100, 100, 250, 50]),
+            vec![0, 100, 200, 450]
+        );
+    }
+
+    #[test]
+    fn note_lists_frame_timestamps() {
+        let animation = SampledAnimation {
+            total_frames: 30,
+            duration_ms: 3000,
+            frames: vec![
+                SampledFrame {
+                    timestamp_ms: 0,
+                    path: "a.png".to_string(),
+                },
+                SampledFrame {
+                    timestamp_ms: 2900,
+                    path: "b.png".to_string(),
+                },
+            ],
+        };
+        let note = animation_note("loop.gif", &animation);
+        assert!(note.contains("`loop.gif`: 30 frames over 3.0s"));
+        assert!(note.contains("next 2 images"));
+        assert!(note.contains("at 0.0s, 2.9s"));
+    }
+
+    #[test]
+    fn still_images_are_not_animated() {
+        let mut png = Cursor::new(Vec::new());
+        image::RgbaImage::new(2, 2)
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        assert!(!is_animated(png.get_ref(), ImageFormat::Png));
+        assert!(is_animated(b"GIF89a", ImageFormat::Gif));
+    }
+}
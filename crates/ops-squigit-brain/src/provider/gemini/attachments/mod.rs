@@ -3,6 +3,7 @@
 
 mod cache;
 mod detector;
+mod frames;
 mod mime;
 mod parser;
 mod parts;
@@ -11,8 +12,10 @@ mod registry;
 mod types;
 mod upload;
 
-pub use cache::ensure_file_uploaded;
+pub use cache::{ensure_file_uploaded, ProviderFileCache, PROVIDER_FILE_CACHE_CAPACITY};
 pub(crate) use detector::extract_attachment_mentions;
+pub(crate) use frames::animated_image_parts;
+pub use frames::{DEFAULT_ANIMATION_FRAMES, MAX_ANIMATION_FRAMES};
 pub use mime::{
     is_gemini_document_path, is_gemini_uploadable_path, is_image_path, is_text_like_path,
     mime_from_extension,
@@ -28,4 +31,4 @@ pub(crate) use registry::{
     recall_chat_attachment, RecallChatAttachmentOutcome,
 };
 pub use types::GeminiFileRef;
-pub use upload::{poll_file_status, upload_file_to_gemini};
+pub use upload::{local_file_ref, poll_file_status, upload_file_to_gemini};
@@ -55,11 +55,8 @@ fn is_attachment_link_path(path: &str) -> bool {
 pub(crate) async fn build_interleaved_parts(
     text: &str,
     api_key: &str,
-    cache: &Arc<
-        tokio::sync::Mutex<
-            HashMap<String, crate::provider::gemini::attachments::GeminiFileRef>,
-        >,
-    >,
+    animation_frames: usize,
+    cache: &Arc<tokio::sync::Mutex<crate::provider::gemini::attachments::ProviderFileCache>>,
 ) -> Result<Vec<GeminiPart>, String> {
     let re = Regex::new(
         r"(?x)
@@ -123,13 +120,32 @@ pub(crate) async fn build_interleaved_parts(
     unique_paths.dedup();
 
     let prepare_futures = unique_paths.iter().map(|p| async {
-        crate::provider::gemini::attachments::ensure_file_uploaded(api_key, p, cache).await
+        let animated = crate::provider::gemini::attachments::animated_image_parts(
+            api_key,
+            p,
+            None,
+            animation_frames,
+            cache,
+        )
+        .await?;
+        if let Some(parts) = animated {
+            return Ok(parts);
+        }
+        let file_ref =
+            crate::provider::gemini::attachments::ensure_file_uploaded(api_key, p, cache).await?;
+        Ok::<_, String>(vec![GeminiPart {
+            file_data: Some(GeminiFileData {
+                mime_type: file_ref.mime_type,
+                file_uri: file_ref.file_uri,
+            }),
+            ..Default::default()
+        }])
     });
 
     let results = join_all(prepare_futures).await;
     let mut prepared_attachments = HashMap::new();
 
-    for (path, result) in unique_paths.into_iter().zip(results.into_iter()) {
+    for (path, result) in unique_paths.into_iter().zip(results) {
         match result {
             Ok(prepared) => {
                 prepared_attachments.insert(path, prepared);
@@ -141,14 +157,8 @@ pub(crate) async fn build_interleaved_parts(
     let mut parts = Vec::new();
     for (is_file, content) in text_chunks {
         if is_file {
-            if let Some(file_ref) = prepared_attachments.get(&content) {
-                parts.push(GeminiPart {
-                    file_data: Some(GeminiFileData {
-                        mime_type: file_ref.mime_type.clone(),
-                        file_uri: file_ref.file_uri.clone(),
-                    }),
-                    ..Default::default()
-                });
+            if let Some(file_parts) = prepared_attachments.get(&content) {
+                parts.extend(file_parts.iter().cloned());
             }
         } else {
             parts.push(GeminiPart {
@@ -11,15 +11,25 @@ pub(crate) fn get_active_storage() -> Result<ChatStorage, String> {
         .map_err(|e| e.to_string())?
         .ok_or_else(|| "No active profile. Please log in first.".to_string())?;
 
-    let chats_dir = profile_store.get_chats_dir(&active_id);
-    ChatStorage::with_base_dir(chats_dir).map_err(|e| e.to_string())
+    profile_store
+        .open_chat_storage(&active_id)
+        .map_err(|e| e.to_string())
 }
 
 fn is_path_within_base(path: &std::path::Path, base: &std::path::Path) -> bool {
     path.starts_with(base)
 }
 
+/// Resolve an attachment to a path its bytes can be read from. Encrypted
+/// objects resolve to a decrypted copy.
 pub(crate) fn resolve_attachment_path_internal(path: &str) -> Result<std::path::PathBuf, String> {
+    let resolved = resolve_stored_path(path)?;
+    get_active_storage()?
+        .readable_path(&resolved)
+        .map_err(|e| e.to_string())
+}
+
+fn resolve_stored_path(path: &str) -> Result<std::path::PathBuf, String> {
     use std::fs;
     use std::path::PathBuf;
 
@@ -65,7 +75,7 @@ pub(crate) fn resolve_attachment_path_internal(path: &str) -> Result<std::path::
 pub(crate) fn resolve_attachment_path_for_local_context(
     path: &str,
 ) -> Result<std::path::PathBuf, String> {
-    let resolved = resolve_attachment_path_internal(path)?;
+    let resolved = resolve_stored_path(path)?;
     let storage = get_active_storage()?;
     let base_dir = std::fs::canonicalize(storage.base_dir()).map_err(|e| {
         format!(
@@ -75,7 +85,17 @@ pub(crate) fn resolve_attachment_path_for_local_context(
     })?;
 
     if is_path_within_base(&resolved, &base_dir) {
-        return Ok(resolved);
+        return storage.readable_path(&resolved).map_err(|e| e.to_string());
+    }
+    // Decrypted copies handed out earlier are in scope too.
+    if let Some(copies_dir) = storage
+        .decrypted_copies_dir()
+        .ok()
+        .and_then(|dir| std::fs::canonicalize(dir).ok())
+    {
+        if is_path_within_base(&resolved, &copies_dir) {
+            return Ok(resolved);
+        }
     }
 
     Err(format!(
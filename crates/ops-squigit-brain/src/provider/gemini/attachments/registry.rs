@@ -8,7 +8,6 @@ use ops_chat_storage::{
     ChatAttachmentKind, ChatAttachmentProviderFile, ChatAttachmentRecord, ChatData, ChatStorage,
     StorageError,
 };
-use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -16,14 +15,15 @@ use tokio::sync::Mutex;
 use super::detector::{extract_attachment_mentions, AttachmentMention};
 use super::types::GeminiFileObject;
 use super::{
-    ensure_file_uploaded, is_gemini_document_path, is_gemini_uploadable_path, is_image_path,
-    is_text_like_path, mime_from_extension, GeminiFileRef,
+    animated_image_parts, ensure_file_uploaded, is_gemini_document_path, is_gemini_uploadable_path,
+    is_image_path, is_text_like_path, mime_from_extension, GeminiFileRef, ProviderFileCache,
 };
+use crate::provider::gemini::client::{endpoint, local_file_path};
 use crate::provider::gemini::transport::types::{GeminiFileData, GeminiPart};
 
 const MAX_ATTACHMENT_CATALOG_ITEMS: usize = 8;
 
-type GeminiFileCache = Arc<Mutex<HashMap<String, GeminiFileRef>>>;
+type GeminiFileCache = Arc<Mutex<ProviderFileCache>>;
 
 pub(crate) struct PreparedTurnAttachments {
     pub(crate) preview_attachment_paths: Vec<String>,
@@ -273,8 +273,8 @@ async fn get_cached_file_ref(
     cache: &GeminiFileCache,
 ) -> Result<Option<GeminiFileRef>, String> {
     let key = cache_key_for_path(path).await?;
-    let cache_lock = cache.lock().await;
-    Ok(cache_lock.get(&key).cloned())
+    let mut cache_lock = cache.lock().await;
+    Ok(cache_lock.get(&key))
 }
 
 async fn insert_cached_file_ref(
@@ -308,11 +308,17 @@ async fn validate_uploaded_file_handle(api_key: &str, file_name: &str) -> bool {
         return false;
     }
 
+    // Handles from the other backend are no use: re-upload, or inline.
+    let endpoint = endpoint();
+    if let Some(path) = local_file_path(file_name) {
+        return !endpoint.uses_files_api() && path.exists();
+    }
+    if !endpoint.uses_files_api() {
+        return false;
+    }
+
     let client = reqwest::Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/{}?key={}",
-        file_name, api_key
-    );
+    let url = endpoint.resource_url(file_name, api_key);
 
     let response = match client.get(url).send().await {
         Ok(response) => response,
@@ -395,6 +401,7 @@ pub(crate) async fn prepare_turn_attachments(
     chat_id: Option<&str>,
     mentions: &[AttachmentMention],
     api_key: &str,
+    animation_frames: usize,
     cache: &GeminiFileCache,
 ) -> Result<PreparedTurnAttachments, String> {
     let mut loaded_chat = match chat_id {
@@ -420,6 +427,19 @@ pub(crate) async fn prepare_turn_attachments(
             continue;
         }
 
+        if let Some(parts) = animated_image_parts(
+            api_key,
+            &path,
+            mention.display_name.as_deref(),
+            animation_frames,
+            cache,
+        )
+        .await?
+        {
+            uploaded_parts.extend(parts);
+            continue;
+        }
+
         let file_ref = if let Some((_, chat, changed)) = loaded_chat.as_mut() {
             let (file_ref, was_changed, _) =
                 ensure_live_file_ref(chat, &path, api_key, cache).await?;
@@ -463,7 +483,7 @@ pub(crate) fn build_chat_attachment_catalog(
         return Ok(None);
     }
 
-    entries.sort_by(|left, right| right.last_seen_at.cmp(&left.last_seen_at));
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_seen_at));
     entries.truncate(MAX_ATTACHMENT_CATALOG_ITEMS);
 
     let lines = entries
@@ -6,6 +6,28 @@ use reqwest::{header, Client};
 
 use super::types::{GeminiFileObject, GeminiFileUploadFinalizeResponse};
 use super::GeminiFileRef;
+use crate::provider::gemini::client::{endpoint, local_file_uri};
+
+/// A reference to a local file by its `file://` URI, read when the request
+/// is sent, in place of an upload.
+pub async fn local_file_ref(
+    file_path: &str,
+    mime_type: &str,
+    display_name: &str,
+) -> Result<GeminiFileRef, String> {
+    tokio::fs::metadata(file_path)
+        .await
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let uri = local_file_uri(file_path);
+    Ok(GeminiFileRef {
+        file_uri: uri.clone(),
+        file_name: uri,
+        mime_type: mime_type.to_string(),
+        display_name: display_name.to_string(),
+        uploaded_at: Utc::now(),
+        expires_at: Utc::now() + Duration::hours(47),
+    })
+}
 
 pub async fn upload_file_to_gemini(
     api_key: &str,
@@ -13,17 +35,22 @@ pub async fn upload_file_to_gemini(
     mime_type: &str,
     display_name: &str,
 ) -> Result<GeminiFileRef, String> {
+    let endpoint = endpoint();
+    if !endpoint.uses_files_api() {
+        // Vertex AI has no Files API; the file is read when it's sent.
+        return local_file_ref(file_path, mime_type, display_name).await;
+    }
+
     let client = Client::new();
     let file_bytes = tokio::fs::read(file_path)
         .await
         .map_err(|e| format!("Failed to read file: {}", e))?;
+    // Local copies keep their metadata; uploads never carry it.
+    let file_bytes = ops_chat_storage::without_image_metadata(file_bytes);
     let file_size = file_bytes.len();
 
     // Step 1: Start Resumable Upload
-    let start_url = format!(
-        "https://generativelanguage.googleapis.com/upload/v1beta/files?key={}",
-        api_key
-    );
+    let start_url = endpoint.upload_url(api_key);
 
     let mut headers = header::HeaderMap::new();
     headers.insert(
@@ -121,10 +148,7 @@ pub async fn upload_file_to_gemini(
 
 pub async fn poll_file_status(api_key: &str, file_name: &str) -> Result<(), String> {
     let client = Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/{}?key={}",
-        file_name, api_key
-    );
+    let url = endpoint().resource_url(file_name, api_key);
 
     loop {
         let res = client
@@ -0,0 +1,318 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gemini API endpoints.
+//!
+//! Every request URL is built here from a base URL and API version, so a
+//! proxy or regional endpoint only has to be configured once. The app
+//! registers where the settings come from with [`set_endpoint_source`];
+//! the source is asked again for each request, so a changed preference
+//! applies without a restart.
+//!
+//! With [`VertexAuth`] the same requests go to Vertex AI instead: model
+//! URLs name the profile's project, requests carry a bearer token, and
+//! since Vertex has no Files API, attachments are sent inline.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use base64::Engine;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::provider::gemini::vertex::VertexAuth;
+
+pub const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+pub const DEFAULT_API_VERSION: &str = "v1beta";
+
+type EndpointSource = Box<dyn Fn() -> GeminiEndpoint + Send + Sync>;
+
+static ENDPOINT_SOURCE: RwLock<Option<EndpointSource>> = RwLock::new(None);
+
+/// Scheme of the file URIs that stand in for uploads on Vertex AI.
+const LOCAL_FILE_SCHEME: &str = "file://";
+
+#[derive(Debug, Clone)]
+pub struct GeminiEndpoint {
+    /// Scheme and host, optionally with a path prefix, without the version.
+    pub base_url: String,
+    /// e.g. `v1beta` or `v1`.
+    pub api_version: String,
+    /// Send requests to Vertex AI; the base URL and version are unused.
+    pub vertex: Option<Arc<VertexAuth>>,
+}
+
+impl Default for GeminiEndpoint {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_version: DEFAULT_API_VERSION.to_string(),
+            vertex: None,
+        }
+    }
+}
+
+impl GeminiEndpoint {
+    /// Blank values fall back to the defaults.
+    pub fn new(base_url: Option<&str>, api_version: Option<&str>) -> Self {
+        let base_url = base_url
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+            .unwrap_or(DEFAULT_BASE_URL);
+        let api_version = api_version
+            .map(|version| version.trim().trim_matches('/'))
+            .filter(|version| !version.is_empty())
+            .unwrap_or(DEFAULT_API_VERSION);
+        Self {
+            base_url: base_url.to_string(),
+            api_version: api_version.to_string(),
+            vertex: None,
+        }
+    }
+
+    pub fn with_vertex(mut self, auth: Arc<VertexAuth>) -> Self {
+        self.vertex = Some(auth);
+        self
+    }
+
+    /// Whether attachments are uploaded, rather than sent inline.
+    pub fn uses_files_api(&self) -> bool {
+        self.vertex.is_none()
+    }
+
+    /// `models/{model}:{method}`, e.g. `generateContent`.
+    pub fn model_url(&self, model: &str, method: &str, api_key: &str) -> String {
+        if let Some(vertex) = &self.vertex {
+            return vertex.model_url(model, method);
+        }
+        format!(
+            "{}/{}/models/{}:{}?key={}",
+            self.base_url, self.api_version, model, method, api_key
+        )
+    }
+
+    /// Server-sent events from `streamGenerateContent`.
+    pub fn stream_url(&self, model: &str, api_key: &str) -> String {
+        if let Some(vertex) = &self.vertex {
+            return format!(
+                "{}?alt=sse",
+                vertex.model_url(model, "streamGenerateContent")
+            );
+        }
+        format!(
+            "{}/{}/models/{}:streamGenerateContent?alt=sse&key={}",
+            self.base_url, self.api_version, model, api_key
+        )
+    }
+
+    /// The model list; the key goes in the query with the page parameters.
+    pub fn models_url(&self) -> String {
+        if let Some(vertex) = &self.vertex {
+            return format!("{}/v1beta1/publishers/google/models", vertex.host());
+        }
+        format!("{}/{}/models", self.base_url, self.api_version)
+    }
+
+    /// A Files API resource by name, e.g. an uploaded file `files/abc123`.
+    pub fn resource_url(&self, name: &str, api_key: &str) -> String {
+        format!(
+            "{}/{}/{}?key={}",
+            self.base_url, self.api_version, name, api_key
+        )
+    }
+
+    /// Starts a resumable Files API upload.
+    pub fn upload_url(&self, api_key: &str) -> String {
+        format!(
+            "{}/upload/{}/files?key={}",
+            self.base_url, self.api_version, api_key
+        )
+    }
+
+    /// Add the Vertex AI access token, if this endpoint needs one.
+    pub async fn authorize(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, String> {
+        match &self.vertex {
+            Some(vertex) => Ok(request.bearer_auth(vertex.access_token().await?)),
+            None => Ok(request),
+        }
+    }
+
+    /// Authorize `request` and attach `body`, with local files inlined on
+    /// Vertex AI.
+    pub async fn json_request<T: Serialize>(
+        &self,
+        request: reqwest::RequestBuilder,
+        body: &T,
+    ) -> Result<reqwest::RequestBuilder, String> {
+        if self.vertex.is_none() {
+            return Ok(request.json(body));
+        }
+        let mut body = serde_json::to_value(body).map_err(|e| e.to_string())?;
+        inline_local_files(&mut body).await?;
+        Ok(self.authorize(request).await?.json(&body))
+    }
+}
+
+/// The stand-in for an upload on Vertex AI, read when a request is sent.
+pub fn local_file_uri(path: &str) -> String {
+    url::Url::from_file_path(path)
+        .map(String::from)
+        .unwrap_or_else(|_| format!("{}{}", LOCAL_FILE_SCHEME, path))
+}
+
+/// The path behind a [`local_file_uri`].
+pub fn local_file_path(uri: &str) -> Option<std::path::PathBuf> {
+    if !uri.starts_with(LOCAL_FILE_SCHEME) {
+        return None;
+    }
+    url::Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// Replace `fileData` parts that point at local files with `inlineData`.
+async fn inline_local_files(body: &mut Value) -> Result<(), String> {
+    let mut uris = Vec::new();
+    collect_local_file_uris(body, &mut uris);
+    let mut data = HashMap::new();
+    for uri in uris {
+        if data.contains_key(&uri) {
+            continue;
+        }
+        let path = local_file_path(&uri).ok_or_else(|| format!("Invalid file URI: {}", uri))?;
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let bytes = ops_chat_storage::without_image_metadata(bytes);
+        data.insert(uri, base64::engine::general_purpose::STANDARD.encode(bytes));
+    }
+    replace_local_file_parts(body, &data);
+    Ok(())
+}
+
+fn local_file_uri_of(part: &serde_json::Map<String, Value>) -> Option<&str> {
+    part.get("fileData")?
+        .get("fileUri")?
+        .as_str()
+        .filter(|uri| uri.starts_with(LOCAL_FILE_SCHEME))
+}
+
+fn collect_local_file_uris(value: &Value, uris: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(uri) = local_file_uri_of(map) {
+                uris.push(uri.to_string());
+            }
+            map.values()
+                .for_each(|value| collect_local_file_uris(value, uris));
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|value| collect_local_file_uris(value, uris)),
+        _ => {}
+    }
+}
+
+fn replace_local_file_parts(value: &mut Value, data: &HashMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            let inline = local_file_uri_of(map).and_then(|uri| data.get(uri));
+            if let Some(encoded) = inline.cloned() {
+                let mime_type = map["fileData"]["mimeType"].clone();
+                map.remove("fileData");
+                map.insert(
+                    "inlineData".to_string(),
+                    serde_json::json!({ "mimeType": mime_type, "data": encoded }),
+                );
+            }
+            map.values_mut()
+                .for_each(|value| replace_local_file_parts(value, data));
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|value| replace_local_file_parts(value, data)),
+        _ => {}
+    }
+}
+
+/// Where [`endpoint`] reads the settings from, e.g. the app's preferences.
+pub fn set_endpoint_source<F>(source: F)
+where
+    F: Fn() -> GeminiEndpoint + Send + Sync + 'static,
+{
+    if let Ok(mut current) = ENDPOINT_SOURCE.write() {
+        *current = Some(Box::new(source));
+    }
+}
+
+/// The endpoint for the next request: the registered source's, or the
+/// public API when none is registered.
+pub fn endpoint() -> GeminiEndpoint {
+    ENDPOINT_SOURCE
+        .read()
+        .ok()
+        .and_then(|source| source.as_ref().map(|source| source()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_settings_use_the_public_api() {
+        let endpoint = GeminiEndpoint::new(Some("  "), None);
+        assert_eq!(
+            endpoint.model_url("gemini-pro", "generateContent", "k"),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-pro:generateContent?key=k"
+        );
+    }
+
+    #[test]
+    fn custom_base_url_and_version_apply_to_every_endpoint() {
+        let endpoint = GeminiEndpoint::new(Some("https://proxy.example.com/gemini/"), Some("v1"));
+        assert_eq!(
+            endpoint.stream_url("m", "k"),
+            "https://proxy.example.com/gemini/v1/models/m:streamGenerateContent?alt=sse&key=k"
+        );
+        assert_eq!(
+            endpoint.upload_url("k"),
+            "https://proxy.example.com/gemini/upload/v1/files?key=k"
+        );
+        assert_eq!(
+            endpoint.resource_url("files/abc", "k"),
+            "https://proxy.example.com/gemini/v1/files/abc?key=k"
+        );
+        assert_eq!(
+            endpoint.models_url(),
+            "https://proxy.example.com/gemini/v1/models"
+        );
+    }
+
+    #[tokio::test]
+    async fn local_file_parts_are_sent_inline() {
+        let path =
+            std::env::temp_dir().join(format!("squigit-inline-{}.txt", rand::random::<u64>()));
+        std::fs::write(&path, b"hello").unwrap();
+        let uri = local_file_uri(path.to_str().unwrap());
+        assert_eq!(local_file_path(&uri).as_deref(), Some(path.as_path()));
+
+        let mut body = serde_json::json!({
+            "contents": [{ "role": "user", "parts": [
+                { "text": "Describe" },
+                { "fileData": { "mimeType": "text/plain", "fileUri": uri } },
+                { "fileData": { "mimeType": "image/png", "fileUri": "https://example.com/f" } },
+            ]}]
+        });
+        inline_local_files(&mut body).await.unwrap();
+
+        let parts = &body["contents"][0]["parts"];
+        assert_eq!(parts[1]["inlineData"]["mimeType"], "text/plain");
+        assert_eq!(parts[1]["inlineData"]["data"], "aGVsbG8=");
+        assert!(parts[1].get("fileData").is_none());
+        assert_eq!(parts[2]["fileData"]["fileUri"], "https://example.com/f");
+
+        let _ = std::fs::remove_file(path);
+    }
+}
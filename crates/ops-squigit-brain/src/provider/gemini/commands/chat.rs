@@ -15,15 +15,46 @@ use crate::provider::gemini::agent::tool_orchestrator::{
     build_system_instruction_with_tool_policy, tool_status_text, tool_step_id,
 };
 use crate::provider::gemini::attachments::{
-    build_attachment_preview_context, build_chat_attachment_catalog, build_interleaved_parts,
-    extract_attachment_mentions, prepare_turn_attachments,
+    animated_image_parts, build_attachment_preview_context, build_chat_attachment_catalog,
+    build_interleaved_parts, ensure_file_uploaded, extract_attachment_mentions,
+    prepare_turn_attachments, ProviderFileCache,
 };
+use crate::provider::gemini::client::{endpoint, local_file_path};
 use crate::provider::gemini::transport::streaming::{emit_event, stream_request_iteration};
 use crate::provider::gemini::transport::types::{
-    GeminiContent, GeminiEvent, GeminiFileData, GeminiFunctionResponse, GeminiPart, GeminiRequest,
+    GeminiContent, GeminiEvent, GeminiFileData, GeminiFunctionResponse, GeminiPart,
+    GeminiPromptPreview, GeminiRequest,
 };
 use crate::events::BrainEventSink;
 use crate::runtime::BrainRuntimeState;
+use ops_profile_store::GlossaryEntry;
+
+/// Best-effort lookup of the stored OCR regions for the chat's active OCR model.
+pub(crate) fn load_active_ocr_regions(chat_id: Option<&str>) -> Option<Vec<ops_chat_storage::OcrRegion>> {
+    let chat_id = chat_id.map(str::trim).filter(|id| !id.is_empty())?;
+    let storage = crate::context::media::get_active_storage().ok()?;
+    let chat = storage.load_chat(chat_id).ok()?;
+    let model_id = chat.metadata.ocr_lang.as_deref()?;
+    storage.get_ocr_data(chat_id, model_id).ok()?
+}
+
+pub(crate) fn load_plugin_notes(chat_id: Option<&str>) -> Vec<ops_chat_storage::PluginNote> {
+    let Some(chat_id) = chat_id.map(str::trim).filter(|id| !id.is_empty()) else {
+        return Vec::new();
+    };
+    crate::context::media::get_active_storage()
+        .and_then(|storage| storage.get_plugin_notes(chat_id).map_err(|e| e.to_string()))
+        .unwrap_or_default()
+}
+
+pub(crate) fn load_web_source(chat_id: Option<&str>) -> Option<ops_chat_storage::WebSource> {
+    let chat_id = chat_id.map(str::trim).filter(|id| !id.is_empty())?;
+    crate::context::media::get_active_storage()
+        .ok()?
+        .get_web_source(chat_id)
+        .ok()
+        .flatten()
+}
 
 fn normalize_attachment_lookup_key(path: &str) -> String {
     let trimmed = path.trim();
@@ -161,8 +192,8 @@ pub async fn stream_gemini_chat_v2(
     api_key: String,
     model: String,
     is_initial_turn: bool,
-    // Initial turn params
-    image_path: Option<String>,
+    // Initial turn params: the chat's image, then any further ones in order
+    image_paths: Vec<String>,
     // Subsequent turn params
     image_description: Option<String>,
     user_first_msg: Option<String>,
@@ -178,17 +209,36 @@ pub async fn stream_gemini_chat_v2(
     user_email: Option<String>,
     user_instruction: Option<String>,
     image_brief: Option<String>,
-) -> Result<(), String> {
+    // BCP 47 tag the reply must be written in, validated by the caller.
+    response_language: Option<String>,
+    // Profile glossary entries to list in the system instruction.
+    glossary: Vec<GlossaryEntry>,
+    // Append the stored OCR transcript to the initial turn.
+    include_ocr_in_prompt: bool,
+    // Replace personal data in that transcript with placeholders.
+    redact_pii: bool,
+    // Frames to sample from animated GIF/APNG images; below 2 sends them as is.
+    animation_frames: usize,
+    // Assemble the request and return it instead of calling the model.
+    dry_run: bool,
+) -> Result<Option<GeminiPromptPreview>, String> {
     const MAX_TOOL_CALLS_PER_TURN: usize = 3;
     const MAX_AGENT_ITERATIONS: usize = 8;
     const MAX_OUTPUT_TOKENS: usize = 2048;
 
+    // A dry run refers to files by their local path instead of uploading.
+    let dry_run_cache =
+        std::sync::Arc::new(tokio::sync::Mutex::new(ProviderFileCache::local_only()));
+    let file_cache = if dry_run {
+        &dry_run_cache
+    } else {
+        &runtime.provider_file_cache
+    };
+
     let result = async {
         let client = reqwest::Client::new();
-        let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
-            model, api_key
-        );
+        let endpoint = endpoint();
+        let url = endpoint.stream_url(&model, &api_key);
 
         let request_control = GeminiRequestControl::new();
         register_request(runtime, channel_id.clone(), request_control.clone()).await;
@@ -209,20 +259,25 @@ pub async fn stream_gemini_chat_v2(
             let system_prompt = crate::context::builder::build_initial_system_prompt()?;
             let mut parts = vec![];
 
-            if let Some(path) = image_path.clone() {
-                let file_ref =
-                    crate::provider::gemini::attachments::ensure_file_uploaded(&api_key, &path, &runtime.provider_file_cache)
-                        .await?;
-                parts.push(GeminiPart {
-                    file_data: Some(GeminiFileData {
-                        mime_type: file_ref.mime_type.clone(),
-                        file_uri: file_ref.file_uri.clone(),
-                    }),
-                    ..Default::default()
-                });
-            } else {
+            if image_paths.is_empty() {
                 return Err("image_path required for initial turn".to_string());
             }
+            for path in &image_paths {
+                if let Some(frame_parts) =
+                    animated_image_parts(&api_key, path, None, animation_frames, file_cache).await?
+                {
+                    parts.extend(frame_parts);
+                } else {
+                    let file_ref = ensure_file_uploaded(&api_key, path, file_cache).await?;
+                    parts.push(GeminiPart {
+                        file_data: Some(GeminiFileData {
+                            mime_type: file_ref.mime_type.clone(),
+                            file_uri: file_ref.file_uri.clone(),
+                        }),
+                        ..Default::default()
+                    });
+                }
+            }
 
             parts.push(GeminiPart {
                 text: Some(system_prompt),
@@ -238,9 +293,46 @@ pub async fn stream_gemini_chat_v2(
                 }
             }
 
+            let ocr_regions = load_active_ocr_regions(chat_id.as_deref()).unwrap_or_default();
+            if let Some(note) = crate::context::builder::build_ocr_confidence_note(
+                &ops_chat_storage::OcrConfidenceSummary::from_regions(&ocr_regions),
+            ) {
+                parts.push(GeminiPart {
+                    text: Some(note),
+                    ..Default::default()
+                });
+            }
+
+            if include_ocr_in_prompt {
+                if let Some(block) =
+                    crate::context::builder::build_ocr_transcript_block(&ocr_regions, redact_pii)
+                {
+                    parts.push(GeminiPart {
+                        text: Some(block),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            if let Some(source) = load_web_source(chat_id.as_deref()) {
+                parts.push(GeminiPart {
+                    text: Some(crate::context::builder::build_web_source_block(&source)),
+                    ..Default::default()
+                });
+            }
+
+            if let Some(block) = crate::context::builder::build_plugin_context_block(
+                &load_plugin_notes(chat_id.as_deref()),
+            ) {
+                parts.push(GeminiPart {
+                    text: Some(block),
+                    ..Default::default()
+                });
+            }
+
             if !user_message.is_empty() {
                 let interleaved_parts =
-                    build_interleaved_parts(&user_message, &api_key, &runtime.provider_file_cache)
+                    build_interleaved_parts(&user_message, &api_key, animation_frames, file_cache)
                         .await?;
                 parts.extend(interleaved_parts);
             }
@@ -286,7 +378,8 @@ pub async fn stream_gemini_chat_v2(
                 chat_id.as_deref(),
                 &attachment_mentions,
                 &api_key,
-                &runtime.provider_file_cache,
+                animation_frames,
+                file_cache,
             )
             .await?;
 
@@ -349,6 +442,8 @@ pub async fn stream_gemini_chat_v2(
                 user_name.as_deref().unwrap_or(""),
                 user_email.as_deref().unwrap_or(""),
                 image_brief.as_deref().unwrap_or(""),
+                response_language.as_deref(),
+                &glossary,
                 allow_tools,
             )?;
             let system_instruction = Some(GeminiContent {
@@ -377,9 +472,16 @@ pub async fn stream_gemini_chat_v2(
                 },
             };
 
+            if dry_run {
+                let request = serde_json::to_value(&request_body)
+                    .map_err(|e| format!("Failed to serialize request preview: {}", e))?;
+                return Ok(Some(GeminiPromptPreview { model, request }));
+            }
+
             let iteration = stream_request_iteration(
                 sink,
                 &client,
+                &endpoint,
                 &url,
                 &request_body,
                 &channel_id,
@@ -388,11 +490,11 @@ pub async fn stream_gemini_chat_v2(
             .await?;
 
             if !allow_tools {
-                return Ok(());
+                return Ok(None);
             }
 
             let Some(function_call) = iteration.function_call else {
-                return Ok(());
+                return Ok(None);
             };
 
             let attachment_display_name = tool_attachment_lookup_value(&function_call)
@@ -427,7 +529,7 @@ pub async fn stream_gemini_chat_v2(
                 api_key: &api_key,
                 model: &model,
                 chat_id: chat_id.as_deref(),
-                gemini_file_cache: &runtime.provider_file_cache,
+                gemini_file_cache: file_cache,
                 request_control: &request_control,
                 web_state: &mut web_tool_state,
             };
@@ -523,3 +625,62 @@ pub async fn stream_gemini_chat_v2(
 
     result
 }
+
+/// Send a request returned by a dry run, as shown or as edited, in one
+/// generation call on `channel_id`. Local file references are uploaded
+/// like any attachment, so they must point into the active chat storage.
+/// The model is not allowed to call tools, since there is no agent loop to
+/// run them; the declarations stay so the request is otherwise unchanged.
+pub async fn send_gemini_prompt_preview(
+    runtime: &BrainRuntimeState,
+    sink: &dyn BrainEventSink,
+    api_key: String,
+    channel_id: String,
+    preview: GeminiPromptPreview,
+) -> Result<(), String> {
+    let mut request_body: GeminiRequest = serde_json::from_value(preview.request)
+        .map_err(|e| format!("Invalid request preview: {}", e))?;
+    for part in request_body
+        .contents
+        .iter_mut()
+        .flat_map(|content| content.parts.iter_mut())
+    {
+        let Some(file_data) = part.file_data.as_mut() else {
+            continue;
+        };
+        let Some(path) = local_file_path(&file_data.file_uri) else {
+            continue;
+        };
+        let file_ref = ensure_file_uploaded(
+            &api_key,
+            &path.to_string_lossy(),
+            &runtime.provider_file_cache,
+        )
+        .await?;
+        file_data.file_uri = file_ref.file_uri;
+        file_data.mime_type = file_ref.mime_type;
+    }
+    if request_body.tools.is_some() {
+        request_body.tool_config = Some(json!({
+            "functionCallingConfig": { "mode": "NONE" }
+        }));
+    }
+
+    let client = reqwest::Client::new();
+    let endpoint = endpoint();
+    let url = endpoint.stream_url(&preview.model, &api_key);
+    let request_control = GeminiRequestControl::new();
+    register_request(runtime, channel_id.clone(), request_control.clone()).await;
+    let result = stream_request_iteration(
+        sink,
+        &client,
+        &endpoint,
+        &url,
+        &request_body,
+        &channel_id,
+        &request_control.cancel_token,
+    )
+    .await;
+    remove_request(runtime, &channel_id).await;
+    result.map(|_| ())
+}
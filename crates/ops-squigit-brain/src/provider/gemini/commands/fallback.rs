@@ -0,0 +1,62 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Model fallback: when the chosen model is missing or overloaded, the
+//! request is retried with the profile's fallback models in order.
+
+/// The chosen model followed by its fallbacks, without duplicates.
+pub fn model_chain(model: &str, fallbacks: &[String]) -> Vec<String> {
+    let mut chain = vec![model.to_string()];
+    for fallback in fallbacks {
+        let fallback = fallback.trim();
+        if !fallback.is_empty() && !chain.iter().any(|known| known == fallback) {
+            chain.push(fallback.to_string());
+        }
+    }
+    chain
+}
+
+/// Whether a Gemini error means the model itself cannot serve the request
+/// (HTTP 404 `NOT_FOUND`, or 503 `UNAVAILABLE` / overloaded), so another
+/// model may succeed. Auth, quota and request errors are not retried.
+pub fn is_model_unavailable(error: &str) -> bool {
+    if !error.starts_with("Gemini API Error") {
+        return false;
+    }
+    let lower = error.to_ascii_lowercase();
+    ["\"not_found\"", "\"unavailable\"", "overloaded"]
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_model_unavailable, model_chain};
+
+    #[test]
+    fn chain_keeps_order_and_drops_duplicates() {
+        let fallbacks = vec![
+            "gemini-flash".to_string(),
+            " ".to_string(),
+            "gemini-flash-lite".to_string(),
+        ];
+        assert_eq!(
+            model_chain("gemini-flash", &fallbacks),
+            vec!["gemini-flash", "gemini-flash-lite"]
+        );
+    }
+
+    #[test]
+    fn only_model_errors_trigger_fallback() {
+        assert!(is_model_unavailable(
+            r#"Gemini API Error: {"error": {"code": 404, "message": "models/x is not found", "status": "NOT_FOUND"}}"#
+        ));
+        assert!(is_model_unavailable(
+            r#"Gemini API Error: {"error": {"code": 503, "message": "The model is overloaded. Please try again later.", "status": "UNAVAILABLE"}}"#
+        ));
+        assert!(!is_model_unavailable(
+            r#"Gemini API Error: {"error": {"code": 400, "message": "API key not valid", "status": "INVALID_ARGUMENT"}}"#
+        ));
+        assert!(!is_model_unavailable("CANCELLED"));
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::provider::gemini::client::endpoint;
 use crate::provider::gemini::transport::types::{
     GeminiContent, GeminiFileData, GeminiPart, GeminiRequest, GeminiResponseChunk,
 };
@@ -17,10 +18,8 @@ pub async fn generate_chat_title(
     println!("Generating Title using model: {}", model);
 
     let client = reqwest::Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model, api_key
-    );
+    let endpoint = endpoint();
+    let url = endpoint.model_url(&model, "generateContent", &api_key);
 
     let title_context: String = prompt_context
         .lines()
@@ -48,9 +47,9 @@ pub async fn generate_chat_title(
         tool_config: None,
     };
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
+    let response = endpoint
+        .json_request(client.post(&url), &request_body)
+        .await?
         .send()
         .await
         .map_err(|e| format!("Failed to send request to Gemini: {}", e))?;
@@ -96,6 +95,279 @@ pub async fn generate_chat_title(
     Ok("New thread".to_string())
 }
 
+const CHAT_SUMMARY_PROMPT: &str = "Summarize this conversation in one short sentence \
+(at most 20 words). Reply with the sentence only, no quotes or markdown.";
+
+/// Generate a one-line summary of a chat from its text context.
+/// Returns an empty string when the model produced no text.
+pub async fn generate_chat_summary(
+    api_key: String,
+    model: String,
+    prompt_context: String,
+) -> Result<String, String> {
+    generate_plain_text(
+        &api_key,
+        &model,
+        format!(
+            "{}\n\nConversation:\n{}",
+            CHAT_SUMMARY_PROMPT, prompt_context
+        ),
+        "summary",
+    )
+    .await
+}
+
+const TRANSCRIPT_CLEANUP_PROMPT: &str = "Clean up this speech-to-text transcript. \
+Fix punctuation, casing and obvious mis-hearings of technical terms. Do not add, \
+remove or reorder content, and do not answer it. Reply with the cleaned transcript only.";
+
+/// Tidy a dictated transcript with a small model.
+/// Returns an empty string when the model produced no text.
+pub async fn clean_transcript(
+    api_key: String,
+    model: String,
+    transcript: String,
+) -> Result<String, String> {
+    generate_plain_text(
+        &api_key,
+        &model,
+        format!(
+            "{}\n\nTranscript:\n{}",
+            TRANSCRIPT_CLEANUP_PROMPT, transcript
+        ),
+        "transcript cleanup",
+    )
+    .await
+}
+
+const OCR_TRANSLATION_PROMPT: &str = "Translate the text below into {language}. It was \
+read from a screenshot by OCR, so correct obvious recognition errors silently. Keep line \
+and paragraph breaks, numbers, code and proper names as they are. Reply with the translation \
+only.";
+
+/// Translate OCR text into `language` (an English language name).
+/// Returns an empty string when the model produced no text.
+pub async fn translate_ocr_text(
+    api_key: String,
+    model: String,
+    text: String,
+    language: &str,
+) -> Result<String, String> {
+    generate_plain_text(
+        &api_key,
+        &model,
+        format!(
+            "{}\n\nText:\n{}",
+            OCR_TRANSLATION_PROMPT.replace("{language}", language),
+            text
+        ),
+        "translation",
+    )
+    .await
+}
+
+/// Ask for the calendar events in a screenshot as JSON matching
+/// [`events_response_schema`].
+///
+/// [`events_response_schema`]: crate::context::calendar::events_response_schema
+pub async fn extract_calendar_events(
+    runtime: &BrainRuntimeState,
+    api_key: String,
+    model: String,
+    image_path: Option<String>,
+    ocr_text: String,
+    today: chrono::NaiveDate,
+) -> Result<String, String> {
+    use crate::context::calendar::{events_response_schema, EVENTS_PROMPT};
+
+    let prompt = EVENTS_PROMPT.replace("{today}", &today.format("%A, %Y-%m-%d").to_string());
+    generate_structured(
+        runtime,
+        &api_key,
+        &model,
+        image_path,
+        &ocr_text,
+        prompt,
+        events_response_schema(),
+        "events",
+    )
+    .await
+}
+
+/// Fill an extraction profile's schema from a screenshot. Returns the raw
+/// JSON reply.
+pub async fn extract_structured(
+    runtime: &BrainRuntimeState,
+    api_key: String,
+    model: String,
+    image_path: Option<String>,
+    ocr_text: String,
+    profile: crate::context::extraction::ExtractionProfile,
+) -> Result<String, String> {
+    generate_structured(
+        runtime,
+        &api_key,
+        &model,
+        image_path,
+        &ocr_text,
+        profile.prompt(),
+        profile.response_schema(),
+        profile.id(),
+    )
+    .await
+}
+
+/// Reconstruct the code in a screenshot as JSON matching
+/// [`code_response_schema`].
+///
+/// [`code_response_schema`]: crate::context::code::code_response_schema
+pub async fn extract_code(
+    runtime: &BrainRuntimeState,
+    api_key: String,
+    model: String,
+    image_path: Option<String>,
+    ocr_text: String,
+) -> Result<String, String> {
+    use crate::context::code::{code_response_schema, CODE_PROMPT};
+
+    generate_structured(
+        runtime,
+        &api_key,
+        &model,
+        image_path,
+        &ocr_text,
+        CODE_PROMPT.to_string(),
+        code_response_schema(),
+        "code",
+    )
+    .await
+}
+
+/// JSON-mode call over an image and its OCR text. The OCR text, when there is
+/// any, helps with small print the model might misread.
+#[allow(clippy::too_many_arguments)]
+async fn generate_structured(
+    runtime: &BrainRuntimeState,
+    api_key: &str,
+    model: &str,
+    image_path: Option<String>,
+    ocr_text: &str,
+    mut prompt: String,
+    response_schema: serde_json::Value,
+    label: &str,
+) -> Result<String, String> {
+    let mut parts = Vec::new();
+    if let Some(image_path) = image_path {
+        let file_ref = crate::provider::gemini::attachments::ensure_file_uploaded(
+            api_key,
+            &image_path,
+            &runtime.provider_file_cache,
+        )
+        .await?;
+        parts.push(GeminiPart {
+            file_data: Some(GeminiFileData {
+                mime_type: file_ref.mime_type.clone(),
+                file_uri: file_ref.file_uri.clone(),
+            }),
+            ..Default::default()
+        });
+    }
+
+    if !ocr_text.trim().is_empty() {
+        prompt.push_str(&format!(
+            "\n\nText recognized in the screenshot:\n{}",
+            ocr_text
+        ));
+    }
+    parts.push(GeminiPart {
+        text: Some(prompt),
+        ..Default::default()
+    });
+
+    let generation_config = serde_json::json!({
+        "responseMimeType": "application/json",
+        "responseSchema": response_schema,
+    });
+    generate_content(api_key, model, parts, Some(generation_config), label).await
+}
+
+/// Send a single text prompt and return the first text part of the reply.
+async fn generate_plain_text(
+    api_key: &str,
+    model: &str,
+    prompt: String,
+    label: &str,
+) -> Result<String, String> {
+    let parts = vec![GeminiPart {
+        text: Some(prompt),
+        ..Default::default()
+    }];
+    generate_content(api_key, model, parts, None, label).await
+}
+
+/// Send one user turn and return the first text part of the reply.
+async fn generate_content(
+    api_key: &str,
+    model: &str,
+    parts: Vec<GeminiPart>,
+    generation_config: Option<serde_json::Value>,
+    label: &str,
+) -> Result<String, String> {
+    let client = reqwest::Client::new();
+    let endpoint = endpoint();
+    let url = endpoint.model_url(model, "generateContent", api_key);
+
+    let contents = vec![GeminiContent {
+        role: "user".to_string(),
+        parts,
+    }];
+
+    let request_body = GeminiRequest {
+        system_instruction: None,
+        contents,
+        generation_config,
+        tools: None,
+        tool_config: None,
+    };
+
+    let response = endpoint
+        .json_request(client.post(&url), &request_body)
+        .await?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send {} request: {}", label, e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Gemini API Error: {}", error_text));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {} response: {}", label, e))?;
+
+    let chunk: GeminiResponseChunk = serde_json::from_str(&body).map_err(|e| {
+        format!(
+            "Failed to parse {} response: {} - Body: {}",
+            label,
+            e,
+            &body[..body.len().min(500)]
+        )
+    })?;
+
+    let text = chunk
+        .candidates
+        .as_ref()
+        .and_then(|candidates| candidates.first())
+        .and_then(|first| first.content.as_ref())
+        .and_then(|content| content.parts.as_ref())
+        .and_then(|parts| parts.iter().find_map(|part| part.text.as_deref()))
+        .map(|text| text.trim().to_string())
+        .unwrap_or_default();
+    Ok(text)
+}
+
 /// Generate a lightweight text description of an image using the cheapest model.
 /// Returns a 2-3 sentence plain-text description of what the image shows.
 /// This runs in parallel with the main analysis and the result is stored
@@ -114,10 +386,8 @@ pub async fn generate_image_brief(
     println!("[ImageBrief] Generating brief using model: {}", lite_model);
 
     let client = reqwest::Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        lite_model, api_key
-    );
+    let endpoint = endpoint();
+    let url = endpoint.model_url(&lite_model, "generateContent", &api_key);
 
     // Upload image via Files API (reuses cache)
     let file_ref = crate::provider::gemini::attachments::ensure_file_uploaded(
@@ -154,9 +424,9 @@ pub async fn generate_image_brief(
         tool_config: None,
     };
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
+    let response = endpoint
+        .json_request(client.post(&url), &request_body)
+        .await?
         .send()
         .await
         .map_err(|e| format!("Failed to send image brief request: {}", e))?;
@@ -217,10 +487,8 @@ pub async fn compress_conversation(
     );
 
     let client = reqwest::Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        lite_model, api_key
-    );
+    let endpoint = endpoint();
+    let url = endpoint.model_url(lite_model, "generateContent", &api_key);
 
     let parts = vec![GeminiPart {
         text: Some(summary_prompt),
@@ -240,9 +508,9 @@ pub async fn compress_conversation(
         tool_config: None,
     };
 
-    let response = client
-        .post(&url)
-        .json(&request_body)
+    let response = endpoint
+        .json_request(client.post(&url), &request_body)
+        .await?
         .send()
         .await
         .map_err(|e| format!("Failed to send compress request: {}", e))?;
@@ -2,4 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod chat;
+pub mod fallback;
 pub mod generation;
+pub mod models;
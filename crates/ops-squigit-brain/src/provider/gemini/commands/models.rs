@@ -0,0 +1,253 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Listing of the models the user's key can reach, for the model picker.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::provider::gemini::client::endpoint;
+
+/// How long a fetched list is reused before the endpoint is queried again.
+pub const MODEL_LIST_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Model families that take images but do not chat (embeddings, image or
+/// speech generation, live audio).
+const NON_CHAT_MARKERS: &[&str] = &[
+    "embedding",
+    "aqa",
+    "image-generation",
+    "-image",
+    "tts",
+    "live",
+    "native-audio",
+];
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelInfo {
+    /// ID passed as `model` in requests, without the `models/` prefix.
+    pub id: String,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub input_token_limit: Option<u64>,
+    pub output_token_limit: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelListPage {
+    #[serde(default)]
+    models: Vec<RemoteModel>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RemoteModel {
+    name: String,
+    display_name: Option<String>,
+    description: Option<String>,
+    input_token_limit: Option<u64>,
+    output_token_limit: Option<u64>,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+}
+
+/// The Vertex AI publisher model list. It does not report generation
+/// methods, so every Gemini model is taken to chat.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PublisherModelListPage {
+    #[serde(default)]
+    publisher_models: Vec<PublisherModel>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PublisherModel {
+    /// `publishers/google/models/{model}`.
+    name: String,
+}
+
+impl From<PublisherModel> for RemoteModel {
+    fn from(model: PublisherModel) -> Self {
+        let id = model.name.rsplit('/').next().unwrap_or(&model.name);
+        RemoteModel {
+            name: format!("models/{}", id),
+            supported_generation_methods: vec!["generateContent".to_string()],
+            ..Default::default()
+        }
+    }
+}
+
+/// Fetched lists keyed by API key, so switching profiles does not show
+/// another key's models.
+#[derive(Default)]
+pub struct ModelListCache {
+    entries: HashMap<String, (Instant, Vec<ModelInfo>)>,
+}
+
+impl ModelListCache {
+    pub fn get(&self, api_key: &str) -> Option<Vec<ModelInfo>> {
+        self.entries
+            .get(api_key)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < MODEL_LIST_TTL)
+            .map(|(_, models)| models.clone())
+    }
+
+    pub fn insert(&mut self, api_key: &str, models: Vec<ModelInfo>) {
+        self.entries
+            .insert(api_key.to_string(), (Instant::now(), models));
+    }
+
+    /// Drop every entry and return how many there were.
+    pub fn clear(&mut self) -> usize {
+        let count = self.entries.len();
+        self.entries.clear();
+        count
+    }
+}
+
+/// Query the models endpoint, following pagination, and keep the
+/// vision-capable chat models.
+pub async fn list_gemini_models(api_key: &str) -> Result<Vec<ModelInfo>, String> {
+    let client = reqwest::Client::new();
+    let endpoint = endpoint();
+    let mut remote = Vec::new();
+    let mut page_token: Option<String> = None;
+
+    loop {
+        let mut query = vec![("pageSize", "1000")];
+        if endpoint.vertex.is_none() {
+            query.push(("key", api_key));
+        }
+        if let Some(token) = page_token.as_deref() {
+            query.push(("pageToken", token));
+        }
+
+        let request = client.get(endpoint.models_url()).query(&query);
+        let response = endpoint
+            .authorize(request)
+            .await?
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request to Gemini: {}", e))?;
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("Gemini API Error: {}", error_text));
+        }
+        let next_page_token = if endpoint.vertex.is_some() {
+            let page: PublisherModelListPage = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse model list: {}", e))?;
+            remote.extend(page.publisher_models.into_iter().map(RemoteModel::from));
+            page.next_page_token
+        } else {
+            let page: ModelListPage = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse model list: {}", e))?;
+            remote.extend(page.models);
+            page.next_page_token
+        };
+
+        match next_page_token.filter(|token| !token.is_empty()) {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(select_chat_models(remote))
+}
+
+/// Gemini models that stream `generateContent` replies. The endpoint does
+/// not report input modalities; every Gemini chat model accepts images, so
+/// the family name is the filter.
+fn select_chat_models(remote: Vec<RemoteModel>) -> Vec<ModelInfo> {
+    let mut models: Vec<ModelInfo> = remote
+        .into_iter()
+        .filter(|model| {
+            model
+                .supported_generation_methods
+                .iter()
+                .any(|method| method == "generateContent")
+        })
+        .filter_map(|model| {
+            let id = model.name.strip_prefix("models/").unwrap_or(&model.name);
+            if !id.starts_with("gemini-") || NON_CHAT_MARKERS.iter().any(|m| id.contains(m)) {
+                return None;
+            }
+            Some(ModelInfo {
+                id: id.to_string(),
+                display_name: model.display_name.unwrap_or_else(|| id.to_string()),
+                description: model.description.filter(|d| !d.trim().is_empty()),
+                input_token_limit: model.input_token_limit,
+                output_token_limit: model.output_token_limit,
+            })
+        })
+        .collect();
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+    models.dedup_by(|a, b| a.id == b.id);
+    models
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(name: &str, methods: &[&str]) -> RemoteModel {
+        RemoteModel {
+            name: name.to_string(),
+            supported_generation_methods: methods.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keeps_only_gemini_chat_models() {
+        let models = select_chat_models(vec![
+            remote(
+                "models/gemini-2.5-flash",
+                &["generateContent", "countTokens"],
+            ),
+            remote("models/gemini-embedding-001", &["embedContent"]),
+            remote("models/gemini-2.5-flash-preview-tts", &["generateContent"]),
+            remote("models/gemini-2.0-flash-live-001", &["bidiGenerateContent"]),
+            remote("models/imagen-4.0-generate-001", &["predict"]),
+            remote("models/gemini-2.0-flash-lite", &["generateContent"]),
+        ]);
+        let ids: Vec<&str> = models.iter().map(|model| model.id.as_str()).collect();
+        assert_eq!(ids, vec!["gemini-2.0-flash-lite", "gemini-2.5-flash"]);
+        assert_eq!(models[0].display_name, "gemini-2.0-flash-lite");
+    }
+
+    #[test]
+    fn publisher_models_are_named_like_gemini_api_models() {
+        let page: PublisherModelListPage = serde_json::from_str(
+            r#"{"publisherModels": [
+                {"name": "publishers/google/models/gemini-2.5-flash"},
+                {"name": "publishers/google/models/text-embedding-005"}
+            ]}"#,
+        )
+        .unwrap();
+        let models = select_chat_models(
+            page.publisher_models
+                .into_iter()
+                .map(RemoteModel::from)
+                .collect(),
+        );
+        let ids: Vec<&str> = models.iter().map(|model| model.id.as_str()).collect();
+        assert_eq!(ids, vec!["gemini-2.5-flash"]);
+    }
+
+    #[test]
+    fn cache_is_per_key() {
+        let mut cache = ModelListCache::default();
+        cache.insert("key-a", Vec::new());
+        assert_eq!(cache.get("key-a"), Some(Vec::new()));
+        assert_eq!(cache.get("key-b"), None);
+    }
+}
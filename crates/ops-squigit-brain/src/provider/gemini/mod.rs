@@ -3,7 +3,9 @@
 
 pub mod agent;
 pub mod attachments;
+pub mod client;
 pub mod commands;
 pub mod transport;
+pub mod vertex;
 
 pub const DEFAULT_MODEL: &str = "gemini-3.1-flash-lite-preview";
@@ -3,6 +3,7 @@
 
 use futures_util::StreamExt;
 use crate::events::BrainEventSink;
+use crate::provider::gemini::client::GeminiEndpoint;
 
 use super::types::{GeminiEvent, GeminiFunctionCall, GeminiRequest, GeminiResponseChunk};
 
@@ -18,13 +19,17 @@ pub(crate) struct StreamIterationResult {
 pub(crate) async fn stream_request_iteration(
     sink: &dyn BrainEventSink,
     client: &reqwest::Client,
+    endpoint: &GeminiEndpoint,
     url: &str,
     request_body: &GeminiRequest,
     channel_id: &str,
     cancel_token: &tokio_util::sync::CancellationToken,
 ) -> Result<StreamIterationResult, String> {
+    let request = endpoint
+        .json_request(client.post(url), request_body)
+        .await?;
     let response_result = tokio::select! {
-        res = client.post(url).json(request_body).send() => res.map_err(|e| format!("Failed to send request to Gemini: {}", e)),
+        res = request.send() => res.map_err(|e| format!("Failed to send request to Gemini: {}", e)),
         _ = cancel_token.cancelled() => Err("CANCELLED".to_string()),
     };
     let response = response_result?;
@@ -43,7 +43,7 @@ pub(crate) struct GeminiContent {
     pub(crate) parts: Vec<GeminiPart>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct GeminiRequest {
     #[serde(rename = "systemInstruction", skip_serializing_if = "Option::is_none")]
     pub(crate) system_instruction: Option<GeminiContent>,
@@ -56,6 +56,16 @@ pub(crate) struct GeminiRequest {
     pub(crate) tool_config: Option<serde_json::Value>,
 }
 
+/// Fully assembled request returned by a dry run instead of being sent.
+/// Nothing is uploaded: attachments are referenced by the `file://` URI of
+/// their local copy. The API key is never included. It can be edited and
+/// sent with `send_gemini_prompt_preview`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiPromptPreview {
+    pub model: String,
+    pub request: serde_json::Value,
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct GeminiResponseCandidate {
     pub(crate) content: Option<GeminiResponseContent>,
@@ -105,4 +115,10 @@ pub enum GeminiEvent {
         result: serde_json::Value,
         message: String,
     },
+    /// `from` was unavailable; the reply is being regenerated with `to`.
+    ModelFallback {
+        from: String,
+        to: String,
+        reason: String,
+    },
 }
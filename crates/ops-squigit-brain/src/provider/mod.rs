@@ -1,7 +1,10 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod anthropic;
 pub mod attachments;
 pub mod gemini;
+pub mod openai;
+pub(crate) mod turn;
 
 pub const DEFAULT_MODEL: &str = gemini::DEFAULT_MODEL;
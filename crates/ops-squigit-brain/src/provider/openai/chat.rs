@@ -0,0 +1,241 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streaming chat over `/chat/completions`, with the image inlined as a
+//! data URL.
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use super::OpenAiCompatibleConfig;
+use crate::events::BrainEventSink;
+use crate::provider::gemini::agent::request_control::{
+    register_request, remove_request, GeminiRequestControl,
+};
+use crate::provider::gemini::transport::types::GeminiEvent;
+use crate::provider::turn::{system_instruction, user_parts, UserPart};
+use crate::runtime::BrainRuntimeState;
+use crate::service::StreamChatRequest;
+
+const MAX_OUTPUT_TOKENS: usize = 2048;
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    max_tokens: usize,
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: &'static str,
+    content: Vec<ContentPart>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Debug, Serialize)]
+struct ImageUrl {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    delta: Option<ChunkDelta>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkDelta {
+    content: Option<String>,
+}
+
+/// One line of the server-sent event stream.
+#[derive(Debug, PartialEq)]
+enum StreamLine {
+    Token(String),
+    Done,
+    Skip,
+}
+
+/// Stream a reply from `request.model` as `Token` events on the request's
+/// channel. `response_language` is the validated tag.
+pub async fn stream_openai_chat(
+    runtime: &BrainRuntimeState,
+    sink: &dyn BrainEventSink,
+    config: &OpenAiCompatibleConfig,
+    request: &StreamChatRequest,
+    response_language: Option<&str>,
+) -> Result<(), String> {
+    let request_control = GeminiRequestControl::new();
+    register_request(runtime, request.channel_id.clone(), request_control.clone()).await;
+
+    let result = async {
+        let system = system_instruction(request, response_language)?;
+        let body = ChatCompletionRequest {
+            model: request.model.clone(),
+            messages: vec![
+                ChatMessage {
+                    role: "system",
+                    content: vec![ContentPart::Text { text: system }],
+                },
+                ChatMessage {
+                    role: "user",
+                    content: user_parts(request)?.into_iter().map(content_part).collect(),
+                },
+            ],
+            max_tokens: MAX_OUTPUT_TOKENS,
+            stream: true,
+        };
+
+        let cancel_token = &request_control.cancel_token;
+        let send = config
+            .authorize(reqwest::Client::new().post(config.endpoint("chat/completions")))
+            .json(&body)
+            .send();
+        let response = tokio::select! {
+            res = send => res.map_err(|e| format!("Failed to reach {}: {}", config.base_url, e))?,
+            _ = cancel_token.cancelled() => return Err("CANCELLED".to_string()),
+        };
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("OpenAI-compatible API Error: {}", error_text));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        loop {
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = cancel_token.cancelled() => return Err("CANCELLED".to_string()),
+            };
+            match chunk {
+                Some(Ok(chunk)) => buffer.push_str(&String::from_utf8_lossy(&chunk)),
+                Some(Err(e)) => return Err(format!("Stream error: {}", e)),
+                None => return Ok(()),
+            }
+            while let Some(idx) = buffer.find('\n') {
+                let line: String = buffer.drain(..idx + 1).collect();
+                match parse_stream_line(&line) {
+                    StreamLine::Token(token) => {
+                        sink.emit(&request.channel_id, GeminiEvent::Token { token })
+                    }
+                    StreamLine::Done => return Ok(()),
+                    StreamLine::Skip => {}
+                }
+            }
+        }
+    }
+    .await;
+
+    remove_request(runtime, &request.channel_id).await;
+    result
+}
+
+fn content_part(part: UserPart) -> ContentPart {
+    match part {
+        UserPart::Image(image) => ContentPart::ImageUrl {
+            image_url: ImageUrl {
+                url: image.data_url(),
+            },
+        },
+        UserPart::Text(text) => ContentPart::Text { text },
+    }
+}
+
+fn parse_stream_line(line: &str) -> StreamLine {
+    let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+        return StreamLine::Skip;
+    };
+    if data == "[DONE]" {
+        return StreamLine::Done;
+    }
+    let Ok(chunk) = serde_json::from_str::<ChatCompletionChunk>(data) else {
+        return StreamLine::Skip;
+    };
+    let Some(choice) = chunk.choices.into_iter().next() else {
+        return StreamLine::Skip;
+    };
+    match choice.delta.and_then(|delta| delta.content) {
+        Some(token) if !token.is_empty() => StreamLine::Token(token),
+        _ if choice.finish_reason.is_some() => StreamLine::Done,
+        _ => StreamLine::Skip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_lines_yield_tokens_until_done() {
+        assert_eq!(
+            parse_stream_line(r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#),
+            StreamLine::Token("Hel".to_string())
+        );
+        assert_eq!(
+            parse_stream_line(r#"data:{"choices":[{"delta":{"role":"assistant"}}]}"#),
+            StreamLine::Skip
+        );
+        assert_eq!(
+            parse_stream_line(r#"data: {"choices":[{"delta":{},"finish_reason":"stop"}]}"#),
+            StreamLine::Done
+        );
+        assert_eq!(parse_stream_line("data: [DONE]\n"), StreamLine::Done);
+        assert_eq!(parse_stream_line(": keep-alive"), StreamLine::Skip);
+        assert_eq!(parse_stream_line("data: {not json"), StreamLine::Skip);
+    }
+
+    #[test]
+    fn request_uses_content_parts() {
+        let body = ChatCompletionRequest {
+            model: "llava".to_string(),
+            messages: vec![ChatMessage {
+                role: "user",
+                content: vec![
+                    ContentPart::ImageUrl {
+                        image_url: ImageUrl {
+                            url: "data:image/png;base64,AA==".to_string(),
+                        },
+                    },
+                    ContentPart::Text {
+                        text: "What is this?".to_string(),
+                    },
+                ],
+            }],
+            max_tokens: MAX_OUTPUT_TOKENS,
+            stream: true,
+        };
+        assert_eq!(
+            serde_json::to_value(&body).unwrap()["messages"][0]["content"],
+            serde_json::json!([
+                {"type": "image_url", "image_url": {"url": "data:image/png;base64,AA=="}},
+                {"type": "text", "text": "What is this?"},
+            ])
+        );
+    }
+
+    #[test]
+    fn endpoints_join_the_base_url() {
+        let config = OpenAiCompatibleConfig {
+            base_url: "http://localhost:1234/v1/".to_string(),
+            api_key: None,
+        };
+        assert_eq!(
+            config.endpoint("chat/completions"),
+            "http://localhost:1234/v1/chat/completions"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Servers speaking the OpenAI chat completions API: Ollama, LM Studio,
+//! vLLM, or OpenAI itself.
+
+pub mod chat;
+pub mod models;
+
+/// Ollama's OpenAI-compatible endpoint.
+pub const DEFAULT_BASE_URL: &str = "http://localhost:11434/v1";
+
+#[derive(Debug, Clone)]
+pub struct OpenAiCompatibleConfig {
+    /// API root including the version, e.g. `http://localhost:1234/v1`.
+    pub base_url: String,
+    /// Sent as a bearer token. Local servers usually need none.
+    pub api_key: Option<String>,
+}
+
+impl OpenAiCompatibleConfig {
+    pub(crate) fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim().trim_end_matches('/'), path)
+    }
+
+    pub(crate) fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.api_key.as_deref().map(str::trim) {
+            Some(key) if !key.is_empty() => request.bearer_auth(key),
+            _ => request,
+        }
+    }
+}
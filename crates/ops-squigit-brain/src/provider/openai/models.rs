@@ -0,0 +1,57 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Models served by an OpenAI-compatible endpoint, for the model picker.
+
+use serde::Deserialize;
+
+use super::OpenAiCompatibleConfig;
+use crate::provider::gemini::commands::models::ModelInfo;
+
+#[derive(Debug, Default, Deserialize)]
+struct ModelListPage {
+    #[serde(default)]
+    data: Vec<RemoteModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteModel {
+    id: String,
+}
+
+/// Query `/models`. Servers do not say which models take images, so all
+/// are listed.
+pub async fn list_openai_models(config: &OpenAiCompatibleConfig) -> Result<Vec<ModelInfo>, String> {
+    let response = config
+        .authorize(reqwest::Client::new().get(config.endpoint("models")))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", config.base_url, e))?;
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("OpenAI-compatible API Error: {}", error_text));
+    }
+    let page: ModelListPage = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse model list: {}", e))?;
+    Ok(to_model_infos(page))
+}
+
+fn to_model_infos(page: ModelListPage) -> Vec<ModelInfo> {
+    let mut models: Vec<ModelInfo> = page
+        .data
+        .into_iter()
+        .filter(|model| !model.id.trim().is_empty())
+        .map(|model| ModelInfo {
+            display_name: model.id.clone(),
+            id: model.id,
+            description: None,
+            input_token_limit: None,
+            output_token_limit: None,
+        })
+        .collect();
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+    models.dedup_by(|a, b| a.id == b.id);
+    models
+}
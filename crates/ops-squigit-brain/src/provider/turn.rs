@@ -0,0 +1,135 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prompt for the providers without Gemini's tools and uploads.
+//!
+//! Assembled like Gemini's from the same templates, with the image inlined.
+//! Web tools, file uploads and animation frames are Gemini-only:
+//! attachments stay as their mentions in the text. Each provider maps the
+//! parts onto its own message format.
+
+use base64::Engine;
+
+use crate::provider::gemini::agent::tool_orchestrator::build_system_instruction_with_tool_policy;
+use crate::provider::gemini::attachments::mime_from_extension;
+use crate::provider::gemini::commands::chat::{
+    load_active_ocr_regions, load_plugin_notes, load_web_source,
+};
+use crate::service::StreamChatRequest;
+
+/// A piece of the user turn, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum UserPart {
+    Image(InlineImage),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InlineImage {
+    pub mime_type: String,
+    /// Standard base64.
+    pub data: String,
+}
+
+impl InlineImage {
+    pub fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.mime_type, self.data)
+    }
+}
+
+/// The system instruction, without the tool policy. `response_language`
+/// is the validated tag.
+pub(crate) fn system_instruction(
+    request: &StreamChatRequest,
+    response_language: Option<&str>,
+) -> Result<String, String> {
+    build_system_instruction_with_tool_policy(
+        request.user_name.as_deref().unwrap_or(""),
+        request.user_email.as_deref().unwrap_or(""),
+        request.image_brief.as_deref().unwrap_or(""),
+        response_language,
+        &request.glossary,
+        false,
+    )
+}
+
+/// The user turn: the images and the analysis prompt on the initial turn,
+/// the conversation frame on later ones, then the user's message.
+pub(crate) fn user_parts(request: &StreamChatRequest) -> Result<Vec<UserPart>, String> {
+    let chat_id = request.chat_id.as_deref();
+    let mut parts = Vec::new();
+
+    if request.is_initial_turn {
+        let paths = request.initial_image_paths();
+        if paths.is_empty() {
+            return Err("image_path required for initial turn".to_string());
+        }
+        for path in &paths {
+            parts.push(UserPart::Image(read_image(path)?));
+        }
+
+        parts.push(UserPart::Text(
+            crate::context::builder::build_initial_system_prompt()?,
+        ));
+        if let Some(instruction) = request
+            .user_instruction
+            .as_deref()
+            .filter(|instruction| !instruction.trim().is_empty())
+        {
+            parts.push(UserPart::Text(format!(
+                "\n## User's Default Instruction\n{}",
+                instruction
+            )));
+        }
+        let ocr_regions = load_active_ocr_regions(chat_id).unwrap_or_default();
+        let mut blocks = vec![crate::context::builder::build_ocr_confidence_note(
+            &ops_chat_storage::OcrConfidenceSummary::from_regions(&ocr_regions),
+        )];
+        if request.include_ocr_in_prompt {
+            blocks.push(crate::context::builder::build_ocr_transcript_block(
+                &ocr_regions,
+                request.redact_pii,
+            ));
+        }
+        blocks.push(
+            load_web_source(chat_id)
+                .map(|source| crate::context::builder::build_web_source_block(&source)),
+        );
+        blocks.push(crate::context::builder::build_plugin_context_block(
+            &load_plugin_notes(chat_id),
+        ));
+        parts.extend(blocks.into_iter().flatten().map(UserPart::Text));
+    } else {
+        let image_description = request
+            .image_description
+            .as_deref()
+            .ok_or("image_description required for subsequent turns")?;
+        parts.push(UserPart::Text(crate::context::builder::build_turn_context(
+            image_description,
+            request.user_first_msg.as_deref().unwrap_or_default(),
+            request.history_log.as_deref().unwrap_or_default(),
+            request.rolling_summary.as_deref().unwrap_or_default(),
+        )));
+    }
+
+    if !request.user_message.trim().is_empty() {
+        parts.push(UserPart::Text(request.user_message.clone()));
+    }
+    Ok(parts)
+}
+
+/// Read an image from a CAS or attachment path, without its metadata.
+fn read_image(path: &str) -> Result<InlineImage, String> {
+    let resolved = crate::provider::attachments::resolve_attachment_path_buf(path)?;
+    let bytes = std::fs::read(&resolved).map_err(|e| format!("Failed to read image: {}", e))?;
+    // Local copies keep their metadata; what is sent never carries it.
+    let bytes = ops_chat_storage::without_image_metadata(bytes);
+    let extension = resolved
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+    Ok(InlineImage {
+        mime_type: mime_from_extension(extension).to_string(),
+        data: base64::engine::general_purpose::STANDARD.encode(bytes),
+    })
+}
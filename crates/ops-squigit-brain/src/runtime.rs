@@ -2,26 +2,39 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::provider::gemini::agent::request_control::GeminiRequestControl;
-use crate::provider::gemini::attachments::GeminiFileRef;
+use crate::provider::gemini::attachments::ProviderFileCache;
+use crate::provider::gemini::commands::models::ModelListCache;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 #[derive(Clone)]
 pub struct BrainRuntimeState {
-    pub provider_file_cache: Arc<Mutex<HashMap<String, GeminiFileRef>>>,
+    pub provider_file_cache: Arc<Mutex<ProviderFileCache>>,
     pub active_requests: Arc<Mutex<HashMap<String, GeminiRequestControl>>>,
+    pub model_list_cache: Arc<Mutex<ModelListCache>>,
 }
 
 impl BrainRuntimeState {
     pub fn new() -> Self {
         Self {
-            provider_file_cache: Arc::new(Mutex::new(HashMap::new())),
+            provider_file_cache: Arc::new(Mutex::new(ProviderFileCache::default())),
             active_requests: Arc::new(Mutex::new(HashMap::new())),
+            model_list_cache: Arc::new(Mutex::new(ModelListCache::default())),
         }
     }
 }
 
+impl BrainRuntimeState {
+    /// Drop cached provider file handles and model lists, e.g. under memory
+    /// pressure. Returns the number of entries dropped.
+    pub async fn clear_caches(&self) -> usize {
+        let files = self.provider_file_cache.lock().await.clear();
+        let models = self.model_list_cache.lock().await.clear();
+        files + models
+    }
+}
+
 impl Default for BrainRuntimeState {
     fn default() -> Self {
         Self::new()
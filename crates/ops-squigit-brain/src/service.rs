@@ -1,12 +1,27 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::audit::audited;
 use crate::context::builder::format_history_log;
-use crate::provider::gemini::transport::types::GeminiEvent;
-use crate::events::BrainEventSink;
+use crate::context::calendar::{parse_events, CalendarEvent};
+use crate::context::code::{format_snippet, parse_code_reply, CodeSnippet};
+use crate::context::extraction::{parse_extraction, ExtractionProfile};
+use crate::context::titles::TitleBackfillProgress;
+use crate::events::{BrainEventSink, CollectingEventSink, NoopEventSink};
+use crate::provider::anthropic::AnthropicConfig;
+use crate::provider::gemini::attachments::DEFAULT_ANIMATION_FRAMES;
+use crate::provider::gemini::commands::fallback::{is_model_unavailable, model_chain};
+use crate::provider::gemini::commands::models::ModelInfo;
+use crate::provider::gemini::transport::types::{GeminiEvent, GeminiPromptPreview};
+use crate::provider::openai::OpenAiCompatibleConfig;
 use crate::runtime::BrainRuntimeState;
-use ops_chat_storage::{ChatData, ChatMessage, ChatMetadata, StoredImage};
-use std::sync::{Arc, Mutex};
+use ops_chat_storage::{
+    ocr_text, ChatData, ChatMessage, ChatMetadata, ChatStorage, Extraction, OcrRegion,
+    OcrTextLayout, StoredImage,
+};
+use ops_profile_store::GlossaryEntry;
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
 
 #[derive(Debug, Clone)]
 pub struct StreamChatRequest {
@@ -14,6 +29,8 @@ pub struct StreamChatRequest {
     pub model: String,
     pub is_initial_turn: bool,
     pub image_path: Option<String>,
+    /// More images for the initial turn, sent after `image_path` in order.
+    pub image_paths: Vec<String>,
     pub image_description: Option<String>,
     pub user_first_msg: Option<String>,
     pub history_log: Option<String>,
@@ -25,6 +42,37 @@ pub struct StreamChatRequest {
     pub user_email: Option<String>,
     pub user_instruction: Option<String>,
     pub image_brief: Option<String>,
+    /// BCP 47 tag from [`RESPONSE_LANGUAGES`] the reply must be written in.
+    ///
+    /// [`RESPONSE_LANGUAGES`]: crate::context::builder::RESPONSE_LANGUAGES
+    pub response_language: Option<String>,
+    /// The profile's glossary, listed in the system instruction.
+    pub glossary: Vec<GlossaryEntry>,
+    /// On the initial turn, append the chat's stored OCR transcript.
+    pub include_ocr_in_prompt: bool,
+    /// Replace personal data in the OCR transcript with placeholders.
+    pub redact_pii: bool,
+    /// Frames sampled from animated GIF/APNG images and sent in their
+    /// place, up to [`MAX_ANIMATION_FRAMES`]. Below 2 sends them as is.
+    ///
+    /// [`MAX_ANIMATION_FRAMES`]: crate::provider::gemini::attachments::MAX_ANIMATION_FRAMES
+    pub animation_frames: usize,
+    /// Models to retry with, in order, when `model` is missing or overloaded.
+    pub fallback_models: Vec<String>,
+}
+
+impl StreamChatRequest {
+    /// `image_path` and `image_paths`, without blanks and repeats.
+    pub(crate) fn initial_image_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = Vec::new();
+        for path in self.image_path.iter().chain(&self.image_paths) {
+            let path = path.trim();
+            if !path.is_empty() && !paths.iter().any(|existing| existing == path) {
+                paths.push(path.to_string());
+            }
+        }
+        paths
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +82,67 @@ pub struct GenerateChatTitleRequest {
     pub prompt_context: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct CleanTranscriptRequest {
+    pub api_key: String,
+    pub model: String,
+    pub transcript: String,
+}
+
+/// Translate selected OCR regions of a chat without adding a chat turn.
+#[derive(Debug, Clone)]
+pub struct TranslateOcrRegionsRequest {
+    pub api_key: String,
+    pub model: String,
+    pub chat_id: String,
+    /// Indexes into the stored OCR regions of the chat's OCR model.
+    pub region_ids: Vec<usize>,
+    /// BCP 47 tag from [`RESPONSE_LANGUAGES`].
+    ///
+    /// [`RESPONSE_LANGUAGES`]: crate::context::builder::RESPONSE_LANGUAGES
+    pub target_lang: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtractEventsRequest {
+    pub api_key: String,
+    pub model: String,
+    pub chat_id: String,
+    /// The user's local date, for resolving relative dates.
+    pub today: chrono::NaiveDate,
+    /// Replace personal data in the OCR text with placeholders.
+    pub redact_pii: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtractStructuredRequest {
+    pub api_key: String,
+    pub model: String,
+    pub chat_id: String,
+    pub profile: ExtractionProfile,
+    pub redact_pii: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeFromScreenshotRequest {
+    pub api_key: String,
+    pub model: String,
+    pub chat_id: String,
+    /// Run the code through a local formatter such as rustfmt or prettier.
+    pub format: bool,
+    pub redact_pii: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrTranslation {
+    pub region_ids: Vec<usize>,
+    pub target_lang: String,
+    /// The selected regions' text in reading order.
+    pub original: String,
+    pub translation: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct GenerateImageBriefRequest {
     pub api_key: String,
@@ -48,6 +157,19 @@ pub struct CompressConversationRequest {
     pub history_to_compress: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct BackfillChatTitlesRequest {
+    pub api_key: String,
+    pub model: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListModelsRequest {
+    pub api_key: String,
+    /// Skip the cache and query the endpoint.
+    pub refresh: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct AnalyzeImageRequest {
     pub api_key: String,
@@ -59,6 +181,18 @@ pub struct AnalyzeImageRequest {
     pub user_email: Option<String>,
     pub user_instruction: Option<String>,
     pub ocr_lang: Option<String>,
+    /// OCR already run on the image with the `ocr_lang` model. Saved with
+    /// the chat and included in the prompt.
+    pub ocr_regions: Option<Vec<OcrRegion>>,
+    /// Replace personal data in `ocr_regions` with placeholders in the prompt.
+    pub redact_pii: bool,
+    pub response_language: Option<String>,
+    pub glossary: Vec<GlossaryEntry>,
+    pub fallback_models: Vec<String>,
+    /// Fixed chat title; generated from the reply when unset.
+    pub title: Option<String>,
+    /// Also generate the image brief, an extra model call.
+    pub generate_brief: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +201,8 @@ pub struct AnalyzeImageResult {
     pub image: StoredImage,
     pub assistant_message: String,
     pub image_brief: Option<String>,
+    /// The model that wrote `assistant_message`.
+    pub model: String,
 }
 
 #[derive(Debug, Clone)]
@@ -78,6 +214,26 @@ pub struct PromptChatRequest {
     pub channel_id: String,
     pub user_name: Option<String>,
     pub user_email: Option<String>,
+    pub response_language: Option<String>,
+    pub glossary: Vec<GlossaryEntry>,
+    pub fallback_models: Vec<String>,
+}
+
+/// Replays the unanswered last user turn of a chat, e.g. after the app quit
+/// mid-generation.
+#[derive(Debug, Clone)]
+pub struct ResumeChatRequest {
+    pub api_key: String,
+    pub model: String,
+    pub chat_id: String,
+    pub channel_id: String,
+    pub user_name: Option<String>,
+    pub user_email: Option<String>,
+    pub response_language: Option<String>,
+    pub glossary: Vec<GlossaryEntry>,
+    pub include_ocr_in_prompt: bool,
+    pub redact_pii: bool,
+    pub fallback_models: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -85,6 +241,8 @@ pub struct PromptChatResult {
     pub chat_id: String,
     pub assistant_message: String,
     pub normalized_user_message: String,
+    /// The model that wrote `assistant_message`.
+    pub model: String,
 }
 
 pub struct BrainService {
@@ -102,70 +260,432 @@ impl BrainService {
         &self.runtime
     }
 
+    /// Stream a reply, falling back through `fallback_models` when the
+    /// model is unavailable. Returns the model that answered.
     pub async fn stream_chat(
         &self,
         sink: &dyn BrainEventSink,
         request: StreamChatRequest,
-    ) -> Result<(), String> {
-        crate::provider::gemini::commands::chat::stream_gemini_chat_v2(
+    ) -> Result<String, String> {
+        self.run_chat(sink, request, false)
+            .await
+            .map(|(_, model)| model)
+    }
+
+    /// Assemble the exact request `stream_chat` would send (system prompt,
+    /// image refs, attachments, history context) without calling the model.
+    pub async fn preview_chat(
+        &self,
+        request: StreamChatRequest,
+    ) -> Result<GeminiPromptPreview, String> {
+        self.run_chat(&NoopEventSink, request, true)
+            .await?
+            .0
+            .ok_or_else(|| "Prompt preview was not produced".to_string())
+    }
+
+    /// Send a request from [`Self::preview_chat`], as shown or edited, in
+    /// one generation call without tools. Returns the model.
+    pub async fn send_prompt_preview(
+        &self,
+        sink: &dyn BrainEventSink,
+        api_key: String,
+        channel_id: String,
+        preview: GeminiPromptPreview,
+    ) -> Result<String, String> {
+        let model = preview.model.clone();
+        let prompt = preview.request.to_string();
+        let call = crate::provider::gemini::commands::chat::send_gemini_prompt_preview(
             &self.runtime,
             sink,
-            request.api_key,
-            request.model,
-            request.is_initial_turn,
-            request.image_path,
-            request.image_description,
-            request.user_first_msg,
-            request.history_log,
-            request.rolling_summary,
-            request.user_message,
-            request.channel_id,
-            request.chat_id,
-            request.user_name,
-            request.user_email,
-            request.user_instruction,
-            request.image_brief,
-        )
-        .await
+            api_key,
+            channel_id,
+            preview,
+        );
+        audited("chat", &model, &prompt, call).await?;
+        Ok(model)
+    }
+
+    /// Stream a reply from an OpenAI-compatible server, on the same event
+    /// channel as [`Self::stream_chat`]. There is no fallback: the
+    /// fallback models name Gemini models. Returns the model.
+    pub async fn stream_openai_chat(
+        &self,
+        sink: &dyn BrainEventSink,
+        config: &OpenAiCompatibleConfig,
+        request: StreamChatRequest,
+    ) -> Result<String, String> {
+        let response_language = checked_response_language(&request)?;
+        let call = crate::provider::openai::chat::stream_openai_chat(
+            &self.runtime,
+            sink,
+            config,
+            &request,
+            response_language.as_deref(),
+        );
+        audited("chat", &request.model, &request.user_message, call).await?;
+        Ok(request.model)
+    }
+
+    /// Stream a reply from Claude, on the same event channel as
+    /// [`Self::stream_chat`]. No fallback, as for OpenAI-compatible
+    /// servers. Returns the model.
+    pub async fn stream_claude_chat(
+        &self,
+        sink: &dyn BrainEventSink,
+        config: &AnthropicConfig,
+        request: StreamChatRequest,
+    ) -> Result<String, String> {
+        let response_language = checked_response_language(&request)?;
+        let call = crate::provider::anthropic::chat::stream_claude_chat(
+            &self.runtime,
+            sink,
+            config,
+            &request,
+            response_language.as_deref(),
+        );
+        audited("chat", &request.model, &request.user_message, call).await?;
+        Ok(request.model)
+    }
+
+    async fn run_chat(
+        &self,
+        sink: &dyn BrainEventSink,
+        request: StreamChatRequest,
+        dry_run: bool,
+    ) -> Result<(Option<GeminiPromptPreview>, String), String> {
+        let response_language = checked_response_language(&request)?;
+
+        // A preview only describes the request for the chosen model.
+        let models = if dry_run {
+            vec![request.model.clone()]
+        } else {
+            model_chain(&request.model, &request.fallback_models)
+        };
+        for (index, model) in models.iter().enumerate() {
+            let call = crate::provider::gemini::commands::chat::stream_gemini_chat_v2(
+                &self.runtime,
+                sink,
+                request.api_key.clone(),
+                model.clone(),
+                request.is_initial_turn,
+                request.initial_image_paths(),
+                request.image_description.clone(),
+                request.user_first_msg.clone(),
+                request.history_log.clone(),
+                request.rolling_summary.clone(),
+                request.user_message.clone(),
+                request.channel_id.clone(),
+                request.chat_id.clone(),
+                request.user_name.clone(),
+                request.user_email.clone(),
+                request.user_instruction.clone(),
+                request.image_brief.clone(),
+                response_language.clone(),
+                request.glossary.clone(),
+                request.include_ocr_in_prompt,
+                request.redact_pii,
+                request.animation_frames,
+                dry_run,
+            );
+            let result = if dry_run {
+                call.await
+            } else {
+                audited("chat", model, &request.user_message, call).await
+            };
+            let error = match result {
+                Ok(preview) => return Ok((preview, model.clone())),
+                Err(error) => error,
+            };
+            let Some(next) = models
+                .get(index + 1)
+                .filter(|_| is_model_unavailable(&error))
+            else {
+                return Err(error);
+            };
+            log::warn!("Model {} unavailable, retrying with {}", model, next);
+            sink.emit(&request.channel_id, GeminiEvent::Reset);
+            sink.emit(
+                &request.channel_id,
+                GeminiEvent::ModelFallback {
+                    from: model.clone(),
+                    to: next.clone(),
+                    reason: error,
+                },
+            );
+        }
+        Err("No model to run".to_string())
     }
 
     pub async fn generate_chat_title(
         &self,
         request: GenerateChatTitleRequest,
     ) -> Result<String, String> {
-        crate::provider::gemini::commands::generation::generate_chat_title(
+        let call = crate::provider::gemini::commands::generation::generate_chat_title(
+            request.api_key,
+            request.model.clone(),
+            request.prompt_context.clone(),
+        );
+        audited("chat_title", &request.model, &request.prompt_context, call).await
+    }
+
+    pub async fn clean_transcript(
+        &self,
+        request: CleanTranscriptRequest,
+    ) -> Result<String, String> {
+        let call = crate::provider::gemini::commands::generation::clean_transcript(
             request.api_key,
-            request.model,
-            request.prompt_context,
+            request.model.clone(),
+            request.transcript.clone(),
+        );
+        audited(
+            "clean_transcript",
+            &request.model,
+            &request.transcript,
+            call,
         )
         .await
     }
 
+    /// Translate the selected OCR regions of a chat. Nothing is stored.
+    pub async fn translate_ocr_regions(
+        &self,
+        request: TranslateOcrRegionsRequest,
+    ) -> Result<OcrTranslation, String> {
+        let target_lang = request.target_lang.trim().to_string();
+        let language = crate::context::builder::response_language_name(&target_lang)
+            .ok_or_else(|| format!("ERR_UNSUPPORTED_TARGET_LANGUAGE: {}", target_lang))?;
+        if request.region_ids.is_empty() {
+            return Err("ERR_NO_OCR_REGIONS_SELECTED".to_string());
+        }
+
+        let storage = crate::context::media::get_active_storage()?;
+        let chat = storage
+            .load_chat(&request.chat_id)
+            .map_err(|e| e.to_string())?;
+        let model_id = chat.metadata.ocr_lang.ok_or("ERR_NO_OCR_DATA")?;
+        let regions = storage
+            .get_ocr_data(&request.chat_id, &model_id)
+            .map_err(|e| e.to_string())?
+            .ok_or("ERR_NO_OCR_DATA")?;
+        let selected = request
+            .region_ids
+            .iter()
+            .map(|&id| {
+                regions
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| format!("ERR_INVALID_OCR_REGION: {}", id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let original = ocr_text(&selected, OcrTextLayout::Plain);
+        if original.is_empty() {
+            return Err("ERR_NO_OCR_TEXT".to_string());
+        }
+
+        let call = crate::provider::gemini::commands::generation::translate_ocr_text(
+            request.api_key,
+            request.model.clone(),
+            original.clone(),
+            language,
+        );
+        let translation = audited("translate", &request.model, &original, call).await?;
+        Ok(OcrTranslation {
+            region_ids: request.region_ids,
+            target_lang,
+            original,
+            translation,
+        })
+    }
+
+    /// Find calendar events in a chat's screenshot and its OCR text.
+    pub async fn extract_events(
+        &self,
+        request: ExtractEventsRequest,
+    ) -> Result<Vec<CalendarEvent>, String> {
+        let (image_path, text) =
+            screenshot_context(&request.chat_id, OcrTextLayout::Plain, request.redact_pii)?;
+        let call = crate::provider::gemini::commands::generation::extract_calendar_events(
+            &self.runtime,
+            request.api_key,
+            request.model.clone(),
+            image_path,
+            text.clone(),
+            request.today,
+        );
+        let reply = audited("extract_events", &request.model, &text, call).await?;
+        parse_events(&reply)
+    }
+
+    /// Fill an extraction profile from a chat's screenshot and store the
+    /// result on the chat, replacing an earlier extraction of the same kind.
+    pub async fn extract_structured(
+        &self,
+        request: ExtractStructuredRequest,
+    ) -> Result<Extraction, String> {
+        let (image_path, text) =
+            screenshot_context(&request.chat_id, OcrTextLayout::Plain, request.redact_pii)?;
+        let call = crate::provider::gemini::commands::generation::extract_structured(
+            &self.runtime,
+            request.api_key,
+            request.model.clone(),
+            image_path,
+            text.clone(),
+            request.profile,
+        );
+        let reply = audited("extract_structured", &request.model, &text, call).await?;
+
+        let extraction = Extraction {
+            kind: request.profile.id().to_string(),
+            data: parse_extraction(request.profile, &reply)?,
+            model: Some(request.model),
+            created_at: chrono::Utc::now(),
+        };
+        crate::context::media::get_active_storage()?
+            .save_extraction(&request.chat_id, &extraction)
+            .map_err(|e| e.to_string())?;
+        Ok(extraction)
+    }
+
+    /// Reconstruct the code in a chat's screenshot, format it with a local
+    /// formatter when one is installed and `format` is set, and add it to the
+    /// chat as an artifact and as an assistant message holding a code block.
+    pub async fn code_from_screenshot(
+        &self,
+        request: CodeFromScreenshotRequest,
+    ) -> Result<CodeSnippet, String> {
+        let (image_path, text) =
+            screenshot_context(&request.chat_id, OcrTextLayout::Lines, request.redact_pii)?;
+        let call = crate::provider::gemini::commands::generation::extract_code(
+            &self.runtime,
+            request.api_key,
+            request.model.clone(),
+            image_path,
+            text.clone(),
+        );
+        let reply = audited("code_from_screenshot", &request.model, &text, call).await?;
+
+        let mut snippet = parse_code_reply(&reply)?;
+        if request.format {
+            snippet = format_snippet(snippet).await;
+        }
+        let storage = crate::context::media::get_active_storage()?;
+        let artifact = storage
+            .save_artifact(
+                &request.chat_id,
+                &snippet.file_name(),
+                snippet.code.as_bytes(),
+                Some(&request.model),
+            )
+            .map_err(|e| e.to_string())?;
+        snippet.artifact_id = Some(artifact.id);
+        storage
+            .append_message(
+                &request.chat_id,
+                &ChatMessage::assistant(snippet.to_markdown()).with_model(request.model),
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(snippet)
+    }
+
     pub async fn generate_image_brief(
         &self,
         request: GenerateImageBriefRequest,
     ) -> Result<String, String> {
-        crate::provider::gemini::commands::generation::generate_image_brief(
+        let model = request
+            .model
+            .clone()
+            .unwrap_or_else(|| crate::constants::DEFAULT_MODEL.to_string());
+        let call = crate::provider::gemini::commands::generation::generate_image_brief(
             &self.runtime,
             request.api_key,
-            request.image_path,
-            request.model,
-        )
-        .await
+            request.image_path.clone(),
+            Some(model.clone()),
+        );
+        audited("image_brief", &model, &request.image_path, call).await
     }
 
     pub async fn compress_conversation(
         &self,
         request: CompressConversationRequest,
     ) -> Result<String, String> {
-        crate::provider::gemini::commands::generation::compress_conversation(
+        let call = crate::provider::gemini::commands::generation::compress_conversation(
             request.api_key,
             request.image_brief,
-            request.history_to_compress,
+            request.history_to_compress.clone(),
+        );
+        audited(
+            "compress_conversation",
+            crate::constants::DEFAULT_MODEL,
+            &request.history_to_compress,
+            call,
+        )
+        .await
+    }
+
+    pub async fn backfill_chat_titles(
+        &self,
+        request: BackfillChatTitlesRequest,
+        stop: &AtomicBool,
+        on_progress: &(dyn Fn(&TitleBackfillProgress) + Send + Sync),
+    ) -> Result<usize, String> {
+        crate::context::titles::backfill_chat_titles(
+            &request.api_key,
+            &request.model,
+            crate::context::titles::BACKFILL_REQUEST_DELAY,
+            stop,
+            on_progress,
         )
         .await
     }
 
+    /// Vision-capable chat models the key can use, cached for
+    /// [`MODEL_LIST_TTL`].
+    ///
+    /// [`MODEL_LIST_TTL`]: crate::provider::gemini::commands::models::MODEL_LIST_TTL
+    pub async fn list_available_models(
+        &self,
+        request: ListModelsRequest,
+    ) -> Result<Vec<ModelInfo>, String> {
+        let cache = &self.runtime.model_list_cache;
+        // On Vertex AI the project, not the key, decides what is listed.
+        let cache_key = match &crate::provider::gemini::client::endpoint().vertex {
+            Some(vertex) => format!("vertex:{}/{}", vertex.project_id(), vertex.location()),
+            None => request.api_key.clone(),
+        };
+        if !request.refresh {
+            if let Some(models) = cache.lock().await.get(&cache_key) {
+                return Ok(models);
+            }
+        }
+        let models =
+            crate::provider::gemini::commands::models::list_gemini_models(&request.api_key).await?;
+        cache.lock().await.insert(&cache_key, models.clone());
+        Ok(models)
+    }
+
+    /// Models an OpenAI-compatible server offers. Not cached: local
+    /// servers change as models are pulled.
+    pub async fn list_openai_models(
+        &self,
+        config: &OpenAiCompatibleConfig,
+    ) -> Result<Vec<ModelInfo>, String> {
+        crate::provider::openai::models::list_openai_models(config).await
+    }
+
+    /// Claude models the key can use. Not cached.
+    pub async fn list_claude_models(
+        &self,
+        config: &AnthropicConfig,
+    ) -> Result<Vec<ModelInfo>, String> {
+        crate::provider::anthropic::models::list_claude_models(config).await
+    }
+
+    /// Drop in-memory caches. Returns the number of entries dropped.
+    pub async fn clear_caches(&self) -> usize {
+        self.runtime.clear_caches().await
+    }
+
     pub async fn cancel_request(&self, channel_id: Option<String>) -> Result<(), String> {
         crate::provider::gemini::agent::request_control::cancel_gemini_request(
             &self.runtime,
@@ -191,7 +711,10 @@ impl BrainService {
         let storage = crate::context::media::get_active_storage()?;
 
         let mut metadata = ChatMetadata::new(
-            "Untitled".to_string(),
+            request
+                .title
+                .clone()
+                .unwrap_or_else(|| "Untitled".to_string()),
             image.hash.clone(),
             request.ocr_lang.clone(),
         );
@@ -199,29 +722,45 @@ impl BrainService {
         let chat = ChatData::new(metadata.clone());
         storage.save_chat(&chat).map_err(|e| e.to_string())?;
 
+        let mut include_ocr_in_prompt = false;
+        if let (Some(model_id), Some(regions)) = (&request.ocr_lang, &request.ocr_regions) {
+            storage
+                .save_ocr_data(&metadata.id, model_id, regions)
+                .map_err(|e| e.to_string())?;
+            include_ocr_in_prompt = !regions.is_empty();
+        }
+
         let text = request.user_message.unwrap_or_default();
         let collector = CollectingEventSink::new(Some(sink));
-        self.stream_chat(
-            &collector,
-            StreamChatRequest {
-                api_key: request.api_key.clone(),
-                model: request.model.clone(),
-                is_initial_turn: true,
-                image_path: Some(image.path.clone()),
-                image_description: None,
-                user_first_msg: None,
-                history_log: None,
-                rolling_summary: None,
-                user_message: text.clone(),
-                channel_id: request.channel_id,
-                chat_id: Some(metadata.id.clone()),
-                user_name: request.user_name,
-                user_email: request.user_email,
-                user_instruction: request.user_instruction,
-                image_brief: None,
-            },
-        )
-        .await?;
+        let model = self
+            .stream_chat(
+                &collector,
+                StreamChatRequest {
+                    api_key: request.api_key.clone(),
+                    model: request.model.clone(),
+                    is_initial_turn: true,
+                    image_path: Some(image.path.clone()),
+                    image_paths: Vec::new(),
+                    image_description: None,
+                    user_first_msg: None,
+                    history_log: None,
+                    rolling_summary: None,
+                    user_message: text.clone(),
+                    channel_id: request.channel_id,
+                    chat_id: Some(metadata.id.clone()),
+                    user_name: request.user_name,
+                    user_email: request.user_email,
+                    user_instruction: request.user_instruction,
+                    image_brief: None,
+                    response_language: request.response_language,
+                    glossary: request.glossary,
+                    include_ocr_in_prompt,
+                    redact_pii: request.redact_pii,
+                    animation_frames: DEFAULT_ANIMATION_FRAMES,
+                    fallback_models: request.fallback_models,
+                },
+            )
+            .await?;
 
         let assistant_message = collector.current_text();
 
@@ -232,39 +771,47 @@ impl BrainService {
         }
         if !assistant_message.trim().is_empty() {
             storage
-                .append_message(&metadata.id, &ChatMessage::assistant(assistant_message.clone()))
+                .append_message(
+                    &metadata.id,
+                    &ChatMessage::assistant(assistant_message.clone()).with_model(model.clone()),
+                )
                 .map_err(|e| e.to_string())?;
         }
 
         let api_key = request.api_key.clone();
 
-        let image_brief = self
-            .generate_image_brief(GenerateImageBriefRequest {
+        let image_brief = if request.generate_brief {
+            self.generate_image_brief(GenerateImageBriefRequest {
                 api_key: api_key.clone(),
                 image_path: image.path.clone(),
                 model: Some(request.model.clone()),
             })
             .await
             .ok()
-            .filter(|value| !value.trim().is_empty());
+            .filter(|value| !value.trim().is_empty())
+        } else {
+            None
+        };
 
         if let Some(brief) = image_brief.as_ref() {
             let _ = storage.save_image_brief(&metadata.id, brief);
         }
 
-        let title_context = format!("User: {}\nAssistant: {}", text, assistant_message);
-        if let Ok(title) = self
-            .generate_chat_title(GenerateChatTitleRequest {
-                api_key,
-                model: request.model,
-                prompt_context: title_context,
-            })
-            .await
-        {
-            let trimmed = title.trim();
-            if !trimmed.is_empty() {
-                metadata.title = trimmed.to_string();
-                let _ = storage.update_chat_metadata(&metadata);
+        if request.title.is_none() {
+            let title_context = format!("User: {}\nAssistant: {}", text, assistant_message);
+            if let Ok(title) = self
+                .generate_chat_title(GenerateChatTitleRequest {
+                    api_key,
+                    model: request.model,
+                    prompt_context: title_context,
+                })
+                .await
+            {
+                let trimmed = title.trim();
+                if !trimmed.is_empty() {
+                    metadata.title = trimmed.to_string();
+                    let _ = storage.update_chat_metadata(&metadata);
+                }
             }
         }
 
@@ -273,6 +820,7 @@ impl BrainService {
             image,
             assistant_message,
             image_brief,
+            model,
         })
     }
 
@@ -288,50 +836,13 @@ impl BrainService {
         let normalized_user_message =
             normalize_prompt_message_with_at_paths(&storage, &request.user_message)?;
 
-        let image_path = storage
-            .get_image_path(&chat.metadata.image_hash)
-            .map_err(|e| e.to_string())?;
-
-        let mut history_pairs = Vec::new();
-        for message in &chat.messages {
-            history_pairs.push((message.role.clone(), message.content.clone()));
-        }
-
-        let image_description = chat
-            .messages
-            .iter()
-            .find(|message| message.role == "assistant")
-            .map(|message| message.content.clone())
-            .unwrap_or_default();
-        let user_first_msg = chat
-            .messages
-            .iter()
-            .find(|message| message.role == "user")
-            .map(|message| message.content.clone())
-            .unwrap_or_default();
-
         let collector = CollectingEventSink::new(Some(sink));
-        self.stream_chat(
-            &collector,
-            StreamChatRequest {
-                api_key: request.api_key,
-                model: request.model,
-                is_initial_turn: false,
-                image_path: Some(image_path),
-                image_description: Some(image_description),
-                user_first_msg: Some(user_first_msg),
-                history_log: Some(format_history_log(&history_pairs, 12)),
-                rolling_summary: chat.rolling_summary.clone(),
-                user_message: normalized_user_message.clone(),
-                channel_id: request.channel_id,
-                chat_id: Some(request.chat_id.clone()),
-                user_name: request.user_name,
-                user_email: request.user_email,
-                user_instruction: None,
-                image_brief: chat.image_brief.clone(),
-            },
-        )
-        .await?;
+        let model = self
+            .stream_chat(
+                &collector,
+                follow_up_request(&storage, &chat, &request, &normalized_user_message)?,
+            )
+            .await?;
 
         let assistant_message = collector.current_text();
 
@@ -344,7 +855,7 @@ impl BrainService {
         storage
             .append_message(
                 &request.chat_id,
-                &ChatMessage::assistant(assistant_message.clone()),
+                &ChatMessage::assistant(assistant_message.clone()).with_model(model.clone()),
             )
             .map_err(|e| e.to_string())?;
 
@@ -352,8 +863,87 @@ impl BrainService {
             chat_id: request.chat_id,
             assistant_message,
             normalized_user_message,
+            model,
         })
     }
+
+    /// Generate the reply to a chat's dangling user turn from stored context
+    /// and append it. Fails when the last message is not a user turn.
+    pub async fn resume_chat(
+        &self,
+        sink: &dyn BrainEventSink,
+        request: ResumeChatRequest,
+    ) -> Result<String, String> {
+        let storage = crate::context::media::get_active_storage()?;
+        let chat = storage
+            .load_chat(&request.chat_id)
+            .map_err(|e| e.to_string())?;
+        let Some((pending, earlier)) = chat.messages.split_last() else {
+            return Err("ERR_NOTHING_TO_RESUME".to_string());
+        };
+        if pending.role != "user" {
+            return Err("ERR_NOTHING_TO_RESUME".to_string());
+        }
+
+        let image_path = storage
+            .chat_image_path(&chat.metadata)
+            .map_err(|e| e.to_string())?;
+        let history_pairs: Vec<(String, String)> = earlier
+            .iter()
+            .map(|message| (message.role.clone(), message.content.clone()))
+            .collect();
+        let image_description = earlier
+            .iter()
+            .find(|message| message.role == "assistant")
+            .map(|message| message.content.clone());
+        // A dangling first turn is the initial image analysis itself.
+        let is_initial_turn = image_description.is_none();
+        let user_first_msg = chat
+            .messages
+            .iter()
+            .find(|message| message.role == "user")
+            .map(|message| message.content.clone());
+
+        let collector = CollectingEventSink::new(Some(sink));
+        let model = self
+            .stream_chat(
+                &collector,
+                StreamChatRequest {
+                    api_key: request.api_key,
+                    model: request.model,
+                    is_initial_turn,
+                    image_path,
+                    image_paths: attachment_image_paths(&storage, &chat.metadata),
+                    image_description,
+                    user_first_msg,
+                    history_log: Some(format_history_log(&history_pairs, 12)),
+                    rolling_summary: chat.rolling_summary.clone(),
+                    user_message: pending.content.clone(),
+                    channel_id: request.channel_id,
+                    chat_id: Some(request.chat_id.clone()),
+                    user_name: request.user_name,
+                    user_email: request.user_email,
+                    user_instruction: None,
+                    image_brief: chat.image_brief.clone(),
+                    response_language: request.response_language,
+                    glossary: request.glossary,
+                    include_ocr_in_prompt: request.include_ocr_in_prompt,
+                    redact_pii: request.redact_pii,
+                    animation_frames: DEFAULT_ANIMATION_FRAMES,
+                    fallback_models: request.fallback_models,
+                },
+            )
+            .await?;
+
+        let assistant_message = collector.current_text();
+        storage
+            .append_message(
+                &request.chat_id,
+                &ChatMessage::assistant(assistant_message.clone()).with_model(model.clone()),
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(assistant_message)
+    }
 }
 
 impl Default for BrainService {
@@ -362,41 +952,115 @@ impl Default for BrainService {
     }
 }
 
-struct CollectingEventSink<'a> {
-    delegate: Option<&'a dyn BrainEventSink>,
-    text: Arc<Mutex<String>>,
-}
-
-impl<'a> CollectingEventSink<'a> {
-    fn new(delegate: Option<&'a dyn BrainEventSink>) -> Self {
-        Self {
-            delegate,
-            text: Arc::new(Mutex::new(String::new())),
+/// The request's response language, trimmed, if it is a supported one.
+fn checked_response_language(request: &StreamChatRequest) -> Result<Option<String>, String> {
+    let response_language = request
+        .response_language
+        .as_deref()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty());
+    if let Some(tag) = response_language.as_deref() {
+        if crate::context::builder::response_language_name(tag).is_none() {
+            return Err(format!("ERR_UNSUPPORTED_RESPONSE_LANGUAGE: {}", tag));
         }
     }
+    Ok(response_language)
+}
 
-    fn current_text(&self) -> String {
-        self.text
-            .lock()
-            .map(|value| value.clone())
-            .unwrap_or_default()
-    }
+/// The follow-up turn for `user_message` in `chat`. Its screenshot is
+/// passed along unless it was discarded, in which case the turn goes on
+/// from the chat's text alone.
+fn follow_up_request(
+    storage: &ChatStorage,
+    chat: &ChatData,
+    request: &PromptChatRequest,
+    user_message: &str,
+) -> Result<StreamChatRequest, String> {
+    let image_path = storage
+        .chat_image_path(&chat.metadata)
+        .map_err(|e| e.to_string())?;
+    let history_pairs: Vec<(String, String)> = chat
+        .messages
+        .iter()
+        .map(|message| (message.role.clone(), message.content.clone()))
+        .collect();
+    let image_description = chat
+        .messages
+        .iter()
+        .find(|message| message.role == "assistant")
+        .map(|message| message.content.clone())
+        .unwrap_or_default();
+    let user_first_msg = chat
+        .messages
+        .iter()
+        .find(|message| message.role == "user")
+        .map(|message| message.content.clone())
+        .unwrap_or_default();
+
+    Ok(StreamChatRequest {
+        api_key: request.api_key.clone(),
+        model: request.model.clone(),
+        is_initial_turn: false,
+        image_path,
+        image_paths: Vec::new(),
+        image_description: Some(image_description),
+        user_first_msg: Some(user_first_msg),
+        history_log: Some(format_history_log(&history_pairs, 12)),
+        rolling_summary: chat.rolling_summary.clone(),
+        user_message: user_message.to_string(),
+        channel_id: request.channel_id.clone(),
+        chat_id: Some(request.chat_id.clone()),
+        user_name: request.user_name.clone(),
+        user_email: request.user_email.clone(),
+        user_instruction: None,
+        image_brief: chat.image_brief.clone(),
+        response_language: request.response_language.clone(),
+        glossary: request.glossary.clone(),
+        include_ocr_in_prompt: false,
+        redact_pii: false,
+        animation_frames: DEFAULT_ANIMATION_FRAMES,
+        fallback_models: request.fallback_models.clone(),
+    })
 }
 
-impl BrainEventSink for CollectingEventSink<'_> {
-    fn emit(&self, channel_id: &str, event: GeminiEvent) {
-        if let Ok(mut text) = self.text.lock() {
-            match &event {
-                GeminiEvent::Token { token } => text.push_str(token),
-                GeminiEvent::Reset => text.clear(),
-                _ => {}
-            }
-        }
+/// Paths of the chat's image attachments, in order. Missing objects are
+/// left out.
+fn attachment_image_paths(storage: &ChatStorage, metadata: &ChatMetadata) -> Vec<String> {
+    metadata
+        .attachments
+        .iter()
+        .filter(|attachment| attachment.mime.starts_with("image/"))
+        .filter_map(|attachment| storage.get_object_path(&attachment.hash).ok())
+        .collect()
+}
 
-        if let Some(delegate) = self.delegate {
-            delegate.emit(channel_id, event);
-        }
+/// A chat's stored image path and OCR text, for extraction calls. With
+/// `redact_pii`, personal data in the text is replaced by placeholders.
+fn screenshot_context(
+    chat_id: &str,
+    layout: OcrTextLayout,
+    redact_pii: bool,
+) -> Result<(Option<String>, String), String> {
+    let storage = crate::context::media::get_active_storage()?;
+    let chat = storage.load_chat(chat_id).map_err(|e| e.to_string())?;
+    let image_path = storage.chat_image_path(&chat.metadata).ok().flatten();
+    let text = match chat.metadata.ocr_lang.as_deref() {
+        Some(model_id) => storage
+            .get_ocr_data(chat_id, model_id)
+            .map_err(|e| e.to_string())?
+            .map(|regions| ocr_text(&regions, layout))
+            .unwrap_or_default(),
+        None => String::new(),
+    };
+    let text = if redact_pii {
+        ops_redaction::redact(&text)
+    } else {
+        text
+    };
+    if image_path.is_none() && text.is_empty() {
+        return Err("ERR_NOTHING_TO_EXTRACT".to_string());
     }
+    Ok((image_path, text))
 }
 
 fn normalize_prompt_message_with_at_paths(
@@ -439,3 +1103,53 @@ fn normalize_prompt_message_with_at_paths(
         Ok(input.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ephemeral_chats_are_prompted_without_their_image() {
+        let base_dir =
+            std::env::temp_dir().join(format!("squigit-prompt-test-{}", rand::random::<u64>()));
+        let storage = ChatStorage::with_base_dir(base_dir.clone()).unwrap();
+        let image = storage.store_image(b"screenshot", None).unwrap();
+        let metadata = ChatMetadata::new("Receipt".to_string(), image.hash, None);
+        let mut chat = ChatData::new(metadata);
+        chat.messages = vec![
+            ChatMessage::user("What is this?".to_string()),
+            ChatMessage::assistant("A receipt for 12.50".to_string()),
+        ];
+        storage.save_chat(&chat).unwrap();
+        storage
+            .discard_chat_image(&chat.metadata.id, true)
+            .unwrap()
+            .unwrap();
+
+        let chat = storage.load_chat(&chat.metadata.id).unwrap();
+        let request = PromptChatRequest {
+            api_key: "key".to_string(),
+            model: "gemini-2.5-flash".to_string(),
+            chat_id: chat.metadata.id.clone(),
+            user_message: "And the tax?".to_string(),
+            channel_id: "channel".to_string(),
+            user_name: None,
+            user_email: None,
+            response_language: None,
+            glossary: Vec::new(),
+            fallback_models: Vec::new(),
+        };
+        let follow_up = follow_up_request(&storage, &chat, &request, "And the tax?").unwrap();
+        assert!(follow_up.image_path.is_none());
+        assert!(!follow_up.is_initial_turn);
+        assert_eq!(
+            follow_up.image_description.as_deref(),
+            Some("A receipt for 12.50")
+        );
+        assert!(follow_up
+            .history_log
+            .is_some_and(|log| log.contains("What is this?")));
+
+        let _ = std::fs::remove_dir_all(base_dir);
+    }
+}
@@ -129,7 +129,7 @@ pub fn search_local_chats(
 
     let mut rows = Vec::new();
     let mut chats = storage.list_chats().map_err(|e| e.to_string())?;
-    chats.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    chats.sort_by_key(|a| std::cmp::Reverse(a.updated_at));
 
     for metadata in chats
         .into_iter()
@@ -26,8 +26,7 @@ pub(crate) fn favicon_for_url(url: &str) -> Option<String> {
 fn active_chat_storage() -> Option<ChatStorage> {
     let profile_store = ProfileStore::new().ok()?;
     let active_id = profile_store.get_active_profile_id().ok()??;
-    let chats_dir = profile_store.get_chats_dir(&active_id);
-    ChatStorage::with_base_dir(chats_dir).ok()
+    profile_store.open_chat_storage(&active_id).ok()
 }
 
 fn favicon_extension_from_content_type(content_type: &str) -> Option<&'static str> {
@@ -118,7 +117,13 @@ async fn fetch_and_store_favicon(
         .unwrap_or_else(|| "png".to_string());
 
     match storage.store_file(bytes.as_ref(), &extension, None) {
-        Ok(stored) => (favicon_url, Some(stored.path)),
+        Ok(stored) => {
+            let path = storage
+                .readable_path(Path::new(&stored.path))
+                .map(|path| path.to_string_lossy().to_string())
+                .ok();
+            (favicon_url, path)
+        }
         Err(_) => (favicon_url, None),
     }
 }
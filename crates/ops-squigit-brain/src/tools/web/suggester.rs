@@ -1,6 +1,7 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::provider::gemini::client::endpoint;
 use serde::Deserialize;
 use std::collections::HashSet;
 use std::time::Duration;
@@ -97,10 +98,8 @@ pub(crate) async fn suggest_fallback_urls(
     query: &str,
     max_urls: usize,
 ) -> Vec<String> {
-    let suggest_url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model, api_key
-    );
+    let endpoint = endpoint();
+    let suggest_url = endpoint.model_url(model, "generateContent", api_key);
 
     let prompt = format!(
         "Suggest up to {max_urls} direct, publicly accessible URLs for this query: \"{query}\".\n\
@@ -117,12 +116,14 @@ pub(crate) async fn suggest_fallback_urls(
         }]
     });
 
-    let response = match tokio::time::timeout(
-        Duration::from_secs(8),
-        client.post(&suggest_url).json(&request_body).send(),
-    )
-    .await
-    {
+    let Ok(request) = endpoint
+        .json_request(client.post(&suggest_url), &request_body)
+        .await
+    else {
+        return Vec::new();
+    };
+
+    let response = match tokio::time::timeout(Duration::from_secs(8), request.send()).await {
         Ok(Ok(resp)) => resp,
         Ok(Err(_)) => return Vec::new(),
         Err(_) => return Vec::new(),
@@ -0,0 +1,236 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional formula recognition pass.
+//!
+//! Regular OCR turns typeset equations into noise. This pass hands the
+//! regions that look like math to a pix2tex-style sidecar which returns
+//! LaTeX, and stores the result on [`OcrRegion::latex`].
+
+use crate::ocr::{extract_json_payload, OcrRuntimeError};
+use ops_chat_storage::OcrRegion;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::Stdio;
+use sys_process_priority::PriorityPolicy;
+use tokio::time::{timeout, Duration};
+
+const FORMULA_TIMEOUT_SECS_DEFAULT: u64 = 60;
+
+/// Share of math-ish characters above which a region is treated as a formula.
+const FORMULA_SYMBOL_RATIO: f64 = 0.25;
+
+const MATH_SYMBOLS: &[char] = &[
+    '=', '+', '-', '*', '/', '^', '_', '(', ')', '[', ']', '{', '}', '<', '>', '|', '\\', '∑', '∫',
+    '∂', '√', '∞', '≤', '≥', '≠', '≈', '±', '×', '÷', '·', 'α', 'β', 'γ', 'δ', 'θ', 'λ', 'μ', 'π',
+    'σ', 'φ', 'ω', 'Δ', 'Σ', 'Ω',
+];
+
+/// A region handed to the formula sidecar.
+#[derive(Debug, Clone, Serialize)]
+pub struct FormulaRegion {
+    /// Index into the OCR region list the result belongs to.
+    pub index: usize,
+    pub bbox: Vec<Vec<i32>>,
+}
+
+/// A single LaTeX result returned by the sidecar.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FormulaResult {
+    pub index: usize,
+    pub latex: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct FormulaRequest {
+    pub sidecar_path: PathBuf,
+    pub image_path: PathBuf,
+    pub regions: Vec<FormulaRegion>,
+    pub timeout_secs: Option<u64>,
+    pub priority: PriorityPolicy,
+}
+
+pub fn resolve_formula_sidecar_path() -> PathBuf {
+    let name = if cfg!(windows) {
+        "squigit-formula.exe"
+    } else {
+        "squigit-formula"
+    };
+
+    which::which(name).unwrap_or_else(|_| PathBuf::from(name))
+}
+
+/// Heuristic check for text that came from a typeset equation.
+pub fn looks_like_formula(text: &str) -> bool {
+    let chars = text
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<Vec<_>>();
+    if chars.len() < 3 {
+        return false;
+    }
+
+    let symbols = chars.iter().filter(|ch| MATH_SYMBOLS.contains(ch)).count();
+    let has_operator = chars
+        .iter()
+        .any(|ch| matches!(ch, '=' | '^' | '∑' | '∫' | '√' | '≤' | '≥' | '≠' | '≈'));
+
+    has_operator && (symbols as f64 / chars.len() as f64) >= FORMULA_SYMBOL_RATIO
+}
+
+/// Pick the regions worth sending to the formula sidecar.
+pub fn select_formula_candidates(regions: &[OcrRegion]) -> Vec<FormulaRegion> {
+    regions
+        .iter()
+        .enumerate()
+        .filter(|(_, region)| region.latex.is_none() && looks_like_formula(&region.text))
+        .map(|(index, region)| FormulaRegion {
+            index,
+            bbox: region.bbox.clone(),
+        })
+        .collect()
+}
+
+/// Attach sidecar results to their regions. Returns how many were updated.
+pub fn apply_formula_results(regions: &mut [OcrRegion], results: &[FormulaResult]) -> usize {
+    let mut applied = 0usize;
+    for result in results {
+        let latex = result.latex.trim();
+        if latex.is_empty() {
+            continue;
+        }
+        if let Some(region) = regions.get_mut(result.index) {
+            region.latex = Some(latex.to_string());
+            applied += 1;
+        }
+    }
+    applied
+}
+
+/// Run the formula sidecar over the requested regions.
+pub async fn run_formula_pass(
+    request: FormulaRequest,
+) -> Result<Vec<FormulaResult>, OcrRuntimeError> {
+    if request.regions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let regions_json = serde_json::to_string(&request.regions)
+        .map_err(|e| OcrRuntimeError::Message(format!("Failed to encode regions: {}", e)))?;
+    let timeout_secs = request.timeout_secs.unwrap_or(FORMULA_TIMEOUT_SECS_DEFAULT);
+
+    let mut cmd = tokio::process::Command::new(&request.sidecar_path);
+    cmd.arg(&request.image_path)
+        .arg("--regions")
+        .arg(regions_json)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(
+            CREATE_NO_WINDOW | sys_process_priority::priority_class(&request.priority),
+        );
+    }
+    #[cfg(unix)]
+    {
+        let priority = request.priority;
+        // SAFETY: The hook only makes async-signal-safe system calls.
+        unsafe {
+            cmd.pre_exec(move || sys_process_priority::apply_to_current_process(&priority));
+        }
+    }
+
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(OcrRuntimeError::Message(
+                "ERR_MISSING_FORMULA_PACKAGE".to_string(),
+            ));
+        }
+        Err(e) => {
+            return Err(OcrRuntimeError::Message(format!(
+                "Failed to spawn formula sidecar: {}",
+                e
+            )));
+        }
+    };
+
+    #[cfg(windows)]
+    if let Some(pid) = child.id() {
+        let _ = sys_process_priority::apply_to_process(pid, &request.priority);
+    }
+
+    let output = timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
+        .await
+        .map_err(|_| {
+            OcrRuntimeError::Message(format!(
+                "Formula recognition timed out after {}s",
+                timeout_secs
+            ))
+        })?
+        .map_err(|e| OcrRuntimeError::Message(format!("Formula sidecar failed: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(OcrRuntimeError::Message(format!(
+            "Formula sidecar failed: {}",
+            stderr.trim()
+        )));
+    }
+
+    parse_formula_output(&stdout)
+}
+
+fn parse_formula_output(raw: &str) -> Result<Vec<FormulaResult>, OcrRuntimeError> {
+    let payload = extract_json_payload(raw).ok_or_else(|| {
+        OcrRuntimeError::Message("Formula sidecar returned no JSON payload".to_string())
+    })?;
+    serde_json::from_str(&payload)
+        .map_err(|e| OcrRuntimeError::Message(format!("Failed to parse formula output: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(text: &str) -> OcrRegion {
+        OcrRegion {
+            text: text.to_string(),
+            bbox: vec![vec![0, 0], vec![10, 10]],
+            confidence: None,
+            low_confidence: false,
+            latex: None,
+            pii: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn formula_heuristic_skips_prose() {
+        assert!(looks_like_formula("E = mc^2"));
+        assert!(looks_like_formula("∑ x_i ≤ n"));
+        assert!(!looks_like_formula("Click the button to continue"));
+        assert!(!looks_like_formula(
+            "a = b in the next section we discuss results"
+        ));
+    }
+
+    #[test]
+    fn results_are_applied_by_index() {
+        let mut regions = vec![region("Intro"), region("x^2 + y^2 = z^2")];
+        let candidates = select_formula_candidates(&regions);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].index, 1);
+
+        let results = parse_formula_output("loading...\n[{\"index\":1,\"latex\":\"x^2+y^2=z^2\"}]")
+            .expect("parse");
+        assert_eq!(apply_formula_results(&mut regions, &results), 1);
+        assert_eq!(regions[1].latex.as_deref(), Some("x^2+y^2=z^2"));
+        assert_eq!(regions[1].export_text(), "$$x^2+y^2=z^2$$");
+        assert_eq!(regions[0].export_text(), "Intro");
+    }
+}
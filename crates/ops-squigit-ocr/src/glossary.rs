@@ -0,0 +1,316 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Post-OCR correction against a user glossary.
+//!
+//! Known misspellings are replaced verbatim (multi-word variants included).
+//! Single-word terms also catch near misses: a token within a small edit
+//! distance of the term, sharing its first letter, is rewritten to the
+//! preferred spelling. Surrounding punctuation is preserved.
+
+use crate::ocr::OcrBox;
+
+/// Terms shorter than this are only corrected through explicit variants;
+/// fuzzy matching short words rewrites too much ordinary text.
+const MIN_FUZZY_CHARS: usize = 5;
+
+/// A glossary term and the spellings that should map to it.
+#[derive(Debug, Clone, Default)]
+pub struct GlossaryTerm {
+    pub term: String,
+    pub variants: Vec<String>,
+}
+
+struct Variant<'a> {
+    words: Vec<String>,
+    term: &'a str,
+}
+
+struct FuzzyTerm<'a> {
+    chars: Vec<char>,
+    term: &'a str,
+}
+
+/// Precomputed lookup tables for a glossary.
+pub struct GlossaryMatcher<'a> {
+    exact_terms: Vec<&'a str>,
+    variants: Vec<Variant<'a>>,
+    fuzzy: Vec<FuzzyTerm<'a>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    core_start: usize,
+    core_end: usize,
+}
+
+impl<'a> GlossaryMatcher<'a> {
+    pub fn new(terms: &'a [GlossaryTerm]) -> Self {
+        let mut exact_terms = Vec::new();
+        let mut variants = Vec::new();
+        let mut fuzzy = Vec::new();
+
+        for entry in terms {
+            let term = entry.term.trim();
+            if term.is_empty() {
+                continue;
+            }
+            exact_terms.push(term);
+
+            for variant in &entry.variants {
+                let words: Vec<String> = variant
+                    .split_whitespace()
+                    .map(|word| strip_punctuation(word).to_lowercase())
+                    .filter(|word| !word.is_empty())
+                    .collect();
+                if !words.is_empty() {
+                    variants.push(Variant { words, term });
+                }
+            }
+
+            let single_word = !term.contains(char::is_whitespace);
+            if single_word && term.chars().count() >= MIN_FUZZY_CHARS {
+                fuzzy.push(FuzzyTerm {
+                    chars: term.to_lowercase().chars().collect(),
+                    term,
+                });
+            }
+        }
+
+        // Longest phrases first so "visual studio code" wins over "visual studio".
+        variants.sort_by_key(|variant| std::cmp::Reverse(variant.words.len()));
+
+        Self {
+            exact_terms,
+            variants,
+            fuzzy,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty() && self.fuzzy.is_empty()
+    }
+
+    /// Correct one line of text. Returns the new text and the number of
+    /// replacements, or `None` when nothing changed.
+    pub fn correct_text(&self, text: &str) -> Option<(String, usize)> {
+        let tokens = tokenize(text);
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0usize;
+        let mut replacements = 0usize;
+        let mut index = 0usize;
+
+        while index < tokens.len() {
+            let Some((consumed, term)) = self.match_at(text, &tokens[index..]) else {
+                index += 1;
+                continue;
+            };
+            let first = tokens[index];
+            let last = tokens[index + consumed - 1];
+            out.push_str(&text[cursor..first.core_start]);
+            out.push_str(term);
+            cursor = last.core_end;
+            replacements += 1;
+            index += consumed;
+        }
+
+        if replacements == 0 {
+            return None;
+        }
+        out.push_str(&text[cursor..]);
+        Some((out, replacements))
+    }
+
+    fn match_at(&self, text: &str, tokens: &[Token]) -> Option<(usize, &'a str)> {
+        let core = core_of(text, tokens[0]);
+        if core.is_empty() {
+            return None;
+        }
+
+        for variant in &self.variants {
+            let len = variant.words.len();
+            if len > tokens.len() {
+                continue;
+            }
+            let matches = variant
+                .words
+                .iter()
+                .zip(&tokens[..len])
+                .all(|(word, token)| core_of(text, *token).to_lowercase() == *word);
+            if matches {
+                let original = &text[tokens[0].core_start..tokens[len - 1].core_end];
+                if original == variant.term {
+                    return None;
+                }
+                return Some((len, variant.term));
+            }
+        }
+
+        if self.exact_terms.contains(&core) {
+            return None;
+        }
+
+        let lower: Vec<char> = core.to_lowercase().chars().collect();
+        if lower.len() < MIN_FUZZY_CHARS - 1 || !lower.iter().all(|c| c.is_alphanumeric()) {
+            return None;
+        }
+        let max_distance = if lower.len() >= 9 { 2 } else { 1 };
+
+        self.fuzzy
+            .iter()
+            .filter(|candidate| candidate.chars.first() == lower.first())
+            .filter_map(|candidate| {
+                let distance = edit_distance(&lower, &candidate.chars, max_distance)?;
+                (distance > 0).then_some((distance, candidate.term))
+            })
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, term)| (1, term))
+    }
+}
+
+/// Apply the glossary to every box. Returns the number of replacements.
+pub fn apply_glossary(boxes: &mut [OcrBox], terms: &[GlossaryTerm]) -> usize {
+    let matcher = GlossaryMatcher::new(terms);
+    if matcher.is_empty() {
+        return 0;
+    }
+
+    let mut total = 0usize;
+    for entry in boxes.iter_mut() {
+        if let Some((text, count)) = matcher.correct_text(&entry.text) {
+            entry.text = text;
+            total += count;
+        }
+    }
+    total
+}
+
+fn strip_punctuation(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+fn core_of(text: &str, token: Token) -> &str {
+    &text[token.core_start..token.core_end]
+}
+
+/// Split on whitespace and record the byte span of each token with leading
+/// and trailing punctuation trimmed.
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    let mut push = |from: usize, to: usize| {
+        let word = &text[from..to];
+        let core = strip_punctuation(word);
+        let offset = word.len()
+            - word
+                .trim_start_matches(|c: char| !c.is_alphanumeric())
+                .len();
+        tokens.push(Token {
+            core_start: from + offset,
+            core_end: from + offset + core.len(),
+        });
+    };
+
+    for (position, ch) in text.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (true, Some(from)) => {
+                push(from, position);
+                start = None;
+            }
+            (false, None) => start = Some(position),
+            _ => {}
+        }
+    }
+    if let Some(from) = start {
+        push(from, text.len());
+    }
+    tokens
+}
+
+/// Levenshtein distance, or `None` once it is known to exceed `max`.
+fn edit_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0usize; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        let mut row_min = current[0];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            row_min = row_min.min(current[j + 1]);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_glossary, GlossaryMatcher, GlossaryTerm};
+    use crate::ocr::OcrBox;
+
+    fn glossary() -> Vec<GlossaryTerm> {
+        vec![
+            GlossaryTerm {
+                term: "Kubernetes".to_string(),
+                variants: vec!["K8".to_string()],
+            },
+            GlossaryTerm {
+                term: "PostgreSQL".to_string(),
+                variants: vec!["postgre sql".to_string()],
+            },
+            GlossaryTerm {
+                term: "gRPC".to_string(),
+                variants: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn variants_and_near_misses_are_corrected() {
+        let terms = glossary();
+        let matcher = GlossaryMatcher::new(&terms);
+
+        let (text, count) = matcher
+            .correct_text("Deploy (Kubemetes) with Postgre SQL, then k8.")
+            .expect("corrected");
+        assert_eq!(
+            text,
+            "Deploy (Kubernetes) with PostgreSQL, then Kubernetes."
+        );
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn correct_and_unrelated_text_is_left_alone() {
+        let terms = glossary();
+        let matcher = GlossaryMatcher::new(&terms);
+
+        assert!(matcher.correct_text("Kubernetes and gRPC").is_none());
+        // "grpc" is too short for fuzzy matching and has no variants.
+        assert!(matcher.correct_text("grpc hello world").is_none());
+        assert!(matcher.correct_text("").is_none());
+    }
+
+    #[test]
+    fn boxes_are_rewritten_in_place() {
+        let mut boxes = vec![OcrBox {
+            text: "PostgreSOL 16".to_string(),
+            box_coords: vec![],
+            confidence: 0.8,
+            low_confidence: false,
+        }];
+        assert_eq!(apply_glossary(&mut boxes, &glossary()), 1);
+        assert_eq!(boxes[0].text, "PostgreSQL 16");
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod formula;
+pub mod glossary;
 pub mod models;
 pub mod network;
 pub mod ocr;
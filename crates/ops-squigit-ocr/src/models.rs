@@ -13,6 +13,7 @@ use tar::Archive;
 use thiserror::Error;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 
 use crate::network::{NetworkStatus, PeerNetworkMonitor};
@@ -32,6 +33,8 @@ pub enum ModelError {
     Extraction(String),
     #[error("Download cancelled")]
     Cancelled,
+    #[error("Download interrupted by system resume")]
+    Interrupted,
 }
 
 pub type Result<T> = std::result::Result<T, ModelError>;
@@ -109,6 +112,9 @@ pub struct ModelManager {
     models_dir: PathBuf,
     cancellation_tokens: Arc<Mutex<HashMap<String, CancellationToken>>>,
     network_monitor: Arc<PeerNetworkMonitor>,
+    /// Fired on system resume to drop in-flight streams whose sockets are
+    /// likely dead, so they reconnect instead of waiting out the timeout.
+    resume_notify: Arc<Notify>,
 }
 
 impl ModelManager {
@@ -127,6 +133,7 @@ impl ModelManager {
             models_dir,
             cancellation_tokens: Arc::new(Mutex::new(HashMap::new())),
             network_monitor,
+            resume_notify: Arc::new(Notify::new()),
         })
     }
 
@@ -134,6 +141,20 @@ impl ModelManager {
         self.network_monitor.start_monitor();
     }
 
+    /// Refresh network state and restart in-flight downloads after the
+    /// system woke from sleep. Interrupted downloads resume from the partial
+    /// file and do not count against the retry limit.
+    pub fn handle_resume(&self) {
+        self.network_monitor.probe_now();
+        self.resume_notify.notify_waiters();
+    }
+
+    /// Slow down network probing while the app is idle in the background.
+    /// Downloads keep working; they only use the probe as a hint.
+    pub fn set_idle(&self, idle: bool) {
+        self.network_monitor.set_idle(idle);
+    }
+
     pub fn models_dir(&self) -> &Path {
         &self.models_dir
     }
@@ -269,6 +290,12 @@ impl ModelManager {
                         let _ = fs::remove_file(&temp_file_path);
                         return Err(ModelError::Cancelled);
                     }
+                    Err(ModelError::Interrupted) => {
+                        println!(
+                            "Download of {} interrupted by resume; reconnecting",
+                            canonical_id
+                        );
+                    }
                     Err(e) => {
                         attempts = attempts.saturating_add(1);
                         println!(
@@ -581,6 +608,10 @@ impl ModelManager {
                 _ = cancel_token.cancelled() => {
                     return Err(ModelError::Cancelled);
                 }
+                _ = self.resume_notify.notified() => {
+                    file.flush().await?;
+                    return Err(ModelError::Interrupted);
+                }
                 item = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next()) => {
                     match item {
                         Ok(Some(chunk_result)) => {
@@ -1,9 +1,17 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::time::sleep;
+use tokio::sync::Notify;
+use tokio::time::{sleep, timeout};
+
+const PROBE_ADDR: &str = "8.8.8.8:53";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const PROBE_INTERVAL: Duration = Duration::from_secs(2);
+/// Probe interval while the app sits idle in the background.
+const IDLE_PROBE_INTERVAL: Duration = Duration::from_secs(5 * 60);
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum NetworkStatus {
@@ -29,6 +37,8 @@ impl Default for NetworkState {
 
 pub struct PeerNetworkMonitor {
     state: Arc<Mutex<NetworkState>>,
+    wake: Arc<Notify>,
+    idle: Arc<AtomicBool>,
 }
 
 impl Default for PeerNetworkMonitor {
@@ -41,6 +51,8 @@ impl PeerNetworkMonitor {
     pub fn new() -> Self {
         Self {
             state: Arc::new(Mutex::new(NetworkState::default())),
+            wake: Arc::new(Notify::new()),
+            idle: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -48,17 +60,36 @@ impl PeerNetworkMonitor {
         self.state.lock().unwrap().clone()
     }
 
+    /// Probe again without waiting for the next interval, e.g. after the
+    /// system resumed and the cached state may be stale.
+    pub fn probe_now(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Probe rarely while `idle`. Leaving idle probes right away, since the
+    /// cached state may be minutes old.
+    pub fn set_idle(&self, idle: bool) {
+        let was_idle = self.idle.swap(idle, Ordering::SeqCst);
+        if was_idle && !idle {
+            self.probe_now();
+        }
+    }
+
     pub fn start_monitor(&self) {
         let state = self.state.clone();
+        let wake = self.wake.clone();
+        let idle = self.idle.clone();
 
         tokio::spawn(async move {
             loop {
                 let start = Instant::now();
                 // 8.8.8.8:53 is Google DNS, very reliable.
-                // Connect timeout of 2s.
+                // Connect timeout of 2s, so a half-dead socket after resume
+                // cannot stall the loop.
                 // This is a "TCP Ping".
-                let status = match tokio::net::TcpStream::connect("8.8.8.8:53").await {
-                    Ok(_) => {
+                let probe = timeout(PROBE_TIMEOUT, tokio::net::TcpStream::connect(PROBE_ADDR));
+                let status = match probe.await {
+                    Ok(Ok(_)) => {
                         let latency = start.elapsed().as_millis() as u64;
                         let status = if latency > 300 {
                             NetworkStatus::Poor
@@ -70,14 +101,22 @@ impl PeerNetworkMonitor {
                             latency_ms: latency,
                         }
                     }
-                    Err(_) => NetworkState {
+                    Ok(Err(_)) | Err(_) => NetworkState {
                         status: NetworkStatus::Offline,
                         latency_ms: 9999,
                     },
                 };
 
                 *state.lock().unwrap() = status;
-                sleep(Duration::from_secs(2)).await;
+                let interval = if idle.load(Ordering::SeqCst) {
+                    IDLE_PROBE_INTERVAL
+                } else {
+                    PROBE_INTERVAL
+                };
+                tokio::select! {
+                    _ = sleep(interval) => {}
+                    _ = wake.notified() => {}
+                }
             }
         });
     }
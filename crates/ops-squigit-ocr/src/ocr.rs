@@ -1,15 +1,18 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
-use ops_chat_storage::{ChatStorage, OcrRegion, StorageError};
+use ops_chat_storage::{ChatStorage, OcrConfidenceSummary, OcrRegion, StorageError};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use sys_process_priority::PriorityPolicy;
 use thiserror::Error;
-use tokio::io::AsyncReadExt;
-use tokio::sync::Mutex;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::sync::{oneshot, Mutex, Semaphore};
 use tokio::time::{timeout, Duration};
 
 #[cfg(unix)]
@@ -20,14 +23,22 @@ use std::os::windows::process::CommandExt;
 
 /// Maximum wall-clock time for a single OCR job (seconds).
 const OCR_TIMEOUT_SECS_DEFAULT: u64 = 120;
+const OCR_TIMEOUT_SECS_RANGE: std::ops::RangeInclusive<u64> = 10..=1800;
 
-/// Global mutex to ensure only one OCR job runs at a time.
-/// Prevents concurrent calls from compounding CPU pressure.
-static OCR_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+/// Unix nice values the sidecar may be given. Negative values would need
+/// privileges.
+const OCR_NICENESS_RANGE: std::ops::RangeInclusive<i32> = 0..=19;
 
-fn ocr_lock() -> &'static Mutex<()> {
-    OCR_LOCK.get_or_init(|| Mutex::new(()))
-}
+/// Threads for the sidecar's math libraries.
+const OCR_THREADS_DEFAULT: u32 = 1;
+const OCR_THREADS_MAX: u32 = 64;
+
+/// Smallest memory cap that still leaves room to load the models.
+const OCR_MEMORY_LIMIT_MIN_MB: u64 = 512;
+
+/// Sidecars that may run at once; further jobs wait for a free slot.
+const OCR_CONCURRENT_JOBS_DEFAULT: u32 = 2;
+const OCR_CONCURRENT_JOBS_MAX: u32 = 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OcrBox {
@@ -35,8 +46,17 @@ pub struct OcrBox {
     pub box_coords: Vec<Vec<f64>>,
     #[serde(default)]
     pub confidence: f64,
+    /// Set when `confidence` fell below the threshold passed to [`apply_min_confidence`].
+    #[serde(default)]
+    pub low_confidence: bool,
 }
 
+/// Called with the index and box of each region as the sidecar reports it
+/// in streaming mode, along with the number of text lines it detected.
+/// That count comes before the first region; lines without text are
+/// skipped, so it is an upper bound on the regions still to come.
+pub type OcrRegionCallback = Box<dyn Fn(usize, Option<usize>, &OcrBox) + Send + Sync>;
+
 #[derive(Debug, Clone)]
 pub struct OcrExecutionResult {
     pub boxes: Vec<OcrBox>,
@@ -44,13 +64,93 @@ pub struct OcrExecutionResult {
     pub raw_text: String,
 }
 
+/// Resource limits for OCR jobs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OcrLimits {
+    pub timeout_secs: u64,
+    /// Unix nice value overriding the one from the request's priority
+    /// policy. On Windows, any positive value runs the sidecar below normal
+    /// priority and 15 or more runs it at idle priority.
+    pub niceness: Option<i32>,
+    /// Value for `OMP_NUM_THREADS` and the other math library thread counts.
+    pub threads: u32,
+    /// Memory cap in MiB, enforced with a Job Object on Windows and
+    /// `RLIMIT_AS` on Linux. Not enforced on macOS.
+    pub memory_limit_mb: Option<u64>,
+    /// Jobs running at once, across all chats.
+    pub max_concurrent_jobs: u32,
+}
+
+impl Default for OcrLimits {
+    /// `SQUIGIT_OCR_TIMEOUT_SECS` overrides the default timeout.
+    fn default() -> Self {
+        Self {
+            timeout_secs: get_ocr_timeout_secs(),
+            niceness: None,
+            threads: OCR_THREADS_DEFAULT,
+            memory_limit_mb: None,
+            max_concurrent_jobs: OCR_CONCURRENT_JOBS_DEFAULT,
+        }
+    }
+}
+
+impl OcrLimits {
+    pub fn validate(&self) -> Result<(), String> {
+        if !OCR_TIMEOUT_SECS_RANGE.contains(&self.timeout_secs) {
+            return Err(format!(
+                "OCR timeout must be between {} and {} seconds",
+                OCR_TIMEOUT_SECS_RANGE.start(),
+                OCR_TIMEOUT_SECS_RANGE.end()
+            ));
+        }
+        if self
+            .niceness
+            .is_some_and(|niceness| !OCR_NICENESS_RANGE.contains(&niceness))
+        {
+            return Err(format!(
+                "OCR niceness must be between {} and {}",
+                OCR_NICENESS_RANGE.start(),
+                OCR_NICENESS_RANGE.end()
+            ));
+        }
+        if self.threads == 0 || self.threads > OCR_THREADS_MAX {
+            return Err(format!(
+                "OCR thread count must be between 1 and {}",
+                OCR_THREADS_MAX
+            ));
+        }
+        if self
+            .memory_limit_mb
+            .is_some_and(|limit| limit < OCR_MEMORY_LIMIT_MIN_MB)
+        {
+            return Err(format!(
+                "OCR memory limit must be at least {} MiB",
+                OCR_MEMORY_LIMIT_MIN_MB
+            ));
+        }
+        if self.max_concurrent_jobs == 0 || self.max_concurrent_jobs > OCR_CONCURRENT_JOBS_MAX {
+            return Err(format!(
+                "Concurrent OCR jobs must be between 1 and {}",
+                OCR_CONCURRENT_JOBS_MAX
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OcrRequest {
+    /// Key for cancelling the job, e.g. the chat ID. A new job with the ID
+    /// of one still queued or running replaces it; without an ID the job
+    /// gets a unique one.
+    pub job_id: Option<String>,
     pub sidecar_path: PathBuf,
     pub runtime_dir: Option<PathBuf>,
     pub image_path: PathBuf,
     pub rec_model_dir_override: Option<PathBuf>,
-    pub timeout_secs: Option<u64>,
+    pub limits: OcrLimits,
+    pub priority: PriorityPolicy,
 }
 
 #[derive(Debug, Error)]
@@ -72,6 +172,25 @@ struct RawOcrResult {
     confidence: Option<f64>,
 }
 
+impl From<RawOcrResult> for OcrBox {
+    fn from(raw: RawOcrResult) -> Self {
+        Self {
+            text: raw.text,
+            box_coords: raw.bounding_box,
+            confidence: raw.confidence.unwrap_or(1.0),
+            low_confidence: false,
+        }
+    }
+}
+
+/// One line of `--stream` output, printed before the final result list.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StreamedLine {
+    Detected { detected: usize },
+    Region { region: RawOcrResult },
+}
+
 #[derive(Debug, Deserialize)]
 struct OcrError {
     error: String,
@@ -79,11 +198,43 @@ struct OcrError {
 
 struct OcrJobHandle {
     child: tokio::process::Child,
+    /// Closing the job kills whatever is left of the sidecar.
+    #[cfg(windows)]
+    _job: Option<windows_job::JobObject>,
 }
 
-#[derive(Default)]
+/// A job from queueing until its sidecar exits.
+struct OcrJob {
+    /// Tells a job from a later one that reuses its ID.
+    token: u64,
+    /// `None` while waiting for a slot.
+    handle: Option<OcrJobHandle>,
+    /// Dropped with the entry, which wakes the job if it is still queued.
+    _queued: oneshot::Sender<()>,
+}
+
+/// Runs OCR jobs, up to [`OcrLimits::max_concurrent_jobs`] at once, each
+/// with its own timeout and cancellable by ID.
 pub struct OcrRuntime {
-    job: Arc<Mutex<Option<OcrJobHandle>>>,
+    jobs: Arc<Mutex<HashMap<String, OcrJob>>>,
+    /// Slot count and the semaphore handing out slots. A changed limit
+    /// gets a new semaphore; jobs holding slots of the old one finish
+    /// under it.
+    slots: std::sync::Mutex<(u32, Arc<Semaphore>)>,
+    next_token: AtomicU64,
+}
+
+impl Default for OcrRuntime {
+    fn default() -> Self {
+        Self {
+            jobs: Arc::default(),
+            slots: std::sync::Mutex::new((
+                OCR_CONCURRENT_JOBS_DEFAULT,
+                Arc::new(Semaphore::new(OCR_CONCURRENT_JOBS_DEFAULT as usize)),
+            )),
+            next_token: AtomicU64::new(1),
+        }
+    }
 }
 
 impl OcrRuntime {
@@ -92,11 +243,81 @@ impl OcrRuntime {
     }
 
     pub async fn run(&self, request: OcrRequest) -> Result<OcrExecutionResult, OcrRuntimeError> {
-        let _guard = ocr_lock().lock().await;
-        let ocr_timeout_secs = request.timeout_secs.unwrap_or_else(get_ocr_timeout_secs);
+        self.execute(request, None).await
+    }
+
+    /// Like [`OcrRuntime::run`], with the sidecar in `--stream` mode:
+    /// `on_region` sees every region as soon as it is recognized. The
+    /// returned result is still built from the sidecar's final list.
+    pub async fn run_streaming(
+        &self,
+        request: OcrRequest,
+        on_region: OcrRegionCallback,
+    ) -> Result<OcrExecutionResult, OcrRuntimeError> {
+        self.execute(request, Some(on_region)).await
+    }
+
+    async fn execute(
+        &self,
+        request: OcrRequest,
+        on_region: Option<OcrRegionCallback>,
+    ) -> Result<OcrExecutionResult, OcrRuntimeError> {
+        request
+            .limits
+            .validate()
+            .map_err(OcrRuntimeError::Message)?;
+
+        let token = self.next_token.fetch_add(1, Ordering::SeqCst);
+        let job_id = request
+            .job_id
+            .clone()
+            .unwrap_or_else(|| format!("job-{}", token));
+        self.cancel_job(&job_id).await;
+        let (queued, cancelled) = oneshot::channel();
+        self.jobs.lock().await.insert(
+            job_id.clone(),
+            OcrJob {
+                token,
+                handle: None,
+                _queued: queued,
+            },
+        );
+
+        let slots = self.slots(request.limits.max_concurrent_jobs);
+        let slot = tokio::select! {
+            slot = slots.acquire_owned() => slot,
+            _ = cancelled => return Err(OcrRuntimeError::Cancelled),
+        };
+        let result = match slot {
+            Ok(_slot) if self.is_current(&job_id, token).await => {
+                self.run_job(&job_id, token, request, on_region).await
+            }
+            Ok(_) => Err(OcrRuntimeError::Cancelled),
+            Err(_) => Err(OcrRuntimeError::Message(
+                "OCR job queue was closed".to_string(),
+            )),
+        };
+
+        // Only a timed-out sidecar is still running here.
+        if let Some(handle) = self.take_job(&job_id, token).await {
+            cancel_job_handle(handle).await;
+        }
+        result
+    }
 
-        // Cancel any lingering previous job (defensive).
-        self.cancel_current_job().await?;
+    async fn run_job(
+        &self,
+        job_id: &str,
+        token: u64,
+        request: OcrRequest,
+        on_region: Option<OcrRegionCallback>,
+    ) -> Result<OcrExecutionResult, OcrRuntimeError> {
+        let limits = request.limits;
+        let ocr_timeout_secs = limits.timeout_secs;
+        let priority = match limits.niceness {
+            Some(niceness) => request.priority.with_niceness(niceness),
+            None => request.priority,
+        };
 
         let mut cmd = tokio::process::Command::new(&request.sidecar_path);
         cmd.arg(&request.image_path)
@@ -108,42 +329,51 @@ impl OcrRuntime {
             cmd.arg("--rec-model-dir").arg(rec_model_dir);
         }
 
+        if on_region.is_some() {
+            cmd.arg("--stream");
+        }
+
         if let Some(ref dir) = request.runtime_dir {
             cmd.current_dir(dir);
         }
 
-        cmd.env("OMP_NUM_THREADS", "1")
-            .env("OPENBLAS_NUM_THREADS", "1")
-            .env("MKL_NUM_THREADS", "1")
-            .env("NUMEXPR_NUM_THREADS", "1")
+        let threads = limits.threads.to_string();
+        cmd.env("OMP_NUM_THREADS", &threads)
+            .env("OPENBLAS_NUM_THREADS", &threads)
+            .env("MKL_NUM_THREADS", &threads)
+            .env("NUMEXPR_NUM_THREADS", &threads)
             .env("OMP_WAIT_POLICY", "PASSIVE");
         apply_runtime_lib_env(&mut cmd, request.runtime_dir.as_deref());
 
         #[cfg(windows)]
         {
             const CREATE_NO_WINDOW: u32 = 0x08000000;
-            const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x00004000;
-            cmd.creation_flags(CREATE_NO_WINDOW | BELOW_NORMAL_PRIORITY_CLASS);
+            cmd.creation_flags(CREATE_NO_WINDOW | sys_process_priority::priority_class(&priority));
         }
 
         #[cfg(unix)]
         {
+            #[cfg(target_os = "linux")]
+            let memory_limit_bytes = limits
+                .memory_limit_mb
+                .map(|limit| limit.saturating_mul(1024 * 1024));
             unsafe {
-                cmd.pre_exec(|| {
+                cmd.pre_exec(move || {
                     if libc::setsid() == -1 {
                         return Err(io::Error::last_os_error());
                     }
 
-                    libc::nice(10);
+                    sys_process_priority::apply_to_current_process(&priority)?;
 
                     #[cfg(target_os = "linux")]
-                    {
-                        libc::syscall(
-                            libc::SYS_ioprio_set,
-                            1, /* IOPRIO_WHO_PROCESS */
-                            0,
-                            (2 << 13) | 7,
-                        );
+                    if let Some(bytes) = memory_limit_bytes {
+                        let limit = libc::rlimit {
+                            rlim_cur: bytes,
+                            rlim_max: bytes,
+                        };
+                        if libc::setrlimit(libc::RLIMIT_AS, &limit) == -1 {
+                            return Err(io::Error::last_os_error());
+                        }
                     }
                     Ok(())
                 });
@@ -163,51 +393,87 @@ impl OcrRuntime {
             }
         };
 
+        // Older Windows builds lack EcoQoS; the priority class still applies.
+        #[cfg(windows)]
+        if let Some(pid) = child.id() {
+            let _ = sys_process_priority::apply_to_process(pid, &priority);
+        }
+
         let stdout_pipe = child.stdout.take();
         let stderr_pipe = child.stderr.take();
-        let stdout_task = tokio::spawn(read_pipe_to_string(stdout_pipe));
+        let stdout_task = match on_region {
+            Some(on_region) => tokio::spawn(read_streamed_stdout(stdout_pipe, on_region)),
+            None => tokio::spawn(read_pipe_to_string(stdout_pipe)),
+        };
         let stderr_task = tokio::spawn(read_stderr_to_string(stderr_pipe));
 
-        {
-            let mut job_lock = self.job.lock().await;
-            *job_lock = Some(OcrJobHandle { child });
+        // The sidecar starts before it can be assigned, so the cap applies
+        // from its first milliseconds onward rather than strictly from launch.
+        #[cfg(windows)]
+        let job = match limits.memory_limit_mb {
+            Some(limit) => match windows_job::JobObject::with_memory_limit(&child, limit) {
+                Ok(job) => Some(job),
+                Err(e) => {
+                    let _ = child.start_kill();
+                    return Err(OcrRuntimeError::Message(format!(
+                        "Failed to apply OCR memory limit: {}",
+                        e
+                    )));
+                }
+            },
+            None => None,
+        };
+
+        let handle = OcrJobHandle {
+            child,
+            #[cfg(windows)]
+            _job: job,
+        };
+        // Cancelled while the sidecar was starting.
+        let unregistered = match self.jobs.lock().await.get_mut(job_id) {
+            Some(entry) if entry.token == token => {
+                entry.handle = Some(handle);
+                None
+            }
+            _ => Some(handle),
+        };
+        if let Some(handle) = unregistered {
+            cancel_job_handle(handle).await;
+            return Err(OcrRuntimeError::Cancelled);
         }
 
         let exit_status = {
             let wait_result = timeout(Duration::from_secs(ocr_timeout_secs), async {
                 loop {
-                    let mut job_lock = self.job.lock().await;
-                    if let Some(ref mut handle) = *job_lock {
-                        match handle.child.try_wait() {
-                            Ok(Some(status)) => return Ok(status),
-                            Ok(None) => {
-                                drop(job_lock);
-                                tokio::time::sleep(Duration::from_millis(50)).await;
-                            }
-                            Err(e) => {
-                                return Err(OcrRuntimeError::Message(format!(
-                                    "Failed to wait for sidecar: {}",
-                                    e
-                                )));
-                            }
-                        }
-                    } else {
+                    let mut jobs = self.jobs.lock().await;
+                    let Some(handle) = jobs
+                        .get_mut(job_id)
+                        .filter(|entry| entry.token == token)
+                        .and_then(|entry| entry.handle.as_mut())
+                    else {
                         return Err(OcrRuntimeError::Cancelled);
+                    };
+                    match handle.child.try_wait() {
+                        Ok(Some(status)) => return Ok(status),
+                        Ok(None) => {
+                            drop(jobs);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                        }
+                        Err(e) => {
+                            return Err(OcrRuntimeError::Message(format!(
+                                "Failed to wait for sidecar: {}",
+                                e
+                            )));
+                        }
                     }
                 }
             })
             .await;
 
-            {
-                let mut job_lock = self.job.lock().await;
-                *job_lock = None;
-            }
-
             match wait_result {
                 Ok(Ok(status)) => status,
                 Ok(Err(err)) => return Err(err),
                 Err(_) => {
-                    let _ = self.cancel_current_job().await;
                     return Err(OcrRuntimeError::Message(format!(
                         "OCR timed out after {}s. The image may be too large or complex. \
                          The process has been terminated to protect system stability.",
@@ -280,14 +546,7 @@ impl OcrRuntime {
                 ))
             })?;
 
-        let boxes: Vec<OcrBox> = raw_results
-            .into_iter()
-            .map(|r| OcrBox {
-                text: r.text,
-                box_coords: r.bounding_box,
-                confidence: r.confidence.unwrap_or(1.0),
-            })
-            .collect();
+        let boxes: Vec<OcrBox> = raw_results.into_iter().map(OcrBox::from).collect();
 
         let raw_text = flatten_raw_text(&boxes);
         Ok(OcrExecutionResult {
@@ -297,20 +556,92 @@ impl OcrRuntime {
         })
     }
 
-    pub async fn cancel_current_job(&self) -> Result<(), OcrRuntimeError> {
-        let handle = {
-            let mut job_lock = self.job.lock().await;
-            job_lock.take()
+    /// Cancel a queued or running job. Returns whether there was one.
+    pub async fn cancel_job(&self, job_id: &str) -> bool {
+        let Some(job) = self.jobs.lock().await.remove(job_id) else {
+            return false;
         };
+        if let Some(handle) = job.handle {
+            cancel_job_handle(handle).await;
+        }
+        true
+    }
 
-        if let Some(handle) = handle {
+    /// Cancel every queued and running job. Returns how many there were.
+    pub async fn cancel_all_jobs(&self) -> usize {
+        let jobs: Vec<OcrJob> = self.jobs.lock().await.drain().map(|(_, job)| job).collect();
+        let count = jobs.len();
+        for handle in jobs.into_iter().filter_map(|job| job.handle) {
             cancel_job_handle(handle).await;
         }
+        count
+    }
 
-        Ok(())
+    /// IDs of the queued and running jobs.
+    pub async fn job_ids(&self) -> Vec<String> {
+        self.jobs.lock().await.keys().cloned().collect()
+    }
+
+    fn slots(&self, limit: u32) -> Arc<Semaphore> {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        if slots.0 != limit {
+            *slots = (limit, Arc::new(Semaphore::new(limit as usize)));
+        }
+        slots.1.clone()
+    }
+
+    async fn is_current(&self, job_id: &str, token: u64) -> bool {
+        self.jobs
+            .lock()
+            .await
+            .get(job_id)
+            .is_some_and(|job| job.token == token)
+    }
+
+    /// Remove the job if it is still this one, returning its sidecar.
+    async fn take_job(&self, job_id: &str, token: u64) -> Option<OcrJobHandle> {
+        let mut jobs = self.jobs.lock().await;
+        if !jobs.get(job_id).is_some_and(|job| job.token == token) {
+            return None;
+        }
+        jobs.remove(job_id)?.handle
     }
 }
 
+/// Flag boxes whose confidence is below `min_confidence`.
+/// When `drop_low_confidence` is set, flagged boxes are removed instead.
+/// The returned summary always counts the boxes as they came from the engine.
+pub fn apply_min_confidence(
+    boxes: Vec<OcrBox>,
+    min_confidence: f64,
+    drop_low_confidence: bool,
+) -> (Vec<OcrBox>, OcrConfidenceSummary) {
+    let total_regions = boxes.len();
+    let mut low_confidence_regions = 0usize;
+
+    let boxes = boxes
+        .into_iter()
+        .filter_map(|mut entry| {
+            entry.low_confidence = entry.confidence < min_confidence;
+            if entry.low_confidence {
+                low_confidence_regions += 1;
+                if drop_low_confidence {
+                    return None;
+                }
+            }
+            Some(entry)
+        })
+        .collect();
+
+    (
+        boxes,
+        OcrConfidenceSummary {
+            total_regions,
+            low_confidence_regions,
+        },
+    )
+}
+
 pub fn boxes_to_storage_regions(boxes: &[OcrBox]) -> Vec<OcrRegion> {
     boxes
         .iter()
@@ -325,6 +656,10 @@ pub fn boxes_to_storage_regions(boxes: &[OcrBox]) -> Vec<OcrRegion> {
                     vec![x, y]
                 })
                 .collect(),
+            confidence: Some(entry.confidence),
+            low_confidence: entry.low_confidence,
+            latex: None,
+            pii: Vec::new(),
         })
         .collect()
 }
@@ -449,6 +784,70 @@ async fn cancel_job_handle(mut handle: OcrJobHandle) {
     }
 }
 
+#[cfg(windows)]
+mod windows_job {
+    use std::io;
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+    };
+
+    pub(super) struct JobObject(HANDLE);
+
+    // SAFETY: The handle is owned and only closed on drop.
+    unsafe impl Send for JobObject {}
+
+    impl JobObject {
+        pub(super) fn with_memory_limit(
+            child: &tokio::process::Child,
+            limit_mb: u64,
+        ) -> io::Result<Self> {
+            let process = child
+                .raw_handle()
+                .ok_or_else(|| io::Error::other("sidecar already exited"))?;
+
+            // SAFETY: Plain Win32 calls on handles we own; the job handle is
+            // wrapped before any early return so it is always closed.
+            unsafe {
+                let handle = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+                if handle.is_null() {
+                    return Err(io::Error::last_os_error());
+                }
+                let job = Self(handle);
+
+                let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+                info.BasicLimitInformation.LimitFlags =
+                    JOB_OBJECT_LIMIT_PROCESS_MEMORY | JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+                info.ProcessMemoryLimit = (limit_mb.saturating_mul(1024 * 1024)) as usize;
+                if SetInformationJobObject(
+                    job.0,
+                    JobObjectExtendedLimitInformation,
+                    &info as *const _ as *const std::ffi::c_void,
+                    std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                ) == 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+                if AssignProcessToJobObject(job.0, process as HANDLE) == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(job)
+            }
+        }
+    }
+
+    impl Drop for JobObject {
+        fn drop(&mut self) {
+            // SAFETY: The handle came from CreateJobObjectW and is closed once.
+            unsafe {
+                CloseHandle(self.0);
+            }
+        }
+    }
+}
+
 async fn read_pipe_to_string(pipe: Option<tokio::process::ChildStdout>) -> String {
     if let Some(mut pipe) = pipe {
         let mut buf = Vec::new();
@@ -459,6 +858,51 @@ async fn read_pipe_to_string(pipe: Option<tokio::process::ChildStdout>) -> Strin
     }
 }
 
+/// Read stdout line by line, handing each streamed region to `on_region`
+/// as soon as its line arrives. Returns everything read, like
+/// [`read_pipe_to_string`].
+async fn read_streamed_stdout(
+    pipe: Option<tokio::process::ChildStdout>,
+    on_region: OcrRegionCallback,
+) -> String {
+    let Some(pipe) = pipe else {
+        return String::new();
+    };
+    let mut reader = BufReader::new(pipe);
+    let mut stdout = String::new();
+    let mut line = Vec::new();
+    let mut index = 0;
+    let mut detected = None;
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let text = String::from_utf8_lossy(&line);
+        match parse_streamed_line(&text) {
+            Some(StreamedLine::Detected { detected: count }) => detected = Some(count),
+            Some(StreamedLine::Region { region }) => {
+                on_region(index, detected, &region.into());
+                index += 1;
+            }
+            None => {}
+        }
+        stdout.push_str(&text);
+    }
+    stdout
+}
+
+fn parse_streamed_line(line: &str) -> Option<StreamedLine> {
+    // Serde also reads structs from arrays, which a one-region final list
+    // would otherwise match.
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    serde_json::from_str(line).ok()
+}
+
 async fn read_stderr_to_string(pipe: Option<tokio::process::ChildStderr>) -> String {
     if let Some(mut pipe) = pipe {
         let mut buf = Vec::new();
@@ -469,7 +913,7 @@ async fn read_stderr_to_string(pipe: Option<tokio::process::ChildStderr>) -> Str
     }
 }
 
-fn extract_json_payload(raw: &str) -> Option<String> {
+pub(crate) fn extract_json_payload(raw: &str) -> Option<String> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return None;
@@ -524,7 +968,10 @@ fn flatten_raw_text(boxes: &[OcrBox]) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{boxes_to_storage_regions, extract_json_payload, flatten_raw_text, OcrBox};
+    use super::{
+        apply_min_confidence, boxes_to_storage_regions, extract_json_payload, flatten_raw_text,
+        parse_streamed_line, OcrBox, OcrLimits, StreamedLine,
+    };
 
     #[test]
     fn json_payload_extraction_handles_noisy_stdout() {
@@ -533,6 +980,36 @@ mod tests {
         assert!(payload.starts_with('['));
     }
 
+    #[test]
+    fn streamed_output_yields_regions_and_final_payload() {
+        let raw = [
+            r#"{"detected":3}"#,
+            r#"{"region":{"text":"Hi","box":[[0,0],[1,0],[1,1],[0,1]],"confidence":0.8}}"#,
+            r#"{"region":{"text":"there","box":[[0,2],[1,2],[1,3],[0,3]]}}"#,
+            r#"[{"text":"Hi","box":[]},{"text":"there","box":[]}]"#,
+        ]
+        .join("\n");
+
+        let lines: Vec<StreamedLine> = raw.lines().filter_map(parse_streamed_line).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(matches!(lines[0], StreamedLine::Detected { detected: 3 }));
+        let regions: Vec<OcrBox> = lines
+            .into_iter()
+            .filter_map(|line| match line {
+                StreamedLine::Region { region } => Some(region.into()),
+                StreamedLine::Detected { .. } => None,
+            })
+            .collect();
+        assert_eq!(regions[0].text, "Hi");
+        assert_eq!(regions[0].confidence, 0.8);
+        assert_eq!(regions[1].confidence, 1.0);
+
+        assert!(parse_streamed_line(r#"[{"text":"Hi","box":[]}]"#).is_none());
+        assert!(parse_streamed_line(r#"[3]"#).is_none());
+        assert!(parse_streamed_line(r#"{"error":"boom"}"#).is_none());
+        assert!(extract_json_payload(&raw).unwrap().starts_with('['));
+    }
+
     #[test]
     fn raw_text_is_ordered_trimmed_and_newline_joined() {
         let boxes = vec![
@@ -540,16 +1017,19 @@ mod tests {
                 text: "  Hello  ".to_string(),
                 box_coords: vec![],
                 confidence: 1.0,
+                low_confidence: false,
             },
             OcrBox {
                 text: " ".to_string(),
                 box_coords: vec![],
                 confidence: 1.0,
+                low_confidence: false,
             },
             OcrBox {
                 text: "World".to_string(),
                 box_coords: vec![],
                 confidence: 1.0,
+                low_confidence: false,
             },
         ];
 
@@ -562,11 +1042,156 @@ mod tests {
             text: "A".to_string(),
             box_coords: vec![vec![1.49, 2.5], vec![3.0, 4.0]],
             confidence: 0.9,
+            low_confidence: false,
         }];
 
         let regions = boxes_to_storage_regions(&boxes);
         assert_eq!(regions.len(), 1);
         assert_eq!(regions[0].text, "A");
         assert_eq!(regions[0].bbox, vec![vec![1, 3], vec![3, 4]]);
+        assert_eq!(regions[0].confidence, Some(0.9));
+    }
+
+    #[test]
+    fn min_confidence_flags_or_drops_weak_boxes() {
+        let make = |text: &str, confidence: f64| OcrBox {
+            text: text.to_string(),
+            box_coords: vec![],
+            confidence,
+            low_confidence: false,
+        };
+        let boxes = vec![
+            make("sharp", 0.95),
+            make("blurry", 0.3),
+            make("faint", 0.49),
+        ];
+
+        let (flagged, summary) = apply_min_confidence(boxes.clone(), 0.5, false);
+        assert_eq!(flagged.len(), 3);
+        assert!(!flagged[0].low_confidence);
+        assert!(flagged[1].low_confidence && flagged[2].low_confidence);
+        assert_eq!(summary.low_confidence_regions, 2);
+        assert_eq!(
+            summary.describe().as_deref(),
+            Some("2 regions below threshold")
+        );
+
+        let (kept, summary) = apply_min_confidence(boxes, 0.5, true);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].text, "sharp");
+        assert_eq!(summary.total_regions, 3);
+    }
+
+    #[test]
+    fn limits_validation_rejects_out_of_range_values() {
+        assert!(OcrLimits::default().validate().is_ok());
+
+        let with = |edit: fn(&mut OcrLimits)| {
+            let mut limits = OcrLimits::default();
+            edit(&mut limits);
+            limits.validate()
+        };
+        assert!(with(|l| l.timeout_secs = 5).is_err());
+        assert!(with(|l| l.niceness = Some(-5)).is_err());
+        assert!(with(|l| l.threads = 0).is_err());
+        assert!(with(|l| l.memory_limit_mb = Some(128)).is_err());
+        assert!(with(|l| l.max_concurrent_jobs = 0).is_err());
+        assert!(with(|l| l.max_concurrent_jobs = 9).is_err());
+        assert!(with(|l| {
+            l.timeout_secs = 600;
+            l.threads = 8;
+            l.niceness = Some(0);
+            l.memory_limit_mb = Some(4096);
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn limits_fill_missing_fields_with_defaults() {
+        let limits: OcrLimits = serde_json::from_str(r#"{"threads":4}"#).unwrap();
+        assert_eq!(limits.threads, 4);
+        assert_eq!(limits.niceness, OcrLimits::default().niceness);
+        assert_eq!(limits.memory_limit_mb, None);
+        assert_eq!(limits.max_concurrent_jobs, 2);
+    }
+
+    #[cfg(unix)]
+    mod jobs {
+        use super::super::{OcrRequest, OcrRuntime, OcrRuntimeError};
+        use super::*;
+        use std::os::unix::fs::PermissionsExt;
+        use std::path::Path;
+        use std::sync::Arc;
+        use std::time::Duration;
+        use sys_process_priority::{PowerProfile, SidecarRole};
+
+        fn sleeping_sidecar(root: &Path) -> std::path::PathBuf {
+            let path = root.join("ocr.sh");
+            std::fs::write(&path, "#!/bin/sh\nsleep 30\n").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        }
+
+        fn request(sidecar: &Path, job_id: &str) -> OcrRequest {
+            OcrRequest {
+                job_id: Some(job_id.to_string()),
+                sidecar_path: sidecar.to_path_buf(),
+                runtime_dir: None,
+                image_path: "/tmp/image.png".into(),
+                rec_model_dir_override: None,
+                limits: OcrLimits {
+                    max_concurrent_jobs: 1,
+                    ..OcrLimits::default()
+                },
+                priority: PowerProfile::Balanced.policy(SidecarRole::Background),
+            }
+        }
+
+        async fn wait_for_jobs(runtime: &OcrRuntime, count: usize) {
+            for _ in 0..100 {
+                if runtime.job_ids().await.len() == count {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            panic!("expected {} OCR jobs", count);
+        }
+
+        #[tokio::test]
+        async fn jobs_are_cancelled_by_id_while_running_or_queued() {
+            let root = tempfile::tempdir().unwrap();
+            let sidecar = sleeping_sidecar(root.path());
+            let runtime = Arc::new(OcrRuntime::new());
+
+            let running = tokio::spawn({
+                let runtime = runtime.clone();
+                let request = request(&sidecar, "chat-a");
+                async move { runtime.run(request).await }
+            });
+            wait_for_jobs(&runtime, 1).await;
+            let queued = tokio::spawn({
+                let runtime = runtime.clone();
+                let request = request(&sidecar, "chat-b");
+                async move { runtime.run(request).await }
+            });
+            wait_for_jobs(&runtime, 2).await;
+
+            assert!(runtime.cancel_job("chat-b").await);
+            let queued = tokio::time::timeout(Duration::from_secs(5), queued)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(matches!(queued, Err(OcrRuntimeError::Cancelled)));
+            assert!(!running.is_finished());
+
+            assert_eq!(runtime.cancel_all_jobs().await, 1);
+            let running = tokio::time::timeout(Duration::from_secs(5), running)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(matches!(running, Err(OcrRuntimeError::Cancelled)));
+            assert!(runtime.job_ids().await.is_empty());
+            assert!(!runtime.cancel_job("chat-a").await);
+        }
     }
 }
@@ -28,6 +28,7 @@ fn cli_style_ocr_write_is_renderable_through_shared_chat_storage_frame() {
             vec![10.3, 40.2],
         ],
         confidence: 0.99,
+        low_confidence: false,
     }];
     persist_boxes_to_chat_storage(&storage, &metadata.id, "pp-ocr-v5-en", &boxes)
         .expect("save ocr frame");
@@ -0,0 +1,32 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LocalApiError {
+    #[error("Failed to start local API on {addr}: {message}")]
+    Bind { addr: String, message: String },
+}
+
+pub type Result<T> = std::result::Result<T, LocalApiError>;
+
+/// A request that failed, answered as `{"error": code}` with `status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: u16,
+    pub code: String,
+}
+
+impl ApiError {
+    pub fn new(status: u16, code: impl Into<String>) -> Self {
+        Self {
+            status,
+            code: code.into(),
+        }
+    }
+
+    pub fn bad_request(code: impl Into<String>) -> Self {
+        Self::new(400, code)
+    }
+}
@@ -0,0 +1,457 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Service: Local API
+//!
+//! A small HTTP server on `127.0.0.1` for companion apps, starting with the
+//! browser extension, which sends page screenshots here instead of going
+//! through an OS-level capture.
+//!
+//! Every request needs `Authorization: Bearer <token>`. Requests from web
+//! pages are refused: an `Origin` header, if present, must belong to a
+//! browser extension, and `Host` must name the loopback address so DNS
+//! rebinding can't reach the server.
+//!
+//! - `GET /v1/health` reports the app version
+//! - `POST /v1/browser/capture` creates a chat, see [`browser`]
+//!
+//! Usage:
+//! ```ignore
+//! let server = LocalApiServer::start(LocalApiConfig { port, token }, handler)?;
+//! // ...
+//! server.stop();
+//! ```
+
+pub mod browser;
+pub mod error;
+
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+
+pub use browser::BrowserCapture;
+pub use error::{ApiError, LocalApiError, Result};
+
+/// Port used when the user has not picked one.
+pub const DEFAULT_PORT: u16 = 47615;
+
+/// Largest accepted request body. Full-page PNGs of long pages get big.
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const EXTENSION_ORIGIN_SCHEMES: &[&str] = &[
+    "chrome-extension://",
+    "moz-extension://",
+    "safari-web-extension://",
+];
+
+#[derive(Debug, Clone)]
+pub struct LocalApiConfig {
+    /// Port on `127.0.0.1`; `0` picks a free one.
+    pub port: u16,
+    /// Bearer token companions must send.
+    pub token: String,
+}
+
+/// What the app does with accepted requests. Called on the server thread.
+pub trait LocalApiHandler: Send + Sync + 'static {
+    /// Create a chat from a browser capture and return its ID.
+    fn browser_capture(&self, capture: BrowserCapture) -> std::result::Result<String, String>;
+}
+
+/// A running server. Stopped when dropped.
+pub struct LocalApiServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LocalApiServer {
+    pub fn start(config: LocalApiConfig, handler: Arc<dyn LocalApiHandler>) -> Result<Self> {
+        let bind_addr = format!("127.0.0.1:{}", config.port);
+        let bind_error = |message: String| LocalApiError::Bind {
+            addr: bind_addr.clone(),
+            message,
+        };
+        let server = Server::http(&bind_addr).map_err(|e| bind_error(e.to_string()))?;
+        let addr = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| bind_error("not an IP listener".to_string()))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = std::thread::Builder::new()
+            .name("local-api".to_string())
+            .spawn(move || {
+                let router = Router {
+                    port: addr.port(),
+                    token: config.token,
+                    handler,
+                };
+                while !thread_stop.load(Ordering::SeqCst) {
+                    match server.recv_timeout(POLL_INTERVAL) {
+                        Ok(Some(request)) => router.handle(request),
+                        Ok(None) => {}
+                        Err(e) => {
+                            log::warn!("Local API stopped: {}", e);
+                            break;
+                        }
+                    }
+                }
+            })
+            .map_err(|e| bind_error(e.to_string()))?;
+
+        log::info!("Local API listening on {}", addr);
+        Ok(Self {
+            addr,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for LocalApiServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+struct Router {
+    port: u16,
+    token: String,
+    handler: Arc<dyn LocalApiHandler>,
+}
+
+impl Router {
+    fn handle(&self, mut request: Request) {
+        let origin = header(&request, "Origin");
+        if origin
+            .as_deref()
+            .is_some_and(|origin| !is_extension_origin(origin))
+        {
+            respond(
+                request,
+                None,
+                Err(ApiError::new(403, "ERR_ORIGIN_NOT_ALLOWED")),
+            );
+            return;
+        }
+        let origin = origin.as_deref();
+        if !self.is_loopback_host(header(&request, "Host").as_deref()) {
+            respond(
+                request,
+                origin,
+                Err(ApiError::new(403, "ERR_HOST_NOT_ALLOWED")),
+            );
+            return;
+        }
+
+        // Preflight requests carry no credentials.
+        if *request.method() == Method::Options {
+            let _ = request.respond(with_cors(
+                Response::from_string("").with_status_code(StatusCode(204)),
+                origin,
+            ));
+            return;
+        }
+
+        if !self.is_authorized(header(&request, "Authorization").as_deref()) {
+            respond(request, origin, Err(ApiError::new(401, "ERR_UNAUTHORIZED")));
+            return;
+        }
+
+        let path = request
+            .url()
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let result = match (request.method(), path.as_str()) {
+            (Method::Get, "/v1/health") => Ok(serde_json::json!({
+                "status": "ok",
+                "version": env!("CARGO_PKG_VERSION"),
+            })),
+            (Method::Post, "/v1/browser/capture") => read_body(&mut request)
+                .and_then(|body| BrowserCapture::from_json(&body))
+                .and_then(|capture| {
+                    self.handler.browser_capture(capture).map_err(|e| {
+                        log::warn!("Browser capture failed: {}", e);
+                        ApiError::new(500, "ERR_CAPTURE_FAILED")
+                    })
+                })
+                .map(|chat_id| serde_json::json!({ "chatId": chat_id })),
+            (_, "/v1/health" | "/v1/browser/capture") => {
+                Err(ApiError::new(405, "ERR_METHOD_NOT_ALLOWED"))
+            }
+            _ => Err(ApiError::new(404, "ERR_NOT_FOUND")),
+        };
+        respond(request, origin, result);
+    }
+
+    fn is_loopback_host(&self, host: Option<&str>) -> bool {
+        let Some((name, port)) = host.and_then(|host| host.rsplit_once(':')) else {
+            return false;
+        };
+        matches!(name, "127.0.0.1" | "localhost") && port.parse() == Ok(self.port)
+    }
+
+    fn is_authorized(&self, authorization: Option<&str>) -> bool {
+        authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.trim().as_bytes(), self.token.as_bytes()))
+    }
+}
+
+fn is_extension_origin(origin: &str) -> bool {
+    EXTENSION_ORIGIN_SCHEMES
+        .iter()
+        .any(|scheme| origin.starts_with(scheme) && origin.len() > scheme.len())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn header(request: &Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str().to_string())
+}
+
+fn read_body(request: &mut Request) -> std::result::Result<Vec<u8>, ApiError> {
+    let too_large = || ApiError::new(413, "ERR_BODY_TOO_LARGE");
+    if request
+        .body_length()
+        .is_some_and(|len| len > MAX_BODY_BYTES)
+    {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|_| ApiError::bad_request("ERR_READ_BODY"))?;
+    if body.len() > MAX_BODY_BYTES {
+        return Err(too_large());
+    }
+    Ok(body)
+}
+
+fn respond(
+    request: Request,
+    origin: Option<&str>,
+    result: std::result::Result<serde_json::Value, ApiError>,
+) {
+    let (status, body) = match result {
+        Ok(body) => (200, body),
+        Err(e) => (e.status, serde_json::json!({ "error": e.code })),
+    };
+    let response = Response::from_string(body.to_string())
+        .with_status_code(StatusCode(status))
+        .with_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap());
+    let _ = request.respond(with_cors(response, origin));
+}
+
+fn with_cors<R: Read>(mut response: Response<R>, origin: Option<&str>) -> Response<R> {
+    let Some(origin) = origin else {
+        return response;
+    };
+    for (field, value) in [
+        ("Access-Control-Allow-Origin", origin),
+        ("Access-Control-Allow-Methods", "GET, POST, OPTIONS"),
+        (
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type",
+        ),
+        ("Vary", "Origin"),
+    ] {
+        if let Ok(header) = Header::from_bytes(field.as_bytes(), value.as_bytes()) {
+            response.add_header(header);
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::sync::Mutex;
+
+    const TOKEN: &str = "secret-token";
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        captures: Mutex<Vec<BrowserCapture>>,
+    }
+
+    impl LocalApiHandler for RecordingHandler {
+        fn browser_capture(&self, capture: BrowserCapture) -> std::result::Result<String, String> {
+            self.captures.lock().unwrap().push(capture);
+            Ok("chat-1".to_string())
+        }
+    }
+
+    fn start() -> (LocalApiServer, Arc<RecordingHandler>) {
+        let handler = Arc::new(RecordingHandler::default());
+        let config = LocalApiConfig {
+            port: 0,
+            token: TOKEN.to_string(),
+        };
+        let server = LocalApiServer::start(config, handler.clone()).expect("start server");
+        (server, handler)
+    }
+
+    /// Send a raw request and return the status code and body.
+    fn send(port: u16, head: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            head,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.to_string())
+            .unwrap_or_default();
+        (status, body)
+    }
+
+    #[test]
+    fn capture_requires_token_and_extension_origin() {
+        let (server, handler) = start();
+        let port = server.port();
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend_from_slice(b"pixels");
+        let body = serde_json::json!({
+            "image": base64::engine::general_purpose::STANDARD.encode(&png),
+            "url": "https://example.com/post",
+            "selectedText": "quoted",
+        })
+        .to_string();
+        let request = |extra: &str| {
+            format!(
+                "POST /v1/browser/capture HTTP/1.1\r\nHost: 127.0.0.1:{}\r\n\
+                Content-Type: application/json{}",
+                port, extra
+            )
+        };
+
+        let (status, _) = send(port, &request(""), &body);
+        assert_eq!(status, 401);
+
+        let (status, _) = send(
+            port,
+            &request(&format!(
+                "\r\nAuthorization: Bearer {}\r\nOrigin: https://evil.example",
+                TOKEN
+            )),
+            &body,
+        );
+        assert_eq!(status, 403);
+        assert!(handler.captures.lock().unwrap().is_empty());
+
+        let (status, response) = send(
+            port,
+            &request(&format!(
+                "\r\nAuthorization: Bearer {}\r\nOrigin: chrome-extension://abcdef",
+                TOKEN
+            )),
+            &body,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(response, r#"{"chatId":"chat-1"}"#);
+
+        let captures = handler.captures.lock().unwrap();
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].image, png);
+        assert_eq!(captures[0].selected_text.as_deref(), Some("quoted"));
+        drop(captures);
+        server.stop();
+    }
+
+    #[test]
+    fn rejects_foreign_host_and_unknown_routes() {
+        let (server, _) = start();
+        let port = server.port();
+        let auth = format!("Authorization: Bearer {}", TOKEN);
+
+        let (status, _) = send(
+            port,
+            &format!(
+                "GET /v1/health HTTP/1.1\r\nHost: attacker.example:{}\r\n{}",
+                port, auth
+            ),
+            "",
+        );
+        assert_eq!(status, 403);
+
+        let (status, body) = send(
+            port,
+            &format!(
+                "GET /v1/health HTTP/1.1\r\nHost: localhost:{}\r\n{}",
+                port, auth
+            ),
+            "",
+        );
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""status":"ok""#));
+
+        let (status, _) = send(
+            port,
+            &format!(
+                "GET /v1/chats HTTP/1.1\r\nHost: localhost:{}\r\n{}",
+                port, auth
+            ),
+            "",
+        );
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn answers_extension_preflight_without_token() {
+        let (server, _) = start();
+        let port = server.port();
+        let (status, _) = send(
+            port,
+            &format!(
+                "OPTIONS /v1/browser/capture HTTP/1.1\r\nHost: 127.0.0.1:{}\r\n\
+                Origin: moz-extension://1234",
+                port
+            ),
+            "",
+        );
+        assert_eq!(status, 204);
+    }
+}
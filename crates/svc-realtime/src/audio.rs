@@ -0,0 +1,74 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion of captured microphone audio to the 16 kHz mono PCM the
+//! Live API accepts.
+
+use crate::protocol::INPUT_SAMPLE_RATE;
+
+/// Downmixes interleaved float samples and resamples them linearly to
+/// 16 kHz. Keeps its phase across calls so chunk boundaries do not click.
+#[derive(Debug, Clone)]
+pub struct InputResampler {
+    channels: usize,
+    step: f64,
+    position: f64,
+    previous: Option<f32>,
+}
+
+impl InputResampler {
+    pub fn new(source_rate: u32, channels: u16) -> Self {
+        Self {
+            channels: usize::from(channels.max(1)),
+            step: f64::from(source_rate.max(1)) / f64::from(INPUT_SAMPLE_RATE),
+            position: 0.0,
+            previous: None,
+        }
+    }
+
+    pub fn process(&mut self, interleaved: &[f32]) -> Vec<i16> {
+        let mut samples: Vec<f32> = Vec::with_capacity(interleaved.len() / self.channels + 1);
+        samples.extend(self.previous);
+        samples.extend(
+            interleaved
+                .chunks_exact(self.channels)
+                .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32),
+        );
+        if samples.len() < 2 {
+            self.previous = samples.last().copied();
+            return Vec::new();
+        }
+
+        let mut out = Vec::new();
+        let last = (samples.len() - 1) as f64;
+        while self.position < last {
+            let index = self.position as usize;
+            let frac = (self.position - index as f64) as f32;
+            let value = samples[index] * (1.0 - frac) + samples[index + 1] * frac;
+            out.push((value.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16);
+            self.position += self.step;
+        }
+        // The last sample starts the next chunk.
+        self.position -= last;
+        self.previous = samples.last().copied();
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputResampler;
+
+    #[test]
+    fn stereo_48k_becomes_mono_16k() {
+        let mut resampler = InputResampler::new(48_000, 2);
+        let chunk: Vec<f32> = [0.5, -0.5].repeat(480);
+
+        let first = resampler.process(&chunk);
+        let second = resampler.process(&chunk);
+        // 10 ms per chunk at 16 kHz, give or take the sample carried over.
+        assert!((159..=161).contains(&first.len()));
+        assert_eq!(first.len() + second.len(), 320);
+        assert!(first.iter().chain(&second).all(|&sample| sample == 0));
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Service: Realtime
+//!
+//! Bidirectional voice sessions with the Gemini Live API over a WebSocket.
+//! Microphone audio and screen frames stream up; transcripts and spoken
+//! reply audio stream back as [`RealtimeEvent`]s.
+//!
+//! Usage:
+//! ```ignore
+//! let (session, mut events) = RealtimeSession::connect(RealtimeConfig::new(key, model)).await?;
+//! let input = session.sender();
+//! input.send_image(&png, "image/png").await?;
+//! input.send_audio(&pcm_16k).await?;
+//! while let Some(event) = events.recv().await {
+//!    // Play audio, show transcripts
+//! }
+//! session.close().await;
+//! ```
+
+pub mod audio;
+pub mod protocol;
+
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+pub use audio::InputResampler;
+pub use protocol::{RealtimeEvent, INPUT_SAMPLE_RATE, OUTPUT_SAMPLE_RATE};
+
+/// Live model used when the caller does not pick one.
+pub const DEFAULT_MODEL: &str = "gemini-2.0-flash-live-001";
+
+/// How long `close` waits for the socket to shut down cleanly.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, thiserror::Error)]
+pub enum RealtimeError {
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+    #[error("Session closed")]
+    Closed,
+}
+
+pub type Result<T> = std::result::Result<T, RealtimeError>;
+
+/// Settings sent in the session's setup message.
+#[derive(Debug, Clone)]
+pub struct RealtimeConfig {
+    pub api_key: String,
+    pub model: String,
+    pub system_instruction: Option<String>,
+    /// Prebuilt voice name, e.g. "Puck". The server default when `None`.
+    pub voice: Option<String>,
+    /// Ask for transcripts of both sides of the conversation.
+    pub transcribe: bool,
+}
+
+impl RealtimeConfig {
+    pub fn new(api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            model: model.into(),
+            system_instruction: None,
+            voice: None,
+            transcribe: true,
+        }
+    }
+}
+
+pub struct RealtimeSession {
+    sender: RealtimeSender,
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+/// Cloneable handle for streaming input into a session.
+#[derive(Clone)]
+pub struct RealtimeSender {
+    outgoing: mpsc::Sender<Message>,
+}
+
+impl RealtimeSession {
+    /// Open the socket and send the setup. Events, starting with
+    /// [`RealtimeEvent::Ready`], arrive on the returned receiver, which
+    /// ends after [`RealtimeEvent::Closed`].
+    pub async fn connect(config: RealtimeConfig) -> Result<(Self, mpsc::Receiver<RealtimeEvent>)> {
+        let url = format!("{}?key={}", protocol::LIVE_ENDPOINT, config.api_key);
+        let (mut socket, _) = tokio_tungstenite::connect_async(url)
+            .await
+            .map_err(|e| RealtimeError::WebSocket(e.to_string()))?;
+        socket
+            .send(Message::text(protocol::setup_message(&config).to_string()))
+            .await
+            .map_err(|e| RealtimeError::WebSocket(e.to_string()))?;
+
+        let (outgoing, mut outgoing_rx) = mpsc::channel::<Message>(64);
+        let (shutdown, mut shutdown_rx) = oneshot::channel::<()>();
+        let (tx, rx) = mpsc::channel(256);
+
+        let task = tokio::spawn(async move {
+            let reason = loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => {
+                        let _ = socket.close(None).await;
+                        break None;
+                    }
+                    Some(message) = outgoing_rx.recv() => {
+                        if let Err(e) = socket.send(message).await {
+                            break Some(e.to_string());
+                        }
+                    }
+                    incoming = socket.next() => {
+                        let payload = match incoming {
+                            Some(Ok(Message::Text(text))) => text.to_string(),
+                            Some(Ok(Message::Binary(data))) => {
+                                String::from_utf8_lossy(&data).into_owned()
+                            }
+                            Some(Ok(Message::Close(frame))) => {
+                                break frame
+                                    .map(|frame| frame.reason.to_string())
+                                    .filter(|reason| !reason.is_empty());
+                            }
+                            Some(Ok(_)) => continue,
+                            Some(Err(e)) => break Some(e.to_string()),
+                            None => break None,
+                        };
+                        let events = match protocol::parse_server_message(&payload) {
+                            Ok(events) => events,
+                            Err(e) => {
+                                log::warn!("Ignoring realtime message: {}", e);
+                                continue;
+                            }
+                        };
+                        for event in events {
+                            if tx.send(event).await.is_err() {
+                                let _ = socket.close(None).await;
+                                return;
+                            }
+                        }
+                    }
+                }
+            };
+            let _ = tx.send(RealtimeEvent::Closed { reason }).await;
+        });
+
+        let session = Self {
+            sender: RealtimeSender { outgoing },
+            shutdown,
+            task,
+        };
+        Ok((session, rx))
+    }
+
+    pub fn sender(&self) -> RealtimeSender {
+        self.sender.clone()
+    }
+
+    /// Close the socket and wait briefly for it to shut down.
+    pub async fn close(self) {
+        let Self {
+            shutdown, mut task, ..
+        } = self;
+        let _ = shutdown.send(());
+        if tokio::time::timeout(CLOSE_TIMEOUT, &mut task)
+            .await
+            .is_err()
+        {
+            task.abort();
+        }
+    }
+}
+
+impl RealtimeSender {
+    /// Stream 16 kHz mono microphone audio.
+    pub async fn send_audio(&self, pcm: &[i16]) -> Result<()> {
+        self.send(protocol::audio_message(pcm)).await
+    }
+
+    /// Signal that the microphone stopped.
+    pub async fn end_audio_stream(&self) -> Result<()> {
+        self.send(protocol::audio_stream_end_message()).await
+    }
+
+    /// Share a frame of screen context.
+    pub async fn send_image(&self, data: &[u8], mime_type: &str) -> Result<()> {
+        self.send(protocol::image_message(data, mime_type)).await
+    }
+
+    /// Send a typed user turn.
+    pub async fn send_text(&self, text: &str) -> Result<()> {
+        self.send(protocol::text_message(text)).await
+    }
+
+    async fn send(&self, message: serde_json::Value) -> Result<()> {
+        self.outgoing
+            .send(Message::text(message.to_string()))
+            .await
+            .map_err(|_| RealtimeError::Closed)
+    }
+}
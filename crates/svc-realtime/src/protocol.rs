@@ -0,0 +1,294 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wire format of the Gemini Live `BidiGenerateContent` WebSocket API.
+//!
+//! Client messages are built as JSON values; server messages are decoded
+//! into [`RealtimeEvent`]s. Audio is 16-bit little-endian mono PCM, 16 kHz
+//! going up and (usually) 24 kHz coming back.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{RealtimeConfig, RealtimeError, Result};
+
+pub const LIVE_ENDPOINT: &str = "wss://generativelanguage.googleapis.com/ws/google.ai.generativelanguage.v1beta.GenerativeService.BidiGenerateContent";
+
+/// Sample rate the API expects for microphone audio.
+pub const INPUT_SAMPLE_RATE: u32 = 16_000;
+
+/// Sample rate assumed for reply audio whose MIME type names none.
+pub const OUTPUT_SAMPLE_RATE: u32 = 24_000;
+
+/// Something the server sent, in arrival order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RealtimeEvent {
+    /// The setup was accepted; input may be streamed.
+    Ready,
+    /// A fragment of the transcription of the user's speech.
+    InputTranscript { text: String },
+    /// A fragment of the transcription of the spoken reply.
+    OutputTranscript { text: String },
+    /// Reply text, for sessions that answer in text.
+    Text { text: String },
+    /// A chunk of reply audio, mono PCM.
+    Audio { pcm: Vec<i16>, sample_rate: u32 },
+    /// The user spoke over the reply; queued reply audio should be dropped.
+    Interrupted,
+    /// The model finished its turn.
+    TurnComplete,
+    /// The server will close the connection soon, e.g. "10s".
+    GoAway { time_left: Option<String> },
+    /// The connection ended.
+    Closed { reason: Option<String> },
+}
+
+pub fn setup_message(config: &RealtimeConfig) -> Value {
+    let model = if config.model.starts_with("models/") {
+        config.model.clone()
+    } else {
+        format!("models/{}", config.model)
+    };
+
+    let mut generation_config = json!({ "responseModalities": ["AUDIO"] });
+    if let Some(voice) = &config.voice {
+        generation_config["speechConfig"] = json!({
+            "voiceConfig": { "prebuiltVoiceConfig": { "voiceName": voice } }
+        });
+    }
+
+    let mut setup = json!({
+        "model": model,
+        "generationConfig": generation_config,
+    });
+    if let Some(instruction) = &config.system_instruction {
+        setup["systemInstruction"] = json!({ "parts": [{ "text": instruction }] });
+    }
+    if config.transcribe {
+        setup["inputAudioTranscription"] = json!({});
+        setup["outputAudioTranscription"] = json!({});
+    }
+    json!({ "setup": setup })
+}
+
+/// Microphone audio, 16 kHz mono.
+pub fn audio_message(pcm: &[i16]) -> Value {
+    let bytes: Vec<u8> = pcm.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+    json!({
+        "realtimeInput": {
+            "audio": {
+                "data": BASE64.encode(bytes),
+                "mimeType": format!("audio/pcm;rate={}", INPUT_SAMPLE_RATE),
+            }
+        }
+    })
+}
+
+/// Tells the server the microphone paused, so it flushes buffered audio.
+pub fn audio_stream_end_message() -> Value {
+    json!({ "realtimeInput": { "audioStreamEnd": true } })
+}
+
+/// A frame of screen context (JPEG or PNG).
+pub fn image_message(data: &[u8], mime_type: &str) -> Value {
+    json!({
+        "realtimeInput": {
+            "video": {
+                "data": BASE64.encode(data),
+                "mimeType": mime_type,
+            }
+        }
+    })
+}
+
+/// A complete typed user turn.
+pub fn text_message(text: &str) -> Value {
+    json!({
+        "clientContent": {
+            "turns": [{ "role": "user", "parts": [{ "text": text }] }],
+            "turnComplete": true,
+        }
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerMessage {
+    setup_complete: Option<Value>,
+    server_content: Option<ServerContent>,
+    go_away: Option<GoAway>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerContent {
+    model_turn: Option<Content>,
+    #[serde(default)]
+    interrupted: bool,
+    #[serde(default)]
+    turn_complete: bool,
+    input_transcription: Option<Transcription>,
+    output_transcription: Option<Transcription>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Content {
+    #[serde(default)]
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Part {
+    text: Option<String>,
+    inline_data: Option<InlineData>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InlineData {
+    mime_type: String,
+    data: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Transcription {
+    text: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoAway {
+    time_left: Option<String>,
+}
+
+/// Decode one server message. Fields this client does not use (usage
+/// metadata, tool calls) are ignored.
+pub fn parse_server_message(payload: &str) -> Result<Vec<RealtimeEvent>> {
+    let message: ServerMessage = serde_json::from_str(payload)?;
+    let mut events = Vec::new();
+
+    if message.setup_complete.is_some() {
+        events.push(RealtimeEvent::Ready);
+    }
+
+    if let Some(content) = message.server_content {
+        if content.interrupted {
+            events.push(RealtimeEvent::Interrupted);
+        }
+        if let Some(text) = content.input_transcription.and_then(|t| t.text) {
+            events.push(RealtimeEvent::InputTranscript { text });
+        }
+        for part in content
+            .model_turn
+            .map(|turn| turn.parts)
+            .unwrap_or_default()
+        {
+            if let Some(text) = part.text {
+                events.push(RealtimeEvent::Text { text });
+            }
+            if let Some(inline) = part.inline_data {
+                if inline.mime_type.starts_with("audio/pcm") {
+                    events.push(RealtimeEvent::Audio {
+                        pcm: decode_pcm(&inline.data)?,
+                        sample_rate: sample_rate_of(&inline.mime_type),
+                    });
+                }
+            }
+        }
+        if let Some(text) = content.output_transcription.and_then(|t| t.text) {
+            events.push(RealtimeEvent::OutputTranscript { text });
+        }
+        if content.turn_complete {
+            events.push(RealtimeEvent::TurnComplete);
+        }
+    }
+
+    if let Some(go_away) = message.go_away {
+        events.push(RealtimeEvent::GoAway {
+            time_left: go_away.time_left,
+        });
+    }
+    Ok(events)
+}
+
+fn decode_pcm(data: &str) -> Result<Vec<i16>> {
+    let bytes = BASE64
+        .decode(data)
+        .map_err(|e| RealtimeError::Protocol(format!("invalid audio payload: {}", e)))?;
+    Ok((0..bytes.len() / 2)
+        .map(|i| i16::from_le_bytes([bytes[2 * i], bytes[2 * i + 1]]))
+        .collect())
+}
+
+/// Rate from a MIME type like `audio/pcm;rate=24000`.
+fn sample_rate_of(mime_type: &str) -> u32 {
+    mime_type
+        .split(';')
+        .filter_map(|param| param.trim().strip_prefix("rate="))
+        .find_map(|rate| rate.parse().ok())
+        .unwrap_or(OUTPUT_SAMPLE_RATE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setup_names_model_voice_and_transcription() {
+        let mut config = RealtimeConfig::new("key", "gemini-live");
+        config.voice = Some("Puck".to_string());
+        config.system_instruction = Some("Be brief.".to_string());
+
+        let setup = &setup_message(&config)["setup"];
+        assert_eq!(setup["model"], "models/gemini-live");
+        assert_eq!(
+            setup["generationConfig"]["speechConfig"]["voiceConfig"]["prebuiltVoiceConfig"]
+                ["voiceName"],
+            "Puck"
+        );
+        assert_eq!(setup["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        assert!(setup["inputAudioTranscription"].is_object());
+    }
+
+    #[test]
+    fn server_content_is_decoded_in_order() {
+        let audio = BASE64.encode([0x01, 0x00, 0xff, 0xff]);
+        let payload = json!({
+            "serverContent": {
+                "interrupted": true,
+                "inputTranscription": { "text": "hello" },
+                "modelTurn": {
+                    "parts": [{ "inlineData": { "mimeType": "audio/pcm;rate=24000", "data": audio } }]
+                },
+                "outputTranscription": { "text": "hi" },
+                "turnComplete": true
+            }
+        })
+        .to_string();
+
+        assert_eq!(
+            parse_server_message(&payload).unwrap(),
+            vec![
+                RealtimeEvent::Interrupted,
+                RealtimeEvent::InputTranscript {
+                    text: "hello".to_string()
+                },
+                RealtimeEvent::Audio {
+                    pcm: vec![1, -1],
+                    sample_rate: 24_000
+                },
+                RealtimeEvent::OutputTranscript {
+                    text: "hi".to_string()
+                },
+                RealtimeEvent::TurnComplete,
+            ]
+        );
+        assert_eq!(
+            parse_server_message(r#"{"setupComplete":{}}"#).unwrap(),
+            vec![RealtimeEvent::Ready]
+        );
+    }
+}
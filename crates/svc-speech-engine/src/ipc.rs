@@ -22,7 +22,27 @@ pub enum SttCommand {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SttEvent {
-    Status { status: String },
-    Transcription { text: String, is_final: bool },
-    Error { message: String },
+    Status {
+        status: String,
+    },
+    Transcription {
+        text: String,
+        is_final: bool,
+    },
+    /// Input RMS (0.0 - 1.0) over the last metering interval (~100 ms).
+    /// Stays at 0 when the microphone delivers no audio.
+    Level {
+        rms: f32,
+    },
+    /// Capture device opened for this session. `index` is `None` for the
+    /// system default.
+    DeviceInfo {
+        name: String,
+        index: Option<i32>,
+        sample_rate: u32,
+        channels: u32,
+    },
+    Error {
+        message: String,
+    },
 }
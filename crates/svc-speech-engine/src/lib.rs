@@ -7,22 +7,31 @@
 //!
 //! Usage:
 //! ```no_run
-//! let engine = SpeechEngine::new(binary_path);
-//! let mut rx = engine.start("model.bin", "en").await?;
+//! # use svc_speech_engine::{PostProcessConfig, SpeechEngine};
+//! # async fn run(binary_path: std::path::PathBuf) -> svc_speech_engine::Result<()> {
+//! let mut engine = SpeechEngine::new(binary_path);
+//! let mut rx = engine
+//!     .start("model.bin".into(), "en".into(), PostProcessConfig::default())
+//!     .await?;
 //! while let Some(event) = rx.recv().await {
 //!    // Handle event
 //! }
+//! # Ok(())
+//! # }
 //! ```
 
 pub mod ipc;
+pub mod postprocess;
 pub mod process;
 pub mod state;
 
 use std::path::PathBuf;
+use sys_process_priority::{PowerProfile, PriorityPolicy, SidecarRole};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::mpsc;
 
 pub use ipc::{SttCommand, SttEvent};
+pub use postprocess::{PostProcessConfig, TranscriptCleaner, VocabularyEntry};
 use process::SidecarProcess;
 
 #[derive(Debug, thiserror::Error)]
@@ -43,6 +52,7 @@ pub type Result<T> = std::result::Result<T, EngineError>;
 
 pub struct SpeechEngine {
     binary_path: PathBuf,
+    priority: PriorityPolicy,
     process: Option<SidecarProcess>,
 }
 
@@ -50,23 +60,32 @@ impl SpeechEngine {
     pub fn new(binary_path: PathBuf) -> Self {
         Self {
             binary_path,
+            priority: PowerProfile::default().policy(SidecarRole::Streaming),
             process: None,
         }
     }
 
+    /// Scheduling policy for the sidecar, applied from the next `start`.
+    pub fn with_priority(mut self, priority: PriorityPolicy) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Start the engine and return a receiver for events.
-    /// This launches the sidecar and sends the Start command.
+    /// This launches the sidecar and sends the Start command. Transcriptions
+    /// pass through `post_processing` before they are sent on the channel.
     pub async fn start(
         &mut self,
         model_path: String,
         language: String,
+        post_processing: PostProcessConfig,
     ) -> Result<mpsc::Receiver<SttEvent>> {
         if self.process.is_some() {
             return Err(EngineError::AlreadyRunning);
         }
 
         // 1. Spawn Process
-        let (mut process, stdout) = SidecarProcess::spawn(&self.binary_path)?;
+        let (mut process, stdout) = SidecarProcess::spawn(&self.binary_path, &self.priority)?;
 
         // 2. Send Start Command
         let cmd = SttCommand::Start {
@@ -100,6 +119,16 @@ impl SpeechEngine {
                 }
 
                 match serde_json::from_str::<SttEvent>(&line) {
+                    Ok(SttEvent::Transcription { text, is_final }) => {
+                        let text = post_processing.process(text, is_final).await;
+                        if tx
+                            .send(SttEvent::Transcription { text, is_final })
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
                     Ok(event) => {
                         if tx.send(event).await.is_err() {
                             break;
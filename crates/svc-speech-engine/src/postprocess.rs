@@ -0,0 +1,235 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transcript post-processing.
+//!
+//! Runs on every transcription before it leaves the engine:
+//! 1. vocabulary replacement (product names, jargon),
+//! 2. rule-based punctuation and casing,
+//! 3. an optional cleanup pass by a caller-supplied model, final text only.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long the cleanup pass may take before the rule-based text is used.
+const CLEANUP_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// A preferred spelling and the ways whisper tends to write it.
+#[derive(Debug, Clone, Default)]
+pub struct VocabularyEntry {
+    pub term: String,
+    pub variants: Vec<String>,
+}
+
+pub type CleanupFuture<'a> = Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>>;
+
+/// Rewrites a final transcript, typically with a small LLM.
+pub trait TranscriptCleaner: Send + Sync {
+    fn clean<'a>(&'a self, text: &'a str) -> CleanupFuture<'a>;
+}
+
+/// Post-processing settings passed to [`crate::SpeechEngine::start`].
+/// The default leaves transcripts untouched.
+#[derive(Clone, Default)]
+pub struct PostProcessConfig {
+    /// Capitalize sentences and add missing terminal punctuation.
+    pub punctuation: bool,
+    pub vocabulary: Vec<VocabularyEntry>,
+    pub cleanup: Option<Arc<dyn TranscriptCleaner>>,
+}
+
+impl fmt::Debug for PostProcessConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostProcessConfig")
+            .field("punctuation", &self.punctuation)
+            .field("vocabulary", &self.vocabulary.len())
+            .field("cleanup", &self.cleanup.is_some())
+            .finish()
+    }
+}
+
+impl PostProcessConfig {
+    /// Apply the configured stages to one transcription event.
+    pub async fn process(&self, text: String, is_final: bool) -> String {
+        let mut text = apply_vocabulary(&text, &self.vocabulary);
+        if self.punctuation {
+            text = punctuate(&text, is_final);
+        }
+
+        let Some(cleaner) = self.cleanup.as_ref().filter(|_| is_final) else {
+            return text;
+        };
+        if text.trim().is_empty() {
+            return text;
+        }
+        match tokio::time::timeout(CLEANUP_TIMEOUT, cleaner.clean(&text)).await {
+            Ok(Ok(cleaned)) if !cleaned.trim().is_empty() => cleaned.trim().to_string(),
+            Ok(Ok(_)) => text,
+            Ok(Err(e)) => {
+                log::warn!("Transcript cleanup failed: {}", e);
+                text
+            }
+            Err(_) => {
+                log::warn!("Transcript cleanup timed out");
+                text
+            }
+        }
+    }
+}
+
+/// A term keeps its own casing when it is more than a capitalized word
+/// ("GitHub", "gRPC", "API"); plain words like "Rust" would otherwise
+/// capitalize ordinary speech.
+fn has_distinctive_casing(term: &str) -> bool {
+    term.chars().skip(1).any(|c| c.is_uppercase())
+}
+
+/// Replace variants (and miscased distinctive terms) with the preferred
+/// spelling. Matching is case-insensitive and on whole words.
+pub fn apply_vocabulary(text: &str, vocabulary: &[VocabularyEntry]) -> String {
+    let mut phrases: Vec<(Vec<String>, &str)> = Vec::new();
+    for entry in vocabulary {
+        let term = entry.term.trim();
+        if term.is_empty() {
+            continue;
+        }
+        let mut spellings: Vec<&str> = entry.variants.iter().map(|v| v.as_str()).collect();
+        if has_distinctive_casing(term) {
+            spellings.push(term);
+        }
+        for spelling in spellings {
+            let words: Vec<String> = spelling
+                .split_whitespace()
+                .map(|word| word.to_lowercase())
+                .collect();
+            if !words.is_empty() {
+                phrases.push((words, term));
+            }
+        }
+    }
+    if phrases.is_empty() {
+        return text.to_string();
+    }
+    phrases.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut out: Vec<String> = Vec::with_capacity(words.len());
+    let mut index = 0usize;
+    'words: while index < words.len() {
+        for (phrase, term) in &phrases {
+            let end = index + phrase.len();
+            if end > words.len() {
+                continue;
+            }
+            let window = &words[index..end];
+            let last = window[window.len() - 1];
+            let trailing = &last[last.trim_end_matches(is_sentence_punct).len()..];
+            let matches = phrase
+                .iter()
+                .zip(window)
+                .enumerate()
+                .all(|(i, (want, got))| {
+                    let got = if i + 1 == phrase.len() {
+                        got.trim_end_matches(is_sentence_punct)
+                    } else {
+                        got
+                    };
+                    got.to_lowercase() == *want
+                });
+            if matches {
+                out.push(format!("{}{}", term, trailing));
+                index = end;
+                continue 'words;
+            }
+        }
+        out.push(words[index].to_string());
+        index += 1;
+    }
+    out.join(" ")
+}
+
+fn is_sentence_punct(c: char) -> bool {
+    matches!(c, '.' | ',' | '!' | '?' | ';' | ':')
+}
+
+/// Rule-based cleanup: tidy spacing, capitalize sentence starts and the
+/// pronoun "i", and end final text with a full stop when it has none.
+pub fn punctuate(text: &str, is_final: bool) -> String {
+    let mut out = String::with_capacity(text.len() + 1);
+    let mut capitalize_next = true;
+
+    for word in text.split_whitespace() {
+        if word.chars().all(is_sentence_punct) && !out.is_empty() {
+            out.push_str(word);
+            capitalize_next = word.ends_with(['.', '!', '?']);
+            continue;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+
+        let core = word.trim_end_matches(is_sentence_punct);
+        if capitalize_next || core == "i" || core.starts_with("i'") {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                out.extend(first.to_uppercase());
+                out.push_str(chars.as_str());
+            }
+        } else {
+            out.push_str(word);
+        }
+        capitalize_next = word.ends_with(['.', '!', '?']);
+    }
+
+    if is_final && !out.is_empty() && !out.ends_with(['.', '!', '?', '…']) {
+        out = out.trim_end_matches([',', ';', ':']).to_string();
+        out.push('.');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_vocabulary, punctuate, VocabularyEntry};
+
+    #[test]
+    fn vocabulary_replaces_variants_and_miscased_terms() {
+        let vocabulary = vec![
+            VocabularyEntry {
+                term: "Kubernetes".to_string(),
+                variants: vec!["cooper netties".to_string()],
+            },
+            VocabularyEntry {
+                term: "GitHub".to_string(),
+                variants: vec![],
+            },
+            VocabularyEntry {
+                term: "Rust".to_string(),
+                variants: vec![],
+            },
+        ];
+
+        assert_eq!(
+            apply_vocabulary("push to github and deploy on Cooper Netties.", &vocabulary),
+            "push to GitHub and deploy on Kubernetes."
+        );
+        assert_eq!(
+            apply_vocabulary("the rust belt", &vocabulary),
+            "the rust belt"
+        );
+    }
+
+    #[test]
+    fn punctuation_fixes_casing_and_ending() {
+        assert_eq!(
+            punctuate("so i think  it works . next step", true),
+            "So I think it works. Next step."
+        );
+        assert_eq!(punctuate("hello world", false), "Hello world");
+        assert_eq!(punctuate("Done!", true), "Done!");
+        assert_eq!(punctuate("", true), "");
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::path::PathBuf;
 use std::process::Stdio;
+use sys_process_priority::PriorityPolicy;
 use tokio::process::{Child, ChildStdin, Command};
 
 #[derive(Debug, thiserror::Error)]
@@ -25,7 +26,10 @@ pub struct SidecarProcess {
 }
 
 impl SidecarProcess {
-    pub fn spawn(binary_path: &PathBuf) -> Result<(Self, tokio::process::ChildStdout)> {
+    pub fn spawn(
+        binary_path: &PathBuf,
+        priority: &PriorityPolicy,
+    ) -> Result<(Self, tokio::process::ChildStdout)> {
         let mut cmd = Command::new(binary_path);
         cmd.stdin(Stdio::piped());
         cmd.stdout(Stdio::piped());
@@ -42,11 +46,25 @@ impl SidecarProcess {
         #[cfg(windows)]
         {
             const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
+            cmd.creation_flags(CREATE_NO_WINDOW | sys_process_priority::priority_class(priority));
+        }
+
+        #[cfg(unix)]
+        {
+            let priority = *priority;
+            // SAFETY: The hook only makes async-signal-safe system calls.
+            unsafe {
+                cmd.pre_exec(move || sys_process_priority::apply_to_current_process(&priority));
+            }
         }
 
         let mut child = cmd.spawn().map_err(ProcessError::SpawnError)?;
 
+        #[cfg(windows)]
+        if let Some(pid) = child.id() {
+            let _ = sys_process_priority::apply_to_process(pid, priority);
+        }
+
         let stdin = child.stdin.take().ok_or(ProcessError::StdinError)?;
         let stdout = child.stdout.take().ok_or(ProcessError::StdoutError)?;
 
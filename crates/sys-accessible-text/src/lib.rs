@@ -0,0 +1,128 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Accessible text grab for native windows.
+//!
+//! When the captured window exposes its content through the platform
+//! accessibility API, reading that tree is faster than OCR and exact.
+//! Each platform uses its native mechanism:
+//!
+//! - **Linux**: AT-SPI over the accessibility D-Bus (window → `_NET_WM_PID` → application → matching window)
+//! - **Windows**: UI Automation (`ElementFromHandle`)
+//! - **macOS**: AX API (`CGWindowID` → owner PID → `AXUIElementCreateApplication`)
+//!
+//! # Usage
+//!
+//! ```no_run
+//! let text = sys_accessible_text::grab_window_text(0x3a00007).expect("grab failed");
+//! if text.is_empty() {
+//!     // Nothing exposed; fall back to OCR.
+//! }
+//! println!("{}", text.to_plain_text());
+//! ```
+
+use serde::Serialize;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+/// Upper bound on visited nodes so huge trees (browsers, IDEs) stay cheap.
+pub(crate) const MAX_NODES: usize = 5000;
+/// Upper bound on tree depth.
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// A single text-bearing node from the accessibility tree.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TextNode {
+    /// Platform role name (e.g. "push button", "AXStaticText", "Edit").
+    pub role: String,
+    pub text: String,
+    /// Depth below the window root.
+    pub depth: usize,
+}
+
+/// Text extracted from a window's accessibility tree, in document order.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WindowText {
+    pub nodes: Vec<TextNode>,
+    /// Set when the walk stopped early at [`MAX_NODES`].
+    pub truncated: bool,
+}
+
+impl WindowText {
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Newline-joined text, skipping consecutive duplicates (a label and
+    /// its value often report the same string).
+    pub fn to_plain_text(&self) -> String {
+        let mut lines: Vec<&str> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let line = node.text.trim();
+            if line.is_empty() || lines.last() == Some(&line) {
+                continue;
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    pub(crate) fn push(&mut self, role: &str, text: &str, depth: usize) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        self.nodes.push(TextNode {
+            role: role.to_string(),
+            text: text.to_string(),
+            depth,
+        });
+    }
+}
+
+/// Read the accessible text of a native window.
+///
+/// `window_id` is the platform window handle: an X11 window id on Linux,
+/// an `HWND` on Windows, and a `CGWindowID` on macOS. An empty result means
+/// the window exposes no accessible text and the caller should fall back to OCR.
+pub fn grab_window_text(window_id: u64) -> Result<WindowText, String> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::grab_window_text(window_id)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::grab_window_text(window_id)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        macos::grab_window_text(window_id)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        let _ = window_id;
+        Err("Accessible text grab is not supported on this platform".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowText;
+
+    #[test]
+    fn plain_text_skips_blank_and_repeated_lines() {
+        let mut text = WindowText::default();
+        text.push("label", "Username", 2);
+        text.push("text", "Username", 3);
+        text.push("filler", "   ", 2);
+        text.push("push button", " Sign in ", 2);
+
+        assert_eq!(text.nodes.len(), 3);
+        assert_eq!(text.to_plain_text(), "Username\nSign in");
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{WindowText, MAX_DEPTH, MAX_NODES};
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+use x11rb::rust_connection::RustConnection;
+use zbus::zvariant::OwnedObjectPath;
+use zbus::{Connection as DbusConnection, Proxy};
+
+const ACCESSIBLE_IFACE: &str = "org.a11y.atspi.Accessible";
+const COMPONENT_IFACE: &str = "org.a11y.atspi.Component";
+const TEXT_IFACE: &str = "org.a11y.atspi.Text";
+const REGISTRY_BUS: &str = "org.a11y.atspi.Registry";
+const REGISTRY_ROOT: &str = "/org/a11y/atspi/accessible/root";
+
+/// AT-SPI `ATSPI_COORD_TYPE_SCREEN`.
+const COORD_TYPE_SCREEN: u32 = 0;
+/// How far, in pixels, AT-SPI extents may be from the X11 geometry and still
+/// match: window frames and client-side shadows shift them apart.
+const GEOMETRY_SLACK: i32 = 64;
+
+type AccessibleRef = (String, OwnedObjectPath);
+/// Screen `(x, y, width, height)`.
+type Extents = (i32, i32, i32, i32);
+
+/// The X11 window to read, as its AT-SPI counterpart is looked up.
+struct X11Window {
+    pid: u32,
+    title: Option<String>,
+    extents: Option<Extents>,
+}
+
+pub fn grab_window_text(window_id: u64) -> Result<WindowText, String> {
+    let window = x11_window(window_id)?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to start AT-SPI runtime: {}", e))?;
+    runtime.block_on(grab_for_window(&window))
+}
+
+fn x11_window(window_id: u64) -> Result<X11Window, String> {
+    let window = u32::try_from(window_id).map_err(|_| "Invalid X11 window id".to_string())?;
+    let (conn, _) = x11rb::connect(None).map_err(|e| format!("X11 connect failed: {}", e))?;
+
+    let pid_atom = atom(&conn, b"_NET_WM_PID")?;
+    let reply = conn
+        .get_property(false, window, pid_atom, AtomEnum::CARDINAL, 0, 1)
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?;
+    let pid = reply
+        .value32()
+        .and_then(|mut values| values.next())
+        .ok_or_else(|| "Window does not report _NET_WM_PID".to_string())?;
+
+    let title = text_property(
+        &conn,
+        window,
+        atom(&conn, b"_NET_WM_NAME")?,
+        atom(&conn, b"UTF8_STRING")?,
+    )
+    .or_else(|| {
+        text_property(
+            &conn,
+            window,
+            AtomEnum::WM_NAME.into(),
+            AtomEnum::STRING.into(),
+        )
+    });
+
+    let extents = (|| {
+        let geometry = conn.get_geometry(window).ok()?.reply().ok()?;
+        let origin = conn
+            .translate_coordinates(window, geometry.root, 0, 0)
+            .ok()?
+            .reply()
+            .ok()?;
+        Some((
+            i32::from(origin.dst_x),
+            i32::from(origin.dst_y),
+            i32::from(geometry.width),
+            i32::from(geometry.height),
+        ))
+    })();
+
+    Ok(X11Window {
+        pid,
+        title,
+        extents,
+    })
+}
+
+fn atom(conn: &RustConnection, name: &[u8]) -> Result<u32, String> {
+    Ok(conn
+        .intern_atom(false, name)
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?
+        .atom)
+}
+
+fn text_property(conn: &RustConnection, window: u32, property: u32, kind: u32) -> Option<String> {
+    let reply = conn
+        .get_property(false, window, property, kind, 0, 1024)
+        .ok()?
+        .reply()
+        .ok()?;
+    let value = String::from_utf8_lossy(&reply.value).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+async fn grab_for_window(window: &X11Window) -> Result<WindowText, String> {
+    let a11y = connect_a11y_bus().await?;
+    let dbus = zbus::fdo::DBusProxy::new(&a11y)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let apps = children(&a11y, REGISTRY_BUS, REGISTRY_ROOT).await?;
+    let mut app = None;
+    for (bus_name, path) in apps {
+        let Ok(name) = zbus::names::BusName::try_from(bus_name.as_str()) else {
+            continue;
+        };
+        if dbus.get_connection_unix_process_id(name).await.ok() == Some(window.pid) {
+            app = Some((bus_name, path));
+            break;
+        }
+    }
+
+    let mut text = WindowText::default();
+    let Some((bus_name, app_path)) = app else {
+        // Application is not on the accessibility bus; caller falls back to OCR.
+        return Ok(text);
+    };
+
+    let windows = children(&a11y, &bus_name, app_path.as_str()).await?;
+    let mut candidates = Vec::with_capacity(windows.len());
+    for (bus_name, path) in &windows {
+        candidates.push((
+            extents(&a11y, bus_name, path.as_str()).await,
+            name(&a11y, bus_name, path.as_str()).await,
+        ));
+    }
+    let Some(index) = matching_window(window, &candidates) else {
+        // No window of the application is clearly this one; reading another
+        // would return the wrong text, so the caller falls back to OCR.
+        return Ok(text);
+    };
+
+    let mut visited = 0usize;
+    walk(&a11y, windows[index].clone(), &mut text, &mut visited).await;
+    Ok(text)
+}
+
+/// Index of the AT-SPI window that is `window`, among `candidates` given as
+/// `(extents, name)`: the one whose extents match its geometry, with its
+/// title breaking ties, else the only one named like it. An application
+/// with a single window needs no match.
+fn matching_window(
+    window: &X11Window,
+    candidates: &[(Option<Extents>, Option<String>)],
+) -> Option<usize> {
+    if candidates.len() == 1 {
+        return Some(0);
+    }
+    let same_title = |name: &Option<String>| {
+        window.title.is_some() && name.as_deref().map(str::trim) == window.title.as_deref()
+    };
+    let same_place = |extents: &Option<Extents>| match (window.extents, extents) {
+        (Some((x, y, width, height)), Some((other_x, other_y, other_width, other_height))) => {
+            (x - other_x).abs() <= GEOMETRY_SLACK
+                && (y - other_y).abs() <= GEOMETRY_SLACK
+                && (width - other_width).abs() <= GEOMETRY_SLACK
+                && (height - other_height).abs() <= GEOMETRY_SLACK
+        }
+        _ => false,
+    };
+
+    let placed: Vec<usize> = (0..candidates.len())
+        .filter(|&index| same_place(&candidates[index].0))
+        .collect();
+    if let Some(&index) = placed
+        .iter()
+        .find(|&&index| same_title(&candidates[index].1))
+    {
+        return Some(index);
+    }
+    if let [index] = placed[..] {
+        return Some(index);
+    }
+    let titled: Vec<usize> = (0..candidates.len())
+        .filter(|&index| same_title(&candidates[index].1))
+        .collect();
+    match titled[..] {
+        [index] => Some(index),
+        _ => None,
+    }
+}
+
+async fn connect_a11y_bus() -> Result<DbusConnection, String> {
+    let session = DbusConnection::session()
+        .await
+        .map_err(|e| format!("Session bus unavailable: {}", e))?;
+    let bus = Proxy::new(&session, "org.a11y.Bus", "/org/a11y/bus", "org.a11y.Bus")
+        .await
+        .map_err(|e| e.to_string())?;
+    let address: String = bus
+        .call("GetAddress", &())
+        .await
+        .map_err(|e| format!("Accessibility bus unavailable: {}", e))?;
+
+    zbus::connection::Builder::address(address.as_str())
+        .map_err(|e| e.to_string())?
+        .build()
+        .await
+        .map_err(|e| format!("Failed to connect to accessibility bus: {}", e))
+}
+
+async fn proxy<'a>(
+    conn: &DbusConnection,
+    bus_name: &'a str,
+    path: &'a str,
+    iface: &'a str,
+) -> Option<Proxy<'a>> {
+    Proxy::new(conn, bus_name, path, iface).await.ok()
+}
+
+async fn children(
+    conn: &DbusConnection,
+    bus_name: &str,
+    path: &str,
+) -> Result<Vec<AccessibleRef>, String> {
+    let Some(accessible) = proxy(conn, bus_name, path, ACCESSIBLE_IFACE).await else {
+        return Ok(Vec::new());
+    };
+    accessible
+        .call("GetChildren", &())
+        .await
+        .map_err(|e| format!("AT-SPI GetChildren failed: {}", e))
+}
+
+async fn extents(conn: &DbusConnection, bus_name: &str, path: &str) -> Option<Extents> {
+    let component = proxy(conn, bus_name, path, COMPONENT_IFACE).await?;
+    component
+        .call("GetExtents", &(COORD_TYPE_SCREEN,))
+        .await
+        .ok()
+}
+
+async fn name(conn: &DbusConnection, bus_name: &str, path: &str) -> Option<String> {
+    let accessible = proxy(conn, bus_name, path, ACCESSIBLE_IFACE).await?;
+    accessible.get_property::<String>("Name").await.ok()
+}
+
+async fn walk(
+    conn: &DbusConnection,
+    root: AccessibleRef,
+    out: &mut WindowText,
+    visited: &mut usize,
+) {
+    // Iterative DFS keeps document order without boxing async recursion.
+    let mut stack = vec![(root, 0usize)];
+    while let Some(((bus_name, path), depth)) = stack.pop() {
+        if *visited >= MAX_NODES {
+            out.truncated = true;
+            return;
+        }
+        *visited += 1;
+
+        let Some(accessible) = proxy(conn, &bus_name, path.as_str(), ACCESSIBLE_IFACE).await else {
+            continue;
+        };
+
+        let role: String = accessible
+            .call("GetRoleName", &())
+            .await
+            .unwrap_or_default();
+        let interfaces: Vec<String> = accessible
+            .call("GetInterfaces", &())
+            .await
+            .unwrap_or_default();
+
+        let mut node_text = None;
+        if interfaces.iter().any(|iface| iface == TEXT_IFACE) {
+            if let Some(text_iface) = proxy(conn, &bus_name, path.as_str(), TEXT_IFACE).await {
+                node_text = text_iface
+                    .call::<_, _, String>("GetText", &(0i32, -1i32))
+                    .await
+                    .ok()
+                    .filter(|value| !value.trim().is_empty());
+            }
+        }
+        if node_text.is_none() {
+            node_text = accessible.get_property::<String>("Name").await.ok();
+        }
+        if let Some(value) = node_text {
+            out.push(&role, &value, depth);
+        }
+
+        if depth >= MAX_DEPTH {
+            continue;
+        }
+        let kids: Vec<AccessibleRef> = accessible
+            .call("GetChildren", &())
+            .await
+            .unwrap_or_default();
+        for child in kids.into_iter().rev() {
+            stack.push((child, depth + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(title: &str, extents: Extents) -> X11Window {
+        X11Window {
+            pid: 1,
+            title: Some(title.to_string()),
+            extents: Some(extents),
+        }
+    }
+
+    #[test]
+    fn matches_the_window_by_geometry_then_title() {
+        let candidates = vec![
+            (Some((0, 0, 800, 600)), Some("Inbox".to_string())),
+            (Some((900, 100, 640, 480)), Some("Compose".to_string())),
+            (Some((900, 100, 640, 480)), Some("Draft".to_string())),
+        ];
+        // Frames shift the extents a little.
+        assert_eq!(
+            matching_window(&window("Inbox", (10, 30, 790, 570)), &candidates),
+            Some(0)
+        );
+        assert_eq!(
+            matching_window(&window("Draft", (900, 100, 640, 480)), &candidates),
+            Some(2)
+        );
+        assert_eq!(
+            matching_window(&window("Compose", (0, 0, 10, 10)), &candidates),
+            Some(1)
+        );
+        assert_eq!(
+            matching_window(&window("Settings", (400, 400, 200, 200)), &candidates),
+            None
+        );
+        assert_eq!(
+            matching_window(&window("Settings", (400, 400, 200, 200)), &candidates[..1]),
+            Some(0)
+        );
+    }
+}
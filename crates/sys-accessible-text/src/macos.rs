@@ -0,0 +1,203 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{WindowText, MAX_DEPTH, MAX_NODES};
+use std::ffi::{c_char, c_void, CString};
+
+type CFTypeRef = *const c_void;
+type CFStringRef = *const c_void;
+type CFArrayRef = *const c_void;
+type CFDictionaryRef = *const c_void;
+type CFNumberRef = *const c_void;
+type AXUIElementRef = *const c_void;
+type AXError = i32;
+type CFIndex = isize;
+type CFTypeID = usize;
+
+const AX_ERROR_SUCCESS: AXError = 0;
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+const CF_NUMBER_SINT32_TYPE: CFIndex = 3;
+const CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW: u32 = 1 << 3;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXIsProcessTrusted() -> bool;
+    fn AXUIElementCreateApplication(pid: i32) -> AXUIElementRef;
+    fn AXUIElementCopyAttributeValue(
+        element: AXUIElementRef,
+        attribute: CFStringRef,
+        value: *mut CFTypeRef,
+    ) -> AXError;
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGWindowListCopyWindowInfo(option: u32, relative_to_window: u32) -> CFArrayRef;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFRelease(cf: CFTypeRef);
+    fn CFGetTypeID(cf: CFTypeRef) -> CFTypeID;
+    fn CFStringGetTypeID() -> CFTypeID;
+    fn CFArrayGetTypeID() -> CFTypeID;
+    fn CFArrayGetCount(array: CFArrayRef) -> CFIndex;
+    fn CFArrayGetValueAtIndex(array: CFArrayRef, index: CFIndex) -> CFTypeRef;
+    fn CFDictionaryGetValue(dict: CFDictionaryRef, key: *const c_void) -> *const c_void;
+    fn CFNumberGetValue(number: CFNumberRef, the_type: CFIndex, value_ptr: *mut c_void) -> bool;
+    fn CFStringCreateWithCString(
+        alloc: *const c_void,
+        c_str: *const c_char,
+        encoding: u32,
+    ) -> CFStringRef;
+    fn CFStringGetLength(string: CFStringRef) -> CFIndex;
+    fn CFStringGetMaximumSizeForEncoding(length: CFIndex, encoding: u32) -> CFIndex;
+    fn CFStringGetCString(
+        string: CFStringRef,
+        buffer: *mut c_char,
+        buffer_size: CFIndex,
+        encoding: u32,
+    ) -> bool;
+}
+
+/// Owned CoreFoundation reference, released on drop.
+struct CfOwned(CFTypeRef);
+
+impl Drop for CfOwned {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { CFRelease(self.0) };
+        }
+    }
+}
+
+fn cf_string(value: &str) -> CfOwned {
+    let c_value = CString::new(value).unwrap_or_default();
+    CfOwned(unsafe {
+        CFStringCreateWithCString(std::ptr::null(), c_value.as_ptr(), CF_STRING_ENCODING_UTF8)
+    })
+}
+
+unsafe fn cf_string_to_rust(string: CFStringRef) -> Option<String> {
+    if string.is_null() || CFGetTypeID(string) != CFStringGetTypeID() {
+        return None;
+    }
+    let length = CFStringGetLength(string);
+    let capacity = CFStringGetMaximumSizeForEncoding(length, CF_STRING_ENCODING_UTF8) + 1;
+    let mut buffer = vec![0u8; capacity.max(1) as usize];
+    if !CFStringGetCString(
+        string,
+        buffer.as_mut_ptr() as *mut c_char,
+        capacity,
+        CF_STRING_ENCODING_UTF8,
+    ) {
+        return None;
+    }
+    let end = buffer.iter().position(|b| *b == 0).unwrap_or(buffer.len());
+    Some(String::from_utf8_lossy(&buffer[..end]).into_owned())
+}
+
+unsafe fn copy_attribute(element: AXUIElementRef, name: &str) -> Option<CfOwned> {
+    let attribute = cf_string(name);
+    let mut value: CFTypeRef = std::ptr::null();
+    if AXUIElementCopyAttributeValue(element, attribute.0, &mut value) != AX_ERROR_SUCCESS
+        || value.is_null()
+    {
+        return None;
+    }
+    Some(CfOwned(value))
+}
+
+unsafe fn string_attribute(element: AXUIElementRef, name: &str) -> Option<String> {
+    let value = copy_attribute(element, name)?;
+    cf_string_to_rust(value.0)
+}
+
+fn window_owner_pid(window_id: u32) -> Result<i32, String> {
+    unsafe {
+        let list = CfOwned(CGWindowListCopyWindowInfo(
+            CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW,
+            window_id,
+        ));
+        if list.0.is_null() || CFArrayGetCount(list.0) == 0 {
+            return Err("Window not found".to_string());
+        }
+
+        let info = CFArrayGetValueAtIndex(list.0, 0);
+        let key = cf_string("kCGWindowOwnerPID");
+        let number = CFDictionaryGetValue(info, key.0);
+        let mut pid: i32 = 0;
+        if number.is_null()
+            || !CFNumberGetValue(
+                number,
+                CF_NUMBER_SINT32_TYPE,
+                &mut pid as *mut i32 as *mut c_void,
+            )
+        {
+            return Err("Window does not report an owner PID".to_string());
+        }
+        Ok(pid)
+    }
+}
+
+pub fn grab_window_text(window_id: u64) -> Result<WindowText, String> {
+    if !unsafe { AXIsProcessTrusted() } {
+        return Err("Accessibility permission not granted".to_string());
+    }
+
+    let window_id = u32::try_from(window_id).map_err(|_| "Invalid CGWindowID".to_string())?;
+    let pid = window_owner_pid(window_id)?;
+
+    let mut text = WindowText::default();
+    let mut visited = 0usize;
+    unsafe {
+        let app = CfOwned(AXUIElementCreateApplication(pid));
+        if app.0.is_null() {
+            return Ok(text);
+        }
+        // Prefer the focused window; fall back to the whole application tree.
+        match copy_attribute(app.0, "AXFocusedWindow") {
+            Some(window) => walk(window.0, 0, &mut text, &mut visited),
+            None => walk(app.0, 0, &mut text, &mut visited),
+        }
+    }
+    Ok(text)
+}
+
+unsafe fn walk(element: AXUIElementRef, depth: usize, out: &mut WindowText, visited: &mut usize) {
+    if *visited >= MAX_NODES {
+        out.truncated = true;
+        return;
+    }
+    *visited += 1;
+
+    let role = string_attribute(element, "AXRole").unwrap_or_default();
+    let node_text = string_attribute(element, "AXValue")
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| string_attribute(element, "AXTitle"))
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| string_attribute(element, "AXDescription"));
+    if let Some(node_text) = node_text {
+        out.push(&role, &node_text, depth);
+    }
+
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    let Some(children) = copy_attribute(element, "AXChildren") else {
+        return;
+    };
+    if CFGetTypeID(children.0) != CFArrayGetTypeID() {
+        return;
+    }
+    for index in 0..CFArrayGetCount(children.0) {
+        if out.truncated {
+            return;
+        }
+        let child = CFArrayGetValueAtIndex(children.0, index);
+        if !child.is_null() {
+            walk(child, depth + 1, out, visited);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{WindowText, MAX_DEPTH, MAX_NODES};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+};
+use windows::Win32::UI::Accessibility::{
+    CUIAutomation, IUIAutomation, IUIAutomationElement, IUIAutomationTreeWalker,
+    IUIAutomationValuePattern, UIA_ValuePatternId,
+};
+
+struct ComGuard {
+    initialized: bool,
+}
+
+impl ComGuard {
+    fn new() -> Self {
+        let initialized = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED).is_ok() };
+        Self { initialized }
+    }
+}
+
+impl Drop for ComGuard {
+    fn drop(&mut self) {
+        if self.initialized {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
+
+pub fn grab_window_text(window_id: u64) -> Result<WindowText, String> {
+    let _com = ComGuard::new();
+
+    unsafe {
+        let automation: IUIAutomation =
+            CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| format!("UI Automation unavailable: {}", e))?;
+        let root = automation
+            .ElementFromHandle(HWND(window_id as usize as *mut core::ffi::c_void))
+            .map_err(|e| format!("Window is not accessible: {}", e))?;
+        let walker = automation
+            .ControlViewWalker()
+            .map_err(|e| format!("UI Automation walker failed: {}", e))?;
+
+        let mut text = WindowText::default();
+        let mut visited = 0usize;
+        walk(&walker, root, 0, &mut text, &mut visited);
+        Ok(text)
+    }
+}
+
+unsafe fn walk(
+    walker: &IUIAutomationTreeWalker,
+    element: IUIAutomationElement,
+    depth: usize,
+    out: &mut WindowText,
+    visited: &mut usize,
+) {
+    if *visited >= MAX_NODES {
+        out.truncated = true;
+        return;
+    }
+    *visited += 1;
+
+    let role = element
+        .CurrentLocalizedControlType()
+        .map(|value| value.to_string())
+        .unwrap_or_default();
+
+    let value = element
+        .GetCurrentPatternAs::<IUIAutomationValuePattern>(UIA_ValuePatternId)
+        .and_then(|pattern| pattern.CurrentValue())
+        .map(|value| value.to_string())
+        .ok()
+        .filter(|value| !value.trim().is_empty());
+    let node_text = match value {
+        Some(value) => Some(value),
+        None => element.CurrentName().map(|name| name.to_string()).ok(),
+    };
+    if let Some(node_text) = node_text {
+        out.push(&role, &node_text, depth);
+    }
+
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    let mut child = walker.GetFirstChildElement(&element).ok();
+    while let Some(current) = child {
+        if out.truncated {
+            return;
+        }
+        let next = walker.GetNextSiblingElement(&current).ok();
+        walk(walker, current, depth + 1, out, visited);
+        child = next;
+    }
+}
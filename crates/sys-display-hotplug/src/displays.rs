@@ -0,0 +1,597 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Enumeration of connected displays with their geometry.
+//!
+//! Linux reads outputs from `xrandr` (names match what Qt's xcb backend
+//! reports) and connectors from `/sys/class/drm`; without `xrandr` only the
+//! connectors are known. Windows uses the GDI monitor APIs and macOS
+//! CoreGraphics, with names from `system_profiler`.
+
+use std::fmt;
+
+/// Position and size of a display in desktop coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DisplayGeometry {
+    /// Parses `x,y,w,h`, the form exchanged with the capture sidecar
+    /// (`DISPLAY_GEO`, `SELECTION_RECT`).
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split(',').map(str::trim);
+        let geometry = Self {
+            x: parts.next()?.parse().ok()?,
+            y: parts.next()?.parse().ok()?,
+            width: parts.next()?.parse().ok()?,
+            height: parts.next()?.parse().ok()?,
+        };
+        parts.next().is_none().then_some(geometry)
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        let (x, y) = (i64::from(x), i64::from(y));
+        x >= i64::from(self.x)
+            && y >= i64::from(self.y)
+            && x < i64::from(self.x) + i64::from(self.width)
+            && y < i64::from(self.y) + i64::from(self.height)
+    }
+
+    /// Top-left corner that centers a `width` x `height` box on the display.
+    pub fn centered(&self, width: u32, height: u32) -> (i32, i32) {
+        let offset = |outer: u32, inner: u32| ((i64::from(outer) - i64::from(inner)) / 2) as i32;
+        (
+            self.x + offset(self.width, width),
+            self.y + offset(self.height, height),
+        )
+    }
+}
+
+impl fmt::Display for DisplayGeometry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{},{}", self.x, self.y, self.width, self.height)
+    }
+}
+
+/// A connected display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayInfo {
+    /// Output name as the capture sidecar sees it: the RandR output on
+    /// Linux (e.g. `HDMI-1`), the GDI device on Windows (e.g.
+    /// `\\.\DISPLAY1`), the display name on macOS.
+    pub name: String,
+    /// Physical connector, e.g. the DRM connector `HDMI-A-1`, when known.
+    pub connector: Option<String>,
+    /// Desktop geometry; `None` when the display is off or the position is
+    /// unknown.
+    pub geometry: Option<DisplayGeometry>,
+    /// Whether this is the main display, when the platform says so.
+    pub primary: bool,
+    /// Device pixels per logical pixel, when known.
+    pub scale: Option<f64>,
+}
+
+/// Connected displays. Empty when they cannot be enumerated.
+pub fn list_displays() -> Vec<DisplayInfo> {
+    platform::list_displays()
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{
+        normalized_connector, parse_drm_mode, parse_xft_dpi, parse_xrandr_outputs, DisplayInfo,
+    };
+    use std::process::Command;
+
+    pub fn list_displays() -> Vec<DisplayInfo> {
+        let connectors = drm_connectors();
+        let Some(outputs) =
+            command_output("xrandr", &["--query"]).map(|s| parse_xrandr_outputs(&s))
+        else {
+            return connectors
+                .into_iter()
+                .map(|connector| DisplayInfo {
+                    name: connector.clone(),
+                    connector: Some(connector),
+                    geometry: None,
+                    primary: false,
+                    scale: None,
+                })
+                .collect();
+        };
+
+        let scale = command_output("xrdb", &["-query"])
+            .and_then(|s| parse_xft_dpi(&s))
+            .map(|dpi| dpi / 96.0);
+        outputs
+            .into_iter()
+            .map(|mut display| {
+                display.connector = connectors
+                    .iter()
+                    .find(|c| normalized_connector(c) == normalized_connector(&display.name))
+                    .cloned();
+                display.scale = scale;
+                display
+            })
+            .collect()
+    }
+
+    /// Connected DRM connectors, e.g. `HDMI-A-1`.
+    fn drm_connectors() -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+            return Vec::new();
+        };
+        let mut connectors: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().into_owned();
+                // Connectors are `card<N>-<connector>`.
+                let connector = name.strip_prefix("card")?.split_once('-')?.1.to_string();
+                let status = std::fs::read_to_string(e.path().join("status")).ok()?;
+                let connected = status.trim() == "connected";
+                // A connected output always lists at least one mode.
+                let has_mode = std::fs::read_to_string(e.path().join("modes"))
+                    .ok()
+                    .and_then(|modes| modes.lines().next().and_then(parse_drm_mode))
+                    .is_some();
+                (connected && has_mode).then_some(connector)
+            })
+            .collect();
+        connectors.sort();
+        connectors
+    }
+
+    fn command_output(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{parse_system_profiler_displays, DisplayGeometry, DisplayInfo};
+    use std::ffi::c_void;
+    use std::process::Command;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CGPoint {
+        x: f64,
+        y: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CGSize {
+        width: f64,
+        height: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CGRect {
+        origin: CGPoint,
+        size: CGSize,
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGGetActiveDisplayList(max: u32, displays: *mut u32, count: *mut u32) -> i32;
+        fn CGDisplayBounds(display: u32) -> CGRect;
+        fn CGDisplayIsMain(display: u32) -> u32;
+        fn CGDisplayCopyDisplayMode(display: u32) -> *mut c_void;
+        fn CGDisplayModeGetPixelWidth(mode: *mut c_void) -> usize;
+        fn CGDisplayModeGetPixelHeight(mode: *mut c_void) -> usize;
+        fn CGDisplayModeRelease(mode: *mut c_void);
+    }
+
+    pub fn list_displays() -> Vec<DisplayInfo> {
+        let mut ids = [0u32; 16];
+        let mut count = 0u32;
+        // SAFETY: `ids` has room for `ids.len()` entries and `count` is valid.
+        let status =
+            unsafe { CGGetActiveDisplayList(ids.len() as u32, ids.as_mut_ptr(), &mut count) };
+        if status != 0 {
+            return Vec::new();
+        }
+
+        let mut profiled = Command::new("system_profiler")
+            .arg("SPDisplaysDataType")
+            .output()
+            .map(|out| parse_system_profiler_displays(&String::from_utf8_lossy(&out.stdout)))
+            .unwrap_or_default();
+
+        ids[..count as usize]
+            .iter()
+            .map(|&id| {
+                // SAFETY: `id` came from CGGetActiveDisplayList; the mode is
+                // released after use.
+                let (bounds, primary, pixels) = unsafe {
+                    let mode = CGDisplayCopyDisplayMode(id);
+                    let pixels = (!mode.is_null()).then(|| {
+                        let size = (
+                            CGDisplayModeGetPixelWidth(mode) as u32,
+                            CGDisplayModeGetPixelHeight(mode) as u32,
+                        );
+                        CGDisplayModeRelease(mode);
+                        size
+                    });
+                    (CGDisplayBounds(id), CGDisplayIsMain(id) != 0, pixels)
+                };
+                let geometry = DisplayGeometry {
+                    x: bounds.origin.x as i32,
+                    y: bounds.origin.y as i32,
+                    width: bounds.size.width as u32,
+                    height: bounds.size.height as u32,
+                };
+                let scale = pixels
+                    .filter(|_| geometry.width > 0)
+                    .map(|(width, _)| f64::from(width) / f64::from(geometry.width));
+
+                // system_profiler lists the same displays in its own order.
+                let matched = profiled
+                    .iter()
+                    .position(|p| {
+                        if primary {
+                            p.main
+                        } else {
+                            !p.main && pixels.is_some() && p.pixels == pixels
+                        }
+                    })
+                    .map(|index| profiled.remove(index));
+                DisplayInfo {
+                    name: matched
+                        .map(|p| p.name)
+                        .unwrap_or_else(|| format!("Display {}", id)),
+                    connector: None,
+                    geometry: Some(geometry),
+                    primary,
+                    scale,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{DisplayGeometry, DisplayInfo};
+    use windows_sys::core::BOOL;
+    use windows_sys::Win32::Foundation::{LPARAM, RECT};
+    use windows_sys::Win32::Graphics::Gdi::{
+        EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+    };
+    use windows_sys::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+
+    const MONITORINFOF_PRIMARY: u32 = 1;
+
+    unsafe extern "system" fn collect_monitor_cb(
+        hmonitor: HMONITOR,
+        _hdc: HDC,
+        _lprc_monitor: *mut RECT,
+        lparam: LPARAM,
+    ) -> BOOL {
+        let monitors_ptr = lparam as *mut Vec<HMONITOR>;
+        if !monitors_ptr.is_null() {
+            // SAFETY: The caller passes a valid pointer to `monitors`.
+            unsafe {
+                (*monitors_ptr).push(hmonitor);
+            }
+        }
+        1
+    }
+
+    pub fn list_displays() -> Vec<DisplayInfo> {
+        let mut monitors: Vec<HMONITOR> = Vec::new();
+        // SAFETY: Null HDC/clip is valid to enumerate all displays; callback and lparam are valid.
+        let ok = unsafe {
+            EnumDisplayMonitors(
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                Some(collect_monitor_cb),
+                (&mut monitors as *mut Vec<HMONITOR>) as isize,
+            )
+        };
+        if ok == 0 {
+            return Vec::new();
+        }
+
+        monitors.into_iter().filter_map(display_info).collect()
+    }
+
+    fn display_info(hmonitor: HMONITOR) -> Option<DisplayInfo> {
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        // SAFETY: `info` is a MONITORINFOEXW with cbSize set, which
+        // GetMonitorInfoW accepts in place of MONITORINFO.
+        let ok = unsafe {
+            GetMonitorInfoW(
+                hmonitor,
+                &mut info as *mut MONITORINFOEXW as *mut MONITORINFO,
+            )
+        };
+        if ok == 0 {
+            return None;
+        }
+
+        let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+        // SAFETY: `hmonitor` is a live monitor handle and both outputs are valid.
+        let hr = unsafe { GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) };
+        let scale = (hr >= 0 && dpi_x > 0).then(|| f64::from(dpi_x) / 96.0);
+
+        let len = info
+            .szDevice
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(info.szDevice.len());
+        let rect = info.monitorInfo.rcMonitor;
+        Some(DisplayInfo {
+            name: String::from_utf16_lossy(&info.szDevice[..len]),
+            connector: None,
+            geometry: Some(DisplayGeometry {
+                x: rect.left,
+                y: rect.top,
+                width: u32::try_from(rect.right - rect.left).ok()?,
+                height: u32::try_from(rect.bottom - rect.top).ok()?,
+            }),
+            primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            scale,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::DisplayInfo;
+
+    pub fn list_displays() -> Vec<DisplayInfo> {
+        Vec::new()
+    }
+}
+
+/// Parses a DRM mode line such as `1920x1080` (optionally suffixed, e.g.
+/// `1920x1080i`).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_drm_mode(line: &str) -> Option<(u32, u32)> {
+    let (w, h) = line.trim().split_once('x')?;
+    let h: String = h.chars().take_while(char::is_ascii_digit).collect();
+    Some((w.parse().ok()?, h.parse().ok()?))
+}
+
+/// DRM and RandR name the same port differently (`HDMI-A-1` vs `HDMI-1`);
+/// drops the one-letter connector subtype so both compare equal.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn normalized_connector(name: &str) -> String {
+    let mut parts: Vec<&str> = name.split('-').collect();
+    if parts.len() == 3 && parts[1].len() == 1 && parts[1].chars().all(|c| c.is_ascii_alphabetic())
+    {
+        parts.remove(1);
+    }
+    parts.join("-").to_ascii_lowercase()
+}
+
+/// Connected outputs from `xrandr --query`, e.g.
+/// `HDMI-1 connected primary 1920x1080+1920+0 (normal ...) 527mm x 296mm`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_xrandr_outputs(output: &str) -> Vec<DisplayInfo> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let name = tokens.next()?;
+            if tokens.next()? != "connected" {
+                return None;
+            }
+            let mut primary = false;
+            let mut geometry = None;
+            for token in tokens {
+                if token == "primary" {
+                    primary = true;
+                } else if token.starts_with('(') {
+                    break;
+                } else if let Some(parsed) = parse_xrandr_geometry(token) {
+                    geometry = Some(parsed);
+                }
+            }
+            Some(DisplayInfo {
+                name: name.to_string(),
+                connector: None,
+                geometry,
+                primary,
+                scale: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses `WxH+X+Y`; either offset may be negative (`+-200` or `-200`).
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_xrandr_geometry(token: &str) -> Option<DisplayGeometry> {
+    let size_end = token.find(['+', '-'])?;
+    let (width, height) = token[..size_end].split_once('x')?;
+    let offsets = &token[size_end..];
+    let (x, y) = offsets.split_at(offsets[1..].find(['+', '-'])? + 1);
+    let offset = |value: &str| value.strip_prefix('+').unwrap_or(value).parse().ok();
+    Some(DisplayGeometry {
+        x: offset(x)?,
+        y: offset(y)?,
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+    })
+}
+
+/// `Xft.dpi` from `xrdb -query`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_xft_dpi(output: &str) -> Option<f64> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("Xft.dpi:"))
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|dpi: &f64| *dpi > 0.0)
+}
+
+/// A display listed by `system_profiler SPDisplaysDataType`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProfiledDisplay {
+    name: String,
+    pixels: Option<(u32, u32)>,
+    main: bool,
+}
+
+/// Displays listed under `Displays:` in `system_profiler SPDisplaysDataType`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_system_profiler_displays(output: &str) -> Vec<ProfiledDisplay> {
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut displays: Vec<ProfiledDisplay> = Vec::new();
+    // Indentation of the `Displays:` header currently being read.
+    let mut section: Option<usize> = None;
+    let mut name_indent: Option<usize> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let depth = indent(line);
+        if section.is_some_and(|header| depth <= header) {
+            section = None;
+            name_indent = None;
+        }
+        if trimmed == "Displays:" {
+            section = Some(depth);
+            continue;
+        }
+        if section.is_none() {
+            continue;
+        }
+
+        if let Some(name) = trimmed.strip_suffix(':') {
+            if !name_indent.is_some_and(|expected| depth > expected) {
+                name_indent = Some(depth);
+                displays.push(ProfiledDisplay {
+                    name: name.to_string(),
+                    pixels: None,
+                    main: false,
+                });
+                continue;
+            }
+        }
+        let Some(display) = displays.last_mut() else {
+            continue;
+        };
+        if let Some(resolution) = trimmed.strip_prefix("Resolution:") {
+            let mut parts = resolution.split_whitespace();
+            let width = parts.next().and_then(|w| w.parse().ok());
+            let height = parts.nth(1).and_then(|h| h.parse().ok());
+            display.pixels = width.zip(height);
+        } else if trimmed == "Main Display: Yes" {
+            display.main = true;
+        }
+    }
+    displays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometry_round_trip_and_centering() {
+        let geometry = DisplayGeometry::parse("-1920, 0,1920,1080").unwrap();
+        assert_eq!(geometry.to_string(), "-1920,0,1920,1080");
+        assert_eq!(geometry.centered(1030, 690), (-1920 + 445, 195));
+        assert!(geometry.contains(-1, 1079));
+        assert!(!geometry.contains(0, 0));
+        assert_eq!(DisplayGeometry::parse("1,2,3"), None);
+        assert_eq!(DisplayGeometry::parse("1,2,3,4,5"), None);
+    }
+
+    #[test]
+    fn test_parse_drm_mode() {
+        assert_eq!(parse_drm_mode("1920x1080"), Some((1920, 1080)));
+        assert_eq!(parse_drm_mode("1920x1080i\n"), Some((1920, 1080)));
+        assert_eq!(parse_drm_mode("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_xrandr_outputs() {
+        let output = "Screen 0: minimum 8 x 8, current 3840 x 1080, maximum 32767 x 32767
+eDP-1 connected 1920x1080+0+0 (normal left inverted right x axis y axis) 344mm x 193mm
+   1920x1080     60.01*+
+HDMI-1 connected primary 1920x1080+1920+0 (normal left inverted right x axis y axis) 527mm x 296mm
+DP-1 disconnected (normal left inverted right x axis y axis)
+DP-2 connected (normal left inverted right x axis y axis)
+";
+        let outputs = parse_xrandr_outputs(output);
+        let names: Vec<&str> = outputs.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["eDP-1", "HDMI-1", "DP-2"]);
+        assert_eq!(
+            outputs[1].geometry,
+            Some(DisplayGeometry {
+                x: 1920,
+                y: 0,
+                width: 1920,
+                height: 1080
+            })
+        );
+        assert!(outputs[1].primary && !outputs[0].primary);
+        assert_eq!(outputs[2].geometry, None);
+        assert_eq!(
+            parse_xrandr_geometry("1280x1024-1280+-200").map(|g| (g.x, g.y)),
+            Some((-1280, -200))
+        );
+    }
+
+    #[test]
+    fn test_connector_names_match_across_backends() {
+        assert_eq!(
+            normalized_connector("HDMI-A-1"),
+            normalized_connector("HDMI-1")
+        );
+        assert_eq!(normalized_connector("eDP-1"), "edp-1");
+        assert_ne!(normalized_connector("DP-1"), normalized_connector("DP-2"));
+    }
+
+    #[test]
+    fn test_parse_xft_dpi() {
+        assert_eq!(
+            parse_xft_dpi("Xft.antialias:\t1\nXft.dpi:\t144\n"),
+            Some(144.0)
+        );
+        assert_eq!(parse_xft_dpi("Xft.antialias:\t1\n"), None);
+    }
+
+    #[test]
+    fn test_parse_system_profiler_displays() {
+        let output = "Graphics/Displays:
+
+    Apple M2:
+
+      Chipset Model: Apple M2
+      Displays:
+        Color LCD:
+          Display Type: Built-in Liquid Retina Display
+          Resolution: 2560 x 1664 Retina
+          Main Display: Yes
+        DELL U2720Q:
+          Resolution: 3840 x 2160 (2160p/4K UHD 1 - Ultra High Definition)
+          Mirror: Off
+";
+        let displays = parse_system_profiler_displays(output);
+        assert_eq!(displays.len(), 2);
+        assert_eq!(displays[0].name, "Color LCD");
+        assert_eq!(displays[0].pixels, Some((2560, 1664)));
+        assert!(displays[0].main);
+        assert_eq!(displays[1].name, "DELL U2720Q");
+        assert!(!displays[1].main);
+    }
+}
@@ -12,6 +12,10 @@ use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
+mod displays;
+
+pub use displays::{list_displays, DisplayGeometry, DisplayInfo};
+
 pub struct DisplayWatcher {
     running: Arc<AtomicBool>,
     handle: Option<JoinHandle<()>>,
@@ -89,82 +93,8 @@ impl DisplayMonitor {
         false
     }
 
-    #[cfg(target_os = "linux")]
-    fn get_monitor_count() -> i32 {
-        if let Ok(entries) = std::fs::read_dir("/sys/class/drm") {
-            let count = entries
-                .filter_map(|e| e.ok())
-                .filter(|e| {
-                    let name = e.file_name().to_string_lossy().into_owned();
-                    if !name.starts_with("card") || !name.contains('-') {
-                        return false;
-                    }
-                    let status_path = e.path().join("status");
-                    std::fs::read_to_string(status_path)
-                        .map(|s| s.trim() == "connected")
-                        .unwrap_or(false)
-                })
-                .count();
-            if count > 0 {
-                return count as i32;
-            }
-        }
-        1
-    }
-
-    #[cfg(target_os = "macos")]
-    fn get_monitor_count() -> i32 {
-        use std::process::Command;
-        let out = Command::new("system_profiler")
-            .arg("SPDisplaysDataType")
-            .output();
-        if let Ok(o) = out {
-            String::from_utf8_lossy(&o.stdout)
-                .matches("Resolution:")
-                .count() as i32
-        } else {
-            1
-        }
-    }
-
-    #[cfg(target_os = "windows")]
     fn get_monitor_count() -> i32 {
-        use windows_sys::core::BOOL;
-        use windows_sys::Win32::Foundation::{LPARAM, RECT};
-        use windows_sys::Win32::Graphics::Gdi::{EnumDisplayMonitors, HDC, HMONITOR};
-
-        unsafe extern "system" fn count_monitor_cb(
-            _hmonitor: HMONITOR,
-            _hdc: HDC,
-            _lprc_monitor: *mut RECT,
-            lparam: LPARAM,
-        ) -> BOOL {
-            let count_ptr = lparam as *mut i32;
-            if !count_ptr.is_null() {
-                // SAFETY: The caller passes a valid pointer to `count`.
-                unsafe {
-                    *count_ptr += 1;
-                }
-            }
-            1
-        }
-
-        let mut count: i32 = 0;
-        // SAFETY: Null HDC/clip is valid to enumerate all displays; callback and lparam are valid.
-        let ok = unsafe {
-            EnumDisplayMonitors(
-                std::ptr::null_mut(),
-                std::ptr::null(),
-                Some(count_monitor_cb),
-                (&mut count as *mut i32) as isize,
-            )
-        };
-
-        if ok != 0 && count > 0 {
-            count
-        } else {
-            1
-        }
+        (list_displays().len() as i32).max(1)
     }
 }
 
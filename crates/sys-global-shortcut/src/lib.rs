@@ -6,7 +6,7 @@
 //! This crate provides a generic API for registering system-wide hotkeys.
 //! Each platform uses its native, most reliable mechanism:
 //!
-//! - **Linux**: Native D-Bus Session Service + Desktop Environment Command Registration (`gsettings`/`qdbus`)
+//! - **Linux**: Native D-Bus Session Service + Desktop Environment Command Registration (`gsettings`/`kwriteconfig`/`xfconf-query`)
 //! - **Windows**: `RegisterHotKey` (Win32)
 //! - **macOS**: `RegisterEventHotKey` (Carbon)
 //!
@@ -32,11 +32,45 @@
 
 #[cfg(target_os = "linux")]
 mod linux;
+#[cfg(target_os = "linux")]
+mod linux_desktop;
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "windows")]
 mod windows;
 
+/// Why a shortcut could not be registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutError {
+    /// Another application already owns this key combination; the caller
+    /// should offer a different binding.
+    AlreadyRegistered,
+    Failed(String),
+}
+
+impl ShortcutError {
+    /// Stable code for the frontend.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AlreadyRegistered => "ERR_SHORTCUT_ALREADY_REGISTERED",
+            Self::Failed(_) => "ERR_SHORTCUT_REGISTRATION_FAILED",
+        }
+    }
+}
+
+impl std::fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadyRegistered => {
+                write!(f, "Shortcut is already registered by another application")
+            }
+            Self::Failed(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ShortcutError {}
+
 pub struct ShortcutConfig {
     pub linux_trigger: String,
     pub linux_description: String,
@@ -56,13 +90,18 @@ pub struct ShortcutHandle {
 }
 
 impl ShortcutHandle {
-    pub fn register<F>(config: ShortcutConfig, callback: F) -> Result<Self, String>
+    /// Register one hotkey. On Windows and macOS each handle owns its own
+    /// hotkey, so several can be registered side by side. On Linux the handle
+    /// is the app's D-Bus service that desktop bindings call to capture; the
+    /// config is unused there and it should be registered once.
+    pub fn register<F>(config: ShortcutConfig, callback: F) -> Result<Self, ShortcutError>
     where
         F: Fn() + Send + Sync + 'static,
     {
         #[cfg(target_os = "linux")]
         {
-            let inner = linux::LinuxHandle::register(config, callback)?;
+            let inner =
+                linux::LinuxHandle::register(config, callback).map_err(ShortcutError::Failed)?;
             Ok(Self { inner })
         }
         #[cfg(target_os = "windows")]
@@ -72,11 +111,19 @@ impl ShortcutHandle {
         }
         #[cfg(target_os = "macos")]
         {
-            let inner = macos::MacosHandle::register(config, callback)?;
+            let inner =
+                macos::MacosHandle::register(config, callback).map_err(ShortcutError::Failed)?;
             Ok(Self { inner })
         }
     }
 
+    /// Drop and re-register the hotkey. Some Windows systems silently lose
+    /// hotkeys across sleep; the backend already does this on resume.
+    #[cfg(target_os = "windows")]
+    pub fn reregister(&self) -> Result<(), ShortcutError> {
+        self.inner.reregister()
+    }
+
     pub fn unregister(self) {
         self.inner.unregister();
     }
@@ -89,5 +136,34 @@ pub fn trigger_linux_ipc() -> bool {
 
 #[cfg(target_os = "linux")]
 pub fn install_linux_shortcut(bin_path: &str, trigger: &str, name: &str) -> Result<(), String> {
-    linux::install_linux_shortcut(bin_path, trigger, name)
+    linux_desktop::install_linux_shortcut(bin_path, trigger, name)
+}
+
+/// Replace the desktop binding installed under `name` with `trigger`.
+#[cfg(target_os = "linux")]
+pub fn update_linux_shortcut(trigger: &str, name: &str) -> Result<(), String> {
+    linux_desktop::update_linux_shortcut(trigger, name)
+}
+
+/// Remove the desktop binding installed under `name` (preference change or
+/// uninstall).
+#[cfg(target_os = "linux")]
+pub fn remove_linux_shortcut(name: &str) -> Result<(), String> {
+    linux_desktop::remove_linux_shortcut(name)
+}
+
+/// Remove the binding for `name` and its helper script, for uninstall.
+#[cfg(target_os = "linux")]
+pub fn uninstall_linux_shortcut(name: &str) -> Result<(), String> {
+    linux_desktop::uninstall_linux_shortcut(name)
+}
+
+#[cfg(target_os = "linux")]
+pub use linux_desktop::{BackendInstallStatus, DesktopBackend, ShortcutInstallStatus};
+
+/// Which desktop backends (GNOME, KDE, XFCE, Cinnamon) have a binding for
+/// `name`, with its trigger and location.
+#[cfg(target_os = "linux")]
+pub fn linux_shortcut_install_status(name: &str) -> ShortcutInstallStatus {
+    linux_desktop::linux_shortcut_install_status(name)
 }
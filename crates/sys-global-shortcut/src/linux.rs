@@ -27,144 +27,7 @@ pub fn trigger_linux_ipc() -> bool {
     try_dbus_send() || try_busctl() || try_gdbus()
 }
 
-pub fn install_linux_shortcut(_bin_path: &str, trigger: &str, name: &str) -> Result<(), String> {
-    let de = std::env::var("XDG_CURRENT_DESKTOP")
-        .unwrap_or_default()
-        .to_lowercase();
-
-    let trigger_gnome = format!(
-        "<Super><Shift>{}",
-        trigger.split('+').next_back().unwrap_or("a")
-    );
-    let trigger_kde = format!(
-        "Meta+Shift+{}",
-        trigger.split('+').next_back().unwrap_or("A").to_uppercase()
-    );
-
-    let app_lower = "Squigit".to_lowercase();
-    let command_str = build_shortcut_command()?;
-    let command = command_str.as_str();
-
-    if de.contains("gnome")
-        || de.contains("ubuntu")
-        || de.contains("unity")
-        || de.contains("budgie")
-    {
-        let binding_path_str = format!(
-            "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings/{}-binding/",
-            app_lower
-        );
-        let binding_path = binding_path_str.as_str();
-        let schema = "org.gnome.settings-daemon.plugins.media-keys.custom-keybinding";
-
-        let get_bindings = Command::new("gsettings")
-            .arg("get")
-            .arg("org.gnome.settings-daemon.plugins.media-keys")
-            .arg("custom-keybindings")
-            .output();
-
-        if let Ok(output) = get_bindings {
-            let mut list = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if list == "@as []" {
-                list = "[]".to_string();
-            }
-            if !list.contains(binding_path) {
-                let new_list = if list == "[]" {
-                    format!("['{}']", binding_path)
-                } else {
-                    format!("{}, '{}']", list.trim_end_matches(']'), binding_path)
-                };
-                let _ = Command::new("gsettings")
-                    .arg("set")
-                    .arg("org.gnome.settings-daemon.plugins.media-keys")
-                    .arg("custom-keybindings")
-                    .arg(new_list)
-                    .output();
-            }
-
-            let binding_schema_path = format!("{}:{}", schema, binding_path);
-            let _ = Command::new("gsettings")
-                .arg("set")
-                .arg(&binding_schema_path)
-                .arg("name")
-                .arg(name)
-                .output();
-            let _ = Command::new("gsettings")
-                .arg("set")
-                .arg(&binding_schema_path)
-                .arg("command")
-                .arg(command)
-                .output();
-            let _ = Command::new("gsettings")
-                .arg("set")
-                .arg(&binding_schema_path)
-                .arg("binding")
-                .arg(&trigger_gnome)
-                .output();
-            return Ok(());
-        }
-        return Err("Failed to setup GNOME bindings via gsettings".to_string());
-    } else if de.contains("kde") || de.contains("plasma") {
-        let uuid_str = format!("12345678-1234-5678-1234-{:012x}", std::process::id());
-        let import_file = format!("/tmp/{}-binding.khotkeys", app_lower);
-        let content = format!(
-            "[Data]
-DataCount=1
-[Data_1]
-Comment={name}
-Enabled=true
-Name={name}
-Type=SIMPLE_ACTION_DATA
-[Data_1Actions]
-ActionsCount=1
-[Data_1Actions0]
-CommandURL={command}
-Type=COMMAND_URL
-[Data_1Triggers]
-TriggersCount=1
-[Data_1Triggers0]
-Key={trigger_kde}
-Type=SHORTCUT
-Uuid={{{uuid_str}}}"
-        );
-        std::fs::write(&import_file, content).map_err(|e| e.to_string())?;
-
-        let _ = Command::new("qdbus")
-            .arg("org.kde.kded5")
-            .arg("/modules/khotkeys")
-            .arg("org.kde.khotkeys.import_shortcuts_list")
-            .arg(&import_file)
-            .output();
-        let _ = Command::new("qdbus")
-            .arg("org.kde.kglobalaccel")
-            .arg("/kglobalaccel")
-            .arg("org.kde.kglobalaccel.Component.importLegacyShortcuts")
-            .arg(&import_file)
-            .output();
-        return Ok(());
-    } else if de.contains("xfce") {
-        let xfce_property = format!("/commands/custom/{}", trigger_gnome);
-        let _ = Command::new("xfconf-query")
-            .arg("--channel")
-            .arg("xfce4-keyboard-shortcuts")
-            .arg("--property")
-            .arg(&xfce_property)
-            .arg("--create")
-            .arg("--type")
-            .arg("string")
-            .arg("--set")
-            .arg(command)
-            .output();
-        return Ok(());
-    }
-
-    Err(format!(
-        "Unsupported Desktop Environment: {}, please configure shortcut manually",
-        de
-    ))
-}
-
-fn build_shortcut_command() -> Result<String, String> {
+pub(crate) fn build_shortcut_command() -> Result<String, String> {
     let script_path = ensure_hotkey_trigger_script()?;
     Ok(format!("/bin/sh {}", script_path))
 }
@@ -232,12 +95,27 @@ exit 1
     Ok(path.to_string_lossy().to_string())
 }
 
+/// Delete the helper script. Bindings that still run it stop working, so
+/// this is only for uninstall, after they are removed.
+pub(crate) fn remove_hotkey_trigger_script() -> Result<(), String> {
+    let path = hotkey_script_path();
+    match std::fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!(
+            "Failed to remove shortcut helper script {}: {}",
+            path.display(),
+            e
+        )),
+    }
+}
+
 fn hotkey_script_path() -> std::path::PathBuf {
     let base = xdg_config_home().unwrap_or_else(|| std::path::PathBuf::from("/tmp"));
     base.join("squigit").join(HOTKEY_SCRIPT_NAME)
 }
 
-fn xdg_config_home() -> Option<std::path::PathBuf> {
+pub(crate) fn xdg_config_home() -> Option<std::path::PathBuf> {
     let xdg_home = std::env::var_os("XDG_CONFIG_HOME")
         .filter(|v| !v.is_empty())
         .map(std::path::PathBuf::from);
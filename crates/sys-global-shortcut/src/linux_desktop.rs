@@ -0,0 +1,945 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Desktop-level shortcut installers for Linux.
+//!
+//! Wayland compositors do not let applications grab keys, so the shortcut is
+//! registered with the desktop environment as a custom command that pokes the
+//! app over D-Bus. Each backend keys its binding by the app name so it can be
+//! found again for update, removal and status. A failed install restores the
+//! settings it touched.
+
+use crate::linux::{build_shortcut_command, remove_hotkey_trigger_script, xdg_config_home};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Command;
+
+const GNOME_LIST_SCHEMA: &str = "org.gnome.settings-daemon.plugins.media-keys";
+const GNOME_BINDING_SCHEMA: &str = "org.gnome.settings-daemon.plugins.media-keys.custom-keybinding";
+const GNOME_BINDING_DIR: &str = "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings";
+
+const CINNAMON_LIST_SCHEMA: &str = "org.cinnamon.desktop.keybindings";
+const CINNAMON_BINDING_SCHEMA: &str = "org.cinnamon.desktop.keybindings.custom-keybinding";
+const CINNAMON_BINDING_DIR: &str = "/org/cinnamon/desktop/keybindings/custom-keybindings";
+
+const XFCE_CHANNEL: &str = "xfce4-keyboard-shortcuts";
+const XFCE_CUSTOM_PREFIX: &str = "/commands/custom/";
+
+const KDE_SHORTCUTS_FILE: &str = "kglobalshortcutsrc";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DesktopBackend {
+    Gnome,
+    Kde,
+    Xfce,
+    Cinnamon,
+}
+
+/// What one backend currently has configured for the app.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendInstallStatus {
+    pub backend: DesktopBackend,
+    /// The backend's configuration tool is on `PATH`.
+    pub available: bool,
+    pub configured: bool,
+    pub trigger: Option<String>,
+    /// Where the binding lives (dconf path, config file, xfconf property).
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutInstallStatus {
+    /// Raw `XDG_CURRENT_DESKTOP`.
+    pub desktop: String,
+    pub detected: Option<DesktopBackend>,
+    pub backends: Vec<BackendInstallStatus>,
+}
+
+impl DesktopBackend {
+    pub const ALL: [Self; 4] = [Self::Gnome, Self::Kde, Self::Xfce, Self::Cinnamon];
+
+    /// Backend for the running session. Cinnamon is checked first since its
+    /// session also reports GNOME.
+    pub fn detect() -> Option<Self> {
+        Self::from_desktop(&current_desktop())
+    }
+
+    fn from_desktop(de: &str) -> Option<Self> {
+        let de = de.to_lowercase();
+        if de.contains("cinnamon") {
+            Some(Self::Cinnamon)
+        } else if de.contains("kde") || de.contains("plasma") {
+            Some(Self::Kde)
+        } else if de.contains("xfce") {
+            Some(Self::Xfce)
+        } else if ["gnome", "ubuntu", "unity", "budgie", "pop"]
+            .iter()
+            .any(|name| de.contains(name))
+        {
+            Some(Self::Gnome)
+        } else {
+            None
+        }
+    }
+
+    fn is_available(self) -> bool {
+        match self {
+            Self::Gnome | Self::Cinnamon => on_path("gsettings"),
+            Self::Kde => kde_tools().is_some(),
+            Self::Xfce => on_path("xfconf-query"),
+        }
+    }
+
+    fn install(
+        self,
+        key: &str,
+        name: &str,
+        command: &str,
+        trigger: &Trigger,
+    ) -> Result<(), String> {
+        match self {
+            Self::Gnome => GsettingsLayout::gnome(key).install(name, command, trigger),
+            Self::Cinnamon => GsettingsLayout::cinnamon(key).install(name, command, trigger),
+            Self::Kde => kde_install(key, name, command, trigger),
+            Self::Xfce => xfce_install(command, trigger),
+        }
+    }
+
+    fn remove(self, key: &str, name: &str, command: &str) -> Result<(), String> {
+        match self {
+            Self::Gnome => GsettingsLayout::gnome(key).remove(),
+            Self::Cinnamon => GsettingsLayout::cinnamon(key).remove(),
+            Self::Kde => kde_remove(key, name, command),
+            Self::Xfce => xfce_remove(command),
+        }
+    }
+
+    fn status(self, key: &str, command: &str) -> BackendInstallStatus {
+        let available = self.is_available();
+        let mut status = BackendInstallStatus {
+            backend: self,
+            available,
+            configured: false,
+            trigger: None,
+            location: None,
+        };
+        if !available {
+            return status;
+        }
+        let found = match self {
+            Self::Gnome => GsettingsLayout::gnome(key).status(),
+            Self::Cinnamon => GsettingsLayout::cinnamon(key).status(),
+            Self::Kde => kde_status(key),
+            Self::Xfce => xfce_status(command),
+        };
+        if let Some((location, trigger)) = found {
+            status.configured = true;
+            status.location = Some(location);
+            status.trigger = trigger;
+        }
+        status
+    }
+}
+
+pub fn install_linux_shortcut(_bin_path: &str, trigger: &str, name: &str) -> Result<(), String> {
+    let de = current_desktop();
+    let Some(backend) = DesktopBackend::from_desktop(&de) else {
+        return Err(format!(
+            "Unsupported Desktop Environment: {}, please configure shortcut manually",
+            de
+        ));
+    };
+    let trigger = Trigger::parse(trigger)?;
+    let command = build_shortcut_command()?;
+    backend.install(&binding_key(name), name, &command, &trigger)
+}
+
+/// Replace the desktop-level binding installed for `name` with one for
+/// `trigger`. The old binding is removed first so it stops firing, and put
+/// back when the new one cannot be installed.
+pub fn update_linux_shortcut(trigger: &str, name: &str) -> Result<(), String> {
+    Trigger::parse(trigger)?;
+    let key = binding_key(name);
+    let command = build_shortcut_command()?;
+    let previous = DesktopBackend::detect().and_then(|backend| {
+        let installed = backend.status(&key, &command).trigger?;
+        Some((backend, Trigger::from_installed(&installed).ok()?))
+    });
+
+    remove_linux_shortcut(name)?;
+    let Err(error) = install_linux_shortcut("", trigger, name) else {
+        return Ok(());
+    };
+    match previous {
+        Some((backend, previous)) => match backend.install(&key, name, &command, &previous) {
+            Ok(()) => Err(error),
+            Err(restore_error) => Err(format!(
+                "{}; restoring the previous shortcut failed: {}",
+                error, restore_error
+            )),
+        },
+        None => Err(error),
+    }
+}
+
+/// Remove the desktop-level binding installed for `name` from every backend
+/// that has one. Succeeds when no binding exists.
+pub fn remove_linux_shortcut(name: &str) -> Result<(), String> {
+    let key = binding_key(name);
+    let command = build_shortcut_command()?;
+    let mut errors = Vec::new();
+    for backend in DesktopBackend::ALL {
+        if !backend.is_available() {
+            continue;
+        }
+        if let Err(e) = backend.remove(&key, name, &command) {
+            errors.push(format!("{:?}: {}", backend, e));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Remove the binding for `name` and the helper script it runs. The
+/// script is removed even when a backend fails, since the app is going.
+pub fn uninstall_linux_shortcut(name: &str) -> Result<(), String> {
+    let removed = remove_linux_shortcut(name);
+    remove_hotkey_trigger_script()?;
+    removed
+}
+
+/// What each backend has configured for `name`.
+pub fn linux_shortcut_install_status(name: &str) -> ShortcutInstallStatus {
+    let key = binding_key(name);
+    let command = build_shortcut_command().unwrap_or_default();
+    ShortcutInstallStatus {
+        desktop: current_desktop(),
+        detected: DesktopBackend::detect(),
+        backends: DesktopBackend::ALL
+            .iter()
+            .map(|backend| backend.status(&key, &command))
+            .collect(),
+    }
+}
+
+// ==========================
+// Triggers
+// ==========================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Trigger {
+    super_key: bool,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    key: String,
+}
+
+impl Trigger {
+    /// Parse `SUPER+SHIFT+a` style triggers.
+    fn parse(trigger: &str) -> Result<Self, String> {
+        let mut parsed = Self {
+            super_key: false,
+            ctrl: false,
+            alt: false,
+            shift: false,
+            key: String::new(),
+        };
+        for part in trigger.split('+').map(str::trim).filter(|p| !p.is_empty()) {
+            match part.to_ascii_lowercase().as_str() {
+                "super" | "meta" | "win" => parsed.super_key = true,
+                "ctrl" | "control" | "primary" => parsed.ctrl = true,
+                "alt" => parsed.alt = true,
+                "shift" => parsed.shift = true,
+                _ if parsed.key.is_empty() => parsed.key = part.to_string(),
+                _ => return Err(format!("Invalid shortcut trigger: {}", trigger)),
+            }
+        }
+        if parsed.key.is_empty() {
+            return Err(format!("Shortcut trigger has no key: {}", trigger));
+        }
+        Ok(parsed)
+    }
+
+    /// Parse a trigger as a backend reports it: an accelerator such as
+    /// `<Super><Shift>a`, or KDE's `Meta+Shift+A`.
+    fn from_installed(trigger: &str) -> Result<Self, String> {
+        let mut rest = trigger.trim();
+        if !rest.starts_with('<') {
+            return Self::parse(rest);
+        }
+        let mut parts = Vec::new();
+        while let Some(modifier) = rest.strip_prefix('<') {
+            let (modifier, tail) = modifier
+                .split_once('>')
+                .ok_or_else(|| format!("Invalid shortcut trigger: {}", trigger))?;
+            parts.push(modifier);
+            rest = tail;
+        }
+        parts.push(rest);
+        Self::parse(&parts.join("+"))
+    }
+
+    /// `<Super><Shift>a` (GNOME, Cinnamon, XFCE).
+    fn to_accelerator(&self) -> String {
+        let mut out = String::new();
+        if self.super_key {
+            out.push_str("<Super>");
+        }
+        if self.ctrl {
+            out.push_str("<Primary>");
+        }
+        if self.alt {
+            out.push_str("<Alt>");
+        }
+        if self.shift {
+            out.push_str("<Shift>");
+        }
+        out.push_str(&self.key.to_lowercase());
+        out
+    }
+
+    /// `Meta+Shift+A` (KDE).
+    fn to_kde(&self) -> String {
+        let mut parts = Vec::new();
+        if self.super_key {
+            parts.push("Meta".to_string());
+        }
+        if self.ctrl {
+            parts.push("Ctrl".to_string());
+        }
+        if self.alt {
+            parts.push("Alt".to_string());
+        }
+        if self.shift {
+            parts.push("Shift".to_string());
+        }
+        parts.push(self.key.to_uppercase());
+        parts.join("+")
+    }
+}
+
+// ==========================
+// GNOME / Cinnamon (gsettings)
+// ==========================
+
+/// Both desktops keep a list of custom bindings plus a relocatable schema
+/// per binding; they differ in what the list holds and the binding type.
+struct GsettingsLayout {
+    list_schema: &'static str,
+    list_key: &'static str,
+    /// Value stored in the list for our binding.
+    list_entry: String,
+    binding_schema: &'static str,
+    binding_path: String,
+    /// Cinnamon stores the accelerator as a string array.
+    binding_is_list: bool,
+}
+
+impl GsettingsLayout {
+    fn gnome(key: &str) -> Self {
+        let binding_path = format!("{}/{}-binding/", GNOME_BINDING_DIR, key);
+        Self {
+            list_schema: GNOME_LIST_SCHEMA,
+            list_key: "custom-keybindings",
+            list_entry: binding_path.clone(),
+            binding_schema: GNOME_BINDING_SCHEMA,
+            binding_path,
+            binding_is_list: false,
+        }
+    }
+
+    fn cinnamon(key: &str) -> Self {
+        Self {
+            list_schema: CINNAMON_LIST_SCHEMA,
+            list_key: "custom-list",
+            list_entry: format!("{}-binding", key),
+            binding_schema: CINNAMON_BINDING_SCHEMA,
+            binding_path: format!("{}/{}-binding/", CINNAMON_BINDING_DIR, key),
+            binding_is_list: true,
+        }
+    }
+
+    fn schema_path(&self) -> String {
+        format!("{}:{}", self.binding_schema, self.binding_path)
+    }
+
+    fn read_list(&self) -> Result<String, String> {
+        run("gsettings", &["get", self.list_schema, self.list_key])
+    }
+
+    fn install(&self, name: &str, command: &str, trigger: &Trigger) -> Result<(), String> {
+        let previous_list = self.read_list()?;
+        let schema_path = self.schema_path();
+        let previous_values: Vec<(&str, Option<String>)> = ["name", "command", "binding"]
+            .into_iter()
+            .map(|key| (key, run("gsettings", &["get", &schema_path, key]).ok()))
+            .collect();
+
+        let accelerator = trigger.to_accelerator();
+        let binding = if self.binding_is_list {
+            format!("['{}']", accelerator)
+        } else {
+            accelerator
+        };
+
+        let result = (|| {
+            let list = gsettings_list_with(&previous_list, &self.list_entry);
+            if list != previous_list {
+                run(
+                    "gsettings",
+                    &["set", self.list_schema, self.list_key, &list],
+                )?;
+            }
+            run("gsettings", &["set", &schema_path, "name", name])?;
+            run("gsettings", &["set", &schema_path, "command", command])?;
+            run("gsettings", &["set", &schema_path, "binding", &binding])?;
+            Ok(())
+        })();
+
+        if result.is_err() {
+            let _ = run(
+                "gsettings",
+                &["set", self.list_schema, self.list_key, &previous_list],
+            );
+            for (key, value) in previous_values {
+                let _ = match value {
+                    Some(value) => run("gsettings", &["set", &schema_path, key, &value]),
+                    None => run("gsettings", &["reset", &schema_path, key]),
+                };
+            }
+        }
+        result
+    }
+
+    fn remove(&self) -> Result<(), String> {
+        let list = self.read_list()?;
+        if gsettings_list_entries(&list).contains(&self.list_entry.as_str()) {
+            let without = gsettings_list_without(&list, &self.list_entry);
+            run(
+                "gsettings",
+                &["set", self.list_schema, self.list_key, &without],
+            )?;
+        }
+        let schema_path = self.schema_path();
+        for key in ["name", "command", "binding"] {
+            let _ = run("gsettings", &["reset", &schema_path, key]);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> Option<(String, Option<String>)> {
+        let list = self.read_list().ok()?;
+        if !gsettings_list_entries(&list).contains(&self.list_entry.as_str()) {
+            return None;
+        }
+        let trigger = run("gsettings", &["get", &self.schema_path(), "binding"])
+            .ok()
+            .map(|value| {
+                value
+                    .trim_matches(|c| c == '[' || c == ']' || c == '\'')
+                    .to_string()
+            })
+            .filter(|value| !value.is_empty());
+        Some((self.binding_path.clone(), trigger))
+    }
+}
+
+fn gsettings_list_entries(list: &str) -> Vec<&str> {
+    list.trim_start_matches("@as")
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|entry| entry.trim().trim_matches('\''))
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+fn format_gsettings_list(entries: &[&str]) -> String {
+    let quoted: Vec<String> = entries.iter().map(|e| format!("'{}'", e)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+/// `gsettings` list literal with `entry` removed.
+fn gsettings_list_without(list: &str, entry: &str) -> String {
+    let entries: Vec<&str> = gsettings_list_entries(list)
+        .into_iter()
+        .filter(|existing| *existing != entry)
+        .collect();
+    format_gsettings_list(&entries)
+}
+
+/// `gsettings` list literal with `entry` appended if missing. Returns `list`
+/// unchanged when it is already present.
+fn gsettings_list_with(list: &str, entry: &str) -> String {
+    let mut entries = gsettings_list_entries(list);
+    if entries.contains(&entry) {
+        return list.to_string();
+    }
+    entries.push(entry);
+    format_gsettings_list(&entries)
+}
+
+// ==========================
+// KDE (kglobalshortcutsrc)
+// ==========================
+
+/// `kwriteconfig`/`kreadconfig` pair and whether this is Plasma 6, which
+/// nests command shortcuts under a `services` group.
+struct KdeTools {
+    write: &'static str,
+    read: &'static str,
+    plasma6: bool,
+}
+
+fn kde_tools() -> Option<KdeTools> {
+    if on_path("kwriteconfig6") && on_path("kreadconfig6") {
+        Some(KdeTools {
+            write: "kwriteconfig6",
+            read: "kreadconfig6",
+            plasma6: true,
+        })
+    } else if on_path("kwriteconfig5") && on_path("kreadconfig5") {
+        Some(KdeTools {
+            write: "kwriteconfig5",
+            read: "kreadconfig5",
+            plasma6: false,
+        })
+    } else {
+        None
+    }
+}
+
+fn kde_desktop_id(key: &str) -> String {
+    format!("{}-capture.desktop", key)
+}
+
+fn kde_desktop_file(key: &str) -> Option<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .filter(|v| !v.is_empty())
+                .map(|home| PathBuf::from(home).join(".local").join("share"))
+        })?;
+    Some(data_home.join("applications").join(kde_desktop_id(key)))
+}
+
+fn kde_shortcuts_file() -> Option<PathBuf> {
+    xdg_config_home().map(|dir| dir.join(KDE_SHORTCUTS_FILE))
+}
+
+/// `--group` arguments addressing our entry in `kglobalshortcutsrc`.
+fn kde_group_args(tools: &KdeTools, desktop_id: &str) -> Vec<String> {
+    let mut args = vec!["--file".to_string(), KDE_SHORTCUTS_FILE.to_string()];
+    if tools.plasma6 {
+        args.extend(["--group".to_string(), "services".to_string()]);
+    }
+    args.extend(["--group".to_string(), desktop_id.to_string()]);
+    args
+}
+
+fn kde_install(key: &str, name: &str, command: &str, trigger: &Trigger) -> Result<(), String> {
+    let tools = kde_tools().ok_or("kwriteconfig not found")?;
+    let desktop_file = kde_desktop_file(key).ok_or("Could not resolve XDG data dir")?;
+    let shortcuts_file = kde_shortcuts_file().ok_or("Could not resolve XDG config dir")?;
+    let desktop_id = kde_desktop_id(key);
+
+    let previous_desktop = std::fs::read_to_string(&desktop_file).ok();
+    let previous_shortcuts = std::fs::read_to_string(&shortcuts_file).ok();
+
+    let result = (|| {
+        if let Some(parent) = desktop_file.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let entry = format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name={name}\n\
+             Exec={command}\n\
+             NoDisplay=true\n\
+             StartupNotify=false\n\
+             X-KDE-GlobalAccel-CommandShortcut=true\n"
+        );
+        std::fs::write(&desktop_file, entry).map_err(|e| e.to_string())?;
+
+        let shortcut = trigger.to_kde();
+        let launch = if tools.plasma6 {
+            shortcut
+        } else {
+            format!("{},none,{}", shortcut, name)
+        };
+        let group = kde_group_args(&tools, &desktop_id);
+        let mut args: Vec<&str> = group.iter().map(String::as_str).collect();
+        args.extend(["--key", "_launch", &launch]);
+        run(tools.write, &args)?;
+
+        let mut args: Vec<&str> = group.iter().map(String::as_str).collect();
+        args.extend(["--key", "_k_friendly_name", name]);
+        run(tools.write, &args)?;
+
+        reload_kglobalaccel(&tools)
+    })();
+
+    if result.is_err() {
+        match previous_desktop {
+            Some(content) => {
+                let _ = std::fs::write(&desktop_file, content);
+            }
+            None => {
+                let _ = std::fs::remove_file(&desktop_file);
+            }
+        }
+        match previous_shortcuts {
+            Some(content) => {
+                let _ = std::fs::write(&shortcuts_file, content);
+            }
+            None => {
+                let _ = std::fs::remove_file(&shortcuts_file);
+            }
+        }
+    }
+    result
+}
+
+fn kde_remove(key: &str, name: &str, command: &str) -> Result<(), String> {
+    let tools = kde_tools().ok_or("kwriteconfig not found")?;
+    let desktop_id = kde_desktop_id(key);
+    let group = kde_group_args(&tools, &desktop_id);
+
+    let mut changed = false;
+    if let Some(desktop_file) = kde_desktop_file(key).filter(|path| path.exists()) {
+        std::fs::remove_file(desktop_file).map_err(|e| e.to_string())?;
+        changed = true;
+    }
+    if kde_status(key).is_some() {
+        for config_key in ["_launch", "_k_friendly_name"] {
+            let mut args: Vec<&str> = group.iter().map(String::as_str).collect();
+            args.extend(["--key", config_key, "--delete"]);
+            run(tools.write, &args)?;
+        }
+        changed = true;
+    }
+
+    // Older releases imported a khotkeys action; disable it in place.
+    let _ = import_legacy_khotkeys(key, name, command);
+
+    if changed {
+        reload_kglobalaccel(&tools)?;
+    }
+    Ok(())
+}
+
+fn kde_status(key: &str) -> Option<(String, Option<String>)> {
+    let tools = kde_tools()?;
+    let group = kde_group_args(&tools, &kde_desktop_id(key));
+    let mut args: Vec<&str> = group.iter().map(String::as_str).collect();
+    args.extend(["--key", "_launch"]);
+    let launch = run(tools.read, &args).ok().filter(|v| !v.is_empty())?;
+    let trigger = launch.split(',').next().map(str::to_string);
+    let location = kde_shortcuts_file()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| KDE_SHORTCUTS_FILE.to_string());
+    Some((location, trigger))
+}
+
+/// kglobalaccel only reads its config at startup; restart it over D-Bus so
+/// the change applies without logging out.
+fn reload_kglobalaccel(tools: &KdeTools) -> Result<(), String> {
+    if tools.plasma6 {
+        return run(
+            "systemctl",
+            &["--user", "try-restart", "plasma-kglobalaccel.service"],
+        )
+        .map(|_| ());
+    }
+
+    let _ = run(
+        "dbus-send",
+        &[
+            "--session",
+            "--type=method_call",
+            "--dest=org.kde.kglobalaccel",
+            "/MainApplication",
+            "org.qtproject.Qt.QCoreApplication.quit",
+        ],
+    );
+    Command::new("kglobalaccel5")
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to restart kglobalaccel: {}", e))
+}
+
+/// Re-import the pre-kglobalshortcutsrc khotkeys action under the same id,
+/// disabled and without a key, so an old binding stops firing.
+fn import_legacy_khotkeys(key: &str, name: &str, command: &str) -> Result<(), String> {
+    let import_file = std::env::temp_dir().join(format!("{}-binding.khotkeys", key));
+    let content = format!(
+        "[Data]
+DataCount=1
+[Data_1]
+Comment={name}
+Enabled=false
+Name={name}
+Type=SIMPLE_ACTION_DATA
+[Data_1Actions]
+ActionsCount=1
+[Data_1Actions0]
+CommandURL={command}
+Type=COMMAND_URL
+[Data_1Triggers]
+TriggersCount=0
+[Main]
+AllowMerge=true
+ImportId={key}-binding
+Version=2"
+    );
+    std::fs::write(&import_file, content).map_err(|e| e.to_string())?;
+    let import_file = import_file.to_string_lossy();
+    run(
+        "qdbus",
+        &[
+            "org.kde.kded5",
+            "/modules/khotkeys",
+            "org.kde.khotkeys.import_shortcuts_list",
+            &import_file,
+        ],
+    )
+    .map(|_| ())
+}
+
+// ==========================
+// XFCE (xfconf)
+// ==========================
+
+/// XFCE keys the property by the accelerator, so a binding the user already
+/// has on it is left alone and the install refused.
+fn xfce_install(command: &str, trigger: &Trigger) -> Result<(), String> {
+    let accelerator = trigger.to_accelerator();
+    let property = format!("{}{}", XFCE_CUSTOM_PREFIX, accelerator);
+    let previous = run(
+        "xfconf-query",
+        &["--channel", XFCE_CHANNEL, "--property", &property],
+    )
+    .ok();
+    if let Some(existing) = previous.as_deref().filter(|value| *value != command) {
+        return Err(format!(
+            "{} is already bound to `{}` in XFCE",
+            accelerator, existing
+        ));
+    }
+
+    let result = run(
+        "xfconf-query",
+        &[
+            "--channel",
+            XFCE_CHANNEL,
+            "--property",
+            &property,
+            "--create",
+            "--type",
+            "string",
+            "--set",
+            command,
+        ],
+    )
+    .map(|_| ());
+
+    if result.is_err() {
+        let _ = match previous {
+            Some(value) => run(
+                "xfconf-query",
+                &[
+                    "--channel",
+                    XFCE_CHANNEL,
+                    "--property",
+                    &property,
+                    "--set",
+                    &value,
+                ],
+            ),
+            None => run(
+                "xfconf-query",
+                &[
+                    "--channel",
+                    XFCE_CHANNEL,
+                    "--property",
+                    &property,
+                    "--reset",
+                ],
+            ),
+        };
+    }
+    result
+}
+
+/// XFCE keys the property by the trigger, so entries are found by command.
+fn xfce_entries(command: &str) -> Vec<String> {
+    let Ok(listing) = run(
+        "xfconf-query",
+        &["--channel", XFCE_CHANNEL, "--list", "--verbose"],
+    ) else {
+        return Vec::new();
+    };
+    listing
+        .lines()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .filter(|(property, value)| {
+            property.starts_with(XFCE_CUSTOM_PREFIX) && value.trim() == command
+        })
+        .map(|(property, _)| property.to_string())
+        .collect()
+}
+
+fn xfce_remove(command: &str) -> Result<(), String> {
+    for property in xfce_entries(command) {
+        run(
+            "xfconf-query",
+            &[
+                "--channel",
+                XFCE_CHANNEL,
+                "--property",
+                &property,
+                "--reset",
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn xfce_status(command: &str) -> Option<(String, Option<String>)> {
+    let property = xfce_entries(command).into_iter().next()?;
+    let trigger = property
+        .strip_prefix(XFCE_CUSTOM_PREFIX)
+        .map(str::to_string);
+    Some((format!("{}:{}", XFCE_CHANNEL, property), trigger))
+}
+
+// ==========================
+// Helpers
+// ==========================
+
+fn current_desktop() -> String {
+    std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default()
+}
+
+/// Stable identifier derived from the app name; bindings are keyed by it so
+/// they can be found again for update and removal.
+fn binding_key(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// Run a configuration tool, returning trimmed stdout or stderr on failure.
+fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(format!(
+            "{} {} failed: {}",
+            program,
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        binding_key, gsettings_list_with, gsettings_list_without, DesktopBackend, GsettingsLayout,
+        Trigger,
+    };
+
+    #[test]
+    fn detects_backends_from_xdg_current_desktop() {
+        assert_eq!(
+            DesktopBackend::from_desktop("X-Cinnamon"),
+            Some(DesktopBackend::Cinnamon)
+        );
+        assert_eq!(
+            DesktopBackend::from_desktop("ubuntu:GNOME"),
+            Some(DesktopBackend::Gnome)
+        );
+        assert_eq!(
+            DesktopBackend::from_desktop("KDE"),
+            Some(DesktopBackend::Kde)
+        );
+        assert_eq!(
+            DesktopBackend::from_desktop("XFCE"),
+            Some(DesktopBackend::Xfce)
+        );
+        assert_eq!(DesktopBackend::from_desktop("sway"), None);
+    }
+
+    #[test]
+    fn triggers_render_per_desktop() {
+        let trigger = Trigger::parse("SUPER+SHIFT+a").unwrap();
+        assert_eq!(trigger.to_accelerator(), "<Super><Shift>a");
+        assert_eq!(trigger.to_kde(), "Meta+Shift+A");
+
+        let trigger = Trigger::parse("ctrl+alt+s").unwrap();
+        assert_eq!(trigger.to_accelerator(), "<Primary><Alt>s");
+        assert!(Trigger::parse("SUPER+SHIFT").is_err());
+        assert!(Trigger::parse("a+b").is_err());
+    }
+
+    #[test]
+    fn installed_triggers_parse_back() {
+        let trigger = Trigger::parse("SUPER+SHIFT+a").unwrap();
+        assert_eq!(
+            Trigger::from_installed(&trigger.to_accelerator()).unwrap(),
+            trigger
+        );
+        let kde = Trigger::from_installed(&trigger.to_kde()).unwrap();
+        assert_eq!(kde.to_kde(), trigger.to_kde());
+        assert_eq!(
+            Trigger::from_installed("<Primary><Alt>s").unwrap(),
+            Trigger::parse("ctrl+alt+s").unwrap()
+        );
+        assert!(Trigger::from_installed("<Super").is_err());
+    }
+
+    #[test]
+    fn gsettings_lists_keep_other_bindings() {
+        let layout = GsettingsLayout::gnome(&binding_key("Squigit"));
+        let ours = layout.list_entry.as_str();
+        assert_eq!(
+            ours,
+            "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings/squigit-binding/"
+        );
+
+        let list = format!("['/custom0/', '{}', '/custom1/']", ours);
+        assert_eq!(
+            gsettings_list_without(&list, ours),
+            "['/custom0/', '/custom1/']"
+        );
+        assert_eq!(gsettings_list_with(&list, ours), list);
+        assert_eq!(gsettings_list_with("@as []", "custom0"), "['custom0']");
+        assert_eq!(gsettings_list_without("@as []", ours), "[]");
+    }
+}
@@ -4,7 +4,7 @@
 use crate::ShortcutConfig;
 use std::ffi::c_void;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU32, Ordering},
     Arc,
 };
 
@@ -33,6 +33,7 @@ struct EventHotKeyID {
 }
 
 const NO_ERR: OSStatus = 0;
+const EVENT_NOT_HANDLED_ERR: OSStatus = -9874;
 
 const K_EVENT_CLASS_KEYBOARD: OSType =
     ((b'k' as u32) << 24) | ((b'e' as u32) << 16) | ((b'y' as u32) << 8) | (b'b' as u32);
@@ -42,6 +43,16 @@ const K_EVENT_HOT_KEY_PRESSED: u32 = 5;
 const HOTKEY_SIGNATURE: OSType =
     ((b'S' as u32) << 24) | ((b'N' as u32) << 16) | ((b'L' as u32) << 8) | (b'M' as u32);
 
+const K_EVENT_PARAM_DIRECT_OBJECT: OSType =
+    ((b'-' as u32) << 24) | ((b'-' as u32) << 16) | ((b'-' as u32) << 8) | (b'-' as u32);
+
+const TYPE_EVENT_HOT_KEY_ID: OSType =
+    ((b'h' as u32) << 24) | ((b'k' as u32) << 16) | ((b'i' as u32) << 8) | (b'd' as u32);
+
+/// Every registration gets its own hotkey ID; all handlers sit on the
+/// application target, so each one checks the ID before firing.
+static NEXT_HOTKEY_ID: AtomicU32 = AtomicU32::new(1);
+
 extern "C" {
     fn GetApplicationEventTarget() -> EventTargetRef;
     fn InstallEventHandler(
@@ -53,6 +64,15 @@ extern "C" {
         out_ref: *mut EventHandlerRef,
     ) -> OSStatus;
     fn RemoveEventHandler(handler: EventHandlerRef) -> OSStatus;
+    fn GetEventParameter(
+        event: EventRef,
+        name: OSType,
+        desired_type: OSType,
+        out_actual_type: *mut OSType,
+        buffer_size: usize,
+        out_actual_size: *mut usize,
+        out_data: *mut c_void,
+    ) -> OSStatus;
     fn RegisterEventHotKey(
         hot_key_code: u32,
         hot_key_modifiers: u32,
@@ -67,15 +87,35 @@ extern "C" {
 }
 
 struct HotkeyContext {
+    id: u32,
     callback: Arc<dyn Fn() + Send + Sync>,
 }
 
 unsafe extern "C" fn hotkey_handler(
     _call_ref: EventHandlerCallRef,
-    _event: EventRef,
+    event: EventRef,
     user_data: *mut c_void,
 ) -> OSStatus {
     let ctx = &*(user_data as *const HotkeyContext);
+
+    let mut hotkey_id = EventHotKeyID {
+        signature: 0,
+        id: 0,
+    };
+    let status = GetEventParameter(
+        event,
+        K_EVENT_PARAM_DIRECT_OBJECT,
+        TYPE_EVENT_HOT_KEY_ID,
+        std::ptr::null_mut(),
+        std::mem::size_of::<EventHotKeyID>(),
+        std::ptr::null_mut(),
+        &mut hotkey_id as *mut EventHotKeyID as *mut c_void,
+    );
+    if status != NO_ERR || hotkey_id.signature != HOTKEY_SIGNATURE || hotkey_id.id != ctx.id {
+        // Another registration's hotkey; let its handler take it.
+        return EVENT_NOT_HANDLED_ERR;
+    }
+
     log::debug!("Global shortcut activated (macOS)");
     (ctx.callback)();
     NO_ERR
@@ -99,6 +139,7 @@ impl MacosHandle {
 
         let keycode = config.macos_keycode;
         let modifiers = config.macos_modifiers;
+        let id = NEXT_HOTKEY_ID.fetch_add(1, Ordering::Relaxed);
 
         let thread = std::thread::Builder::new()
             .name("global-shortcut-macos".into())
@@ -110,7 +151,7 @@ impl MacosHandle {
                     event_kind: K_EVENT_HOT_KEY_PRESSED,
                 };
 
-                let ctx = Box::new(HotkeyContext { callback });
+                let ctx = Box::new(HotkeyContext { id, callback });
                 let ctx_ptr = Box::into_raw(ctx) as *mut c_void;
 
                 let mut handler_ref: EventHandlerRef = std::ptr::null_mut();
@@ -134,7 +175,7 @@ impl MacosHandle {
 
                 let hotkey_id = EventHotKeyID {
                     signature: HOTKEY_SIGNATURE,
-                    id: 1,
+                    id,
                 };
 
                 let mut hotkey_ref: EventHotKeyRef = std::ptr::null_mut();
@@ -166,8 +207,7 @@ impl MacosHandle {
             .map_err(|e| format!("Failed to spawn shortcut thread: {}", e))?;
 
         rx.recv()
-            .map_err(|_| "Shortcut thread died before registration".to_string())?
-            .map_err(|e| e)?;
+            .map_err(|_| "Shortcut thread died before registration".to_string())??;
 
         Ok(Self {
             shutdown,
@@ -1,57 +1,170 @@
 // Copyright 2026 a7mddra
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::ShortcutConfig;
+use crate::{ShortcutConfig, ShortcutError};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    Arc,
+    mpsc, Arc, Mutex,
 };
 
-use windows::Win32::Foundation::HWND;
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{
+    ERROR_HOTKEY_ALREADY_REGISTERED, HANDLE, HWND, LPARAM, LRESULT, WPARAM,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Power::{
+    RegisterSuspendResumeNotification, UnregisterSuspendResumeNotification, HPOWERNOTIFY,
+};
 use windows::Win32::UI::Input::KeyboardAndMouse::{
     RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetMessageW, PostThreadMessageW, MSG, WM_HOTKEY, WM_QUIT,
+    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetMessageW, PostMessageW,
+    PostThreadMessageW, RegisterClassW, TranslateMessage, DEVICE_NOTIFY_WINDOW_HANDLE,
+    HWND_MESSAGE, MSG, PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND, WINDOW_EX_STYLE, WINDOW_STYLE,
+    WM_APP, WM_HOTKEY, WM_POWERBROADCAST, WM_QUIT, WNDCLASSW,
 };
 
 const HOTKEY_ID: i32 = 0x7001;
+const WINDOW_CLASS: PCWSTR = w!("SquigitGlobalShortcutWindow");
+
+/// Posted to the message window to drop and re-register the hotkey.
+const WM_REREGISTER: u32 = WM_APP + 1;
+/// Posted by the window procedure when the system resumes from sleep.
+const WM_RESUMED: u32 = WM_APP + 2;
+
+/// Reply channel for a pending `WM_REREGISTER`.
+type ReregisterReply = Arc<Mutex<Option<mpsc::Sender<Result<(), ShortcutError>>>>>;
 
 pub(crate) struct WindowsHandle {
     thread_id: u32,
-
+    /// Message-only window handle, stored as `isize` so the handle is `Send`.
+    hwnd: isize,
     shutdown: Arc<AtomicBool>,
+    reregister_result: ReregisterReply,
 
     _thread: std::thread::JoinHandle<()>,
 }
 
+struct Hotkey {
+    modifiers: HOT_KEY_MODIFIERS,
+    vk: u32,
+}
+
+impl Hotkey {
+    fn register(&self, hwnd: HWND) -> Result<(), ShortcutError> {
+        unsafe { RegisterHotKey(hwnd, HOTKEY_ID, self.modifiers, self.vk) }.map_err(|e| {
+            if e.code() == ERROR_HOTKEY_ALREADY_REGISTERED.to_hresult() {
+                ShortcutError::AlreadyRegistered
+            } else {
+                ShortcutError::Failed(format!("RegisterHotKey failed: {}", e))
+            }
+        })
+    }
+
+    fn reregister(&self, hwnd: HWND) -> Result<(), ShortcutError> {
+        unsafe {
+            let _ = UnregisterHotKey(hwnd, HOTKEY_ID);
+        }
+        self.register(hwnd)
+    }
+}
+
+unsafe extern "system" fn window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    // WM_POWERBROADCAST is sent, not posted, so it never reaches the queue;
+    // forward resume events to the loop.
+    if msg == WM_POWERBROADCAST
+        && (wparam.0 as u32 == PBT_APMRESUMEAUTOMATIC || wparam.0 as u32 == PBT_APMRESUMESUSPEND)
+    {
+        let _ = PostMessageW(hwnd, WM_RESUMED, WPARAM(0), LPARAM(0));
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+fn create_message_window() -> Result<HWND, ShortcutError> {
+    unsafe {
+        let instance = GetModuleHandleW(None)
+            .map_err(|e| ShortcutError::Failed(format!("GetModuleHandleW failed: {}", e)))?;
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance.into(),
+            lpszClassName: WINDOW_CLASS,
+            ..Default::default()
+        };
+        // Re-registering after a previous handle is fine: the class persists
+        // for the process and CreateWindowExW only needs it to exist.
+        RegisterClassW(&class);
+
+        CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            WINDOW_CLASS,
+            w!("Squigit Global Shortcut"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            None,
+            instance,
+            None,
+        )
+        .map_err(|e| ShortcutError::Failed(format!("CreateWindowExW failed: {}", e)))
+    }
+}
+
 impl WindowsHandle {
-    pub fn register<F>(config: ShortcutConfig, callback: F) -> Result<Self, String>
+    pub fn register<F>(config: ShortcutConfig, callback: F) -> Result<Self, ShortcutError>
     where
         F: Fn() + Send + Sync + 'static,
     {
         let shutdown = Arc::new(AtomicBool::new(false));
         let shutdown_clone = shutdown.clone();
         let callback = Arc::new(callback);
+        let reregister_result: ReregisterReply = Arc::new(Mutex::new(None));
+        let reregister_result_clone = reregister_result.clone();
 
-        let (tx, rx) = std::sync::mpsc::channel::<Result<u32, String>>();
+        let (tx, rx) = mpsc::channel::<Result<(u32, isize), ShortcutError>>();
 
         let thread = std::thread::Builder::new()
             .name("global-shortcut-windows".into())
             .spawn(move || {
                 let thread_id = unsafe { windows::Win32::System::Threading::GetCurrentThreadId() };
 
-                let modifiers = HOT_KEY_MODIFIERS(config.windows_modifiers as u32);
-                let result = unsafe {
-                    RegisterHotKey(HWND::default(), HOTKEY_ID, modifiers, config.windows_vk)
+                let hwnd = match create_message_window() {
+                    Ok(hwnd) => hwnd,
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
                 };
 
-                if let Err(e) = result {
-                    let _ = tx.send(Err(format!("RegisterHotKey failed: {}", e)));
+                let hotkey = Hotkey {
+                    modifiers: HOT_KEY_MODIFIERS(config.windows_modifiers),
+                    vk: config.windows_vk,
+                };
+                if let Err(e) = hotkey.register(hwnd) {
+                    unsafe {
+                        let _ = DestroyWindow(hwnd);
+                    }
+                    let _ = tx.send(Err(e));
                     return;
                 }
 
-                let _ = tx.send(Ok(thread_id));
+                // Hotkeys can drop across sleep; resume notifications let the
+                // loop re-register. Message-only windows get no broadcasts, so
+                // this explicit registration is required.
+                let power_notify: Option<HPOWERNOTIFY> = unsafe {
+                    RegisterSuspendResumeNotification(HANDLE(hwnd.0), DEVICE_NOTIFY_WINDOW_HANDLE)
+                }
+                .ok();
+
+                let _ = tx.send(Ok((thread_id, hwnd.0 as isize)));
 
                 let mut msg = MSG::default();
                 loop {
@@ -61,31 +174,80 @@ impl WindowsHandle {
                         break;
                     }
 
-                    if msg.message == WM_HOTKEY && msg.wParam.0 as i32 == HOTKEY_ID {
-                        log::debug!("Global shortcut activated (Windows)");
-                        callback();
+                    match msg.message {
+                        WM_HOTKEY if msg.wParam.0 as i32 == HOTKEY_ID => {
+                            log::debug!("Global shortcut activated (Windows)");
+                            callback();
+                        }
+                        WM_RESUMED => {
+                            log::info!("Resumed from sleep, re-registering global shortcut");
+                            if let Err(e) = hotkey.reregister(hwnd) {
+                                log::warn!("Global shortcut re-registration failed: {}", e);
+                            }
+                        }
+                        WM_REREGISTER => {
+                            let result = hotkey.reregister(hwnd);
+                            if let Some(reply) = reregister_result_clone
+                                .lock()
+                                .ok()
+                                .and_then(|mut guard| guard.take())
+                            {
+                                let _ = reply.send(result);
+                            }
+                        }
+                        _ => unsafe {
+                            let _ = TranslateMessage(&msg);
+                            DispatchMessageW(&msg);
+                        },
                     }
                 }
 
                 unsafe {
-                    let _ = UnregisterHotKey(HWND::default(), HOTKEY_ID);
+                    if let Some(power_notify) = power_notify {
+                        let _ = UnregisterSuspendResumeNotification(power_notify);
+                    }
+                    let _ = UnregisterHotKey(hwnd, HOTKEY_ID);
+                    let _ = DestroyWindow(hwnd);
                 }
                 log::info!("Windows global shortcut listener exited");
             })
-            .map_err(|e| format!("Failed to spawn shortcut thread: {}", e))?;
+            .map_err(|e| {
+                ShortcutError::Failed(format!("Failed to spawn shortcut thread: {}", e))
+            })?;
 
-        let thread_id = rx
-            .recv()
-            .map_err(|_| "Shortcut thread died before registration".to_string())?
-            .map_err(|e| e)?;
+        let (thread_id, hwnd) = rx.recv().map_err(|_| {
+            ShortcutError::Failed("Shortcut thread died before registration".to_string())
+        })??;
 
         Ok(Self {
             thread_id,
+            hwnd,
             shutdown,
+            reregister_result,
             _thread: thread,
         })
     }
 
+    pub fn reregister(&self) -> Result<(), ShortcutError> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        if let Ok(mut guard) = self.reregister_result.lock() {
+            *guard = Some(reply_tx);
+        }
+        unsafe {
+            PostMessageW(
+                HWND(self.hwnd as *mut core::ffi::c_void),
+                WM_REREGISTER,
+                WPARAM(0),
+                LPARAM(0),
+            )
+        }
+        .map_err(|e| ShortcutError::Failed(format!("PostMessageW failed: {}", e)))?;
+
+        reply_rx
+            .recv_timeout(std::time::Duration::from_secs(2))
+            .map_err(|_| ShortcutError::Failed("Shortcut thread did not respond".to_string()))?
+    }
+
     pub fn unregister(self) {
         self.shutdown.store(true, Ordering::SeqCst);
 
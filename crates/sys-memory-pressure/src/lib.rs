@@ -0,0 +1,94 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! System memory pressure notifications.
+//!
+//! Caches that are cheap to rebuild should be dropped when the system runs
+//! short on memory. This crate reports changes of the pressure level so
+//! callers can react. Each platform uses its native signal:
+//!
+//! - **Linux**: pressure stall information from `/proc/pressure/memory`,
+//!   falling back to `MemAvailable` in `/proc/meminfo` on kernels without PSI
+//! - **Windows**: `GlobalMemoryStatusEx` memory load
+//! - **macOS**: libdispatch `DISPATCH_SOURCE_TYPE_MEMORYPRESSURE`
+//!
+//! Linux and Windows poll every few seconds; macOS is notified by the kernel.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use sys_memory_pressure::{MemoryPressure, MemoryPressureWatcher};
+//!
+//! let watcher = MemoryPressureWatcher::start(|level| {
+//!     if level != MemoryPressure::Normal {
+//!         println!("Memory is running low");
+//!     }
+//! })
+//! .expect("Failed to watch memory pressure");
+//!
+//! // Later: watcher.stop();
+//! ```
+
+use std::sync::Arc;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+mod poll;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MemoryPressure {
+    Normal,
+    /// Memory is getting scarce; drop what is cheap to rebuild.
+    Warning,
+    /// The system is reclaiming aggressively or about to kill processes.
+    Critical,
+}
+
+pub(crate) type PressureCallback = Arc<dyn Fn(MemoryPressure) + Send + Sync + 'static>;
+
+/// Reports every change of the pressure level, starting with the first
+/// level above [`MemoryPressure::Normal`].
+pub struct MemoryPressureWatcher {
+    #[cfg(target_os = "linux")]
+    inner: poll::PollingWatcher,
+    #[cfg(target_os = "windows")]
+    inner: poll::PollingWatcher,
+    #[cfg(target_os = "macos")]
+    inner: macos::MacosWatcher,
+}
+
+impl MemoryPressureWatcher {
+    pub fn start<F>(callback: F) -> Result<Self, String>
+    where
+        F: Fn(MemoryPressure) + Send + Sync + 'static,
+    {
+        let callback: PressureCallback = Arc::new(callback);
+
+        #[cfg(target_os = "linux")]
+        {
+            let inner =
+                poll::PollingWatcher::start("memory-pressure-psi", linux::current, callback)?;
+            Ok(Self { inner })
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let inner =
+                poll::PollingWatcher::start("memory-pressure-load", windows::current, callback)?;
+            Ok(Self { inner })
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let inner = macos::MacosWatcher::start(callback)?;
+            Ok(Self { inner })
+        }
+    }
+
+    pub fn stop(self) {
+        self.inner.stop();
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MemoryPressure;
+
+const PSI_PATH: &str = "/proc/pressure/memory";
+const MEMINFO_PATH: &str = "/proc/meminfo";
+
+/// Share of the last 10s in which at least one task stalled on memory.
+const SOME_WARNING_PERCENT: f64 = 10.0;
+const SOME_CRITICAL_PERCENT: f64 = 40.0;
+/// Share of the last 10s in which all tasks stalled on memory.
+const FULL_WARNING_PERCENT: f64 = 2.0;
+const FULL_CRITICAL_PERCENT: f64 = 10.0;
+
+/// Available memory thresholds for kernels without PSI.
+const AVAILABLE_WARNING_PERCENT: f64 = 10.0;
+const AVAILABLE_CRITICAL_PERCENT: f64 = 5.0;
+
+pub(crate) fn current() -> Result<MemoryPressure, String> {
+    if let Ok(psi) = std::fs::read_to_string(PSI_PATH) {
+        if let Some(level) = level_from_psi(&psi) {
+            return Ok(level);
+        }
+    }
+    let meminfo = std::fs::read_to_string(MEMINFO_PATH)
+        .map_err(|e| format!("Failed to read {}: {}", MEMINFO_PATH, e))?;
+    level_from_meminfo(&meminfo).ok_or_else(|| format!("Unexpected {} format", MEMINFO_PATH))
+}
+
+/// Classify `/proc/pressure/memory` by its `avg10` values:
+///
+/// ```text
+/// some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+/// full avg10=0.00 avg60=0.00 avg300=0.00 total=0
+/// ```
+fn level_from_psi(content: &str) -> Option<MemoryPressure> {
+    let avg10 = |kind: &str| {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix(kind)?.strip_prefix(' '))?
+            .split_whitespace()
+            .find_map(|field| field.strip_prefix("avg10="))?
+            .parse::<f64>()
+            .ok()
+    };
+    let some = avg10("some")?;
+    let full = avg10("full").unwrap_or(0.0);
+
+    Some(
+        if some >= SOME_CRITICAL_PERCENT || full >= FULL_CRITICAL_PERCENT {
+            MemoryPressure::Critical
+        } else if some >= SOME_WARNING_PERCENT || full >= FULL_WARNING_PERCENT {
+            MemoryPressure::Warning
+        } else {
+            MemoryPressure::Normal
+        },
+    )
+}
+
+/// Classify `/proc/meminfo` by `MemAvailable` relative to `MemTotal`.
+fn level_from_meminfo(content: &str) -> Option<MemoryPressure> {
+    let kib = |key: &str| {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))?
+            .split_whitespace()
+            .next()?
+            .parse::<u64>()
+            .ok()
+    };
+    let total = kib("MemTotal").filter(|total| *total > 0)?;
+    let available = kib("MemAvailable")?;
+    let percent = available as f64 * 100.0 / total as f64;
+
+    Some(if percent < AVAILABLE_CRITICAL_PERCENT {
+        MemoryPressure::Critical
+    } else if percent < AVAILABLE_WARNING_PERCENT {
+        MemoryPressure::Warning
+    } else {
+        MemoryPressure::Normal
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn psi(some: f64, full: f64) -> String {
+        format!(
+            "some avg10={:.2} avg60=0.00 avg300=0.00 total=0\n\
+             full avg10={:.2} avg60=0.00 avg300=0.00 total=0\n",
+            some, full
+        )
+    }
+
+    #[test]
+    fn classifies_psi() {
+        assert_eq!(level_from_psi(&psi(0.5, 0.0)), Some(MemoryPressure::Normal));
+        assert_eq!(
+            level_from_psi(&psi(12.0, 0.0)),
+            Some(MemoryPressure::Warning)
+        );
+        assert_eq!(
+            level_from_psi(&psi(3.0, 2.5)),
+            Some(MemoryPressure::Warning)
+        );
+        assert_eq!(
+            level_from_psi(&psi(20.0, 15.0)),
+            Some(MemoryPressure::Critical)
+        );
+        assert_eq!(level_from_psi("garbage"), None);
+    }
+
+    #[test]
+    fn classifies_meminfo() {
+        let meminfo = |available: u64| {
+            format!(
+                "MemTotal:       1000000 kB\nMemFree:          10000 kB\nMemAvailable:   {} kB\n",
+                available
+            )
+        };
+        assert_eq!(
+            level_from_meminfo(&meminfo(500_000)),
+            Some(MemoryPressure::Normal)
+        );
+        assert_eq!(
+            level_from_meminfo(&meminfo(80_000)),
+            Some(MemoryPressure::Warning)
+        );
+        assert_eq!(
+            level_from_meminfo(&meminfo(20_000)),
+            Some(MemoryPressure::Critical)
+        );
+        assert_eq!(level_from_meminfo("MemTotal: 0 kB\n"), None);
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MemoryPressure, PressureCallback};
+use std::ffi::c_void;
+
+type DispatchObject = *mut c_void;
+type DispatchFunction = unsafe extern "C" fn(context: *mut c_void);
+
+const DISPATCH_MEMORYPRESSURE_NORMAL: usize = 0x01;
+const DISPATCH_MEMORYPRESSURE_WARN: usize = 0x02;
+const DISPATCH_MEMORYPRESSURE_CRITICAL: usize = 0x04;
+const DISPATCH_QUEUE_PRIORITY_DEFAULT: isize = 0;
+
+// libdispatch is part of libSystem, which is always linked.
+extern "C" {
+    static _dispatch_source_type_memorypressure: c_void;
+    fn dispatch_get_global_queue(identifier: isize, flags: usize) -> DispatchObject;
+    fn dispatch_source_create(
+        source_type: *const c_void,
+        handle: usize,
+        mask: usize,
+        queue: DispatchObject,
+    ) -> DispatchObject;
+    fn dispatch_set_context(object: DispatchObject, context: *mut c_void);
+    fn dispatch_source_set_event_handler_f(source: DispatchObject, handler: DispatchFunction);
+    fn dispatch_source_set_cancel_handler_f(source: DispatchObject, handler: DispatchFunction);
+    fn dispatch_source_get_data(source: DispatchObject) -> usize;
+    fn dispatch_resume(object: DispatchObject);
+    fn dispatch_source_cancel(source: DispatchObject);
+    fn dispatch_release(object: DispatchObject);
+}
+
+struct CallbackContext {
+    callback: PressureCallback,
+    /// The source itself, to read the event data from the handler.
+    source: DispatchObject,
+}
+
+unsafe extern "C" fn pressure_event(context: *mut c_void) {
+    // SAFETY: `context` is the box set on the source, freed only by the
+    // cancel handler, which never runs concurrently with this one.
+    let context = unsafe { &*(context as *const CallbackContext) };
+    let data = unsafe { dispatch_source_get_data(context.source) };
+    let level = if data & DISPATCH_MEMORYPRESSURE_CRITICAL != 0 {
+        MemoryPressure::Critical
+    } else if data & DISPATCH_MEMORYPRESSURE_WARN != 0 {
+        MemoryPressure::Warning
+    } else {
+        MemoryPressure::Normal
+    };
+    log::info!("Memory pressure (dispatch): {:?}", level);
+    (context.callback)(level);
+}
+
+unsafe extern "C" fn pressure_cancelled(context: *mut c_void) {
+    // SAFETY: The source is cancelled, so no event handler runs anymore.
+    unsafe { drop(Box::from_raw(context as *mut CallbackContext)) };
+}
+
+pub(crate) struct MacosWatcher {
+    source: DispatchObject,
+}
+
+// SAFETY: Dispatch objects are thread-safe and the source is only cancelled
+// and released once, in `stop`.
+unsafe impl Send for MacosWatcher {}
+unsafe impl Sync for MacosWatcher {}
+
+impl MacosWatcher {
+    pub fn start(callback: PressureCallback) -> Result<Self, String> {
+        // SAFETY: Standard libdispatch source setup; the context outlives
+        // the source until the cancel handler frees it.
+        unsafe {
+            let queue = dispatch_get_global_queue(DISPATCH_QUEUE_PRIORITY_DEFAULT, 0);
+            let source = dispatch_source_create(
+                &_dispatch_source_type_memorypressure as *const c_void,
+                0,
+                DISPATCH_MEMORYPRESSURE_NORMAL
+                    | DISPATCH_MEMORYPRESSURE_WARN
+                    | DISPATCH_MEMORYPRESSURE_CRITICAL,
+                queue,
+            );
+            if source.is_null() {
+                return Err("Failed to create memory pressure dispatch source".to_string());
+            }
+
+            let context = Box::into_raw(Box::new(CallbackContext { callback, source }));
+            dispatch_set_context(source, context as *mut c_void);
+            dispatch_source_set_event_handler_f(source, pressure_event);
+            dispatch_source_set_cancel_handler_f(source, pressure_cancelled);
+            dispatch_resume(source);
+
+            Ok(Self { source })
+        }
+    }
+
+    pub fn stop(self) {
+        // SAFETY: The source was created and resumed in `start`.
+        unsafe {
+            dispatch_source_cancel(self.source);
+            dispatch_release(self.source);
+        }
+    }
+}
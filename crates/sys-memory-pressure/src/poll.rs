@@ -0,0 +1,60 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MemoryPressure, PressureCallback};
+use std::sync::mpsc;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Samples the level on a thread and reports changes.
+pub(crate) struct PollingWatcher {
+    stop: mpsc::Sender<()>,
+
+    _thread: std::thread::JoinHandle<()>,
+}
+
+impl PollingWatcher {
+    pub fn start(
+        name: &str,
+        sample: fn() -> Result<MemoryPressure, String>,
+        callback: PressureCallback,
+    ) -> Result<Self, String> {
+        // Fail early when the source cannot be read at all.
+        sample()?;
+
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = std::thread::Builder::new()
+            .name(name.into())
+            .spawn(move || {
+                let mut last = MemoryPressure::Normal;
+                loop {
+                    match sample() {
+                        Ok(level) if level != last => {
+                            log::info!("Memory pressure: {:?}", level);
+                            last = level;
+                            callback(level);
+                        }
+                        Ok(_) => {}
+                        Err(e) => log::debug!("Failed to sample memory pressure: {}", e),
+                    }
+                    match stopped.recv_timeout(POLL_INTERVAL) {
+                        Err(mpsc::RecvTimeoutError::Timeout) => {}
+                        _ => break,
+                    }
+                }
+                log::info!("Memory pressure poller exited");
+            })
+            .map_err(|e| format!("Failed to spawn memory pressure thread: {}", e))?;
+
+        Ok(Self {
+            stop,
+            _thread: thread,
+        })
+    }
+
+    /// No callback fires after this returns, except one already running.
+    pub fn stop(self) {
+        let _ = self.stop.send(());
+    }
+}
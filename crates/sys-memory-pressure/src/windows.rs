@@ -0,0 +1,29 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MemoryPressure;
+use windows_sys::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+/// Percent of physical memory in use.
+const LOAD_WARNING_PERCENT: u32 = 85;
+const LOAD_CRITICAL_PERCENT: u32 = 95;
+
+pub(crate) fn current() -> Result<MemoryPressure, String> {
+    // SAFETY: Zeroed MEMORYSTATUSEX is valid; dwLength must be set first.
+    let mut status: MEMORYSTATUSEX = unsafe { std::mem::zeroed() };
+    status.dwLength = std::mem::size_of::<MEMORYSTATUSEX>() as u32;
+    if unsafe { GlobalMemoryStatusEx(&mut status) } == 0 {
+        return Err(format!(
+            "GlobalMemoryStatusEx failed: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+
+    Ok(if status.dwMemoryLoad >= LOAD_CRITICAL_PERCENT {
+        MemoryPressure::Critical
+    } else if status.dwMemoryLoad >= LOAD_WARNING_PERCENT {
+        MemoryPressure::Warning
+    } else {
+        MemoryPressure::Normal
+    })
+}
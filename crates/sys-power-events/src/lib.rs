@@ -0,0 +1,114 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! System suspend/resume notifications and battery status.
+//!
+//! Long-lived sessions (D-Bus connections, registered hotkeys, open sockets,
+//! audio devices) can come back stale after sleep. This crate reports the
+//! transition so callers can re-initialize them. Each platform uses its
+//! native notification:
+//!
+//! - **Linux**: logind `PrepareForSleep` on the system bus
+//! - **Windows**: `PowerRegisterSuspendResumeNotification` callback
+//! - **macOS**: IOKit `IORegisterForSystemPower`
+//!
+//! [`battery_status`] reads the current battery state from UPower on Linux,
+//! `GetSystemPowerStatus` on Windows and IOKit power sources on macOS.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use sys_power_events::{PowerEvent, PowerWatcher};
+//!
+//! let watcher = PowerWatcher::start(|event| {
+//!     if event == PowerEvent::Resumed {
+//!         println!("System resumed");
+//!     }
+//! })
+//! .expect("Failed to watch power events");
+//!
+//! // Later: watcher.stop();
+//! ```
+
+use std::sync::Arc;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerEvent {
+    /// The system is about to sleep.
+    Suspending,
+    /// The system woke up.
+    Resumed,
+}
+
+/// Snapshot of the system battery.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatteryStatus {
+    /// Running from the battery rather than external power.
+    pub on_battery: bool,
+    /// Charge in percent, when the platform reports it.
+    pub percent: Option<f32>,
+}
+
+/// Current battery state, or `None` when the machine has no battery or
+/// the platform service cannot be reached. Blocking.
+pub fn battery_status() -> Option<BatteryStatus> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::battery_status()
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::battery_status()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        macos::battery_status()
+    }
+}
+
+pub(crate) type PowerCallback = Arc<dyn Fn(PowerEvent) + Send + Sync + 'static>;
+
+pub struct PowerWatcher {
+    #[cfg(target_os = "linux")]
+    inner: linux::LinuxWatcher,
+    #[cfg(target_os = "windows")]
+    inner: windows::WindowsWatcher,
+    #[cfg(target_os = "macos")]
+    inner: macos::MacosWatcher,
+}
+
+impl PowerWatcher {
+    pub fn start<F>(callback: F) -> Result<Self, String>
+    where
+        F: Fn(PowerEvent) + Send + Sync + 'static,
+    {
+        let callback: PowerCallback = Arc::new(callback);
+
+        #[cfg(target_os = "linux")]
+        {
+            let inner = linux::LinuxWatcher::start(callback)?;
+            Ok(Self { inner })
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let inner = windows::WindowsWatcher::start(callback)?;
+            Ok(Self { inner })
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let inner = macos::MacosWatcher::start(callback)?;
+            Ok(Self { inner })
+        }
+    }
+
+    pub fn stop(self) {
+        self.inner.stop();
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BatteryStatus, PowerCallback, PowerEvent};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc, Arc,
+};
+
+const LOGIND_DESTINATION: &str = "org.freedesktop.login1";
+const LOGIND_PATH: &str = "/org/freedesktop/login1";
+const LOGIND_INTERFACE: &str = "org.freedesktop.login1.Manager";
+const SLEEP_SIGNAL: &str = "PrepareForSleep";
+
+const UPOWER_DESTINATION: &str = "org.freedesktop.UPower";
+const UPOWER_PATH: &str = "/org/freedesktop/UPower";
+const UPOWER_INTERFACE: &str = "org.freedesktop.UPower";
+/// Composite device that sums up all batteries.
+const UPOWER_DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+const UPOWER_DEVICE_INTERFACE: &str = "org.freedesktop.UPower.Device";
+
+pub(crate) struct LinuxWatcher {
+    running: Arc<AtomicBool>,
+
+    _thread: std::thread::JoinHandle<()>,
+}
+
+impl LinuxWatcher {
+    pub fn start(callback: PowerCallback) -> Result<Self, String> {
+        let running = Arc::new(AtomicBool::new(true));
+        let running_clone = running.clone();
+        let (tx, rx) = mpsc::channel::<Result<(), String>>();
+
+        let thread = std::thread::Builder::new()
+            .name("power-events-logind".into())
+            .spawn(move || {
+                let signals = match subscribe() {
+                    Ok(signals) => {
+                        let _ = tx.send(Ok(()));
+                        signals
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
+                };
+
+                // PrepareForSleep(true) before suspend, (false) after resume.
+                for message in signals {
+                    if !running_clone.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(sleeping) = message.body().deserialize::<bool>() else {
+                        continue;
+                    };
+                    let event = if sleeping {
+                        PowerEvent::Suspending
+                    } else {
+                        PowerEvent::Resumed
+                    };
+                    log::info!("Power event (logind): {:?}", event);
+                    callback(event);
+                }
+                log::info!("logind power listener exited");
+            })
+            .map_err(|e| format!("Failed to spawn power listener thread: {}", e))?;
+
+        rx.recv()
+            .map_err(|_| "Power listener thread died before subscribing".to_string())??;
+
+        Ok(Self {
+            running,
+            _thread: thread,
+        })
+    }
+
+    /// The listener thread blocks on the bus and exits at the next signal;
+    /// no callback fires after this returns.
+    pub fn stop(self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+fn subscribe() -> Result<zbus::blocking::proxy::SignalIterator<'static>, String> {
+    let connection = zbus::blocking::Connection::system()
+        .map_err(|e| format!("Failed to connect to system bus: {}", e))?;
+    let proxy = zbus::blocking::Proxy::new_owned(
+        connection,
+        LOGIND_DESTINATION,
+        LOGIND_PATH,
+        LOGIND_INTERFACE,
+    )
+    .map_err(|e| format!("Failed to create logind proxy: {}", e))?;
+    proxy
+        .receive_signal(SLEEP_SIGNAL)
+        .map_err(|e| format!("Failed to subscribe to {}: {}", SLEEP_SIGNAL, e))
+}
+
+pub(crate) fn battery_status() -> Option<BatteryStatus> {
+    let connection = zbus::blocking::Connection::system().ok()?;
+    let device = zbus::blocking::Proxy::new(
+        &connection,
+        UPOWER_DESTINATION,
+        UPOWER_DISPLAY_DEVICE_PATH,
+        UPOWER_DEVICE_INTERFACE,
+    )
+    .ok()?;
+    if !device.get_property::<bool>("IsPresent").ok()? {
+        return None;
+    }
+
+    let upower = zbus::blocking::Proxy::new(
+        &connection,
+        UPOWER_DESTINATION,
+        UPOWER_PATH,
+        UPOWER_INTERFACE,
+    )
+    .ok()?;
+    Some(BatteryStatus {
+        on_battery: upower.get_property::<bool>("OnBattery").unwrap_or(false),
+        percent: device
+            .get_property::<f64>("Percentage")
+            .ok()
+            .map(|percent| percent as f32),
+    })
+}
@@ -0,0 +1,271 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BatteryStatus, PowerCallback, PowerEvent};
+use std::ffi::c_void;
+use std::sync::{
+    atomic::{AtomicU32, AtomicUsize, Ordering},
+    mpsc, Arc,
+};
+
+type IoConnect = u32;
+type IoObject = u32;
+type IoNotificationPortRef = *mut c_void;
+type CfRunLoopRef = *mut c_void;
+type CfRunLoopSourceRef = *mut c_void;
+type CfStringRef = *const c_void;
+type CfTypeRef = *const c_void;
+type CfArrayRef = *const c_void;
+type CfDictionaryRef = *const c_void;
+type CfNumberRef = *const c_void;
+type IoServiceInterestCallback = unsafe extern "C" fn(
+    refcon: *mut c_void,
+    service: IoObject,
+    message: u32,
+    argument: *mut c_void,
+);
+
+// iokit_common_msg(n) = sys_iokit | sub_iokit_common | n
+const IO_MESSAGE_CAN_SYSTEM_SLEEP: u32 = 0xE000_0270;
+const IO_MESSAGE_SYSTEM_WILL_SLEEP: u32 = 0xE000_0280;
+const IO_MESSAGE_SYSTEM_HAS_POWERED_ON: u32 = 0xE000_0300;
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IORegisterForSystemPower(
+        refcon: *mut c_void,
+        the_port_ref: *mut IoNotificationPortRef,
+        callback: IoServiceInterestCallback,
+        notifier: *mut IoObject,
+    ) -> IoConnect;
+    fn IODeregisterForSystemPower(notifier: *mut IoObject) -> i32;
+    fn IOAllowPowerChange(kernel_port: IoConnect, notification_id: isize) -> i32;
+    fn IONotificationPortGetRunLoopSource(notify: IoNotificationPortRef) -> CfRunLoopSourceRef;
+    fn IONotificationPortDestroy(notify: IoNotificationPortRef);
+    fn IOServiceClose(connect: IoConnect) -> i32;
+    fn IOPSCopyPowerSourcesInfo() -> CfTypeRef;
+    fn IOPSCopyPowerSourcesList(blob: CfTypeRef) -> CfArrayRef;
+    fn IOPSGetPowerSourceDescription(blob: CfTypeRef, ps: CfTypeRef) -> CfDictionaryRef;
+    fn IOPSGetProvidingPowerSourceType(snapshot: CfTypeRef) -> CfStringRef;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFRunLoopDefaultMode: CfStringRef;
+    fn CFRunLoopGetCurrent() -> CfRunLoopRef;
+    fn CFRunLoopAddSource(rl: CfRunLoopRef, source: CfRunLoopSourceRef, mode: CfStringRef);
+    fn CFRunLoopRun();
+    fn CFRunLoopStop(rl: CfRunLoopRef);
+    fn CFRelease(cf: CfTypeRef);
+    fn CFEqual(cf1: CfTypeRef, cf2: CfTypeRef) -> u8;
+    fn CFArrayGetCount(array: CfArrayRef) -> isize;
+    fn CFArrayGetValueAtIndex(array: CfArrayRef, index: isize) -> *const c_void;
+    fn CFDictionaryGetValue(dict: CfDictionaryRef, key: *const c_void) -> *const c_void;
+    fn CFNumberGetValue(number: CfNumberRef, number_type: isize, value: *mut c_void) -> u8;
+    fn CFStringCreateWithCString(
+        alloc: *const c_void,
+        c_str: *const std::ffi::c_char,
+        encoding: u32,
+    ) -> CfStringRef;
+}
+
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+const CF_NUMBER_SINT32_TYPE: isize = 3;
+
+struct CallbackContext {
+    callback: PowerCallback,
+    /// Root power domain port, needed to acknowledge sleep notifications.
+    root_port: AtomicU32,
+}
+
+unsafe extern "C" fn power_callback(
+    refcon: *mut c_void,
+    _service: IoObject,
+    message: u32,
+    argument: *mut c_void,
+) {
+    // SAFETY: `refcon` is the context owned by the listener thread, which
+    // outlives the registration.
+    let context = unsafe { &*(refcon as *const CallbackContext) };
+    let root_port = context.root_port.load(Ordering::SeqCst);
+
+    match message {
+        IO_MESSAGE_CAN_SYSTEM_SLEEP => unsafe {
+            IOAllowPowerChange(root_port, argument as isize);
+        },
+        IO_MESSAGE_SYSTEM_WILL_SLEEP => {
+            log::info!("Power event (IOKit): {:?}", PowerEvent::Suspending);
+            (context.callback)(PowerEvent::Suspending);
+            // Sleep is delayed (up to 30s) until acknowledged.
+            unsafe {
+                IOAllowPowerChange(root_port, argument as isize);
+            }
+        }
+        IO_MESSAGE_SYSTEM_HAS_POWERED_ON => {
+            log::info!("Power event (IOKit): {:?}", PowerEvent::Resumed);
+            (context.callback)(PowerEvent::Resumed);
+        }
+        _ => {}
+    }
+}
+
+pub(crate) struct MacosWatcher {
+    /// `CFRunLoopRef` of the listener thread, stored as `usize` to be `Send`.
+    run_loop: Arc<AtomicUsize>,
+
+    _thread: std::thread::JoinHandle<()>,
+}
+
+impl MacosWatcher {
+    pub fn start(callback: PowerCallback) -> Result<Self, String> {
+        let run_loop = Arc::new(AtomicUsize::new(0));
+        let run_loop_clone = run_loop.clone();
+        let (tx, rx) = mpsc::channel::<Result<(), String>>();
+
+        let thread = std::thread::Builder::new()
+            .name("power-events-iokit".into())
+            .spawn(move || {
+                let context = Box::into_raw(Box::new(CallbackContext {
+                    callback,
+                    root_port: AtomicU32::new(0),
+                }));
+                let mut port: IoNotificationPortRef = std::ptr::null_mut();
+                let mut notifier: IoObject = 0;
+
+                // SAFETY: `context` is freed only after deregistering below.
+                let root_port = unsafe {
+                    IORegisterForSystemPower(
+                        context as *mut c_void,
+                        &mut port,
+                        power_callback,
+                        &mut notifier,
+                    )
+                };
+                if root_port == 0 {
+                    unsafe { drop(Box::from_raw(context)) };
+                    let _ = tx.send(Err("IORegisterForSystemPower failed".to_string()));
+                    return;
+                }
+
+                unsafe {
+                    (*context).root_port.store(root_port, Ordering::SeqCst);
+                    let current = CFRunLoopGetCurrent();
+                    run_loop_clone.store(current as usize, Ordering::SeqCst);
+                    CFRunLoopAddSource(
+                        current,
+                        IONotificationPortGetRunLoopSource(port),
+                        kCFRunLoopDefaultMode,
+                    );
+                }
+                let _ = tx.send(Ok(()));
+
+                unsafe {
+                    CFRunLoopRun();
+
+                    IODeregisterForSystemPower(&mut notifier);
+                    IOServiceClose(root_port);
+                    IONotificationPortDestroy(port);
+                    drop(Box::from_raw(context));
+                }
+                log::info!("IOKit power listener exited");
+            })
+            .map_err(|e| format!("Failed to spawn power listener thread: {}", e))?;
+
+        rx.recv()
+            .map_err(|_| "Power listener thread died before registering".to_string())??;
+
+        Ok(Self {
+            run_loop,
+            _thread: thread,
+        })
+    }
+
+    pub fn stop(self) {
+        let run_loop = self.run_loop.load(Ordering::SeqCst);
+        if run_loop != 0 {
+            // SAFETY: The run loop belongs to the listener thread, which is
+            // still inside CFRunLoopRun; CFRunLoopStop is thread-safe.
+            unsafe { CFRunLoopStop(run_loop as CfRunLoopRef) };
+        }
+    }
+}
+
+/// Owned CoreFoundation reference, released on drop.
+struct CfOwned(CfTypeRef);
+
+impl CfOwned {
+    fn new(cf: CfTypeRef) -> Option<Self> {
+        if cf.is_null() {
+            None
+        } else {
+            Some(Self(cf))
+        }
+    }
+
+    fn string(value: &std::ffi::CStr) -> Option<Self> {
+        // SAFETY: `value` is a valid NUL-terminated string.
+        Self::new(unsafe {
+            CFStringCreateWithCString(std::ptr::null(), value.as_ptr(), CF_STRING_ENCODING_UTF8)
+        })
+    }
+}
+
+impl Drop for CfOwned {
+    fn drop(&mut self) {
+        // SAFETY: We hold the only reference taken by a Copy/Create call.
+        unsafe { CFRelease(self.0) };
+    }
+}
+
+pub(crate) fn battery_status() -> Option<BatteryStatus> {
+    // SAFETY: IOKit power source calls on a snapshot we own for the whole
+    // block; borrowed values are not used after it is released.
+    unsafe {
+        let info = CfOwned::new(IOPSCopyPowerSourcesInfo())?;
+        let list = CfOwned::new(IOPSCopyPowerSourcesList(info.0))?;
+
+        let current_key = CfOwned::string(c"Current Capacity")?;
+        let max_key = CfOwned::string(c"Max Capacity")?;
+        let (mut current_total, mut max_total) = (0i64, 0i64);
+        for index in 0..CFArrayGetCount(list.0) {
+            let source = CFArrayGetValueAtIndex(list.0, index);
+            let description = IOPSGetPowerSourceDescription(info.0, source);
+            if description.is_null() {
+                continue;
+            }
+            if let (Some(current), Some(max)) = (
+                dictionary_i32(description, &current_key),
+                dictionary_i32(description, &max_key),
+            ) {
+                current_total += current as i64;
+                max_total += max as i64;
+            }
+        }
+        if max_total <= 0 {
+            return None;
+        }
+
+        let battery_power = CfOwned::string(c"Battery Power")?;
+        let providing = IOPSGetProvidingPowerSourceType(info.0);
+        Some(BatteryStatus {
+            on_battery: !providing.is_null() && CFEqual(providing, battery_power.0) != 0,
+            percent: Some(current_total as f32 * 100.0 / max_total as f32),
+        })
+    }
+}
+
+/// # Safety
+/// `dict` must be a live CFDictionary.
+unsafe fn dictionary_i32(dict: CfDictionaryRef, key: &CfOwned) -> Option<i32> {
+    let number = CFDictionaryGetValue(dict, key.0);
+    if number.is_null() {
+        return None;
+    }
+    let mut value = 0i32;
+    (CFNumberGetValue(
+        number,
+        CF_NUMBER_SINT32_TYPE,
+        &mut value as *mut i32 as *mut c_void,
+    ) != 0)
+        .then_some(value)
+}
@@ -0,0 +1,125 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BatteryStatus, PowerCallback, PowerEvent};
+use std::ffi::c_void;
+
+use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+use windows_sys::Win32::System::Power::{
+    GetSystemPowerStatus, PowerRegisterSuspendResumeNotification,
+    PowerUnregisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS, HPOWERNOTIFY,
+    SYSTEM_POWER_STATUS,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND,
+};
+
+pub(crate) struct WindowsWatcher {
+    registration: *mut c_void,
+    /// Kept alive for the registration; the callback context points into it.
+    context: *mut PowerCallback,
+    /// Must outlive the registration as well.
+    params: *mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS,
+}
+
+// SAFETY: The raw pointers are owned by the watcher and only released in
+// `stop`, after the registration is removed.
+unsafe impl Send for WindowsWatcher {}
+unsafe impl Sync for WindowsWatcher {}
+
+unsafe extern "system" fn power_callback(
+    context: *const c_void,
+    event_type: u32,
+    _setting: *const c_void,
+) -> u32 {
+    // PBT_APMRESUMESUSPEND only follows user-initiated wakes, while
+    // PBT_APMRESUMEAUTOMATIC is sent for every resume, so only that one is
+    // reported to avoid firing twice.
+    let event = match event_type {
+        PBT_APMSUSPEND => PowerEvent::Suspending,
+        PBT_APMRESUMEAUTOMATIC => PowerEvent::Resumed,
+        _ => return ERROR_SUCCESS,
+    };
+    if !context.is_null() {
+        // SAFETY: `context` is the `PowerCallback` box owned by the watcher.
+        let callback = unsafe { &*(context as *const PowerCallback) };
+        log::info!("Power event (Windows): {:?}", event);
+        callback(event);
+    }
+    ERROR_SUCCESS
+}
+
+impl WindowsWatcher {
+    pub fn start(callback: PowerCallback) -> Result<Self, String> {
+        let context = Box::into_raw(Box::new(callback));
+        let params = Box::into_raw(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+            Callback: Some(power_callback),
+            Context: context as *mut c_void,
+        }));
+
+        let mut registration: *mut c_void = std::ptr::null_mut();
+        // SAFETY: `params` stays valid until `stop` unregisters.
+        let status = unsafe {
+            PowerRegisterSuspendResumeNotification(
+                DEVICE_NOTIFY_CALLBACK,
+                params as *mut c_void,
+                &mut registration,
+            )
+        };
+
+        if status != ERROR_SUCCESS {
+            // SAFETY: Registration failed, so nothing else references these.
+            unsafe {
+                drop(Box::from_raw(params));
+                drop(Box::from_raw(context));
+            }
+            return Err(format!(
+                "PowerRegisterSuspendResumeNotification failed: {}",
+                status
+            ));
+        }
+
+        Ok(Self {
+            registration,
+            context,
+            params,
+        })
+    }
+
+    pub fn stop(self) {
+        // SAFETY: Unregister first so the callback can no longer run, then
+        // free what it referenced.
+        unsafe {
+            PowerUnregisterSuspendResumeNotification(self.registration as HPOWERNOTIFY);
+            drop(Box::from_raw(self.params));
+            drop(Box::from_raw(self.context));
+        }
+    }
+}
+
+const BATTERY_FLAG_NO_SYSTEM_BATTERY: u8 = 128;
+const BATTERY_FLAG_UNKNOWN: u8 = 255;
+const BATTERY_PERCENT_UNKNOWN: u8 = 255;
+const AC_LINE_OFFLINE: u8 = 0;
+
+pub(crate) fn battery_status() -> Option<BatteryStatus> {
+    // SAFETY: Plain out-parameter call with a zeroed struct.
+    let status = unsafe {
+        let mut status: SYSTEM_POWER_STATUS = std::mem::zeroed();
+        if GetSystemPowerStatus(&mut status) == 0 {
+            return None;
+        }
+        status
+    };
+    if status.BatteryFlag == BATTERY_FLAG_UNKNOWN
+        || status.BatteryFlag & BATTERY_FLAG_NO_SYSTEM_BATTERY != 0
+    {
+        return None;
+    }
+
+    Some(BatteryStatus {
+        on_battery: status.ACLineStatus == AC_LINE_OFFLINE,
+        percent: (status.BatteryLifePercent != BATTERY_PERCENT_UNKNOWN)
+            .then_some(status.BatteryLifePercent as f32),
+    })
+}
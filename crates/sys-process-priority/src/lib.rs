@@ -0,0 +1,126 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scheduling priority and efficiency mode for helper processes.
+//!
+//! Every sidecar runs with a [`PriorityPolicy`] derived from the user's
+//! [`PowerProfile`] and the sidecar's [`SidecarRole`], so one preference
+//! tunes them all consistently. Each platform applies it natively:
+//!
+//! - **Linux**: `nice` and the idle I/O class, set in the child before exec
+//! - **macOS**: `nice`, the QoS class and `PRIO_DARWIN_BG`, set before exec
+//! - **Windows**: the priority class at creation, then EcoQoS (efficiency
+//!   mode) on the running process
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use sys_process_priority::{PowerProfile, SidecarRole};
+//!
+//! let policy = PowerProfile::Battery.policy(SidecarRole::Background);
+//! let mut cmd = std::process::Command::new("ocr-sidecar");
+//!
+//! #[cfg(unix)]
+//! unsafe {
+//!     use std::os::unix::process::CommandExt;
+//!     cmd.pre_exec(move || sys_process_priority::apply_to_current_process(&policy));
+//! }
+//! #[cfg(windows)]
+//! {
+//!     use std::os::windows::process::CommandExt;
+//!     cmd.creation_flags(sys_process_priority::priority_class(&policy));
+//! }
+//!
+//! let child = cmd.spawn().expect("Failed to spawn");
+//! #[cfg(windows)]
+//! let _ = sys_process_priority::apply_to_process(child.id(), &policy);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(unix)]
+mod unix;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(unix)]
+pub use unix::apply_to_current_process;
+#[cfg(target_os = "windows")]
+pub use windows::{apply_to_process, priority_class};
+
+/// The "performance vs battery" preference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerProfile {
+    Performance,
+    #[default]
+    Balanced,
+    Battery,
+}
+
+/// What a sidecar does, which decides how much it may be throttled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SidecarRole {
+    /// The user is waiting on it right now (screen capture).
+    Interactive,
+    /// Produces live output the user follows (speech recognition).
+    Streaming,
+    /// Batch work that can finish a little later (OCR, formulas).
+    Background,
+}
+
+/// macOS quality-of-service classes, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QosClass {
+    UserInteractive,
+    UserInitiated,
+    Utility,
+    Background,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityPolicy {
+    /// Unix nice value, 0 to 19. On Windows, any positive value selects
+    /// below-normal priority and 15 or more selects idle priority.
+    pub niceness: i32,
+    /// Use the idle I/O class (Linux).
+    pub background_io: bool,
+    pub qos: QosClass,
+    /// EcoQoS on Windows, `PRIO_DARWIN_BG` on macOS.
+    pub efficiency_mode: bool,
+}
+
+impl PowerProfile {
+    pub fn policy(self, role: SidecarRole) -> PriorityPolicy {
+        use PowerProfile::*;
+        use SidecarRole::*;
+
+        let (niceness, background_io, qos, efficiency_mode) = match (self, role) {
+            (_, Interactive) => (0, false, QosClass::UserInteractive, false),
+            (Performance, Streaming) => (0, false, QosClass::UserInitiated, false),
+            (Balanced, Streaming) => (0, false, QosClass::UserInitiated, false),
+            (Battery, Streaming) => (5, false, QosClass::Utility, false),
+            (Performance, Background) => (5, false, QosClass::UserInitiated, false),
+            (Balanced, Background) => (10, true, QosClass::Utility, false),
+            (Battery, Background) => (19, true, QosClass::Background, true),
+        };
+        PriorityPolicy {
+            niceness,
+            background_io,
+            qos,
+            efficiency_mode,
+        }
+    }
+}
+
+impl PriorityPolicy {
+    /// Replace the nice value, keeping the rest of the policy.
+    pub fn with_niceness(self, niceness: i32) -> Self {
+        Self {
+            niceness: niceness.clamp(0, 19),
+            ..self
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::PriorityPolicy;
+use std::io;
+
+/// Apply `policy` to the calling process. Meant for `pre_exec`, so it only
+/// makes async-signal-safe system calls. Failures to lower priority are
+/// ignored; the sidecar still runs, just less politely.
+pub fn apply_to_current_process(policy: &PriorityPolicy) -> io::Result<()> {
+    // SAFETY: Plain system calls on the current process with valid arguments.
+    unsafe {
+        if policy.niceness > 0 {
+            libc::nice(policy.niceness);
+        }
+
+        #[cfg(target_os = "linux")]
+        if policy.background_io {
+            libc::syscall(
+                libc::SYS_ioprio_set,
+                1, /* IOPRIO_WHO_PROCESS */
+                0,
+                (2 << 13) | 7,
+            );
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let qos = match policy.qos {
+                crate::QosClass::UserInteractive => libc::qos_class_t::QOS_CLASS_USER_INTERACTIVE,
+                crate::QosClass::UserInitiated => libc::qos_class_t::QOS_CLASS_USER_INITIATED,
+                crate::QosClass::Utility => libc::qos_class_t::QOS_CLASS_UTILITY,
+                crate::QosClass::Background => libc::qos_class_t::QOS_CLASS_BACKGROUND,
+            };
+            libc::pthread_set_qos_class_self_np(qos, 0);
+            if policy.efficiency_mode {
+                libc::setpriority(libc::PRIO_DARWIN_PROCESS, 0, libc::PRIO_DARWIN_BG as _);
+            }
+        }
+    }
+    Ok(())
+}
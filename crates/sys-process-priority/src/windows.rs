@@ -0,0 +1,59 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::PriorityPolicy;
+use std::io;
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::Threading::{
+    OpenProcess, ProcessPowerThrottling, SetProcessInformation, BELOW_NORMAL_PRIORITY_CLASS,
+    IDLE_PRIORITY_CLASS, PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+    PROCESS_POWER_THROTTLING_EXECUTION_SPEED, PROCESS_POWER_THROTTLING_STATE,
+    PROCESS_SET_INFORMATION,
+};
+
+/// Priority class flag to OR into `creation_flags`. Zero means normal.
+pub fn priority_class(policy: &PriorityPolicy) -> u32 {
+    if policy.efficiency_mode || policy.niceness >= 15 {
+        IDLE_PRIORITY_CLASS
+    } else if policy.niceness > 0 {
+        BELOW_NORMAL_PRIORITY_CLASS
+    } else {
+        0
+    }
+}
+
+/// Turn EcoQoS on or off for the running process `pid`. Windows versions
+/// before 10 1709 do not know the setting and return an error.
+pub fn apply_to_process(pid: u32, policy: &PriorityPolicy) -> io::Result<()> {
+    let state = PROCESS_POWER_THROTTLING_STATE {
+        Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+        ControlMask: PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+        StateMask: if policy.efficiency_mode {
+            PROCESS_POWER_THROTTLING_EXECUTION_SPEED
+        } else {
+            0
+        },
+    };
+
+    // SAFETY: The handle is checked, used once and closed; `state` outlives
+    // the call.
+    unsafe {
+        let process = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+        if process.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let ok = SetProcessInformation(
+            process,
+            ProcessPowerThrottling,
+            &state as *const _ as *const std::ffi::c_void,
+            std::mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+        );
+        let result = if ok == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        };
+        CloseHandle(process);
+        result
+    }
+}
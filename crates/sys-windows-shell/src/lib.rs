@@ -0,0 +1,200 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Windows taskbar jump lists and toast notifications.
+//!
+//! Both are tied to the app's AppUserModelID, which the installer also sets
+//! on the Start menu shortcut; call [`set_app_id`] before any window exists
+//! so the taskbar button belongs to that ID.
+//!
+//! - **Jump lists**: `ICustomDestinationList` with user tasks and one custom
+//!   category, each entry relaunching the app with its arguments
+//! - **Toasts**: `ToastNotificationManager` with protocol activation, so
+//!   clicking a toast or one of its buttons opens a URI (a deep link) and
+//!   needs no COM activator
+//!
+//! The types and the toast XML are available everywhere; the calls that
+//! touch the shell only exist on Windows.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # #[cfg(target_os = "windows")]
+//! # {
+//! use sys_windows_shell::{JumpList, JumpListLink, Toast, ToastAction};
+//!
+//! sys_windows_shell::set_app_id("squigit").expect("Failed to set app ID");
+//! sys_windows_shell::set_jump_list(
+//!     "squigit",
+//!     &JumpList {
+//!         tasks: vec![JumpListLink::new("Capture region", "snapllm://capture")],
+//!         ..Default::default()
+//!     },
+//! )
+//! .expect("Failed to update jump list");
+//! sys_windows_shell::show_toast(
+//!     "squigit",
+//!     &Toast {
+//!         title: "Capture saved".into(),
+//!         body: "Ready to chat".into(),
+//!         launch_uri: None,
+//!         actions: vec![ToastAction::new("Capture again", "snapllm://capture")],
+//!     },
+//! )
+//! .expect("Failed to show toast");
+//! # }
+//! ```
+
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(target_os = "windows")]
+pub use windows::{set_app_id, set_jump_list, show_toast};
+
+/// Windows shows at most five buttons on a toast.
+pub const MAX_TOAST_ACTIONS: usize = 5;
+
+/// A jump-list entry that relaunches the app with `arguments`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpListLink {
+    pub title: String,
+    pub arguments: String,
+    /// Tooltip; the title is used when empty.
+    pub description: String,
+}
+
+impl JumpListLink {
+    pub fn new(title: impl Into<String>, arguments: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            arguments: arguments.into(),
+            description: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JumpList {
+    /// Shown under "Tasks".
+    pub tasks: Vec<JumpListLink>,
+    /// Heading of the custom category, e.g. "Recent chats".
+    pub category: String,
+    /// Entries of the custom category. Ones the user removed from the jump
+    /// list are skipped; Windows rejects the whole category otherwise.
+    pub category_links: Vec<JumpListLink>,
+}
+
+/// A toast button that opens `uri`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToastAction {
+    pub label: String,
+    pub uri: String,
+}
+
+impl ToastAction {
+    pub fn new(label: impl Into<String>, uri: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            uri: uri.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub title: String,
+    pub body: String,
+    /// Opened when the toast itself is clicked.
+    pub launch_uri: Option<String>,
+    /// Buttons, at most [`MAX_TOAST_ACTIONS`]; extra ones are dropped.
+    pub actions: Vec<ToastAction>,
+}
+
+impl Toast {
+    /// The toast as `ToastGeneric` XML.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<toast");
+        if let Some(uri) = &self.launch_uri {
+            xml.push_str(&format!(
+                " activationType=\"protocol\" launch=\"{}\"",
+                escape_xml(uri)
+            ));
+        }
+        xml.push_str("><visual><binding template=\"ToastGeneric\">");
+        xml.push_str(&format!("<text>{}</text>", escape_xml(&self.title)));
+        if !self.body.is_empty() {
+            xml.push_str(&format!("<text>{}</text>", escape_xml(&self.body)));
+        }
+        xml.push_str("</binding></visual>");
+        if !self.actions.is_empty() {
+            xml.push_str("<actions>");
+            for action in self.actions.iter().take(MAX_TOAST_ACTIONS) {
+                xml.push_str(&format!(
+                    "<action content=\"{}\" activationType=\"protocol\" arguments=\"{}\"/>",
+                    escape_xml(&action.label),
+                    escape_xml(&action.uri)
+                ));
+            }
+            xml.push_str("</actions>");
+        }
+        xml.push_str("</toast>");
+        xml
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Not allowed in XML 1.0 at all.
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toast_xml_escapes_text_and_caps_actions() {
+        let toast = Toast {
+            title: "Saved <\"chat\"> & more".into(),
+            body: String::new(),
+            launch_uri: Some("snapllm://open-chat/a-1?x=1&y=2".into()),
+            actions: (0..7)
+                .map(|i| ToastAction::new(format!("Action {}", i), "snapllm://capture"))
+                .collect(),
+        };
+        let xml = toast.to_xml();
+
+        assert!(xml.starts_with(
+            "<toast activationType=\"protocol\" launch=\"snapllm://open-chat/a-1?x=1&amp;y=2\">"
+        ));
+        assert!(xml.contains("<text>Saved &lt;&quot;chat&quot;&gt; &amp; more</text></binding>"));
+        assert_eq!(xml.matches("<action ").count(), MAX_TOAST_ACTIONS);
+        assert!(xml.ends_with("</actions></toast>"));
+    }
+
+    #[test]
+    fn toast_xml_without_launch_or_actions() {
+        let toast = Toast {
+            title: "Done".into(),
+            body: "line\u{0}".into(),
+            launch_uri: None,
+            actions: Vec::new(),
+        };
+        assert_eq!(
+            toast.to_xml(),
+            "<toast><visual><binding template=\"ToastGeneric\"><text>Done</text>\
+             <text>line</text></binding></visual></toast>"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{JumpList, JumpListLink, Toast};
+use std::collections::HashSet;
+use windows::core::{Interface, HSTRING, PROPVARIANT};
+use windows::Data::Xml::Dom::XmlDocument;
+use windows::Win32::Foundation::E_FAIL;
+use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER,
+    COINIT_APARTMENTTHREADED,
+};
+use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+use windows::Win32::UI::Shell::{
+    DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW,
+    SetCurrentProcessExplicitAppUserModelID, ShellLink,
+};
+use windows::UI::Notifications::{ToastNotification, ToastNotificationManager};
+
+/// Longest argument string read back from a removed jump-list entry.
+const MAX_ARGUMENTS_LEN: usize = 2048;
+
+/// Set the AppUserModelID of the current process.
+pub fn set_app_id(app_id: &str) -> Result<(), String> {
+    unsafe { SetCurrentProcessExplicitAppUserModelID(&HSTRING::from(app_id)) }
+        .map_err(|e| format!("SetCurrentProcessExplicitAppUserModelID failed: {}", e))
+}
+
+/// Replace the app's jump list. Every entry launches the current executable.
+pub fn set_jump_list(app_id: &str, list: &JumpList) -> Result<(), String> {
+    let exe =
+        std::env::current_exe().map_err(|e| format!("Failed to resolve the executable: {}", e))?;
+    let exe = HSTRING::from(exe.as_os_str());
+    let app_id = HSTRING::from(app_id);
+    let list = list.clone();
+    on_com_thread(move || unsafe { replace_jump_list(&app_id, &exe, &list) })
+        .map_err(|e| format!("Failed to update jump list: {}", e))
+}
+
+/// Show a toast for the app.
+pub fn show_toast(app_id: &str, toast: &Toast) -> Result<(), String> {
+    let xml = HSTRING::from(toast.to_xml());
+    let app_id = HSTRING::from(app_id);
+    on_com_thread(move || {
+        let document = XmlDocument::new()?;
+        document.LoadXml(&xml)?;
+        let notification = ToastNotification::CreateToastNotification(&document)?;
+        ToastNotificationManager::CreateToastNotifierWithId(&app_id)?.Show(&notification)
+    })
+    .map_err(|e| format!("Failed to show toast: {}", e))
+}
+
+/// Run `work` on its own thread with COM initialized, so the caller's
+/// apartment does not matter.
+fn on_com_thread<F>(work: F) -> windows::core::Result<()>
+where
+    F: FnOnce() -> windows::core::Result<()> + Send + 'static,
+{
+    std::thread::Builder::new()
+        .name("windows-shell".into())
+        .spawn(move || unsafe {
+            CoInitializeEx(None, COINIT_APARTMENTTHREADED).ok()?;
+            let result = work();
+            CoUninitialize();
+            result
+        })
+        .map_err(|e| windows::core::Error::new(E_FAIL, e.to_string()))?
+        .join()
+        .unwrap_or_else(|_| Err(windows::core::Error::new(E_FAIL, "Shell thread panicked")))
+}
+
+unsafe fn replace_jump_list(
+    app_id: &HSTRING,
+    exe: &HSTRING,
+    list: &JumpList,
+) -> windows::core::Result<()> {
+    let destinations: ICustomDestinationList =
+        CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+    destinations.SetAppID(app_id)?;
+
+    let mut min_slots = 0u32;
+    let removed: IObjectArray = destinations.BeginList(&mut min_slots)?;
+    let removed = removed_arguments(&removed);
+
+    let result = (|| {
+        let links: Vec<&JumpListLink> = list
+            .category_links
+            .iter()
+            .filter(|link| !removed.contains(&link.arguments))
+            .collect();
+        if !list.category.is_empty() && !links.is_empty() {
+            let links = link_array(exe, links)?;
+            destinations.AppendCategory(&HSTRING::from(list.category.as_str()), &links)?;
+        }
+        if !list.tasks.is_empty() {
+            destinations.AddUserTasks(&link_array(exe, list.tasks.iter().collect())?)?;
+        }
+        destinations.CommitList()
+    })();
+
+    if result.is_err() {
+        let _ = destinations.AbortList();
+    }
+    result
+}
+
+/// Arguments of the entries the user removed from the jump list.
+unsafe fn removed_arguments(removed: &IObjectArray) -> HashSet<String> {
+    let mut arguments = HashSet::new();
+    for index in 0..removed.GetCount().unwrap_or(0) {
+        let Ok(link) = removed.GetAt::<IShellLinkW>(index) else {
+            continue;
+        };
+        let mut buffer = vec![0u16; MAX_ARGUMENTS_LEN];
+        if link.GetArguments(&mut buffer).is_ok() {
+            let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+            arguments.insert(String::from_utf16_lossy(&buffer[..len]));
+        }
+    }
+    arguments
+}
+
+unsafe fn link_array(
+    exe: &HSTRING,
+    links: Vec<&JumpListLink>,
+) -> windows::core::Result<IObjectArray> {
+    let collection: IObjectCollection =
+        CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+    for link in links {
+        collection.AddObject(&shell_link(exe, link)?)?;
+    }
+    collection.cast()
+}
+
+unsafe fn shell_link(exe: &HSTRING, link: &JumpListLink) -> windows::core::Result<IShellLinkW> {
+    let shell_link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+    shell_link.SetPath(exe)?;
+    shell_link.SetArguments(&HSTRING::from(link.arguments.as_str()))?;
+    shell_link.SetIconLocation(exe, 0)?;
+    let description = if link.description.is_empty() {
+        &link.title
+    } else {
+        &link.description
+    };
+    shell_link.SetDescription(&HSTRING::from(description.as_str()))?;
+
+    // Jump lists show the title property, not the description.
+    let properties: IPropertyStore = shell_link.cast()?;
+    properties.SetValue(&PKEY_Title, &PROPVARIANT::from(link.title.as_str()))?;
+    properties.Commit()?;
+    Ok(shell_link)
+}
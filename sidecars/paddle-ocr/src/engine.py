@@ -13,14 +13,17 @@ and bounding boxes from images using PaddleOCR.
 
 import logging
 import os
+import queue
 import tempfile
-from typing import Any, Iterable, List, Optional, Tuple
+import threading
+from typing import Any, Callable, Iterable, Iterator, List, Optional, Tuple
 
 # Must be set before importing paddleocr/paddlex to avoid online source probing in offline mode.
 os.environ.setdefault("DISABLE_MODEL_SOURCE_CHECK", "True")
 os.environ.setdefault("PADDLE_PDX_DISABLE_MODEL_SOURCE_CHECK", "True")
 
 import cv2
+import numpy as np
 from paddleocr import PaddleOCR
 
 from .config import EngineConfig
@@ -28,6 +31,12 @@ from .models import BoundingBox, OCRResult
 
 MAX_DET_SIDE = 2048
 
+# Crops at least this much taller than wide are vertical text lines.
+VERTICAL_CROP_RATIO = 1.5
+
+# Text line crops prepared ahead of recognition while streaming.
+CROP_QUEUE_DEPTH = 8
+
 MODEL_NAME_ALIASES = {
     # App-level IDs -> official PaddleOCR model names
     "pp-ocr-v5-en": "en_PP-OCRv5_mobile_rec",
@@ -51,6 +60,8 @@ class OCREngine:
         self.config = config or EngineConfig()
         self._setup_environment()
         self._ocr: Optional[PaddleOCR] = None
+        self._det: Any = None
+        self._rec: Any = None
 
     def _setup_environment(self) -> None:
         os.environ["PADDLEOCR_BASE_PATH"] = str(self.config.model_dir)
@@ -130,6 +141,167 @@ class OCREngine:
 
         return self._parse_results(result, scale)
 
+    def process_stream(
+        self,
+        image_path: str,
+        on_detected: Optional[Callable[[int], None]] = None,
+    ) -> Iterator[OCRResult]:
+        """
+        Yield results one region at a time, in reading order.
+
+        Detection runs once; each detected line is then recognized on its
+        own, so callers see text long before the whole image is done. A
+        worker thread cuts out the next lines while the current one is
+        recognized. `on_detected` gets the number of detected lines before
+        the first result; lines with no text are skipped, so fewer results
+        may follow. Falls back to `process` when the standalone modules are
+        missing (PaddleOCR 2.x).
+        """
+        if not os.path.exists(image_path):
+            raise FileNotFoundError(f"Image not found: {image_path}")
+
+        try:
+            det, rec = self._get_stream_modules()
+        except ImportError:
+            results = self.process(image_path)
+            if on_detected is not None:
+                on_detected(len(results))
+            yield from results
+            return
+
+        det_path, scale, tmp_path = self._preprocess_image(image_path)
+        try:
+            img = cv2.imread(det_path)
+            if img is None:
+                raise RuntimeError(f"Failed to read image: {image_path}")
+            try:
+                det_result = next(iter(det.predict(img)), None)
+            except Exception as exc:
+                raise RuntimeError(f"OCR detection failed: {exc}") from exc
+        finally:
+            if tmp_path and os.path.exists(tmp_path):
+                os.unlink(tmp_path)
+
+        polys = det_result.get("dt_polys", []) if det_result is not None else []
+        quads = [q for q in map(self._normalize_quad, polys) if q is not None]
+        quads.sort(key=lambda q: (round(q[0][1] / 10), q[0][0]))
+        inv_scale = 1.0 / scale if scale != 1.0 else 1.0
+        if on_detected is not None:
+            on_detected(len(quads))
+
+        for quad, crop in self._pipelined_crops(img, quads):
+            try:
+                rec_result = next(iter(rec.predict(crop)), None)
+            except Exception as exc:
+                raise RuntimeError(f"OCR recognition failed: {exc}") from exc
+            if rec_result is None:
+                continue
+
+            text, confidence = self._parse_text_and_conf(
+                rec_result.get("rec_text", ""), rec_result.get("rec_score")
+            )
+            if text == "":
+                continue
+            if inv_scale != 1.0:
+                quad = [[c[0] * inv_scale, c[1] * inv_scale] for c in quad]
+            yield OCRResult(
+                text=text,
+                box=BoundingBox(
+                    top_left=quad[0],
+                    top_right=quad[1],
+                    bottom_right=quad[2],
+                    bottom_left=quad[3],
+                ),
+                confidence=confidence,
+            )
+
+    def _pipelined_crops(
+        self, img: Any, quads: List[List[List[float]]]
+    ) -> Iterator[Tuple[List[List[float]], Any]]:
+        """
+        Yield `(quad, crop)` pairs in order, cropping on a worker thread.
+
+        OpenCV releases the GIL while warping, so the next crops are ready
+        by the time recognition of the current one returns.
+        """
+        crops: "queue.Queue[Any]" = queue.Queue(maxsize=CROP_QUEUE_DEPTH)
+        done = object()
+        stop = threading.Event()
+
+        def produce() -> None:
+            try:
+                for quad in quads:
+                    if stop.is_set():
+                        return
+                    crops.put((quad, self._crop_quad(img, quad)))
+            except Exception as exc:
+                crops.put(exc)
+            crops.put(done)
+
+        worker = threading.Thread(target=produce, daemon=True)
+        worker.start()
+        try:
+            while True:
+                item = crops.get()
+                if item is done:
+                    return
+                if isinstance(item, Exception):
+                    raise RuntimeError(f"OCR cropping failed: {item}") from item
+                yield item
+        finally:
+            stop.set()
+            # Unblock a producer waiting on a full queue.
+            while worker.is_alive():
+                try:
+                    crops.get(timeout=0.05)
+                except queue.Empty:
+                    pass
+
+    def _get_stream_modules(self) -> Tuple[Any, Any]:
+        if self._det is None or self._rec is None:
+            from paddleocr import TextDetection, TextRecognition
+
+            try:
+                self._det = TextDetection(
+                    model_name=self._model_name_from_dir(self.config.det_model_dir),
+                    model_dir=str(self.config.det_model_dir),
+                    enable_mkldnn=False,
+                )
+                self._rec = TextRecognition(
+                    model_name=self._model_name_from_dir(self.config.rec_model_dir),
+                    model_dir=str(self.config.rec_model_dir),
+                    enable_mkldnn=False,
+                )
+            except Exception as exc:
+                raise RuntimeError(f"Failed to initialize PaddleOCR: {exc}") from exc
+        return self._det, self._rec
+
+    @staticmethod
+    def _crop_quad(img: Any, quad: List[List[float]]) -> Any:
+        """Perspective-correct crop of one text line."""
+        pts = np.array(quad, dtype=np.float32)
+        width = max(
+            int(max(np.linalg.norm(pts[0] - pts[1]), np.linalg.norm(pts[2] - pts[3]))),
+            1,
+        )
+        height = max(
+            int(max(np.linalg.norm(pts[0] - pts[3]), np.linalg.norm(pts[1] - pts[2]))),
+            1,
+        )
+        target = np.array(
+            [[0, 0], [width, 0], [width, height], [0, height]], dtype=np.float32
+        )
+        crop = cv2.warpPerspective(
+            img,
+            cv2.getPerspectiveTransform(pts, target),
+            (width, height),
+            borderMode=cv2.BORDER_REPLICATE,
+            flags=cv2.INTER_CUBIC,
+        )
+        if height / width >= VERTICAL_CROP_RATIO:
+            crop = np.rot90(crop)
+        return crop
+
     @staticmethod
     def _as_sequence(value: Any) -> Optional[List[Any]]:
         if isinstance(value, (list, tuple)):
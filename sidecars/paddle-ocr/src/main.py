@@ -176,6 +176,12 @@ def _emit_json(payload: Any) -> None:
     stream.flush()
 
 
+def _emit_line(payload: Any) -> None:
+    stream = sys.__stdout__
+    stream.write(json.dumps(payload, cls=NumpyEncoder) + "\n")
+    stream.flush()
+
+
 def _emit_error(message: str) -> int:
     _emit_json({"error": message})
     return 1
@@ -208,6 +214,32 @@ def process_path(image_path: str, args: argparse.Namespace) -> int:
         return _emit_error(_format_exception(exc))
 
 
+def stream_path(image_path: str, args: argparse.Namespace) -> int:
+    """
+    Print a `{"detected": n}` line once text lines are found, then one
+    `{"region": ...}` line per region as it is recognized, then the full
+    result list as the last line, exactly as `process_path` does.
+    """
+    if not Path(image_path).exists():
+        return _emit_error(f"Image not found: {image_path}")
+
+    try:
+        config = _create_config(args)
+        output = []
+        with contextlib.redirect_stdout(sys.stderr):
+            engine = OCREngine(config)
+            for result in engine.process_stream(
+                image_path, lambda count: _emit_line({"detected": count})
+            ):
+                region = result.to_dict()
+                output.append(region)
+                _emit_line({"region": region})
+        _emit_json(output)
+        return 0
+    except Exception as exc:
+        return _emit_error(_format_exception(exc))
+
+
 def _build_parser() -> argparse.ArgumentParser:
     parser = argparse.ArgumentParser(
         description="Squigit PaddleOCR sidecar (CLI mode)."
@@ -239,11 +271,18 @@ def _build_parser() -> argparse.ArgumentParser:
         action="store_false",
         help="Disable textline orientation model.",
     )
+    parser.add_argument(
+        "--stream",
+        action="store_true",
+        help="Print each region as a JSON line as soon as it is recognized.",
+    )
     return parser
 
 
 def main() -> int:
     args = _build_parser().parse_args()
+    if args.stream:
+        return stream_path(args.image_path, args)
     return process_path(args.image_path, args)
 
 
@@ -97,7 +97,15 @@ void CaptureController::finishRectCapture(QPointF start, QPointF end) {
   cropAndSave(selectionRect);
 }
 
-void CaptureController::cropAndSave(const QRectF &logicalRect) {
+bool CaptureController::captureRegion(const QRectF &logicalRect) {
+  if (logicalRect.width() < 1 || logicalRect.height() < 1) {
+    emitFailure();
+    return false;
+  }
+  return cropAndSave(logicalRect);
+}
+
+bool CaptureController::cropAndSave(const QRectF &logicalRect) {
   int physX = qRound(logicalRect.x() * m_devicePixelRatio);
   int physY = qRound(logicalRect.y() * m_devicePixelRatio);
   int physW = qRound(logicalRect.width() * m_devicePixelRatio);
@@ -113,7 +121,7 @@ void CaptureController::cropAndSave(const QRectF &logicalRect) {
 
   if (physW <= 0 || physH <= 0) {
     emitFailure();
-    return;
+    return false;
   }
 
   QImage cropped = m_backgroundImage.copy(physX, physY, physW, physH);
@@ -124,18 +132,28 @@ void CaptureController::cropAndSave(const QRectF &logicalRect) {
   QString finalPath =
       QDir::temp().filePath(QString("squigit_capture_%1.png").arg(timestamp));
 
-  if (cropped.save(finalPath, "PNG", -1)) {
-    emitSuccess(finalPath);
-  } else {
+  if (!cropped.save(finalPath, "PNG", -1)) {
     emitFailure();
+    return false;
   }
+
+  emitSuccess(finalPath, logicalRect.toAlignedRect());
+  return true;
 }
 
-void CaptureController::emitSuccess(const QString &path) {
+void CaptureController::emitSuccess(const QString &path,
+                                    const QRect &selection) {
   std::cout << "CAPTURE_SUCCESS" << std::endl;
   std::cout << "DISPLAY_GEO:" << m_displayGeometry.x() << ","
             << m_displayGeometry.y() << "," << m_displayGeometry.width() << ","
             << m_displayGeometry.height() << std::endl;
+  // The saved image is in physical pixels, the geometries above are not.
+  std::cout << "DEVICE_PIXEL_RATIO:" << m_devicePixelRatio << std::endl;
+  // Relative to the display, in logical pixels, so it can be replayed with
+  // --region.
+  std::cout << "SELECTION_RECT:" << selection.x() << "," << selection.y()
+            << "," << selection.width() << "," << selection.height()
+            << std::endl;
   std::cout << path.toStdString() << std::endl;
   std::cout.flush();
 
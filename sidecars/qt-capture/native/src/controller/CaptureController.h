@@ -56,6 +56,12 @@ public:
   Q_INVOKABLE void finishSquiggleCapture(const QVariantList &points);
   Q_INVOKABLE void finishRectCapture(QPointF start, QPointF end);
 
+  /**
+   * @brief Crop a display-relative logical rect without any UI.
+   * @return true when the crop was saved.
+   */
+  bool captureRegion(const QRectF &logicalRect);
+
 signals:
   void backgroundSourceChanged();
   void captureModeChanged();
@@ -64,8 +70,8 @@ signals:
   void captureFailed();
 
 private:
-  void cropAndSave(const QRectF &logicalRect);
-  void emitSuccess(const QString &path);
+  bool cropAndSave(const QRectF &logicalRect);
+  void emitSuccess(const QString &path, const QRect &selection);
   void emitFailure();
 
   QImage m_backgroundImage;
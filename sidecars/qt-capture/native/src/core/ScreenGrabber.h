@@ -12,6 +12,7 @@
 #include <QImage>
 #include <QObject>
 #include <QRect>
+#include <QRectF>
 #include <QString>
 #include <algorithm>
 #include <vector>
@@ -31,12 +32,51 @@ public:
   explicit ScreenGrabber(QObject *parent = nullptr) : QObject(parent) {}
   virtual ~ScreenGrabber() = default;
   virtual std::vector<CapturedFrame> captureAll() = 0;
+
+  /**
+   * @brief Locates the focused window among captured frames.
+   *
+   * Sets `frame` to the index in `frames` of the display under the window's
+   * center and `region` to the window's part on it, display-relative in
+   * logical pixels as CaptureController::captureRegion takes them. False
+   * when there is no focused window or the platform cannot tell.
+   */
+  virtual bool locateActiveWindow(const std::vector<CapturedFrame> &frames,
+                                  size_t *frame, QRectF *region) {
+    Q_UNUSED(frames);
+    Q_UNUSED(frame);
+    Q_UNUSED(region);
+    return false;
+  }
+
   static void sortLeftToRight(std::vector<CapturedFrame> &frames) {
     std::sort(frames.begin(), frames.end(),
               [](const CapturedFrame &a, const CapturedFrame &b) {
                 return a.geometry.x() < b.geometry.x();
               });
   }
+
+protected:
+  /// locateActiveWindow() for a window rect in device pixels, where each
+  /// frame's image starts at the top-left of its geometry.
+  static bool locatePixelRect(const std::vector<CapturedFrame> &frames,
+                              const QRect &rect, size_t *frame,
+                              QRectF *region) {
+    for (size_t i = 0; i < frames.size(); ++i) {
+      const QRect pixels(frames[i].geometry.topLeft(), frames[i].image.size());
+      if (!pixels.contains(rect.center())) {
+        continue;
+      }
+      const QRect visible =
+          rect.intersected(pixels).translated(-pixels.topLeft());
+      const qreal ratio = frames[i].devicePixelRatio;
+      *frame = i;
+      *region = QRectF(visible.x() / ratio, visible.y() / ratio,
+                       visible.width() / ratio, visible.height() / ratio);
+      return true;
+    }
+    return false;
+  }
 };
 
 #endif // SCREENGRABBER_H
@@ -21,10 +21,91 @@
 #include <QUrl>
 #include <QUuid>
 #include <QWindow>
+#include <cstdlib>
+#include <cstring>
+#include <xcb/xcb.h>
 #endif
 #include <cmath>
 
 #if defined(Q_OS_LINUX)
+static xcb_atom_t internAtom(xcb_connection_t *connection, const char *name) {
+  xcb_intern_atom_reply_t *reply = xcb_intern_atom_reply(
+      connection, xcb_intern_atom(connection, 1, std::strlen(name), name),
+      nullptr);
+  if (!reply) {
+    return XCB_ATOM_NONE;
+  }
+  const xcb_atom_t atom = reply->atom;
+  std::free(reply);
+  return atom;
+}
+
+/// Bounds of the window in _NET_ACTIVE_WINDOW, with the decorations from
+/// _NET_FRAME_EXTENTS, in root window pixels.
+static bool activeX11Window(QRect *rect) {
+  xcb_connection_t *connection = xcb_connect(nullptr, nullptr);
+  if (xcb_connection_has_error(connection)) {
+    xcb_disconnect(connection);
+    return false;
+  }
+  const xcb_window_t root =
+      xcb_setup_roots_iterator(xcb_get_setup(connection)).data->root;
+
+  bool found = false;
+  xcb_get_property_reply_t *active = xcb_get_property_reply(
+      connection,
+      xcb_get_property(connection, 0, root,
+                       internAtom(connection, "_NET_ACTIVE_WINDOW"),
+                       XCB_ATOM_WINDOW, 0, 1),
+      nullptr);
+  xcb_window_t window = XCB_WINDOW_NONE;
+  if (active && xcb_get_property_value_length(active) >=
+                    static_cast<int>(sizeof(xcb_window_t))) {
+    std::memcpy(&window, xcb_get_property_value(active), sizeof(window));
+  }
+  std::free(active);
+
+  if (window != XCB_WINDOW_NONE) {
+    xcb_get_geometry_reply_t *geometry = xcb_get_geometry_reply(
+        connection, xcb_get_geometry(connection, window), nullptr);
+    xcb_translate_coordinates_reply_t *origin =
+        xcb_translate_coordinates_reply(
+            connection,
+            xcb_translate_coordinates(connection, window, root, 0, 0),
+            nullptr);
+    if (geometry && origin) {
+      // Left, right, top and bottom decoration sizes.
+      uint32_t extents[4] = {0, 0, 0, 0};
+      xcb_get_property_reply_t *frameExtents = xcb_get_property_reply(
+          connection,
+          xcb_get_property(connection, 0, window,
+                           internAtom(connection, "_NET_FRAME_EXTENTS"),
+                           XCB_ATOM_CARDINAL, 0, 4),
+          nullptr);
+      if (frameExtents && xcb_get_property_value_length(frameExtents) ==
+                              static_cast<int>(sizeof(extents))) {
+        std::memcpy(extents, xcb_get_property_value(frameExtents),
+                    sizeof(extents));
+      }
+      std::free(frameExtents);
+
+      const int left = static_cast<int>(extents[0]);
+      const int right = static_cast<int>(extents[1]);
+      const int top = static_cast<int>(extents[2]);
+      const int bottom = static_cast<int>(extents[3]);
+      *rect = QRect(origin->dst_x - left, origin->dst_y - top,
+                    geometry->width + left + right,
+                    geometry->height + top + bottom);
+      found = rect->width() > 0 && rect->height() > 0;
+    }
+    std::free(geometry);
+    std::free(origin);
+  }
+
+  xcb_disconnect(connection);
+  return found;
+}
+
 class PortalHelper : public QObject {
   Q_OBJECT
 public:
@@ -97,6 +178,20 @@ public:
 #endif
   }
 
+#if defined(Q_OS_LINUX)
+  bool locateActiveWindow(const std::vector<CapturedFrame> &frames,
+                          size_t *frame, QRectF *region) override {
+    QString sessionType = qgetenv("XDG_SESSION_TYPE").toLower();
+    if (sessionType == "wayland") {
+      // XWayland only knows X11 clients, not the focused native window.
+      return false;
+    }
+    QRect rect;
+    return activeX11Window(&rect) &&
+           locatePixelRect(frames, rect, frame, region);
+  }
+#endif
+
 private:
   std::vector<CapturedFrame> captureStandard() {
     std::vector<CapturedFrame> frames;
@@ -100,6 +100,63 @@ public:
     ScreenGrabber::sortLeftToRight(frames);
     return frames;
   }
+
+  bool locateActiveWindow(const std::vector<CapturedFrame> &frames,
+                          size_t *frame, QRectF *region) override {
+    const pid_t frontmost =
+        NSWorkspace.sharedWorkspace.frontmostApplication.processIdentifier;
+    CFArrayRef windows = CGWindowListCopyWindowInfo(
+        kCGWindowListOptionOnScreenOnly |
+            kCGWindowListExcludeDesktopElements,
+        kCGNullWindowID);
+    if (!windows) {
+      return false;
+    }
+
+    // Listed front to back, so the frontmost app's first window in the
+    // normal layer is its focused one. Bounds are in points with a top-left
+    // origin, the same as the frame geometry.
+    QRect bounds;
+    for (CFIndex i = 0; i < CFArrayGetCount(windows) && bounds.isEmpty();
+         ++i) {
+      auto info = static_cast<CFDictionaryRef>(
+          CFArrayGetValueAtIndex(windows, i));
+      auto owner = static_cast<CFNumberRef>(
+          CFDictionaryGetValue(info, kCGWindowOwnerPID));
+      auto layer = static_cast<CFNumberRef>(
+          CFDictionaryGetValue(info, kCGWindowLayer));
+      auto dictionary = static_cast<CFDictionaryRef>(
+          CFDictionaryGetValue(info, kCGWindowBounds));
+      int ownerPid = 0;
+      int layerNumber = -1;
+      CGRect rect;
+      if (!owner || !layer || !dictionary ||
+          !CFNumberGetValue(owner, kCFNumberIntType, &ownerPid) ||
+          !CFNumberGetValue(layer, kCFNumberIntType, &layerNumber) ||
+          ownerPid != frontmost || layerNumber != 0 ||
+          !CGRectMakeWithDictionaryRepresentation(dictionary, &rect)) {
+        continue;
+      }
+      bounds = QRectF(rect.origin.x, rect.origin.y, rect.size.width,
+                      rect.size.height)
+                   .toAlignedRect();
+    }
+    CFRelease(windows);
+    if (bounds.isEmpty()) {
+      return false;
+    }
+
+    for (size_t i = 0; i < frames.size(); ++i) {
+      const QRect geometry = frames[i].geometry;
+      if (geometry.contains(bounds.center())) {
+        *frame = i;
+        *region = QRectF(
+            bounds.intersected(geometry).translated(-geometry.topLeft()));
+        return true;
+      }
+    }
+    return false;
+  }
 };
 
 extern "C" ScreenGrabber *createUnixEngine(QObject *parent) {
@@ -10,11 +10,13 @@
 #include <iostream>
 
 #include <windows.h>
+#include <dwmapi.h>
 #include <gdiplus.h>
 
 #pragma comment(lib, "Gdiplus.lib")
 #pragma comment(lib, "User32.lib")
 #pragma comment(lib, "Gdi32.lib")
+#pragma comment(lib, "Dwmapi.lib")
 
 #include <ShellScalingApi.h>
 #pragma comment(lib, "Shcore.lib")
@@ -73,6 +75,26 @@ public:
     return data.frames;
   }
 
+  bool locateActiveWindow(const std::vector<CapturedFrame> &frames,
+                          size_t *frame, QRectF *region) override {
+    HWND window = GetForegroundWindow();
+    if (!window || IsIconic(window)) {
+      return false;
+    }
+    // The extended frame bounds leave out the invisible resize borders.
+    RECT bounds;
+    if (FAILED(DwmGetWindowAttribute(window, DWMWA_EXTENDED_FRAME_BOUNDS,
+                                     &bounds, sizeof(bounds))) &&
+        !GetWindowRect(window, &bounds)) {
+      return false;
+    }
+    // Per-monitor DPI awareness makes these physical pixels, like the
+    // monitor geometry of the frames.
+    const QRect rect(bounds.left, bounds.top, bounds.right - bounds.left,
+                     bounds.bottom - bounds.top);
+    return locatePixelRect(frames, rect, frame, region);
+  }
+
 private:
   ULONG_PTR m_gdiplusToken;
   bool m_gdiplusReady;
@@ -5,6 +5,9 @@
  */
 
 #include <QCommandLineParser>
+#include <QCursor>
+#include <QDateTime>
+#include <QDir>
 #include <QGuiApplication>
 #include <QQmlApplicationEngine>
 #include <QQmlComponent>
@@ -85,6 +88,35 @@ static void logTimingStage(const char *stage,
   std::cerr << "TIMING " << stage << " " << elapsedMs << "ms" << std::endl;
 }
 
+/// Parses "x,y,w,h" as printed in DISPLAY_GEO and SELECTION_RECT.
+static bool parseRect(const QString &value, QRect *rect) {
+  const QStringList parts = value.split(',');
+  if (parts.size() != 4) {
+    return false;
+  }
+  int numbers[4];
+  for (int i = 0; i < 4; ++i) {
+    bool ok = false;
+    numbers[i] = parts[i].trimmed().toInt(&ok);
+    if (!ok) {
+      return false;
+    }
+  }
+  *rect = QRect(numbers[0], numbers[1], numbers[2], numbers[3]);
+  return rect->width() > 0 && rect->height() > 0;
+}
+
+/// Output names differ between backends: DRM calls a connector
+/// "HDMI-A-1" where X11 may say "HDMI-1". Drops the one-letter connector
+/// subtype so both compare equal.
+static QString normalizedOutputName(const QString &name) {
+  QStringList parts = name.split('-');
+  if (parts.size() == 3 && parts[1].size() == 1 && parts[1][0].isLetter()) {
+    parts.removeAt(1);
+  }
+  return parts.join('-').toLower();
+}
+
 static void applyPlatformWindowHacks(QQuickWindow *window) {
 #ifdef Q_OS_WIN
   HWND hwnd = reinterpret_cast<HWND>(window->winId());
@@ -182,6 +214,45 @@ int main(int argc, char *argv[]) {
                                      "Use rectangle selection mode");
   parser.addOption(rectangleOption);
 
+  QCommandLineOption activeMonitorOption(
+      QStringList() << "a"
+                    << "active-monitor",
+      "Grab the monitor under the cursor without showing a selection UI");
+  parser.addOption(activeMonitorOption);
+
+  QCommandLineOption monitorOption(
+      "monitor",
+      "Grab the whole output with this name without showing a selection UI",
+      "name");
+  parser.addOption(monitorOption);
+
+  QCommandLineOption windowOption(
+      "window", "Grab the focused window without showing a selection UI");
+  parser.addOption(windowOption);
+
+  QCommandLineOption displayNameOption(
+      "display-name",
+      "Show the selection overlay only on the output with this name",
+      "name");
+  parser.addOption(displayNameOption);
+
+  QCommandLineOption cursorDisplayOption(
+      "cursor-display",
+      "Show the selection overlay only on the display under the cursor");
+  parser.addOption(cursorDisplayOption);
+
+  QCommandLineOption regionOption(
+      "region",
+      "Crop this display-relative rect (x,y,w,h in logical pixels) without "
+      "showing a selection UI; requires --display",
+      "rect");
+  parser.addOption(regionOption);
+
+  QCommandLineOption displayOption(
+      "display", "Display geometry (x,y,w,h) that --region refers to",
+      "rect");
+  parser.addOption(displayOption);
+
   parser.process(app);
 
   QString captureMode = "freeshape";
@@ -211,6 +282,130 @@ int main(int argc, char *argv[]) {
     return 1;
   }
 
+  if (parser.isSet(windowOption)) {
+    size_t index = 0;
+    QRectF region;
+    if (!engine->locateActiveWindow(frames, &index, &region)) {
+      std::cerr << "CAPTURE_NATIVE_ERROR: no focused window to capture"
+                << std::endl;
+      std::cout << "CAPTURE_FAIL" << std::endl;
+      return 1;
+    }
+
+    // A window across displays is cut to the one under its center.
+    const CapturedFrame &target = frames[index];
+    CaptureController controller(&app);
+    controller.setDisplayIndex(target.index);
+    controller.setBackgroundImage(target.image, target.devicePixelRatio);
+    controller.setDisplayGeometry(target.geometry);
+    return controller.captureRegion(region) ? 0 : 1;
+  }
+
+  if (parser.isSet(activeMonitorOption) || parser.isSet(monitorOption)) {
+    const CapturedFrame *active = nullptr;
+    if (parser.isSet(monitorOption)) {
+      const QString wanted = parser.value(monitorOption);
+      for (const auto &frame : frames) {
+        if (frame.name == wanted ||
+            normalizedOutputName(frame.name) == normalizedOutputName(wanted)) {
+          active = &frame;
+          break;
+        }
+      }
+      if (!active) {
+        // The output was unplugged since it was listed.
+        std::cout << "CAPTURE_FAIL" << std::endl;
+        return 1;
+      }
+    } else {
+      const QPoint cursor = QCursor::pos();
+      active = &frames.front();
+      for (const auto &frame : frames) {
+        if (frame.geometry.contains(cursor)) {
+          active = &frame;
+          break;
+        }
+      }
+    }
+
+    QImage image = active->image;
+    image.setDevicePixelRatio(1.0);
+    const QString timestamp =
+        QDateTime::currentDateTime().toString("yyyyMMdd_hhmmss_zzz");
+    const QString path =
+        QDir::temp().filePath(QString("squigit_monitor_%1.png").arg(timestamp));
+    if (!image.save(path, "PNG", -1)) {
+      std::cout << "CAPTURE_FAIL" << std::endl;
+      return 1;
+    }
+
+    std::cout << "CAPTURE_SUCCESS" << std::endl;
+    std::cout << "DISPLAY_GEO:" << active->geometry.x() << ","
+              << active->geometry.y() << "," << active->geometry.width() << ","
+              << active->geometry.height() << std::endl;
+    std::cout << "DEVICE_PIXEL_RATIO:" << active->devicePixelRatio
+              << std::endl;
+    std::cout << path.toStdString() << std::endl;
+    std::cout.flush();
+    return 0;
+  }
+
+  if (parser.isSet(regionOption)) {
+    QRect region;
+    QRect display;
+    if (!parseRect(parser.value(regionOption), &region) ||
+        !parseRect(parser.value(displayOption), &display)) {
+      std::cerr << "CAPTURE_NATIVE_ERROR: invalid --region or --display"
+                << std::endl;
+      return 1;
+    }
+
+    const CapturedFrame *target = nullptr;
+    for (const auto &frame : frames) {
+      if (frame.geometry == display) {
+        target = &frame;
+        break;
+      }
+    }
+    if (!target) {
+      // The display layout changed since the region was recorded.
+      std::cout << "CAPTURE_FAIL" << std::endl;
+      return 1;
+    }
+
+    CaptureController controller(&app);
+    controller.setDisplayIndex(target->index);
+    controller.setBackgroundImage(target->image, target->devicePixelRatio);
+    controller.setDisplayGeometry(target->geometry);
+    return controller.captureRegion(QRectF(region)) ? 0 : 1;
+  }
+
+  if (parser.isSet(displayNameOption) || parser.isSet(cursorDisplayOption)) {
+    const QString wanted = parser.value(displayNameOption);
+    const QPoint cursor = QCursor::pos();
+    std::vector<CapturedFrame> chosen;
+    for (const auto &frame : frames) {
+      const bool match =
+          parser.isSet(displayNameOption)
+              ? frame.name == wanted ||
+                    normalizedOutputName(frame.name) ==
+                        normalizedOutputName(wanted)
+              : frame.geometry.contains(cursor);
+      if (match) {
+        chosen.push_back(frame);
+        break;
+      }
+    }
+    if (chosen.empty()) {
+      // The preferred display is gone; fall back to all of them.
+      std::cerr << "CAPTURE_NATIVE_WARNING: preferred display not found, "
+                   "showing all displays"
+                << std::endl;
+    } else {
+      frames = chosen;
+    }
+  }
+
   QList<QScreen *> qtScreens = app.screens();
   QQmlApplicationEngine qmlEngine;
   auto *backgroundProvider = new BackgroundImageProvider();
@@ -8,15 +8,29 @@
 //! - Linux/Wayland: Portal may play a sound
 //!
 //! Windows and X11 are silent by default, so no action needed.
+//!
+//! Mixer commands run on worker threads with a hard timeout, so a hung
+//! `pactl` or `osascript` never holds up the capture IPC loop. A sink that
+//! is already muted is left alone.
 
 #[cfg(target_os = "linux")]
 use std::env;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 use std::sync::Mutex;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 use std::sync::OnceLock;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::thread::{self, JoinHandle};
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::time::{Duration, Instant};
+
+/// Longest a mixer command may run before it is killed.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const COMMAND_TIMEOUT: Duration = Duration::from_millis(1500);
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 #[cfg(target_os = "linux")]
 static HAS_WPCTL: OnceLock<bool> = OnceLock::new();
@@ -29,7 +43,11 @@ static AUDIO_STATE: OnceLock<Mutex<AudioState>> = OnceLock::new();
 #[derive(Default)]
 struct AudioState {
     depth: usize,
-    session: Option<SuppressionSession>,
+    /// Worker muting the sink, yielding the session to restore.
+    session: Option<JoinHandle<SuppressionSession>>,
+    /// Worker restoring the last session. The next mute waits for it so
+    /// it does not mistake our own mute for the user's.
+    restoring: Option<JoinHandle<()>>,
 }
 
 #[cfg(any(target_os = "macos", target_os = "linux"))]
@@ -80,6 +98,28 @@ impl AudioGuard {
         }
     }
 
+    /// Undo any outstanding mute and wait for the restore, before the
+    /// process exits and takes the worker threads with it.
+    pub fn shutdown() {
+        #[cfg(any(target_os = "macos", target_os = "linux"))]
+        {
+            {
+                let mut state = Self::state().lock().unwrap_or_else(|e| e.into_inner());
+                state.depth = state.depth.min(1);
+            }
+            Self::unmute_scoped();
+
+            let restoring = Self::state()
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .restoring
+                .take();
+            if let Some(restoring) = restoring {
+                let _ = restoring.join();
+            }
+        }
+    }
+
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     fn state() -> &'static Mutex<AudioState> {
         AUDIO_STATE.get_or_init(|| Mutex::new(AudioState::default()))
@@ -93,14 +133,21 @@ impl AudioGuard {
             return;
         }
 
-        state.session = Some(match Self::detect_backend_and_state() {
-            Ok((backend, already_muted)) => {
-                if already_muted {
-                    SuppressionSession::Managed {
-                        backend,
-                        changed_by_us: false,
-                    }
-                } else if Self::set_muted(backend, true) {
+        let restoring = state.restoring.take();
+        state.session = Some(thread::spawn(move || {
+            if let Some(restoring) = restoring {
+                let _ = restoring.join();
+            }
+            Self::start_session()
+        }));
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    fn start_session() -> SuppressionSession {
+        match Self::detect_backend_and_state() {
+            Ok((_, true)) => SuppressionSession::Disabled,
+            Ok((backend, false)) => {
+                if Self::set_muted(backend, true) {
                     SuppressionSession::Managed {
                         backend,
                         changed_by_us: true,
@@ -116,32 +163,62 @@ impl AudioGuard {
                 eprintln!("[qt-capture] Audio suppression disabled: {}", reason);
                 SuppressionSession::Disabled
             }
-        });
+        }
     }
 
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     fn unmute_scoped() {
-        let session = {
-            let mut state = Self::state().lock().unwrap_or_else(|e| e.into_inner());
-            if state.depth == 0 {
-                return;
-            }
+        let mut state = Self::state().lock().unwrap_or_else(|e| e.into_inner());
+        if state.depth == 0 {
+            return;
+        }
 
-            state.depth -= 1;
-            if state.depth > 0 {
-                return;
-            }
+        state.depth -= 1;
+        if state.depth > 0 {
+            return;
+        }
 
-            state.session.take()
+        let Some(session) = state.session.take() else {
+            return;
         };
+        state.restoring = Some(thread::spawn(move || {
+            if let Ok(SuppressionSession::Managed {
+                backend,
+                changed_by_us: true,
+            }) = session.join()
+            {
+                if !Self::set_muted(backend, false) {
+                    eprintln!("[qt-capture] Failed to restore previous audio mute state");
+                }
+            }
+        }));
+    }
 
-        if let Some(SuppressionSession::Managed {
-            backend,
-            changed_by_us: true,
-        }) = session
-        {
-            if !Self::set_muted(backend, false) {
-                eprintln!("[qt-capture] Failed to restore previous audio mute state");
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    fn succeeds(command: &mut Command) -> bool {
+        Self::output_with_timeout(command).is_some_and(|o| o.status.success())
+    }
+
+    /// Run a mixer command, killing it after [`COMMAND_TIMEOUT`].
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    fn output_with_timeout(command: &mut Command) -> Option<Output> {
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let deadline = Instant::now() + COMMAND_TIMEOUT;
+        loop {
+            match child.try_wait() {
+                Ok(Some(_)) => return child.wait_with_output().ok(),
+                Ok(None) if Instant::now() < deadline => thread::sleep(COMMAND_POLL_INTERVAL),
+                _ => {
+                    eprintln!("[qt-capture] Mixer command timed out: {:?}", command);
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return None;
+                }
             }
         }
     }
@@ -155,10 +232,9 @@ impl AudioGuard {
 
     #[cfg(target_os = "macos")]
     fn query_macos_mute_state() -> Option<bool> {
-        let output = Command::new("osascript")
-            .args(["-e", "output muted of (get volume settings)"])
-            .output()
-            .ok()?;
+        let output = Self::output_with_timeout(
+            Command::new("osascript").args(["-e", "output muted of (get volume settings)"]),
+        )?;
         if !output.status.success() {
             return None;
         }
@@ -182,11 +258,7 @@ impl AudioGuard {
             "set volume without output muted"
         };
 
-        Command::new("osascript")
-            .args(["-e", script])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+        Self::succeeds(Command::new("osascript").args(["-e", script]))
     }
 
     #[cfg(target_os = "linux")]
@@ -216,43 +288,30 @@ impl AudioGuard {
     #[cfg(target_os = "linux")]
     fn set_muted(backend: Backend, muted: bool) -> bool {
         match backend {
-            Backend::Wpctl => Command::new("wpctl")
-                .args([
-                    "set-mute",
-                    "@DEFAULT_AUDIO_SINK@",
-                    if muted { "1" } else { "0" },
-                ])
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false),
-            Backend::Pactl => Command::new("pactl")
-                .args([
-                    "set-sink-mute",
-                    "@DEFAULT_SINK@",
-                    if muted { "1" } else { "0" },
-                ])
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false),
-            Backend::Amixer => Command::new("amixer")
-                .args([
-                    "-q",
-                    "sset",
-                    "Master",
-                    if muted { "mute" } else { "unmute" },
-                ])
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false),
+            Backend::Wpctl => Self::succeeds(Command::new("wpctl").args([
+                "set-mute",
+                "@DEFAULT_AUDIO_SINK@",
+                if muted { "1" } else { "0" },
+            ])),
+            Backend::Pactl => Self::succeeds(Command::new("pactl").args([
+                "set-sink-mute",
+                "@DEFAULT_SINK@",
+                if muted { "1" } else { "0" },
+            ])),
+            Backend::Amixer => Self::succeeds(Command::new("amixer").args([
+                "-q",
+                "sset",
+                "Master",
+                if muted { "mute" } else { "unmute" },
+            ])),
         }
     }
 
     #[cfg(target_os = "linux")]
     fn query_wpctl_mute_state() -> Option<bool> {
-        let output = Command::new("wpctl")
-            .args(["get-volume", "@DEFAULT_AUDIO_SINK@"])
-            .output()
-            .ok()?;
+        let output = Self::output_with_timeout(
+            Command::new("wpctl").args(["get-volume", "@DEFAULT_AUDIO_SINK@"]),
+        )?;
         if !output.status.success() {
             return None;
         }
@@ -269,10 +328,9 @@ impl AudioGuard {
 
     #[cfg(target_os = "linux")]
     fn query_pactl_mute_state() -> Option<bool> {
-        let output = Command::new("pactl")
-            .args(["get-sink-mute", "@DEFAULT_SINK@"])
-            .output()
-            .ok()?;
+        let output = Self::output_with_timeout(
+            Command::new("pactl").args(["get-sink-mute", "@DEFAULT_SINK@"]),
+        )?;
         if !output.status.success() {
             return None;
         }
@@ -294,10 +352,7 @@ impl AudioGuard {
 
     #[cfg(target_os = "linux")]
     fn query_amixer_mute_state() -> Option<bool> {
-        let output = Command::new("amixer")
-            .args(["sget", "Master"])
-            .output()
-            .ok()?;
+        let output = Self::output_with_timeout(Command::new("amixer").args(["sget", "Master"]))?;
         if !output.status.success() {
             return None;
         }
@@ -321,10 +376,21 @@ impl AudioGuard {
 
     #[cfg(target_os = "linux")]
     fn has_cmd(cmd: &str) -> bool {
-        Command::new("which")
-            .arg(cmd)
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+        Self::succeeds(Command::new("which").arg(cmd))
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hung_mixer_commands_are_killed() {
+        let started = Instant::now();
+        assert!(AudioGuard::output_with_timeout(Command::new("sleep").arg("10")).is_none());
+        assert!(started.elapsed() < COMMAND_TIMEOUT + Duration::from_secs(1));
+
+        let output = AudioGuard::output_with_timeout(Command::new("echo").arg("Mute: no"));
+        assert_eq!(output.unwrap().stdout, b"Mute: no\n");
     }
 }
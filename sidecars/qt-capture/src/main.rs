@@ -6,6 +6,7 @@
 mod paths;
 mod qt_app;
 mod audio_guard;
+mod temp_cleanup;
 
 use anyhow::Result;
 use audio_guard::AudioGuard;
@@ -17,7 +18,7 @@ fn main() -> ExitCode {
         Ok(code) => code,
         Err(e) => {
             eprintln!("[qt-capture] Error: {:#}", e);
-            AudioGuard::unmute();
+            AudioGuard::shutdown();
             ExitCode::from(1)
         }
     }
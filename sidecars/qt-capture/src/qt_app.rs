@@ -13,6 +13,7 @@ use ops_chat_storage::{ChatData, ChatMetadata, ChatStorage};
 use ops_profile_store::ProfileStore;
 
 use crate::paths::QtPaths;
+use crate::temp_cleanup::{sweep_stale_temp_files, RetentionPolicy};
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
@@ -23,6 +24,7 @@ const CREATE_NO_WINDOW: u32 = 0x08000000;
 pub struct QtApp {
     args: Vec<String>,
     input_only: bool,
+    retention: RetentionPolicy,
 }
 
 impl QtApp {
@@ -42,13 +44,25 @@ impl QtApp {
         let mut args: Vec<String> = env::args().skip(1).collect();
         let input_only = args.contains(&"--input-only".to_string());
         args.retain(|a| a != "--input-only");
-        Self { args, input_only }
+        let retention = RetentionPolicy::take_from_args(&mut args);
+        Self {
+            args,
+            input_only,
+            retention,
+        }
     }
 
     pub fn run(&mut self) -> Result<ExitCode> {
         let _lock = InstanceLock::try_acquire("qt-capture")
             .context("Failed to acquire instance lock - is another capture running?")?;
 
+        // Under the instance lock no other capture is writing temp files.
+        sweep_stale_temp_files(
+            &env::temp_dir(),
+            self.retention,
+            std::time::SystemTime::now(),
+        );
+
         let mut child = self.spawn_process()?;
         let child_pid = child.id();
 
@@ -60,7 +74,7 @@ impl QtApp {
 
         watcher.stop();
         let _ = child.wait();
-        AudioGuard::unmute();
+        AudioGuard::shutdown();
 
         Ok(exit_code)
     }
@@ -93,6 +107,8 @@ impl QtApp {
             let mut capture_path: Option<String> = None;
             let mut image_hash: Option<String> = None;
             let mut display_geo: Option<String> = None;
+            let mut selection_rect: Option<String> = None;
+            let mut device_pixel_ratio: Option<f64> = None;
 
             for line in reader.lines() {
                 match line {
@@ -102,6 +118,15 @@ impl QtApp {
                             display_geo = Some(geo.to_string());
                             continue;
                         }
+                        if let Some(rect) = trimmed.strip_prefix("SELECTION_RECT:") {
+                            selection_rect = Some(rect.to_string());
+                            continue;
+                        }
+                        if let Some(ratio) = trimmed.strip_prefix("DEVICE_PIXEL_RATIO:") {
+                            device_pixel_ratio =
+                                ratio.parse().ok().filter(|ratio: &f64| *ratio > 0.0);
+                            continue;
+                        }
                         match trimmed {
                             "AUDIO_MUTE" | "REQ_MUTE" => {
                                 AudioGuard::mute();
@@ -143,7 +168,10 @@ impl QtApp {
                                             image_hash = hash;
                                         }
                                     } else {
-                                        let (path, hash) = self.process_capture(trimmed);
+                                        let (path, hash) = self.process_capture(
+                                            trimmed,
+                                            device_pixel_ratio.unwrap_or(1.0),
+                                        );
                                         if let Some(p) = path {
                                             capture_path = Some(p);
                                             image_hash = hash;
@@ -161,6 +189,9 @@ impl QtApp {
             if let Some(res) = capture_path {
                 if self.input_only {
                     println!("CAS_PATH:{}", res);
+                    if let Some(hash) = image_hash {
+                        println!("IMAGE_HASH:{}", hash);
+                    }
                 } else {
                     println!("CHAT_ID:{}", res);
                     if let Some(hash) = image_hash {
@@ -170,6 +201,12 @@ impl QtApp {
                 if let Some(geo) = display_geo {
                     println!("DISPLAY_GEO:{}", geo);
                 }
+                if let Some(rect) = selection_rect {
+                    println!("SELECTION_RECT:{}", rect);
+                }
+                if let Some(ratio) = device_pixel_ratio {
+                    println!("DEVICE_PIXEL_RATIO:{}", ratio);
+                }
                 ExitCode::from(0)
             } else {
                 if !saw_terminal_signal {
@@ -185,7 +222,11 @@ This usually indicates a native crash during startup/capture."
         }
     }
 
-    fn process_capture(&self, path: &str) -> (Option<String>, Option<String>) {
+    fn process_capture(
+        &self,
+        path: &str,
+        device_pixel_ratio: f64,
+    ) -> (Option<String>, Option<String>) {
         ProfileStore::new()
             .ok()
             .and_then(|profile_store| {
@@ -206,8 +247,9 @@ This usually indicates a native crash during startup/capture."
                     .map(|stored| (storage, stored))
             })
             .map(|(storage, stored)| {
-                let metadata =
+                let mut metadata =
                     ChatMetadata::new("New thread".to_string(), stored.hash.clone(), None);
+                metadata.device_pixel_ratio = device_pixel_ratio;
                 let chat = ChatData::new(metadata.clone());
                 let _ = storage.save_chat(&chat);
                 let _ = std::fs::remove_file(path);
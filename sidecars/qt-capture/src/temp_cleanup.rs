@@ -0,0 +1,134 @@
+// Copyright 2026 a7mddra
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retention for the PNGs the native capture binary writes to the temp dir.
+//!
+//! Successful captures are moved into CAS and removed right away, but a
+//! failed or interrupted capture leaves its file behind. On startup the
+//! wrapper sweeps temp files it owns that are older than the retention age.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// File name prefixes written by the native capture binary.
+const TEMP_FILE_PREFIXES: &[&str] = &["squigit_capture_", "squigit_monitor_", "capture_bg_"];
+
+/// Default age after which a leftover temp file is removed.
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+const RETENTION_FLAG: &str = "--temp-retention-secs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// `None` keeps temp files forever.
+    pub max_age: Option<Duration>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age: Some(DEFAULT_MAX_AGE),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Take `--temp-retention-secs <n>` out of `args` (it is not forwarded to
+    /// the native binary). `0` disables the sweep.
+    pub fn take_from_args(args: &mut Vec<String>) -> Self {
+        let Some(index) = args.iter().position(|a| a == RETENTION_FLAG) else {
+            return Self::default();
+        };
+        let value = if index + 1 < args.len() {
+            Some(args.remove(index + 1))
+        } else {
+            None
+        };
+        args.remove(index);
+
+        match value.and_then(|v| v.trim().parse::<u64>().ok()) {
+            Some(0) => Self { max_age: None },
+            Some(secs) => Self {
+                max_age: Some(Duration::from_secs(secs)),
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+fn is_capture_temp_file(name: &str) -> bool {
+    name.ends_with(".png")
+        && TEMP_FILE_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+/// Remove capture temp files in `dir` older than the policy allows.
+/// Returns the number of files removed.
+pub fn sweep_stale_temp_files(dir: &Path, policy: RetentionPolicy, now: SystemTime) -> usize {
+    let Some(max_age) = policy.max_age else {
+        return 0;
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if !name.to_str().is_some_and(is_capture_temp_file) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let stale = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age >= max_age);
+        if stale && fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sweep_stale_temp_files, RetentionPolicy};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn sweep_removes_only_stale_capture_files() {
+        let dir = std::env::temp_dir().join(format!("qt-capture-sweep-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["squigit_capture_1.png", "capture_bg_0.png", "keep.png"] {
+            std::fs::write(dir.join(name), b"png").unwrap();
+        }
+
+        let mut args = vec![
+            "-r".to_string(),
+            "--temp-retention-secs".to_string(),
+            "60".to_string(),
+        ];
+        let policy = RetentionPolicy::take_from_args(&mut args);
+        assert_eq!(args, vec!["-r".to_string()]);
+
+        let now = SystemTime::now();
+        assert_eq!(sweep_stale_temp_files(&dir, policy, now), 0);
+        let later = now + Duration::from_secs(120);
+        assert_eq!(sweep_stale_temp_files(&dir, policy, later), 2);
+        assert!(dir.join("keep.png").exists());
+
+        let disabled = RetentionPolicy { max_age: None };
+        std::fs::write(dir.join("squigit_monitor_1.png"), b"png").unwrap();
+        assert_eq!(sweep_stale_temp_files(&dir, disabled, later), 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -67,6 +67,7 @@ bool AudioCapture::init(int device_index) {
                                &captureCount) == MA_SUCCESS) {
       if (device_index < (int)captureCount) {
         ctx->deviceConfig.capture.pDeviceID = &pCaptureInfos[device_index].id;
+        selected_index = device_index;
         std::cerr << "[audio] Using device " << device_index << ": "
                   << pCaptureInfos[device_index].name << std::endl;
       } else {
@@ -130,6 +131,22 @@ void AudioCapture::terminate() {
   }
 }
 
+DeviceInfo AudioCapture::device_info() const {
+  DeviceInfo info;
+  if (!ctx->device_inited)
+    return info;
+
+  char name[MA_MAX_DEVICE_NAME_LENGTH + 1] = {0};
+  if (ma_device_get_name(&ctx->device, ma_device_type_capture, name,
+                         sizeof(name), nullptr) == MA_SUCCESS) {
+    info.name = name;
+  }
+  info.index = selected_index;
+  info.sample_rate = ctx->device.sampleRate;
+  info.channels = ctx->device.capture.channels;
+  return info;
+}
+
 std::vector<std::string> AudioCapture::list_devices() {
   // Implementation for listing devices if needed
   return {};
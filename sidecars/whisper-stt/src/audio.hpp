@@ -12,6 +12,13 @@ namespace squigit {
 
 using AudioCallback = std::function<void(const std::vector<float> &pcm_data)>;
 
+struct DeviceInfo {
+  std::string name;
+  int index = -1; // -1 when the system default device is used
+  unsigned int sample_rate = 0;
+  unsigned int channels = 0;
+};
+
 class AudioCapture {
 public:
   AudioCapture();
@@ -22,12 +29,16 @@ public:
   bool stop();
   void terminate();
 
+  // Details of the initialized capture device.
+  DeviceInfo device_info() const;
+
   // Helper to list devices if needed later
   static std::vector<std::string> list_devices();
 
 private:
   struct Context;
   Context *ctx = nullptr;
+  int selected_index = -1;
 
   std::atomic<bool> is_running{false};
   std::mutex callback_mutex;
@@ -1,5 +1,8 @@
 #include <atomic>
+#include <chrono>
+#include <cmath>
 #include <iostream>
+#include <mutex>
 #include <string>
 #include <thread>
 #include <vector>
@@ -30,6 +33,18 @@ std::unique_ptr<squigit::InferenceEngine> inference_engine;
 std::thread inference_thread;
 std::atomic<bool> is_processing{false};
 
+// Input level metering: the audio callback accumulates, a meter thread
+// reports the RMS every interval so the UI can draw a VU meter and spot a
+// dead microphone (rms stays at 0).
+constexpr auto kLevelInterval = std::chrono::milliseconds(100);
+std::mutex level_mutex;
+double level_sum_squares = 0.0;
+size_t level_samples = 0;
+std::thread level_thread;
+std::atomic<bool> level_running{false};
+
+std::mutex stdout_mutex;
+
 fs::path get_executable_dir() {
 #ifdef _WIN32
     char path[MAX_PATH];
@@ -77,7 +92,55 @@ std::string resolve_model_path(const std::string& provided_model) {
     return provided_model;
 }
 
-void send_json(const json &j) { std::cout << j.dump() << std::endl; }
+void send_json(const json &j) {
+  std::lock_guard<std::mutex> lock(stdout_mutex);
+  std::cout << j.dump() << std::endl;
+}
+
+void accumulate_level(const std::vector<float> &pcm) {
+  double sum = 0.0;
+  for (float sample : pcm) {
+    sum += static_cast<double>(sample) * sample;
+  }
+  std::lock_guard<std::mutex> lock(level_mutex);
+  level_sum_squares += sum;
+  level_samples += pcm.size();
+}
+
+void run_level_meter() {
+  while (level_running) {
+    std::this_thread::sleep_for(kLevelInterval);
+
+    double sum = 0.0;
+    size_t count = 0;
+    {
+      std::lock_guard<std::mutex> lock(level_mutex);
+      sum = level_sum_squares;
+      count = level_samples;
+      level_sum_squares = 0.0;
+      level_samples = 0;
+    }
+
+    json j;
+    j["type"] = "level";
+    j["rms"] = count > 0 ? std::sqrt(sum / static_cast<double>(count)) : 0.0;
+    send_json(j);
+  }
+}
+
+void send_device_info(const squigit::DeviceInfo &info) {
+  json j;
+  j["type"] = "device_info";
+  j["name"] = info.name;
+  if (info.index >= 0) {
+    j["index"] = info.index;
+  } else {
+    j["index"] = nullptr;
+  }
+  j["sample_rate"] = info.sample_rate;
+  j["channels"] = info.channels;
+  send_json(j);
+}
 
 void on_transcription(const squigit::TranscriptionResult &result) {
   json j;
@@ -121,8 +184,18 @@ void start_engine(const std::string &model_path, const std::string &language,
     send_json(ready);
   }
 
+  if (!audio_capture->init(device_index)) {
+    json j;
+    j["type"] = "error";
+    j["message"] = "Failed to open audio device";
+    send_json(j);
+    return;
+  }
+  send_device_info(audio_capture->device_info());
+
   // Start Audio
   bool started = audio_capture->start([&](const std::vector<float> &pcm) {
+    accumulate_level(pcm);
     if (inference_engine) {
       inference_engine->add_audio(pcm);
     }
@@ -138,6 +211,9 @@ void start_engine(const std::string &model_path, const std::string &language,
 
   is_processing = true;
 
+  level_running = true;
+  level_thread = std::thread(run_level_meter);
+
   // Start Inference Loop
   inference_thread =
       std::thread([&]() { inference_engine->run(on_transcription); });
@@ -152,6 +228,11 @@ void stop_engine() {
   if (!is_processing)
     return;
 
+  level_running = false;
+  if (level_thread.joinable()) {
+    level_thread.join();
+  }
+
   if (audio_capture)
     audio_capture->stop();
   if (inference_engine)
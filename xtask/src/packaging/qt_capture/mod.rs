@@ -86,6 +86,12 @@ pub fn capture() -> Result<()> {
     println!("  Copying binary to {}", dst_binary_path.display());
     fs::copy(&src_binary_path, &dst_binary_path)?;
 
+    // Only the capture sidecar is bundled under `binaries/`; the OCR and
+    // Whisper sidecars ship separately and are not covered.
+    let manifest_path =
+        ops_sidecar_integrity::SidecarManifest::generate(&app_binaries)?.write(&app_binaries)?;
+    println!("  Wrote sidecar checksums to {}", manifest_path.display());
+
     let debug_binaries = project_root().join("target").join("debug").join("binaries");
     fs::create_dir_all(&debug_binaries)?;
 